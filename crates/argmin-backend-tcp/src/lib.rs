@@ -0,0 +1,480 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! This crate evaluates cost functions in worker processes over TCP.
+//!
+//! [`TcpBackend`] implements argmin's [`EvaluationBackend`]: It connects to a number of workers
+//! and distributes batches of parameter vectors among them. Each worker evaluates one parameter
+//! vector at a time; as soon as it returns a result, it is sent the next pending parameter
+//! vector. The results are returned in the order of the parameter vectors. Wrapped in
+//! [`Distributed`](`argmin::core::Distributed`), the backend can be used with any solver, but
+//! population-based solvers such as
+//! [`ParticleSwarm`](`argmin::solver::particleswarm::ParticleSwarm`) benefit most, since they
+//! evaluate entire populations at once.
+//!
+//! Workers are served by [`TcpWorker`], which wraps a problem implementing
+//! [`CostFunction`].
+//!
+//! # Protocol
+//!
+//! Requests and responses are JSON objects, one per line. A request contains an `id` and the
+//! parameter vector, and is answered by a response with the same `id` and either the output of
+//! the cost function or an error message:
+//!
+//! ```text
+//! {"id":0,"param":[1.0,2.0]}
+//! {"id":0,"result":{"Ok":100.0}}
+//! {"id":1,"param":[-1.0,2.0]}
+//! {"id":1,"result":{"Err":"parameter out of range"}}
+//! ```
+//!
+//! This makes it easy to implement workers in other languages. Note that JSON cannot represent
+//! infinite values and `NaN`.
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-backend-tcp = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use argmin::core::{CostFunction, Distributed, Error, Executor, State};
+//! use argmin::solver::particleswarm::ParticleSwarm;
+//! use argmin_backend_tcp::{TcpBackend, TcpWorker};
+//! use argmin_testfunctions::rosenbrock;
+//! use std::net::TcpListener;
+//!
+//! struct Rosenbrock {}
+//!
+//! impl CostFunction for Rosenbrock {
+//!     type Param = Vec<f64>;
+//!     type Output = f64;
+//!
+//!     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+//!         Ok(rosenbrock(p))
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), Error> {
+//! // Usually, the workers run in separate processes on other machines.
+//! let mut addresses = vec![];
+//! for _ in 0..4 {
+//!     let listener = TcpListener::bind("127.0.0.1:0")?;
+//!     addresses.push(listener.local_addr()?);
+//!     std::thread::spawn(move || TcpWorker::new(Rosenbrock {}).serve(listener));
+//! }
+//!
+//! let backend: TcpBackend<Vec<f64>, f64> = TcpBackend::connect(&addresses)?;
+//! let solver = ParticleSwarm::new((vec![-2.0, -2.0], vec![2.0, 2.0]), 40);
+//! let res = Executor::new(Distributed::new(backend), solver)
+//!     .configure(|state| state.max_iters(10))
+//!     .run()?;
+//! # assert!(res.state().get_best_cost() < 1.0);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+use argmin::core::{ArgminError, Error};
+pub use argmin::core::{CostFunction, EvaluationBackend};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::marker::PhantomData;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+/// Request sent to a worker
+#[derive(Serialize, Deserialize)]
+struct Request<P> {
+    /// Identifies the request
+    id: u64,
+    /// Parameter vector
+    param: P,
+}
+
+/// Response of a worker
+#[derive(Serialize, Deserialize)]
+struct Response<O> {
+    /// Identifier of the corresponding request
+    id: u64,
+    /// Output of the cost function or error message
+    result: Result<O, String>,
+}
+
+/// Creates an `ArgminError::ConditionViolated` for the worker at `addr`
+fn worker_error(addr: &SocketAddr, msg: impl std::fmt::Display) -> Error {
+    ArgminError::ConditionViolated {
+        text: format!("`TcpBackend`: worker {addr}: {msg}"),
+    }
+    .into()
+}
+
+/// Connection to a single worker
+struct Connection {
+    /// Address of the worker
+    addr: SocketAddr,
+    /// Reading half of the stream
+    reader: BufReader<TcpStream>,
+    /// Writing half of the stream
+    writer: TcpStream,
+    /// Identifier of the next request
+    next_id: u64,
+    /// Reusable buffer for responses
+    line: String,
+}
+
+impl Connection {
+    /// Connects to the worker at `addr`
+    fn connect(addr: SocketAddr) -> Result<Self, Error> {
+        let writer = TcpStream::connect(addr).map_err(|e| worker_error(&addr, e))?;
+        writer.set_nodelay(true)?;
+        Ok(Connection {
+            addr,
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+            next_id: 0,
+            line: String::new(),
+        })
+    }
+
+    /// Sends `param` to the worker and waits for the response.
+    ///
+    /// The outer error indicates that the connection is broken, the inner one that the worker
+    /// failed to evaluate the cost function.
+    fn request<P: Serialize, O: DeserializeOwned>(
+        &mut self,
+        param: &P,
+    ) -> Result<Result<O, Error>, Error> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut request = serde_json::to_vec(&Request { id, param })?;
+        request.push(b'\n');
+        self.writer
+            .write_all(&request)
+            .map_err(|e| worker_error(&self.addr, e))?;
+        self.line.clear();
+        let num_bytes = self
+            .reader
+            .read_line(&mut self.line)
+            .map_err(|e| worker_error(&self.addr, e))?;
+        if num_bytes == 0 {
+            return Err(worker_error(&self.addr, "connection closed"));
+        }
+        let response: Response<O> = serde_json::from_str(&self.line)
+            .map_err(|e| worker_error(&self.addr, format!("invalid response: {e}")))?;
+        if response.id != id {
+            return Err(worker_error(
+                &self.addr,
+                format!("expected response to request {id}, got {}", response.id),
+            ));
+        }
+        Ok(response
+            .result
+            .map_err(|msg| worker_error(&self.addr, format!("evaluation failed: {msg}"))))
+    }
+}
+
+/// Evaluation backend which distributes evaluations among workers over TCP.
+///
+/// `P` is the type of the parameter vector and `O` the type of the output of the cost function.
+/// See the [crate documentation](`crate`) for details.
+pub struct TcpBackend<P, O> {
+    /// Connections to the workers
+    connections: Vec<Mutex<Connection>>,
+    /// Types of the parameter vector and the output
+    _types: PhantomData<fn(&P) -> O>,
+}
+
+impl<P, O> TcpBackend<P, O> {
+    /// Connects to the workers listening on `addrs`.
+    ///
+    /// Fails if `addrs` is empty or if any of the workers cannot be reached.
+    pub fn connect<A: ToSocketAddrs>(addrs: &[A]) -> Result<Self, Error> {
+        let mut connections = vec![];
+        for addr in addrs {
+            for addr in addr.to_socket_addrs()? {
+                connections.push(Mutex::new(Connection::connect(addr)?));
+            }
+        }
+        if connections.is_empty() {
+            return Err(ArgminError::InvalidParameter {
+                text: "`TcpBackend`: at least one worker address is required.".to_string(),
+            }
+            .into());
+        }
+        Ok(TcpBackend {
+            connections,
+            _types: PhantomData,
+        })
+    }
+
+    /// Set a timeout for sending a parameter vector to a worker and for receiving its result.
+    ///
+    /// Evaluations which take longer fail. By default, there is no timeout.
+    pub fn with_timeout(self, timeout: Duration) -> Result<Self, Error> {
+        for connection in self.connections.iter() {
+            let connection = connection.lock().unwrap_or_else(PoisonError::into_inner);
+            connection.writer.set_read_timeout(Some(timeout))?;
+            connection.writer.set_write_timeout(Some(timeout))?;
+        }
+        Ok(self)
+    }
+
+    /// Returns the number of workers
+    pub fn num_workers(&self) -> usize {
+        self.connections.len()
+    }
+}
+
+impl<P, O> EvaluationBackend for TcpBackend<P, O>
+where
+    P: Serialize + Sync,
+    O: DeserializeOwned + Send,
+{
+    type Param = P;
+    type Output = O;
+
+    /// Evaluates `params` on all workers concurrently and returns the results in order.
+    ///
+    /// If an evaluation fails, the remaining evaluations are still carried out, but the first
+    /// error (in order of the parameter vectors) is returned.
+    fn evaluate(&self, params: &[&P]) -> Result<Vec<O>, Error> {
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<(usize, Result<O, Error>)> = thread::scope(|s| {
+            let handles: Vec<_> = self
+                .connections
+                .iter()
+                .map(|connection| {
+                    let next = &next;
+                    s.spawn(move || {
+                        let mut connection =
+                            connection.lock().unwrap_or_else(PoisonError::into_inner);
+                        let mut results = vec![];
+                        loop {
+                            let idx = next.fetch_add(1, Ordering::Relaxed);
+                            if idx >= params.len() {
+                                break;
+                            }
+                            match connection.request(params[idx]) {
+                                Ok(result) => results.push((idx, result)),
+                                Err(e) => {
+                                    // A broken connection cannot process further requests
+                                    results.push((idx, Err(e)));
+                                    break;
+                                }
+                            }
+                        }
+                        results
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap_or_default())
+                .collect()
+        });
+        if results.len() < params.len() {
+            return Err(ArgminError::ConditionViolated {
+                text: format!(
+                    "`TcpBackend`: only {} of {} parameter vectors were evaluated.",
+                    results.len(),
+                    params.len()
+                ),
+            }
+            .into());
+        }
+        results.sort_by_key(|(idx, _)| *idx);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+}
+
+/// Serves evaluations of a cost function to [`TcpBackend`]s.
+///
+/// See the [crate documentation](`crate`) for an example.
+pub struct TcpWorker<O> {
+    /// Problem which is evaluated
+    problem: O,
+}
+
+impl<O> TcpWorker<O>
+where
+    O: CostFunction,
+    O::Param: DeserializeOwned,
+    O::Output: Serialize,
+{
+    /// Construct a new instance of `TcpWorker` which evaluates `problem`
+    pub fn new(problem: O) -> Self {
+        TcpWorker { problem }
+    }
+
+    /// Answers requests on `stream` until the backend closes the connection.
+    ///
+    /// Errors of the cost function are sent to the backend. Fails if the stream cannot be read or
+    /// written or if a request is malformed.
+    pub fn handle_connection(&self, stream: TcpStream) -> Result<(), Error> {
+        stream.set_nodelay(true)?;
+        let mut writer = stream.try_clone()?;
+        for line in BufReader::new(stream).lines() {
+            let request: Request<O::Param> = serde_json::from_str(&line?)?;
+            let response = Response {
+                id: request.id,
+                result: self.problem.cost(&request.param).map_err(|e| e.to_string()),
+            };
+            let mut response = serde_json::to_vec(&response)?;
+            response.push(b'\n');
+            writer.write_all(&response)?;
+        }
+        Ok(())
+    }
+
+    /// Accepts connections on `listener` and serves each of them in its own thread.
+    ///
+    /// Runs until accepting a connection fails. Failing connections are closed without affecting
+    /// the others.
+    pub fn serve(&self, listener: TcpListener) -> Result<(), Error>
+    where
+        O: Sync,
+    {
+        thread::scope(|s| {
+            for stream in listener.incoming() {
+                let stream = stream?;
+                s.spawn(move || self.handle_connection(stream));
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns a reference to the wrapped problem
+    pub fn inner(&self) -> &O {
+        &self.problem
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::{Distributed, Executor, State};
+    use argmin::solver::particleswarm::ParticleSwarm;
+
+    /// Sum of the parameter vector, fails for negative sums
+    struct Sum {}
+
+    impl CostFunction for Sum {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let sum: f64 = p.iter().sum();
+            if sum < 0.0 {
+                return Err(ArgminError::InvalidParameter {
+                    text: "negative sum".to_string(),
+                }
+                .into());
+            }
+            Ok(sum)
+        }
+    }
+
+    /// Starts `num` workers and returns their addresses
+    fn start_workers(num: usize) -> Vec<SocketAddr> {
+        (0..num)
+            .map(|_| {
+                let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+                let addr = listener.local_addr().unwrap();
+                thread::spawn(move || TcpWorker::new(Sum {}).serve(listener));
+                addr
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_connect() {
+        let addrs: [SocketAddr; 0] = [];
+        assert!(TcpBackend::<Vec<f64>, f64>::connect(&addrs).is_err());
+        let backend: TcpBackend<Vec<f64>, f64> = TcpBackend::connect(&start_workers(3)).unwrap();
+        assert_eq!(backend.num_workers(), 3);
+    }
+
+    #[test]
+    fn test_evaluate_in_order() {
+        let backend: TcpBackend<Vec<f64>, f64> = TcpBackend::connect(&start_workers(3))
+            .unwrap()
+            .with_timeout(Duration::from_secs(10))
+            .unwrap();
+        let params: Vec<Vec<f64>> = (0..50).map(|i| vec![f64::from(i), 0.5]).collect();
+        let refs: Vec<&Vec<f64>> = params.iter().collect();
+        let costs = backend.evaluate(&refs).unwrap();
+        for (i, c) in costs.iter().enumerate() {
+            assert_eq!(c.to_ne_bytes(), (i as f64 + 0.5).to_ne_bytes());
+        }
+        // Connections are reused
+        assert_eq!(backend.evaluate(&refs[..2]).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_evaluation_error() {
+        let backend: TcpBackend<Vec<f64>, f64> = TcpBackend::connect(&start_workers(2)).unwrap();
+        let params = [vec![1.0], vec![-1.0], vec![2.0]];
+        let refs: Vec<&Vec<f64>> = params.iter().collect();
+        let err = backend.evaluate(&refs).unwrap_err().to_string();
+        assert!(err.contains("evaluation failed"));
+        assert!(err.contains("negative sum"));
+        // The connections remain usable
+        assert_eq!(backend.evaluate(&[&vec![3.0]]).unwrap(), vec![3.0]);
+    }
+
+    #[test]
+    fn test_closed_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            // Accept a single connection and close it after reading the request
+            let (stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(stream).read_line(&mut line).unwrap();
+        });
+        let backend: TcpBackend<Vec<f64>, f64> = TcpBackend::connect(&[addr]).unwrap();
+        let err = backend.evaluate(&[&vec![1.0]]).unwrap_err().to_string();
+        assert!(err.contains("connection closed"));
+    }
+
+    #[test]
+    fn test_particleswarm() {
+        let backend: TcpBackend<Vec<f64>, f64> = TcpBackend::connect(&start_workers(4)).unwrap();
+        let solver = ParticleSwarm::new((vec![0.0, 0.0], vec![1.0, 1.0]), 20);
+        let res = Executor::new(Distributed::new(backend), solver)
+            .configure(|state| state.max_iters(5).counting(true))
+            .run()
+            .unwrap();
+        assert_eq!(res.state().get_func_counts()["cost_count"], 20 * 6);
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * Copyright 2018-2024 argmin developers
+ *
+ * Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+
+/*
+ * C API for argmin.
+ *
+ * Functions returning `int` return 0 on success and -1 on failure. In case of a failure, a
+ * description of the error can be obtained via `argmin_solver_last_error`.
+ */
+
+#ifndef ARGMIN_H
+#define ARGMIN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Opaque solver handle */
+typedef struct ArgminSolver ArgminSolver;
+
+/*
+ * Cost function callback: writes the cost of the `n` parameters at `param` to `cost`.
+ * Returning anything other than 0 aborts the optimization.
+ */
+typedef int (*ArgminCostFn)(const double *param, size_t n, double *cost, void *user_data);
+
+/*
+ * Gradient callback: writes the `n` elements of the gradient at `param` to `gradient`.
+ * Returning anything other than 0 aborts the optimization.
+ */
+typedef int (*ArgminGradientFn)(const double *param, size_t n, double *gradient, void *user_data);
+
+/*
+ * Creates a solver by name (`steepest_descent`, `lbfgs`, `bfgs`, `nelder_mead` or
+ * `particle_swarm`). Returns NULL for unknown names.
+ */
+ArgminSolver *argmin_solver_new(const char *name);
+
+/* Frees a solver. */
+void argmin_solver_free(ArgminSolver *solver);
+
+/* Sets an option (for instance `max_iters`) from key/value strings. */
+int argmin_solver_set_option(ArgminSolver *solver, const char *key, const char *value);
+
+/* Sets the cost function and the user data pointer which is passed to all callbacks. */
+int argmin_solver_set_cost(ArgminSolver *solver, ArgminCostFn cost, void *user_data);
+
+/* Sets the gradient. Without a gradient, it is approximated via finite differences. */
+int argmin_solver_set_gradient(ArgminSolver *solver, ArgminGradientFn gradient);
+
+/* Sets (copies) the initial parameter vector. */
+int argmin_solver_set_initial_param(ArgminSolver *solver, const double *param, size_t n);
+
+/* Sets (copies) lower and upper bounds, required by `particle_swarm`. */
+int argmin_solver_set_bounds(ArgminSolver *solver, const double *lower, const double *upper,
+                             size_t n);
+
+/* Runs the solver. */
+int argmin_solver_run(ArgminSolver *solver);
+
+/* Returns the message of the last error or NULL if the last operation succeeded. */
+const char *argmin_solver_last_error(const ArgminSolver *solver);
+
+/* Returns the best cost function value or NaN if the solver has not run successfully. */
+double argmin_solver_best_cost(const ArgminSolver *solver);
+
+/* Copies the best parameter vector into `out`, which must hold exactly `n` elements. */
+int argmin_solver_best_param(ArgminSolver *solver, double *out, size_t n);
+
+/* Returns the number of iterations of the last run. */
+uint64_t argmin_solver_iterations(const ArgminSolver *solver);
+
+/* Returns how often a function (for instance `cost_count` or `gradient_count`) was evaluated. */
+uint64_t argmin_solver_func_count(const ArgminSolver *solver, const char *name);
+
+/* Returns why the last run terminated or NULL if the solver has not run successfully. */
+const char *argmin_solver_termination_status(const ArgminSolver *solver);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ARGMIN_H */
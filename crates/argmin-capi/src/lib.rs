@@ -0,0 +1,954 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! C API for argmin.
+//!
+//! This crate exports a C ABI which allows using a selection of the solvers of argmin from C,
+//! C++, Fortran, Julia and any other language which can call C functions. It is built as a shared
+//! and a static library. The corresponding header is located in `include/argmin.h`.
+//!
+//! A solver is created by name via [`argmin_solver_new`], configured via key/value strings
+//! ([`argmin_solver_set_option`]) and provided with a cost function, optionally a gradient and an
+//! initial parameter vector. Both the cost function and the gradient are function pointers which
+//! receive an opaque user data pointer. After [`argmin_solver_run`], the results can be queried
+//! from the solver handle, which finally needs to be freed with [`argmin_solver_free`].
+//!
+//! Functions returning `int` return `0` on success and `-1` on failure. In case of a failure, a
+//! description of the error can be obtained via [`argmin_solver_last_error`].
+//!
+//! # Solvers and options
+//!
+//! All solvers operate on vectors of `double`s and accept the options `max_iters` (maximum number
+//! of iterations, default `100`), `target_cost` (stop once the cost is below this value) and
+//! `seed` (seed for the random number generator).
+//!
+//! | Name               | Solver                                 | Additional options                |
+//! |--------------------|----------------------------------------|-----------------------------------|
+//! | `steepest_descent` | Steepest descent (More-Thuente)        |                                   |
+//! | `lbfgs`            | L-BFGS (More-Thuente)                  | `m`, `tol_grad`, `tol_cost`       |
+//! | `bfgs`             | BFGS (More-Thuente)                    | `tol_grad`, `tol_cost`            |
+//! | `nelder_mead`      | Nelder-Mead                            | `sd_tolerance`, `simplex_step`    |
+//! | `particle_swarm`   | Particle Swarm Optimization            | `num_particles`                   |
+//!
+//! If no gradient is provided, gradient-based solvers approximate it via central finite
+//! differences. Nelder-Mead constructs its initial simplex from the initial parameter vector by
+//! moving each coordinate by `simplex_step` (default `0.1`). Particle Swarm Optimization requires
+//! bounds ([`argmin_solver_set_bounds`]) instead of an initial parameter vector.
+//!
+//! # Example
+//!
+//! ```c
+//! #include <stdio.h>
+//! #include "argmin.h"
+//!
+//! int cost(const double *x, size_t n, double *out, void *data) {
+//!     out[0] = (1.0 - x[0]) * (1.0 - x[0]) + 100.0 * (x[1] - x[0] * x[0]) * (x[1] - x[0] * x[0]);
+//!     return 0;
+//! }
+//!
+//! int main(void) {
+//!     double init[2] = {-1.2, 1.0};
+//!     double best[2];
+//!     ArgminSolver *solver = argmin_solver_new("lbfgs");
+//!     argmin_solver_set_option(solver, "max_iters", "100");
+//!     argmin_solver_set_cost(solver, cost, NULL);
+//!     argmin_solver_set_initial_param(solver, init, 2);
+//!     if (argmin_solver_run(solver) != 0) {
+//!         printf("%s\n", argmin_solver_last_error(solver));
+//!     }
+//!     argmin_solver_best_param(solver, best, 2);
+//!     argmin_solver_free(solver);
+//! }
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted
+//! for inclusion in the work by you, as defined in the Apache-2.0 license, shall be
+//! dual licensed as above, without any additional terms or conditions.
+
+use std::collections::HashMap;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use argmin::core::{
+    CostFunction, Error, Executor, Gradient, IterState, PopulationState, Solver, State,
+};
+use argmin::solver::gradientdescent::SteepestDescent;
+use argmin::solver::linesearch::MoreThuenteLineSearch;
+use argmin::solver::neldermead::NelderMead;
+use argmin::solver::particleswarm::{Particle, ParticleSwarm};
+use argmin::solver::quasinewton::{BFGS, LBFGS};
+
+/// Cost function callback
+///
+/// Writes the cost of the `n` parameters at `param` to `cost` and returns `0` on success. Any
+/// other return value aborts the optimization.
+pub type ArgminCostFn = Option<
+    unsafe extern "C" fn(
+        param: *const f64,
+        n: usize,
+        cost: *mut f64,
+        user_data: *mut c_void,
+    ) -> c_int,
+>;
+
+/// Gradient callback
+///
+/// Writes the `n` elements of the gradient at `param` to `gradient` and returns `0` on success.
+/// Any other return value aborts the optimization.
+pub type ArgminGradientFn = Option<
+    unsafe extern "C" fn(
+        param: *const f64,
+        n: usize,
+        gradient: *mut f64,
+        user_data: *mut c_void,
+    ) -> c_int,
+>;
+
+const OK: c_int = 0;
+const FAILURE: c_int = -1;
+
+/// Solvers available via the C API
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SolverKind {
+    SteepestDescent,
+    Lbfgs,
+    Bfgs,
+    NelderMead,
+    ParticleSwarm,
+}
+
+impl SolverKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "steepest_descent" => Some(SolverKind::SteepestDescent),
+            "lbfgs" => Some(SolverKind::Lbfgs),
+            "bfgs" => Some(SolverKind::Bfgs),
+            "nelder_mead" => Some(SolverKind::NelderMead),
+            "particle_swarm" => Some(SolverKind::ParticleSwarm),
+            _ => None,
+        }
+    }
+
+    /// Options accepted in addition to `max_iters`, `target_cost` and `seed`
+    fn options(&self) -> &'static [&'static str] {
+        match self {
+            SolverKind::SteepestDescent => &[],
+            SolverKind::Lbfgs => &["m", "tol_grad", "tol_cost"],
+            SolverKind::Bfgs => &["tol_grad", "tol_cost"],
+            SolverKind::NelderMead => &["sd_tolerance", "simplex_step"],
+            SolverKind::ParticleSwarm => &["num_particles"],
+        }
+    }
+}
+
+/// Problem defined by C function pointers
+struct CProblem {
+    cost: ArgminCostFn,
+    gradient: ArgminGradientFn,
+    user_data: *mut c_void,
+}
+
+impl CostFunction for CProblem {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let cost_fn = self
+            .cost
+            .ok_or_else(|| Error::msg("no cost function provided"))?;
+        let mut cost = f64::NAN;
+        // SAFETY: The caller of `argmin_solver_set_cost` guarantees that the function pointer
+        // can be called with `n` parameters and a pointer to a single `double`.
+        let status = unsafe { cost_fn(param.as_ptr(), param.len(), &mut cost, self.user_data) };
+        if status != OK {
+            return Err(Error::msg(format!(
+                "cost function returned error code {status}"
+            )));
+        }
+        Ok(cost)
+    }
+}
+
+impl Gradient for CProblem {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let Some(gradient_fn) = self.gradient else {
+            return finitediff::vec::central_diff(&|p| self.cost(p))(param);
+        };
+        let mut gradient = vec![f64::NAN; param.len()];
+        // SAFETY: The caller of `argmin_solver_set_gradient` guarantees that the function
+        // pointer can be called with `n` parameters and a buffer of `n` `double`s.
+        let status = unsafe {
+            gradient_fn(
+                param.as_ptr(),
+                param.len(),
+                gradient.as_mut_ptr(),
+                self.user_data,
+            )
+        };
+        if status != OK {
+            return Err(Error::msg(format!("gradient returned error code {status}")));
+        }
+        Ok(gradient)
+    }
+}
+
+/// Options set via key/value strings
+#[derive(Clone, Debug, Default)]
+struct Options {
+    max_iters: Option<u64>,
+    target_cost: Option<f64>,
+    seed: Option<u64>,
+    m: Option<usize>,
+    tol_grad: Option<f64>,
+    tol_cost: Option<f64>,
+    sd_tolerance: Option<f64>,
+    simplex_step: Option<f64>,
+    num_particles: Option<usize>,
+}
+
+impl Options {
+    fn set(&mut self, solver: SolverKind, key: &str, value: &str) -> Result<(), Error> {
+        fn parse<T: std::str::FromStr>(key: &str, value: &str) -> Result<Option<T>, Error> {
+            value
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|_| Error::msg(format!("invalid value `{value}` for option `{key}`")))
+        }
+
+        if !["max_iters", "target_cost", "seed"].contains(&key) && !solver.options().contains(&key)
+        {
+            return Err(Error::msg(format!(
+                "unknown option `{key}` for solver {solver:?}"
+            )));
+        }
+        match key {
+            "max_iters" => self.max_iters = parse(key, value)?,
+            "target_cost" => self.target_cost = parse(key, value)?,
+            "seed" => self.seed = parse(key, value)?,
+            "m" => self.m = parse(key, value)?,
+            "tol_grad" => self.tol_grad = parse(key, value)?,
+            "tol_cost" => self.tol_cost = parse(key, value)?,
+            "sd_tolerance" => self.sd_tolerance = parse(key, value)?,
+            "simplex_step" => self.simplex_step = parse(key, value)?,
+            "num_particles" => self.num_particles = parse(key, value)?,
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+}
+
+/// Results of a run
+struct RunResult {
+    best_param: Vec<f64>,
+    best_cost: f64,
+    iterations: u64,
+    termination_status: CString,
+    func_counts: HashMap<String, u64>,
+}
+
+/// Opaque solver handle
+pub struct ArgminSolver {
+    kind: SolverKind,
+    options: Options,
+    cost: ArgminCostFn,
+    gradient: ArgminGradientFn,
+    user_data: *mut c_void,
+    init_param: Option<Vec<f64>>,
+    bounds: Option<(Vec<f64>, Vec<f64>)>,
+    result: Option<RunResult>,
+    last_error: Option<CString>,
+}
+
+impl ArgminSolver {
+    /// Stores the outcome of an operation as the last error and converts it to a status code.
+    fn status(&mut self, result: Result<(), Error>) -> c_int {
+        match result {
+            Ok(()) => {
+                self.last_error = None;
+                OK
+            }
+            Err(err) => {
+                let msg = format!("{err:#}").replace('\0', " ");
+                self.last_error = Some(CString::new(msg).unwrap());
+                FAILURE
+            }
+        }
+    }
+
+    fn run(&mut self) -> Result<(), Error> {
+        let problem = CProblem {
+            cost: self.cost,
+            gradient: self.gradient,
+            user_data: self.user_data,
+        };
+        if problem.cost.is_none() {
+            return Err(Error::msg("no cost function provided"));
+        }
+        let opts = self.options.clone();
+        let max_iters = opts.max_iters.unwrap_or(100);
+        if self.kind == SolverKind::ParticleSwarm {
+            let bounds = self
+                .bounds
+                .clone()
+                .ok_or_else(|| Error::msg("particle_swarm requires bounds"))?;
+            let solver = ParticleSwarm::new(bounds, opts.num_particles.unwrap_or(40));
+            self.result = Some(execute(
+                problem,
+                solver,
+                &opts,
+                |state: PopulationState<_, _>| state.max_iters(max_iters),
+            )?);
+            return Ok(());
+        }
+        let init_param = self
+            .init_param
+            .clone()
+            .ok_or_else(|| Error::msg("no initial parameter vector provided"))?;
+        let result = match self.kind {
+            SolverKind::SteepestDescent => {
+                let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+                execute(
+                    problem,
+                    solver,
+                    &opts,
+                    |state: IterState<_, _, _, _, _, _>| {
+                        state.param(init_param).max_iters(max_iters)
+                    },
+                )?
+            }
+            SolverKind::Lbfgs => {
+                let mut solver = LBFGS::new(MoreThuenteLineSearch::new(), opts.m.unwrap_or(7));
+                if let Some(tol_grad) = opts.tol_grad {
+                    solver = solver.with_tolerance_grad(tol_grad)?;
+                }
+                if let Some(tol_cost) = opts.tol_cost {
+                    solver = solver.with_tolerance_cost(tol_cost)?;
+                }
+                execute(
+                    problem,
+                    solver,
+                    &opts,
+                    |state: IterState<_, _, _, _, _, _>| {
+                        state.param(init_param).max_iters(max_iters)
+                    },
+                )?
+            }
+            SolverKind::Bfgs => {
+                let n = init_param.len();
+                let inv_hessian: Vec<Vec<f64>> = (0..n)
+                    .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+                    .collect();
+                let mut solver = BFGS::new(MoreThuenteLineSearch::new());
+                if let Some(tol_grad) = opts.tol_grad {
+                    solver = solver.with_tolerance_grad(tol_grad)?;
+                }
+                if let Some(tol_cost) = opts.tol_cost {
+                    solver = solver.with_tolerance_cost(tol_cost)?;
+                }
+                execute(
+                    problem,
+                    solver,
+                    &opts,
+                    |state: IterState<_, _, _, _, _, _>| {
+                        state
+                            .param(init_param)
+                            .inv_hessian(inv_hessian)
+                            .max_iters(max_iters)
+                    },
+                )?
+            }
+            SolverKind::NelderMead => {
+                let step = opts.simplex_step.unwrap_or(0.1);
+                let mut simplex = vec![init_param.clone()];
+                for i in 0..init_param.len() {
+                    let mut vertex = init_param.clone();
+                    vertex[i] += step;
+                    simplex.push(vertex);
+                }
+                let mut solver = NelderMead::new(simplex);
+                if let Some(sd_tolerance) = opts.sd_tolerance {
+                    solver = solver.with_sd_tolerance(sd_tolerance)?;
+                }
+                execute(
+                    problem,
+                    solver,
+                    &opts,
+                    |state: IterState<_, (), (), (), (), _>| state.max_iters(max_iters),
+                )?
+            }
+            SolverKind::ParticleSwarm => unreachable!(),
+        };
+        self.result = Some(result);
+        Ok(())
+    }
+}
+
+/// Gives access to the best parameter vector of the states of the exposed solvers.
+trait BestParam: State<Float = f64> {
+    fn best_param_vec(&self) -> Option<&Vec<f64>>;
+}
+
+impl<G, J, H, R> BestParam for IterState<Vec<f64>, G, J, H, R, f64>
+where
+    Self: State<Param = Vec<f64>, Float = f64>,
+{
+    fn best_param_vec(&self) -> Option<&Vec<f64>> {
+        self.get_best_param()
+    }
+}
+
+impl BestParam for PopulationState<Particle<Vec<f64>, f64>, f64> {
+    fn best_param_vec(&self) -> Option<&Vec<f64>> {
+        self.get_best_param().map(|particle| &particle.position)
+    }
+}
+
+fn execute<S, I>(
+    problem: CProblem,
+    solver: S,
+    opts: &Options,
+    configure: impl FnOnce(I) -> I,
+) -> Result<RunResult, Error>
+where
+    S: Solver<CProblem, I>,
+    I: BestParam,
+{
+    let mut executor = Executor::new(problem, solver).configure(configure);
+    if let Some(target_cost) = opts.target_cost {
+        executor = executor.target_cost(target_cost);
+    }
+    if let Some(seed) = opts.seed {
+        executor = executor.deterministic(seed);
+    }
+    let result = executor.run()?;
+    let state = result.state();
+    Ok(RunResult {
+        best_param: state.best_param_vec().cloned().unwrap_or_default(),
+        best_cost: state.get_best_cost(),
+        iterations: state.get_iter(),
+        termination_status: CString::new(state.get_termination_status().to_string())
+            .unwrap_or_default(),
+        func_counts: result
+            .func_counts()
+            .iter()
+            .map(|(k, &v)| (k.to_string(), v))
+            .collect(),
+    })
+}
+
+/// Converts a C string into a `&str`.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a nul-terminated string.
+unsafe fn to_str<'a>(ptr: *const c_char) -> Result<&'a str, Error> {
+    if ptr.is_null() {
+        return Err(Error::msg("string must not be NULL"));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| Error::msg("string is not valid UTF-8"))
+}
+
+/// Copies `n` values from `ptr` into a `Vec`.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to at least `n` `double`s.
+unsafe fn to_vec(ptr: *const f64, n: usize) -> Result<Vec<f64>, Error> {
+    if ptr.is_null() {
+        return Err(Error::msg("parameter vector must not be NULL"));
+    }
+    if n == 0 {
+        return Err(Error::msg("parameter vector must not be empty"));
+    }
+    Ok(std::slice::from_raw_parts(ptr, n).to_vec())
+}
+
+/// Creates a new solver by name.
+///
+/// Returns NULL if no solver with this name exists. The returned handle must be freed with
+/// [`argmin_solver_free`].
+///
+/// # Safety
+///
+/// `name` must be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_new(name: *const c_char) -> *mut ArgminSolver {
+    let Some(kind) = to_str(name).ok().and_then(SolverKind::from_name) else {
+        return std::ptr::null_mut();
+    };
+    Box::into_raw(Box::new(ArgminSolver {
+        kind,
+        options: Options::default(),
+        cost: None,
+        gradient: None,
+        user_data: std::ptr::null_mut(),
+        init_param: None,
+        bounds: None,
+        result: None,
+        last_error: None,
+    }))
+}
+
+/// Frees a solver created with [`argmin_solver_new`].
+///
+/// # Safety
+///
+/// `solver` must be NULL or a handle returned by [`argmin_solver_new`] which was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_free(solver: *mut ArgminSolver) {
+    if !solver.is_null() {
+        drop(Box::from_raw(solver));
+    }
+}
+
+/// Sets an option of the solver from key/value strings.
+///
+/// Fails if the solver does not know the option or if the value cannot be parsed.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle and `key` and `value` must be nul-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_set_option(
+    solver: *mut ArgminSolver,
+    key: *const c_char,
+    value: *const c_char,
+) -> c_int {
+    let Some(solver) = solver.as_mut() else {
+        return FAILURE;
+    };
+    let result = to_str(key)
+        .and_then(|key| Ok((key, to_str(value)?)))
+        .and_then(|(key, value)| solver.options.set(solver.kind, key, value));
+    solver.status(result)
+}
+
+/// Sets the cost function and the user data pointer which is passed to all callbacks.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle. `cost` must be safe to call with the documented arguments and
+/// `user_data` must remain valid for all calls of the callbacks.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_set_cost(
+    solver: *mut ArgminSolver,
+    cost: ArgminCostFn,
+    user_data: *mut c_void,
+) -> c_int {
+    let Some(solver) = solver.as_mut() else {
+        return FAILURE;
+    };
+    solver.cost = cost;
+    solver.user_data = user_data;
+    solver.status(if cost.is_some() {
+        Ok(())
+    } else {
+        Err(Error::msg("cost function must not be NULL"))
+    })
+}
+
+/// Sets the gradient.
+///
+/// If no gradient is set (or it is set to NULL), it is approximated via finite differences.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle and `gradient` must be safe to call with the documented
+/// arguments.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_set_gradient(
+    solver: *mut ArgminSolver,
+    gradient: ArgminGradientFn,
+) -> c_int {
+    let Some(solver) = solver.as_mut() else {
+        return FAILURE;
+    };
+    solver.gradient = gradient;
+    solver.status(Ok(()))
+}
+
+/// Sets the initial parameter vector of `n` elements, which are copied.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle and `param` must point to at least `n` `double`s.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_set_initial_param(
+    solver: *mut ArgminSolver,
+    param: *const f64,
+    n: usize,
+) -> c_int {
+    let Some(solver) = solver.as_mut() else {
+        return FAILURE;
+    };
+    let result = to_vec(param, n).map(|param| solver.init_param = Some(param));
+    solver.status(result)
+}
+
+/// Sets the lower and upper bounds of `n` elements each, which are copied.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle and `lower` and `upper` must point to at least `n` `double`s
+/// each.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_set_bounds(
+    solver: *mut ArgminSolver,
+    lower: *const f64,
+    upper: *const f64,
+    n: usize,
+) -> c_int {
+    let Some(solver) = solver.as_mut() else {
+        return FAILURE;
+    };
+    let result = to_vec(lower, n)
+        .and_then(|lower| Ok((lower, to_vec(upper, n)?)))
+        .map(|bounds| solver.bounds = Some(bounds));
+    solver.status(result)
+}
+
+/// Runs the solver.
+///
+/// Results of previous runs are discarded.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_run(solver: *mut ArgminSolver) -> c_int {
+    let Some(solver) = solver.as_mut() else {
+        return FAILURE;
+    };
+    solver.result = None;
+    let result = catch_unwind(AssertUnwindSafe(|| solver.run()))
+        .unwrap_or_else(|_| Err(Error::msg("solver panicked")));
+    solver.status(result)
+}
+
+/// Returns the message of the last error or NULL if the last operation succeeded.
+///
+/// The string is owned by the solver and valid until the next call of a function with this handle.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_last_error(solver: *const ArgminSolver) -> *const c_char {
+    solver
+        .as_ref()
+        .and_then(|solver| solver.last_error.as_ref())
+        .map_or(std::ptr::null(), |err| err.as_ptr())
+}
+
+/// Returns the best cost function value found or NaN if the solver has not run successfully.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_best_cost(solver: *const ArgminSolver) -> f64 {
+    solver
+        .as_ref()
+        .and_then(|solver| solver.result.as_ref())
+        .map_or(f64::NAN, |result| result.best_cost)
+}
+
+/// Copies the best parameter vector into `out`, which must hold `n` elements.
+///
+/// Fails if the solver has not run successfully or if `n` does not match the number of
+/// parameters.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle and `out` must point to at least `n` `double`s.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_best_param(
+    solver: *mut ArgminSolver,
+    out: *mut f64,
+    n: usize,
+) -> c_int {
+    let Some(solver) = solver.as_mut() else {
+        return FAILURE;
+    };
+    let result = match solver.result.as_ref() {
+        None => Err(Error::msg("solver has not run successfully")),
+        Some(_) if out.is_null() => Err(Error::msg("output buffer must not be NULL")),
+        Some(result) if result.best_param.len() != n => Err(Error::msg(format!(
+            "output buffer has {n} elements, expected {}",
+            result.best_param.len()
+        ))),
+        Some(result) => {
+            std::slice::from_raw_parts_mut(out, n).copy_from_slice(&result.best_param);
+            Ok(())
+        }
+    };
+    solver.status(result)
+}
+
+/// Returns the number of iterations of the last run.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_iterations(solver: *const ArgminSolver) -> u64 {
+    solver
+        .as_ref()
+        .and_then(|solver| solver.result.as_ref())
+        .map_or(0, |result| result.iterations)
+}
+
+/// Returns how often the given function was evaluated in the last run.
+///
+/// `name` is for instance `cost_count` or `gradient_count`.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle and `name` must be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_func_count(
+    solver: *const ArgminSolver,
+    name: *const c_char,
+) -> u64 {
+    let Ok(name) = to_str(name) else {
+        return 0;
+    };
+    solver
+        .as_ref()
+        .and_then(|solver| solver.result.as_ref())
+        .and_then(|result| result.func_counts.get(name).copied())
+        .unwrap_or(0)
+}
+
+/// Returns a description of why the last run terminated or NULL if the solver has not run
+/// successfully.
+///
+/// The string is owned by the solver and valid until it is run again or freed.
+///
+/// # Safety
+///
+/// `solver` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn argmin_solver_termination_status(
+    solver: *const ArgminSolver,
+) -> *const c_char {
+    solver
+        .as_ref()
+        .and_then(|solver| solver.result.as_ref())
+        .map_or(std::ptr::null(), |result| {
+            result.termination_status.as_ptr()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    unsafe extern "C" fn quadratic(
+        param: *const f64,
+        n: usize,
+        cost: *mut f64,
+        user_data: *mut c_void,
+    ) -> c_int {
+        let param = std::slice::from_raw_parts(param, n);
+        *cost = param
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (i + 1) as f64 * x * x)
+            .sum();
+        if !user_data.is_null() {
+            *(user_data as *mut u64) += 1;
+        }
+        0
+    }
+
+    unsafe extern "C" fn quadratic_gradient(
+        param: *const f64,
+        n: usize,
+        gradient: *mut f64,
+        _user_data: *mut c_void,
+    ) -> c_int {
+        let param = std::slice::from_raw_parts(param, n);
+        let gradient = std::slice::from_raw_parts_mut(gradient, n);
+        for (i, (g, x)) in gradient.iter_mut().zip(param).enumerate() {
+            *g = 2.0 * (i + 1) as f64 * x;
+        }
+        0
+    }
+
+    unsafe extern "C" fn failing(
+        _param: *const f64,
+        _n: usize,
+        _cost: *mut f64,
+        _user_data: *mut c_void,
+    ) -> c_int {
+        3
+    }
+
+    fn new(name: &str) -> *mut ArgminSolver {
+        let name = CString::new(name).unwrap();
+        unsafe { argmin_solver_new(name.as_ptr()) }
+    }
+
+    fn set_option(solver: *mut ArgminSolver, key: &str, value: &str) -> c_int {
+        let key = CString::new(key).unwrap();
+        let value = CString::new(value).unwrap();
+        unsafe { argmin_solver_set_option(solver, key.as_ptr(), value.as_ptr()) }
+    }
+
+    fn last_error(solver: *const ArgminSolver) -> String {
+        unsafe { CStr::from_ptr(argmin_solver_last_error(solver)) }
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_unknown_solver() {
+        assert!(new("foo").is_null());
+    }
+
+    #[test]
+    fn test_options() {
+        let solver = new("lbfgs");
+        assert_eq!(set_option(solver, "max_iters", "20"), 0);
+        assert_eq!(set_option(solver, "m", "5"), 0);
+        assert_eq!(set_option(solver, "tol_grad", "1e-8"), 0);
+        assert_eq!(set_option(solver, "m", "five"), -1);
+        assert_eq!(last_error(solver), "invalid value `five` for option `m`");
+        assert_eq!(set_option(solver, "sd_tolerance", "1e-8"), -1);
+        assert_eq!(
+            last_error(solver),
+            "unknown option `sd_tolerance` for solver Lbfgs"
+        );
+        unsafe { argmin_solver_free(solver) };
+    }
+
+    #[test]
+    fn test_solvers() {
+        for (name, gradient, max_iters) in [
+            ("steepest_descent", true, "20"),
+            ("lbfgs", true, "100"),
+            ("lbfgs", false, "100"),
+            ("bfgs", true, "100"),
+            ("nelder_mead", false, "500"),
+        ] {
+            let solver = new(name);
+            let mut count = 0u64;
+            let init = [1.0, -2.0, 0.5];
+            let mut best = [f64::NAN; 3];
+            unsafe {
+                argmin_solver_set_cost(
+                    solver,
+                    Some(quadratic),
+                    &mut count as *mut u64 as *mut c_void,
+                );
+                if gradient {
+                    argmin_solver_set_gradient(solver, Some(quadratic_gradient));
+                }
+                argmin_solver_set_initial_param(solver, init.as_ptr(), 3);
+            }
+            assert_eq!(set_option(solver, "max_iters", max_iters), 0);
+            assert_eq!(
+                unsafe { argmin_solver_run(solver) },
+                0,
+                "{name}: {}",
+                last_error(solver)
+            );
+            assert_eq!(
+                unsafe { argmin_solver_best_param(solver, best.as_mut_ptr(), 3) },
+                0
+            );
+            for x in best {
+                assert_relative_eq!(x, 0.0, epsilon = 1e-3);
+            }
+            assert!(unsafe { argmin_solver_best_cost(solver) } < 1e-6);
+            assert!(unsafe { argmin_solver_iterations(solver) } > 0);
+            let cost_count = CString::new("cost_count").unwrap();
+            let cost_count = unsafe { argmin_solver_func_count(solver, cost_count.as_ptr()) };
+            // Evaluations for finite differences are not counted as calls of the cost function
+            if gradient || name == "nelder_mead" {
+                assert_eq!(cost_count, count);
+            } else {
+                assert!(cost_count > 0 && cost_count < count);
+            }
+            assert!(!unsafe { argmin_solver_termination_status(solver) }.is_null());
+            unsafe { argmin_solver_free(solver) };
+        }
+    }
+
+    #[test]
+    fn test_particle_swarm() {
+        let solver = new("particle_swarm");
+        let lower = [-5.0, -5.0];
+        let upper = [5.0, 5.0];
+        unsafe {
+            argmin_solver_set_cost(solver, Some(quadratic), std::ptr::null_mut());
+        }
+        assert_eq!(unsafe { argmin_solver_run(solver) }, -1);
+        assert_eq!(last_error(solver), "particle_swarm requires bounds");
+        unsafe { argmin_solver_set_bounds(solver, lower.as_ptr(), upper.as_ptr(), 2) };
+        assert_eq!(set_option(solver, "seed", "42"), 0);
+        assert_eq!(set_option(solver, "num_particles", "20"), 0);
+        assert_eq!(unsafe { argmin_solver_run(solver) }, 0);
+        assert!(unsafe { argmin_solver_best_cost(solver) } < 1e-3);
+        assert_eq!(unsafe { argmin_solver_iterations(solver) }, 100);
+        unsafe { argmin_solver_free(solver) };
+    }
+
+    #[test]
+    fn test_errors() {
+        let solver = new("lbfgs");
+        assert_eq!(unsafe { argmin_solver_run(solver) }, -1);
+        assert_eq!(last_error(solver), "no cost function provided");
+        let init = [1.0];
+        let mut best = [0.0; 2];
+        unsafe {
+            argmin_solver_set_cost(solver, Some(failing), std::ptr::null_mut());
+            argmin_solver_set_initial_param(solver, init.as_ptr(), 1);
+        }
+        assert_eq!(unsafe { argmin_solver_run(solver) }, -1);
+        assert_eq!(last_error(solver), "cost function returned error code 3");
+        assert!(unsafe { argmin_solver_best_cost(solver) }.is_nan());
+        assert!(unsafe { argmin_solver_termination_status(solver) }.is_null());
+
+        unsafe { argmin_solver_set_cost(solver, Some(quadratic), std::ptr::null_mut()) };
+        assert_eq!(unsafe { argmin_solver_run(solver) }, 0);
+        assert!(unsafe { argmin_solver_last_error(solver) }.is_null());
+        assert_eq!(
+            unsafe { argmin_solver_best_param(solver, best.as_mut_ptr(), 2) },
+            -1
+        );
+        assert_eq!(
+            last_error(solver),
+            "output buffer has 2 elements, expected 1"
+        );
+        unsafe { argmin_solver_free(solver) };
+        assert_eq!(unsafe { argmin_solver_run(std::ptr::null_mut()) }, -1);
+    }
+}
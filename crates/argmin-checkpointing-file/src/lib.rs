@@ -11,6 +11,21 @@
 //! For details on the usage please see the documentation of [`FileCheckpoint`] or have a look at
 //! the [example](https://github.com/argmin-rs/argmin/tree/main/examples/checkpoint).
 //!
+//! By default, checkpoints are stored in a compact binary format using
+//! [bincode](https://crates.io/crates/bincode). Other formats can be chosen via
+//! [`FileCheckpoint::with_format`] (see [`CheckpointFormat`]):
+//!
+//! * `json`: Human readable JSON, which allows one to inspect and edit checkpoints. Since plain
+//!   JSON cannot represent infinite values and `NaN` (which are common in states, for instance as
+//!   initial best cost), the [JSON5](https://json5.org/) extensions `Infinity` and `NaN` are used
+//!   for those.
+//! * `cbor`: [CBOR](https://cbor.io/), a self-describing binary format
+//!
+//! Checkpoints are first written to a temporary file which then atomically replaces the previous
+//! checkpoint, therefore a crash while writing does not destroy the last checkpoint. Optionally,
+//! older checkpoints can be kept (see [`FileCheckpoint::with_keep`]). Binary checkpoints contain a
+//! CRC32 checksum which is verified when loading, such that corrupted checkpoints are detected.
+//!
 //! # Usage
 //!
 //! Add the following line to your dependencies list:
@@ -39,13 +54,99 @@
 //! without any additional terms or conditions.
 
 pub use argmin::core::checkpointing::{Checkpoint, CheckpointingFrequency};
-use argmin::core::Error;
+use argmin::core::{ArgminError, Error};
 use serde::{de::DeserializeOwned, Serialize};
-use std::fs::File;
-use std::io::{BufReader, BufWriter};
-use std::path::PathBuf;
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Marks the beginning of a checkpoint file with checksum.
+const MAGIC: &[u8; 8] = b"ARGMINCP";
+
+/// Serialization format of checkpoints.
+///
+/// Apart from `Bincode`, all formats require enabling the feature of the same name. Since
+/// features are additive, matches on this enum must include a wildcard arm.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash, Default)]
+#[non_exhaustive]
+pub enum CheckpointFormat {
+    /// Compact binary format using `bincode` (default, file extension `arg`)
+    #[default]
+    Bincode,
+    /// Human readable JSON format using `json5` (file extension `json`)
+    #[cfg(feature = "json")]
+    Json,
+    /// Self-describing binary CBOR format using `ciborium` (file extension `cbor`)
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl CheckpointFormat {
+    /// Returns the file extension used for this format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_checkpointing_file::CheckpointFormat;
+    ///
+    /// assert_eq!(CheckpointFormat::Bincode.extension(), "arg");
+    /// ```
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CheckpointFormat::Bincode => "arg",
+            #[cfg(feature = "json")]
+            CheckpointFormat::Json => "json",
+            #[cfg(feature = "cbor")]
+            CheckpointFormat::Cbor => "cbor",
+        }
+    }
+
+    /// Serializes `value` into `writer`.
+    fn serialize_into<T: Serialize, W: Write>(&self, writer: W, value: &T) -> Result<(), Error> {
+        match self {
+            CheckpointFormat::Bincode => bincode::serialize_into(writer, value)?,
+            #[cfg(feature = "json")]
+            CheckpointFormat::Json => {
+                let mut writer = writer;
+                writer.write_all(json5::to_string(value)?.as_bytes())?
+            }
+            #[cfg(feature = "cbor")]
+            CheckpointFormat::Cbor => ciborium::into_writer(value, writer)?,
+        }
+        Ok(())
+    }
+
+    /// Indicates whether checkpoints of this format are written with a checksum.
+    ///
+    /// Human readable formats are written without checksum such that they can be edited by hand.
+    fn checksum(&self) -> bool {
+        match self {
+            CheckpointFormat::Bincode => true,
+            #[cfg(feature = "json")]
+            CheckpointFormat::Json => false,
+            #[cfg(feature = "cbor")]
+            CheckpointFormat::Cbor => true,
+        }
+    }
+
+    /// Deserializes a value from `reader`.
+    fn deserialize_from<T: DeserializeOwned, R: Read>(&self, reader: R) -> Result<T, Error> {
+        Ok(match self {
+            CheckpointFormat::Bincode => bincode::deserialize_from(reader)?,
+            #[cfg(feature = "json")]
+            CheckpointFormat::Json => {
+                let mut reader = reader;
+                let mut content = String::new();
+                reader.read_to_string(&mut content)?;
+                json5::from_str(&content)?
+            }
+            #[cfg(feature = "cbor")]
+            CheckpointFormat::Cbor => ciborium::from_reader(reader)?,
+        })
+    }
+}
 
-/// Handles saving a checkpoint to disk as a binary file.
+/// Handles saving a checkpoint to disk as a file.
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
 pub struct FileCheckpoint {
     /// Indicates how often a checkpoint is created
@@ -54,6 +155,10 @@ pub struct FileCheckpoint {
     pub directory: PathBuf,
     /// Name of the checkpoint files
     pub filename: PathBuf,
+    /// Serialization format
+    pub format: CheckpointFormat,
+    /// Number of checkpoints to keep (including the most recent one)
+    pub keep: usize,
 }
 
 impl Default for FileCheckpoint {
@@ -65,6 +170,7 @@ impl Default for FileCheckpoint {
     ///
     /// ```
     /// use argmin_checkpointing_file::FileCheckpoint;
+    /// # use argmin_checkpointing_file::CheckpointFormat;
     /// # use argmin::core::checkpointing::CheckpointingFrequency;
     /// # use std::path::PathBuf;
     ///
@@ -72,12 +178,16 @@ impl Default for FileCheckpoint {
     /// # assert_eq!(checkpoint.frequency, CheckpointingFrequency::default());
     /// # assert_eq!(checkpoint.directory, PathBuf::from(".checkpoints"));
     /// # assert_eq!(checkpoint.filename, PathBuf::from("checkpoint.arg"));
+    /// # assert_eq!(checkpoint.format, CheckpointFormat::Bincode);
+    /// # assert_eq!(checkpoint.keep, 1);
     /// ```
     fn default() -> FileCheckpoint {
         FileCheckpoint {
             frequency: CheckpointingFrequency::default(),
             directory: PathBuf::from(".checkpoints"),
             filename: PathBuf::from("checkpoint.arg"),
+            format: CheckpointFormat::default(),
+            keep: 1,
         }
     }
 }
@@ -106,10 +216,96 @@ impl FileCheckpoint {
             frequency,
             directory: PathBuf::from(directory.as_ref()),
             filename: PathBuf::from(format!("{}.arg", name.as_ref())),
+            format: CheckpointFormat::default(),
+            keep: 1,
+        }
+    }
+
+    /// Sets the serialization format (default: [`CheckpointFormat::Bincode`]).
+    ///
+    /// The extension of the checkpoint file is changed according to the format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_checkpointing_file::{CheckpointFormat, FileCheckpoint, CheckpointingFrequency};
+    /// # use std::path::PathBuf;
+    ///
+    /// let checkpoint = FileCheckpoint::new("checkpoints", "optimization", CheckpointingFrequency::Always)
+    ///     .with_format(CheckpointFormat::Bincode);
+    /// # assert_eq!(checkpoint.format, CheckpointFormat::Bincode);
+    /// # assert_eq!(checkpoint.filename, PathBuf::from("optimization.arg"));
+    /// ```
+    #[must_use]
+    pub fn with_format(mut self, format: CheckpointFormat) -> Self {
+        self.format = format;
+        self.filename.set_extension(format.extension());
+        self
+    }
+
+    /// Sets the number of checkpoints to keep, including the most recent one (default: 1).
+    ///
+    /// Older checkpoints are renamed by appending `.1`, `.2`, ... to the file name, where `.1` is
+    /// the second most recent one. Only the most recent checkpoint is loaded automatically; an
+    /// older one can be restored by renaming it. A value of 0 is treated as 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_checkpointing_file::{FileCheckpoint, CheckpointingFrequency};
+    ///
+    /// // Keeps `optimization.arg`, `optimization.arg.1` and `optimization.arg.2`
+    /// let checkpoint = FileCheckpoint::new("checkpoints", "optimization", CheckpointingFrequency::Always)
+    ///     .with_keep(3);
+    /// # assert_eq!(checkpoint.keep, 3);
+    /// ```
+    #[must_use]
+    pub fn with_keep(mut self, keep: usize) -> Self {
+        self.keep = keep.max(1);
+        self
+    }
+
+    /// Returns the path of the most recent checkpoint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_checkpointing_file::{FileCheckpoint, CheckpointingFrequency};
+    /// # use std::path::PathBuf;
+    ///
+    /// let checkpoint = FileCheckpoint::new("checkpoints", "optimization", CheckpointingFrequency::Always);
+    /// assert_eq!(checkpoint.path(), PathBuf::from("checkpoints/optimization.arg"));
+    /// ```
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(&self.filename)
+    }
+
+    /// Shifts the existing checkpoints by one and copies the most recent one to `<file>.1`.
+    fn rotate(&self, path: &Path) -> Result<(), Error> {
+        if self.keep < 2 || !path.exists() {
+            return Ok(());
         }
+        for i in (1..self.keep - 1).rev() {
+            let older = with_suffix(path, &i.to_string());
+            if older.exists() {
+                std::fs::rename(&older, with_suffix(path, &(i + 1).to_string()))?;
+            }
+        }
+        // Copying (instead of renaming) ensures that a valid checkpoint exists at `path` at all
+        // times.
+        std::fs::copy(path, with_suffix(path, "1"))?;
+        Ok(())
     }
 }
 
+/// Appends `.<suffix>` to the file name of `path`.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
 impl<S, I> Checkpoint<S, I> for FileCheckpoint
 where
     S: Serialize + DeserializeOwned,
@@ -117,8 +313,9 @@ where
 {
     /// Writes checkpoint to disk.
     ///
-    /// If the directory does not exist already, it will be created. It uses `bincode` to serialize
-    /// the data.
+    /// If the directory does not exist already, it will be created. The data is serialized in the
+    /// configured [`CheckpointFormat`] and written to a temporary file, which then atomically
+    /// replaces the previous checkpoint. Binary formats are prefixed with a checksum.
     /// It will return an error if creating the directory or file or serialization failed.
     ///
     /// # Example
@@ -126,16 +323,12 @@ where
     /// ```
     /// use argmin_checkpointing_file::{FileCheckpoint, CheckpointingFrequency, Checkpoint};
     ///
-    /// # use std::fs::File;
-    /// # use std::io::BufReader;
     /// # let checkpoint = FileCheckpoint::new(".checkpoints", "save_test" , CheckpointingFrequency::Always);
     /// # let solver: u64 = 12;
     /// # let state: u64 = 21;
     /// # let _ = std::fs::remove_file(".checkpoints/save_test.arg");
     /// checkpoint.save(&solver, &state);
-    /// # let (f_solver, f_state): (u64, u64) = bincode::deserialize_from(
-    /// #     BufReader::new(File::open(".checkpoints/save_test.arg").unwrap())
-    /// # ).unwrap();
+    /// # let (f_solver, f_state): (u64, u64) = checkpoint.load().unwrap().unwrap();
     /// # assert_eq!(solver, f_solver);
     /// # assert_eq!(state, f_state);
     /// # let _ = std::fs::remove_file(".checkpoints/save_test.arg");
@@ -144,9 +337,26 @@ where
         if !self.directory.exists() {
             std::fs::create_dir_all(&self.directory)?
         }
-        let fname = self.directory.join(&self.filename);
-        let f = BufWriter::new(File::create(fname)?);
-        bincode::serialize_into(f, &(solver, state))?;
+        let path = self.path();
+        let mut payload = vec![];
+        self.format.serialize_into(&mut payload, &(solver, state))?;
+
+        let tmp = with_suffix(&path, "tmp");
+        let mut f = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp)?;
+        if self.format.checksum() {
+            f.write_all(MAGIC)?;
+            f.write_all(&crc32fast::hash(&payload).to_le_bytes())?;
+        }
+        f.write_all(&payload)?;
+        f.sync_all()?;
+        drop(f);
+
+        self.rotate(&path)?;
+        std::fs::rename(&tmp, &path)?;
         Ok(())
     }
 
@@ -154,7 +364,9 @@ where
     ///
     ///
     /// If there is no checkpoint on disk, it will return `Ok(None)`.
-    /// Returns an error if opening the file or deserialization failed.
+    /// Returns an error if opening the file or deserialization failed or if the checksum does not
+    /// match or the header containing it is missing (i.e. the checkpoint is corrupted). Bincode
+    /// checkpoints without header, as written by earlier versions, are accepted as well.
     ///
     /// # Example
     ///
@@ -187,13 +399,48 @@ where
     /// # }
     /// ```
     fn load(&self) -> Result<Option<(S, I)>, Error> {
-        let path = &self.directory.join(&self.filename);
+        let path = self.path();
         if !path.exists() {
             return Ok(None);
         }
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        Ok(Some(bincode::deserialize_from(reader)?))
+        let content = std::fs::read(&path)?;
+        let payload = match content.strip_prefix(MAGIC.as_slice()) {
+            Some(rest) if self.format.checksum() => {
+                if rest.len() < 4 {
+                    return Err(ArgminError::CheckpointFailed {
+                        text: format!(
+                            "Checkpoint {} is corrupted: file is truncated.",
+                            path.display()
+                        ),
+                    }
+                    .into());
+                }
+                let (checksum, payload) = rest.split_at(4);
+                if crc32fast::hash(payload).to_le_bytes() != checksum {
+                    return Err(ArgminError::CheckpointFailed {
+                        text: format!(
+                            "Checkpoint {} is corrupted: checksum does not match.",
+                            path.display()
+                        ),
+                    }
+                    .into());
+                }
+                payload
+            }
+            None if !self.format.checksum() => &content[..],
+            // Legacy checkpoints, written before checksums were introduced
+            None if self.format == CheckpointFormat::Bincode => &content[..],
+            _ => {
+                return Err(ArgminError::CheckpointFailed {
+                    text: format!(
+                        "Checkpoint {} is corrupted: invalid header.",
+                        path.display()
+                    ),
+                }
+                .into());
+            }
+        };
+        Ok(Some(self.format.deserialize_from(payload)?))
     }
 
     /// Returns the how often a checkpoint is to be saved.
@@ -202,6 +449,40 @@ where
     fn frequency(&self) -> CheckpointingFrequency {
         self.frequency
     }
+
+    /// Checks whether the frequency is valid and whether the directory is writable.
+    ///
+    /// The directory is created if it does not exist already and a temporary file is written to
+    /// and removed from it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_checkpointing_file::{FileCheckpoint, CheckpointingFrequency, Checkpoint};
+    ///
+    /// let checkpoint = FileCheckpoint::new(".checkpoints", "validate_test", CheckpointingFrequency::Always);
+    /// # let checkpoint: &dyn Checkpoint<u64, u64> = &checkpoint;
+    /// assert!(checkpoint.validate().is_ok());
+    /// ```
+    fn validate(&self) -> Result<(), Error> {
+        self.frequency.validate()?;
+        std::fs::create_dir_all(&self.directory).map_err(|e| ArgminError::CheckpointFailed {
+            text: format!(
+                "Checkpoint directory {} cannot be created: {e}",
+                self.directory.display()
+            ),
+        })?;
+        let probe = with_suffix(&self.path(), "probe");
+        std::fs::write(&probe, b"")
+            .and_then(|()| std::fs::remove_file(&probe))
+            .map_err(|e| ArgminError::CheckpointFailed {
+                text: format!(
+                    "Checkpoint directory {} is not writable: {e}",
+                    self.directory.display()
+                ),
+            })?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -222,4 +503,206 @@ mod tests {
         let _loaded: Option<(TestSolver, IterState<Vec<f64>, (), (), (), (), f64>)> =
             check.load().unwrap();
     }
+
+    #[test]
+    fn test_rotation() {
+        let check = FileCheckpoint::new("checkpoints", "rotation", CheckpointingFrequency::Always)
+            .with_keep(3);
+        for i in 0..5u64 {
+            check.save(&i, &i).unwrap();
+        }
+        assert!(!PathBuf::from("checkpoints/rotation.arg.tmp").exists());
+        assert!(!PathBuf::from("checkpoints/rotation.arg.3").exists());
+        let loaded: Option<(u64, u64)> = check.load().unwrap();
+        assert_eq!(loaded, Some((4, 4)));
+        for (i, expected) in [(1, 3u64), (2, 2u64)] {
+            let older = FileCheckpoint {
+                filename: PathBuf::from(format!("rotation.arg.{i}")),
+                ..check.clone()
+            };
+            let loaded: Option<(u64, u64)> = older.load().unwrap();
+            assert_eq!(loaded, Some((expected, expected)));
+        }
+        for name in ["rotation.arg", "rotation.arg.1", "rotation.arg.2"] {
+            let _ = std::fs::remove_file(PathBuf::from("checkpoints").join(name));
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let check = FileCheckpoint::new("checkpoints", "validate", CheckpointingFrequency::Always);
+        assert!(Checkpoint::<u64, u64>::validate(&check).is_ok());
+        assert!(!PathBuf::from("checkpoints/validate.arg.probe").exists());
+
+        let check =
+            FileCheckpoint::new("checkpoints", "validate", CheckpointingFrequency::Every(0));
+        assert!(Checkpoint::<u64, u64>::validate(&check).is_err());
+
+        // The directory cannot be created because a file of the same name exists
+        std::fs::create_dir_all("checkpoints").unwrap();
+        std::fs::write("checkpoints/not_a_directory", b"").unwrap();
+        let check = FileCheckpoint::new(
+            "checkpoints/not_a_directory",
+            "validate",
+            CheckpointingFrequency::Always,
+        );
+        assert!(Checkpoint::<u64, u64>::validate(&check).is_err());
+        let _ = std::fs::remove_file("checkpoints/not_a_directory");
+    }
+
+    #[test]
+    fn test_corrupted() {
+        let check = FileCheckpoint::new("checkpoints", "corrupted", CheckpointingFrequency::Always);
+        check.save(&1u64, &vec![1.0f64, 2.0]).unwrap();
+
+        let mut content = std::fs::read(check.path()).unwrap();
+        let last = content.len() - 1;
+        content[last] ^= 0xff;
+        std::fs::write(check.path(), &content).unwrap();
+
+        let loaded: Result<Option<(u64, Vec<f64>)>, _> = check.load();
+        let err = loaded.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ArgminError>(),
+            Some(ArgminError::CheckpointFailed { .. })
+        ));
+        assert!(err.to_string().contains("checksum does not match"));
+        let _ = std::fs::remove_file(check.path());
+    }
+
+    #[test]
+    fn test_load_without_checksum() {
+        let check = FileCheckpoint::new("checkpoints", "legacy", CheckpointingFrequency::Always);
+        std::fs::create_dir_all("checkpoints").unwrap();
+        std::fs::write(check.path(), bincode::serialize(&(1u64, 2u64)).unwrap()).unwrap();
+        let loaded: Option<(u64, u64)> = check.load().unwrap();
+        assert_eq!(loaded, Some((1, 2)));
+        let _ = std::fs::remove_file(check.path());
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_cbor_without_checksum() {
+        let check =
+            FileCheckpoint::new("checkpoints", "headerless", CheckpointingFrequency::Always)
+                .with_format(CheckpointFormat::Cbor);
+        check.save(&1u64, &2u64).unwrap();
+
+        // Corrupt the header
+        let mut content = std::fs::read(check.path()).unwrap();
+        content[0] ^= 0xff;
+        std::fs::write(check.path(), &content).unwrap();
+
+        let loaded: Result<Option<(u64, u64)>, _> = check.load();
+        let err = loaded.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ArgminError>(),
+            Some(ArgminError::CheckpointFailed { .. })
+        ));
+        assert!(err.to_string().contains("invalid header"));
+        let _ = std::fs::remove_file(check.path());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    #[allow(clippy::type_complexity)]
+    fn test_save_load_json() {
+        let solver = TestSolver::new();
+        let state: IterState<Vec<f64>, (), (), (), (), f64> =
+            IterState::new().param(vec![1.0f64, 0.0]);
+        let check =
+            FileCheckpoint::new("checkpoints", "solver_json", CheckpointingFrequency::Always)
+                .with_format(CheckpointFormat::Json);
+        assert_eq!(check.filename, PathBuf::from("solver_json.json"));
+        check.save_cond(&solver, &state, 20).unwrap();
+
+        let content = std::fs::read_to_string("checkpoints/solver_json.json").unwrap();
+        assert!(content.contains("\"param\""));
+
+        let loaded: Option<(TestSolver, IterState<Vec<f64>, (), (), (), (), f64>)> =
+            check.load().unwrap();
+        let (_, loaded_state) = loaded.unwrap();
+        assert_eq!(loaded_state.get_param(), Some(&vec![1.0f64, 0.0]));
+        let _ = std::fs::remove_file("checkpoints/solver_json.json");
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    #[allow(clippy::type_complexity)]
+    fn test_save_load_cbor() {
+        let solver = TestSolver::new();
+        let state: IterState<Vec<f64>, (), (), (), (), f64> =
+            IterState::new().param(vec![1.0f64, 0.0]);
+        let check =
+            FileCheckpoint::new("checkpoints", "solver_cbor", CheckpointingFrequency::Always)
+                .with_format(CheckpointFormat::Cbor);
+        assert_eq!(check.filename, PathBuf::from("solver_cbor.cbor"));
+        check.save_cond(&solver, &state, 20).unwrap();
+
+        let loaded: Option<(TestSolver, IterState<Vec<f64>, (), (), (), (), f64>)> =
+            check.load().unwrap();
+        let (_, loaded_state) = loaded.unwrap();
+        assert_eq!(loaded_state.get_param(), Some(&vec![1.0f64, 0.0]));
+        let _ = std::fs::remove_file("checkpoints/solver_cbor.cbor");
+    }
+
+    #[test]
+    fn test_resume_particleswarm() {
+        use argmin::core::{CostFunction, Error, Executor};
+        use argmin::solver::particleswarm::ParticleSwarm;
+        use rand::SeedableRng;
+        use rand_xoshiro::Xoshiro256PlusPlus;
+
+        #[derive(Clone, Copy)]
+        struct Sphere {}
+
+        impl CostFunction for Sphere {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(param.iter().map(|x| x.powi(2)).sum())
+            }
+        }
+
+        let solver = || {
+            ParticleSwarm::new((vec![-4.0, -4.0], vec![4.0, 4.0]), 20)
+                .with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(42))
+        };
+
+        // Uninterrupted run
+        let uninterrupted = Executor::new(Sphere {}, solver())
+            .configure(|state| state.max_iters(10))
+            .run()
+            .unwrap();
+
+        // Interrupted run, resumed from checkpoint
+        let checkpoint = FileCheckpoint::new(
+            "checkpoints",
+            "pso_resume_test",
+            CheckpointingFrequency::Always,
+        );
+        let _ = Executor::new(Sphere {}, solver())
+            .configure(|state| state.max_iters(5))
+            .checkpointing(checkpoint.clone())
+            .run()
+            .unwrap();
+        let resumed: Executor<_, ParticleSwarm<_, f64, Xoshiro256PlusPlus>, _> =
+            Executor::from_checkpoint(Sphere {}, checkpoint).unwrap();
+        let resumed = resumed
+            .configure(|state| state.max_iters(10))
+            .run()
+            .unwrap();
+
+        assert_eq!(resumed.state.get_iter(), 10);
+        assert_eq!(
+            resumed.state.get_best_param(),
+            uninterrupted.state.get_best_param()
+        );
+        assert_eq!(
+            resumed.state.get_population(),
+            uninterrupted.state.get_population()
+        );
+        let _ = std::fs::remove_file("checkpoints/pso_resume_test.arg");
+    }
 }
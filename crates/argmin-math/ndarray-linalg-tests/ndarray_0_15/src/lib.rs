@@ -1,6 +1,9 @@
 mod add {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/add.rs"));
 }
+mod addassign {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/addassign.rs"));
+}
 mod conj {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/conj.rs"));
 }
@@ -28,21 +31,33 @@ mod minmax {
 mod mul {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/mul.rs"));
 }
+mod mulassign {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/mulassign.rs"));
+}
 mod random {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/random.rs"));
 }
 mod scaledadd {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/scaledadd.rs"));
 }
+mod scaledaddassign {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/scaledaddassign.rs"));
+}
 mod scaledsub {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/scaledsub.rs"));
 }
 mod signum {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/signum.rs"));
 }
+mod solve {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/solve.rs"));
+}
 mod sub {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/sub.rs"));
 }
+mod subassign {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/subassign.rs"));
+}
 mod transpose {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/transpose.rs"));
 }
@@ -4,6 +4,12 @@ mod add {
         "/../../ndarray-tests-src/add.rs"
     ));
 }
+mod addassign {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/addassign.rs"
+    ));
+}
 mod conj {
     include!(concat!(
         env!("CARGO_MANIFEST_DIR"),
@@ -58,6 +64,12 @@ mod mul {
         "/../../ndarray-tests-src/mul.rs"
     ));
 }
+mod mulassign {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/mulassign.rs"
+    ));
+}
 mod random {
     include!(concat!(
         env!("CARGO_MANIFEST_DIR"),
@@ -70,6 +82,12 @@ mod scaledadd {
         "/../../ndarray-tests-src/scaledadd.rs"
     ));
 }
+mod scaledaddassign {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/scaledaddassign.rs"
+    ));
+}
 mod scaledsub {
     include!(concat!(
         env!("CARGO_MANIFEST_DIR"),
@@ -82,12 +100,24 @@ mod signum {
         "/../../ndarray-tests-src/signum.rs"
     ));
 }
+mod solve {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/solve.rs"
+    ));
+}
 mod sub {
     include!(concat!(
         env!("CARGO_MANIFEST_DIR"),
         "/../../ndarray-tests-src/sub.rs"
     ));
 }
+mod subassign {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/subassign.rs"
+    ));
+}
 mod transpose {
     include!(concat!(
         env!("CARGO_MANIFEST_DIR"),
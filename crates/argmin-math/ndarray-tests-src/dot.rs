@@ -366,4 +366,32 @@ mod tests {
     make_test!(u64);
     make_test!(f32);
     make_test!(f64);
+
+    macro_rules! make_test_real {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_vec_vec_complex_real_ $t>]() {
+                    let a = array![
+                        Complex::new(2 as $t, 2 as $t),
+                        Complex::new(5 as $t, 2 as $t),
+                        Complex::new(3 as $t, 2 as $t),
+                    ];
+                    let b = array![
+                        Complex::new(5 as $t, 3 as $t),
+                        Complex::new(2 as $t, 4 as $t),
+                        Complex::new(8 as $t, 4 as $t),
+                    ];
+                    let res = <Array1<Complex<$t>> as ArgminDot<Array1<Complex<$t>>, $t>>::dot(&a, &b);
+                    let target = (a[0].conj() * b[0] + a[1].conj() * b[1] + a[2].conj() * b[2]).re;
+                    assert_relative_eq!(res as f64, target as f64, epsilon = f64::EPSILON);
+                    let norm = <Array1<Complex<$t>> as ArgminDot<Array1<Complex<$t>>, $t>>::dot(&a, &a);
+                    assert_relative_eq!(norm as f64, 50.0, epsilon = f64::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test_real!(f32);
+    make_test_real!(f64);
 }
@@ -37,6 +37,46 @@ mod tests {
                 }
             }
 
+            item! {
+                #[test]
+                fn [<test_inv_positive_definite_ $t>]() {
+                    let a = array![
+                        [2 as $t, 1 as $t],
+                        [1 as $t, 1 as $t],
+                    ];
+                    let target = array![
+                        [1 as $t, -1 as $t],
+                        [-1 as $t, 2 as $t],
+                    ];
+                    let res = <Array2<$t> as ArgminInv<Array2<$t>>>::inv(&a).unwrap();
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(res[(i, j)], target[(i, j)], epsilon = $t::EPSILON.sqrt());
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_inv_symmetric_indefinite_ $t>]() {
+                    let a = array![
+                        [1 as $t, 2 as $t],
+                        [2 as $t, 1 as $t],
+                    ];
+                    let target = array![
+                        [-1 as $t / 3 as $t, 2 as $t / 3 as $t],
+                        [2 as $t / 3 as $t, -1 as $t / 3 as $t],
+                    ];
+                    let res = <Array2<$t> as ArgminInv<Array2<$t>>>::inv(&a).unwrap();
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(res[(i, j)], target[(i, j)], epsilon = $t::EPSILON.sqrt());
+                        }
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_inv_scalar_ $t>]() {
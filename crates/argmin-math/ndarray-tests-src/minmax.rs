@@ -13,6 +13,7 @@ mod tests {
     use argmin_math::ArgminMinMax;
     use ndarray::array;
     use ndarray::{Array1, Array2};
+    use num_complex::Complex;
     use paste::item;
 
     macro_rules! make_test {
@@ -75,4 +76,29 @@ mod tests {
     make_test!(u64);
     make_test!(f32);
     make_test!(f64);
+
+    macro_rules! make_test_complex {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_minmax_vec_complex_ $t>]() {
+                    let a = array![Complex::new(1 as $t, 4 as $t), Complex::new(-2 as $t, 0 as $t)];
+                    let b = array![Complex::new(2 as $t, 3 as $t), Complex::new(-3 as $t, 1 as $t)];
+                    let target_min = array![Complex::new(1 as $t, 3 as $t), Complex::new(-3 as $t, 0 as $t)];
+                    let target_max = array![Complex::new(2 as $t, 4 as $t), Complex::new(-2 as $t, 1 as $t)];
+                    let res_min = <Array1<Complex<$t>> as ArgminMinMax>::min(&a, &b);
+                    let res_max = <Array1<Complex<$t>> as ArgminMinMax>::max(&a, &b);
+                    for i in 0..2 {
+                        assert_relative_eq!(target_min[i].re as f64, res_min[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target_min[i].im as f64, res_min[i].im as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target_max[i].re as f64, res_max[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target_max[i].im as f64, res_max[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+        };
+    }
+
+    make_test_complex!(f32);
+    make_test_complex!(f64);
 }
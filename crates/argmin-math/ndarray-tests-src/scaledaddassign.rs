@@ -0,0 +1,95 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use approx::assert_relative_eq;
+    use argmin_math::ArgminScaledAddAssign;
+    use ndarray::array;
+    use ndarray::{Array1, Array2};
+    use num_complex::Complex;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scaledaddassign_vec_ $t>]() {
+                    let mut a = array![1 as $t, 2 as $t, 3 as $t];
+                    let b = 2 as $t;
+                    let c = array![4 as $t, 5 as $t, 6 as $t];
+                    <Array1<$t> as ArgminScaledAddAssign<Array1<$t>, $t>>::scaled_add_assign(&mut a, &b, &c);
+                    let target = array![9 as $t, 12 as $t, 15 as $t];
+                    for i in 0..3 {
+                        assert_relative_eq!(a[i] as f64, target[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scaledaddassign_vec_panic_ $t>]() {
+                    let mut a = array![1 as $t, 2 as $t, 3 as $t];
+                    let b = 2 as $t;
+                    let c = array![4 as $t, 5 as $t];
+                    <Array1<$t> as ArgminScaledAddAssign<Array1<$t>, $t>>::scaled_add_assign(&mut a, &b, &c);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_scaledaddassign_mat_ $t>]() {
+                    let mut a = array![[1 as $t, 2 as $t, 3 as $t], [4 as $t, 5 as $t, 6 as $t]];
+                    let b = 2 as $t;
+                    let c = array![[4 as $t, 5 as $t, 6 as $t], [1 as $t, 2 as $t, 3 as $t]];
+                    <Array2<$t> as ArgminScaledAddAssign<Array2<$t>, $t>>::scaled_add_assign(&mut a, &b, &c);
+                    let target = array![[9 as $t, 12 as $t, 15 as $t], [6 as $t, 9 as $t, 12 as $t]];
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(a[(j, i)] as f64, target[(j, i)] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+
+    macro_rules! make_test_real_scalar {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scaledaddassign_vec_complex_real_scalar_ $t>]() {
+                    let mut a = array![Complex::new(5 as $t, 3 as $t), Complex::new(8 as $t, 2 as $t)];
+                    let b = array![Complex::new(1 as $t, 2 as $t), Complex::new(3 as $t, 4 as $t)];
+                    a.scaled_add_assign(&(2 as $t), &b);
+                    let target = array![Complex::new(7 as $t, 7 as $t), Complex::new(14 as $t, 10 as $t)];
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i].re as f64, a[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, a[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+        };
+    }
+
+    make_test_real_scalar!(f32);
+    make_test_real_scalar!(f64);
+}
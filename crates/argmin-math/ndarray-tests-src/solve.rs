@@ -0,0 +1,84 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use approx::assert_relative_eq;
+    use argmin_math::ArgminSolve;
+    use ndarray::array;
+    use ndarray::{Array1, Array2};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_solve_ $t>]() {
+                    let a = array![
+                        [2 as $t, 5 as $t],
+                        [1 as $t, 3 as $t],
+                    ];
+                    let b = array![1 as $t, 2 as $t];
+                    let target = array![-7 as $t, 3 as $t];
+                    let res = <Array2<$t> as ArgminSolve<Array1<$t>, Array1<$t>>>::solve(&a, &b).unwrap();
+                    for i in 0..2 {
+                        assert_relative_eq!(res[i], target[i], epsilon = $t::EPSILON.sqrt());
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_positive_definite_ $t>]() {
+                    let a = array![
+                        [4 as $t, 2 as $t, 0 as $t],
+                        [2 as $t, 5 as $t, 1 as $t],
+                        [0 as $t, 1 as $t, 3 as $t],
+                    ];
+                    let target = array![1 as $t, -2 as $t, 3 as $t];
+                    let b = a.dot(&target);
+                    let res = <Array2<$t> as ArgminSolve<Array1<$t>, Array1<$t>>>::solve(&a, &b).unwrap();
+                    for i in 0..3 {
+                        assert_relative_eq!(res[i], target[i], epsilon = $t::EPSILON.sqrt());
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_symmetric_indefinite_ $t>]() {
+                    // Cholesky decomposition fails, LU decomposition is used instead
+                    let a = array![
+                        [1 as $t, 2 as $t],
+                        [2 as $t, 1 as $t],
+                    ];
+                    let b = array![3 as $t, 3 as $t];
+                    let res = <Array2<$t> as ArgminSolve<Array1<$t>, Array1<$t>>>::solve(&a, &b).unwrap();
+                    assert_relative_eq!(res[0], 1 as $t, epsilon = $t::EPSILON.sqrt());
+                    assert_relative_eq!(res[1], 1 as $t, epsilon = $t::EPSILON.sqrt());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_error_ $t>]() {
+                    let a = array![
+                        [2 as $t, 5 as $t],
+                        [4 as $t, 10 as $t],
+                    ];
+                    let b = array![1 as $t, 2 as $t];
+                    assert!(<Array2<$t> as ArgminSolve<Array1<$t>, Array1<$t>>>::solve(&a, &b).is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
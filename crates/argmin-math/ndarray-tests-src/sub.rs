@@ -13,6 +13,7 @@ mod tests {
     use argmin_math::ArgminSub;
     use ndarray::array;
     use ndarray::{Array1, Array2};
+    use num_complex::Complex;
     use paste::item;
 
     macro_rules! make_test {
@@ -171,4 +172,29 @@ mod tests {
     make_test!(u64);
     make_test!(f32);
     make_test!(f64);
+
+    macro_rules! make_test_real_scalar {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_sub_vec_complex_real_scalar_ $t>]() {
+                    let a = array![Complex::new(5 as $t, 3 as $t), Complex::new(8 as $t, 2 as $t)];
+                    let b = 2 as $t;
+                    let target = array![Complex::new(3 as $t, 3 as $t), Complex::new(6 as $t, 2 as $t)];
+                    let target2 = array![Complex::new(-3 as $t, -3 as $t), Complex::new(-6 as $t, -2 as $t)];
+                    let res = <Array1<Complex<$t>> as ArgminSub<$t, Array1<Complex<$t>>>>::sub(&a, &b);
+                    let res2 = <$t as ArgminSub<Array1<Complex<$t>>, Array1<Complex<$t>>>>::sub(&b, &a);
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target2[i].re as f64, res2[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target2[i].im as f64, res2[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+        };
+    }
+
+    make_test_real_scalar!(f32);
+    make_test_real_scalar!(f64);
 }
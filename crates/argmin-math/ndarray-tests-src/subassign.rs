@@ -0,0 +1,98 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use approx::assert_relative_eq;
+    use argmin_math::ArgminSubAssign;
+    use ndarray::array;
+    use ndarray::{Array1, Array2};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_subassign_vec_scalar_ $t>]() {
+                    let mut a = array![42 as $t, 44 as $t, 48 as $t];
+                    let b = 2 as $t;
+                    let target = array![40 as $t, 42 as $t, 46 as $t];
+                    <Array1<$t> as ArgminSubAssign<$t>>::sub_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, a[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_subassign_vec_vec_ $t>]() {
+                    let mut a = array![42 as $t, 44 as $t, 48 as $t];
+                    let b = array![41 as $t, 40 as $t, 6 as $t];
+                    let target = array![1 as $t, 4 as $t, 42 as $t];
+                    <Array1<$t> as ArgminSubAssign<Array1<$t>>>::sub_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, a[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_subassign_vec_vec_panic_ $t>]() {
+                    let mut a = array![42 as $t, 44 as $t, 48 as $t];
+                    let b = array![1 as $t, 2 as $t];
+                    <Array1<$t> as ArgminSubAssign<Array1<$t>>>::sub_assign(&mut a, &b);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_subassign_mat_mat_ $t>]() {
+                    let mut a = array![[42 as $t, 44 as $t, 48 as $t], [42 as $t, 44 as $t, 48 as $t]];
+                    let b = array![[41 as $t, 40 as $t, 6 as $t], [41 as $t, 40 as $t, 6 as $t]];
+                    let target = array![[1 as $t, 4 as $t, 42 as $t], [1 as $t, 4 as $t, 42 as $t]];
+                    <Array2<$t> as ArgminSubAssign<Array2<$t>>>::sub_assign(&mut a, &b);
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(target[(j, i)] as f64, a[(j, i)] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_subassign_mat_scalar_ $t>]() {
+                    let mut a = array![[42 as $t, 44 as $t, 48 as $t], [42 as $t, 44 as $t, 48 as $t]];
+                    let b = 2 as $t;
+                    let target = array![[40 as $t, 42 as $t, 46 as $t], [40 as $t, 42 as $t, 46 as $t]];
+                    <Array2<$t> as ArgminSubAssign<$t>>::sub_assign(&mut a, &b);
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(target[(j, i)] as f64, a[(j, i)] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,63 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use approx::assert_relative_eq;
+    use argmin_math::ArgminSvd;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_singular_values_ $t>]() {
+                    let a = array![
+                        [3 as $t, 0 as $t],
+                        [4 as $t, 5 as $t],
+                    ];
+                    let sigma = a.singular_values().unwrap();
+                    assert_eq!(sigma.len(), 2);
+                    assert_relative_eq!(sigma[0], (45 as $t).sqrt(), epsilon = 1e-5);
+                    assert_relative_eq!(sigma[1], (5 as $t).sqrt(), epsilon = 1e-5);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_map_singular_values_ $t>]() {
+                    let a = array![
+                        [1 as $t, 2 as $t, 3 as $t],
+                        [4 as $t, 5 as $t, 6 as $t],
+                    ];
+                    let res = a.map_singular_values(|s| 2 as $t * s).unwrap();
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert_relative_eq!(res[(i, j)], 2 as $t * a[(i, j)], epsilon = 1e-4);
+                        }
+                    }
+
+                    let a = array![
+                        [3 as $t, 0 as $t],
+                        [0 as $t, 1 as $t],
+                    ];
+                    let res = a.map_singular_values(|s| (s - 2 as $t).max(0 as $t)).unwrap();
+                    assert_relative_eq!(res[(0, 0)], 1 as $t, epsilon = 1e-5);
+                    assert_relative_eq!(res[(0, 1)], 0 as $t, epsilon = 1e-5);
+                    assert_relative_eq!(res[(1, 0)], 0 as $t, epsilon = 1e-5);
+                    assert_relative_eq!(res[(1, 1)], 0 as $t, epsilon = 1e-5);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
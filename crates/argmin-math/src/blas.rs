@@ -0,0 +1,121 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Kernels for `f32` and `f64` slices which are routed through BLAS (feature `blas`).
+//!
+//! For small dimensions the overhead of calling into BLAS outweighs its benefits, therefore the
+//! kernels fall back to plain loops below certain sizes.
+
+use cblas_sys::{
+    cblas_daxpy, cblas_ddot, cblas_dgemm, cblas_saxpy, cblas_sdot, cblas_sgemm, CBLAS_LAYOUT,
+    CBLAS_TRANSPOSE,
+};
+use std::os::raw::c_int;
+
+/// Minimum length of vectors for which dot products and `axpy` are computed by BLAS
+const MIN_LEN: usize = 64;
+
+/// Minimum number of multiplications (`m * n * k`) for which matrix products are computed by BLAS
+const MIN_GEMM_OPS: usize = 32 * 32 * 32;
+
+macro_rules! make_blas {
+    (
+        $t:ty,
+        $dot_fn:ident,
+        $axpy_fn:ident,
+        $matmul_fn:ident,
+        $dot:ident,
+        $axpy:ident,
+        $gemm:ident
+    ) => {
+        /// Dot product of the first `min(a.len(), b.len())` elements of `a` and `b`.
+        #[inline]
+        pub(crate) fn $dot_fn(a: &[$t], b: &[$t]) -> $t {
+            let n = a.len().min(b.len());
+            if n < MIN_LEN {
+                return a.iter().zip(b.iter()).map(|(a, b)| a * b).sum();
+            }
+            // Safety: Both slices hold at least `n` elements and are read with unit stride.
+            unsafe { $dot(n as c_int, a.as_ptr(), 1, b.as_ptr(), 1) }
+        }
+
+        /// Adds `alpha * x` to `y`; both must be of the same length.
+        #[inline]
+        pub(crate) fn $axpy_fn(alpha: $t, x: &[$t], y: &mut [$t]) {
+            let n = y.len();
+            assert_eq!(x.len(), n);
+            if n < MIN_LEN {
+                y.iter_mut()
+                    .zip(x.iter())
+                    .for_each(|(y, x)| *y += alpha * x);
+                return;
+            }
+            // Safety: Both slices hold `n` elements and are accessed with unit stride.
+            unsafe { $axpy(n as c_int, alpha, x.as_ptr(), 1, y.as_mut_ptr(), 1) }
+        }
+
+        /// Matrix product of `a` (`m x k`) and `b` (`k x n`), both given as vectors of rows.
+        ///
+        /// Returns `None` if the product is too small to benefit from BLAS or if the shapes are
+        /// inconsistent, which leaves the error handling to the caller.
+        pub(crate) fn $matmul_fn(a: &[Vec<$t>], b: &[Vec<$t>]) -> Option<Vec<Vec<$t>>> {
+            let m = a.len();
+            let k = a.first()?.len();
+            let n = b.first()?.len();
+            if m * n * k < MIN_GEMM_OPS
+                || b.len() != k
+                || a.iter().any(|row| row.len() != k)
+                || b.iter().any(|row| row.len() != n)
+            {
+                return None;
+            }
+            let a = a.concat();
+            let b = b.concat();
+            let mut c = vec![0.0; m * n];
+            // Safety: `a`, `b` and `c` are contiguous row-major matrices of the shapes `m x k`,
+            // `k x n` and `m x n`, respectively.
+            unsafe {
+                $gemm(
+                    CBLAS_LAYOUT::CblasRowMajor,
+                    CBLAS_TRANSPOSE::CblasNoTrans,
+                    CBLAS_TRANSPOSE::CblasNoTrans,
+                    m as c_int,
+                    n as c_int,
+                    k as c_int,
+                    1.0,
+                    a.as_ptr(),
+                    k as c_int,
+                    b.as_ptr(),
+                    n as c_int,
+                    0.0,
+                    c.as_mut_ptr(),
+                    n as c_int,
+                )
+            };
+            Some(c.chunks_exact(n).map(<[$t]>::to_vec).collect())
+        }
+    };
+}
+
+make_blas!(
+    f32,
+    dot_f32,
+    axpy_f32,
+    matmul_f32,
+    cblas_sdot,
+    cblas_saxpy,
+    cblas_sgemm
+);
+make_blas!(
+    f64,
+    dot_f64,
+    axpy_f64,
+    matmul_f64,
+    cblas_ddot,
+    cblas_daxpy,
+    cblas_dgemm
+);
@@ -6,7 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 //! argmin-math provides mathematics related abstractions needed in argmin. It supports
-//! implementations of these abstractions for basic `Vec`s and for `ndarray` and `nalgebra`.
+//! implementations of these abstractions for basic `Vec`s and for `ndarray`, `nalgebra` and `sprs`.
 //! The traits can of course also be implemented for your own types to make them compatible with
 //! argmin.
 //!
@@ -34,9 +34,21 @@
 //!
 //! | Feature                | Default | Comment                                               |
 //! |------------------------|---------|-------------------------------------------------------|
+//! | `std`                  | yes     | support for the standard library                      |
 //! | `primitives`           | yes     | basic integer and floating point types                |
 //! | `vec`                  | yes     | `Vec`s (basic functionality)                          |
 //!
+//! ### `no_std`
+//!
+//! Without the `std` feature, this crate is `no_std` and only requires `alloc`. Only the
+//! `primitives` and `vec` backends are available in this case; all other backends (and `blas`)
+//! enable `std`. Floating point functions such as `sqrt` are provided by `libm`.
+//!
+//! ```toml
+//! [dependencies]
+//! argmin-math = { version = "*", default-features = false, features = ["primitives", "vec"] }
+//! ```
+//!
 //! ### `ndarray`
 //!
 //! | Feature                         | Default | Comment                                                            |
@@ -50,10 +62,10 @@
 //!
 //! Note that the `*-nolinalg*` features do NOT pull in `ndarray-linalg` as a dependency. This
 //! avoids linking against a BLAS library. This will however disable the implementation of
-//! `ArgminInv`, meaning that any solver which requires the matrix inverse will not work with the
-//! `ndarray` backend. It is recommended to use the `*-nolinalg*` options if the matrix inverse is
-//! not needed in order to keep the compilation times low and avoid problems when linking against a
-//! BLAS library.
+//! `ArgminInv` and `ArgminSolve`, meaning that any solver which requires the matrix inverse or
+//! solves linear systems will not work with the `ndarray` backend. It is recommended to use the
+//! `*-nolinalg*` options if neither is needed in order to keep the compilation times low and avoid
+//! problems when linking against a BLAS library.
 //!
 //! Using the `ndarray_*` features with `ndarray-linalg` support may require to explicitly choose
 //! the `ndarray-linalg` BLAS backend in your `Cargo.toml` (see the [`ndarray-linalg` documentation
@@ -63,6 +75,10 @@
 //! ndarray-linalg = { version = "<appropriate_version>", features = ["<linalg_backend>"] }
 //! ```
 //!
+//! With `ndarray-linalg`, linear systems (`ArgminSolve`) are solved and inverses (`ArgminInv`) are
+//! computed via a Cholesky decomposition if the matrix is symmetric (Hermitian) and positive
+//! definite, and via an LU decomposition with partial pivoting otherwise.
+//!
 //! ### `nalgebra`
 //!
 //! | Feature                | Default | Comment                                  |
@@ -74,6 +90,54 @@
 //! | `nalgebra_v0_30`       | no      | version 0.30                             |
 //! | `nalgebra_v0_29`       | no      | version 0.29                             |
 //!
+//! ### `sprs`
+//!
+//! | Feature                | Default | Comment                                  |
+//! |------------------------|---------|------------------------------------------|
+//! | `sprs_latest`          | no      | latest supported version                 |
+//! | `sprs_v0_11`           | no      | version 0.11 with `sprs-ldl` 0.10        |
+//!
+//! Sparse matrices (`CsMat`) can be used as Hessians and Jacobians, while parameter vectors,
+//! gradients and residuals are dense `ndarray` arrays (`Array1`). The `sprs_*` features therefore
+//! activate `ndarray` version 0.15 without `ndarray-linalg` (which can be added by additionally
+//! activating `ndarray_v0_15`) and cannot be combined with other versions of `ndarray`.
+//! Linear systems are solved via a sparse LDL^T decomposition, which requires the matrix to be
+//! symmetric. This is the case for Hessians and for the normal equations of Gauss-Newton
+//! methods (`J^T J`).
+//!
+//! ### BLAS
+//!
+//! | Feature                | Default | Comment                                  |
+//! |------------------------|---------|------------------------------------------|
+//! | `blas`                 | no      | route dense operations through BLAS      |
+//!
+//! With the `blas` feature, dot products and matrix products of `Vec<f32>`/`Vec<f64>` (and
+//! `Vec<Vec<f32>>`/`Vec<Vec<f64>>`) are computed via BLAS (`dot`, `gemm`) once the dimensions are
+//! large enough for this to pay off. If an `ndarray` backend is active, its `blas` feature is
+//! enabled as well, which makes `ndarray` compute `dot` via `gemv` and `gemm`.
+//! This feature only declares the BLAS interface; a BLAS implementation has to be linked by
+//! adding `blas-src` with the desired backend (the same one `ndarray-linalg` uses, if any) to the
+//! dependencies:
+//!
+//! ```toml
+//! [dependencies]
+//! argmin-math = { version = "*", features = ["vec", "blas"] }
+//! blas-src = { version = "0.8", features = ["openblas"] }
+//! openblas-src = { version = "0.10", features = ["cblas", "system"] }
+//! ```
+//!
+//! Since `blas-src` is not referenced otherwise, it must be linked explicitly via
+//! `extern crate blas_src;`.
+//!
+//! ## Complex numbers
+//!
+//! For `Vec<Complex<T>>` and `Array1<Complex<T>>` (with `T` being `f32` or `f64`), `ArgminDot`
+//! with a real-valued output computes the real inner product `Re(a^H b)`, which treats `C^n` as
+//! `R^{2n}`. Together with the arithmetic between complex vectors and real scalars, as well as
+//! `ArgminMinMax` which operates on real and imaginary parts separately, this allows solvers which
+//! expect real-valued step sizes and dot products to operate on complex parameter vectors. The
+//! `ArgminDot` implementation with a complex-valued output is the bilinear product without
+//! conjugation.
 //!
 //! ## Choosing a backend
 //!
@@ -126,11 +190,14 @@
 //! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
 //! without any additional terms or conditions.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
 // Explicitly disallow EQ comparison of floats. (This clippy lint is denied by default; however,
 // this is just to make sure that it will always stay this way.)
 #![deny(clippy::float_cmp)]
 
+extern crate alloc;
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "nalgebra_0_33")] {
         extern crate nalgebra_0_33 as nalgebra;
@@ -203,6 +270,13 @@ cfg_if::cfg_if! {
     }
 }
 
+cfg_if::cfg_if! {
+    if #[cfg(feature = "sprs_0_11")] {
+        extern crate sprs_0_11 as sprs;
+        extern crate sprs_ldl_0_10 as sprs_ldl;
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "num-complex_0_2")] {
         extern crate num_complex_0_2 as num_complex;
@@ -213,6 +287,9 @@ cfg_if::cfg_if! {
     }
 }
 
+#[cfg(feature = "blas")]
+mod blas;
+
 #[cfg(feature = "primitives")]
 mod primitives;
 #[cfg(feature = "primitives")]
@@ -231,6 +308,12 @@ mod nalgebra_m;
 #[allow(unused_imports)]
 pub use crate::nalgebra_m::*;
 
+#[cfg(feature = "sprs_all")]
+mod sprs_m;
+#[cfg(feature = "sprs_all")]
+#[allow(unused_imports)]
+pub use crate::sprs_m::*;
+
 #[cfg(feature = "vec")]
 mod vec;
 #[cfg(feature = "vec")]
@@ -318,6 +401,30 @@ pub trait ArgminScaledSub<T, U, V> {
     fn scaled_sub(&self, factor: &U, vec: &T) -> V;
 }
 
+/// Add a `T` to `self` in place
+pub trait ArgminAddAssign<T> {
+    /// Add a `T` to `self` in place
+    fn add_assign(&mut self, other: &T);
+}
+
+/// Subtract a `T` from `self` in place
+pub trait ArgminSubAssign<T> {
+    /// Subtract a `T` from `self` in place
+    fn sub_assign(&mut self, other: &T);
+}
+
+/// (Pointwise) Multiply `self` with a `T` in place
+pub trait ArgminMulAssign<T> {
+    /// (Pointwise) Multiply `self` with a `T` in place
+    fn mul_assign(&mut self, other: &T);
+}
+
+/// Add a `T` scaled by an `U` to `self` in place
+pub trait ArgminScaledAddAssign<T, U> {
+    /// Add a `T` scaled by an `U` to `self` in place
+    fn scaled_add_assign(&mut self, factor: &U, vec: &T);
+}
+
 /// Compute the l1-norm (`U`) of `self`
 pub trait ArgminL1Norm<U> {
     /// Compute the l1-norm (`U`) of `self`
@@ -330,6 +437,12 @@ pub trait ArgminL2Norm<U> {
     fn l2_norm(&self) -> U;
 }
 
+/// Compute the maximum norm (`U`) of `self`, i.e. the largest absolute value of its components
+pub trait ArgminLInfNorm<U> {
+    /// Compute the maximum norm (`U`) of `self`
+    fn linf_norm(&self) -> U;
+}
+
 // Sub-optimal: self is moved. ndarray however offers array views...
 /// Return the transpose (`U`) of `self`
 pub trait ArgminTranspose<U> {
@@ -343,6 +456,26 @@ pub trait ArgminInv<T> {
     fn inv(&self) -> Result<T, Error>;
 }
 
+/// Solve the linear system `self * x = b` for `x` (`X`)
+///
+/// This is usually faster and numerically more accurate than computing the inverse of `self`.
+pub trait ArgminSolve<B, X> {
+    /// Solve the linear system `self * x = b` for `x`
+    fn solve(&self, b: &B) -> Result<X, Error>;
+}
+
+/// Singular value decomposition `self = U S V^T` of a matrix with singular values of type `F`
+pub trait ArgminSvd<F>: Sized {
+    /// Compute the singular values in descending order
+    fn singular_values(&self) -> Result<alloc::vec::Vec<F>, Error>;
+
+    /// Compute `U f(S) V^T`, where `f` is applied to each singular value
+    ///
+    /// The singular vectors of vanishing singular values are not unique, therefore `f(0)` should
+    /// be `0`.
+    fn map_singular_values<G: FnMut(F) -> F>(&self, f: G) -> Result<Self, Error>;
+}
+
 /// Create a random number
 pub trait ArgminRandom {
     /// Get a random element between min and max,
@@ -0,0 +1,137 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminAddAssign, ClosedAdd};
+
+use nalgebra::{
+    base::{
+        constraint::{SameNumberOfColumns, SameNumberOfRows, ShapeConstraint},
+        dimension::Dim,
+        storage::{Storage, StorageMut},
+        Scalar,
+    },
+    Matrix,
+};
+
+impl<N, R, C, S> ArgminAddAssign<N> for Matrix<N, R, C, S>
+where
+    N: Scalar + ClosedAdd + Copy,
+    R: Dim,
+    C: Dim,
+    S: StorageMut<N, R, C>,
+{
+    #[inline]
+    fn add_assign(&mut self, other: &N) {
+        self.iter_mut().for_each(|entry| *entry += *other);
+    }
+}
+
+impl<N, R1, C1, R2, C2, SA, SB> ArgminAddAssign<Matrix<N, R2, C2, SB>> for Matrix<N, R1, C1, SA>
+where
+    N: Scalar + ClosedAdd,
+    R1: Dim,
+    C1: Dim,
+    R2: Dim,
+    C2: Dim,
+    SA: StorageMut<N, R1, C1>,
+    SB: Storage<N, R2, C2>,
+    ShapeConstraint: SameNumberOfRows<R1, R2> + SameNumberOfColumns<C1, C2>,
+{
+    #[inline]
+    fn add_assign(&mut self, other: &Matrix<N, R2, C2, SB>) {
+        *self += other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::{DVector, Matrix2x3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_addassign_vec_scalar_ $t>]() {
+                    let mut a = Vector3::new(1 as $t, 4 as $t, 8 as $t);
+                    let b = 34 as $t;
+                    let target = Vector3::new(35 as $t, 38 as $t, 42 as $t);
+                    <Vector3<$t> as ArgminAddAssign<$t>>::add_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, a[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_addassign_vec_vec_ $t>]() {
+                    let mut a = Vector3::new(1 as $t, 4 as $t, 8 as $t);
+                    let b = Vector3::new(41 as $t, 38 as $t, 34 as $t);
+                    let target = Vector3::new(42 as $t, 42 as $t, 42 as $t);
+                    <Vector3<$t> as ArgminAddAssign<Vector3<$t>>>::add_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, a[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_addassign_vec_vec_panic_ $t>]() {
+                    let mut a = DVector::from_vec(vec![1 as $t, 4 as $t, 8 as $t]);
+                    let b = DVector::from_vec(vec![1 as $t, 2 as $t]);
+                    <DVector<$t> as ArgminAddAssign<DVector<$t>>>::add_assign(&mut a, &b);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_addassign_mat_mat_ $t>]() {
+                    let mut a = Matrix2x3::new(1 as $t, 4 as $t, 8 as $t, 1 as $t, 4 as $t, 8 as $t);
+                    let b = Matrix2x3::new(41 as $t, 38 as $t, 34 as $t, 41 as $t, 38 as $t, 34 as $t);
+                    let target = Matrix2x3::new(42 as $t, 42 as $t, 42 as $t, 42 as $t, 42 as $t, 42 as $t);
+                    <Matrix2x3<$t> as ArgminAddAssign<Matrix2x3<$t>>>::add_assign(&mut a, &b);
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(target[(j, i)] as f64, a[(j, i)] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_addassign_mat_scalar_ $t>]() {
+                    let mut a = Matrix2x3::new(1 as $t, 4 as $t, 8 as $t, 1 as $t, 4 as $t, 8 as $t);
+                    let b = 34 as $t;
+                    let target = Matrix2x3::new(35 as $t, 38 as $t, 42 as $t, 35 as $t, 38 as $t, 42 as $t);
+                    <Matrix2x3<$t> as ArgminAddAssign<$t>>::add_assign(&mut a, &b);
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(target[(j, i)] as f64, a[(j, i)] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
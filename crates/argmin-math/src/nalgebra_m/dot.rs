@@ -17,7 +17,7 @@ use nalgebra::{
         storage::Storage,
         Scalar,
     },
-    DefaultAllocator, Matrix, OMatrix,
+    DMatrix, DVector, DefaultAllocator, Matrix, OMatrix, SMatrix, SVector,
 };
 
 impl<N, R1, R2, C1, C2, SA, SB> ArgminDot<Matrix<N, R2, C2, SB>, N> for Matrix<N, R1, C1, SA>
@@ -85,6 +85,39 @@ where
     }
 }
 
+/// Outer product of two column vectors
+impl<N> ArgminDot<DVector<N>, DMatrix<N>> for DVector<N>
+where
+    N: Scalar + Zero + One + ClosedAdd + ClosedMul,
+{
+    #[inline]
+    fn dot(&self, other: &DVector<N>) -> DMatrix<N> {
+        self * other.transpose()
+    }
+}
+
+/// Outer products of two fixed-size column vectors.
+///
+/// Implemented for individual dimensions, because an implementation for a generic dimension `D`
+/// would overlap with the matrix product for `D = 1`.
+macro_rules! make_outer_product {
+    ($($d:literal),*) => {
+        $(
+            impl<N> ArgminDot<SVector<N, $d>, SMatrix<N, $d, $d>> for SVector<N, $d>
+            where
+                N: Scalar + Zero + One + ClosedAdd + ClosedMul,
+            {
+                #[inline]
+                fn dot(&self, other: &SVector<N, $d>) -> SMatrix<N, $d, $d> {
+                    self * other.transpose()
+                }
+            }
+        )*
+    };
+}
+
+make_outer_product!(2, 3, 4, 5, 6);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,6 +185,32 @@ mod tests {
                 }
             }
 
+            item! {
+                #[test]
+                fn [<test_outer_product_ $t>]() {
+                    let a = Vector3::new(1 as $t, 2 as $t, 3 as $t);
+                    let b = Vector3::new(4 as $t, 5 as $t, 6 as $t);
+                    let res = Matrix3::new(
+                        4 as $t, 5 as $t, 6 as $t,
+                        8 as $t, 10 as $t, 12 as $t,
+                        12 as $t, 15 as $t, 18 as $t
+                    );
+                    let product: Matrix3<$t> =
+                        <Vector3<$t> as ArgminDot<Vector3<$t>, Matrix3<$t>>>::dot(&a, &b);
+                    let a = DVector::from_column_slice(a.as_slice());
+                    let b = DVector::from_column_slice(b.as_slice());
+                    let product_dyn: DMatrix<$t> =
+                        <DVector<$t> as ArgminDot<DVector<$t>, DMatrix<$t>>>::dot(&a, &b);
+                    assert_eq!(product_dyn.shape(), (3, 3));
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            assert_relative_eq!(res[(i, j)] as f64, product[(i, j)] as f64, epsilon = f64::EPSILON);
+                            assert_relative_eq!(res[(i, j)] as f64, product_dyn[(i, j)] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_mat_vec_2_ $t>]() {
@@ -8,6 +8,7 @@
 #![allow(unused_imports)]
 
 mod add;
+mod addassign;
 mod conj;
 mod div;
 mod dot;
@@ -15,17 +16,24 @@ mod eye;
 mod inv;
 mod l1norm;
 mod l2norm;
+mod linfnorm;
 mod minmax;
 mod mul;
+mod mulassign;
 mod random;
 mod scaledadd;
+mod scaledaddassign;
 mod scaledsub;
 mod signum;
+mod solve;
 mod sub;
+mod subassign;
+mod svd;
 mod transpose;
 mod zero;
 
 pub use add::*;
+pub use addassign::*;
 pub use conj::*;
 pub use div::*;
 pub use dot::*;
@@ -33,12 +41,18 @@ pub use eye::*;
 pub use inv::*;
 pub use l1norm::*;
 pub use l2norm::*;
+pub use linfnorm::*;
 pub use minmax::*;
 pub use mul::*;
+pub use mulassign::*;
 pub use random::*;
 pub use scaledadd::*;
+pub use scaledaddassign::*;
 pub use scaledsub::*;
 pub use signum::*;
+pub use solve::*;
 pub use sub::*;
+pub use subassign::*;
+pub use svd::*;
 pub use transpose::*;
 pub use zero::*;
@@ -0,0 +1,101 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{Allocator, ArgminSolve, Error};
+use nalgebra::{
+    base::{
+        dimension::{Dim, DimMin},
+        storage::Storage,
+    },
+    ComplexField, DefaultAllocator, OMatrix, SquareMatrix,
+};
+use std::fmt;
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+struct SolveError;
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Singular matrix")
+    }
+}
+
+/// Solves the linear system via an LU decomposition with partial pivoting.
+impl<N, D, C, S> ArgminSolve<OMatrix<N, D, C>, OMatrix<N, D, C>> for SquareMatrix<N, D, S>
+where
+    N: ComplexField,
+    D: DimMin<D, Output = D>,
+    C: Dim,
+    S: Storage<N, D, D>,
+    DefaultAllocator: Allocator<N, D, D> + Allocator<N, D, C> + Allocator<(usize, usize), D>,
+{
+    #[inline]
+    fn solve(&self, b: &OMatrix<N, D, C>) -> Result<OMatrix<N, D, C>, Error> {
+        match self.clone_owned().lu().solve(b) {
+            Some(x) => Ok(x),
+            None => Err(SolveError {}.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, DVector, Matrix2, Vector2};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_solve_ $t>]() {
+                    let a = Matrix2::new(
+                        2 as $t, 5 as $t,
+                        1 as $t, 3 as $t,
+                    );
+                    let b = Vector2::new(1 as $t, 2 as $t);
+                    let target = Vector2::new(-7 as $t, 3 as $t);
+                    let res = <Matrix2<$t> as ArgminSolve<Vector2<$t>, Vector2<$t>>>::solve(&a, &b).unwrap();
+                    for i in 0..2 {
+                        assert_relative_eq!(res[i], target[i], epsilon = $t::EPSILON.sqrt());
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_dynamic_ $t>]() {
+                    let a = DMatrix::from_row_slice(2, 2, &[2 as $t, 5 as $t, 1 as $t, 3 as $t]);
+                    let b = DVector::from_column_slice(&[1 as $t, 2 as $t]);
+                    let target = [-7 as $t, 3 as $t];
+                    let res = <DMatrix<$t> as ArgminSolve<DVector<$t>, DVector<$t>>>::solve(&a, &b).unwrap();
+                    for i in 0..2 {
+                        assert_relative_eq!(res[i], target[i], epsilon = $t::EPSILON.sqrt());
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_error_ $t>]() {
+                    let a = Matrix2::new(
+                        2 as $t, 5 as $t,
+                        4 as $t, 10 as $t,
+                    );
+                    let b = Vector2::new(1 as $t, 2 as $t);
+                    let err = <Matrix2<$t> as ArgminSolve<Vector2<$t>, Vector2<$t>>>::solve(&a, &b).unwrap_err().downcast::<SolveError>().unwrap();
+                    assert_eq!(err, SolveError {});
+                    assert_eq!(format!("{}", err), "Singular matrix");
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,97 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{Allocator, ArgminSvd, Error};
+use nalgebra::{
+    base::dimension::{Dim, DimDiff, DimMin, DimMinimum, DimSub, U1},
+    DefaultAllocator, OMatrix, RealField,
+};
+
+impl<N, R, C> ArgminSvd<N> for OMatrix<N, R, C>
+where
+    N: RealField + Copy,
+    R: DimMin<C>,
+    C: Dim,
+    DimMinimum<R, C>: DimSub<U1>,
+    DefaultAllocator: Allocator<N, R, C>
+        + Allocator<N, C>
+        + Allocator<N, R>
+        + Allocator<N, DimDiff<DimMinimum<R, C>, U1>>
+        + Allocator<N, DimMinimum<R, C>, C>
+        + Allocator<N, R, DimMinimum<R, C>>
+        + Allocator<N, DimMinimum<R, C>>,
+{
+    #[inline]
+    fn singular_values(&self) -> Result<Vec<N>, Error> {
+        Ok(self
+            .clone()
+            .svd(false, false)
+            .singular_values
+            .iter()
+            .copied()
+            .collect())
+    }
+
+    #[inline]
+    fn map_singular_values<G: FnMut(N) -> N>(&self, mut f: G) -> Result<Self, Error> {
+        let mut svd = self.clone().svd(true, true);
+        svd.singular_values.apply(|s| *s = f(*s));
+        svd.recompose().map_err(|e| anyhow::anyhow!(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::{DMatrix, Matrix2, Matrix2x3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_singular_values_ $t>]() {
+                    let a = Matrix2::new(
+                        3 as $t, 0 as $t,
+                        4 as $t, 5 as $t,
+                    );
+                    let sigma = <Matrix2<$t> as ArgminSvd<$t>>::singular_values(&a).unwrap();
+                    assert_eq!(sigma.len(), 2);
+                    assert_relative_eq!(sigma[0], (45 as $t).sqrt(), epsilon = 1e-5);
+                    assert_relative_eq!(sigma[1], (5 as $t).sqrt(), epsilon = 1e-5);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_map_singular_values_ $t>]() {
+                    let a = Matrix2x3::new(
+                        1 as $t, 2 as $t, 3 as $t,
+                        4 as $t, 5 as $t, 6 as $t,
+                    );
+                    let res = a.map_singular_values(|s| 2 as $t * s).unwrap();
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert_relative_eq!(res[(i, j)], 2 as $t * a[(i, j)], epsilon = 1e-4);
+                        }
+                    }
+
+                    let a = DMatrix::from_row_slice(2, 2, &[3 as $t, 0 as $t, 0 as $t, 1 as $t]);
+                    let res = a.map_singular_values(|s| (s - 2 as $t).max(0 as $t)).unwrap();
+                    assert_relative_eq!(res[(0, 0)], 1 as $t, epsilon = 1e-5);
+                    assert_relative_eq!(res[(0, 1)], 0 as $t, epsilon = 1e-5);
+                    assert_relative_eq!(res[(1, 0)], 0 as $t, epsilon = 1e-5);
+                    assert_relative_eq!(res[(1, 1)], 0 as $t, epsilon = 1e-5);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
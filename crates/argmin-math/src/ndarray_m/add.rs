@@ -61,6 +61,28 @@ make_add!(f64);
 make_add!(Complex<f32>);
 make_add!(Complex<f64>);
 
+// Addition of complex vectors and real scalars
+macro_rules! make_add_real_scalar {
+    ($t:ty) => {
+        impl ArgminAdd<$t, Array1<Complex<$t>>> for Array1<Complex<$t>> {
+            #[inline]
+            fn add(&self, other: &$t) -> Array1<Complex<$t>> {
+                self.mapv(|a| a + *other)
+            }
+        }
+
+        impl ArgminAdd<Array1<Complex<$t>>, Array1<Complex<$t>>> for $t {
+            #[inline]
+            fn add(&self, other: &Array1<Complex<$t>>) -> Array1<Complex<$t>> {
+                other.mapv(|a| *self + a)
+            }
+        }
+    };
+}
+
+make_add_real_scalar!(f32);
+make_add_real_scalar!(f64);
+
 // All code that does not depend on a linked ndarray-linalg backend can still be tested as normal.
 // To avoid dublicating tests and to allow convenient testing of functionality that does not need ndarray-linalg the tests are still included here.
 // The tests expect the name for the crate containing the tested functions to be argmin_math
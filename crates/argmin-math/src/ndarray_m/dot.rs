@@ -140,6 +140,26 @@ make_dot_ndarray!(u64);
 make_dot_ndarray!(f32);
 make_dot_ndarray!(f64);
 
+// Real-valued inner product `Re(a^H b)` of complex vectors, which corresponds to the inner product
+// of the vectors interpreted as real vectors of twice the length.
+macro_rules! make_real_dot_complex {
+    ($t:ty) => {
+        impl ArgminDot<Array1<Complex<$t>>, $t> for Array1<Complex<$t>> {
+            #[inline]
+            fn dot(&self, other: &Array1<Complex<$t>>) -> $t {
+                assert_eq!(self.len(), other.len());
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| a.re * b.re + a.im * b.im)
+                    .sum()
+            }
+        }
+    };
+}
+
+make_real_dot_complex!(f32);
+make_real_dot_complex!(f64);
+
 // All code that does not depend on a linked ndarray-linalg backend can still be tested as normal.
 // To avoid dublicating tests and to allow convenient testing of functionality that does not need ndarray-linalg the tests are still included here.
 // The tests expect the name for the crate containing the tested functions to be argmin_math
@@ -5,20 +5,29 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::solve::is_hermitian;
 use crate::ArgminInv;
 use crate::Error;
 use ndarray::Array2;
-use ndarray_linalg::Inverse;
+use ndarray_linalg::{Inverse, InverseC};
 use num_complex::Complex;
 
 macro_rules! make_inv {
     ($t:ty) => {
+        /// Computes the inverse via a Cholesky decomposition if the matrix is Hermitian
+        /// (symmetric) and positive definite and via an LU decomposition with partial pivoting
+        /// otherwise.
         impl ArgminInv<Array2<$t>> for Array2<$t>
         where
             Array2<$t>: Inverse,
         {
             #[inline]
             fn inv(&self) -> Result<Array2<$t>, Error> {
+                if is_hermitian(self) {
+                    if let Ok(inv) = self.invc() {
+                        return Ok(inv);
+                    }
+                }
                 Ok(<Self as Inverse>::inv(&self)?)
             }
         }
@@ -7,6 +7,7 @@
 
 use crate::ArgminMinMax;
 use ndarray::{Array1, Array2};
+use num_complex::Complex;
 
 macro_rules! make_minmax {
     ($t:ty) => {
@@ -77,6 +78,35 @@ make_minmax!(u64);
 make_minmax!(f32);
 make_minmax!(f64);
 
+// Complex numbers are not ordered; the minimum and maximum are selected separately for the real
+// and imaginary parts (consistent with `ArgminSignum` and `ArgminL1Norm` of complex vectors).
+macro_rules! make_minmax_complex {
+    ($t:ty) => {
+        impl ArgminMinMax for Array1<Complex<$t>> {
+            #[inline]
+            fn min(x: &Self, y: &Self) -> Array1<Complex<$t>> {
+                assert_eq!(x.shape(), y.shape());
+                x.iter()
+                    .zip(y)
+                    .map(|(a, b)| Complex::new(a.re.min(b.re), a.im.min(b.im)))
+                    .collect()
+            }
+
+            #[inline]
+            fn max(x: &Self, y: &Self) -> Array1<Complex<$t>> {
+                assert_eq!(x.shape(), y.shape());
+                x.iter()
+                    .zip(y)
+                    .map(|(a, b)| Complex::new(a.re.max(b.re), a.im.max(b.im)))
+                    .collect()
+            }
+        }
+    };
+}
+
+make_minmax_complex!(f32);
+make_minmax_complex!(f64);
+
 // All code that does not depend on a linked ndarray-linalg backend can still be tested as normal.
 // To avoid dublicating tests and to allow convenient testing of functionality that does not need ndarray-linalg the tests are still included here.
 // The tests expect the name for the crate containing the tested functions to be argmin_math
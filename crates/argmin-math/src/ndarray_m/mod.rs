@@ -8,6 +8,7 @@
 #![allow(unused_imports)]
 
 mod add;
+mod addassign;
 mod conj;
 mod div;
 mod dot;
@@ -16,17 +17,26 @@ mod eye;
 mod inv;
 mod l1norm;
 mod l2norm;
+mod linfnorm;
 mod minmax;
 mod mul;
+mod mulassign;
 mod random;
 mod scaledadd;
+mod scaledaddassign;
 mod scaledsub;
 mod signum;
+#[cfg(feature = "ndarray-linalg_0_16")]
+mod solve;
 mod sub;
+mod subassign;
+#[cfg(feature = "ndarray-linalg_0_16")]
+mod svd;
 mod transpose;
 mod zero;
 
 pub use add::*;
+pub use addassign::*;
 pub use conj::*;
 pub use div::*;
 pub use dot::*;
@@ -35,11 +45,19 @@ pub use eye::*;
 pub use inv::*;
 pub use l1norm::*;
 pub use l2norm::*;
+pub use linfnorm::*;
 pub use minmax::*;
 pub use mul::*;
+pub use mulassign::*;
 pub use scaledadd::*;
+pub use scaledaddassign::*;
 pub use scaledsub::*;
 pub use signum::*;
+#[cfg(feature = "ndarray-linalg_0_16")]
+pub use solve::*;
 pub use sub::*;
+pub use subassign::*;
+#[cfg(feature = "ndarray-linalg_0_16")]
+pub use svd::*;
 pub use transpose::*;
 pub use zero::*;
@@ -0,0 +1,80 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminMulAssign;
+use ndarray::{Array1, Array2};
+use num_complex::Complex;
+
+macro_rules! make_mulassign {
+    ($t:ty) => {
+        impl ArgminMulAssign<$t> for Array1<$t> {
+            #[inline]
+            fn mul_assign(&mut self, other: &$t) {
+                *self *= *other;
+            }
+        }
+
+        impl ArgminMulAssign<Array1<$t>> for Array1<$t> {
+            #[inline]
+            fn mul_assign(&mut self, other: &Array1<$t>) {
+                *self *= other;
+            }
+        }
+
+        impl ArgminMulAssign<Array2<$t>> for Array2<$t> {
+            #[inline]
+            fn mul_assign(&mut self, other: &Array2<$t>) {
+                *self *= other;
+            }
+        }
+
+        impl ArgminMulAssign<$t> for Array2<$t> {
+            #[inline]
+            fn mul_assign(&mut self, other: &$t) {
+                *self *= *other;
+            }
+        }
+    };
+}
+
+make_mulassign!(i8);
+make_mulassign!(i16);
+make_mulassign!(i32);
+make_mulassign!(i64);
+make_mulassign!(u8);
+make_mulassign!(u16);
+make_mulassign!(u32);
+make_mulassign!(u64);
+make_mulassign!(f32);
+make_mulassign!(f64);
+make_mulassign!(Complex<f32>);
+make_mulassign!(Complex<f64>);
+
+// Multiplication of complex vectors with real scalars
+macro_rules! make_mulassign_real_scalar {
+    ($t:ty) => {
+        impl ArgminMulAssign<$t> for Array1<Complex<$t>> {
+            #[inline]
+            fn mul_assign(&mut self, other: &$t) {
+                self.mapv_inplace(|a| a * *other);
+            }
+        }
+    };
+}
+
+make_mulassign_real_scalar!(f32);
+make_mulassign_real_scalar!(f64);
+
+// All code that does not depend on a linked ndarray-linalg backend can still be tested as normal.
+// To avoid dublicating tests and to allow convenient testing of functionality that does not need ndarray-linalg the tests are still included here.
+// The tests expect the name for the crate containing the tested functions to be argmin_math
+#[cfg(test)]
+use crate as argmin_math;
+include!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/ndarray-tests-src/mulassign.rs"
+));
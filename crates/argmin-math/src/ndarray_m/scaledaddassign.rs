@@ -0,0 +1,66 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminScaledAddAssign;
+use ndarray::{Array1, Array2};
+use num_complex::Complex;
+
+macro_rules! make_scaledaddassign {
+    ($t:ty) => {
+        impl ArgminScaledAddAssign<Array1<$t>, $t> for Array1<$t> {
+            #[inline]
+            fn scaled_add_assign(&mut self, factor: &$t, vec: &Array1<$t>) {
+                Array1::scaled_add(self, *factor, vec);
+            }
+        }
+
+        impl ArgminScaledAddAssign<Array2<$t>, $t> for Array2<$t> {
+            #[inline]
+            fn scaled_add_assign(&mut self, factor: &$t, vec: &Array2<$t>) {
+                Array2::scaled_add(self, *factor, vec);
+            }
+        }
+    };
+}
+
+make_scaledaddassign!(i8);
+make_scaledaddassign!(i16);
+make_scaledaddassign!(i32);
+make_scaledaddassign!(i64);
+make_scaledaddassign!(u8);
+make_scaledaddassign!(u16);
+make_scaledaddassign!(u32);
+make_scaledaddassign!(u64);
+make_scaledaddassign!(f32);
+make_scaledaddassign!(f64);
+make_scaledaddassign!(Complex<f32>);
+make_scaledaddassign!(Complex<f64>);
+
+// Scaled addition of complex vectors with real factors
+macro_rules! make_scaledaddassign_real_scalar {
+    ($t:ty) => {
+        impl ArgminScaledAddAssign<Array1<Complex<$t>>, $t> for Array1<Complex<$t>> {
+            #[inline]
+            fn scaled_add_assign(&mut self, factor: &$t, vec: &Array1<Complex<$t>>) {
+                Array1::scaled_add(self, Complex::new(*factor, 0 as $t), vec);
+            }
+        }
+    };
+}
+
+make_scaledaddassign_real_scalar!(f32);
+make_scaledaddassign_real_scalar!(f64);
+
+// All code that does not depend on a linked ndarray-linalg backend can still be tested as normal.
+// To avoid dublicating tests and to allow convenient testing of functionality that does not need ndarray-linalg the tests are still included here.
+// The tests expect the name for the crate containing the tested functions to be argmin_math
+#[cfg(test)]
+use crate as argmin_math;
+include!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/ndarray-tests-src/scaledaddassign.rs"
+));
@@ -0,0 +1,64 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminSolve;
+use crate::Error;
+use ndarray::{Array1, Array2};
+use ndarray_linalg::{Scalar, Solve, SolveC};
+use num_complex::Complex;
+use num_traits::Float;
+
+/// Returns `true` if `a` is square and Hermitian (symmetric in the real case) up to rounding
+/// errors.
+pub(crate) fn is_hermitian<A: Scalar>(a: &Array2<A>) -> bool {
+    if !a.is_square() {
+        return false;
+    }
+    let tol = A::real(16.0) * A::Real::epsilon();
+    let n = a.nrows();
+    (0..n).all(|i| {
+        (0..=i).all(|j| {
+            let (aij, aji) = (a[(i, j)], a[(j, i)]);
+            (aij - aji.conj()).abs() <= tol * (aij.abs() + aji.abs())
+        })
+    })
+}
+
+macro_rules! make_solve {
+    ($t:ty) => {
+        /// Solves the linear system via a Cholesky decomposition if the matrix is Hermitian
+        /// (symmetric) and positive definite, which is the case for instance for Hessians close to
+        /// a minimum and for the normal equations of least squares problems. Otherwise an LU
+        /// decomposition with partial pivoting is used.
+        impl ArgminSolve<Array1<$t>, Array1<$t>> for Array2<$t> {
+            #[inline]
+            fn solve(&self, b: &Array1<$t>) -> Result<Array1<$t>, Error> {
+                if is_hermitian(self) {
+                    if let Ok(x) = self.solvec(b) {
+                        return Ok(x);
+                    }
+                }
+                Ok(<Self as Solve<$t>>::solve(self, b)?)
+            }
+        }
+    };
+}
+
+make_solve!(f32);
+make_solve!(f64);
+make_solve!(Complex<f32>);
+make_solve!(Complex<f64>);
+
+// All code that does not depend on a linked ndarray-linalg backend can still be tested as normal.
+// To avoid dublicating tests and to allow convenient testing of functionality that does not need ndarray-linalg the tests are still included here.
+// The tests expect the name for the crate containing the tested functions to be argmin_math
+#[cfg(test)]
+use crate as argmin_math;
+include!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/ndarray-tests-src/solve.rs"
+));
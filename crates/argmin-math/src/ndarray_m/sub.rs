@@ -61,6 +61,28 @@ make_sub!(f64);
 make_sub!(Complex<f32>);
 make_sub!(Complex<f64>);
 
+// Subtraction of complex vectors and real scalars
+macro_rules! make_sub_real_scalar {
+    ($t:ty) => {
+        impl ArgminSub<$t, Array1<Complex<$t>>> for Array1<Complex<$t>> {
+            #[inline]
+            fn sub(&self, other: &$t) -> Array1<Complex<$t>> {
+                self.mapv(|a| a - *other)
+            }
+        }
+
+        impl ArgminSub<Array1<Complex<$t>>, Array1<Complex<$t>>> for $t {
+            #[inline]
+            fn sub(&self, other: &Array1<Complex<$t>>) -> Array1<Complex<$t>> {
+                other.mapv(|a| *self - a)
+            }
+        }
+    };
+}
+
+make_sub_real_scalar!(f32);
+make_sub_real_scalar!(f64);
+
 // All code that does not depend on a linked ndarray-linalg backend can still be tested as normal.
 // To avoid dublicating tests and to allow convenient testing of functionality that does not need ndarray-linalg the tests are still included here.
 // The tests expect the name for the crate containing the tested functions to be argmin_math
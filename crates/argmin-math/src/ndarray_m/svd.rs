@@ -0,0 +1,47 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminSvd, Error};
+use ndarray::{s, Array2, Axis};
+use ndarray_linalg::SVD;
+
+macro_rules! make_svd {
+    ($t:ty) => {
+        impl ArgminSvd<$t> for Array2<$t> {
+            #[inline]
+            fn singular_values(&self) -> Result<Vec<$t>, Error> {
+                let (_, sigma, _) = self.svd(false, false)?;
+                Ok(sigma.to_vec())
+            }
+
+            #[inline]
+            fn map_singular_values<G: FnMut($t) -> $t>(&self, mut f: G) -> Result<Self, Error> {
+                let (u, sigma, vt) = self.svd(true, true)?;
+                let (u, vt) = match (u, vt) {
+                    (Some(u), Some(vt)) => (u, vt),
+                    _ => return Err(anyhow::anyhow!("SVD did not return singular vectors")),
+                };
+                let k = sigma.len();
+                let mut us = u.slice(s![.., ..k]).to_owned();
+                for (mut col, &sigma) in us.axis_iter_mut(Axis(1)).zip(sigma.iter()) {
+                    col *= f(sigma);
+                }
+                Ok(us.dot(&vt.slice(s![..k, ..])))
+            }
+        }
+    };
+}
+
+make_svd!(f32);
+make_svd!(f64);
+
+#[cfg(test)]
+use crate as argmin_math;
+include!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/ndarray-tests-src/svd.rs"
+));
@@ -7,6 +7,7 @@
 
 use crate::ArgminL2Norm;
 use num_complex::Complex;
+use num_traits::Float;
 
 macro_rules! make_norm_unsigned {
     ($t:ty) => {
@@ -0,0 +1,74 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminScaledAddAssign;
+use num_complex::Complex;
+
+macro_rules! make_scaledaddassign {
+    ($t:ty) => {
+        impl ArgminScaledAddAssign<$t, $t> for $t {
+            #[inline]
+            fn scaled_add_assign(&mut self, factor: &$t, vec: &$t) {
+                *self += *factor * *vec;
+            }
+        }
+    };
+}
+
+make_scaledaddassign!(i8);
+make_scaledaddassign!(i16);
+make_scaledaddassign!(i32);
+make_scaledaddassign!(i64);
+make_scaledaddassign!(u8);
+make_scaledaddassign!(u16);
+make_scaledaddassign!(u32);
+make_scaledaddassign!(u64);
+make_scaledaddassign!(f32);
+make_scaledaddassign!(f64);
+make_scaledaddassign!(Complex<i8>);
+make_scaledaddassign!(Complex<i16>);
+make_scaledaddassign!(Complex<i32>);
+make_scaledaddassign!(Complex<i64>);
+make_scaledaddassign!(Complex<u8>);
+make_scaledaddassign!(Complex<u16>);
+make_scaledaddassign!(Complex<u32>);
+make_scaledaddassign!(Complex<u64>);
+make_scaledaddassign!(Complex<f32>);
+make_scaledaddassign!(Complex<f64>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scaledaddassign_ $t>]() {
+                    let mut a = 2 as $t;
+                    let b = 4 as $t;
+                    let c = 10 as $t;
+                    <$t as ArgminScaledAddAssign<$t, $t>>::scaled_add_assign(&mut a, &b, &c);
+                    assert_relative_eq!(42 as f64, a as f64, epsilon = f64::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
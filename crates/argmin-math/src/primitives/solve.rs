@@ -0,0 +1,50 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminSolve, Error};
+use num_complex::Complex;
+
+macro_rules! make_solve {
+    ($t:ty) => {
+        // scalar "systems" (1d solvers)
+        impl ArgminSolve<$t, $t> for $t {
+            #[inline]
+            fn solve(&self, b: &$t) -> Result<$t, Error> {
+                Ok(b / self)
+            }
+        }
+    };
+}
+
+make_solve!(f32);
+make_solve!(f64);
+make_solve!(Complex<f32>);
+make_solve!(Complex<f64>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_solve_ $t>]() {
+                    let a = 4 as $t;
+                    let b = 2 as $t;
+                    let res = <$t as ArgminSolve<$t, $t>>::solve(&a, &b).unwrap();
+                    assert_relative_eq!(0.5 as f64, res as f64, epsilon = f64::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
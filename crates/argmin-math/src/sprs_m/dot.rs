@@ -0,0 +1,114 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminDot;
+use ndarray::Array1;
+use sprs::CsMat;
+
+macro_rules! make_dot {
+    ($t:ty) => {
+        impl ArgminDot<Array1<$t>, Array1<$t>> for CsMat<$t> {
+            #[inline]
+            fn dot(&self, other: &Array1<$t>) -> Array1<$t> {
+                self * other
+            }
+        }
+
+        impl ArgminDot<CsMat<$t>, CsMat<$t>> for CsMat<$t> {
+            #[inline]
+            fn dot(&self, other: &CsMat<$t>) -> CsMat<$t> {
+                self * other
+            }
+        }
+
+        impl ArgminDot<$t, CsMat<$t>> for CsMat<$t> {
+            #[inline]
+            fn dot(&self, other: &$t) -> CsMat<$t> {
+                self.map(|x| x * other)
+            }
+        }
+
+        impl ArgminDot<CsMat<$t>, CsMat<$t>> for $t {
+            #[inline]
+            fn dot(&self, other: &CsMat<$t>) -> CsMat<$t> {
+                other.map(|x| x * self)
+            }
+        }
+    };
+}
+
+make_dot!(f32);
+make_dot!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArgminTranspose;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+    use paste::item;
+    use sprs::TriMat;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_mat_vec_ $t>]() {
+                    let mut a = TriMat::new((3, 3));
+                    a.add_triplet(0, 0, 1 as $t);
+                    a.add_triplet(0, 2, 2 as $t);
+                    a.add_triplet(1, 1, 3 as $t);
+                    a.add_triplet(2, 0, 4 as $t);
+                    let a: CsMat<$t> = a.to_csr();
+                    let b = array![1 as $t, 2 as $t, 3 as $t];
+                    let res = <CsMat<$t> as ArgminDot<Array1<$t>, Array1<$t>>>::dot(&a, &b);
+                    let target = array![7 as $t, 6 as $t, 4 as $t];
+                    for i in 0..3 {
+                        assert_relative_eq!(res[i] as f64, target[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_mat_mat_ $t>]() {
+                    let mut a = TriMat::new((3, 2));
+                    a.add_triplet(0, 0, 1 as $t);
+                    a.add_triplet(1, 1, 2 as $t);
+                    a.add_triplet(2, 0, 3 as $t);
+                    let a: CsMat<$t> = a.to_csr();
+                    // J^T J with mixed storage orders
+                    let at = <CsMat<$t> as ArgminTranspose<CsMat<$t>>>::t(a.clone());
+                    let res = <CsMat<$t> as ArgminDot<CsMat<$t>, CsMat<$t>>>::dot(&at, &a);
+                    let target = [[10 as $t, 0 as $t], [0 as $t, 4 as $t]];
+                    let res = res.to_dense();
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(res[(i, j)] as f64, target[i][j] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_mat_scalar_ $t>]() {
+                    let a: CsMat<$t> = CsMat::new((2, 2), vec![0, 1, 2], vec![1, 0], vec![1 as $t, 2 as $t]);
+                    let res = <CsMat<$t> as ArgminDot<$t, CsMat<$t>>>::dot(&a, &(2 as $t));
+                    let res2 = <$t as ArgminDot<CsMat<$t>, CsMat<$t>>>::dot(&(2 as $t), &a);
+                    assert_eq!(res.nnz(), 2);
+                    assert_eq!(res, res2);
+                    assert_relative_eq!(*res.get(0, 1).unwrap() as f64, 2.0, epsilon = f64::EPSILON);
+                    assert_relative_eq!(*res.get(1, 0).unwrap() as f64, 4.0, epsilon = f64::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
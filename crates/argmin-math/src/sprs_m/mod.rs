@@ -0,0 +1,20 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#![allow(unused_imports)]
+
+mod dot;
+mod eye;
+mod mul;
+mod solve;
+mod transpose;
+
+pub use dot::*;
+pub use eye::*;
+pub use mul::*;
+pub use solve::*;
+pub use transpose::*;
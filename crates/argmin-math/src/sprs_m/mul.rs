@@ -0,0 +1,56 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminMul;
+use sprs::CsMat;
+
+macro_rules! make_mul {
+    ($t:ty) => {
+        impl ArgminMul<$t, CsMat<$t>> for CsMat<$t> {
+            #[inline]
+            fn mul(&self, other: &$t) -> CsMat<$t> {
+                self.map(|x| x * other)
+            }
+        }
+
+        impl ArgminMul<CsMat<$t>, CsMat<$t>> for $t {
+            #[inline]
+            fn mul(&self, other: &CsMat<$t>) -> CsMat<$t> {
+                other.map(|x| x * self)
+            }
+        }
+    };
+}
+
+make_mul!(f32);
+make_mul!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_mul_scalar_ $t>]() {
+                    let a: CsMat<$t> = CsMat::new((2, 2), vec![0, 1, 2], vec![1, 0], vec![1 as $t, 2 as $t]);
+                    let res = <CsMat<$t> as ArgminMul<$t, CsMat<$t>>>::mul(&a, &(3 as $t));
+                    let res2 = <$t as ArgminMul<CsMat<$t>, CsMat<$t>>>::mul(&(3 as $t), &a);
+                    assert_eq!(res, res2);
+                    assert_relative_eq!(*res.get(0, 1).unwrap() as f64, 3.0, epsilon = f64::EPSILON);
+                    assert_relative_eq!(*res.get(1, 0).unwrap() as f64, 6.0, epsilon = f64::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,131 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminSolve, Error};
+use ndarray::Array1;
+use sprs::{CsMat, SymmetryCheck};
+use sprs_ldl::Ldl;
+use std::fmt;
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+struct ShapeError {
+    shape: (usize, usize),
+    len: usize,
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Cannot solve linear system with matrix of shape {:?} and right-hand side of length {}",
+            self.shape, self.len
+        )
+    }
+}
+
+macro_rules! make_solve {
+    ($t:ty) => {
+        /// Solves the linear system via a sparse LDL^T decomposition with a fill-in reducing
+        /// ordering (reverse Cuthill-McKee).
+        ///
+        /// The matrix is assumed to be symmetric, which is not checked.
+        impl ArgminSolve<Array1<$t>, Array1<$t>> for CsMat<$t> {
+            #[inline]
+            fn solve(&self, b: &Array1<$t>) -> Result<Array1<$t>, Error> {
+                if self.rows() != self.cols() || self.rows() != b.len() {
+                    return Err(ShapeError {
+                        shape: self.shape(),
+                        len: b.len(),
+                    }
+                    .into());
+                }
+                let ldl = Ldl::new()
+                    .check_symmetry(SymmetryCheck::DontCheckSymmetry)
+                    .numeric(self.view())?;
+                Ok(ldl.solve(b))
+            }
+        }
+    };
+}
+
+make_solve!(f32);
+make_solve!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::array;
+    use paste::item;
+    use sprs::TriMat;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_solve_ $t>]() {
+                    // Tridiagonal matrix of a 1D Laplacian
+                    let n = 5;
+                    let mut a = TriMat::new((n, n));
+                    for i in 0..n {
+                        a.add_triplet(i, i, 2 as $t);
+                        if i > 0 {
+                            a.add_triplet(i, i - 1, -1 as $t);
+                            a.add_triplet(i - 1, i, -1 as $t);
+                        }
+                    }
+                    let a: CsMat<$t> = a.to_csc();
+                    let target = array![1 as $t, 2 as $t, 3 as $t, 4 as $t, 5 as $t];
+                    let b = &a * &target;
+                    let res = <CsMat<$t> as ArgminSolve<Array1<$t>, Array1<$t>>>::solve(&a, &b).unwrap();
+                    for i in 0..n {
+                        assert_relative_eq!(res[i], target[i], epsilon = $t::EPSILON.sqrt());
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_indefinite_ $t>]() {
+                    let a: CsMat<$t> = CsMat::new((2, 2), vec![0, 2, 4], vec![0, 1, 0, 1], vec![1 as $t, 2 as $t, 2 as $t, 1 as $t]);
+                    let b = array![3 as $t, 3 as $t];
+                    let res = <CsMat<$t> as ArgminSolve<Array1<$t>, Array1<$t>>>::solve(&a, &b).unwrap();
+                    assert_relative_eq!(res[0], 1 as $t, epsilon = $t::EPSILON.sqrt());
+                    assert_relative_eq!(res[1], 1 as $t, epsilon = $t::EPSILON.sqrt());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_singular_ $t>]() {
+                    let a: CsMat<$t> = CsMat::new((2, 2), vec![0, 2, 4], vec![0, 1, 0, 1], vec![1 as $t, 1 as $t, 1 as $t, 1 as $t]);
+                    let b = array![1 as $t, 2 as $t];
+                    assert!(<CsMat<$t> as ArgminSolve<Array1<$t>, Array1<$t>>>::solve(&a, &b).is_err());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_shape_error_ $t>]() {
+                    let a: CsMat<$t> = CsMat::eye(2);
+                    let b = array![1 as $t, 2 as $t, 3 as $t];
+                    let err = <CsMat<$t> as ArgminSolve<Array1<$t>, Array1<$t>>>::solve(&a, &b)
+                        .unwrap_err()
+                        .downcast::<ShapeError>()
+                        .unwrap();
+                    assert_eq!(
+                        format!("{}", err),
+                        "Cannot solve linear system with matrix of shape (2, 2) and right-hand side of length 3"
+                    );
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,52 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminTranspose;
+use sprs::CsMat;
+
+macro_rules! make_transpose {
+    ($t:ty) => {
+        // The transpose is obtained without copying by switching between compressed sparse row
+        // and column storage.
+        impl ArgminTranspose<CsMat<$t>> for CsMat<$t> {
+            #[inline]
+            fn t(self) -> CsMat<$t> {
+                self.transpose_into()
+            }
+        }
+    };
+}
+
+make_transpose!(f32);
+make_transpose!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_transpose_ $t>]() {
+                    let a: CsMat<$t> = CsMat::new((2, 3), vec![0, 1, 3], vec![2, 0, 1], vec![1 as $t, 2 as $t, 3 as $t]);
+                    let res = <CsMat<$t> as ArgminTranspose<CsMat<$t>>>::t(a);
+                    assert_eq!(res.shape(), (3, 2));
+                    assert_relative_eq!(*res.get(2, 0).unwrap() as f64, 1.0, epsilon = f64::EPSILON);
+                    assert_relative_eq!(*res.get(0, 1).unwrap() as f64, 2.0, epsilon = f64::EPSILON);
+                    assert_relative_eq!(*res.get(1, 1).unwrap() as f64, 3.0, epsilon = f64::EPSILON);
+                    assert!(res.get(0, 0).is_none());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
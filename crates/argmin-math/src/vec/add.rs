@@ -6,6 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminAdd;
+use alloc::vec::Vec;
+use num_complex::Complex;
 
 macro_rules! make_add {
     ($t:ty) => {
@@ -82,6 +84,38 @@ make_add!(u32);
 make_add!(u64);
 make_add!(f32);
 make_add!(f64);
+make_add!(Complex<i8>);
+make_add!(Complex<i16>);
+make_add!(Complex<i32>);
+make_add!(Complex<i64>);
+make_add!(Complex<u8>);
+make_add!(Complex<u16>);
+make_add!(Complex<u32>);
+make_add!(Complex<u64>);
+make_add!(Complex<f32>);
+make_add!(Complex<f64>);
+
+// Addition of complex vectors and real scalars
+macro_rules! make_add_real_scalar {
+    ($t:ty) => {
+        impl ArgminAdd<$t, Vec<Complex<$t>>> for Vec<Complex<$t>> {
+            #[inline]
+            fn add(&self, other: &$t) -> Vec<Complex<$t>> {
+                self.iter().map(|a| a + other).collect()
+            }
+        }
+
+        impl ArgminAdd<Vec<Complex<$t>>, Vec<Complex<$t>>> for $t {
+            #[inline]
+            fn add(&self, other: &Vec<Complex<$t>>) -> Vec<Complex<$t>> {
+                other.iter().map(|a| self + a).collect()
+            }
+        }
+    };
+}
+
+make_add_real_scalar!(f32);
+make_add_real_scalar!(f64);
 
 #[cfg(test)]
 mod tests {
@@ -261,4 +295,52 @@ mod tests {
     make_test!(u64);
     make_test!(f32);
     make_test!(f64);
+
+    macro_rules! make_test_real_scalar {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_add_vec_complex_real_scalar_ $t>]() {
+                    let a = vec![Complex::new(5 as $t, 3 as $t), Complex::new(8 as $t, 2 as $t)];
+                    let b = 2 as $t;
+                    let target = vec![Complex::new(7 as $t, 3 as $t), Complex::new(10 as $t, 2 as $t)];
+                    let target2 = vec![Complex::new(7 as $t, 3 as $t), Complex::new(10 as $t, 2 as $t)];
+                    let res = <Vec<Complex<$t>> as ArgminAdd<$t, Vec<Complex<$t>>>>::add(&a, &b);
+                    let res2 = <$t as ArgminAdd<Vec<Complex<$t>>, Vec<Complex<$t>>>>::add(&b, &a);
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target2[i].re as f64, res2[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target2[i].im as f64, res2[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+        };
+    }
+
+    make_test_real_scalar!(f32);
+    make_test_real_scalar!(f64);
+
+    macro_rules! make_test_complex {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_add_vec_vec_complex_ $t>]() {
+                    let a = vec![Complex::new(1 as $t, 4 as $t), Complex::new(2 as $t, 5 as $t)];
+                    let b = vec![Complex::new(3 as $t, 1 as $t), Complex::new(6 as $t, 2 as $t)];
+                    let target = vec![Complex::new(4 as $t, 5 as $t), Complex::new(8 as $t, 7 as $t)];
+                    let res = <Vec<Complex<$t>> as ArgminAdd<Vec<Complex<$t>>, Vec<Complex<$t>>>>::add(&a, &b);
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+        };
+    }
+
+    make_test_complex!(i32);
+    make_test_complex!(u32);
+    make_test_complex!(f32);
+    make_test_complex!(f64);
 }
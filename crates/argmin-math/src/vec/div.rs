@@ -6,6 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminDiv;
+use alloc::vec::Vec;
 use num_complex::Complex;
 
 macro_rules! make_div {
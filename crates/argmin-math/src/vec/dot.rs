@@ -7,14 +7,25 @@
 
 use crate::ArgminDot;
 use crate::ArgminTranspose;
+use alloc::vec;
+use alloc::vec::Vec;
 use num_complex::Complex;
 
 macro_rules! make_dot_vec {
     ($t:ty) => {
+        make_dot_vec!(
+            $t,
+            |a: &[$t], b: &[$t]| a.iter().zip(b.iter()).map(|(a, b)| a * b).sum::<$t>(),
+            |_: &[Vec<$t>], _: &[Vec<$t>]| -> Option<Vec<Vec<$t>>> { None }
+        );
+    };
+    // `$dot` computes the dot product of two slices, `$matmul` optionally computes a matrix
+    // product (`None` falls back to the generic implementation).
+    ($t:ty, $dot:expr, $matmul:expr) => {
         impl ArgminDot<Vec<$t>, $t> for Vec<$t> {
             #[inline]
             fn dot(&self, other: &Vec<$t>) -> $t {
-                self.iter().zip(other.iter()).map(|(a, b)| a * b).sum()
+                ($dot)(self, other)
             }
         }
 
@@ -51,6 +62,9 @@ macro_rules! make_dot_vec {
         impl ArgminDot<Vec<Vec<$t>>, Vec<Vec<$t>>> for Vec<Vec<$t>> {
             #[inline]
             fn dot(&self, other: &Vec<Vec<$t>>) -> Vec<Vec<$t>> {
+                if let Some(out) = ($matmul)(self, other) {
+                    return out;
+                }
                 // Would be more efficient if this wasn't necessary!
                 let other = other.clone().t();
                 let sr = self.len();
@@ -94,8 +108,14 @@ macro_rules! make_dot_vec {
     };
 }
 
+#[cfg(not(feature = "blas"))]
 make_dot_vec!(f32);
+#[cfg(not(feature = "blas"))]
 make_dot_vec!(f64);
+#[cfg(feature = "blas")]
+make_dot_vec!(f32, crate::blas::dot_f32, crate::blas::matmul_f32);
+#[cfg(feature = "blas")]
+make_dot_vec!(f64, crate::blas::dot_f64, crate::blas::matmul_f64);
 make_dot_vec!(i8);
 make_dot_vec!(i16);
 make_dot_vec!(i32);
@@ -115,6 +135,25 @@ make_dot_vec!(Complex<u16>);
 make_dot_vec!(Complex<u32>);
 make_dot_vec!(Complex<u64>);
 
+// Real-valued inner product `Re(a^H b)` of complex vectors, which corresponds to the inner product
+// of the vectors interpreted as real vectors of twice the length.
+macro_rules! make_real_dot_complex {
+    ($t:ty) => {
+        impl ArgminDot<Vec<Complex<$t>>, $t> for Vec<Complex<$t>> {
+            #[inline]
+            fn dot(&self, other: &Vec<Complex<$t>>) -> $t {
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| a.re * b.re + a.im * b.im)
+                    .sum()
+            }
+        }
+    };
+}
+
+make_real_dot_complex!(f32);
+make_real_dot_complex!(f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -557,4 +596,32 @@ mod tests {
     make_test!(u64);
     make_test!(f32);
     make_test!(f64);
+
+    macro_rules! make_test_real {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_vec_vec_complex_real_ $t>]() {
+                    let a = vec![
+                        Complex::new(2 as $t, 2 as $t),
+                        Complex::new(5 as $t, 2 as $t),
+                        Complex::new(3 as $t, 2 as $t),
+                    ];
+                    let b = vec![
+                        Complex::new(5 as $t, 3 as $t),
+                        Complex::new(2 as $t, 4 as $t),
+                        Complex::new(8 as $t, 4 as $t),
+                    ];
+                    let res: $t = a.dot(&b);
+                    let target = (a[0].conj() * b[0] + a[1].conj() * b[1] + a[2].conj() * b[2]).re;
+                    assert_relative_eq!(res as f64, target as f64, epsilon = f64::EPSILON);
+                    let norm: $t = a.dot(&a);
+                    assert_relative_eq!(norm as f64, 50.0, epsilon = f64::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test_real!(f32);
+    make_test_real!(f64);
 }
@@ -6,6 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminEye;
+use alloc::vec;
+use alloc::vec::Vec;
 
 macro_rules! make_eye {
     ($t:ty) => {
@@ -0,0 +1,140 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminInv, Error};
+use alloc::vec;
+use alloc::vec::Vec;
+
+macro_rules! make_inv {
+    ($t:ty) => {
+        /// Computes the inverse via Gauss-Jordan elimination with partial pivoting.
+        ///
+        /// This is intended for small, dense matrices, for instance to compute an initial inverse
+        /// Hessian for quasi-Newton methods.
+        impl ArgminInv<Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn inv(&self) -> Result<Vec<Vec<$t>>, Error> {
+                let n = self.len();
+                if self.iter().any(|row| row.len() != n) {
+                    return Err(anyhow::anyhow!("Cannot invert non-square matrix"));
+                }
+                let mut a = self.clone();
+                let mut out = vec![vec![0.0 as $t; n]; n];
+                for (i, row) in out.iter_mut().enumerate() {
+                    row[i] = 1.0;
+                }
+                for k in 0..n {
+                    let pivot = (k..n)
+                        .max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs()))
+                        .unwrap();
+                    let p = a[pivot][k];
+                    if p == 0.0 || !p.is_finite() {
+                        return Err(anyhow::anyhow!("Non-invertible matrix"));
+                    }
+                    a.swap(k, pivot);
+                    out.swap(k, pivot);
+                    for j in 0..n {
+                        a[k][j] /= p;
+                        out[k][j] /= p;
+                    }
+                    for i in (0..n).filter(|&i| i != k) {
+                        let factor = a[i][k];
+                        if factor == 0.0 {
+                            continue;
+                        }
+                        for j in 0..n {
+                            a[i][j] -= factor * a[k][j];
+                            out[i][j] -= factor * out[k][j];
+                        }
+                    }
+                }
+                Ok(out)
+            }
+        }
+    };
+}
+
+make_inv!(f32);
+make_inv!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_inv_ $t>]() {
+                    let a = vec![
+                        vec![2 as $t, 5 as $t],
+                        vec![1 as $t, 3 as $t],
+                    ];
+                    let target = vec![
+                        vec![3 as $t, -5 as $t],
+                        vec![-1 as $t, 2 as $t],
+                    ];
+                    let res = <Vec<Vec<$t>> as ArgminInv<Vec<Vec<$t>>>>::inv(&a).unwrap();
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(res[i][j], target[i][j], epsilon = 1e-5);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_inv_pivoting_ $t>]() {
+                    // Requires row exchanges since the first diagonal element is zero
+                    let a = vec![
+                        vec![0 as $t, 2 as $t, 1 as $t],
+                        vec![1 as $t, 1 as $t, 0 as $t],
+                        vec![2 as $t, 0 as $t, 3 as $t],
+                    ];
+                    let res = <Vec<Vec<$t>> as ArgminInv<Vec<Vec<$t>>>>::inv(&a).unwrap();
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            let prod: $t = (0..3).map(|k| a[i][k] * res[k][j]).sum();
+                            let target = if i == j { 1 as $t } else { 0 as $t };
+                            assert_relative_eq!(prod, target, epsilon = 1e-5);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_inv_singular_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t],
+                        vec![2 as $t, 4 as $t],
+                    ];
+                    let res = <Vec<Vec<$t>> as ArgminInv<Vec<Vec<$t>>>>::inv(&a);
+                    assert_eq!(res.unwrap_err().to_string(), "Non-invertible matrix");
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_inv_non_square_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t, 3 as $t],
+                        vec![2 as $t, 4 as $t, 5 as $t],
+                    ];
+                    let res = <Vec<Vec<$t>> as ArgminInv<Vec<Vec<$t>>>>::inv(&a);
+                    assert_eq!(res.unwrap_err().to_string(), "Cannot invert non-square matrix");
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -6,8 +6,10 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminL2Norm;
+use alloc::vec::Vec;
 use num_complex::Complex;
 use num_integer::Roots;
+use num_traits::Float;
 
 macro_rules! make_norm_float {
     ($t:ty) => {
@@ -0,0 +1,132 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminLInfNorm;
+use alloc::vec::Vec;
+use num_complex::Complex;
+
+macro_rules! make_linfnorm_unsigned {
+    ($t:ty) => {
+        impl ArgminLInfNorm<$t> for Vec<$t> {
+            #[inline]
+            fn linf_norm(&self) -> $t {
+                self.iter().copied().fold(0, |acc, a| acc.max(a))
+            }
+        }
+    };
+}
+
+macro_rules! make_linfnorm_signed {
+    ($t:ty) => {
+        impl ArgminLInfNorm<$t> for Vec<$t> {
+            #[inline]
+            fn linf_norm(&self) -> $t {
+                self.iter().fold(0, |acc, a| acc.max(a.abs()))
+            }
+        }
+    };
+}
+
+macro_rules! make_linfnorm_float {
+    ($t:ty) => {
+        impl ArgminLInfNorm<$t> for Vec<$t> {
+            #[inline]
+            fn linf_norm(&self) -> $t {
+                self.iter().fold(0.0, |acc, a| acc.max(a.abs()))
+            }
+        }
+    };
+}
+
+macro_rules! make_linfnorm_complex {
+    ($t:ty) => {
+        impl ArgminLInfNorm<$t> for Vec<Complex<$t>> {
+            #[inline]
+            fn linf_norm(&self) -> $t {
+                self.iter().fold(0.0, |acc, a| acc.max(a.norm()))
+            }
+        }
+    };
+}
+
+make_linfnorm_unsigned!(u8);
+make_linfnorm_unsigned!(u16);
+make_linfnorm_unsigned!(u32);
+make_linfnorm_unsigned!(u64);
+make_linfnorm_signed!(i8);
+make_linfnorm_signed!(i16);
+make_linfnorm_signed!(i32);
+make_linfnorm_signed!(i64);
+make_linfnorm_float!(f32);
+make_linfnorm_float!(f64);
+make_linfnorm_complex!(f32);
+make_linfnorm_complex!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_linfnorm_ $t>]() {
+                    let a = vec![3 as $t, 8 as $t, 4 as $t];
+                    let res = <Vec<$t> as ArgminLInfNorm<$t>>::linf_norm(&a);
+                    let target = 8 as $t;
+                    assert_relative_eq!(target as f64, res as f64, epsilon = f64::EPSILON);
+                }
+            }
+        };
+    }
+
+    macro_rules! make_test_signed {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_linfnorm_signed_ $t>]() {
+                    let a = vec![3 as $t, -8 as $t, 4 as $t];
+                    let res = <Vec<$t> as ArgminLInfNorm<$t>>::linf_norm(&a);
+                    let target = 8 as $t;
+                    assert_relative_eq!(target as f64, res as f64, epsilon = f64::EPSILON);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+
+    make_test_signed!(i8);
+    make_test_signed!(i16);
+    make_test_signed!(i32);
+    make_test_signed!(i64);
+    make_test_signed!(f32);
+    make_test_signed!(f64);
+
+    #[test]
+    fn test_linfnorm_complex() {
+        let a = vec![Complex::new(3.0f64, 4.0), Complex::new(-1.0, 2.0)];
+        assert_relative_eq!(a.linf_norm(), 5.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_linfnorm_empty() {
+        let a: Vec<f64> = vec![];
+        assert_eq!(a.linf_norm().to_ne_bytes(), 0.0f64.to_ne_bytes());
+    }
+}
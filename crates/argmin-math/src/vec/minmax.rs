@@ -6,6 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminMinMax;
+use alloc::vec::Vec;
+use num_complex::Complex;
 
 macro_rules! make_minmax {
     ($t:ty) => {
@@ -66,6 +68,37 @@ make_minmax!(u64);
 make_minmax!(f32);
 make_minmax!(f64);
 
+// Complex numbers are not ordered; the minimum and maximum are selected separately for the real
+// and imaginary parts (consistent with `ArgminSignum` and `ArgminL1Norm` of complex vectors).
+macro_rules! make_minmax_complex {
+    ($t:ty) => {
+        impl ArgminMinMax for Vec<Complex<$t>> {
+            fn min(x: &Self, y: &Self) -> Self {
+                assert!(!x.is_empty());
+                assert_eq!(x.len(), y.len());
+
+                x.iter()
+                    .zip(y.iter())
+                    .map(|(a, b)| Complex::new(a.re.min(b.re), a.im.min(b.im)))
+                    .collect()
+            }
+
+            fn max(x: &Self, y: &Self) -> Self {
+                assert!(!x.is_empty());
+                assert_eq!(x.len(), y.len());
+
+                x.iter()
+                    .zip(y.iter())
+                    .map(|(a, b)| Complex::new(a.re.max(b.re), a.im.max(b.im)))
+                    .collect()
+            }
+        }
+    };
+}
+
+make_minmax_complex!(f32);
+make_minmax_complex!(f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,4 +165,29 @@ mod tests {
     make_test!(u64);
     make_test!(f32);
     make_test!(f64);
+
+    macro_rules! make_test_complex {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_minmax_vec_complex_ $t>]() {
+                    let a = vec![Complex::new(1 as $t, 4 as $t), Complex::new(-2 as $t, 0 as $t)];
+                    let b = vec![Complex::new(2 as $t, 3 as $t), Complex::new(-3 as $t, 1 as $t)];
+                    let target_min = vec![Complex::new(1 as $t, 3 as $t), Complex::new(-3 as $t, 0 as $t)];
+                    let target_max = vec![Complex::new(2 as $t, 4 as $t), Complex::new(-2 as $t, 1 as $t)];
+                    let res_min = <Vec<Complex<$t>> as ArgminMinMax>::min(&a, &b);
+                    let res_max = <Vec<Complex<$t>> as ArgminMinMax>::max(&a, &b);
+                    for i in 0..2 {
+                        assert_relative_eq!(target_min[i].re as f64, res_min[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target_min[i].im as f64, res_min[i].im as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target_max[i].re as f64, res_max[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target_max[i].im as f64, res_max[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+        };
+    }
+
+    make_test_complex!(f32);
+    make_test_complex!(f64);
 }
@@ -6,6 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminMul;
+use alloc::vec::Vec;
 use num_complex::Complex;
 
 macro_rules! make_mul {
@@ -93,6 +94,28 @@ make_mul!(Complex<u64>);
 make_mul!(Complex<f32>);
 make_mul!(Complex<f64>);
 
+// Multiplication of complex vectors with real scalars
+macro_rules! make_mul_real_scalar {
+    ($t:ty) => {
+        impl ArgminMul<$t, Vec<Complex<$t>>> for Vec<Complex<$t>> {
+            #[inline]
+            fn mul(&self, other: &$t) -> Vec<Complex<$t>> {
+                self.iter().map(|a| a * other).collect()
+            }
+        }
+
+        impl ArgminMul<Vec<Complex<$t>>, Vec<Complex<$t>>> for $t {
+            #[inline]
+            fn mul(&self, other: &Vec<Complex<$t>>) -> Vec<Complex<$t>> {
+                other.iter().map(|a| a * self).collect()
+            }
+        }
+    };
+}
+
+make_mul_real_scalar!(f32);
+make_mul_real_scalar!(f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -422,4 +445,28 @@ mod tests {
     make_test!(u64);
     make_test!(f32);
     make_test!(f64);
+
+    macro_rules! make_test_real_scalar {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_mul_vec_complex_real_scalar_ $t>]() {
+                    let a = vec![Complex::new(5 as $t, 3 as $t), Complex::new(8 as $t, 2 as $t)];
+                    let b = 2 as $t;
+                    let target = vec![Complex::new(10 as $t, 6 as $t), Complex::new(16 as $t, 4 as $t)];
+                    let res = <Vec<Complex<$t>> as ArgminMul<$t, Vec<Complex<$t>>>>::mul(&a, &b);
+                    let res2 = <$t as ArgminMul<Vec<Complex<$t>>, Vec<Complex<$t>>>>::mul(&b, &a);
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].re as f64, res2[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res2[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+        };
+    }
+
+    make_test_real_scalar!(f32);
+    make_test_real_scalar!(f64);
 }
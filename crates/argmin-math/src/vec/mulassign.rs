@@ -0,0 +1,214 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminMulAssign;
+use alloc::vec::Vec;
+use num_complex::Complex;
+
+macro_rules! make_mulassign {
+    ($t:ty) => {
+        impl ArgminMulAssign<$t> for Vec<$t> {
+            #[inline]
+            fn mul_assign(&mut self, other: &$t) {
+                self.iter_mut().for_each(|a| *a *= *other);
+            }
+        }
+
+        impl ArgminMulAssign<Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn mul_assign(&mut self, other: &Vec<$t>) {
+                let n1 = self.len();
+                let n2 = other.len();
+                assert!(n1 > 0);
+                assert!(n2 > 0);
+                assert_eq!(n1, n2);
+                self.iter_mut()
+                    .zip(other.iter())
+                    .for_each(|(a, b)| *a *= *b);
+            }
+        }
+
+        impl ArgminMulAssign<Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn mul_assign(&mut self, other: &Vec<Vec<$t>>) {
+                let sr = self.len();
+                let or = other.len();
+                assert!(sr > 0);
+                // implicitly, or > 0
+                assert_eq!(sr, or);
+                let sc = self[0].len();
+                self.iter_mut().zip(other.iter()).for_each(|(a, b)| {
+                    assert_eq!(a.len(), sc);
+                    assert_eq!(b.len(), sc);
+                    <Vec<$t> as ArgminMulAssign<Vec<$t>>>::mul_assign(a, b)
+                });
+            }
+        }
+
+        impl ArgminMulAssign<$t> for Vec<Vec<$t>> {
+            #[inline]
+            fn mul_assign(&mut self, other: &$t) {
+                self.iter_mut()
+                    .for_each(|a| <Vec<$t> as ArgminMulAssign<$t>>::mul_assign(a, other));
+            }
+        }
+    };
+}
+
+make_mulassign!(i8);
+make_mulassign!(u8);
+make_mulassign!(i16);
+make_mulassign!(u16);
+make_mulassign!(i32);
+make_mulassign!(u32);
+make_mulassign!(i64);
+make_mulassign!(u64);
+make_mulassign!(f32);
+make_mulassign!(f64);
+make_mulassign!(Complex<i8>);
+make_mulassign!(Complex<u8>);
+make_mulassign!(Complex<i16>);
+make_mulassign!(Complex<u16>);
+make_mulassign!(Complex<i32>);
+make_mulassign!(Complex<u32>);
+make_mulassign!(Complex<i64>);
+make_mulassign!(Complex<u64>);
+make_mulassign!(Complex<f32>);
+make_mulassign!(Complex<f64>);
+
+// Multiplication of complex vectors with real scalars
+macro_rules! make_mulassign_real_scalar {
+    ($t:ty) => {
+        impl ArgminMulAssign<$t> for Vec<Complex<$t>> {
+            #[inline]
+            fn mul_assign(&mut self, other: &$t) {
+                self.iter_mut().for_each(|a| *a *= *other);
+            }
+        }
+    };
+}
+
+make_mulassign_real_scalar!(f32);
+make_mulassign_real_scalar!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_mulassign_vec_scalar_ $t>]() {
+                    let mut a = vec![1 as $t, 4 as $t, 8 as $t];
+                    let b = 2 as $t;
+                    let target = vec![2 as $t, 8 as $t, 16 as $t];
+                    <Vec<$t> as ArgminMulAssign<$t>>::mul_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, a[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_mulassign_vec_vec_ $t>]() {
+                    let mut a = vec![1 as $t, 4 as $t, 8 as $t];
+                    let b = vec![42 as $t, 10 as $t, 5 as $t];
+                    let target = vec![42 as $t, 40 as $t, 40 as $t];
+                    <Vec<$t> as ArgminMulAssign<Vec<$t>>>::mul_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, a[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_mulassign_vec_vec_panic_ $t>]() {
+                    let mut a = vec![1 as $t, 4 as $t, 8 as $t];
+                    let b = vec![1 as $t, 2 as $t];
+                    <Vec<$t> as ArgminMulAssign<Vec<$t>>>::mul_assign(&mut a, &b);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_mulassign_mat_mat_ $t>]() {
+                    let mut a = vec![vec![1 as $t, 4 as $t, 8 as $t], vec![1 as $t, 4 as $t, 8 as $t]];
+                    let b = vec![vec![42 as $t, 10 as $t, 5 as $t], vec![42 as $t, 10 as $t, 5 as $t]];
+                    let target = vec![vec![42 as $t, 40 as $t, 40 as $t], vec![42 as $t, 40 as $t, 40 as $t]];
+                    <Vec<Vec<$t>> as ArgminMulAssign<Vec<Vec<$t>>>>::mul_assign(&mut a, &b);
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(target[j][i] as f64, a[j][i] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_mulassign_mat_mat_panic_ $t>]() {
+                    let mut a = vec![vec![1 as $t, 4 as $t, 8 as $t], vec![1 as $t, 4 as $t, 8 as $t]];
+                    let b = vec![vec![42 as $t, 10 as $t, 5 as $t], vec![1 as $t, 2 as $t]];
+                    <Vec<Vec<$t>> as ArgminMulAssign<Vec<Vec<$t>>>>::mul_assign(&mut a, &b);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_mulassign_mat_scalar_ $t>]() {
+                    let mut a = vec![vec![1 as $t, 4 as $t, 8 as $t], vec![1 as $t, 4 as $t, 8 as $t]];
+                    let b = 2 as $t;
+                    let target = vec![vec![2 as $t, 8 as $t, 16 as $t], vec![2 as $t, 8 as $t, 16 as $t]];
+                    <Vec<Vec<$t>> as ArgminMulAssign<$t>>::mul_assign(&mut a, &b);
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(target[j][i] as f64, a[j][i] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+
+    macro_rules! make_test_real_scalar {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_mulassign_vec_complex_real_scalar_ $t>]() {
+                    let mut a = vec![Complex::new(5 as $t, 3 as $t), Complex::new(8 as $t, 2 as $t)];
+                    a.mul_assign(&(2 as $t));
+                    let target = vec![Complex::new(10 as $t, 6 as $t), Complex::new(16 as $t, 4 as $t)];
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i].re as f64, a[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, a[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+        };
+    }
+
+    make_test_real_scalar!(f32);
+    make_test_real_scalar!(f64);
+}
@@ -6,6 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminRandom;
+use alloc::vec::Vec;
 use rand::Rng;
 
 macro_rules! make_random {
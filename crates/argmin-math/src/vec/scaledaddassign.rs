@@ -0,0 +1,219 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminScaledAddAssign;
+use alloc::vec::Vec;
+use num_complex::Complex;
+
+macro_rules! make_scaledaddassign {
+    ($t:ty) => {
+        make_scaledaddassign!($t, |alpha: $t, x: &[$t], y: &mut [$t]| {
+            y.iter_mut()
+                .zip(x.iter())
+                .for_each(|(y, x)| *y += alpha * *x)
+        });
+    };
+    // `$axpy` adds `alpha * x` to `y`
+    ($t:ty, $axpy:expr) => {
+        impl ArgminScaledAddAssign<Vec<$t>, $t> for Vec<$t> {
+            #[inline]
+            fn scaled_add_assign(&mut self, factor: &$t, vec: &Vec<$t>) {
+                let n1 = self.len();
+                let n2 = vec.len();
+                assert!(n1 > 0);
+                assert!(n2 > 0);
+                assert_eq!(n1, n2);
+                ($axpy)(*factor, vec, self)
+            }
+        }
+
+        impl ArgminScaledAddAssign<Vec<Vec<$t>>, $t> for Vec<Vec<$t>> {
+            #[inline]
+            fn scaled_add_assign(&mut self, factor: &$t, vec: &Vec<Vec<$t>>) {
+                let sr = self.len();
+                let or = vec.len();
+                assert!(sr > 0);
+                // implicitly, or > 0
+                assert_eq!(sr, or);
+                let sc = self[0].len();
+                self.iter_mut().zip(vec.iter()).for_each(|(a, b)| {
+                    assert_eq!(a.len(), sc);
+                    assert_eq!(b.len(), sc);
+                    <Vec<$t> as ArgminScaledAddAssign<Vec<$t>, $t>>::scaled_add_assign(a, factor, b)
+                });
+            }
+        }
+    };
+}
+
+make_scaledaddassign!(i8);
+make_scaledaddassign!(u8);
+make_scaledaddassign!(i16);
+make_scaledaddassign!(u16);
+make_scaledaddassign!(i32);
+make_scaledaddassign!(u32);
+make_scaledaddassign!(i64);
+make_scaledaddassign!(u64);
+#[cfg(not(feature = "blas"))]
+make_scaledaddassign!(f32);
+#[cfg(not(feature = "blas"))]
+make_scaledaddassign!(f64);
+#[cfg(feature = "blas")]
+make_scaledaddassign!(f32, crate::blas::axpy_f32);
+#[cfg(feature = "blas")]
+make_scaledaddassign!(f64, crate::blas::axpy_f64);
+make_scaledaddassign!(Complex<i8>);
+make_scaledaddassign!(Complex<u8>);
+make_scaledaddassign!(Complex<i16>);
+make_scaledaddassign!(Complex<u16>);
+make_scaledaddassign!(Complex<i32>);
+make_scaledaddassign!(Complex<u32>);
+make_scaledaddassign!(Complex<i64>);
+make_scaledaddassign!(Complex<u64>);
+make_scaledaddassign!(Complex<f32>);
+make_scaledaddassign!(Complex<f64>);
+
+// Scaled addition of complex vectors with real factors
+macro_rules! make_scaledaddassign_real_scalar {
+    ($t:ty) => {
+        impl ArgminScaledAddAssign<Vec<Complex<$t>>, $t> for Vec<Complex<$t>> {
+            #[inline]
+            fn scaled_add_assign(&mut self, factor: &$t, vec: &Vec<Complex<$t>>) {
+                let n1 = self.len();
+                let n2 = vec.len();
+                assert!(n1 > 0);
+                assert!(n2 > 0);
+                assert_eq!(n1, n2);
+                self.iter_mut()
+                    .zip(vec.iter())
+                    .for_each(|(a, b)| *a += b * *factor);
+            }
+        }
+    };
+}
+
+make_scaledaddassign_real_scalar!(f32);
+make_scaledaddassign_real_scalar!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scaledaddassign_vec_ $t>]() {
+                    let mut a = vec![1 as $t, 2 as $t, 3 as $t];
+                    let b = 2 as $t;
+                    let c = vec![4 as $t, 5 as $t, 6 as $t];
+                    a.scaled_add_assign(&b, &c);
+                    let target = vec![9 as $t, 12 as $t, 15 as $t];
+                    for i in 0..3 {
+                        assert_relative_eq!(a[i] as f64, target[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scaledaddassign_vec_panic_ $t>]() {
+                    let mut a = vec![1 as $t, 2 as $t, 3 as $t];
+                    let b = 2 as $t;
+                    let c = vec![4 as $t, 5 as $t];
+                    a.scaled_add_assign(&b, &c);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_scaledaddassign_vec_long_ $t>]() {
+                    // Long enough to be computed via BLAS with the `blas` feature
+                    let mut a = vec![1 as $t; 100];
+                    let b = 2 as $t;
+                    let c: Vec<$t> = (0..100).map(|i| (i % 10) as $t).collect();
+                    a.scaled_add_assign(&b, &c);
+                    for i in 0..100 {
+                        assert_relative_eq!(a[i] as f64, (1 + 2 * (i % 10)) as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_scaledaddassign_mat_ $t>]() {
+                    let mut a = vec![vec![1 as $t, 2 as $t, 3 as $t], vec![4 as $t, 5 as $t, 6 as $t]];
+                    let b = 2 as $t;
+                    let c = vec![vec![4 as $t, 5 as $t, 6 as $t], vec![1 as $t, 2 as $t, 3 as $t]];
+                    a.scaled_add_assign(&b, &c);
+                    let target = vec![vec![9 as $t, 12 as $t, 15 as $t], vec![6 as $t, 9 as $t, 12 as $t]];
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(a[j][i] as f64, target[j][i] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scaledaddassign_mat_panic_ $t>]() {
+                    let mut a = vec![vec![1 as $t, 2 as $t, 3 as $t], vec![4 as $t, 5 as $t, 6 as $t]];
+                    let b = 2 as $t;
+                    let c = vec![vec![4 as $t, 5 as $t, 6 as $t]];
+                    a.scaled_add_assign(&b, &c);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+
+    macro_rules! make_test_real_scalar {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scaledaddassign_vec_complex_real_scalar_ $t>]() {
+                    let mut a = vec![Complex::new(5 as $t, 3 as $t), Complex::new(8 as $t, 2 as $t)];
+                    let b = vec![Complex::new(1 as $t, 2 as $t), Complex::new(3 as $t, 4 as $t)];
+                    a.scaled_add_assign(&(2 as $t), &b);
+                    let target = vec![Complex::new(7 as $t, 7 as $t), Complex::new(14 as $t, 10 as $t)];
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i].re as f64, a[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, a[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_scaledaddassign_vec_complex_real_scalar_panic_ $t>]() {
+                    let mut a = vec![Complex::new(5 as $t, 3 as $t), Complex::new(8 as $t, 2 as $t)];
+                    let b = vec![Complex::new(1 as $t, 2 as $t)];
+                    a.scaled_add_assign(&(2 as $t), &b);
+                }
+            }
+        };
+    }
+
+    make_test_real_scalar!(f32);
+    make_test_real_scalar!(f64);
+}
@@ -0,0 +1,207 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminSolve, Error};
+use alloc::vec::Vec;
+
+macro_rules! make_solve {
+    ($t:ty) => {
+        /// Solves the linear system via a Cholesky decomposition and two triangular solves if the
+        /// matrix is symmetric and positive definite and via Gaussian elimination with partial
+        /// pivoting otherwise.
+        impl ArgminSolve<Vec<$t>, Vec<$t>> for Vec<Vec<$t>> {
+            #[inline]
+            fn solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                let n = self.len();
+                if self.iter().any(|row| row.len() != n) || b.len() != n {
+                    return Err(anyhow::anyhow!(
+                        "Cannot solve linear system with matrix of shape ({}, {}) and right-hand side of length {}",
+                        n,
+                        self.first().map(|row| row.len()).unwrap_or(0),
+                        b.len()
+                    ));
+                }
+                // Symmetric up to rounding errors
+                let tol = 16.0 * <$t>::EPSILON;
+                let symmetric = (0..n).all(|i| {
+                    (0..i).all(|j| {
+                        let (aij, aji) = (self[i][j], self[j][i]);
+                        (aij - aji).abs() <= tol * (aij.abs() + aji.abs())
+                    })
+                });
+                if symmetric {
+                    if let Some(l) = cholesky(self) {
+                        // L z = b
+                        let mut x = b.clone();
+                        for i in 0..n {
+                            for k in 0..i {
+                                x[i] -= l[i][k] * x[k];
+                            }
+                            x[i] /= l[i][i];
+                        }
+                        // L^T x = z
+                        for i in (0..n).rev() {
+                            for k in (i + 1)..n {
+                                x[i] -= l[k][i] * x[k];
+                            }
+                            x[i] /= l[i][i];
+                        }
+                        return Ok(x);
+                    }
+                }
+                let mut a = self.clone();
+                let mut x = b.clone();
+                for k in 0..n {
+                    let pivot = (k..n)
+                        .max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs()))
+                        .unwrap();
+                    if a[pivot][k] == 0.0 || !a[pivot][k].is_finite() {
+                        return Err(anyhow::anyhow!("Singular matrix"));
+                    }
+                    a.swap(k, pivot);
+                    x.swap(k, pivot);
+                    for i in (k + 1)..n {
+                        let factor = a[i][k] / a[k][k];
+                        for j in k..n {
+                            a[i][j] -= factor * a[k][j];
+                        }
+                        x[i] -= factor * x[k];
+                    }
+                }
+                for i in (0..n).rev() {
+                    for j in (i + 1)..n {
+                        x[i] -= a[i][j] * x[j];
+                    }
+                    x[i] /= a[i][i];
+                }
+                Ok(x)
+            }
+        }
+    };
+}
+
+make_solve!(f32);
+make_solve!(f64);
+
+/// Returns the lower triangular Cholesky factor `L` with `A = L L^T` of a symmetric matrix, or
+/// `None` if the matrix is not positive definite.
+fn cholesky<T>(a: &[Vec<T>]) -> Option<Vec<Vec<T>>>
+where
+    T: num_traits::Float,
+{
+    let n = a.len();
+    let mut l = alloc::vec![alloc::vec![T::zero(); n]; n];
+    for j in 0..n {
+        let d = l[j][..j].iter().fold(a[j][j], |d, &ljk| d - ljk * ljk);
+        if d <= T::zero() || !d.is_finite() {
+            return None;
+        }
+        let d = d.sqrt();
+        l[j][j] = d;
+        for i in (j + 1)..n {
+            let s = l[i][..j]
+                .iter()
+                .zip(l[j][..j].iter())
+                .fold(a[i][j], |s, (&lik, &ljk)| s - lik * ljk);
+            l[i][j] = s / d;
+        }
+    }
+    Some(l)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_solve_spd_ $t>]() {
+                    let a = vec![
+                        vec![4 as $t, 1 as $t, 0 as $t],
+                        vec![1 as $t, 3 as $t, 1 as $t],
+                        vec![0 as $t, 1 as $t, 2 as $t],
+                    ];
+                    let x = vec![1 as $t, -2 as $t, 3 as $t];
+                    let b: Vec<$t> = a.iter().map(|row| row.iter().zip(x.iter()).map(|(a, x)| a * x).sum()).collect();
+                    let res = <Vec<Vec<$t>> as ArgminSolve<Vec<$t>, Vec<$t>>>::solve(&a, &b).unwrap();
+                    for i in 0..3 {
+                        assert_relative_eq!(res[i], x[i], epsilon = 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_general_ $t>]() {
+                    // Neither symmetric nor with nonzero first diagonal element
+                    let a = vec![
+                        vec![0 as $t, 2 as $t, 1 as $t],
+                        vec![1 as $t, 1 as $t, 0 as $t],
+                        vec![2 as $t, 0 as $t, 3 as $t],
+                    ];
+                    let x = vec![1 as $t, -2 as $t, 3 as $t];
+                    let b: Vec<$t> = a.iter().map(|row| row.iter().zip(x.iter()).map(|(a, x)| a * x).sum()).collect();
+                    let res = <Vec<Vec<$t>> as ArgminSolve<Vec<$t>, Vec<$t>>>::solve(&a, &b).unwrap();
+                    for i in 0..3 {
+                        assert_relative_eq!(res[i], x[i], epsilon = 1e-5);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_indefinite_ $t>]() {
+                    // Symmetric, but not positive definite
+                    let a = vec![
+                        vec![1 as $t, 2 as $t],
+                        vec![2 as $t, 1 as $t],
+                    ];
+                    let b = vec![3 as $t, 3 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminSolve<Vec<$t>, Vec<$t>>>::solve(&a, &b).unwrap();
+                    assert_relative_eq!(res[0], 1 as $t, epsilon = 1e-5);
+                    assert_relative_eq!(res[1], 1 as $t, epsilon = 1e-5);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_singular_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t],
+                        vec![2 as $t, 4 as $t],
+                    ];
+                    let b = vec![1 as $t, 1 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminSolve<Vec<$t>, Vec<$t>>>::solve(&a, &b);
+                    assert_eq!(res.unwrap_err().to_string(), "Singular matrix");
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_solve_shape_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t],
+                        vec![2 as $t, 4 as $t],
+                    ];
+                    let b = vec![1 as $t, 1 as $t, 1 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminSolve<Vec<$t>, Vec<$t>>>::solve(&a, &b);
+                    assert_eq!(
+                        res.unwrap_err().to_string(),
+                        "Cannot solve linear system with matrix of shape (2, 2) and right-hand side of length 3"
+                    );
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
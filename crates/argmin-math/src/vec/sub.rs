@@ -6,6 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminSub;
+use alloc::vec::Vec;
 use num_complex::Complex;
 
 macro_rules! make_sub {
@@ -93,6 +94,28 @@ make_sub!(Complex<u64>);
 make_sub!(Complex<f32>);
 make_sub!(Complex<f64>);
 
+// Subtraction of complex vectors and real scalars
+macro_rules! make_sub_real_scalar {
+    ($t:ty) => {
+        impl ArgminSub<$t, Vec<Complex<$t>>> for Vec<Complex<$t>> {
+            #[inline]
+            fn sub(&self, other: &$t) -> Vec<Complex<$t>> {
+                self.iter().map(|a| a - other).collect()
+            }
+        }
+
+        impl ArgminSub<Vec<Complex<$t>>, Vec<Complex<$t>>> for $t {
+            #[inline]
+            fn sub(&self, other: &Vec<Complex<$t>>) -> Vec<Complex<$t>> {
+                other.iter().map(|a| self - a).collect()
+            }
+        }
+    };
+}
+
+make_sub_real_scalar!(f32);
+make_sub_real_scalar!(f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -381,4 +404,29 @@ mod tests {
     make_test!(u64);
     make_test!(f32);
     make_test!(f64);
+
+    macro_rules! make_test_real_scalar {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_sub_vec_complex_real_scalar_ $t>]() {
+                    let a = vec![Complex::new(5 as $t, 3 as $t), Complex::new(8 as $t, 2 as $t)];
+                    let b = 2 as $t;
+                    let target = vec![Complex::new(3 as $t, 3 as $t), Complex::new(6 as $t, 2 as $t)];
+                    let target2 = vec![Complex::new(-3 as $t, -3 as $t), Complex::new(-6 as $t, -2 as $t)];
+                    let res = <Vec<Complex<$t>> as ArgminSub<$t, Vec<Complex<$t>>>>::sub(&a, &b);
+                    let res2 = <$t as ArgminSub<Vec<Complex<$t>>, Vec<Complex<$t>>>>::sub(&b, &a);
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target2[i].re as f64, res2[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target2[i].im as f64, res2[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+        };
+    }
+
+    make_test_real_scalar!(f32);
+    make_test_real_scalar!(f64);
 }
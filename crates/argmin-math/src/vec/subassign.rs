@@ -0,0 +1,179 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminSubAssign;
+use alloc::vec::Vec;
+use num_complex::Complex;
+
+macro_rules! make_subassign {
+    ($t:ty) => {
+        impl ArgminSubAssign<$t> for Vec<$t> {
+            #[inline]
+            fn sub_assign(&mut self, other: &$t) {
+                self.iter_mut().for_each(|a| *a -= *other);
+            }
+        }
+
+        impl ArgminSubAssign<Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn sub_assign(&mut self, other: &Vec<$t>) {
+                let n1 = self.len();
+                let n2 = other.len();
+                assert!(n1 > 0);
+                assert!(n2 > 0);
+                assert_eq!(n1, n2);
+                self.iter_mut()
+                    .zip(other.iter())
+                    .for_each(|(a, b)| *a -= *b);
+            }
+        }
+
+        impl ArgminSubAssign<Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn sub_assign(&mut self, other: &Vec<Vec<$t>>) {
+                let sr = self.len();
+                let or = other.len();
+                assert!(sr > 0);
+                // implicitly, or > 0
+                assert_eq!(sr, or);
+                let sc = self[0].len();
+                self.iter_mut().zip(other.iter()).for_each(|(a, b)| {
+                    assert_eq!(a.len(), sc);
+                    assert_eq!(b.len(), sc);
+                    <Vec<$t> as ArgminSubAssign<Vec<$t>>>::sub_assign(a, b)
+                });
+            }
+        }
+
+        impl ArgminSubAssign<$t> for Vec<Vec<$t>> {
+            #[inline]
+            fn sub_assign(&mut self, other: &$t) {
+                self.iter_mut()
+                    .for_each(|a| <Vec<$t> as ArgminSubAssign<$t>>::sub_assign(a, other));
+            }
+        }
+    };
+}
+
+make_subassign!(i8);
+make_subassign!(u8);
+make_subassign!(i16);
+make_subassign!(u16);
+make_subassign!(i32);
+make_subassign!(u32);
+make_subassign!(i64);
+make_subassign!(u64);
+make_subassign!(f32);
+make_subassign!(f64);
+make_subassign!(Complex<i8>);
+make_subassign!(Complex<u8>);
+make_subassign!(Complex<i16>);
+make_subassign!(Complex<u16>);
+make_subassign!(Complex<i32>);
+make_subassign!(Complex<u32>);
+make_subassign!(Complex<i64>);
+make_subassign!(Complex<u64>);
+make_subassign!(Complex<f32>);
+make_subassign!(Complex<f64>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_subassign_vec_scalar_ $t>]() {
+                    let mut a = vec![42 as $t, 44 as $t, 48 as $t];
+                    let b = 2 as $t;
+                    let target = vec![40 as $t, 42 as $t, 46 as $t];
+                    <Vec<$t> as ArgminSubAssign<$t>>::sub_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, a[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_subassign_vec_vec_ $t>]() {
+                    let mut a = vec![42 as $t, 44 as $t, 48 as $t];
+                    let b = vec![41 as $t, 40 as $t, 6 as $t];
+                    let target = vec![1 as $t, 4 as $t, 42 as $t];
+                    <Vec<$t> as ArgminSubAssign<Vec<$t>>>::sub_assign(&mut a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, a[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_subassign_vec_vec_panic_ $t>]() {
+                    let mut a = vec![42 as $t, 44 as $t, 48 as $t];
+                    let b = vec![1 as $t, 2 as $t];
+                    <Vec<$t> as ArgminSubAssign<Vec<$t>>>::sub_assign(&mut a, &b);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_subassign_mat_mat_ $t>]() {
+                    let mut a = vec![vec![42 as $t, 44 as $t, 48 as $t], vec![42 as $t, 44 as $t, 48 as $t]];
+                    let b = vec![vec![41 as $t, 40 as $t, 6 as $t], vec![41 as $t, 40 as $t, 6 as $t]];
+                    let target = vec![vec![1 as $t, 4 as $t, 42 as $t], vec![1 as $t, 4 as $t, 42 as $t]];
+                    <Vec<Vec<$t>> as ArgminSubAssign<Vec<Vec<$t>>>>::sub_assign(&mut a, &b);
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(target[j][i] as f64, a[j][i] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_subassign_mat_mat_panic_ $t>]() {
+                    let mut a = vec![vec![42 as $t, 44 as $t, 48 as $t], vec![42 as $t, 44 as $t, 48 as $t]];
+                    let b = vec![vec![41 as $t, 40 as $t, 6 as $t], vec![1 as $t, 2 as $t]];
+                    <Vec<Vec<$t>> as ArgminSubAssign<Vec<Vec<$t>>>>::sub_assign(&mut a, &b);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_subassign_mat_scalar_ $t>]() {
+                    let mut a = vec![vec![42 as $t, 44 as $t, 48 as $t], vec![42 as $t, 44 as $t, 48 as $t]];
+                    let b = 2 as $t;
+                    let target = vec![vec![40 as $t, 42 as $t, 46 as $t], vec![40 as $t, 42 as $t, 46 as $t]];
+                    <Vec<Vec<$t>> as ArgminSubAssign<$t>>::sub_assign(&mut a, &b);
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(target[j][i] as f64, a[j][i] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,240 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminSvd, Error};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::Sum;
+use num_traits::Float;
+
+/// Maximum number of sweeps of the Jacobi method
+const MAX_SWEEPS: usize = 100;
+
+macro_rules! make_svd {
+    ($t:ty) => {
+        /// Computes the singular value decomposition via one-sided Jacobi rotations.
+        ///
+        /// This is intended for small, dense matrices.
+        impl ArgminSvd<$t> for Vec<Vec<$t>> {
+            fn singular_values(&self) -> Result<Vec<$t>, Error> {
+                let JacobiSvd { w, .. } = jacobi_svd(self)?;
+                let mut sigma: Vec<$t> = w.iter().map(|wj| norm(wj)).collect();
+                sigma.sort_by(|a, b| b.total_cmp(a));
+                Ok(sigma)
+            }
+
+            fn map_singular_values<G: FnMut($t) -> $t>(&self, mut f: G) -> Result<Self, Error> {
+                let JacobiSvd { w, v, transposed } = jacobi_svd(self)?;
+                let mut out = vec![vec![0.0 as $t; self[0].len()]; self.len()];
+                for (wj, vj) in w.iter().zip(v.iter()) {
+                    let sigma = norm(wj);
+                    if sigma <= 0.0 {
+                        continue;
+                    }
+                    let factor = f(sigma) / sigma;
+                    let (left, right) = if transposed { (vj, wj) } else { (wj, vj) };
+                    for (row, l) in out.iter_mut().zip(left.iter()) {
+                        for (o, r) in row.iter_mut().zip(right.iter()) {
+                            *o += factor * l * r;
+                        }
+                    }
+                }
+                Ok(out)
+            }
+        }
+    };
+}
+
+/// Decomposition `a = sum_j w_j v_j^T` (or `a = sum_j v_j w_j^T` if `transposed` is set), where
+/// the `w_j` are orthogonal and the `v_j` orthonormal. The norms of the `w_j` are the singular
+/// values of `a`.
+struct JacobiSvd<T> {
+    w: Vec<Vec<T>>,
+    v: Vec<Vec<T>>,
+    transposed: bool,
+}
+
+/// One-sided Jacobi method
+fn jacobi_svd<T: Float + Sum>(a: &[Vec<T>]) -> Result<JacobiSvd<T>, Error> {
+    let m = a.len();
+    let n = a.first().map(|row| row.len()).unwrap_or(0);
+    if m == 0 || n == 0 || a.iter().any(|row| row.len() != n) {
+        return Err(anyhow::anyhow!(
+            "Cannot compute the SVD of an empty or non-rectangular matrix"
+        ));
+    }
+    // The columns of `w` are orthogonalized; working on the transpose of wide matrices keeps their
+    // number at `min(m, n)`.
+    let transposed = m < n;
+    let mut w: Vec<Vec<T>> = if transposed {
+        a.to_vec()
+    } else {
+        (0..n)
+            .map(|j| a.iter().map(|row| row[j]).collect())
+            .collect()
+    };
+    let k = w.len();
+    let mut v: Vec<Vec<T>> = (0..k)
+        .map(|j| {
+            let mut e = vec![T::zero(); k];
+            e[j] = T::one();
+            e
+        })
+        .collect();
+    let two = T::one() + T::one();
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..k {
+            for q in (p + 1)..k {
+                let alpha = dot(&w[p], &w[p]);
+                let beta = dot(&w[q], &w[q]);
+                let gamma = dot(&w[p], &w[q]);
+                if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (two * gamma);
+                let t = zeta.signum() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                let c = T::one() / (T::one() + t * t).sqrt();
+                let s = c * t;
+                rotate(&mut w, p, q, c, s);
+                rotate(&mut v, p, q, c, s);
+            }
+        }
+        if !rotated {
+            return Ok(JacobiSvd { w, v, transposed });
+        }
+    }
+    Err(anyhow::anyhow!("SVD did not converge"))
+}
+
+fn dot<T: Float + Sum>(a: &[T], b: &[T]) -> T {
+    a.iter().zip(b.iter()).map(|(&x, &y)| x * y).sum()
+}
+
+fn norm<T: Float + Sum>(a: &[T]) -> T {
+    dot(a, a).sqrt()
+}
+
+/// Rotates the vectors `p` and `q` of `x` in their common plane
+fn rotate<T: Float>(x: &mut [Vec<T>], p: usize, q: usize, c: T, s: T) {
+    for i in 0..x[p].len() {
+        let (xp, xq) = (x[p][i], x[q][i]);
+        x[p][i] = c * xp - s * xq;
+        x[q][i] = s * xp + c * xq;
+    }
+}
+
+make_svd!(f32);
+make_svd!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_singular_values_ $t>]() {
+                    let a = vec![
+                        vec![3 as $t, 0 as $t],
+                        vec![4 as $t, 5 as $t],
+                    ];
+                    let sigma = a.singular_values().unwrap();
+                    assert_eq!(sigma.len(), 2);
+                    assert_relative_eq!(sigma[0], (45.0 as $t).sqrt(), epsilon = 1e-5);
+                    assert_relative_eq!(sigma[1], (5.0 as $t).sqrt(), epsilon = 1e-5);
+
+                    // Wide and tall matrices
+                    let a = vec![
+                        vec![1 as $t, 0 as $t, 0 as $t],
+                        vec![0 as $t, -2 as $t, 0 as $t],
+                    ];
+                    let sigma = a.singular_values().unwrap();
+                    assert_eq!(sigma.len(), 2);
+                    assert_relative_eq!(sigma[0], 2.0, epsilon = 1e-5);
+                    assert_relative_eq!(sigma[1], 1.0, epsilon = 1e-5);
+                    let a = vec![
+                        vec![1 as $t, 0 as $t],
+                        vec![0 as $t, -2 as $t],
+                        vec![0 as $t, 0 as $t],
+                    ];
+                    let sigma = a.singular_values().unwrap();
+                    assert_eq!(sigma.len(), 2);
+                    assert_relative_eq!(sigma[0], 2.0, epsilon = 1e-5);
+                    assert_relative_eq!(sigma[1], 1.0, epsilon = 1e-5);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_map_singular_values_ $t>]() {
+                    let matrices = [
+                        vec![
+                            vec![3 as $t, 0 as $t],
+                            vec![4 as $t, 5 as $t],
+                        ],
+                        vec![
+                            vec![1 as $t, 2 as $t, 3 as $t],
+                            vec![4 as $t, 5 as $t, 6 as $t],
+                        ],
+                        vec![
+                            vec![1 as $t, 2 as $t],
+                            vec![2 as $t, 4 as $t],
+                            vec![-1 as $t, 0 as $t],
+                        ],
+                    ];
+                    for a in matrices {
+                        // Identity
+                        let res = a.map_singular_values(|s| s).unwrap();
+                        for i in 0..a.len() {
+                            for j in 0..a[0].len() {
+                                assert_relative_eq!(res[i][j], a[i][j], epsilon = 1e-4);
+                            }
+                        }
+                        // Scaling
+                        let res = a.map_singular_values(|s| 2.0 * s).unwrap();
+                        for i in 0..a.len() {
+                            for j in 0..a[0].len() {
+                                assert_relative_eq!(res[i][j], 2.0 * a[i][j], epsilon = 1e-4);
+                            }
+                        }
+                    }
+
+                    // Shrinking the singular values of diag(3, 1) by 2
+                    let a = vec![
+                        vec![3 as $t, 0 as $t],
+                        vec![0 as $t, 1 as $t],
+                    ];
+                    let res = a.map_singular_values(|s| (s - 2.0).max(0.0)).unwrap();
+                    assert_relative_eq!(res[0][0], 1.0, epsilon = 1e-5);
+                    assert_relative_eq!(res[0][1], 0.0, epsilon = 1e-5);
+                    assert_relative_eq!(res[1][0], 0.0, epsilon = 1e-5);
+                    assert_relative_eq!(res[1][1], 0.0, epsilon = 1e-5);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_svd_error_ $t>]() {
+                    let a: Vec<Vec<$t>> = vec![];
+                    assert!(a.singular_values().is_err());
+                    let a = vec![vec![1 as $t, 2 as $t], vec![1 as $t]];
+                    assert!(a.map_singular_values(|s| s).is_err());
+                    let a = vec![vec![<$t>::NAN, 2 as $t], vec![1 as $t, 1 as $t]];
+                    assert!(a.singular_values().is_err());
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -9,6 +9,8 @@
 // ArrayViews, which would probably make it more efficient.
 
 use crate::ArgminTranspose;
+use alloc::vec;
+use alloc::vec::Vec;
 use num_complex::Complex;
 
 macro_rules! make_transpose {
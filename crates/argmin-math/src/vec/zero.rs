@@ -6,6 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminZeroLike;
+use alloc::vec;
+use alloc::vec::Vec;
 
 impl<T> ArgminZeroLike for Vec<T>
 where
@@ -0,0 +1,351 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Records the states of an optimization run in a binary log and replays them offline.
+//!
+//! The observer [`RunLog`] writes a snapshot of the state (and the key-value store provided by
+//! the solver) after initialization, after every observed iteration and at the end of a run into
+//! a single file using [bincode](https://crates.io/crates/bincode). With [`replay`] such a log
+//! can later be fed into any other observer, for instance to regenerate plots or to recompute
+//! metrics of a long run without having to run it again. [`read_run_log`] returns all recorded
+//! entries for custom post-processing.
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-observer-runlog = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+use argmin::core::observers::Observe;
+use argmin::core::{Error, KV};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// A single entry of a run log.
+///
+/// Each entry corresponds to one call of a method of the [`Observe`] trait.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RunLogEntry<I> {
+    /// Recorded in `observe_init`
+    Init {
+        /// Name of the solver
+        name: String,
+        /// State after initialization of the solver
+        state: I,
+        /// Key-value store provided by the solver
+        kv: KV,
+    },
+    /// Recorded in `observe_iter`
+    Iter {
+        /// State after the iteration
+        state: I,
+        /// Key-value store provided by the solver
+        kv: KV,
+    },
+    /// Recorded in `observe_final`
+    Final {
+        /// Final state of the run
+        state: I,
+    },
+}
+
+/// Records the states of an optimization run in a binary log file.
+///
+/// The log is (re)created when the solver is initialized. If the run starts without
+/// initialization (for instance when it is resumed from a checkpoint), entries are appended to
+/// an existing log. Every entry is flushed to disk immediately, therefore the log is usable
+/// even if the run crashes.
+///
+/// The state used by the solver needs to be serializable, which requires the `serde1` feature of
+/// `argmin`.
+///
+/// # Example
+///
+/// ```
+/// use argmin_observer_runlog::RunLog;
+///
+/// let observer = RunLog::new("logs/run.bin");
+/// ```
+#[derive(Debug)]
+pub struct RunLog {
+    /// Path of the log file
+    path: PathBuf,
+    /// Writer, opened on first use
+    writer: Option<BufWriter<File>>,
+}
+
+impl RunLog {
+    /// Create a new instance of `RunLog` which writes to the file at `path`.
+    ///
+    /// Missing parent directories are created once the first entry is written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_runlog::RunLog;
+    /// let observer = RunLog::new("logs/run.bin");
+    /// # assert_eq!(observer.path(), std::path::Path::new("logs/run.bin"));
+    /// ```
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        RunLog {
+            path: path.as_ref().to_path_buf(),
+            writer: None,
+        }
+    }
+
+    /// Returns the path of the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Opens the log file, either truncating it or appending to it.
+    fn open(&mut self, truncate: bool) -> Result<&mut BufWriter<File>, Error> {
+        if self.writer.is_none() || truncate {
+            if let Some(parent) = self.path.parent() {
+                if !parent.as_os_str().is_empty() && !parent.exists() {
+                    std::fs::create_dir_all(parent)?;
+                }
+            }
+            let file = if truncate {
+                File::create(&self.path)?
+            } else {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?
+            };
+            self.writer = Some(BufWriter::new(file));
+        }
+        Ok(self.writer.as_mut().unwrap())
+    }
+
+    /// Serializes a single entry and flushes it to disk.
+    fn write<I: Serialize>(
+        &mut self,
+        entry: &RunLogEntry<&I>,
+        truncate: bool,
+    ) -> Result<(), Error> {
+        let writer = self.open(truncate)?;
+        bincode::serialize_into(&mut *writer, entry)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<I> Observe<I> for RunLog
+where
+    I: Serialize,
+{
+    /// Starts a new log and records the initial state.
+    fn observe_init(&mut self, name: &str, state: &I, kv: &KV) -> Result<(), Error> {
+        let entry = RunLogEntry::Init {
+            name: name.to_string(),
+            state,
+            kv: kv.clone(),
+        };
+        self.write(&entry, true)
+    }
+
+    /// Records the state after an iteration.
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        let entry = RunLogEntry::Iter {
+            state,
+            kv: kv.clone(),
+        };
+        self.write(&entry, false)
+    }
+
+    /// Records the final state.
+    fn observe_final(&mut self, state: &I) -> Result<(), Error> {
+        self.write(&RunLogEntry::Final { state }, false)
+    }
+}
+
+/// Reads all entries of a run log written by [`RunLog`].
+///
+/// Returns an error if the file cannot be opened or if an entry cannot be deserialized into
+/// `RunLogEntry<I>`, which is typically the case if the log was written with a different state
+/// type or if the last entry was cut off.
+///
+/// # Example
+///
+/// ```no_run
+/// # use argmin::core::{Error, IterState};
+/// use argmin_observer_runlog::{read_run_log, RunLogEntry};
+///
+/// # fn main() -> Result<(), Error> {
+/// let entries: Vec<RunLogEntry<IterState<Vec<f64>, (), (), (), (), f64>>> =
+///     read_run_log("logs/run.bin")?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_run_log<I, P>(path: P) -> Result<Vec<RunLogEntry<I>>, Error>
+where
+    I: DeserializeOwned,
+    P: AsRef<Path>,
+{
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut entries = vec![];
+    while !reader.fill_buf()?.is_empty() {
+        entries.push(bincode::deserialize_from(&mut reader)?);
+    }
+    Ok(entries)
+}
+
+/// Replays a run log written by [`RunLog`] through an observer.
+///
+/// For each recorded entry, the corresponding method of `observer` is called in the original
+/// order. Since [`Observers`](`argmin::core::observers::Observers`) implements [`Observe`],
+/// several observers (including their [`ObserverMode`](`argmin::core::observers::ObserverMode`))
+/// can be replayed at once.
+///
+/// # Example
+///
+/// ```no_run
+/// # use argmin::core::{Error, IterState};
+/// # use argmin::core::observers::{Observers, ObserverMode};
+/// # struct Plotter {}
+/// # impl<I> argmin::core::observers::Observe<I> for Plotter {}
+/// use argmin_observer_runlog::replay;
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut observers: Observers<IterState<Vec<f64>, (), (), (), (), f64>> = Observers::new();
+/// observers.push(Plotter {}, ObserverMode::Every(10));
+///
+/// replay("logs/run.bin", &mut observers)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn replay<I, O, P>(path: P, observer: &mut O) -> Result<(), Error>
+where
+    I: DeserializeOwned,
+    O: Observe<I>,
+    P: AsRef<Path>,
+{
+    for entry in read_run_log(path)? {
+        match entry {
+            RunLogEntry::Init { name, state, kv } => observer.observe_init(&name, &state, &kv)?,
+            RunLogEntry::Iter { state, kv } => observer.observe_iter(&state, &kv)?,
+            RunLogEntry::Final { state } => observer.observe_final(&state)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::test_utils::{TestProblem, TestSolver};
+    use argmin::core::{Executor, IterState, State};
+    use argmin::kv;
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    #[derive(Default)]
+    struct Recorder {
+        name: String,
+        iters: Vec<u64>,
+        kvs: Vec<KV>,
+        final_iter: Option<u64>,
+    }
+
+    impl Observe<TestState> for Recorder {
+        fn observe_init(&mut self, name: &str, _state: &TestState, _kv: &KV) -> Result<(), Error> {
+            self.name = name.to_string();
+            Ok(())
+        }
+
+        fn observe_iter(&mut self, state: &TestState, kv: &KV) -> Result<(), Error> {
+            self.iters.push(state.get_iter());
+            self.kvs.push(kv.clone());
+            Ok(())
+        }
+
+        fn observe_final(&mut self, state: &TestState) -> Result<(), Error> {
+            self.final_iter = Some(state.get_iter());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let path = std::env::temp_dir()
+            .join("argmin-observer-runlog")
+            .join("record_and_replay.bin");
+
+        let _ = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(3))
+            .add_observer(RunLog::new(&path), Default::default())
+            .run()
+            .unwrap();
+
+        let entries: Vec<RunLogEntry<TestState>> = read_run_log(&path).unwrap();
+        assert_eq!(entries.len(), 5);
+        assert!(matches!(&entries[0], RunLogEntry::Init { name, .. } if name == "TestSolver"));
+        assert!(matches!(&entries[4], RunLogEntry::Final { state } if state.get_iter() == 3));
+
+        let mut recorder = Recorder::default();
+        replay(&path, &mut recorder).unwrap();
+        assert_eq!(recorder.name, "TestSolver");
+        assert_eq!(recorder.iters, vec![0, 1, 2]);
+        assert_eq!(recorder.final_iter, Some(3));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_append_without_init() {
+        let path = std::env::temp_dir()
+            .join("argmin-observer-runlog")
+            .join("append_without_init.bin");
+
+        let state: TestState = IterState::new().param(vec![1.0]);
+        let mut log = RunLog::new(&path);
+        log.observe_init("solver", &state, &KV::new()).unwrap();
+        drop(log);
+
+        // A resumed run does not call `observe_init`
+        let mut log = RunLog::new(&path);
+        log.observe_iter(&state, &kv!("a" => 1.0;)).unwrap();
+        log.observe_final(&state).unwrap();
+
+        let entries: Vec<RunLogEntry<TestState>> = read_run_log(&path).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[1],
+            RunLogEntry::Iter {
+                state: state.clone(),
+                kv: kv!("a" => 1.0;)
+            }
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,1173 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Writes and reads traces of optimization runs in a documented CSV or JSON format.
+//!
+//! A trace consists of one record per observed iteration. The observer [`TraceWriter`] writes
+//! traces of argmin runs; [`Trace`] reads and writes traces in both formats. Since the format is
+//! simple and documented below, traces can be produced and consumed by other tools as well, which
+//! allows comparing runs of argmin with runs of SciPy or NLopt in external analysis scripts and
+//! vice versa.
+//!
+//! # Format (version 1)
+//!
+//! Each record consists of the following fields:
+//!
+//! | Field            | Type            | Required | Description                                              |
+//! |------------------|-----------------|----------|----------------------------------------------------------|
+//! | `iter`           | integer         | yes      | Iteration number, starting at 0                          |
+//! | `time`           | float           | no       | Seconds elapsed since the start of the run               |
+//! | `cost`           | float           | yes      | Cost function value of the current parameter vector      |
+//! | `best_cost`      | float           | no       | Best cost function value so far                          |
+//! | `gradient_norm`  | float           | no       | L2 norm of the gradient at the current parameter vector  |
+//! | `cost_count`     | integer         | no       | Total number of cost function evaluations so far         |
+//! | `gradient_count` | integer         | no       | Total number of gradient evaluations so far              |
+//! | `hessian_count`  | integer         | no       | Total number of Hessian evaluations so far               |
+//! | `jacobian_count` | integer         | no       | Total number of Jacobian evaluations so far              |
+//! | `param`          | array of floats | no       | Current parameter vector                                 |
+//!
+//! Non-finite floats are written as `inf`, `-inf` and `NaN`.
+//!
+//! ## CSV
+//!
+//! The first non-comment line is a header containing the names of the columns. The parameter
+//! vector is split into the columns `param_0`, `param_1`, ..., `param_{n-1}`. Missing values are
+//! represented by empty fields. Lines starting with `#` are comments; the writer adds the comments
+//! `# argmin-trace 1` (the version of the format) and `# solver: <name>`, which are interpreted by
+//! the reader. The reader accepts columns in any order and ignores unknown columns.
+//!
+//! ```text
+//! # argmin-trace 1
+//! # solver: L-BFGS
+//! iter,time,cost,best_cost,gradient_norm,cost_count,gradient_count,hessian_count,jacobian_count,param_0,param_1
+//! 0,0.000012,4.7318,4.7318,,2,2,,,-0.9716,0.9549
+//! 1,0.000020,4.0461,4.0461,,3,3,,,-1.0127,1.0104
+//! ```
+//!
+//! ## JSON
+//!
+//! A single object with the version of the format, the name of the solver (optional) and an
+//! array of records. Missing optional fields are omitted (`null` is accepted as well).
+//! Non-finite floats are written as strings.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "solver": "L-BFGS",
+//!   "records": [
+//!     {"iter": 0, "time": 0.000012, "cost": 4.7318, "best_cost": 4.7318, "cost_count": 2},
+//!     {"iter": 1, "time": 0.000020, "cost": "inf", "best_cost": 4.7318, "cost_count": 3}
+//!   ]
+//! }
+//! ```
+//!
+//! ## Other tools
+//!
+//! CSV traces can be loaded with pandas via `pandas.read_csv(path, comment="#")`. A trace of a
+//! SciPy run can be written with a callback, for instance:
+//!
+//! ```python
+//! import csv, time
+//! from scipy.optimize import minimize, rosen, rosen_der
+//!
+//! with open("scipy.csv", "w", newline="") as f:
+//!     writer = csv.writer(f)
+//!     writer.writerow(["iter", "time", "cost", "param_0", "param_1"])
+//!     start, it = time.perf_counter(), iter(range(10**9))
+//!     callback = lambda x: writer.writerow([next(it), time.perf_counter() - start, rosen(x), *x])
+//!     minimize(rosen, [-1.2, 1.0], jac=rosen_der, method="L-BFGS-B", callback=callback)
+//! ```
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-observer-trace = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! # use argmin::core::{CostFunction, Error, Executor, Gradient, IterState};
+//! # use argmin::core::observers::ObserverMode;
+//! # use argmin::solver::linesearch::MoreThuenteLineSearch;
+//! # use argmin::solver::quasinewton::LBFGS;
+//! # use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+//! use argmin_observer_trace::{Trace, TraceWriter};
+//! # struct Rosenbrock {}
+//! # impl CostFunction for Rosenbrock {
+//! #     type Param = Vec<f64>;
+//! #     type Output = f64;
+//! #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+//! #         Ok(rosenbrock(p))
+//! #     }
+//! # }
+//! # impl Gradient for Rosenbrock {
+//! #     type Param = Vec<f64>;
+//! #     type Gradient = Vec<f64>;
+//! #     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+//! #         Ok(rosenbrock_derivative(p))
+//! #     }
+//! # }
+//!
+//! # fn main() -> Result<(), Error> {
+//! # let dir = std::env::temp_dir().join("argmin-observer-trace-doc");
+//! # let path = dir.join("lbfgs.csv");
+//! let solver = LBFGS::new(MoreThuenteLineSearch::new(), 7);
+//! Executor::new(Rosenbrock {}, solver)
+//!     .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100).counting(true))
+//!     .add_observer(
+//!         TraceWriter::new(&path).with_params().with_gradient_norm(),
+//!         ObserverMode::Always,
+//!     )
+//!     .run()?;
+//!
+//! let trace = Trace::read(&path)?;
+//! assert_eq!(trace.solver.as_deref(), Some("L-BFGS"));
+//! for record in trace.records.iter() {
+//!     println!("{} {} {:?}", record.iter, record.cost, record.cost_count);
+//! }
+//! # assert!(!trace.records.is_empty());
+//! # std::fs::remove_dir_all(&dir)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+use argmin::core::observers::Observe;
+use argmin::core::{ArgminFloat, Error, IterState, State, KV};
+use argmin_math::ArgminL2Norm;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Version of the trace format written by this crate
+pub const TRACE_VERSION: u64 = 1;
+
+/// Names of the CSV columns apart from the parameter vector
+const COLUMNS: [&str; 9] = [
+    "iter",
+    "time",
+    "cost",
+    "best_cost",
+    "gradient_norm",
+    "cost_count",
+    "gradient_count",
+    "hessian_count",
+    "jacobian_count",
+];
+
+/// Prefix of the CSV columns of the parameter vector
+const PARAM_PREFIX: &str = "param_";
+
+/// Extracts the parameter vector from a state
+type ParamExtractor<I> = fn(&I) -> Option<Vec<f64>>;
+
+/// Computes the gradient norm from a state
+type GradientNormExtractor<I> = fn(&I) -> Option<f64>;
+
+/// A single record of a trace.
+///
+/// See the [crate documentation](`crate`) for a description of the fields.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TraceRecord {
+    /// Iteration number
+    pub iter: u64,
+    /// Seconds elapsed since the start of the run
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "opt_float")]
+    pub time: Option<f64>,
+    /// Cost function value of the current parameter vector
+    #[serde(with = "float")]
+    pub cost: f64,
+    /// Best cost function value so far
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "opt_float")]
+    pub best_cost: Option<f64>,
+    /// L2 norm of the gradient
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "opt_float")]
+    pub gradient_norm: Option<f64>,
+    /// Total number of cost function evaluations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_count: Option<u64>,
+    /// Total number of gradient evaluations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gradient_count: Option<u64>,
+    /// Total number of Hessian evaluations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hessian_count: Option<u64>,
+    /// Total number of Jacobian evaluations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jacobian_count: Option<u64>,
+    /// Current parameter vector
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "opt_floats")]
+    pub param: Option<Vec<f64>>,
+}
+
+impl TraceRecord {
+    /// Constructs a record from the state of a run.
+    ///
+    /// Evaluation counts are only available if counting is enabled in the state (for instance
+    /// via [`IterState::counting`]).
+    fn from_state<I: State>(
+        state: &I,
+        params: Option<ParamExtractor<I>>,
+        gradient_norm: Option<GradientNormExtractor<I>>,
+    ) -> Self {
+        let counts = state.get_func_counts();
+        TraceRecord {
+            iter: state.get_iter(),
+            time: state.get_time().map(|time| time.as_secs_f64()),
+            cost: to_f64(state.get_cost()),
+            best_cost: Some(to_f64(state.get_best_cost())),
+            gradient_norm: gradient_norm.and_then(|extractor| extractor(state)),
+            cost_count: counts.get("cost_count").copied(),
+            gradient_count: counts.get("gradient_count").copied(),
+            hessian_count: counts.get("hessian_count").copied(),
+            jacobian_count: counts.get("jacobian_count").copied(),
+            param: params.and_then(|extractor| extractor(state)),
+        }
+    }
+
+    /// Writes the record as a CSV line with `num_params` parameter columns.
+    fn write_csv<W: Write>(&self, writer: &mut W, num_params: usize) -> Result<(), Error> {
+        let float = |x: Option<f64>| x.map(|x| x.to_string()).unwrap_or_default();
+        let int = |x: Option<u64>| x.map(|x| x.to_string()).unwrap_or_default();
+        let mut fields = vec![
+            self.iter.to_string(),
+            float(self.time),
+            self.cost.to_string(),
+            float(self.best_cost),
+            float(self.gradient_norm),
+            int(self.cost_count),
+            int(self.gradient_count),
+            int(self.hessian_count),
+            int(self.jacobian_count),
+        ];
+        let param = self.param.as_deref().unwrap_or(&[]);
+        fields.extend((0..num_params).map(|i| float(param.get(i).copied())));
+        writeln!(writer, "{}", fields.join(","))?;
+        Ok(())
+    }
+}
+
+/// Format of a trace file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TraceFormat {
+    /// CSV
+    Csv,
+    /// JSON
+    Json,
+}
+
+impl TraceFormat {
+    /// Determines the format from the extension of `path` (`csv` or `json`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trace::TraceFormat;
+    /// assert_eq!(TraceFormat::from_path("run.json"), Some(TraceFormat::Json));
+    /// assert_eq!(TraceFormat::from_path("run.txt"), None);
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "csv" => Some(TraceFormat::Csv),
+            "json" => Some(TraceFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// Same as [`from_path`](`TraceFormat::from_path`), but returns an error for unknown
+    /// extensions.
+    fn from_path_checked(path: &Path) -> Result<Self, Error> {
+        TraceFormat::from_path(path).ok_or_else(|| {
+            Error::msg(format!(
+                "Cannot determine trace format of `{}`, expected extension `csv` or `json`.",
+                path.display()
+            ))
+        })
+    }
+}
+
+/// A trace of an optimization run.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::Error;
+/// use argmin_observer_trace::{Trace, TraceRecord};
+///
+/// # fn main() -> Result<(), Error> {
+/// let csv = "# Output of some other library\niter,cost,param_0\n0,2.5,1.0\n1,0.5,0.4\n";
+/// let trace = Trace::read_csv(csv.as_bytes())?;
+/// assert_eq!(trace.records.len(), 2);
+/// assert_eq!(trace.records[1].param, Some(vec![0.4]));
+///
+/// let mut json = vec![];
+/// trace.write_json(&mut json)?;
+/// assert_eq!(Trace::read_json(json.as_slice())?, trace);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Trace {
+    /// Version of the format
+    #[serde(default = "default_version")]
+    pub version: u64,
+    /// Name of the solver
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solver: Option<String>,
+    /// Records, one per observed iteration
+    pub records: Vec<TraceRecord>,
+}
+
+fn default_version() -> u64 {
+    TRACE_VERSION
+}
+
+impl Default for Trace {
+    fn default() -> Self {
+        Trace {
+            version: TRACE_VERSION,
+            solver: None,
+            records: vec![],
+        }
+    }
+}
+
+impl Trace {
+    /// Constructs an empty trace.
+    pub fn new() -> Self {
+        Trace::default()
+    }
+
+    /// Reads a trace from a file, whose format is determined from the extension of `path`.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let reader = BufReader::new(File::open(path)?);
+        match TraceFormat::from_path_checked(path)? {
+            TraceFormat::Csv => Trace::read_csv(reader),
+            TraceFormat::Json => Trace::read_json(reader),
+        }
+    }
+
+    /// Writes the trace to a file, whose format is determined from the extension of `path`.
+    ///
+    /// Missing parent directories are created.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let format = TraceFormat::from_path_checked(path)?;
+        let mut writer = BufWriter::new(create(path)?);
+        match format {
+            TraceFormat::Csv => self.write_csv(&mut writer)?,
+            TraceFormat::Json => self.write_json(&mut writer)?,
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a trace in JSON format.
+    pub fn read_json<R: Read>(reader: R) -> Result<Self, Error> {
+        let trace: Trace = serde_json::from_reader(reader)?;
+        check_version(trace.version)?;
+        Ok(trace)
+    }
+
+    /// Writes the trace in JSON format.
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<(), Error> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+
+    /// Reads a trace in CSV format.
+    ///
+    /// Returns an error if the columns `iter` or `cost` are missing, if the parameter columns are
+    /// not numbered consecutively or if a field cannot be parsed.
+    pub fn read_csv<R: BufRead>(reader: R) -> Result<Self, Error> {
+        let mut trace = Trace::new();
+        let mut header: Option<CsvHeader> = None;
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error =
+                |text: String| Error::msg(format!("CSV trace, line {}: {text}", number + 1));
+            if let Some(comment) = line.strip_prefix('#') {
+                let comment = comment.trim();
+                if let Some(solver) = comment.strip_prefix("solver:") {
+                    trace.solver = Some(solver.trim().to_string());
+                } else if let Some(version) = comment.strip_prefix("argmin-trace") {
+                    trace.version = version
+                        .trim()
+                        .parse()
+                        .map_err(|_| error(format!("invalid version `{}`.", version.trim())))?;
+                    check_version(trace.version)?;
+                }
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(|field| field.trim()).collect();
+            match header.as_ref() {
+                None => header = Some(CsvHeader::parse(&fields).map_err(error)?),
+                Some(header) => trace
+                    .records
+                    .push(header.parse_record(&fields).map_err(error)?),
+            }
+        }
+        Ok(trace)
+    }
+
+    /// Writes the trace in CSV format.
+    ///
+    /// The number of parameter columns is the length of the longest parameter vector.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        write_csv_preamble(&mut writer, self.solver.as_deref())?;
+        let num_params = self
+            .records
+            .iter()
+            .filter_map(|record| record.param.as_ref().map(|param| param.len()))
+            .max()
+            .unwrap_or(0);
+        write_csv_header(&mut writer, num_params)?;
+        for record in self.records.iter() {
+            record.write_csv(&mut writer, num_params)?;
+        }
+        Ok(())
+    }
+}
+
+/// Positions of the known columns in a CSV header
+struct CsvHeader {
+    /// Positions of the columns in [`COLUMNS`]
+    columns: [Option<usize>; COLUMNS.len()],
+    /// Positions of the parameter columns
+    params: Vec<usize>,
+}
+
+impl CsvHeader {
+    fn parse(fields: &[&str]) -> Result<Self, String> {
+        let mut columns = [None; COLUMNS.len()];
+        let mut params: Vec<(usize, usize)> = vec![];
+        for (position, field) in fields.iter().enumerate() {
+            let name = field.trim_matches('"');
+            if let Some(index) = COLUMNS.iter().position(|column| *column == name) {
+                columns[index] = Some(position);
+            } else if let Some(Ok(index)) = name.strip_prefix(PARAM_PREFIX).map(str::parse) {
+                params.push((index, position));
+            }
+        }
+        for required in ["iter", "cost"] {
+            if columns[column_index(required)].is_none() {
+                return Err(format!("missing column `{required}`."));
+            }
+        }
+        params.sort_unstable();
+        if params.iter().enumerate().any(|(i, &(index, _))| i != index) {
+            return Err(format!(
+                "parameter columns must be numbered consecutively starting at `{PARAM_PREFIX}0`."
+            ));
+        }
+        Ok(CsvHeader {
+            columns,
+            params: params.into_iter().map(|(_, position)| position).collect(),
+        })
+    }
+
+    fn parse_record(&self, fields: &[&str]) -> Result<TraceRecord, String> {
+        let field = |position: usize| -> Result<Option<&str>, String> {
+            match fields.get(position) {
+                Some(&"") => Ok(None),
+                Some(field) => Ok(Some(field)),
+                None => Err(format!(
+                    "expected at least {} fields, got {}.",
+                    position + 1,
+                    fields.len()
+                )),
+            }
+        };
+        let column = |name: &str| match self.columns[column_index(name)] {
+            Some(position) => field(position),
+            None => Ok(None),
+        };
+        let float = |name: &str| -> Result<Option<f64>, String> {
+            column(name)?
+                .map(|value| parse_float(name, value))
+                .transpose()
+        };
+        let int = |name: &str| -> Result<Option<u64>, String> {
+            column(name)?
+                .map(|value| parse_int(name, value))
+                .transpose()
+        };
+        let param: Vec<Option<f64>> = self
+            .params
+            .iter()
+            .map(|&position| {
+                field(position)?
+                    .map(|value| parse_float("param", value))
+                    .transpose()
+            })
+            .collect::<Result<_, _>>()?;
+        let param = if param.iter().all(Option::is_none) {
+            None
+        } else {
+            Some(param.into_iter().map(|x| x.unwrap_or(f64::NAN)).collect())
+        };
+        Ok(TraceRecord {
+            iter: int("iter")?.ok_or("missing value of `iter`.")?,
+            time: float("time")?,
+            cost: float("cost")?.ok_or("missing value of `cost`.")?,
+            best_cost: float("best_cost")?,
+            gradient_norm: float("gradient_norm")?,
+            cost_count: int("cost_count")?,
+            gradient_count: int("gradient_count")?,
+            hessian_count: int("hessian_count")?,
+            jacobian_count: int("jacobian_count")?,
+            param,
+        })
+    }
+}
+
+fn column_index(name: &str) -> usize {
+    COLUMNS.iter().position(|column| *column == name).unwrap()
+}
+
+fn parse_float(name: &str, value: &str) -> Result<f64, String> {
+    value
+        .trim_matches('"')
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` of `{name}`."))
+}
+
+/// Parses an integer, which may also be written as float without fractional part (as done by
+/// some tools for columns with missing values).
+fn parse_int(name: &str, value: &str) -> Result<u64, String> {
+    let value = value.trim_matches('"');
+    value
+        .parse()
+        .ok()
+        .or_else(|| {
+            let x: f64 = value.parse().ok()?;
+            (x >= 0.0 && x.fract() == 0.0 && x <= u64::MAX as f64).then_some(x as u64)
+        })
+        .ok_or_else(|| format!("invalid value `{value}` of `{name}`."))
+}
+
+fn check_version(version: u64) -> Result<(), Error> {
+    if version > TRACE_VERSION {
+        return Err(Error::msg(format!(
+            "Trace has version {version}, but only versions up to {TRACE_VERSION} are supported."
+        )));
+    }
+    Ok(())
+}
+
+fn write_csv_preamble<W: Write>(writer: &mut W, solver: Option<&str>) -> Result<(), Error> {
+    writeln!(writer, "# argmin-trace {TRACE_VERSION}")?;
+    if let Some(solver) = solver {
+        writeln!(writer, "# solver: {solver}")?;
+    }
+    Ok(())
+}
+
+fn write_csv_header<W: Write>(writer: &mut W, num_params: usize) -> Result<(), Error> {
+    let mut columns: Vec<String> = COLUMNS.iter().map(|column| column.to_string()).collect();
+    columns.extend((0..num_params).map(|i| format!("{PARAM_PREFIX}{i}")));
+    writeln!(writer, "{}", columns.join(","))?;
+    Ok(())
+}
+
+/// Creates a file, including missing parent directories.
+fn create(path: &Path) -> Result<File, Error> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    Ok(File::create(path)?)
+}
+
+fn to_f64<F: ToPrimitive>(x: F) -> f64 {
+    x.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Writes the trace of an optimization run to a CSV or JSON file.
+///
+/// The format is determined from the extension of the path (default: CSV) or set via
+/// [`with_format`](`TraceWriter::with_format`). A record is written for every observed iteration;
+/// which iterations are observed is controlled by the
+/// [`ObserverMode`](`argmin::core::observers::ObserverMode`). The trace is (re)created when the
+/// solver is initialized. If the run starts without initialization (for instance when it is
+/// resumed from a checkpoint), records are appended to an existing trace.
+///
+/// CSV traces are written and flushed line by line, therefore they are usable even if the run
+/// crashes. The number of parameter columns is determined by the first record. JSON traces are
+/// written at the end of the run.
+///
+/// By default, the iteration number, the elapsed time (if the timer is enabled), the current and
+/// best cost and the evaluation counts are recorded. Evaluation counts are only available if
+/// counting is enabled in the state (for instance via [`IterState::counting`]). Parameter vectors
+/// and gradient norms are only recorded if enabled via [`with_params`](`TraceWriter::with_params`)
+/// and [`with_gradient_norm`](`TraceWriter::with_gradient_norm`) (or the corresponding methods
+/// taking extractor functions).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::IterState;
+/// use argmin_observer_trace::{TraceFormat, TraceWriter};
+///
+/// let observer: TraceWriter<IterState<Vec<f64>, Vec<f64>, (), (), (), f64>> =
+///     TraceWriter::new("traces/run.json").with_params().with_gradient_norm();
+/// # assert_eq!(observer.format(), TraceFormat::Json);
+/// ```
+pub struct TraceWriter<I: State> {
+    /// Path of the trace file
+    path: PathBuf,
+    /// Format of the trace file
+    format: TraceFormat,
+    /// Name of the solver and records (only stored for JSON traces or until the CSV file is
+    /// opened)
+    trace: Trace,
+    /// Whether the solver was initialized in the current run
+    initialized: bool,
+    /// CSV writer and number of parameter columns, opened on first use
+    csv: Option<(BufWriter<File>, usize)>,
+    /// Extracts the parameter vector from the state
+    params: Option<ParamExtractor<I>>,
+    /// Computes the gradient norm from the state
+    gradient_norm: Option<GradientNormExtractor<I>>,
+}
+
+impl<I: State> TraceWriter<I> {
+    /// Constructs a new `TraceWriter` which writes to the file at `path`.
+    ///
+    /// Missing parent directories are created once the first record is written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::IterState;
+    /// # use argmin_observer_trace::TraceWriter;
+    /// let observer: TraceWriter<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     TraceWriter::new("traces/run.csv");
+    /// # assert_eq!(observer.path(), std::path::Path::new("traces/run.csv"));
+    /// ```
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        TraceWriter {
+            format: TraceFormat::from_path(&path).unwrap_or(TraceFormat::Csv),
+            path,
+            trace: Trace::new(),
+            initialized: false,
+            csv: None,
+            params: None,
+            gradient_norm: None,
+        }
+    }
+
+    /// Sets the format of the trace file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::IterState;
+    /// # use argmin_observer_trace::{TraceFormat, TraceWriter};
+    /// let observer: TraceWriter<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     TraceWriter::new("traces/run.txt").with_format(TraceFormat::Json);
+    /// ```
+    #[must_use]
+    pub fn with_format(mut self, format: TraceFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets a function which extracts the parameter vector from the state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{PopulationState, State};
+    /// # use argmin::solver::particleswarm::Particle;
+    /// # use argmin_observer_trace::TraceWriter;
+    /// let observer: TraceWriter<PopulationState<Particle<Vec<f64>, f64>, f64>> =
+    ///     TraceWriter::new("traces/pso.csv")
+    ///         .params(|state| state.get_param().map(|particle| particle.position.clone()));
+    /// ```
+    #[must_use]
+    pub fn params(mut self, extractor: fn(&I) -> Option<Vec<f64>>) -> Self {
+        self.params = Some(extractor);
+        self
+    }
+
+    /// Sets a function which computes the gradient norm from the state.
+    #[must_use]
+    pub fn gradient_norm(mut self, extractor: fn(&I) -> Option<f64>) -> Self {
+        self.gradient_norm = Some(extractor);
+        self
+    }
+
+    /// Returns the path of the trace file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the format of the trace file.
+    pub fn format(&self) -> TraceFormat {
+        self.format
+    }
+
+    /// Writes a record to the CSV file, which is opened on first use.
+    fn write_csv_record(&mut self, record: &TraceRecord) -> Result<(), Error> {
+        if self.csv.is_none() {
+            let appending = !self.initialized
+                && std::fs::metadata(&self.path).is_ok_and(|metadata| metadata.len() > 0);
+            let (file, num_params) = if appending {
+                // Continue with the parameter columns of the existing trace
+                let header = BufReader::new(File::open(&self.path)?)
+                    .lines()
+                    .map_while(Result::ok)
+                    .find(|line| !line.trim().is_empty() && !line.starts_with('#'))
+                    .unwrap_or_default();
+                let num_params = header
+                    .split(',')
+                    .filter(|column| column.trim().starts_with(PARAM_PREFIX))
+                    .count();
+                let file = OpenOptions::new().append(true).open(&self.path)?;
+                (file, num_params)
+            } else {
+                let mut file = create(&self.path)?;
+                let num_params = record.param.as_ref().map(|param| param.len()).unwrap_or(0);
+                write_csv_preamble(&mut file, self.trace.solver.as_deref())?;
+                write_csv_header(&mut file, num_params)?;
+                (file, num_params)
+            };
+            self.csv = Some((BufWriter::new(file), num_params));
+        }
+        let (writer, num_params) = self.csv.as_mut().unwrap();
+        record.write_csv(writer, *num_params)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the JSON file. When resuming a run, the records are appended to the existing trace.
+    fn write_json(&mut self) -> Result<(), Error> {
+        if !self.initialized && self.path.exists() {
+            let mut trace = Trace::read_json(BufReader::new(File::open(&self.path)?))?;
+            trace.records.append(&mut self.trace.records);
+            self.trace = trace;
+            self.initialized = true;
+        }
+        let mut writer = BufWriter::new(create(&self.path)?);
+        self.trace.write_json(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<I> TraceWriter<I>
+where
+    I: State,
+    for<'a> &'a I::Param: IntoIterator<Item = &'a I::Float>,
+{
+    /// Records the parameter vector of each iteration, which is possible for parameter vectors
+    /// which can be iterated over (such as `Vec<f64>` or `ndarray::Array1<f64>`).
+    ///
+    /// The CSV file has as many parameter columns as the parameter vector of the first record.
+    #[must_use]
+    pub fn with_params(self) -> Self {
+        self.params(|state| {
+            state
+                .get_param()
+                .map(|param| param.into_iter().map(|x| to_f64(*x)).collect())
+        })
+    }
+}
+
+impl<P, G, J, H, R, F> TraceWriter<IterState<P, G, J, H, R, F>>
+where
+    G: ArgminL2Norm<F>,
+    F: ArgminFloat,
+    IterState<P, G, J, H, R, F>: State<Float = F>,
+{
+    /// Records the L2 norm of the gradient stored in [`IterState`].
+    #[must_use]
+    pub fn with_gradient_norm(self) -> Self {
+        self.gradient_norm(|state| state.get_gradient().map(|grad| to_f64(grad.l2_norm())))
+    }
+}
+
+impl<I: State> Observe<I> for TraceWriter<I> {
+    /// Starts a new trace.
+    fn observe_init(&mut self, name: &str, _state: &I, _kv: &KV) -> Result<(), Error> {
+        self.trace = Trace::new();
+        self.trace.solver = Some(name.to_string());
+        self.initialized = true;
+        self.csv = None;
+        Ok(())
+    }
+
+    /// Writes a record of the state after an iteration.
+    fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
+        let record = TraceRecord::from_state(state, self.params, self.gradient_norm);
+        match self.format {
+            TraceFormat::Csv => self.write_csv_record(&record),
+            TraceFormat::Json => {
+                self.trace.records.push(record);
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes the JSON trace.
+    fn observe_final(&mut self, _state: &I) -> Result<(), Error> {
+        match self.format {
+            TraceFormat::Csv => Ok(()),
+            TraceFormat::Json => self.write_json(),
+        }
+    }
+}
+
+/// JSON representation of floats which also supports non-finite values.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonFloat {
+    Number(f64),
+    Text(String),
+    Null,
+}
+
+impl JsonFloat {
+    fn new(x: f64) -> Self {
+        if x.is_finite() {
+            JsonFloat::Number(x)
+        } else {
+            JsonFloat::Text(x.to_string())
+        }
+    }
+
+    fn value<E: serde::de::Error>(self) -> Result<f64, E> {
+        match self {
+            JsonFloat::Number(x) => Ok(x),
+            JsonFloat::Text(text) => text
+                .parse()
+                .map_err(|_| E::custom(format!("invalid float `{text}`"))),
+            JsonFloat::Null => Ok(f64::NAN),
+        }
+    }
+}
+
+mod float {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(x: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        JsonFloat::new(*x).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        JsonFloat::deserialize(deserializer)?.value()
+    }
+}
+
+mod opt_float {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(x: &Option<f64>, serializer: S) -> Result<S::Ok, S::Error> {
+        x.map(JsonFloat::new).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<f64>, D::Error> {
+        Option::<JsonFloat>::deserialize(deserializer)?
+            .map(JsonFloat::value)
+            .transpose()
+    }
+}
+
+mod opt_floats {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        x: &Option<Vec<f64>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        x.as_ref()
+            .map(|x| x.iter().map(|x| JsonFloat::new(*x)).collect::<Vec<_>>())
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<f64>>, D::Error> {
+        Option::<Vec<JsonFloat>>::deserialize(deserializer)?
+            .map(|x| x.into_iter().map(JsonFloat::value).collect())
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::observers::ObserverMode;
+    use argmin::core::{CostFunction, Executor, Gradient};
+    use argmin::solver::linesearch::MoreThuenteLineSearch;
+    use argmin::solver::quasinewton::LBFGS;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+
+    type TestState = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(rosenbrock_derivative(p))
+        }
+    }
+
+    fn example_trace() -> Trace {
+        Trace {
+            version: TRACE_VERSION,
+            solver: Some("Test, solver".to_string()),
+            records: vec![
+                TraceRecord {
+                    iter: 0,
+                    time: Some(0.25),
+                    cost: f64::INFINITY,
+                    best_cost: Some(f64::NEG_INFINITY),
+                    gradient_norm: Some(1.5),
+                    cost_count: Some(1),
+                    gradient_count: Some(2),
+                    hessian_count: Some(3),
+                    jacobian_count: Some(4),
+                    param: Some(vec![1.0, -2.5e-12]),
+                },
+                TraceRecord {
+                    iter: 1,
+                    cost: 0.1 + 0.2,
+                    param: Some(vec![0.5]),
+                    ..TraceRecord::default()
+                },
+                TraceRecord {
+                    iter: 2,
+                    cost: 7.0,
+                    ..TraceRecord::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let trace = example_trace();
+        let mut csv = vec![];
+        trace.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(
+            csv,
+            concat!(
+                "# argmin-trace 1\n",
+                "# solver: Test, solver\n",
+                "iter,time,cost,best_cost,gradient_norm,cost_count,gradient_count,",
+                "hessian_count,jacobian_count,param_0,param_1\n",
+                "0,0.25,inf,-inf,1.5,1,2,3,4,1,-0.0000000000025\n",
+                "1,,0.30000000000000004,,,,,,,0.5,\n",
+                "2,,7,,,,,,,,\n",
+            )
+        );
+        let mut expected = trace;
+        // Missing components are filled with NaN
+        expected.records[1].param = Some(vec![0.5, f64::NAN]);
+        let read = Trace::read_csv(csv.as_bytes()).unwrap();
+        assert_eq!(read.solver, expected.solver);
+        assert_eq!(read.records[0], expected.records[0]);
+        assert_eq!(read.records[1].param.as_ref().unwrap()[0], 0.5);
+        assert!(read.records[1].param.as_ref().unwrap()[1].is_nan());
+        assert_eq!(read.records[2], expected.records[2]);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut trace = example_trace();
+        trace.records[2].param = Some(vec![f64::NAN]);
+        let mut json = vec![];
+        trace.write_json(&mut json).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(value["version"], 1);
+        assert_eq!(value["records"][0]["cost"], "inf");
+        assert_eq!(value["records"][0]["best_cost"], "-inf");
+        assert_eq!(value["records"][2]["param"][0], "NaN");
+        assert!(value["records"][1].get("time").is_none());
+
+        let read = Trace::read_json(json.as_slice()).unwrap();
+        assert_eq!(read.records[..2], trace.records[..2]);
+        assert!(read.records[2].param.as_ref().unwrap()[0].is_nan());
+    }
+
+    #[test]
+    fn test_read_external() {
+        // Index column as written by pandas, integers written as floats, unknown columns and
+        // columns in a different order
+        let csv = concat!(
+            ",cost,iter,param_1,param_0,nfev\n",
+            "0,2.5,0,0.5,1.0,3\n",
+            "1,1.5,1.0,,,5\n",
+        );
+        let trace = Trace::read_csv(csv.as_bytes()).unwrap();
+        assert_eq!(trace.solver, None);
+        assert_eq!(trace.records.len(), 2);
+        assert_eq!(trace.records[0].param, Some(vec![1.0, 0.5]));
+        assert_eq!(trace.records[1].iter, 1);
+        assert_eq!(trace.records[1].param, None);
+        assert_eq!(trace.records[1].cost_count, None);
+
+        let json = r#"{"records": [{"iter": 3, "cost": 1.0, "best_cost": null}]}"#;
+        let trace = Trace::read_json(json.as_bytes()).unwrap();
+        assert_eq!(trace.version, TRACE_VERSION);
+        assert_eq!(trace.records[0].iter, 3);
+        assert_eq!(trace.records[0].best_cost, None);
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let cases = [
+            ("iter\n0\n", "CSV trace, line 1: missing column `cost`."),
+            (
+                "iter,cost,param_1\n",
+                concat!(
+                    "CSV trace, line 1: parameter columns must be numbered consecutively ",
+                    "starting at `param_0`."
+                ),
+            ),
+            (
+                "# comment\niter,cost\n0,1.0\n1,x\n",
+                "CSV trace, line 4: invalid value `x` of `cost`.",
+            ),
+            (
+                "iter,cost\n-1,1.0\n",
+                "CSV trace, line 2: invalid value `-1` of `iter`.",
+            ),
+            (
+                "iter,cost\n0\n",
+                "CSV trace, line 2: expected at least 2 fields, got 1.",
+            ),
+            (
+                "iter,cost\n0,\n",
+                "CSV trace, line 2: missing value of `cost`.",
+            ),
+            (
+                "# argmin-trace 2\niter,cost\n",
+                "Trace has version 2, but only versions up to 1 are supported.",
+            ),
+        ];
+        for (csv, msg) in cases {
+            let err = Trace::read_csv(csv.as_bytes()).unwrap_err();
+            assert_eq!(err.to_string(), msg);
+        }
+        let err = Trace::read_json(r#"{"version": 2, "records": []}"#.as_bytes()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Trace has version 2, but only versions up to 1 are supported."
+        );
+        let err = Trace::new().write("trace.txt").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot determine trace format of `trace.txt`, expected extension `csv` or `json`."
+        );
+    }
+
+    #[test]
+    fn test_trace_writer() {
+        let dir = std::env::temp_dir().join("argmin-observer-trace");
+        for file in ["writer.csv", "writer.json"] {
+            let path = dir.join(file);
+            let res = Executor::new(Rosenbrock {}, LBFGS::new(MoreThuenteLineSearch::new(), 7))
+                .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(5).counting(true))
+                .add_observer(
+                    TraceWriter::new(&path).with_params().with_gradient_norm(),
+                    ObserverMode::Every(2),
+                )
+                .run()
+                .unwrap();
+
+            let trace = Trace::read(&path).unwrap();
+            assert_eq!(trace.solver.as_deref(), Some("L-BFGS"));
+            let iters: Vec<u64> = trace.records.iter().map(|record| record.iter).collect();
+            assert_eq!(iters, vec![0, 2, 4]);
+            for record in trace.records.iter() {
+                assert_eq!(record.param.as_ref().unwrap().len(), 2);
+                assert!(record.gradient_norm.unwrap() > 0.0);
+                assert!(record.cost_count.unwrap() > 0);
+                assert!(record.best_cost.unwrap() <= record.cost);
+            }
+            let last = trace.records.last().unwrap();
+            assert!(last.cost_count.unwrap() <= res.func_counts()["cost_count"]);
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+
+    #[test]
+    fn test_trace_writer_append_without_init() {
+        let dir = std::env::temp_dir().join("argmin-observer-trace");
+        for file in ["append.csv", "append.json"] {
+            let path = dir.join(file);
+            let state: TestState = IterState::new().param(vec![1.0, 2.0]).cost(3.0);
+            let mut writer = TraceWriter::new(&path).with_params();
+            writer.observe_init("solver", &state, &KV::new()).unwrap();
+            writer.observe_iter(&state, &KV::new()).unwrap();
+            writer.observe_final(&state).unwrap();
+            drop(writer);
+
+            // A resumed run does not call `observe_init`
+            let mut writer = TraceWriter::new(&path).with_params();
+            let state = state.cost(1.0);
+            writer.observe_iter(&state, &KV::new()).unwrap();
+            writer.observe_final(&state).unwrap();
+
+            let trace = Trace::read(&path).unwrap();
+            assert_eq!(trace.solver.as_deref(), Some("solver"));
+            let costs: Vec<f64> = trace.records.iter().map(|record| record.cost).collect();
+            assert_eq!(costs, vec![3.0, 1.0]);
+            assert_eq!(trace.records[1].param, Some(vec![1.0, 2.0]));
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
@@ -3,7 +3,8 @@ use argmin_testfunctions::{
     beale, beale_derivative, beale_hessian, booth, booth_derivative, booth_hessian, bukin_n6,
     bukin_n6_derivative, bukin_n6_hessian, cross_in_tray, cross_in_tray_derivative,
     cross_in_tray_hessian, easom, easom_derivative, easom_hessian, eggholder, eggholder_derivative,
-    eggholder_hessian, goldsteinprice, goldsteinprice_derivative, goldsteinprice_hessian,
+    eggholder_hessian, goldsteinprice, goldsteinprice_derivative, goldsteinprice_hessian, griewank,
+    griewank_derivative, griewank_derivative_const, griewank_hessian, griewank_hessian_const,
     himmelblau, himmelblau_derivative, himmelblau_hessian, holder_table, holder_table_derivative,
     holder_table_hessian, levy, levy_derivative, levy_derivative_const, levy_hessian,
     levy_hessian_const, levy_n13, levy_n13_derivative, levy_n13_hessian, matyas, matyas_derivative,
@@ -12,7 +13,8 @@ use argmin_testfunctions::{
     rastrigin_derivative_const, rastrigin_hessian, rastrigin_hessian_const, rosenbrock,
     rosenbrock_derivative, rosenbrock_derivative_const, rosenbrock_hessian,
     rosenbrock_hessian_const, schaffer_n2, schaffer_n2_derivative, schaffer_n2_hessian,
-    schaffer_n4, schaffer_n4_derivative, schaffer_n4_hessian, sphere, sphere_derivative,
+    schaffer_n4, schaffer_n4_derivative, schaffer_n4_hessian, schwefel, schwefel_derivative,
+    schwefel_derivative_const, schwefel_hessian, schwefel_hessian_const, sphere, sphere_derivative,
     sphere_derivative_const, sphere_hessian, sphere_hessian_const, styblinski_tang,
     styblinski_tang_derivative, styblinski_tang_derivative_const, styblinski_tang_hessian,
     styblinski_tang_hessian_const, threehumpcamel, threehumpcamel_derivative,
@@ -183,6 +185,55 @@ pub fn bm_goldsteinprice(c: &mut Criterion) {
     g.finish();
 }
 
+pub fn bm_griewank(c: &mut Criterion) {
+    let mut g = c.benchmark_group("griewank");
+    // Test function
+    g.bench_function("griewank 02", |b| b.iter(|| griewank(black_box(P2))));
+    g.bench_function("griewank 10", |b| b.iter(|| griewank(black_box(P10))));
+    g.bench_function("griewank 20", |b| b.iter(|| griewank(black_box(P20))));
+    // Derivative
+    g.bench_function("griewank_derivative 02", |b| {
+        b.iter(|| griewank_derivative(black_box(P2)))
+    });
+    g.bench_function("griewank_derivative 10", |b| {
+        b.iter(|| griewank_derivative(black_box(P10)))
+    });
+    g.bench_function("griewank_derivative 20", |b| {
+        b.iter(|| griewank_derivative(black_box(P20)))
+    });
+    // Derivative const
+    g.bench_function("griewank_derivative_const 02", |b| {
+        b.iter(|| griewank_derivative_const(black_box(P2)))
+    });
+    g.bench_function("griewank_derivative_const 10", |b| {
+        b.iter(|| griewank_derivative_const(black_box(P10)))
+    });
+    g.bench_function("griewank_derivative_const 20", |b| {
+        b.iter(|| griewank_derivative_const(black_box(P20)))
+    });
+    // Hessian
+    g.bench_function("griewank_hessian 02", |b| {
+        b.iter(|| griewank_hessian(black_box(P2)))
+    });
+    g.bench_function("griewank_hessian 10", |b| {
+        b.iter(|| griewank_hessian(black_box(P10)))
+    });
+    g.bench_function("griewank_hessian 20", |b| {
+        b.iter(|| griewank_hessian(black_box(P20)))
+    });
+    // Hessian
+    g.bench_function("griewank_hessian_const 02", |b| {
+        b.iter(|| griewank_hessian_const(black_box(P2)))
+    });
+    g.bench_function("griewank_hessian_const 10", |b| {
+        b.iter(|| griewank_hessian_const(black_box(P10)))
+    });
+    g.bench_function("griewank_hessian_const 20", |b| {
+        b.iter(|| griewank_hessian_const(black_box(P20)))
+    });
+    g.finish();
+}
+
 pub fn bm_himmelblau(c: &mut Criterion) {
     let mut g = c.benchmark_group("himmelblau");
     // Test function
@@ -450,6 +501,55 @@ pub fn bm_schaffer_n4(c: &mut Criterion) {
     g.finish();
 }
 
+pub fn bm_schwefel(c: &mut Criterion) {
+    let mut g = c.benchmark_group("schwefel");
+    // Test function
+    g.bench_function("schwefel 02", |b| b.iter(|| schwefel(black_box(P2))));
+    g.bench_function("schwefel 10", |b| b.iter(|| schwefel(black_box(P10))));
+    g.bench_function("schwefel 20", |b| b.iter(|| schwefel(black_box(P20))));
+    // Derivative
+    g.bench_function("schwefel_derivative 02", |b| {
+        b.iter(|| schwefel_derivative(black_box(P2)))
+    });
+    g.bench_function("schwefel_derivative 10", |b| {
+        b.iter(|| schwefel_derivative(black_box(P10)))
+    });
+    g.bench_function("schwefel_derivative 20", |b| {
+        b.iter(|| schwefel_derivative(black_box(P20)))
+    });
+    // Derivative const
+    g.bench_function("schwefel_derivative_const 02", |b| {
+        b.iter(|| schwefel_derivative_const(black_box(P2)))
+    });
+    g.bench_function("schwefel_derivative_const 10", |b| {
+        b.iter(|| schwefel_derivative_const(black_box(P10)))
+    });
+    g.bench_function("schwefel_derivative_const 20", |b| {
+        b.iter(|| schwefel_derivative_const(black_box(P20)))
+    });
+    // Hessian
+    g.bench_function("schwefel_hessian 02", |b| {
+        b.iter(|| schwefel_hessian(black_box(P2)))
+    });
+    g.bench_function("schwefel_hessian 10", |b| {
+        b.iter(|| schwefel_hessian(black_box(P10)))
+    });
+    g.bench_function("schwefel_hessian 20", |b| {
+        b.iter(|| schwefel_hessian(black_box(P20)))
+    });
+    // Hessian
+    g.bench_function("schwefel_hessian_const 02", |b| {
+        b.iter(|| schwefel_hessian_const(black_box(P2)))
+    });
+    g.bench_function("schwefel_hessian_const 10", |b| {
+        b.iter(|| schwefel_hessian_const(black_box(P10)))
+    });
+    g.bench_function("schwefel_hessian_const 20", |b| {
+        b.iter(|| schwefel_hessian_const(black_box(P20)))
+    });
+    g.finish();
+}
+
 pub fn bm_sphere(c: &mut Criterion) {
     let mut g = c.benchmark_group("sphere");
     // Test function
@@ -630,6 +730,7 @@ criterion_group!(
     bm_easom,
     bm_eggholder,
     bm_goldsteinprice,
+    bm_griewank,
     bm_himmelblau,
     bm_holder_table,
     bm_levy,
@@ -641,6 +742,7 @@ criterion_group!(
     bm_rosenbrock,
     bm_schaffer_n2,
     bm_schaffer_n4,
+    bm_schwefel,
     bm_sphere,
     bm_styblinski_tang,
     bm_threehumpcamel,
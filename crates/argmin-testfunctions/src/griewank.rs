@@ -0,0 +1,316 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Griewank test function
+//!
+//! Defined as
+//!
+//! `f(x_1, x_2, ..., x_n) = 1 + \sum_{i=1}^{n} \frac{x_i^2}{4000} - \prod_{i=1}^{n}
+//! cos(\frac{x_i}{\sqrt{i}})`
+//!
+//! where `x_i \in [-600, 600]`.
+//!
+//! The global minimum is at `f(x_1, x_2, ..., x_n) = f(0, 0, ..., 0) = 0`.
+
+use num::{Float, FromPrimitive};
+use std::iter::Sum;
+
+/// Griewank test function
+///
+/// Defined as
+///
+/// `f(x_1, x_2, ..., x_n) = 1 + \sum_{i=1}^{n} \frac{x_i^2}{4000} - \prod_{i=1}^{n}
+/// cos(\frac{x_i}{\sqrt{i}})`
+///
+/// where `x_i \in [-600, 600]`.
+///
+/// The global minimum is at `f(x_1, x_2, ..., x_n) = f(0, 0, ..., 0) = 0`.
+pub fn griewank<T>(param: &[T]) -> T
+where
+    T: Float + FromPrimitive + Sum,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+    let n4000 = T::from_f64(4000.0).unwrap();
+
+    let sum: T = param.iter().map(|x| x.powi(2) / n4000).sum();
+    let prod = scaled(param)
+        .map(|(x, sqrt_i)| (x / sqrt_i).cos())
+        .fold(n1, |acc, c| acc * c);
+    n1 + sum - prod
+}
+
+/// Derivative of Griewank test function
+pub fn griewank_derivative<T>(param: &[T]) -> Vec<T>
+where
+    T: Float + FromPrimitive + Sum,
+{
+    let n2000 = T::from_f64(2000.0).unwrap();
+
+    let (cos, sin) = cos_sin(param);
+    scaled(param)
+        .enumerate()
+        .map(|(k, (x, sqrt_k))| x / n2000 + sin[k] / sqrt_k * product_without(&cos, &[k]))
+        .collect()
+}
+
+/// Derivative of Griewank test function
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn griewank_derivative_const<const N: usize, T>(param: &[T; N]) -> [T; N]
+where
+    T: Float + FromPrimitive + Sum,
+{
+    let n0 = T::from_f64(0.0).unwrap();
+    let n2000 = T::from_f64(2000.0).unwrap();
+
+    let (cos, sin) = cos_sin(param);
+    let mut out = [n0; N];
+
+    scaled(param)
+        .zip(out.iter_mut())
+        .enumerate()
+        .map(|(k, ((x, sqrt_k), o))| *o = x / n2000 + sin[k] / sqrt_k * product_without(&cos, &[k]))
+        .count();
+
+    out
+}
+
+/// Hessian of Griewank test function
+pub fn griewank_hessian<T>(param: &[T]) -> Vec<Vec<T>>
+where
+    T: Float + FromPrimitive + Sum,
+{
+    let n0 = T::from_f64(0.0).unwrap();
+
+    let n = param.len();
+    let mut out = vec![vec![n0; n]; n];
+
+    let (cos, sin) = cos_sin(param);
+    for (k, row) in out.iter_mut().enumerate() {
+        for (l, o) in row.iter_mut().enumerate() {
+            *o = hessian_entry(&cos, &sin, k, l);
+        }
+    }
+
+    out
+}
+
+/// Hessian of Griewank test function
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn griewank_hessian_const<const N: usize, T>(param: &[T; N]) -> [[T; N]; N]
+where
+    T: Float + FromPrimitive + Sum,
+{
+    let n0 = T::from_f64(0.0).unwrap();
+
+    let mut out = [[n0; N]; N];
+
+    let (cos, sin) = cos_sin(param);
+    for (k, row) in out.iter_mut().enumerate() {
+        for (l, o) in row.iter_mut().enumerate() {
+            *o = hessian_entry(&cos, &sin, k, l);
+        }
+    }
+
+    out
+}
+
+/// Iterates over the parameters `x_i` together with `\sqrt{i}` (starting with `i = 1`)
+fn scaled<T>(param: &[T]) -> impl Iterator<Item = (T, T)> + '_
+where
+    T: Float + FromPrimitive,
+{
+    param
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (*x, T::from_usize(i + 1).unwrap().sqrt()))
+}
+
+/// Returns `cos(x_i / \sqrt{i})` and `sin(x_i / \sqrt{i})` for all parameters
+fn cos_sin<T>(param: &[T]) -> (Vec<T>, Vec<T>)
+where
+    T: Float + FromPrimitive,
+{
+    scaled(param)
+        .map(|(x, sqrt_i)| ((x / sqrt_i).cos(), (x / sqrt_i).sin()))
+        .unzip()
+}
+
+/// Product of all `values` except for those at the indices in `skip`
+///
+/// The product is computed explicitly instead of dividing the full product, since the factors may
+/// be zero.
+fn product_without<T: Float>(values: &[T], skip: &[usize]) -> T {
+    values
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| !skip.contains(j))
+        .fold(T::one(), |acc, (_, v)| acc * *v)
+}
+
+/// Entry `(k, l)` of the Hessian, given `cos(x_i / \sqrt{i})` and `sin(x_i / \sqrt{i})`
+fn hessian_entry<T>(cos: &[T], sin: &[T], k: usize, l: usize) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let n4000 = T::from_f64(4000.0).unwrap();
+
+    let sqrt_k = T::from_usize(k + 1).unwrap().sqrt();
+    if k == l {
+        T::from_f64(2.0).unwrap() / n4000 + cos[k] / sqrt_k.powi(2) * product_without(cos, &[k])
+    } else {
+        let sqrt_l = T::from_usize(l + 1).unwrap().sqrt();
+        -sin[k] / sqrt_k * sin[l] / sqrt_l * product_without(cos, &[k, l])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+    use std::{f32, f64};
+
+    #[test]
+    fn test_griewank_optimum() {
+        assert_relative_eq!(
+            griewank(&[0.0_f32, 0.0_f32, 0.0_f32]),
+            0.0,
+            epsilon = f32::EPSILON
+        );
+        assert_relative_eq!(
+            griewank(&[0.0_f64, 0.0_f64, 0.0_f64]),
+            0.0,
+            epsilon = f64::EPSILON
+        );
+
+        let deriv = griewank_derivative(&[0.0_f64, 0.0_f64, 0.0_f64]);
+        for d in deriv {
+            assert_relative_eq!(d, 0.0, epsilon = f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_griewank_values() {
+        // f(x) = 1 + 1/4000 - cos(1)
+        assert_relative_eq!(
+            griewank(&[1.0_f64]),
+            1.0 + 1.0 / 4000.0 - 1.0_f64.cos(),
+            epsilon = f64::EPSILON
+        );
+        // The second parameter is scaled by 1/sqrt(2)
+        assert_relative_eq!(
+            griewank(&[0.0_f64, 2.0_f64.sqrt()]),
+            1.0 + 2.0 / 4000.0 - 1.0_f64.cos(),
+            epsilon = f64::EPSILON
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_griewank_derivative_finitediff(a in -600.0..600.0,
+                                               b in -600.0..600.0,
+                                               c in -600.0..600.0,
+                                               d in -600.0..600.0,
+                                               e in -600.0..600.0,
+                                               f in -600.0..600.0,
+                                               g in -600.0..600.0,
+                                               h in -600.0..600.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let derivative = griewank_derivative(&param);
+            let derivative_fd = Vec::from(param).central_diff(&|x| griewank(x));
+            for i in 0..derivative.len() {
+                assert_relative_eq!(
+                    derivative[i],
+                    derivative_fd[i],
+                    epsilon = 1e-5,
+                    max_relative = 1e-2
+                );
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_griewank_derivative_const_finitediff(a in -600.0..600.0,
+                                                     b in -600.0..600.0,
+                                                     c in -600.0..600.0,
+                                                     d in -600.0..600.0,
+                                                     e in -600.0..600.0,
+                                                     f in -600.0..600.0,
+                                                     g in -600.0..600.0,
+                                                     h in -600.0..600.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let derivative = griewank_derivative_const(&param);
+            let derivative_fd = Vec::from(param).central_diff(&|x| griewank(x));
+            for i in 0..derivative.len() {
+                assert_relative_eq!(
+                    derivative[i],
+                    derivative_fd[i],
+                    epsilon = 1e-5,
+                    max_relative = 1e-2
+                );
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_griewank_hessian_finitediff(a in -600.0..600.0,
+                                            b in -600.0..600.0,
+                                            c in -600.0..600.0,
+                                            d in -600.0..600.0,
+                                            e in -600.0..600.0,
+                                            f in -600.0..600.0,
+                                            g in -600.0..600.0,
+                                            h in -600.0..600.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let hessian = griewank_hessian(&param);
+            let hessian_fd = Vec::from(param).central_hessian(&|x| griewank_derivative(x));
+            for i in 0..hessian.len() {
+                for j in 0..hessian[i].len() {
+                    assert_relative_eq!(
+                        hessian[i][j],
+                        hessian_fd[i][j],
+                        epsilon = 1e-5,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_griewank_hessian_const_finitediff(a in -600.0..600.0,
+                                                  b in -600.0..600.0,
+                                                  c in -600.0..600.0,
+                                                  d in -600.0..600.0,
+                                                  e in -600.0..600.0,
+                                                  f in -600.0..600.0,
+                                                  g in -600.0..600.0,
+                                                  h in -600.0..600.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let hessian = griewank_hessian_const(&param);
+            let hessian_fd = Vec::from(param).central_hessian(&|x| griewank_derivative(x));
+            for i in 0..hessian.len() {
+                for j in 0..hessian[i].len() {
+                    assert_relative_eq!(
+                        hessian[i][j],
+                        hessian_fd[i][j],
+                        epsilon = 1e-5,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,692 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Constrained test problems of the Hock-Schittkowski collection
+//!
+//! A subset of the problems of
+//!
+//! W. Hock and K. Schittkowski. Test Examples for Nonlinear Programming Codes. Lecture Notes in
+//! Economics and Mathematical Systems, Vol. 187, Springer, 1981.
+//!
+//! Each problem `hsNNN` consists of the objective function `hsNNN` with its derivative
+//! `hsNNN_derivative` and Hessian `hsNNN_hessian`, the constraints `hsNNN_constraints` with their
+//! Jacobian `hsNNN_constraints_jacobian` and a [`ConstrainedTestProblem`] `HSNNN` which holds the
+//! initial parameter vector, the bounds and the known optimum.
+//!
+//! The constraints are returned as a vector `c(x)`, where the first
+//! [`num_equality`](`ConstrainedTestProblem::num_equality`) constraints are equality constraints
+//! `c_i(x) = 0` and all others are inequality constraints `c_i(x) >= 0`. Bounds on the parameters
+//! are not part of the constraints.
+
+use num::{Float, FromPrimitive};
+
+/// Description of a constrained test problem with `N` parameters and `M` constraints
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConstrainedTestProblem<const N: usize, const M: usize> {
+    /// Initial parameter vector (may be infeasible)
+    pub initial_param: [f64; N],
+    /// Lower bounds of the parameters (`-inf` if unbounded)
+    pub lower_bound: [f64; N],
+    /// Upper bounds of the parameters (`inf` if unbounded)
+    pub upper_bound: [f64; N],
+    /// Number of equality constraints, which precede the inequality constraints
+    pub num_equality: usize,
+    /// Parameter vector at the known optimum
+    pub optimum: [f64; N],
+    /// Objective function value at the known optimum
+    pub optimal_cost: f64,
+}
+
+impl<const N: usize, const M: usize> ConstrainedTestProblem<N, M> {
+    /// Number of inequality constraints
+    pub fn num_inequality(&self) -> usize {
+        M - self.num_equality
+    }
+
+    /// Returns `true` if `param` lies within the bounds and `constraints` (the constraints
+    /// evaluated at `param`) are satisfied up to `tol`.
+    pub fn is_feasible(&self, param: &[f64; N], constraints: &[f64; M], tol: f64) -> bool {
+        let within_bounds = param
+            .iter()
+            .zip(self.lower_bound.iter().zip(self.upper_bound.iter()))
+            .all(|(x, (l, u))| *x >= l - tol && *x <= u + tol);
+        let (equality, inequality) = constraints.split_at(self.num_equality);
+        within_bounds
+            && equality.iter().all(|c| c.abs() <= tol)
+            && inequality.iter().all(|c| *c >= -tol)
+    }
+}
+
+const INF: f64 = f64::INFINITY;
+
+/// Hock-Schittkowski problem 6
+///
+/// `min (1 - x_1)^2` subject to `10 * (x_2 - x_1^2) = 0`.
+///
+/// The minimum is at `f(x_1, x_2) = f(1, 1) = 0`.
+pub const HS006: ConstrainedTestProblem<2, 1> = ConstrainedTestProblem {
+    initial_param: [-1.2, 1.0],
+    lower_bound: [-INF; 2],
+    upper_bound: [INF; 2],
+    num_equality: 1,
+    optimum: [1.0, 1.0],
+    optimal_cost: 0.0,
+};
+
+/// Objective function of Hock-Schittkowski problem 6 (see [`HS006`])
+pub fn hs006<T>(param: &[T; 2]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, _] = *param;
+    (T::one() - x1).powi(2)
+}
+
+/// Derivative of the objective function of Hock-Schittkowski problem 6
+pub fn hs006_derivative<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, _] = *param;
+    let n2 = T::from_f64(2.0).unwrap();
+    [-n2 * (T::one() - x1), T::zero()]
+}
+
+/// Hessian of the objective function of Hock-Schittkowski problem 6
+pub fn hs006_hessian<T>(_param: &[T; 2]) -> [[T; 2]; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n0 = T::zero();
+    let n2 = T::from_f64(2.0).unwrap();
+    [[n2, n0], [n0, n0]]
+}
+
+/// Constraints of Hock-Schittkowski problem 6 (one equality constraint)
+pub fn hs006_constraints<T>(param: &[T; 2]) -> [T; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2] = *param;
+    let n10 = T::from_f64(10.0).unwrap();
+    [n10 * (x2 - x1.powi(2))]
+}
+
+/// Jacobian of the constraints of Hock-Schittkowski problem 6
+pub fn hs006_constraints_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, _] = *param;
+    let n10 = T::from_f64(10.0).unwrap();
+    let n20 = T::from_f64(20.0).unwrap();
+    [[-n20 * x1, n10]]
+}
+
+/// Hock-Schittkowski problem 21
+///
+/// `min 0.01 * x_1^2 + x_2^2 - 100` subject to `10 * x_1 - x_2 - 10 >= 0`, `2 <= x_1 <= 50` and
+/// `-50 <= x_2 <= 50`.
+///
+/// The minimum is at `f(x_1, x_2) = f(2, 0) = -99.96`.
+pub const HS021: ConstrainedTestProblem<2, 1> = ConstrainedTestProblem {
+    initial_param: [-1.0, -1.0],
+    lower_bound: [2.0, -50.0],
+    upper_bound: [50.0, 50.0],
+    num_equality: 0,
+    optimum: [2.0, 0.0],
+    optimal_cost: -99.96,
+};
+
+/// Objective function of Hock-Schittkowski problem 21 (see [`HS021`])
+pub fn hs021<T>(param: &[T; 2]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2] = *param;
+    let n0_01 = T::from_f64(0.01).unwrap();
+    let n100 = T::from_f64(100.0).unwrap();
+    n0_01 * x1.powi(2) + x2.powi(2) - n100
+}
+
+/// Derivative of the objective function of Hock-Schittkowski problem 21
+pub fn hs021_derivative<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2] = *param;
+    let n0_02 = T::from_f64(0.02).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+    [n0_02 * x1, n2 * x2]
+}
+
+/// Hessian of the objective function of Hock-Schittkowski problem 21
+pub fn hs021_hessian<T>(_param: &[T; 2]) -> [[T; 2]; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n0 = T::zero();
+    let n0_02 = T::from_f64(0.02).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+    [[n0_02, n0], [n0, n2]]
+}
+
+/// Constraints of Hock-Schittkowski problem 21 (one inequality constraint)
+pub fn hs021_constraints<T>(param: &[T; 2]) -> [T; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2] = *param;
+    let n10 = T::from_f64(10.0).unwrap();
+    [n10 * x1 - x2 - n10]
+}
+
+/// Jacobian of the constraints of Hock-Schittkowski problem 21
+pub fn hs021_constraints_jacobian<T>(_param: &[T; 2]) -> [[T; 2]; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n10 = T::from_f64(10.0).unwrap();
+    [[n10, -T::one()]]
+}
+
+/// Hock-Schittkowski problem 28
+///
+/// `min (x_1 + x_2)^2 + (x_2 + x_3)^2` subject to `x_1 + 2 * x_2 + 3 * x_3 - 1 = 0`.
+///
+/// The minimum is at `f(x_1, x_2, x_3) = f(0.5, -0.5, 0.5) = 0`.
+pub const HS028: ConstrainedTestProblem<3, 1> = ConstrainedTestProblem {
+    initial_param: [-4.0, 1.0, 1.0],
+    lower_bound: [-INF; 3],
+    upper_bound: [INF; 3],
+    num_equality: 1,
+    optimum: [0.5, -0.5, 0.5],
+    optimal_cost: 0.0,
+};
+
+/// Objective function of Hock-Schittkowski problem 28 (see [`HS028`])
+pub fn hs028<T>(param: &[T; 3]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    (x1 + x2).powi(2) + (x2 + x3).powi(2)
+}
+
+/// Derivative of the objective function of Hock-Schittkowski problem 28
+pub fn hs028_derivative<T>(param: &[T; 3]) -> [T; 3]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    let n2 = T::from_f64(2.0).unwrap();
+    [
+        n2 * (x1 + x2),
+        n2 * (x1 + x2) + n2 * (x2 + x3),
+        n2 * (x2 + x3),
+    ]
+}
+
+/// Hessian of the objective function of Hock-Schittkowski problem 28
+pub fn hs028_hessian<T>(_param: &[T; 3]) -> [[T; 3]; 3]
+where
+    T: Float + FromPrimitive,
+{
+    let n0 = T::zero();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    [[n2, n2, n0], [n2, n4, n2], [n0, n2, n2]]
+}
+
+/// Constraints of Hock-Schittkowski problem 28 (one equality constraint)
+pub fn hs028_constraints<T>(param: &[T; 3]) -> [T; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    let n2 = T::from_f64(2.0).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+    [x1 + n2 * x2 + n3 * x3 - T::one()]
+}
+
+/// Jacobian of the constraints of Hock-Schittkowski problem 28
+pub fn hs028_constraints_jacobian<T>(_param: &[T; 3]) -> [[T; 3]; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+    [[T::one(), n2, n3]]
+}
+
+/// Hock-Schittkowski problem 35
+///
+/// `min 9 - 8 * x_1 - 6 * x_2 - 4 * x_3 + 2 * x_1^2 + 2 * x_2^2 + x_3^2 + 2 * x_1 * x_2 + 2 * x_1
+/// * x_3` subject to `3 - x_1 - x_2 - 2 * x_3 >= 0` and `x_i >= 0`.
+///
+/// The minimum is at `f(x_1, x_2, x_3) = f(4/3, 7/9, 4/9) = 1/9`.
+pub const HS035: ConstrainedTestProblem<3, 1> = ConstrainedTestProblem {
+    initial_param: [0.5, 0.5, 0.5],
+    lower_bound: [0.0; 3],
+    upper_bound: [INF; 3],
+    num_equality: 0,
+    optimum: [4.0 / 3.0, 7.0 / 9.0, 4.0 / 9.0],
+    optimal_cost: 1.0 / 9.0,
+};
+
+/// Objective function of Hock-Schittkowski problem 35 (see [`HS035`])
+pub fn hs035<T>(param: &[T; 3]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    let n6 = T::from_f64(6.0).unwrap();
+    let n8 = T::from_f64(8.0).unwrap();
+    let n9 = T::from_f64(9.0).unwrap();
+    n9 - n8 * x1 - n6 * x2 - n4 * x3
+        + n2 * x1.powi(2)
+        + n2 * x2.powi(2)
+        + x3.powi(2)
+        + n2 * x1 * x2
+        + n2 * x1 * x3
+}
+
+/// Derivative of the objective function of Hock-Schittkowski problem 35
+pub fn hs035_derivative<T>(param: &[T; 3]) -> [T; 3]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    let n6 = T::from_f64(6.0).unwrap();
+    let n8 = T::from_f64(8.0).unwrap();
+    [
+        -n8 + n4 * x1 + n2 * x2 + n2 * x3,
+        -n6 + n4 * x2 + n2 * x1,
+        -n4 + n2 * x3 + n2 * x1,
+    ]
+}
+
+/// Hessian of the objective function of Hock-Schittkowski problem 35
+pub fn hs035_hessian<T>(_param: &[T; 3]) -> [[T; 3]; 3]
+where
+    T: Float + FromPrimitive,
+{
+    let n0 = T::zero();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    [[n4, n2, n2], [n2, n4, n0], [n2, n0, n2]]
+}
+
+/// Constraints of Hock-Schittkowski problem 35 (one inequality constraint)
+pub fn hs035_constraints<T>(param: &[T; 3]) -> [T; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    let n2 = T::from_f64(2.0).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+    [n3 - x1 - x2 - n2 * x3]
+}
+
+/// Jacobian of the constraints of Hock-Schittkowski problem 35
+pub fn hs035_constraints_jacobian<T>(_param: &[T; 3]) -> [[T; 3]; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+    [[-T::one(), -T::one(), -n2]]
+}
+
+/// Hock-Schittkowski problem 71
+///
+/// `min x_1 * x_4 * (x_1 + x_2 + x_3) + x_3` subject to `x_1^2 + x_2^2 + x_3^2 + x_4^2 - 40 = 0`,
+/// `x_1 * x_2 * x_3 * x_4 - 25 >= 0` and `1 <= x_i <= 5`.
+///
+/// The minimum is at `f(x_1, x_2, x_3, x_4) = f(1, 4.742999, 3.821150, 1.379408) = 17.014017`.
+pub const HS071: ConstrainedTestProblem<4, 2> = ConstrainedTestProblem {
+    initial_param: [1.0, 5.0, 5.0, 1.0],
+    lower_bound: [1.0; 4],
+    upper_bound: [5.0; 4],
+    num_equality: 1,
+    optimum: [1.0, 4.742999637, 3.821149984, 1.379408293],
+    optimal_cost: 17.0140172867,
+};
+
+/// Objective function of Hock-Schittkowski problem 71 (see [`HS071`])
+pub fn hs071<T>(param: &[T; 4]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    x1 * x4 * (x1 + x2 + x3) + x3
+}
+
+/// Derivative of the objective function of Hock-Schittkowski problem 71
+pub fn hs071_derivative<T>(param: &[T; 4]) -> [T; 4]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let n2 = T::from_f64(2.0).unwrap();
+    [
+        x4 * (n2 * x1 + x2 + x3),
+        x1 * x4,
+        x1 * x4 + T::one(),
+        x1 * (x1 + x2 + x3),
+    ]
+}
+
+/// Hessian of the objective function of Hock-Schittkowski problem 71
+pub fn hs071_hessian<T>(param: &[T; 4]) -> [[T; 4]; 4]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let n0 = T::zero();
+    let n2 = T::from_f64(2.0).unwrap();
+    let h14 = n2 * x1 + x2 + x3;
+    [
+        [n2 * x4, x4, x4, h14],
+        [x4, n0, n0, x1],
+        [x4, n0, n0, x1],
+        [h14, x1, x1, n0],
+    ]
+}
+
+/// Constraints of Hock-Schittkowski problem 71 (one equality and one inequality constraint)
+pub fn hs071_constraints<T>(param: &[T; 4]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let n25 = T::from_f64(25.0).unwrap();
+    let n40 = T::from_f64(40.0).unwrap();
+    [
+        x1.powi(2) + x2.powi(2) + x3.powi(2) + x4.powi(2) - n40,
+        x1 * x2 * x3 * x4 - n25,
+    ]
+}
+
+/// Jacobian of the constraints of Hock-Schittkowski problem 71
+pub fn hs071_constraints_jacobian<T>(param: &[T; 4]) -> [[T; 4]; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let n2 = T::from_f64(2.0).unwrap();
+    [
+        [n2 * x1, n2 * x2, n2 * x3, n2 * x4],
+        [x2 * x3 * x4, x1 * x3 * x4, x1 * x2 * x4, x1 * x2 * x3],
+    ]
+}
+
+/// Hock-Schittkowski problem 76
+///
+/// `min x_1^2 + 0.5 * x_2^2 + x_3^2 + 0.5 * x_4^2 - x_1 * x_3 + x_3 * x_4 - x_1 - 3 * x_2 + x_3 -
+/// x_4` subject to `5 - x_1 - 2 * x_2 - x_3 - x_4 >= 0`, `4 - 3 * x_1 - x_2 - 2 * x_3 + x_4 >= 0`,
+/// `x_2 + 4 * x_3 - 1.5 >= 0` and `x_i >= 0`.
+///
+/// The minimum is at `f(x_1, x_2, x_3, x_4) = f(3/11, 23/11, 0, 6/11) = -103/22`.
+pub const HS076: ConstrainedTestProblem<4, 3> = ConstrainedTestProblem {
+    initial_param: [0.5, 0.5, 0.5, 0.5],
+    lower_bound: [0.0; 4],
+    upper_bound: [INF; 4],
+    num_equality: 0,
+    optimum: [3.0 / 11.0, 23.0 / 11.0, 0.0, 6.0 / 11.0],
+    optimal_cost: -103.0 / 22.0,
+};
+
+/// Objective function of Hock-Schittkowski problem 76 (see [`HS076`])
+pub fn hs076<T>(param: &[T; 4]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let n0_5 = T::from_f64(0.5).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+    x1.powi(2) + n0_5 * x2.powi(2) + x3.powi(2) + n0_5 * x4.powi(2) - x1 * x3 + x3 * x4
+        - x1
+        - n3 * x2
+        + x3
+        - x4
+}
+
+/// Derivative of the objective function of Hock-Schittkowski problem 76
+pub fn hs076_derivative<T>(param: &[T; 4]) -> [T; 4]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let n2 = T::from_f64(2.0).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+    [
+        n2 * x1 - x3 - T::one(),
+        x2 - n3,
+        n2 * x3 - x1 + x4 + T::one(),
+        x4 + x3 - T::one(),
+    ]
+}
+
+/// Hessian of the objective function of Hock-Schittkowski problem 76
+pub fn hs076_hessian<T>(_param: &[T; 4]) -> [[T; 4]; 4]
+where
+    T: Float + FromPrimitive,
+{
+    let n0 = T::zero();
+    let n1 = T::one();
+    let n2 = T::from_f64(2.0).unwrap();
+    [
+        [n2, n0, -n1, n0],
+        [n0, n1, n0, n0],
+        [-n1, n0, n2, n1],
+        [n0, n0, n1, n1],
+    ]
+}
+
+/// Constraints of Hock-Schittkowski problem 76 (three inequality constraints)
+pub fn hs076_constraints<T>(param: &[T; 4]) -> [T; 3]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let n1_5 = T::from_f64(1.5).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    let n5 = T::from_f64(5.0).unwrap();
+    [
+        n5 - x1 - n2 * x2 - x3 - x4,
+        n4 - n3 * x1 - x2 - n2 * x3 + x4,
+        x2 + n4 * x3 - n1_5,
+    ]
+}
+
+/// Jacobian of the constraints of Hock-Schittkowski problem 76
+pub fn hs076_constraints_jacobian<T>(_param: &[T; 4]) -> [[T; 4]; 3]
+where
+    T: Float + FromPrimitive,
+{
+    let n0 = T::zero();
+    let n1 = T::one();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    [[-n1, -n2, -n1, -n1], [-n3, -n1, -n2, n1], [n0, n1, n4, n0]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+
+    /// Checks the known optimum as well as the derivatives at `param` against finite differences
+    macro_rules! check_problem {
+        ($problem:expr, $param:expr, $f:ident, $derivative:ident, $hessian:ident,
+         $constraints:ident, $jacobian:ident) => {{
+            let problem = $problem;
+            let param = $param;
+            let optimum = problem.optimum;
+
+            assert_relative_eq!($f(&optimum), problem.optimal_cost, epsilon = 1e-7);
+            assert!(problem.is_feasible(&optimum, &$constraints(&optimum), 1e-7));
+            assert_eq!(
+                problem.num_equality + problem.num_inequality(),
+                $constraints(&optimum).len()
+            );
+
+            let to_array = |x: &Vec<f64>| x.clone().try_into().unwrap();
+
+            let derivative = $derivative(&param);
+            let derivative_fd = Vec::from(param).central_diff(&|x| $f(&to_array(x)));
+            for i in 0..derivative.len() {
+                assert_relative_eq!(
+                    derivative[i],
+                    derivative_fd[i],
+                    epsilon = 1e-5,
+                    max_relative = 1e-2
+                );
+            }
+
+            let hessian = $hessian(&param);
+            let hessian_fd =
+                Vec::from(param).central_jacobian(&|x| $derivative(&to_array(x)).to_vec());
+            for i in 0..hessian.len() {
+                for j in 0..hessian[i].len() {
+                    assert_relative_eq!(
+                        hessian[i][j],
+                        hessian_fd[i][j],
+                        epsilon = 1e-5,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+
+            // `finitediff` returns the transposed Jacobian
+            let jacobian = $jacobian(&param);
+            let jacobian_fd =
+                Vec::from(param).central_jacobian(&|x| $constraints(&to_array(x)).to_vec());
+            for i in 0..jacobian.len() {
+                for j in 0..jacobian[i].len() {
+                    assert_relative_eq!(
+                        jacobian[i][j],
+                        jacobian_fd[j][i],
+                        epsilon = 1e-5,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }};
+    }
+
+    #[test]
+    fn test_is_feasible() {
+        assert!(HS071.is_feasible(&HS071.initial_param, &[0.0, 0.0], 0.0));
+        // Equality constraint violated
+        assert!(!HS071.is_feasible(&HS071.initial_param, &[1e-3, 0.0], 1e-4));
+        // Inequality constraint violated
+        assert!(!HS071.is_feasible(&HS071.initial_param, &[0.0, -1e-3], 1e-4));
+        // Bounds violated
+        assert!(!HS071.is_feasible(&[0.5, 5.0, 5.0, 1.0], &[0.0, 0.0], 1e-4));
+        assert_eq!(HS071.num_inequality(), 1);
+    }
+
+    proptest! {
+        #[test]
+        fn test_hs006(a in -5.0..5.0, b in -5.0..5.0) {
+            check_problem!(
+                HS006,
+                [a, b],
+                hs006,
+                hs006_derivative,
+                hs006_hessian,
+                hs006_constraints,
+                hs006_constraints_jacobian
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_hs021(a in 2.0..50.0, b in -50.0..50.0) {
+            check_problem!(
+                HS021,
+                [a, b],
+                hs021,
+                hs021_derivative,
+                hs021_hessian,
+                hs021_constraints,
+                hs021_constraints_jacobian
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_hs028(a in -5.0..5.0, b in -5.0..5.0, c in -5.0..5.0) {
+            check_problem!(
+                HS028,
+                [a, b, c],
+                hs028,
+                hs028_derivative,
+                hs028_hessian,
+                hs028_constraints,
+                hs028_constraints_jacobian
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_hs035(a in 0.0..5.0, b in 0.0..5.0, c in 0.0..5.0) {
+            check_problem!(
+                HS035,
+                [a, b, c],
+                hs035,
+                hs035_derivative,
+                hs035_hessian,
+                hs035_constraints,
+                hs035_constraints_jacobian
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_hs071(a in 1.0..5.0, b in 1.0..5.0, c in 1.0..5.0, d in 1.0..5.0) {
+            check_problem!(
+                HS071,
+                [a, b, c, d],
+                hs071,
+                hs071_derivative,
+                hs071_hessian,
+                hs071_constraints,
+                hs071_constraints_jacobian
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_hs076(a in 0.0..5.0, b in 0.0..5.0, c in 0.0..5.0, d in 0.0..5.0) {
+            check_problem!(
+                HS076,
+                [a, b, c, d],
+                hs076,
+                hs076_derivative,
+                hs076_hessian,
+                hs076_constraints,
+                hs076_constraints_jacobian
+            );
+        }
+    }
+}
@@ -0,0 +1,558 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Nonlinear least-squares test problems
+//!
+//! A subset of the problems of
+//!
+//! J. J. Moré, B. S. Garbow and K. E. Hillstrom. Testing Unconstrained Optimization Software.
+//! ACM Transactions on Mathematical Software, 7(1), 1981.
+//!
+//! and of the nonlinear regression problems of the NIST Statistical Reference Datasets (StRD),
+//! which come with certified solutions.
+//!
+//! Each problem `<name>` consists of the residuals `<name>_residuals` and their Jacobian
+//! `<name>_jacobian` as well as a [`LeastSquaresTestProblem`] `<NAME>` which holds the initial
+//! parameter vector and the known solution. The objective is the sum of squared residuals
+//! `f(x) = \sum_{i=1}^{m} r_i(x)^2`. For the NIST problems, the residuals are `r_i = y_i -
+//! model(x_i)`.
+
+use num::{Float, FromPrimitive};
+
+/// Description of a least-squares test problem with `N` parameters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LeastSquaresTestProblem<const N: usize> {
+    /// Initial parameter vector (for the NIST problems the first of the certified starting values)
+    pub initial_param: [f64; N],
+    /// Parameter vector at the known solution
+    pub solution: [f64; N],
+    /// Sum of squared residuals at the known solution
+    pub residual_sum_of_squares: f64,
+}
+
+/// Rosenbrock function as least-squares problem (Moré, Garbow and Hillstrom, problem 1)
+///
+/// `r_1 = 10 * (x_2 - x_1^2)`, `r_2 = 1 - x_1`.
+pub const ROSENBROCK: LeastSquaresTestProblem<2> = LeastSquaresTestProblem {
+    initial_param: [-1.2, 1.0],
+    solution: [1.0, 1.0],
+    residual_sum_of_squares: 0.0,
+};
+
+/// Residuals of the Rosenbrock function (see [`ROSENBROCK`])
+pub fn rosenbrock_residuals<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2] = *param;
+    let n10 = T::from_f64(10.0).unwrap();
+    [n10 * (x2 - x1.powi(2)), T::one() - x1]
+}
+
+/// Jacobian of the residuals of the Rosenbrock function
+pub fn rosenbrock_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, _] = *param;
+    let n10 = T::from_f64(10.0).unwrap();
+    let n20 = T::from_f64(20.0).unwrap();
+    [[-n20 * x1, n10], [-T::one(), T::zero()]]
+}
+
+/// Freudenstein and Roth function (Moré, Garbow and Hillstrom, problem 2)
+///
+/// `r_1 = -13 + x_1 + ((5 - x_2) * x_2 - 2) * x_2`, `r_2 = -29 + x_1 + ((x_2 + 1) * x_2 - 14) *
+/// x_2`.
+///
+/// Besides the global minimum, there is a local minimum at `f(11.41..., -0.8968...) = 48.9842...`.
+pub const FREUDENSTEIN_ROTH: LeastSquaresTestProblem<2> = LeastSquaresTestProblem {
+    initial_param: [0.5, -2.0],
+    solution: [5.0, 4.0],
+    residual_sum_of_squares: 0.0,
+};
+
+/// Residuals of the Freudenstein and Roth function (see [`FREUDENSTEIN_ROTH`])
+pub fn freudenstein_roth_residuals<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2] = *param;
+    let n1 = T::one();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n5 = T::from_f64(5.0).unwrap();
+    let n13 = T::from_f64(13.0).unwrap();
+    let n14 = T::from_f64(14.0).unwrap();
+    let n29 = T::from_f64(29.0).unwrap();
+    [
+        -n13 + x1 + ((n5 - x2) * x2 - n2) * x2,
+        -n29 + x1 + ((x2 + n1) * x2 - n14) * x2,
+    ]
+}
+
+/// Jacobian of the residuals of the Freudenstein and Roth function
+pub fn freudenstein_roth_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let [_, x2] = *param;
+    let n1 = T::one();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+    let n10 = T::from_f64(10.0).unwrap();
+    let n14 = T::from_f64(14.0).unwrap();
+    [
+        [n1, (n10 - n3 * x2) * x2 - n2],
+        [n1, (n3 * x2 + n2) * x2 - n14],
+    ]
+}
+
+/// Powell badly scaled function (Moré, Garbow and Hillstrom, problem 3)
+///
+/// `r_1 = 10^4 * x_1 * x_2 - 1`, `r_2 = exp(-x_1) + exp(-x_2) - 1.0001`.
+pub const POWELL_BADLY_SCALED: LeastSquaresTestProblem<2> = LeastSquaresTestProblem {
+    initial_param: [0.0, 1.0],
+    solution: [1.098159329699697e-5, 9.106146739867523],
+    residual_sum_of_squares: 0.0,
+};
+
+/// Residuals of the Powell badly scaled function (see [`POWELL_BADLY_SCALED`])
+pub fn powell_badly_scaled_residuals<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2] = *param;
+    let n1e4 = T::from_f64(1e4).unwrap();
+    let n1_0001 = T::from_f64(1.0001).unwrap();
+    [
+        n1e4 * x1 * x2 - T::one(),
+        (-x1).exp() + (-x2).exp() - n1_0001,
+    ]
+}
+
+/// Jacobian of the residuals of the Powell badly scaled function
+pub fn powell_badly_scaled_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2] = *param;
+    let n1e4 = T::from_f64(1e4).unwrap();
+    [[n1e4 * x2, n1e4 * x1], [-(-x1).exp(), -(-x2).exp()]]
+}
+
+/// Helical valley function (Moré, Garbow and Hillstrom, problem 7)
+///
+/// `r_1 = 10 * (x_3 - 10 * theta(x_1, x_2))`, `r_2 = 10 * (\sqrt{x_1^2 + x_2^2} - 1)`, `r_3 =
+/// x_3`, where `theta(x_1, x_2) = arctan(x_2 / x_1) / (2 * pi)` for `x_1 > 0` and `theta(x_1,
+/// x_2) = arctan(x_2 / x_1) / (2 * pi) + 0.5` for `x_1 < 0`.
+///
+/// The residuals are not defined for `x_1 = 0`.
+pub const HELICAL_VALLEY: LeastSquaresTestProblem<3> = LeastSquaresTestProblem {
+    initial_param: [-1.0, 0.0, 0.0],
+    solution: [1.0, 0.0, 0.0],
+    residual_sum_of_squares: 0.0,
+};
+
+/// Residuals of the helical valley function (see [`HELICAL_VALLEY`])
+pub fn helical_valley_residuals<T>(param: &[T; 3]) -> [T; 3]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    let n0_5 = T::from_f64(0.5).unwrap();
+    let n10 = T::from_f64(10.0).unwrap();
+    let n2pi = T::from_f64(2.0 * std::f64::consts::PI).unwrap();
+    let theta = if x1 > T::zero() {
+        (x2 / x1).atan() / n2pi
+    } else {
+        (x2 / x1).atan() / n2pi + n0_5
+    };
+    [
+        n10 * (x3 - n10 * theta),
+        n10 * ((x1.powi(2) + x2.powi(2)).sqrt() - T::one()),
+        x3,
+    ]
+}
+
+/// Jacobian of the residuals of the helical valley function
+pub fn helical_valley_jacobian<T>(param: &[T; 3]) -> [[T; 3]; 3]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, _] = *param;
+    let n0 = T::zero();
+    let n10 = T::from_f64(10.0).unwrap();
+    let n50 = T::from_f64(50.0).unwrap();
+    let npi = T::from_f64(std::f64::consts::PI).unwrap();
+    let norm_sq = x1.powi(2) + x2.powi(2);
+    let norm = norm_sq.sqrt();
+    [
+        [n50 * x2 / (npi * norm_sq), -n50 * x1 / (npi * norm_sq), n10],
+        [n10 * x1 / norm, n10 * x2 / norm, n0],
+        [n0, n0, T::one()],
+    ]
+}
+
+/// Bard function (Moré, Garbow and Hillstrom, problem 8)
+///
+/// `r_i = y_i - (x_1 + u_i / (v_i * x_2 + w_i * x_3))` for `i = 1, ..., 15`, where `u_i = i`,
+/// `v_i = 16 - i` and `w_i = min(u_i, v_i)`.
+pub const BARD: LeastSquaresTestProblem<3> = LeastSquaresTestProblem {
+    initial_param: [1.0, 1.0, 1.0],
+    solution: [0.08241055992095622, 1.133036097745946, 2.3436951731415716],
+    residual_sum_of_squares: 8.214877306578964e-3,
+};
+
+/// Data of the Bard function
+const BARD_Y: [f64; 15] = [
+    0.14, 0.18, 0.22, 0.25, 0.29, 0.32, 0.35, 0.39, 0.37, 0.58, 0.73, 0.96, 1.34, 2.10, 4.39,
+];
+
+/// Returns `(u_i, v_i, w_i)` of the Bard function for `i = 1, ..., 15`
+fn bard_uvw<T: Float + FromPrimitive>(i: usize) -> (T, T, T) {
+    let u = i as f64;
+    let v = 16.0 - u;
+    (
+        T::from_f64(u).unwrap(),
+        T::from_f64(v).unwrap(),
+        T::from_f64(u.min(v)).unwrap(),
+    )
+}
+
+/// Residuals of the Bard function (see [`BARD`])
+pub fn bard_residuals<T>(param: &[T; 3]) -> [T; 15]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    let mut out = [T::zero(); 15];
+    for (i, (o, y)) in out.iter_mut().zip(BARD_Y.iter()).enumerate() {
+        let (u, v, w) = bard_uvw::<T>(i + 1);
+        *o = T::from_f64(*y).unwrap() - (x1 + u / (v * x2 + w * x3));
+    }
+    out
+}
+
+/// Jacobian of the residuals of the Bard function
+pub fn bard_jacobian<T>(param: &[T; 3]) -> [[T; 3]; 15]
+where
+    T: Float + FromPrimitive,
+{
+    let [_, x2, x3] = *param;
+    let mut out = [[T::zero(); 3]; 15];
+    for (i, o) in out.iter_mut().enumerate() {
+        let (u, v, w) = bard_uvw::<T>(i + 1);
+        let denom = (v * x2 + w * x3).powi(2);
+        *o = [-T::one(), u * v / denom, u * w / denom];
+    }
+    out
+}
+
+/// Powell singular function (Moré, Garbow and Hillstrom, problem 13)
+///
+/// `r_1 = x_1 + 10 * x_2`, `r_2 = \sqrt{5} * (x_3 - x_4)`, `r_3 = (x_2 - 2 * x_3)^2`, `r_4 =
+/// \sqrt{10} * (x_1 - x_4)^2`.
+///
+/// The Jacobian is singular at the solution.
+pub const POWELL_SINGULAR: LeastSquaresTestProblem<4> = LeastSquaresTestProblem {
+    initial_param: [3.0, -1.0, 0.0, 1.0],
+    solution: [0.0, 0.0, 0.0, 0.0],
+    residual_sum_of_squares: 0.0,
+};
+
+/// Residuals of the Powell singular function (see [`POWELL_SINGULAR`])
+pub fn powell_singular_residuals<T>(param: &[T; 4]) -> [T; 4]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let n2 = T::from_f64(2.0).unwrap();
+    let n10 = T::from_f64(10.0).unwrap();
+    let sqrt5 = T::from_f64(5.0).unwrap().sqrt();
+    let sqrt10 = n10.sqrt();
+    [
+        x1 + n10 * x2,
+        sqrt5 * (x3 - x4),
+        (x2 - n2 * x3).powi(2),
+        sqrt10 * (x1 - x4).powi(2),
+    ]
+}
+
+/// Jacobian of the residuals of the Powell singular function
+pub fn powell_singular_jacobian<T>(param: &[T; 4]) -> [[T; 4]; 4]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let n0 = T::zero();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    let n10 = T::from_f64(10.0).unwrap();
+    let sqrt5 = T::from_f64(5.0).unwrap().sqrt();
+    let sqrt10 = n10.sqrt();
+    let d23 = x2 - n2 * x3;
+    let d14 = x1 - x4;
+    [
+        [T::one(), n10, n0, n0],
+        [n0, n0, sqrt5, -sqrt5],
+        [n0, n2 * d23, -n4 * d23, n0],
+        [n2 * sqrt10 * d14, n0, n0, -n2 * sqrt10 * d14],
+    ]
+}
+
+/// NIST StRD problem Misra1a (lower difficulty)
+///
+/// Model: `y = b_1 * (1 - exp(-b_2 * x))` fitted to 14 observations.
+///
+/// Certified values: `b_1 = 2.3894212918E+02`, `b_2 = 5.5015643181E-04`, residual sum of squares
+/// `1.2455138894E-01`. The second certified starting value is `(250, 5E-04)`.
+pub const MISRA1A: LeastSquaresTestProblem<2> = LeastSquaresTestProblem {
+    initial_param: [500.0, 1e-4],
+    solution: [2.3894212918E+02, 5.5015643181E-04],
+    residual_sum_of_squares: 1.2455138894E-01,
+};
+
+/// Data of the Misra1a problem as `(x, y)`
+const MISRA1A_DATA: [(f64, f64); 14] = [
+    (77.6, 10.07),
+    (114.9, 14.73),
+    (141.1, 17.94),
+    (190.8, 23.93),
+    (239.9, 29.61),
+    (289.0, 35.18),
+    (332.8, 40.02),
+    (378.4, 44.82),
+    (434.8, 50.76),
+    (477.3, 55.05),
+    (536.8, 61.01),
+    (593.1, 66.40),
+    (689.1, 75.47),
+    (760.0, 81.78),
+];
+
+/// Residuals of the Misra1a problem (see [`MISRA1A`])
+pub fn misra1a_residuals<T>(param: &[T; 2]) -> [T; 14]
+where
+    T: Float + FromPrimitive,
+{
+    let [b1, b2] = *param;
+    let mut out = [T::zero(); 14];
+    for (o, (x, y)) in out.iter_mut().zip(MISRA1A_DATA.iter()) {
+        let x = T::from_f64(*x).unwrap();
+        *o = T::from_f64(*y).unwrap() - b1 * (T::one() - (-b2 * x).exp());
+    }
+    out
+}
+
+/// Jacobian of the residuals of the Misra1a problem
+pub fn misra1a_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 14]
+where
+    T: Float + FromPrimitive,
+{
+    let [b1, b2] = *param;
+    let mut out = [[T::zero(); 2]; 14];
+    for (o, (x, _)) in out.iter_mut().zip(MISRA1A_DATA.iter()) {
+        let x = T::from_f64(*x).unwrap();
+        let e = (-b2 * x).exp();
+        *o = [e - T::one(), -b1 * x * e];
+    }
+    out
+}
+
+/// NIST StRD problem DanWood (lower difficulty)
+///
+/// Model: `y = b_1 * x^b_2` fitted to 6 observations.
+///
+/// Certified values: `b_1 = 7.6886226176E-01`, `b_2 = 3.8604055871E+00`, residual sum of squares
+/// `4.3173084083E-03`. The second certified starting value is `(0.7, 4)`.
+pub const DANWOOD: LeastSquaresTestProblem<2> = LeastSquaresTestProblem {
+    initial_param: [1.0, 5.0],
+    solution: [7.6886226176E-01, 3.8604055871E+00],
+    residual_sum_of_squares: 4.3173084083E-03,
+};
+
+/// Data of the DanWood problem as `(x, y)`
+const DANWOOD_DATA: [(f64, f64); 6] = [
+    (1.309, 2.138),
+    (1.471, 3.421),
+    (1.490, 3.597),
+    (1.565, 4.340),
+    (1.611, 4.882),
+    (1.680, 5.660),
+];
+
+/// Residuals of the DanWood problem (see [`DANWOOD`])
+pub fn danwood_residuals<T>(param: &[T; 2]) -> [T; 6]
+where
+    T: Float + FromPrimitive,
+{
+    let [b1, b2] = *param;
+    let mut out = [T::zero(); 6];
+    for (o, (x, y)) in out.iter_mut().zip(DANWOOD_DATA.iter()) {
+        let x = T::from_f64(*x).unwrap();
+        *o = T::from_f64(*y).unwrap() - b1 * x.powf(b2);
+    }
+    out
+}
+
+/// Jacobian of the residuals of the DanWood problem
+pub fn danwood_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 6]
+where
+    T: Float + FromPrimitive,
+{
+    let [b1, b2] = *param;
+    let mut out = [[T::zero(); 2]; 6];
+    for (o, (x, _)) in out.iter_mut().zip(DANWOOD_DATA.iter()) {
+        let x = T::from_f64(*x).unwrap();
+        let p = x.powf(b2);
+        *o = [-p, -b1 * p * x.ln()];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+
+    /// Checks the known solution as well as the Jacobian at `param` against finite differences
+    macro_rules! check_problem {
+        ($problem:expr, $param:expr, $residuals:ident, $jacobian:ident) => {{
+            let problem = $problem;
+            let param = $param;
+            let solution = problem.solution;
+
+            let residuals = $residuals(&solution);
+            let rss: f64 = residuals.iter().map(|r| r.powi(2)).sum();
+            assert_relative_eq!(
+                rss,
+                problem.residual_sum_of_squares,
+                epsilon = 1e-12,
+                max_relative = 1e-8
+            );
+
+            // The gradient `2 * J^T r` vanishes at the solution. Since the certified solutions
+            // are only given to a limited number of digits, the gradient is scaled with the
+            // magnitude of parameters which are smaller than one.
+            let jacobian = $jacobian(&solution);
+            for (j, x) in solution.iter().enumerate() {
+                let gradient: f64 = jacobian
+                    .iter()
+                    .zip(residuals.iter())
+                    .map(|(row, r)| 2.0 * row[j] * r)
+                    .sum();
+                let scale = if *x == 0.0 { 1.0 } else { x.abs().min(1.0) };
+                assert_relative_eq!(gradient * scale, 0.0, epsilon = 1e-6);
+            }
+
+            let to_array = |x: &Vec<f64>| x.clone().try_into().unwrap();
+
+            // `finitediff` returns the transposed Jacobian
+            let jacobian = $jacobian(&param);
+            let jacobian_fd =
+                Vec::from(param).central_jacobian(&|x| $residuals(&to_array(x)).to_vec());
+            for i in 0..jacobian.len() {
+                for j in 0..jacobian[i].len() {
+                    assert_relative_eq!(
+                        jacobian[i][j],
+                        jacobian_fd[j][i],
+                        epsilon = 1e-5,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }};
+    }
+
+    proptest! {
+        #[test]
+        fn test_rosenbrock(a in -5.0..5.0, b in -5.0..5.0) {
+            check_problem!(ROSENBROCK, [a, b], rosenbrock_residuals, rosenbrock_jacobian);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_freudenstein_roth(a in -5.0..5.0, b in -5.0..5.0) {
+            check_problem!(
+                FREUDENSTEIN_ROTH,
+                [a, b],
+                freudenstein_roth_residuals,
+                freudenstein_roth_jacobian
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_powell_badly_scaled(a in -1.0..1.0, b in -1.0..1.0) {
+            check_problem!(
+                POWELL_BADLY_SCALED,
+                [a, b],
+                powell_badly_scaled_residuals,
+                powell_badly_scaled_jacobian
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_helical_valley(a in 0.1f64..5.0, b in -5.0..5.0, c in -5.0..5.0, negative: bool) {
+            let a = if negative { -a } else { a };
+            check_problem!(
+                HELICAL_VALLEY,
+                [a, b, c],
+                helical_valley_residuals,
+                helical_valley_jacobian
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_bard(a in -5.0..5.0, b in 0.1..5.0, c in 0.1..5.0) {
+            check_problem!(BARD, [a, b, c], bard_residuals, bard_jacobian);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_powell_singular(a in -5.0..5.0, b in -5.0..5.0, c in -5.0..5.0, d in -5.0..5.0) {
+            check_problem!(
+                POWELL_SINGULAR,
+                [a, b, c, d],
+                powell_singular_residuals,
+                powell_singular_jacobian
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_misra1a(a in 100.0..500.0, b in 1e-4..1e-3) {
+            check_problem!(MISRA1A, [a, b], misra1a_residuals, misra1a_jacobian);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_danwood(a in 0.5..1.0, b in 3.0..5.0) {
+            check_problem!(DANWOOD, [a, b], danwood_residuals, danwood_jacobian);
+        }
+    }
+
+    #[test]
+    fn test_helical_valley_initial_param() {
+        // theta = 0.5 for x = (-1, 0, 0)
+        let residuals = helical_valley_residuals(&HELICAL_VALLEY.initial_param);
+        assert_relative_eq!(residuals[0], -50.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(residuals[1], 0.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(residuals[2], 0.0, epsilon = f64::EPSILON);
+    }
+}
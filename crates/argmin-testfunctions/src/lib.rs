@@ -23,6 +23,19 @@
 //!
 //! All functions are generic over their inputs and work with `[f64]` and `[f32]`.
 //!
+//! In addition, a subset of the constrained test problems of the Hock-Schittkowski collection is
+//! available (`hs006`, `hs021`, `hs028`, `hs035`, `hs071` and `hs076`), including the Jacobians of
+//! the constraints and a description of bounds and known optima (for instance `HS071`).
+//!
+//! For testing least-squares solvers, several problems of the Moré-Garbow-Hillstrom collection and
+//! of the NIST Statistical Reference Datasets are provided as residuals and Jacobians (for instance
+//! `bard_residuals` and `bard_jacobian`) together with their known or certified solutions (for
+//! instance `BARD`).
+//!
+//! With the `ndarray` feature, the derivative and Hessian of the multidimensional Rosenbrock
+//! function are also available for `ndarray` arrays (`rosenbrock_derivative_ndarray` and
+//! `rosenbrock_hessian_ndarray`).
+//!
 //! ## Python wrapper
 //!
 //! Thanks to the python module
@@ -82,8 +95,11 @@ mod crossintray;
 mod easom;
 mod eggholder;
 mod goldsteinprice;
+mod griewank;
 mod himmelblau;
+mod hockschittkowski;
 mod holdertable;
+mod leastsquares;
 mod levy;
 mod matyas;
 mod mccorminck;
@@ -91,6 +107,7 @@ mod picheny;
 mod rastrigin;
 mod rosenbrock;
 mod schaffer;
+mod schwefel;
 mod sphere;
 mod styblinskitang;
 mod threehumpcamel;
@@ -104,8 +121,11 @@ pub use crossintray::*;
 pub use easom::*;
 pub use eggholder::*;
 pub use goldsteinprice::*;
+pub use griewank::*;
 pub use himmelblau::*;
+pub use hockschittkowski::*;
 pub use holdertable::*;
+pub use leastsquares::*;
 pub use levy::*;
 pub use matyas::*;
 pub use mccorminck::*;
@@ -113,6 +133,7 @@ pub use picheny::*;
 pub use rastrigin::*;
 pub use rosenbrock::*;
 pub use schaffer::*;
+pub use schwefel::*;
 pub use sphere::*;
 pub use styblinskitang::*;
 pub use threehumpcamel::*;
@@ -19,6 +19,8 @@
 //!
 //! The minimum is at `f(x_1, x_2, ..., x_n) = f(1, 1, ..., 1) = 0`.
 
+#[cfg(feature = "ndarray")]
+use ndarray::{Array1, Array2, ArrayBase, Data, Ix1};
 use num::{Float, FromPrimitive};
 use std::{iter::Sum, ops::AddAssign};
 
@@ -119,8 +121,8 @@ where
 
 /// Hessian of the multidimensional Rosenbrock test function
 ///
-/// The parameters `a` and `b` can be chosen freely.
-pub fn rosenbrock_ab_hessian<T>(param: &[T], a: T, b: T) -> Vec<Vec<T>>
+/// The parameters `a` and `b` can be chosen freely (the Hessian does not depend on `a`).
+pub fn rosenbrock_ab_hessian<T>(param: &[T], _a: T, b: T) -> Vec<Vec<T>>
 where
     T: Float + FromPrimitive + AddAssign,
 {
@@ -136,7 +138,7 @@ where
         let xi = param[i];
         let xi1 = param[i + 1];
 
-        hessian[i][i] += n12 * b * xi.powi(2) - n4 * b * xi1 + n2 * a;
+        hessian[i][i] += n12 * b * xi.powi(2) - n4 * b * xi1 + n2;
         hessian[i + 1][i + 1] = n2 * b;
         hessian[i][i + 1] = -n4 * b * xi;
         hessian[i + 1][i] = -n4 * b * xi;
@@ -209,11 +211,11 @@ where
 
 /// Hessian of the multidimensional Rosenbrock test function
 ///
-/// The parameters `a` and `b` can be chosen freely.
+/// The parameters `a` and `b` can be chosen freely (the Hessian does not depend on `a`).
 ///
 /// This is the const generics version, which requires the number of parameters to be known
 /// at compile time.
-pub fn rosenbrock_ab_hessian_const<const N: usize, T>(x: &[T; N], a: T, b: T) -> [[T; N]; N]
+pub fn rosenbrock_ab_hessian_const<const N: usize, T>(x: &[T; N], _a: T, b: T) -> [[T; N]; N]
 where
     T: Float + FromPrimitive + AddAssign,
 {
@@ -228,7 +230,7 @@ where
         let xi = x[i];
         let xi1 = x[i + 1];
 
-        hessian[i][i] += n12 * b * xi.powi(2) - n4 * b * xi1 + n2 * a;
+        hessian[i][i] += n12 * b * xi.powi(2) - n4 * b * xi1 + n2;
         hessian[i + 1][i + 1] = n2 * b;
         hessian[i][i + 1] = -n4 * b * xi;
         hessian[i + 1][i] = -n4 * b * xi;
@@ -236,6 +238,107 @@ where
     hessian
 }
 
+/// Derivative of the multidimensional Rosenbrock test function
+///
+/// The parameters `a` and `b` are set to `1.0` and `100.0`, respectively.
+///
+/// This is the `ndarray` version (requires the `ndarray` feature), which accepts any
+/// one-dimensional array (including views).
+#[cfg(feature = "ndarray")]
+pub fn rosenbrock_derivative_ndarray<T, S>(param: &ArrayBase<S, Ix1>) -> Array1<T>
+where
+    T: Float + FromPrimitive + AddAssign,
+    S: Data<Elem = T>,
+{
+    rosenbrock_ab_derivative_ndarray(
+        param,
+        T::from_f64(1.0).unwrap(),
+        T::from_f64(100.0).unwrap(),
+    )
+}
+
+/// Derivative of the multidimensional Rosenbrock test function
+///
+/// The parameters `a` and `b` can be chosen freely.
+///
+/// This is the `ndarray` version (requires the `ndarray` feature), which accepts any
+/// one-dimensional array (including views). An empty array results in an empty result.
+#[cfg(feature = "ndarray")]
+pub fn rosenbrock_ab_derivative_ndarray<T, S>(param: &ArrayBase<S, Ix1>, a: T, b: T) -> Array1<T>
+where
+    T: Float + FromPrimitive + AddAssign,
+    S: Data<Elem = T>,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+
+    let n = param.len();
+
+    let mut result = Array1::zeros(n);
+
+    for i in 0..n.saturating_sub(1) {
+        let xi = param[i];
+        let xi1 = param[i + 1];
+
+        let t1 = -n4 * b * xi * (xi1 - xi.powi(2));
+        let t2 = n2 * b * (xi1 - xi.powi(2));
+
+        result[i] += t1 + n2 * (xi - a);
+        result[i + 1] += t2;
+    }
+    result
+}
+
+/// Hessian of the multidimensional Rosenbrock test function
+///
+/// The parameters `a` and `b` are set to `1.0` and `100.0`, respectively.
+///
+/// This is the `ndarray` version (requires the `ndarray` feature), which accepts any
+/// one-dimensional array (including views).
+#[cfg(feature = "ndarray")]
+pub fn rosenbrock_hessian_ndarray<T, S>(param: &ArrayBase<S, Ix1>) -> Array2<T>
+where
+    T: Float + FromPrimitive + AddAssign,
+    S: Data<Elem = T>,
+{
+    rosenbrock_ab_hessian_ndarray(
+        param,
+        T::from_f64(1.0).unwrap(),
+        T::from_f64(100.0).unwrap(),
+    )
+}
+
+/// Hessian of the multidimensional Rosenbrock test function
+///
+/// The parameters `a` and `b` can be chosen freely (the Hessian does not depend on `a`).
+///
+/// This is the `ndarray` version (requires the `ndarray` feature), which accepts any
+/// one-dimensional array (including views). An empty array results in an empty result.
+#[cfg(feature = "ndarray")]
+pub fn rosenbrock_ab_hessian_ndarray<T, S>(param: &ArrayBase<S, Ix1>, _a: T, b: T) -> Array2<T>
+where
+    T: Float + FromPrimitive + AddAssign,
+    S: Data<Elem = T>,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    let n12 = T::from_f64(12.0).unwrap();
+
+    let n = param.len();
+    let mut hessian = Array2::zeros((n, n));
+
+    for i in 0..n.saturating_sub(1) {
+        let xi = param[i];
+        let xi1 = param[i + 1];
+
+        hessian[(i, i)] += n12 * b * xi.powi(2) - n4 * b * xi1 + n2;
+        hessian[(i + 1, i + 1)] = n2 * b;
+        hessian[(i, i + 1)] = -n4 * b * xi;
+        hessian[(i + 1, i)] = -n4 * b * xi;
+    }
+    hessian
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -314,6 +417,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rosenbrock_ab_hessian() {
+        // The Hessian does not depend on `a`
+        let param = [0.0, 0.1, 0.2, 0.3];
+        let hessian = rosenbrock_hessian(&param);
+        let hessian_ab = rosenbrock_ab_hessian(&param, 2.0, 100.0);
+        let hessian_ab_const = rosenbrock_ab_hessian_const(&param, 2.0, 100.0);
+        let hessian_fd =
+            Vec::from(param).central_hessian(&|x| rosenbrock_ab_derivative(x, 2.0, 100.0));
+        for i in 0..param.len() {
+            for j in 0..param.len() {
+                assert_relative_eq!(hessian_ab[i][j], hessian[i][j], epsilon = f64::EPSILON);
+                assert_relative_eq!(
+                    hessian_ab_const[i][j],
+                    hessian[i][j],
+                    epsilon = f64::EPSILON
+                );
+                assert_relative_eq!(
+                    hessian_ab[i][j],
+                    hessian_fd[i][j],
+                    epsilon = 1e-5,
+                    max_relative = 1e-2
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_rosenbrock_ndarray() {
+        use ndarray::{s, Array1};
+
+        let param = [0.3, -0.1, 1.2, 0.7, 2.0];
+        let derivative = rosenbrock_derivative(&param);
+        let hessian = rosenbrock_hessian(&param);
+
+        let array = Array1::from(param.to_vec());
+        // Non-contiguous view of the same values
+        let strided = Array1::from_iter(param.iter().flat_map(|&x| [x, f64::NAN]));
+        let view = strided.slice(s![..;2]);
+
+        for derivative_nd in [
+            rosenbrock_derivative_ndarray(&array),
+            rosenbrock_derivative_ndarray(&view),
+        ] {
+            assert_eq!(derivative_nd.to_vec(), derivative);
+        }
+        for hessian_nd in [
+            rosenbrock_hessian_ndarray(&array),
+            rosenbrock_hessian_ndarray(&view),
+        ] {
+            assert_eq!(hessian_nd.dim(), (param.len(), param.len()));
+            for (row_nd, row) in hessian_nd.rows().into_iter().zip(&hessian) {
+                assert_eq!(&row_nd.to_vec(), row);
+            }
+        }
+
+        let derivative_ab = rosenbrock_ab_derivative(&param, 2.0, 50.0);
+        assert_eq!(
+            rosenbrock_ab_derivative_ndarray(&array, 2.0, 50.0).to_vec(),
+            derivative_ab
+        );
+        let hessian_ab = rosenbrock_ab_hessian(&param, 2.0, 50.0);
+        let hessian_ab_nd = rosenbrock_ab_hessian_ndarray(&array, 2.0, 50.0);
+        for (row_nd, row) in hessian_ab_nd.rows().into_iter().zip(&hessian_ab) {
+            assert_eq!(&row_nd.to_vec(), row);
+        }
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_rosenbrock_ndarray_empty() {
+        let empty: ndarray::Array1<f64> = ndarray::Array1::zeros(0);
+        assert_eq!(rosenbrock_derivative_ndarray(&empty).len(), 0);
+        assert_eq!(rosenbrock_hessian_ndarray(&empty).dim(), (0, 0));
+    }
+
     proptest! {
         #[test]
         fn test_rosenbrock_derivative_finitediff(a in -1.0..1.0,
@@ -0,0 +1,272 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Schwefel test function
+//!
+//! Defined as
+//!
+//! `f(x_1, x_2, ..., x_n) = 418.9829 * n - \sum_{i=1}^{n} x_i * sin(\sqrt{|x_i|})`
+//!
+//! where `x_i \in [-500, 500]`.
+//!
+//! The global minimum is at `f(x_1, x_2, ..., x_n) = f(420.9687, 420.9687, ..., 420.9687) = 0`.
+
+use num::{Float, FromPrimitive};
+use std::iter::Sum;
+
+/// Schwefel test function
+///
+/// Defined as
+///
+/// `f(x_1, x_2, ..., x_n) = 418.9829 * n - \sum_{i=1}^{n} x_i * sin(\sqrt{|x_i|})`
+///
+/// where `x_i \in [-500, 500]`.
+///
+/// The global minimum is at `f(x_1, x_2, ..., x_n) = f(420.9687, 420.9687, ..., 420.9687) = 0`.
+pub fn schwefel<T>(param: &[T]) -> T
+where
+    T: Float + FromPrimitive + Sum,
+{
+    let n418 = T::from_f64(418.9828872724338).unwrap();
+
+    param.iter().map(|x| n418 - *x * x.abs().sqrt().sin()).sum()
+}
+
+/// Derivative of Schwefel test function
+pub fn schwefel_derivative<T>(param: &[T]) -> Vec<T>
+where
+    T: Float + FromPrimitive + Sum,
+{
+    param.iter().map(|x| derivative(*x)).collect()
+}
+
+/// Derivative of Schwefel test function
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn schwefel_derivative_const<const N: usize, T>(param: &[T; N]) -> [T; N]
+where
+    T: Float + FromPrimitive + Sum,
+{
+    let n0 = T::from_f64(0.0).unwrap();
+
+    let mut out = [n0; N];
+
+    param
+        .iter()
+        .zip(out.iter_mut())
+        .map(|(x, o)| *o = derivative(*x))
+        .count();
+
+    out
+}
+
+/// Hessian of Schwefel test function
+///
+/// The Hessian is not defined if any `x_i` is zero.
+pub fn schwefel_hessian<T>(param: &[T]) -> Vec<Vec<T>>
+where
+    T: Float + FromPrimitive + Sum,
+{
+    let n0 = T::from_f64(0.0).unwrap();
+
+    let n = param.len();
+    let mut out = vec![vec![n0; n]; n];
+
+    param
+        .iter()
+        .enumerate()
+        .map(|(i, x)| out[i][i] = second_derivative(*x))
+        .count();
+
+    out
+}
+
+/// Hessian of Schwefel test function
+///
+/// The Hessian is not defined if any `x_i` is zero.
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn schwefel_hessian_const<const N: usize, T>(param: &[T; N]) -> [[T; N]; N]
+where
+    T: Float + FromPrimitive + Sum,
+{
+    let n0 = T::from_f64(0.0).unwrap();
+
+    let mut out = [[n0; N]; N];
+
+    param
+        .iter()
+        .enumerate()
+        .map(|(i, x)| out[i][i] = second_derivative(*x))
+        .count();
+
+    out
+}
+
+/// Derivative of `-x * sin(\sqrt{|x|})`
+fn derivative<T>(x: T) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let s = x.abs().sqrt();
+    -(s.sin() + T::from_f64(0.5).unwrap() * s * s.cos())
+}
+
+/// Second derivative of `-x * sin(\sqrt{|x|})`
+fn second_derivative<T>(x: T) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let s = x.abs().sqrt();
+    -x.signum() * (T::from_f64(3.0).unwrap() * s.cos() - s * s.sin())
+        / (T::from_f64(4.0).unwrap() * s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+    use std::{f32, f64};
+
+    #[test]
+    fn test_schwefel_optimum() {
+        assert_relative_eq!(
+            schwefel(&[420.96875_f32, 420.96875_f32, 420.96875_f32]),
+            0.0,
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            schwefel(&[420.968746_f64, 420.968746_f64, 420.968746_f64]),
+            0.0,
+            epsilon = 1e-10
+        );
+
+        let deriv = schwefel_derivative(&[420.968746_f64, 420.968746_f64, 420.968746_f64]);
+        for d in deriv {
+            assert_relative_eq!(d, 0.0, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_schwefel_derivative_zero() {
+        // The derivative is continuous at zero
+        assert_relative_eq!(
+            schwefel_derivative(&[0.0_f64])[0],
+            0.0,
+            epsilon = f64::EPSILON
+        );
+        assert_relative_eq!(
+            schwefel_derivative_const(&[0.0_f32])[0],
+            0.0,
+            epsilon = f32::EPSILON
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_schwefel_derivative_finitediff(a in -500.0..-1.0,
+                                               b in -500.0..-1.0,
+                                               c in -500.0..-1.0,
+                                               d in -500.0..-1.0,
+                                               e in 1.0..500.0,
+                                               f in 1.0..500.0,
+                                               g in 1.0..500.0,
+                                               h in 1.0..500.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let derivative = schwefel_derivative(&param);
+            let derivative_fd = Vec::from(param).central_diff(&|x| schwefel(x));
+            for i in 0..derivative.len() {
+                assert_relative_eq!(
+                    derivative[i],
+                    derivative_fd[i],
+                    epsilon = 1e-4,
+                    max_relative = 1e-2
+                );
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_schwefel_derivative_const_finitediff(a in -500.0..-1.0,
+                                                     b in -500.0..-1.0,
+                                                     c in -500.0..-1.0,
+                                                     d in -500.0..-1.0,
+                                                     e in 1.0..500.0,
+                                                     f in 1.0..500.0,
+                                                     g in 1.0..500.0,
+                                                     h in 1.0..500.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let derivative = schwefel_derivative_const(&param);
+            let derivative_fd = Vec::from(param).central_diff(&|x| schwefel(x));
+            for i in 0..derivative.len() {
+                assert_relative_eq!(
+                    derivative[i],
+                    derivative_fd[i],
+                    epsilon = 1e-4,
+                    max_relative = 1e-2
+                );
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_schwefel_hessian_finitediff(a in -500.0..-1.0,
+                                            b in -500.0..-1.0,
+                                            c in -500.0..-1.0,
+                                            d in -500.0..-1.0,
+                                            e in 1.0..500.0,
+                                            f in 1.0..500.0,
+                                            g in 1.0..500.0,
+                                            h in 1.0..500.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let hessian = schwefel_hessian(&param);
+            let hessian_fd = Vec::from(param).central_hessian(&|x| schwefel_derivative(x));
+            for i in 0..hessian.len() {
+                for j in 0..hessian[i].len() {
+                    assert_relative_eq!(
+                        hessian[i][j],
+                        hessian_fd[i][j],
+                        epsilon = 1e-4,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_schwefel_hessian_const_finitediff(a in -500.0..-1.0,
+                                                  b in -500.0..-1.0,
+                                                  c in -500.0..-1.0,
+                                                  d in -500.0..-1.0,
+                                                  e in 1.0..500.0,
+                                                  f in 1.0..500.0,
+                                                  g in 1.0..500.0,
+                                                  h in 1.0..500.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let hessian = schwefel_hessian_const(&param);
+            let hessian_fd = Vec::from(param).central_hessian(&|x| schwefel_derivative(x));
+            for i in 0..hessian.len() {
+                for j in 0..hessian[i].len() {
+                    assert_relative_eq!(
+                        hessian[i][j],
+                        hessian_fd[i][j],
+                        epsilon = 1e-4,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,700 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Benchmarking of solvers
+//!
+//! [`Benchmark`] runs a set of solvers against a set of [`TestProblem`]s, giving each solver the
+//! same budget of function evaluations on every problem. Each run records the best cost function
+//! value over the course of the evaluations, the number of evaluations and the wall-clock time.
+//!
+//! Whether a run solved a problem is decided by the convergence test of
+//!
+//! J. J. Moré and S. M. Wild. Benchmarking Derivative-Free Optimization Algorithms. SIAM Journal
+//! on Optimization, 20(1), 2009.
+//!
+//! A problem counts as solved once a cost function value `f(x) <= f_L + tau * (f(x_0) - f_L)` was
+//! found within the budget, where `f(x_0)` is the cost at the initial parameter vector and `f_L`
+//! is the smallest cost found by any of the solvers on this problem. From the number of
+//! evaluations needed to solve the problems, [`BenchmarkResults`] computes success rates as well
+//! as performance and data profiles, which can be written as CSV.
+//!
+//! ```
+//! # use argmin::core::Error;
+//! use argmin::core::Executor;
+//! use argmin::solver::neldermead::NelderMead;
+//! use argmin_testproblems::benchmarking::Benchmark;
+//! use argmin_testproblems::ProblemSet;
+//!
+//! # fn main() -> Result<(), Error> {
+//! let problems = ProblemSet::from_toml_str(
+//!     r#"
+//!     [[problems]]
+//!     function = "sphere"
+//!     dimension = 2
+//!     initial_param = 1.0
+//!
+//!     [[problems]]
+//!     function = "rosenbrock"
+//!     dimension = 2
+//!     initial_param = [-1.2, 1.0]
+//!     "#,
+//! )?
+//! .build()?;
+//!
+//! let results = Benchmark::new(problems, 200.0)?
+//!     .add_solver("nelder-mead", |problem, budget| {
+//!         let init = problem.problem().initial_param().to_vec();
+//!         let simplex = vec![
+//!             init.clone(),
+//!             vec![init[0] + 0.5, init[1]],
+//!             vec![init[0], init[1] + 0.5],
+//!         ];
+//!         Executor::new(problem, NelderMead::new(simplex))
+//!             .evaluation_budget(budget)
+//!             .configure(|state| state.max_iters(1000))
+//!             .run()?;
+//!         Ok(())
+//!     })
+//!     .run();
+//!
+//! let mut csv = Vec::new();
+//! results.performance_profile().write_csv(&mut csv)?;
+//! # assert_eq!(results.success_rates(), vec![("nelder-mead".to_string(), 1.0)]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::TestProblem;
+use argmin::argmin_error;
+use argmin::core::{CostFunction, Error, EvaluationBudget, Gradient, Hessian};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Runs a solver on a problem within a budget of function evaluations
+type SolverFn = Box<dyn Fn(BenchmarkProblem, EvaluationBudget) -> Result<(), Error>>;
+
+/// Benchmark of a set of solvers on a set of problems.
+///
+/// See the [module documentation](self) for details.
+pub struct Benchmark {
+    /// Problems
+    problems: Vec<TestProblem>,
+    /// Solvers and their names
+    solvers: Vec<(String, SolverFn)>,
+    /// Budget of (weighted) function evaluations
+    budget: f64,
+    /// Weights of the counters of function evaluations
+    weights: HashMap<String, f64>,
+    /// Tolerance of the convergence test
+    tolerance: f64,
+}
+
+impl Benchmark {
+    /// Constructs a benchmark on `problems` where every solver gets `budget` function evaluations
+    /// per problem.
+    ///
+    /// The budget must be positive.
+    pub fn new(problems: Vec<TestProblem>, budget: f64) -> Result<Self, Error> {
+        if budget.is_nan() || budget <= 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Benchmark`: budget must be > 0."
+            ));
+        }
+        Ok(Benchmark {
+            problems,
+            solvers: vec![],
+            budget,
+            weights: HashMap::new(),
+            tolerance: 1e-3,
+        })
+    }
+
+    /// Sets the weight of `counter` (`cost_count`, `gradient_count` or `hessian_count`) in the
+    /// budget (default: 1).
+    ///
+    /// This allows for instance to count a gradient evaluation as expensive as `n` cost function
+    /// evaluations. The weight must be non-negative.
+    pub fn with_weight(mut self, counter: &str, weight: f64) -> Result<Self, Error> {
+        if weight.is_nan() || weight < 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Benchmark`: weight must be >= 0."
+            ));
+        }
+        self.weights.insert(counter.to_string(), weight);
+        Ok(self)
+    }
+
+    /// Sets the tolerance `tau` of the convergence test (default: `1e-3`).
+    ///
+    /// The tolerance must be in `(0, 1)`.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Result<Self, Error> {
+        if tolerance.is_nan() || tolerance <= 0.0 || tolerance >= 1.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Benchmark`: tolerance must be in (0, 1)."
+            ));
+        }
+        self.tolerance = tolerance;
+        Ok(self)
+    }
+
+    /// Adds a solver.
+    ///
+    /// `run` is called once per problem with the problem and the evaluation budget. It is expected
+    /// to run an `Executor` on the problem and to pass the budget to
+    /// [`Executor::evaluation_budget`](`argmin::core::Executor::evaluation_budget`). Evaluations
+    /// beyond the budget are ignored. If `run` returns an error, the problem counts as unsolved
+    /// by this solver and the error is recorded in the [`Run`].
+    #[must_use]
+    pub fn add_solver<F>(mut self, name: &str, run: F) -> Self
+    where
+        F: Fn(BenchmarkProblem, EvaluationBudget) -> Result<(), Error> + 'static,
+    {
+        self.solvers.push((name.to_string(), Box::new(run)));
+        self
+    }
+
+    /// Returns the evaluation budget passed to the solvers.
+    fn evaluation_budget(&self) -> EvaluationBudget {
+        let budget = EvaluationBudget::new().with_total(self.budget).unwrap();
+        self.weights
+            .iter()
+            .fold(budget, |budget, (counter, weight)| {
+                budget.with_weight(counter, *weight).unwrap()
+            })
+    }
+
+    /// Runs all solvers on all problems.
+    pub fn run(&self) -> BenchmarkResults {
+        let mut runs = vec![];
+        for problem in self.problems.iter() {
+            let initial_cost = problem.cost(&problem.initial_param().to_vec()).unwrap();
+            for (solver, run) in self.solvers.iter() {
+                let trace = Arc::new(Mutex::new(Trace {
+                    budget: self.budget,
+                    weights: self.weights.clone(),
+                    evaluations: 0.0,
+                    best_cost: initial_cost,
+                    improvements: vec![],
+                }));
+                let benchmark_problem = BenchmarkProblem {
+                    problem: problem.clone(),
+                    trace: Arc::clone(&trace),
+                };
+                let start = Instant::now();
+                let result = run(benchmark_problem, self.evaluation_budget());
+                let time = start.elapsed();
+                let trace = trace.lock().unwrap();
+                runs.push(Run {
+                    solver: solver.clone(),
+                    problem: problem.name().to_string(),
+                    dimension: problem.dimension(),
+                    initial_cost,
+                    best_cost: trace.best_cost,
+                    evaluations: trace.evaluations.min(self.budget),
+                    improvements: trace.improvements.clone(),
+                    time,
+                    error: result.err().map(|e| e.to_string()),
+                });
+            }
+        }
+        BenchmarkResults {
+            solvers: self.solvers.iter().map(|(name, _)| name.clone()).collect(),
+            problems: self.problems.iter().map(|p| p.name().to_string()).collect(),
+            tolerance: self.tolerance,
+            runs,
+        }
+    }
+}
+
+/// Counts the evaluations of a run and keeps track of the best cost function value
+struct Trace {
+    /// Budget of (weighted) function evaluations
+    budget: f64,
+    /// Weights of the counters of function evaluations
+    weights: HashMap<String, f64>,
+    /// Weighted number of evaluations so far
+    evaluations: f64,
+    /// Best cost function value within the budget
+    best_cost: f64,
+    /// Number of evaluations and cost function value whenever the best cost improved
+    improvements: Vec<(f64, f64)>,
+}
+
+impl Trace {
+    /// Counts an evaluation of `counter`.
+    fn count(&mut self, counter: &str) {
+        self.evaluations += self.weights.get(counter).copied().unwrap_or(1.0);
+    }
+
+    /// Records the cost function value `cost`, unless the budget is exceeded.
+    fn record(&mut self, cost: f64) {
+        if self.evaluations <= self.budget && cost < self.best_cost {
+            self.best_cost = cost;
+            self.improvements.push((self.evaluations, cost));
+        }
+    }
+}
+
+/// Problem passed to the solvers of a [`Benchmark`].
+///
+/// Forwards all evaluations to the [`TestProblem`] while counting the evaluations and recording
+/// the cost function values.
+#[derive(Clone)]
+pub struct BenchmarkProblem {
+    /// Problem
+    problem: TestProblem,
+    /// Trace of the run
+    trace: Arc<Mutex<Trace>>,
+}
+
+impl BenchmarkProblem {
+    /// Returns the underlying problem, for instance to obtain the initial parameter vector or the
+    /// bounds.
+    pub fn problem(&self) -> &TestProblem {
+        &self.problem
+    }
+}
+
+impl CostFunction for BenchmarkProblem {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let cost = self.problem.cost(param)?;
+        let mut trace = self.trace.lock().unwrap();
+        trace.count("cost_count");
+        trace.record(cost);
+        Ok(cost)
+    }
+}
+
+impl Gradient for BenchmarkProblem {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let gradient = self.problem.gradient(param)?;
+        self.trace.lock().unwrap().count("gradient_count");
+        Ok(gradient)
+    }
+}
+
+impl Hessian for BenchmarkProblem {
+    type Param = Vec<f64>;
+    type Hessian = Vec<Vec<f64>>;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let hessian = self.problem.hessian(param)?;
+        self.trace.lock().unwrap().count("hessian_count");
+        Ok(hessian)
+    }
+}
+
+/// Result of running a solver on a problem.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    /// Name of the solver
+    pub solver: String,
+    /// Name of the problem
+    pub problem: String,
+    /// Number of parameters of the problem
+    pub dimension: usize,
+    /// Cost function value at the initial parameter vector
+    pub initial_cost: f64,
+    /// Best cost function value found within the budget
+    pub best_cost: f64,
+    /// Weighted number of evaluations (at most the budget)
+    pub evaluations: f64,
+    /// Number of evaluations and cost function value whenever the best cost improved
+    pub improvements: Vec<(f64, f64)>,
+    /// Wall-clock time of the run
+    pub time: Duration,
+    /// Error returned by the solver, if any
+    pub error: Option<String>,
+}
+
+impl Run {
+    /// Returns the number of evaluations after which a cost function value of at most `target`
+    /// was found, if any.
+    pub fn evaluations_to_reach(&self, target: f64) -> Option<f64> {
+        self.improvements
+            .iter()
+            .find(|(_, cost)| *cost <= target)
+            .map(|(evaluations, _)| *evaluations)
+    }
+}
+
+/// Results of a [`Benchmark`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchmarkResults {
+    /// Names of the solvers
+    solvers: Vec<String>,
+    /// Names of the problems
+    problems: Vec<String>,
+    /// Tolerance of the convergence test
+    tolerance: f64,
+    /// All runs, ordered by problem and solver
+    runs: Vec<Run>,
+}
+
+impl BenchmarkResults {
+    /// Returns all runs, ordered by problem and solver.
+    pub fn runs(&self) -> &[Run] {
+        &self.runs
+    }
+
+    /// Returns the runs on the problem with index `problem`.
+    fn problem_runs(&self, problem: usize) -> &[Run] {
+        let n = self.solvers.len();
+        &self.runs[problem * n..(problem + 1) * n]
+    }
+
+    /// Returns the number of evaluations each solver needed to solve each problem (indexed by
+    /// problem and solver).
+    fn evaluations_to_solve(&self) -> Vec<Vec<Option<f64>>> {
+        (0..self.problems.len())
+            .map(|p| {
+                let runs = self.problem_runs(p);
+                let best = runs
+                    .iter()
+                    .map(|run| run.best_cost)
+                    .fold(f64::INFINITY, f64::min);
+                runs.iter()
+                    .map(|run| {
+                        let target = best + self.tolerance * (run.initial_cost - best);
+                        run.evaluations_to_reach(target)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the fraction of problems solved by each solver.
+    pub fn success_rates(&self) -> Vec<(String, f64)> {
+        let evaluations = self.evaluations_to_solve();
+        self.solvers
+            .iter()
+            .enumerate()
+            .map(|(s, name)| {
+                let solved = evaluations.iter().filter(|e| e[s].is_some()).count();
+                (name.clone(), fraction(solved, self.problems.len()))
+            })
+            .collect()
+    }
+
+    /// Computes the performance profile.
+    ///
+    /// For each solver, the profile gives the fraction of problems which were solved with at most
+    /// `alpha` times the number of evaluations of the best solver on the respective problem.
+    pub fn performance_profile(&self) -> Profile {
+        let ratios: Vec<Vec<Option<f64>>> = self
+            .evaluations_to_solve()
+            .into_iter()
+            .map(|evaluations| {
+                let min = evaluations
+                    .iter()
+                    .flatten()
+                    .fold(f64::INFINITY, |acc, e| acc.min(*e));
+                evaluations
+                    .iter()
+                    .map(|e| e.map(|e| if min > 0.0 { e / min } else { 1.0 }))
+                    .collect()
+            })
+            .collect();
+        self.profile("alpha", ratios)
+    }
+
+    /// Computes the data profile.
+    ///
+    /// For each solver, the profile gives the fraction of problems which were solved with at most
+    /// `kappa * (n + 1)` evaluations, where `n` is the dimension of the respective problem.
+    /// `kappa` therefore corresponds to the number of simplex gradient estimates.
+    pub fn data_profile(&self) -> Profile {
+        let costs: Vec<Vec<Option<f64>>> = self
+            .evaluations_to_solve()
+            .into_iter()
+            .enumerate()
+            .map(|(p, evaluations)| {
+                let scale = (self.problem_runs(p)[0].dimension + 1) as f64;
+                evaluations.iter().map(|e| e.map(|e| e / scale)).collect()
+            })
+            .collect();
+        self.profile("kappa", costs)
+    }
+
+    /// Computes a profile from per problem and solver values, where `None` marks unsolved
+    /// problems.
+    fn profile(&self, label: &'static str, values: Vec<Vec<Option<f64>>>) -> Profile {
+        let mut breakpoints: Vec<f64> = values.iter().flatten().flatten().copied().collect();
+        breakpoints.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        breakpoints.dedup();
+        let points = breakpoints
+            .into_iter()
+            .map(|x| {
+                let fractions = (0..self.solvers.len())
+                    .map(|s| {
+                        let count = values
+                            .iter()
+                            .filter(|v| v[s].map(|v| v <= x).unwrap_or(false))
+                            .count();
+                        fraction(count, self.problems.len())
+                    })
+                    .collect();
+                (x, fractions)
+            })
+            .collect();
+        Profile {
+            label,
+            solvers: self.solvers.clone(),
+            points,
+        }
+    }
+
+    /// Writes a summary of all runs as CSV.
+    ///
+    /// The columns are solver, problem, dimension, initial cost, best cost, evaluations, time in
+    /// seconds, whether the problem was solved and the error message.
+    pub fn write_runs_csv<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        writeln!(
+            writer,
+            "solver,problem,dimension,initial_cost,best_cost,evaluations,time,solved,error"
+        )?;
+        let evaluations = self.evaluations_to_solve();
+        for (i, run) in self.runs.iter().enumerate() {
+            let solved = evaluations[i / self.solvers.len()][i % self.solvers.len()].is_some();
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{}",
+                csv_field(&run.solver),
+                csv_field(&run.problem),
+                run.dimension,
+                run.initial_cost,
+                run.best_cost,
+                run.evaluations,
+                run.time.as_secs_f64(),
+                solved,
+                csv_field(run.error.as_deref().unwrap_or("")),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Performance or data profile of a set of solvers.
+///
+/// The profile is a step function for each solver which is given at the points where any of the
+/// functions changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    /// Label of the abscissa
+    label: &'static str,
+    /// Names of the solvers
+    solvers: Vec<String>,
+    /// Abscissa and the fraction of problems for each solver
+    points: Vec<(f64, Vec<f64>)>,
+}
+
+impl Profile {
+    /// Returns the names of the solvers in the order of the values of [`Profile::points`].
+    pub fn solvers(&self) -> &[String] {
+        &self.solvers
+    }
+
+    /// Returns the points at which the profile changes together with the fraction of problems for
+    /// each solver.
+    pub fn points(&self) -> &[(f64, Vec<f64>)] {
+        &self.points
+    }
+
+    /// Writes the profile as CSV with one column per solver.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        write!(writer, "{}", self.label)?;
+        for solver in self.solvers.iter() {
+            write!(writer, ",{}", csv_field(solver))?;
+        }
+        writeln!(writer)?;
+        for (x, fractions) in self.points.iter() {
+            write!(writer, "{x}")?;
+            for f in fractions.iter() {
+                write!(writer, ",{f}")?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns `count / total`, or 0 if `total` is 0.
+fn fraction(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+/// Quotes a CSV field if necessary.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProblemSet;
+    use approx::assert_relative_eq;
+    use argmin::core::Executor;
+    use argmin::solver::neldermead::NelderMead;
+
+    fn problems() -> Vec<TestProblem> {
+        ProblemSet::from_toml_str(
+            r#"
+            [[problems]]
+            function = "sphere"
+            dimension = 2
+            initial_param = 1.0
+
+            [[problems]]
+            function = "rosenbrock"
+            dimension = 2
+            initial_param = [-1.2, 1.0]
+            "#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+    }
+
+    fn nelder_mead(problem: BenchmarkProblem, budget: EvaluationBudget) -> Result<(), Error> {
+        let init = problem.problem().initial_param().to_vec();
+        let simplex = vec![
+            init.clone(),
+            vec![init[0] + 0.5, init[1]],
+            vec![init[0], init[1] + 0.5],
+        ];
+        Executor::new(problem, NelderMead::new(simplex))
+            .evaluation_budget(budget)
+            .configure(|state| state.max_iters(10000))
+            .run()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(Benchmark::new(problems(), 0.0).is_err());
+        assert!(Benchmark::new(problems(), 10.0)
+            .unwrap()
+            .with_weight("gradient_count", -1.0)
+            .is_err());
+        assert!(Benchmark::new(problems(), 10.0)
+            .unwrap()
+            .with_tolerance(1.0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_benchmark() {
+        let results = Benchmark::new(problems(), 300.0)
+            .unwrap()
+            .add_solver("nelder-mead", nelder_mead)
+            // Evaluates the cost function at the initial parameter vector and a single other point
+            .add_solver("lazy", |problem, _budget| {
+                let init = problem.problem().initial_param().to_vec();
+                problem.cost(&init)?;
+                problem.cost(&init.iter().map(|x| 0.9 * x).collect())?;
+                Ok(())
+            })
+            .add_solver("failing", |_problem, _budget| {
+                Err(argmin_error!(NotImplemented, "failing"))
+            })
+            .run();
+
+        assert_eq!(results.runs().len(), 6);
+        for run in results.runs().iter().filter(|r| r.solver == "nelder-mead") {
+            assert!(run.evaluations <= 300.0);
+            assert!(run.best_cost < 1e-3 * run.initial_cost);
+        }
+        let lazy = &results.runs()[1];
+        assert_eq!(lazy.evaluations, 2.0);
+        assert_eq!(lazy.improvements.len(), 1);
+        assert_eq!(lazy.improvements[0].0, 2.0);
+        assert_relative_eq!(lazy.improvements[0].1, 1.62, epsilon = 1e-12);
+        assert!(results.runs()[2].error.is_some());
+
+        assert_eq!(
+            results.success_rates(),
+            vec![
+                ("nelder-mead".to_string(), 1.0),
+                ("lazy".to_string(), 0.0),
+                ("failing".to_string(), 0.0)
+            ]
+        );
+
+        let profile = results.performance_profile();
+        // Only Nelder-Mead solves the problems, hence all ratios are 1
+        assert_eq!(profile.points(), &[(1.0, vec![1.0, 0.0, 0.0])]);
+        let profile = results.data_profile();
+        assert_eq!(profile.points().len(), 2);
+        assert_eq!(profile.points()[0].1, vec![0.5, 0.0, 0.0]);
+        assert_eq!(profile.points()[1].1, vec![1.0, 0.0, 0.0]);
+
+        let mut csv = Vec::new();
+        profile.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("kappa,nelder-mead,lazy,failing\n"));
+        assert_eq!(csv.lines().count(), profile.points().len() + 1);
+
+        let mut csv = Vec::new();
+        results.write_runs_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 7);
+        assert!(csv
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("nelder-mead,sphere-2,2,2,"));
+    }
+
+    #[test]
+    fn test_weights() {
+        let results = Benchmark::new(problems(), 10.0)
+            .unwrap()
+            .with_weight("gradient_count", 4.0)
+            .unwrap()
+            .add_solver("gradient", |problem, _budget| {
+                let init = problem.problem().initial_param().to_vec();
+                problem.gradient(&init)?;
+                problem.cost(&init.iter().map(|x| 0.5 * x).collect())?;
+                problem.gradient(&init)?;
+                problem.gradient(&init)?;
+                // Beyond the budget
+                problem.cost(&init.iter().map(|x| 0.1 * x).collect())?;
+                Ok(())
+            })
+            .run();
+        let run = &results.runs()[0];
+        assert_eq!(run.evaluations, 10.0);
+        assert_eq!(run.improvements.len(), 1);
+        assert_eq!(run.improvements[0].0, 5.0);
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("solver"), "solver");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+    }
+}
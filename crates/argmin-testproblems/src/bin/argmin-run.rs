@@ -0,0 +1,38 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Runs an optimization described by a configuration file and writes a JSON report.
+//!
+//! See `argmin_testproblems::cli` for the format of the configuration file.
+
+use std::path::PathBuf;
+
+use argmin::core::Error;
+use argmin_testproblems::cli::RunConfig;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Run configuration (JSON or TOML)
+    config: PathBuf,
+
+    /// Write the report to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let report = RunConfig::from_file(&args.config)?.run()?;
+    let report = serde_json::to_string_pretty(&report)?;
+    match args.output {
+        Some(path) => std::fs::write(path, report + "\n")?,
+        None => println!("{report}"),
+    }
+    Ok(())
+}
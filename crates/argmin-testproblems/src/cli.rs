@@ -0,0 +1,953 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Running optimizations from configuration files (feature `cli`).
+//!
+//! A [`RunConfig`] describes a single optimization run: the problem, the solver, termination
+//! criteria of the executor, observers and checkpointing. The `argmin-run` binary reads such a
+//! configuration from a JSON or TOML file, runs the optimization and writes a JSON report (see
+//! [`RunConfig::run`]):
+//!
+//! ```bash
+//! argmin-run rosenbrock.toml --output result.json
+//! ```
+//!
+//! ```toml
+//! [problem]
+//! function = "rosenbrock"
+//! dimension = 2
+//! initial_param = [-1.2, 1.0]
+//!
+//! [solver]
+//! name = "lbfgs"
+//! m = 7
+//!
+//! [executor]
+//! max_iters = 100
+//! target_cost = 1e-10
+//!
+//! [[observers]]
+//! kind = "terminal"
+//! mode = { every = 10 }
+//!
+//! [checkpointing]
+//! directory = ".checkpoints"
+//! name = "rosenbrock"
+//! frequency = { every = 20 }
+//! ```
+//!
+//! # Problems
+//!
+//! The problem is either a test function, defined exactly like a single entry of a problem file
+//! (see [`ProblemDefinition`]), or a plugin: a shared library which exports the cost function and
+//! optionally the gradient as C functions (see [`PluginDefinition`]).
+//!
+//! # Solvers
+//!
+//! The solver is selected by `name` (see [`SolverConfig`]):
+//!
+//! | Name               | Solver                          | Options                              |
+//! |--------------------|---------------------------------|--------------------------------------|
+//! | `steepest_descent` | Steepest descent (More-Thuente) |                                      |
+//! | `lbfgs`            | L-BFGS (More-Thuente)           | `m` (7), `tol_grad`, `tol_cost`      |
+//! | `bfgs`             | BFGS (More-Thuente)             | `tol_grad`, `tol_cost`               |
+//! | `nelder_mead`      | Nelder-Mead                     | `simplex_step` (0.1), `sd_tolerance` |
+//! | `particle_swarm`   | Particle Swarm Optimization     | `num_particles` (40)                 |
+//!
+//! Gradient-based solvers use central finite differences if a plugin does not provide a
+//! gradient. Nelder-Mead constructs its initial simplex from the initial parameter vector by
+//! moving each coordinate by `simplex_step`. Particle Swarm Optimization requires bounds.
+//!
+//! # Observers and checkpointing
+//!
+//! Observers log to the terminal (`terminal`), to a file (`log_file` with a `path`) or write the
+//! parameter vectors to a directory (`param_writer` with `dir`, `prefix` and `format`). The `mode`
+//! is one of `always` (default), `never`, `new_best` or `{ every = N }`.
+//!
+//! If `checkpointing` is configured, checkpoints are written via `argmin-checkpointing-file` and
+//! an existing checkpoint is resumed, which allows restarting interrupted cluster jobs with the
+//! same configuration. The `frequency` is one of `always` (default), `never`, `new_best`,
+//! `{ every = N }` or `{ time = <seconds> }`.
+
+use std::ffi::{c_int, c_void};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use argmin::argmin_error;
+use argmin::core::checkpointing::CheckpointingFrequency;
+use argmin::core::observers::ObserverMode;
+use argmin::core::{
+    CostFunction, Error, Executor, Gradient, IterState, PopulationState, Solver, State,
+};
+use argmin::solver::gradientdescent::SteepestDescent;
+use argmin::solver::linesearch::MoreThuenteLineSearch;
+use argmin::solver::neldermead::NelderMead;
+use argmin::solver::particleswarm::{Particle, ParticleSwarm};
+use argmin::solver::quasinewton::{BFGS, LBFGS};
+use argmin_checkpointing_file::FileCheckpoint;
+use argmin_observer_paramwriter::{ParamWriter, ParamWriterFormat};
+use argmin_observer_slog::SlogLogger;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::{expand_param_and_bounds, ProblemDefinition, TestProblem, Values};
+
+/// Signature of the functions exported by plugins
+///
+/// The function writes the result for the `n` parameters at `param` (a single value for the cost
+/// function, `n` values for the gradient) to `out` and returns `0` on success. This is the same
+/// signature as the callbacks of `argmin-capi`; `user_data` is always NULL.
+pub type PluginFn = unsafe extern "C" fn(
+    param: *const f64,
+    n: usize,
+    out: *mut f64,
+    user_data: *mut c_void,
+) -> c_int;
+
+/// Problem provided by a dynamically loaded shared library.
+///
+/// ```toml
+/// [problem]
+/// plugin = "./libmyproblem.so"
+/// cost = "my_cost"
+/// gradient = "my_gradient"
+/// dimension = 3
+/// initial_param = [1.0, 2.0, 3.0]
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginDefinition {
+    /// Name of the problem (defaults to the file name of the library)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Path to the shared library
+    pub plugin: PathBuf,
+    /// Symbol of the cost function (defaults to `cost`)
+    #[serde(default = "default_cost_symbol")]
+    pub cost: String,
+    /// Symbol of the gradient (approximated via finite differences if not given)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gradient: Option<String>,
+    /// Number of parameters
+    pub dimension: usize,
+    /// Initial parameter vector
+    pub initial_param: Values,
+    /// Lower bound (requires `upper_bound`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lower_bound: Option<Values>,
+    /// Upper bound (requires `lower_bound`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upper_bound: Option<Values>,
+}
+
+fn default_cost_symbol() -> String {
+    "cost".to_string()
+}
+
+/// The problem of a run, either a plugin or a test function
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ProblemConfig {
+    /// Problem provided by a shared library
+    Plugin(PluginDefinition),
+    /// Test function of `argmin_testfunctions`
+    TestFunction(ProblemDefinition),
+}
+
+/// Solver and its options
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "name", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SolverConfig {
+    /// Steepest descent with More-Thuente line search
+    SteepestDescent,
+    /// L-BFGS with More-Thuente line search
+    Lbfgs {
+        /// Number of stored updates
+        #[serde(default = "default_m")]
+        m: usize,
+        /// Tolerance for the stopping criterion based on the gradient
+        tol_grad: Option<f64>,
+        /// Tolerance for the stopping criterion based on the change of the cost
+        tol_cost: Option<f64>,
+    },
+    /// BFGS with More-Thuente line search, starting from the identity as inverse Hessian
+    Bfgs {
+        /// Tolerance for the stopping criterion based on the gradient
+        tol_grad: Option<f64>,
+        /// Tolerance for the stopping criterion based on the change of the cost
+        tol_cost: Option<f64>,
+    },
+    /// Nelder-Mead
+    NelderMead {
+        /// Offset of the vertices of the initial simplex
+        #[serde(default = "default_simplex_step")]
+        simplex_step: f64,
+        /// Tolerance for the standard deviation of the cost function values of the simplex
+        sd_tolerance: Option<f64>,
+    },
+    /// Particle Swarm Optimization
+    ParticleSwarm {
+        /// Number of particles
+        #[serde(default = "default_num_particles")]
+        num_particles: usize,
+    },
+}
+
+fn default_m() -> usize {
+    7
+}
+
+fn default_simplex_step() -> f64 {
+    0.1
+}
+
+fn default_num_particles() -> usize {
+    40
+}
+
+/// Termination criteria and seeding of the `Executor`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutorConfig {
+    /// Maximum number of iterations (default: 100)
+    #[serde(default = "default_max_iters")]
+    pub max_iters: u64,
+    /// Terminate once the cost is below this value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_cost: Option<f64>,
+    /// Terminate after this wall-clock time in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_time: Option<f64>,
+    /// Seed of the random number generators, which makes the run reproducible
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+fn default_max_iters() -> u64 {
+    100
+}
+
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        ExecutorConfig {
+            max_iters: default_max_iters(),
+            target_cost: None,
+            max_time: None,
+            seed: None,
+        }
+    }
+}
+
+/// When observers are called
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    /// In every iteration
+    #[default]
+    Always,
+    /// Never
+    Never,
+    /// Whenever a new best parameter vector was found
+    NewBest,
+    /// Every N iterations
+    Every(u64),
+}
+
+impl From<Mode> for ObserverMode {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Always => ObserverMode::Always,
+            Mode::Never => ObserverMode::Never,
+            Mode::NewBest => ObserverMode::NewBest,
+            Mode::Every(n) => ObserverMode::Every(n),
+        }
+    }
+}
+
+/// Format of the files written by the `param_writer` observer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamFormat {
+    /// JSON files
+    #[default]
+    Json,
+    /// Binary files (bincode)
+    Binary,
+}
+
+/// Observer of a run
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ObserverConfig {
+    /// Logs to the terminal (stderr)
+    Terminal {
+        /// When to log
+        #[serde(default)]
+        mode: Mode,
+    },
+    /// Logs to a file in JSON format
+    LogFile {
+        /// Path of the log file, which is truncated
+        path: PathBuf,
+        /// When to log
+        #[serde(default)]
+        mode: Mode,
+    },
+    /// Writes parameter vectors to files
+    ParamWriter {
+        /// Directory of the files
+        dir: PathBuf,
+        /// Prefix of the file names (defaults to `param`)
+        #[serde(default = "default_prefix")]
+        prefix: String,
+        /// File format
+        #[serde(default)]
+        format: ParamFormat,
+        /// When to write
+        #[serde(default)]
+        mode: Mode,
+    },
+}
+
+fn default_prefix() -> String {
+    "param".to_string()
+}
+
+/// When checkpoints are written
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Frequency {
+    /// In every iteration
+    #[default]
+    Always,
+    /// Never
+    Never,
+    /// Whenever a new best parameter vector was found
+    NewBest,
+    /// Every N iterations
+    Every(u64),
+    /// Whenever the given wall-clock time in seconds has passed
+    Time(f64),
+}
+
+/// Checkpointing of a run
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointConfig {
+    /// Directory of the checkpoint (defaults to `.checkpoints`)
+    #[serde(default = "default_checkpoint_directory")]
+    pub directory: PathBuf,
+    /// Name of the checkpoint file (without extension)
+    pub name: String,
+    /// When checkpoints are written
+    #[serde(default)]
+    pub frequency: Frequency,
+}
+
+fn default_checkpoint_directory() -> PathBuf {
+    PathBuf::from(".checkpoints")
+}
+
+/// Configuration of a single optimization run.
+///
+/// See the [module documentation](crate::cli) for the format.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RunConfig {
+    /// The problem
+    pub problem: ProblemConfig,
+    /// The solver
+    pub solver: SolverConfig,
+    /// Termination criteria and seeding
+    #[serde(default)]
+    pub executor: ExecutorConfig,
+    /// Observers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub observers: Vec<ObserverConfig>,
+    /// Checkpointing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpointing: Option<CheckpointConfig>,
+}
+
+impl RunConfig {
+    /// Parses a run configuration in JSON format.
+    pub fn from_json_str(content: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    /// Parses a run configuration in TOML format.
+    pub fn from_toml_str(content: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Reads a run configuration from a file.
+    ///
+    /// The format is determined by the file extension (`json` or `toml`).
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json_str(&std::fs::read_to_string(path)?),
+            Some("toml") => Self::from_toml_str(&std::fs::read_to_string(path)?),
+            _ => Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`RunConfig`: unsupported format of configuration file `{}`.",
+                    path.display()
+                )
+            )),
+        }
+    }
+
+    /// Runs the optimization and returns a JSON report.
+    ///
+    /// The report contains the name (`problem`) and dimension (`dimension`) of the problem as
+    /// well as the [`Report`](argmin::core::Report) of the run (`report`), which includes the
+    /// configuration of the solver, the termination status, the best parameter vector and cost,
+    /// function evaluation counts and timings.
+    pub fn run(&self) -> Result<serde_json::Value, Error> {
+        let problem = CliProblem::new(&self.problem)?;
+        let init = problem.initial_param.clone();
+        let max_iters = self.executor.max_iters;
+        match self.solver {
+            SolverConfig::SteepestDescent => {
+                let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+                self.execute(problem, solver, |state: IterState<_, _, _, _, _, _>| {
+                    state.param(init).max_iters(max_iters)
+                })
+            }
+            SolverConfig::Lbfgs {
+                m,
+                tol_grad,
+                tol_cost,
+            } => {
+                let mut solver = LBFGS::new(MoreThuenteLineSearch::new(), m);
+                if let Some(tol_grad) = tol_grad {
+                    solver = solver.with_tolerance_grad(tol_grad)?;
+                }
+                if let Some(tol_cost) = tol_cost {
+                    solver = solver.with_tolerance_cost(tol_cost)?;
+                }
+                self.execute(problem, solver, |state: IterState<_, _, _, _, _, _>| {
+                    state.param(init).max_iters(max_iters)
+                })
+            }
+            SolverConfig::Bfgs { tol_grad, tol_cost } => {
+                let n = init.len();
+                let inv_hessian: Vec<Vec<f64>> = (0..n)
+                    .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+                    .collect();
+                let mut solver = BFGS::new(MoreThuenteLineSearch::new());
+                if let Some(tol_grad) = tol_grad {
+                    solver = solver.with_tolerance_grad(tol_grad)?;
+                }
+                if let Some(tol_cost) = tol_cost {
+                    solver = solver.with_tolerance_cost(tol_cost)?;
+                }
+                self.execute(problem, solver, |state: IterState<_, _, _, _, _, _>| {
+                    state
+                        .param(init)
+                        .inv_hessian(inv_hessian)
+                        .max_iters(max_iters)
+                })
+            }
+            SolverConfig::NelderMead {
+                simplex_step,
+                sd_tolerance,
+            } => {
+                let mut simplex = vec![init.clone()];
+                for i in 0..init.len() {
+                    let mut vertex = init.clone();
+                    vertex[i] += simplex_step;
+                    simplex.push(vertex);
+                }
+                let mut solver = NelderMead::new(simplex);
+                if let Some(sd_tolerance) = sd_tolerance {
+                    solver = solver.with_sd_tolerance(sd_tolerance)?;
+                }
+                self.execute(problem, solver, |state: IterState<_, (), (), (), (), _>| {
+                    state.max_iters(max_iters)
+                })
+            }
+            SolverConfig::ParticleSwarm { num_particles } => {
+                let bounds = problem.bounds.clone().ok_or_else(|| -> Error {
+                    argmin_error!(
+                        InvalidParameter,
+                        "`RunConfig`: `particle_swarm` requires bounds."
+                    )
+                })?;
+                let solver = ParticleSwarm::new(bounds, num_particles);
+                self.execute(
+                    problem,
+                    solver,
+                    |state: PopulationState<Particle<Vec<f64>, f64>, f64>| {
+                        state.max_iters(max_iters)
+                    },
+                )
+            }
+        }
+    }
+
+    fn execute<S, I>(
+        &self,
+        problem: CliProblem,
+        solver: S,
+        configure: impl FnOnce(I) -> I,
+    ) -> Result<serde_json::Value, Error>
+    where
+        S: Solver<CliProblem, I> + Serialize + DeserializeOwned + 'static,
+        I: State<Float = f64> + Serialize + DeserializeOwned + 'static,
+        I::Param: Serialize,
+    {
+        let name = problem.name.clone();
+        let dimension = problem.initial_param.len();
+        let mut executor = Executor::new(problem, solver).configure(configure);
+        if let Some(target_cost) = self.executor.target_cost {
+            executor = executor.target_cost(target_cost);
+        }
+        if let Some(max_time) = self.executor.max_time {
+            executor = executor.max_time(Duration::from_secs_f64(max_time));
+        }
+        if let Some(seed) = self.executor.seed {
+            executor = executor.deterministic(seed);
+        }
+        for observer in self.observers.iter() {
+            executor = match observer {
+                ObserverConfig::Terminal { mode } => {
+                    executor.add_observer(SlogLogger::term(), (*mode).into())
+                }
+                ObserverConfig::LogFile { path, mode } => executor.add_observer(
+                    SlogLogger::file(path.to_string_lossy(), true)?,
+                    (*mode).into(),
+                ),
+                ObserverConfig::ParamWriter {
+                    dir,
+                    prefix,
+                    format,
+                    mode,
+                } => {
+                    let format = match format {
+                        ParamFormat::Json => ParamWriterFormat::JSON,
+                        ParamFormat::Binary => ParamWriterFormat::Binary,
+                    };
+                    let dir = dir.to_string_lossy().to_string();
+                    executor.add_observer(
+                        ParamWriter::new(dir.as_str(), prefix.as_str(), format),
+                        (*mode).into(),
+                    )
+                }
+            };
+        }
+        if let Some(checkpointing) = self.checkpointing.as_ref() {
+            let frequency = match checkpointing.frequency {
+                Frequency::Always => CheckpointingFrequency::Always,
+                Frequency::Never => CheckpointingFrequency::Never,
+                Frequency::NewBest => CheckpointingFrequency::NewBest,
+                Frequency::Every(n) => CheckpointingFrequency::Every(n),
+                Frequency::Time(secs) => {
+                    CheckpointingFrequency::Time(Duration::from_secs_f64(secs))
+                }
+            };
+            let directory = checkpointing.directory.to_string_lossy().to_string();
+            executor = executor.checkpointing(FileCheckpoint::new(
+                directory.as_str(),
+                checkpointing.name.as_str(),
+                frequency,
+            ));
+        }
+        let result = executor.run()?;
+        Ok(serde_json::json!({
+            "problem": name,
+            "dimension": dimension,
+            "report": result.to_report(),
+        }))
+    }
+}
+
+/// Functions of a loaded plugin
+struct Plugin {
+    cost: PluginFn,
+    gradient: Option<PluginFn>,
+    /// Keeps the library loaded as long as the function pointers are in use
+    _library: libloading::Library,
+}
+
+impl Plugin {
+    fn load(definition: &PluginDefinition) -> Result<Self, Error> {
+        // SAFETY: Loading a library runs its initialization routines. The user is responsible for
+        // only loading trusted plugins.
+        let library = unsafe { libloading::Library::new(&definition.plugin)? };
+        // SAFETY: The plugin is required to export the symbols with the signature of `PluginFn`.
+        let cost = unsafe { *library.get::<PluginFn>(definition.cost.as_bytes())? };
+        let gradient = match definition.gradient.as_ref() {
+            // SAFETY: See above
+            Some(symbol) => Some(unsafe { *library.get::<PluginFn>(symbol.as_bytes())? }),
+            None => None,
+        };
+        Ok(Plugin {
+            cost,
+            gradient,
+            _library: library,
+        })
+    }
+
+    fn call(&self, func: PluginFn, param: &[f64], out: &mut [f64]) -> Result<(), Error> {
+        // SAFETY: The plugin is required to read `n` parameters and to write `out.len()` values.
+        let status = unsafe {
+            func(
+                param.as_ptr(),
+                param.len(),
+                out.as_mut_ptr(),
+                std::ptr::null_mut(),
+            )
+        };
+        if status != 0 {
+            return Err(argmin_error!(
+                PotentialBug,
+                format!("Plugin function returned error code {status}.")
+            ));
+        }
+        Ok(())
+    }
+}
+
+enum CliFunction {
+    TestFunction(TestProblem),
+    Plugin(Plugin),
+}
+
+/// The problem of a run
+pub struct CliProblem {
+    name: String,
+    initial_param: Vec<f64>,
+    bounds: Option<(Vec<f64>, Vec<f64>)>,
+    function: CliFunction,
+}
+
+impl CliProblem {
+    fn new(config: &ProblemConfig) -> Result<Self, Error> {
+        match config {
+            ProblemConfig::TestFunction(definition) => {
+                let problem = definition.build()?;
+                Ok(CliProblem {
+                    name: problem.name().to_string(),
+                    initial_param: problem.initial_param().to_vec(),
+                    bounds: problem.bounds(),
+                    function: CliFunction::TestFunction(problem),
+                })
+            }
+            ProblemConfig::Plugin(definition) => {
+                let name = definition.name.clone().unwrap_or_else(|| {
+                    definition
+                        .plugin
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                });
+                let (initial_param, bounds) = expand_param_and_bounds(
+                    definition.dimension,
+                    &definition.initial_param,
+                    definition.lower_bound.as_ref(),
+                    definition.upper_bound.as_ref(),
+                )
+                .map_err(|msg| -> Error {
+                    argmin_error!(
+                        InvalidParameter,
+                        format!("`PluginDefinition` `{name}`: {msg}")
+                    )
+                })?;
+                Ok(CliProblem {
+                    name,
+                    initial_param,
+                    bounds,
+                    function: CliFunction::Plugin(Plugin::load(definition)?),
+                })
+            }
+        }
+    }
+
+    fn check_dimension(&self, param: &[f64]) -> Result<(), Error> {
+        if param.len() != self.initial_param.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`CliProblem` `{}`: expected {} parameters, got {}.",
+                    self.name,
+                    self.initial_param.len(),
+                    param.len()
+                )
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl CostFunction for CliProblem {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        match &self.function {
+            CliFunction::TestFunction(problem) => problem.cost(param),
+            CliFunction::Plugin(plugin) => {
+                self.check_dimension(param)?;
+                let mut cost = [f64::NAN];
+                plugin.call(plugin.cost, param, &mut cost)?;
+                Ok(cost[0])
+            }
+        }
+    }
+}
+
+impl Gradient for CliProblem {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        match &self.function {
+            CliFunction::TestFunction(problem) => problem.gradient(param),
+            CliFunction::Plugin(plugin) => {
+                self.check_dimension(param)?;
+                let Some(gradient_fn) = plugin.gradient else {
+                    return finitediff::vec::central_diff(&|p| self.cost(p))(param);
+                };
+                let mut gradient = vec![f64::NAN; param.len()];
+                plugin.call(gradient_fn, param, &mut gradient)?;
+                Ok(gradient)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::ArgminError;
+
+    #[test]
+    fn test_parse() {
+        let config = RunConfig::from_toml_str(
+            r#"
+            [problem]
+            function = "rosenbrock"
+            dimension = 2
+            initial_param = [-1.2, 1.0]
+
+            [solver]
+            name = "lbfgs"
+            tol_grad = 1e-8
+
+            [executor]
+            target_cost = 1e-10
+
+            [[observers]]
+            kind = "terminal"
+            mode = { every = 10 }
+
+            [checkpointing]
+            name = "rosenbrock"
+            frequency = "new_best"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(config.problem, ProblemConfig::TestFunction(_)));
+        assert_eq!(
+            config.solver,
+            SolverConfig::Lbfgs {
+                m: 7,
+                tol_grad: Some(1e-8),
+                tol_cost: None
+            }
+        );
+        assert_eq!(config.executor.max_iters, 100);
+        assert_eq!(config.executor.target_cost, Some(1e-10));
+        assert_eq!(
+            config.observers,
+            vec![ObserverConfig::Terminal {
+                mode: Mode::Every(10)
+            }]
+        );
+        let checkpointing = config.checkpointing.unwrap();
+        assert_eq!(checkpointing.directory, PathBuf::from(".checkpoints"));
+        assert_eq!(checkpointing.frequency, Frequency::NewBest);
+
+        let config = RunConfig::from_json_str(
+            r#"{
+                "problem": {
+                    "plugin": "libproblem.so",
+                    "gradient": "gradient",
+                    "dimension": 3,
+                    "initial_param": 1.0
+                },
+                "solver": {"name": "nelder_mead"}
+            }"#,
+        )
+        .unwrap();
+        let ProblemConfig::Plugin(plugin) = config.problem else {
+            panic!("expected plugin");
+        };
+        assert_eq!(plugin.cost, "cost");
+        assert_eq!(plugin.gradient.as_deref(), Some("gradient"));
+        assert_eq!(
+            config.solver,
+            SolverConfig::NelderMead {
+                simplex_step: 0.1,
+                sd_tolerance: None
+            }
+        );
+
+        // Options of other solvers are rejected
+        assert!(RunConfig::from_toml_str(
+            r#"
+            [problem]
+            function = "sphere"
+            dimension = 2
+            initial_param = 1.0
+
+            [solver]
+            name = "bfgs"
+            m = 7
+            "#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        for solver in ["steepest_descent", "lbfgs", "bfgs", "nelder_mead"] {
+            let config = RunConfig::from_toml_str(&format!(
+                r#"
+                [problem]
+                function = "sphere"
+                dimension = 3
+                initial_param = [1.0, -2.0, 0.5]
+
+                [solver]
+                name = "{solver}"
+
+                [executor]
+                max_iters = 300
+                target_cost = 1e-10
+                "#
+            ))
+            .unwrap();
+            let report = config.run().unwrap();
+            assert_eq!(report["problem"], "sphere-3");
+            assert_eq!(report["dimension"], 3);
+            assert!(
+                report["report"]["best_cost"].as_f64().unwrap() < 1e-8,
+                "{solver}"
+            );
+            assert_eq!(
+                report["report"]["termination_status"]["Terminated"], "TargetCostReached",
+                "{solver}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_particle_swarm() {
+        let definition = r#"
+            [problem]
+            function = "sphere"
+            dimension = 2
+            initial_param = 1.0
+            {bounds}
+
+            [solver]
+            name = "particle_swarm"
+            num_particles = 20
+
+            [executor]
+            max_iters = 20
+            seed = 42
+            "#;
+        let config = RunConfig::from_toml_str(&definition.replace("{bounds}", "")).unwrap();
+        let err = config.run().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ArgminError>(),
+            Some(ArgminError::InvalidParameter { .. })
+        ));
+
+        let config = RunConfig::from_toml_str(
+            &definition.replace("{bounds}", "lower_bound = -5.0\nupper_bound = 5.0"),
+        )
+        .unwrap();
+        let first = config.run().unwrap();
+        let second = config.run().unwrap();
+        assert_eq!(first["report"]["iters"], 20);
+        assert_eq!(
+            first["report"]["best_param"],
+            second["report"]["best_param"]
+        );
+    }
+
+    #[test]
+    fn test_run_with_observers_and_checkpointing() {
+        let dir = std::env::temp_dir().join("argmin-testproblems-cli-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let config = RunConfig::from_toml_str(&format!(
+            r#"
+            [problem]
+            function = "rosenbrock"
+            dimension = 2
+            initial_param = [-1.2, 1.0]
+
+            [solver]
+            name = "lbfgs"
+
+            [executor]
+            max_iters = 10
+
+            [[observers]]
+            kind = "param_writer"
+            dir = "{params}"
+            mode = {{ every = 5 }}
+
+            [checkpointing]
+            directory = "{checkpoints}"
+            name = "rosenbrock"
+            frequency = {{ every = 5 }}
+            "#,
+            params = dir.join("params").display(),
+            checkpoints = dir.join("checkpoints").display(),
+        ))
+        .unwrap();
+        let report = config.run().unwrap();
+        assert_eq!(report["report"]["iters"], 10);
+        assert!(dir.join("params").join("param_0.json").exists());
+        assert!(dir.join("params").join("param_5.json").exists());
+        assert!(dir.join("checkpoints").join("rosenbrock.arg").exists());
+
+        // A second run resumes from the checkpoint of the last iteration
+        let cost_count = |report: &serde_json::Value| {
+            report["report"]["func_counts"]["cost_count"]
+                .as_u64()
+                .unwrap_or(0)
+        };
+        let resumed = config.run().unwrap();
+        assert_eq!(resumed["report"]["iters"], 10);
+        assert!(cost_count(&resumed) < cost_count(&report));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_missing_plugin() {
+        let config = RunConfig::from_toml_str(
+            r#"
+            [problem]
+            plugin = "/nonexistent/libproblem.so"
+            dimension = 2
+            initial_param = 1.0
+
+            [solver]
+            name = "lbfgs"
+            "#,
+        )
+        .unwrap();
+        assert!(config.run().is_err());
+    }
+}
@@ -0,0 +1,897 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! This crate constructs argmin problems from declarative problem files.
+//!
+//! A problem file describes a set of problems, each of which consists of a test function of
+//! [`argmin_testfunctions`], the dimension, the initial parameter vector and optionally lower and
+//! upper bounds. This allows benchmark suites and command line tools to run many problems without
+//! recompiling. Problem files can be written in JSON (feature `json`) or TOML (feature `toml`),
+//! both of which are enabled by default.
+//!
+//! ```toml
+//! [[problems]]
+//! name = "rosenbrock-10"
+//! function = "rosenbrock"
+//! dimension = 10
+//! initial_param = -1.2
+//! lower_bound = -5.0
+//! upper_bound = 5.0
+//!
+//! [[problems]]
+//! function = "rastrigin"
+//! a = 5.0
+//! dimension = 3
+//! initial_param = [1.0, -2.0, 0.5]
+//! ```
+//!
+//! The `function` field selects the test function (see [`TestFunction`] for the available
+//! functions and their optional shape parameters). Initial parameter vector and bounds are either
+//! given per dimension or as a single value which is used for all dimensions. If no name is given,
+//! the problem is named after the function and the dimension (for instance `rastrigin-3`).
+//!
+//! [`ProblemSet::build`] validates the definitions and returns [`TestProblem`]s, which implement
+//! [`CostFunction`], [`Gradient`] and [`Hessian`] for `Vec<f64>` and can therefore be used with
+//! most solvers.
+//!
+//! The [`benchmarking`] module compares solvers on a set of problems under equal evaluation
+//! budgets and computes success rates as well as performance and data profiles.
+//!
+//! With the `cli` feature, the [`cli`] module and the `argmin-run` binary run optimizations
+//! described by configuration files, including observers and checkpointing.
+//!
+//! ```
+//! # use argmin::core::Error;
+//! use argmin::core::{Executor, State};
+//! use argmin::solver::neldermead::NelderMead;
+//! use argmin_testproblems::ProblemSet;
+//!
+//! # fn main() -> Result<(), Error> {
+//! let set = ProblemSet::from_toml_str(
+//!     r#"
+//!     [[problems]]
+//!     function = "sphere"
+//!     dimension = 2
+//!     initial_param = [1.0, 2.0]
+//!     "#,
+//! )?;
+//!
+//! for problem in set.build()? {
+//!     let init = problem.initial_param().to_vec();
+//!     let simplex = vec![
+//!         init.clone(),
+//!         vec![init[0] + 1.0, init[1]],
+//!         vec![init[0], init[1] + 1.0],
+//!     ];
+//!     let name = problem.name().to_string();
+//!     let res = Executor::new(problem, NelderMead::new(simplex).with_sd_tolerance(1e-10)?)
+//!         .configure(|state| state.max_iters(200))
+//!         .run()?;
+//!     println!("{name}: {}", res.state().get_best_cost());
+//! #   assert!(res.state().get_best_cost() < 1e-8);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-testproblems = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+pub mod benchmarking;
+#[cfg(feature = "cli")]
+pub mod cli;
+
+use argmin::argmin_error;
+use argmin::core::{CostFunction, Error, Gradient, Hessian};
+use argmin_testfunctions as tf;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Cost function, derivative and Hessian of a two-dimensional test function
+type TwoDimensional = (
+    fn(&[f64; 2]) -> f64,
+    fn(&[f64; 2]) -> [f64; 2],
+    fn(&[f64; 2]) -> [[f64; 2]; 2],
+);
+
+/// Test functions which can be used in problem files.
+///
+/// The names used in problem files are those of the corresponding functions in
+/// [`argmin_testfunctions`]. Optional shape parameters default to the values used by the
+/// functions without shape parameters (for instance `rosenbrock` instead of `rosenbrock_ab`).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "function", rename_all = "snake_case")]
+pub enum TestFunction {
+    /// Ackley function (multidimensional)
+    Ackley {
+        /// Defaults to 20
+        #[serde(default = "default_ackley_a")]
+        a: f64,
+        /// Defaults to 0.2
+        #[serde(default = "default_ackley_b")]
+        b: f64,
+        /// Defaults to 2π
+        #[serde(default = "default_ackley_c")]
+        c: f64,
+    },
+    /// Griewank function (multidimensional)
+    Griewank,
+    /// Levy function (multidimensional)
+    Levy,
+    /// Rastrigin function (multidimensional)
+    Rastrigin {
+        /// Defaults to 10
+        #[serde(default = "default_rastrigin_a")]
+        a: f64,
+    },
+    /// Rosenbrock function (multidimensional, at least two dimensions)
+    Rosenbrock {
+        /// Defaults to 1
+        #[serde(default = "default_rosenbrock_a")]
+        a: f64,
+        /// Defaults to 100
+        #[serde(default = "default_rosenbrock_b")]
+        b: f64,
+    },
+    /// Schwefel function (multidimensional)
+    Schwefel,
+    /// Sphere function (multidimensional)
+    Sphere,
+    /// Styblinski-Tang function (multidimensional)
+    StyblinskiTang,
+    /// Zero function (multidimensional)
+    Zero,
+    /// Beale function (two-dimensional)
+    Beale,
+    /// Booth function (two-dimensional)
+    Booth,
+    /// Bukin function N. 6 (two-dimensional)
+    BukinN6,
+    /// Cross-in-tray function (two-dimensional)
+    CrossInTray,
+    /// Easom function (two-dimensional)
+    Easom,
+    /// Eggholder function (two-dimensional)
+    Eggholder,
+    /// Goldstein-Price function (two-dimensional)
+    #[serde(rename = "goldsteinprice")]
+    GoldsteinPrice,
+    /// Himmelblau function (two-dimensional)
+    Himmelblau,
+    /// Hölder table function (two-dimensional)
+    HolderTable,
+    /// Levy function N. 13 (two-dimensional)
+    LevyN13,
+    /// Matyas function (two-dimensional)
+    Matyas,
+    /// McCormick function (two-dimensional)
+    #[serde(rename = "mccorminck")]
+    McCorminck,
+    /// Picheny function (two-dimensional)
+    Picheny,
+    /// Schaffer function N. 2 (two-dimensional)
+    SchafferN2,
+    /// Schaffer function N. 4 (two-dimensional)
+    SchafferN4,
+    /// Three-hump camel function (two-dimensional)
+    #[serde(rename = "threehumpcamel")]
+    ThreeHumpCamel,
+}
+
+fn default_ackley_a() -> f64 {
+    20.0
+}
+
+fn default_ackley_b() -> f64 {
+    0.2
+}
+
+fn default_ackley_c() -> f64 {
+    2.0 * std::f64::consts::PI
+}
+
+fn default_rastrigin_a() -> f64 {
+    10.0
+}
+
+fn default_rosenbrock_a() -> f64 {
+    1.0
+}
+
+fn default_rosenbrock_b() -> f64 {
+    100.0
+}
+
+impl TestFunction {
+    /// Returns the name of the function as used in problem files.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_testproblems::TestFunction;
+    ///
+    /// assert_eq!(TestFunction::GoldsteinPrice.name(), "goldsteinprice");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            TestFunction::Ackley { .. } => "ackley",
+            TestFunction::Griewank => "griewank",
+            TestFunction::Levy => "levy",
+            TestFunction::Rastrigin { .. } => "rastrigin",
+            TestFunction::Rosenbrock { .. } => "rosenbrock",
+            TestFunction::Schwefel => "schwefel",
+            TestFunction::Sphere => "sphere",
+            TestFunction::StyblinskiTang => "styblinski_tang",
+            TestFunction::Zero => "zero",
+            TestFunction::Beale => "beale",
+            TestFunction::Booth => "booth",
+            TestFunction::BukinN6 => "bukin_n6",
+            TestFunction::CrossInTray => "cross_in_tray",
+            TestFunction::Easom => "easom",
+            TestFunction::Eggholder => "eggholder",
+            TestFunction::GoldsteinPrice => "goldsteinprice",
+            TestFunction::Himmelblau => "himmelblau",
+            TestFunction::HolderTable => "holder_table",
+            TestFunction::LevyN13 => "levy_n13",
+            TestFunction::Matyas => "matyas",
+            TestFunction::McCorminck => "mccorminck",
+            TestFunction::Picheny => "picheny",
+            TestFunction::SchafferN2 => "schaffer_n2",
+            TestFunction::SchafferN4 => "schaffer_n4",
+            TestFunction::ThreeHumpCamel => "threehumpcamel",
+        }
+    }
+
+    /// Indicates whether the function is defined for `dimension` parameters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_testproblems::TestFunction;
+    ///
+    /// assert!(TestFunction::Sphere.supports_dimension(5));
+    /// assert!(!TestFunction::Beale.supports_dimension(5));
+    /// ```
+    pub fn supports_dimension(&self, dimension: usize) -> bool {
+        match self {
+            TestFunction::Rosenbrock { .. } => dimension >= 2,
+            f if f.two_dimensional().is_some() => dimension == 2,
+            _ => dimension >= 1,
+        }
+    }
+
+    /// Returns cost function, derivative and Hessian of two-dimensional functions.
+    fn two_dimensional(&self) -> Option<TwoDimensional> {
+        Some(match self {
+            TestFunction::Beale => (tf::beale, tf::beale_derivative, tf::beale_hessian),
+            TestFunction::Booth => (tf::booth, tf::booth_derivative, tf::booth_hessian),
+            TestFunction::BukinN6 => (tf::bukin_n6, tf::bukin_n6_derivative, tf::bukin_n6_hessian),
+            TestFunction::CrossInTray => (
+                tf::cross_in_tray,
+                tf::cross_in_tray_derivative,
+                tf::cross_in_tray_hessian,
+            ),
+            TestFunction::Easom => (tf::easom, tf::easom_derivative, tf::easom_hessian),
+            TestFunction::Eggholder => (
+                tf::eggholder,
+                tf::eggholder_derivative,
+                tf::eggholder_hessian,
+            ),
+            TestFunction::GoldsteinPrice => (
+                tf::goldsteinprice,
+                tf::goldsteinprice_derivative,
+                tf::goldsteinprice_hessian,
+            ),
+            TestFunction::Himmelblau => (
+                tf::himmelblau,
+                tf::himmelblau_derivative,
+                tf::himmelblau_hessian,
+            ),
+            TestFunction::HolderTable => (
+                tf::holder_table,
+                tf::holder_table_derivative,
+                tf::holder_table_hessian,
+            ),
+            TestFunction::LevyN13 => (tf::levy_n13, tf::levy_n13_derivative, tf::levy_n13_hessian),
+            TestFunction::Matyas => (tf::matyas, tf::matyas_derivative, tf::matyas_hessian),
+            TestFunction::McCorminck => (
+                tf::mccorminck,
+                tf::mccorminck_derivative,
+                tf::mccorminck_hessian,
+            ),
+            TestFunction::Picheny => (tf::picheny, tf::picheny_derivative, tf::picheny_hessian),
+            TestFunction::SchafferN2 => (
+                tf::schaffer_n2,
+                tf::schaffer_n2_derivative,
+                tf::schaffer_n2_hessian,
+            ),
+            TestFunction::SchafferN4 => (
+                tf::schaffer_n4,
+                tf::schaffer_n4_derivative,
+                tf::schaffer_n4_hessian,
+            ),
+            TestFunction::ThreeHumpCamel => (
+                tf::threehumpcamel,
+                tf::threehumpcamel_derivative,
+                tf::threehumpcamel_hessian,
+            ),
+            _ => return None,
+        })
+    }
+
+    /// Computes the cost function value. The length of `param` must be supported.
+    fn cost(&self, param: &[f64]) -> f64 {
+        match *self {
+            TestFunction::Ackley { a, b, c } => tf::ackley_abc(param, a, b, c),
+            TestFunction::Griewank => tf::griewank(param),
+            TestFunction::Levy => tf::levy(param),
+            TestFunction::Rastrigin { a } => tf::rastrigin_a(param, a),
+            TestFunction::Rosenbrock { a, b } => tf::rosenbrock_ab(param, a, b),
+            TestFunction::Schwefel => tf::schwefel(param),
+            TestFunction::Sphere => tf::sphere(param),
+            TestFunction::StyblinskiTang => tf::styblinski_tang(param),
+            TestFunction::Zero => tf::zero(param),
+            f => (f.two_dimensional().unwrap().0)(&[param[0], param[1]]),
+        }
+    }
+
+    /// Computes the gradient. The length of `param` must be supported.
+    fn gradient(&self, param: &[f64]) -> Vec<f64> {
+        match *self {
+            TestFunction::Ackley { a, b, c } => tf::ackley_abc_derivative(param, a, b, c),
+            TestFunction::Griewank => tf::griewank_derivative(param),
+            TestFunction::Levy => tf::levy_derivative(param),
+            TestFunction::Rastrigin { a } => tf::rastrigin_a_derivative(param, a),
+            TestFunction::Rosenbrock { a, b } => tf::rosenbrock_ab_derivative(param, a, b),
+            TestFunction::Schwefel => tf::schwefel_derivative(param),
+            TestFunction::Sphere => tf::sphere_derivative(param),
+            TestFunction::StyblinskiTang => tf::styblinski_tang_derivative(param),
+            TestFunction::Zero => tf::zero_derivative(param),
+            f => (f.two_dimensional().unwrap().1)(&[param[0], param[1]]).to_vec(),
+        }
+    }
+
+    /// Computes the Hessian. The length of `param` must be supported.
+    fn hessian(&self, param: &[f64]) -> Vec<Vec<f64>> {
+        match *self {
+            TestFunction::Ackley { a, b, c } => tf::ackley_abc_hessian(param, a, b, c),
+            TestFunction::Griewank => tf::griewank_hessian(param),
+            TestFunction::Levy => tf::levy_hessian(param),
+            TestFunction::Rastrigin { a } => tf::rastrigin_a_hessian(param, a),
+            TestFunction::Rosenbrock { a, b } => tf::rosenbrock_ab_hessian(param, a, b),
+            TestFunction::Schwefel => tf::schwefel_hessian(param),
+            TestFunction::Sphere => tf::sphere_hessian(param),
+            TestFunction::StyblinskiTang => tf::styblinski_tang_hessian(param),
+            TestFunction::Zero => tf::zero_hessian(param),
+            f => (f.two_dimensional().unwrap().2)(&[param[0], param[1]])
+                .iter()
+                .map(|row| row.to_vec())
+                .collect(),
+        }
+    }
+}
+
+/// Values of a parameter vector, either given per dimension or as a single value for all
+/// dimensions.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Values {
+    /// The same value for all dimensions
+    Scalar(f64),
+    /// One value per dimension
+    Vector(Vec<f64>),
+}
+
+impl Values {
+    /// Expands the values to a vector of length `dimension`.
+    ///
+    /// Fails if a vector of the wrong length was given.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_testproblems::Values;
+    ///
+    /// assert_eq!(Values::Scalar(1.0).expand(3).unwrap(), vec![1.0, 1.0, 1.0]);
+    /// assert!(Values::Vector(vec![1.0, 2.0]).expand(3).is_err());
+    /// ```
+    pub fn expand(&self, dimension: usize) -> Result<Vec<f64>, Error> {
+        match self {
+            Values::Scalar(value) => Ok(vec![*value; dimension]),
+            Values::Vector(values) if values.len() == dimension => Ok(values.clone()),
+            Values::Vector(values) => Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`Values`: expected {dimension} values, got {}.",
+                    values.len()
+                )
+            )),
+        }
+    }
+}
+
+/// Declarative definition of a single problem.
+///
+/// See the [crate documentation](crate) for the format.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProblemDefinition {
+    /// Name of the problem (defaults to `<function>-<dimension>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Test function including its shape parameters
+    #[serde(flatten)]
+    pub function: TestFunction,
+    /// Number of parameters
+    pub dimension: usize,
+    /// Initial parameter vector
+    pub initial_param: Values,
+    /// Lower bound (requires `upper_bound`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lower_bound: Option<Values>,
+    /// Upper bound (requires `lower_bound`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upper_bound: Option<Values>,
+}
+
+impl ProblemDefinition {
+    /// Returns the name of the problem.
+    ///
+    /// If no name was given, the name is composed of function name and dimension.
+    pub fn name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("{}-{}", self.function.name(), self.dimension))
+    }
+
+    /// Validates the definition and constructs the problem.
+    ///
+    /// Fails if the function does not support the dimension, if the initial parameter vector or
+    /// the bounds do not match the dimension, if only one of the bounds is given, if a lower bound
+    /// exceeds the corresponding upper bound or if the initial parameter vector is not within the
+    /// bounds.
+    pub fn build(&self) -> Result<TestProblem, Error> {
+        let name = self.name();
+        let invalid = |msg: String| -> Error {
+            argmin_error!(
+                InvalidParameter,
+                format!("`ProblemDefinition` `{name}`: {msg}")
+            )
+        };
+
+        if !self.function.supports_dimension(self.dimension) {
+            return Err(invalid(format!(
+                "function `{}` is not defined for {} dimension(s).",
+                self.function.name(),
+                self.dimension
+            )));
+        }
+
+        let (initial_param, bounds) = expand_param_and_bounds(
+            self.dimension,
+            &self.initial_param,
+            self.lower_bound.as_ref(),
+            self.upper_bound.as_ref(),
+        )
+        .map_err(invalid)?;
+
+        Ok(TestProblem {
+            name,
+            function: self.function,
+            initial_param,
+            bounds,
+        })
+    }
+}
+
+/// Lower and upper bounds
+pub(crate) type Bounds = (Vec<f64>, Vec<f64>);
+
+/// Expands the initial parameter vector and the bounds to `dimension` and checks that they are
+/// consistent. On failure, a description of the problem is returned.
+pub(crate) fn expand_param_and_bounds(
+    dimension: usize,
+    initial_param: &Values,
+    lower_bound: Option<&Values>,
+    upper_bound: Option<&Values>,
+) -> Result<(Vec<f64>, Option<Bounds>), String> {
+    let initial_param = initial_param
+        .expand(dimension)
+        .map_err(|e| format!("initial parameter vector: {e}"))?;
+
+    let bounds = match (lower_bound, upper_bound) {
+        (None, None) => None,
+        (Some(lower), Some(upper)) => {
+            let lower = lower
+                .expand(dimension)
+                .map_err(|e| format!("lower bound: {e}"))?;
+            let upper = upper
+                .expand(dimension)
+                .map_err(|e| format!("upper bound: {e}"))?;
+            if lower.iter().zip(upper.iter()).any(|(l, u)| l > u) {
+                return Err("lower bound must not exceed upper bound.".to_string());
+            }
+            if initial_param
+                .iter()
+                .zip(lower.iter().zip(upper.iter()))
+                .any(|(x, (l, u))| x < l || x > u)
+            {
+                return Err("initial parameter vector must be within the bounds.".to_string());
+            }
+            Some((lower, upper))
+        }
+        _ => return Err("lower and upper bound must be given together.".to_string()),
+    };
+    Ok((initial_param, bounds))
+}
+
+/// A set of problem definitions as stored in a problem file.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProblemSet {
+    /// Problem definitions
+    pub problems: Vec<ProblemDefinition>,
+}
+
+impl ProblemSet {
+    /// Parses a problem set in JSON format.
+    #[cfg(feature = "json")]
+    pub fn from_json_str(content: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    /// Parses a problem set in TOML format.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(content: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Reads a problem set from a file.
+    ///
+    /// The format is determined by the file extension (`json` or `toml`). Each format requires
+    /// the feature of the same name.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "json")]
+            Some("json") => Self::from_json_str(&std::fs::read_to_string(path)?),
+            #[cfg(feature = "toml")]
+            Some("toml") => Self::from_toml_str(&std::fs::read_to_string(path)?),
+            _ => Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`ProblemSet`: unsupported format of problem file `{}`.",
+                    path.display()
+                )
+            )),
+        }
+    }
+
+    /// Validates all definitions and constructs the problems (see [`ProblemDefinition::build`]).
+    pub fn build(&self) -> Result<Vec<TestProblem>, Error> {
+        self.problems.iter().map(|p| p.build()).collect()
+    }
+}
+
+/// Problem constructed from a [`ProblemDefinition`].
+///
+/// Implements [`CostFunction`], [`Gradient`] and [`Hessian`] for `Vec<f64>`. Evaluating the
+/// problem with a parameter vector of the wrong length returns an error.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestProblem {
+    /// Name of the problem
+    name: String,
+    /// Test function
+    function: TestFunction,
+    /// Initial parameter vector
+    initial_param: Vec<f64>,
+    /// Lower and upper bounds
+    bounds: Option<(Vec<f64>, Vec<f64>)>,
+}
+
+impl TestProblem {
+    /// Returns the name of the problem.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the test function.
+    pub fn function(&self) -> TestFunction {
+        self.function
+    }
+
+    /// Returns the number of parameters.
+    pub fn dimension(&self) -> usize {
+        self.initial_param.len()
+    }
+
+    /// Returns the initial parameter vector.
+    pub fn initial_param(&self) -> &[f64] {
+        &self.initial_param
+    }
+
+    /// Returns lower and upper bounds, if given.
+    ///
+    /// The bounds can be passed directly to solvers such as `ParticleSwarm`.
+    pub fn bounds(&self) -> Option<(Vec<f64>, Vec<f64>)> {
+        self.bounds.clone()
+    }
+
+    /// Ensures that `param` has the dimension of the problem.
+    fn check_dimension(&self, param: &[f64]) -> Result<(), Error> {
+        if param.len() != self.dimension() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`TestProblem` `{}`: expected {} parameters, got {}.",
+                    self.name,
+                    self.dimension(),
+                    param.len()
+                )
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl CostFunction for TestProblem {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.check_dimension(param)?;
+        Ok(self.function.cost(param))
+    }
+}
+
+impl Gradient for TestProblem {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.check_dimension(param)?;
+        Ok(self.function.gradient(param))
+    }
+}
+
+impl Hessian for TestProblem {
+    type Param = Vec<f64>;
+    type Hessian = Vec<Vec<f64>>;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        self.check_dimension(param)?;
+        Ok(self.function.hessian(param))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use argmin::core::ArgminError;
+
+    fn assert_invalid(definition: &str) {
+        let set = ProblemSet::from_toml_str(definition).unwrap();
+        let err = set.build().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ArgminError>(),
+            Some(ArgminError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_toml_str() {
+        let set = ProblemSet::from_toml_str(
+            r#"
+            [[problems]]
+            name = "rosenbrock-10"
+            function = "rosenbrock"
+            dimension = 10
+            initial_param = -1.2
+            lower_bound = -5.0
+            upper_bound = 5
+
+            [[problems]]
+            function = "rastrigin"
+            a = 5.0
+            dimension = 3
+            initial_param = [1.0, -2.0, 0.5]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(set.problems.len(), 2);
+        assert_eq!(
+            set.problems[0].function,
+            TestFunction::Rosenbrock { a: 1.0, b: 100.0 }
+        );
+        assert_eq!(set.problems[1].function, TestFunction::Rastrigin { a: 5.0 });
+
+        let problems = set.build().unwrap();
+        assert_eq!(problems[0].name(), "rosenbrock-10");
+        assert_eq!(problems[0].initial_param(), &[-1.2; 10]);
+        assert_eq!(problems[0].bounds(), Some((vec![-5.0; 10], vec![5.0; 10])));
+        assert_eq!(problems[1].name(), "rastrigin-3");
+        assert_eq!(problems[1].initial_param(), &[1.0, -2.0, 0.5]);
+        assert_eq!(problems[1].bounds(), None);
+    }
+
+    #[test]
+    fn test_from_json_str() {
+        let set = ProblemSet::from_json_str(
+            r#"{
+                "problems": [
+                    {"function": "ackley", "c": 3.0, "dimension": 2, "initial_param": [1, 2]},
+                    {"function": "goldsteinprice", "dimension": 2, "initial_param": 0.5}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            set.problems[0].function,
+            TestFunction::Ackley {
+                a: 20.0,
+                b: 0.2,
+                c: 3.0
+            }
+        );
+        assert_eq!(set.problems[1].function, TestFunction::GoldsteinPrice);
+        assert_eq!(set.build().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let set = ProblemSet {
+            problems: vec![ProblemDefinition {
+                name: None,
+                function: TestFunction::Rosenbrock { a: 2.0, b: 50.0 },
+                dimension: 3,
+                initial_param: Values::Vector(vec![1.0, 2.0, 3.0]),
+                lower_bound: Some(Values::Scalar(-10.0)),
+                upper_bound: Some(Values::Scalar(10.0)),
+            }],
+        };
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(ProblemSet::from_json_str(&json).unwrap(), set);
+        let toml = toml::to_string(&set).unwrap();
+        assert_eq!(ProblemSet::from_toml_str(&toml).unwrap(), set);
+    }
+
+    #[test]
+    fn test_from_file() {
+        let dir = std::env::temp_dir().join("argmin-testproblems-test_from_file");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("problems.toml");
+        std::fs::write(
+            &path,
+            "[[problems]]\nfunction = \"sphere\"\ndimension = 4\ninitial_param = 1.0\n",
+        )
+        .unwrap();
+        let set = ProblemSet::from_file(&path).unwrap();
+        assert_eq!(set.problems[0].function, TestFunction::Sphere);
+
+        let path = dir.join("problems.yaml");
+        std::fs::write(&path, "").unwrap();
+        let err = ProblemSet::from_file(&path).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ArgminError>(),
+            Some(ArgminError::InvalidParameter { .. })
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_function() {
+        assert!(ProblemSet::from_toml_str(
+            "[[problems]]\nfunction = \"unknown\"\ndimension = 2\ninitial_param = 1.0\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_invalid_definitions() {
+        // Two-dimensional function with three dimensions
+        assert_invalid("[[problems]]\nfunction = \"beale\"\ndimension = 3\ninitial_param = 1.0\n");
+        // Rosenbrock requires at least two dimensions
+        assert_invalid(
+            "[[problems]]\nfunction = \"rosenbrock\"\ndimension = 1\ninitial_param = 1.0\n",
+        );
+        // Wrong length of initial parameter vector
+        assert_invalid(
+            "[[problems]]\nfunction = \"sphere\"\ndimension = 3\ninitial_param = [1.0, 2.0]\n",
+        );
+        // Only one bound
+        assert_invalid(
+            "[[problems]]\nfunction = \"sphere\"\ndimension = 2\ninitial_param = 1.0\nlower_bound = 0.0\n",
+        );
+        // Lower bound exceeds upper bound
+        assert_invalid(
+            "[[problems]]\nfunction = \"sphere\"\ndimension = 2\ninitial_param = 1.0\nlower_bound = [0.0, 3.0]\nupper_bound = 2.0\n",
+        );
+        // Initial parameter vector outside of bounds
+        assert_invalid(
+            "[[problems]]\nfunction = \"sphere\"\ndimension = 2\ninitial_param = 3.0\nlower_bound = 0.0\nupper_bound = 2.0\n",
+        );
+    }
+
+    #[test]
+    fn test_evaluation() {
+        let problem = ProblemDefinition {
+            name: None,
+            function: TestFunction::Rosenbrock { a: 1.0, b: 100.0 },
+            dimension: 3,
+            initial_param: Values::Scalar(0.5),
+            lower_bound: None,
+            upper_bound: None,
+        }
+        .build()
+        .unwrap();
+        let param = vec![0.5, -1.0, 2.0];
+        assert_relative_eq!(
+            problem.cost(&param).unwrap(),
+            tf::rosenbrock(&param),
+            epsilon = f64::EPSILON
+        );
+        assert_eq!(
+            problem.gradient(&param).unwrap(),
+            tf::rosenbrock_derivative(&param)
+        );
+        assert_eq!(
+            problem.hessian(&param).unwrap(),
+            tf::rosenbrock_hessian(&param)
+        );
+        assert!(problem.cost(&vec![1.0, 1.0]).is_err());
+
+        let problem = ProblemDefinition {
+            name: None,
+            function: TestFunction::Himmelblau,
+            dimension: 2,
+            initial_param: Values::Scalar(0.5),
+            lower_bound: None,
+            upper_bound: None,
+        }
+        .build()
+        .unwrap();
+        let param = vec![0.5, -1.0];
+        assert_relative_eq!(
+            problem.cost(&param).unwrap(),
+            tf::himmelblau(&[0.5, -1.0]),
+            epsilon = f64::EPSILON
+        );
+        assert_eq!(
+            problem.gradient(&param).unwrap(),
+            tf::himmelblau_derivative(&[0.5, -1.0]).to_vec()
+        );
+        assert_eq!(
+            problem.hessian(&param).unwrap()[1],
+            tf::himmelblau_hessian(&[0.5, -1.0])[1].to_vec()
+        );
+    }
+}
@@ -0,0 +1,377 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{CostFunction, Error, Gradient, SendAlias, SyncAlias};
+use std::borrow::Borrow;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+/// Asynchronous version of [`CostFunction`].
+///
+/// Useful for cost functions which call remote services, submit cluster jobs or perform other
+/// I/O. Wrap the problem in [`Awaited`] in order to use it with any solver.
+pub trait AsyncCostFunction {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the return value of the cost function
+    type Output;
+
+    /// Compute cost function
+    fn cost(&self, param: &Self::Param) -> impl Future<Output = Result<Self::Output, Error>>;
+}
+
+/// Asynchronous version of [`Gradient`].
+///
+/// Wrap the problem in [`Awaited`] in order to use it with any solver.
+pub trait AsyncGradient {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the gradient
+    type Gradient;
+
+    /// Compute gradient
+    fn gradient(&self, param: &Self::Param) -> impl Future<Output = Result<Self::Gradient, Error>>;
+}
+
+/// Wakes a thread which is parked while waiting for futures
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs `future` to completion on the current thread.
+///
+/// This is a minimal executor which parks the current thread while the future is pending. It
+/// does not depend on a particular async runtime, but it also does not provide one: Futures
+/// which rely on a runtime (for instance for timers or sockets) need to be created within the
+/// context of that runtime.
+pub fn block_on<Fut: Future>(future: Fut) -> Fut::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// Runs `futures` to completion on the current thread with at most `max_concurrency` of them in
+/// flight, and returns their outputs in the order of `futures`.
+fn join_bounded<T, Fut, I>(futures: I, max_concurrency: usize) -> Vec<T>
+where
+    Fut: Future<Output = T>,
+    I: IntoIterator<Item = Fut>,
+{
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut queued = futures.into_iter().enumerate();
+    let mut in_flight: Vec<(usize, Pin<Box<Fut>>)> = Vec::with_capacity(max_concurrency);
+    let mut outputs: Vec<Option<T>> = Vec::new();
+    loop {
+        while in_flight.len() < max_concurrency {
+            match queued.next() {
+                Some((idx, future)) => {
+                    outputs.push(None);
+                    in_flight.push((idx, Box::pin(future)));
+                }
+                None => break,
+            }
+        }
+        if in_flight.is_empty() {
+            break;
+        }
+        let mut completed = false;
+        in_flight.retain_mut(|(idx, future)| match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => {
+                outputs[*idx] = Some(output);
+                completed = true;
+                false
+            }
+            Poll::Pending => true,
+        });
+        if !completed {
+            thread::park();
+        }
+    }
+    outputs.into_iter().flatten().collect()
+}
+
+/// Adapter which allows to use problems implementing [`AsyncCostFunction`] and [`AsyncGradient`]
+/// with all solvers.
+///
+/// `Awaited` implements [`CostFunction`] and [`Gradient`] by running the futures of the wrapped
+/// problem to completion on the thread of the solver (see [`block_on`]). The `bulk_cost` and
+/// `bulk_gradient` methods, which are used by population-based solvers such as
+/// [`ParticleSwarm`](`crate::solver::particleswarm::ParticleSwarm`), await up to
+/// `max_concurrency` evaluations concurrently (default: `16`). This bounds the number of requests
+/// to a remote service or jobs submitted to a cluster at any time. The results are returned in
+/// the order of the parameter vectors, independent of the order in which they complete.
+///
+/// Evaluations are counted by [`Problem`](`crate::core::Problem`) as usual.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{AsyncCostFunction, Awaited, Error, Executor, State};
+/// # use argmin::solver::particleswarm::ParticleSwarm;
+/// # use argmin_testfunctions::rosenbrock;
+/// struct Remote {}
+///
+/// impl AsyncCostFunction for Remote {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     async fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         // For instance, send `p` to a remote service and await the response.
+///         Ok(rosenbrock(p))
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let problem = Awaited::new(Remote {}).with_max_concurrency(8)?;
+/// let solver = ParticleSwarm::new((vec![-2.0, -2.0], vec![2.0, 2.0]), 40);
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.max_iters(10))
+///     .run()?;
+/// # assert!(res.state().get_best_cost() < 1.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Awaited<O> {
+    /// Wrapped problem
+    op: O,
+    /// Maximum number of evaluations in flight
+    max_concurrency: usize,
+}
+
+impl<O> Awaited<O> {
+    /// Construct a new instance of `Awaited`
+    pub fn new(op: O) -> Self {
+        Awaited {
+            op,
+            max_concurrency: 16,
+        }
+    }
+
+    /// Set the maximum number of evaluations awaited concurrently by `bulk_cost` and
+    /// `bulk_gradient`.
+    ///
+    /// Must be larger than 0. Defaults to `16`.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Result<Self, Error> {
+        if max_concurrency == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Awaited`: maximum concurrency must be > 0."
+            ));
+        }
+        self.max_concurrency = max_concurrency;
+        Ok(self)
+    }
+
+    /// Returns a reference to the wrapped problem
+    pub fn inner(&self) -> &O {
+        &self.op
+    }
+
+    /// Returns the wrapped problem
+    pub fn into_inner(self) -> O {
+        self.op
+    }
+}
+
+impl<O: AsyncCostFunction> CostFunction for Awaited<O> {
+    type Param = O::Param;
+    type Output = O::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        block_on(self.op.cost(param))
+    }
+
+    /// Awaits up to `max_concurrency` evaluations of the cost function concurrently.
+    fn bulk_cost<P>(&self, params: &[P]) -> Result<Vec<Self::Output>, Error>
+    where
+        P: Borrow<Self::Param> + SyncAlias,
+        Self::Output: SendAlias,
+        Self: SyncAlias,
+    {
+        join_bounded(
+            params.iter().map(|p| self.op.cost(p.borrow())),
+            self.max_concurrency,
+        )
+        .into_iter()
+        .collect()
+    }
+}
+
+impl<O: AsyncGradient> Gradient for Awaited<O> {
+    type Param = O::Param;
+    type Gradient = O::Gradient;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        block_on(self.op.gradient(param))
+    }
+
+    /// Awaits up to `max_concurrency` evaluations of the gradient concurrently.
+    fn bulk_gradient<P>(&self, params: &[P]) -> Result<Vec<Self::Gradient>, Error>
+    where
+        P: Borrow<Self::Param> + SyncAlias,
+        Self::Gradient: SendAlias,
+        Self: SyncAlias,
+    {
+        join_bounded(
+            params.iter().map(|p| self.op.gradient(p.borrow())),
+            self.max_concurrency,
+        )
+        .into_iter()
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::solver::particleswarm::ParticleSwarm;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Future which is pending `polls` times before it completes, waking itself each time
+    struct Yield {
+        polls: usize,
+    }
+
+    impl Future for Yield {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.polls == 0 {
+                Poll::Ready(())
+            } else {
+                self.polls -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Sphere function which records how many evaluations are in flight at the same time
+    #[derive(Default)]
+    struct Remote {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    impl AsyncCostFunction for Remote {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        async fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            // Complete in an order which differs from the order of the requests
+            Yield {
+                polls: (p[0].to_bits() % 5) as usize,
+            }
+            .await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            if p[0].is_nan() {
+                return Err(argmin_error!(NonFiniteValue, "`Remote`: NaN parameter."));
+            }
+            Ok(p.iter().map(|x| x * x).sum())
+        }
+    }
+
+    impl AsyncGradient for Remote {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        async fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Yield { polls: 2 }.await;
+            Ok(p.iter().map(|x| 2.0 * x).collect())
+        }
+    }
+
+    test_trait_impl!(awaited, Awaited<Vec<f64>>);
+
+    #[test]
+    fn test_invalid_max_concurrency() {
+        assert_error!(
+            Awaited::new(Remote::default()).with_max_concurrency(0),
+            ArgminError,
+            "Invalid parameter: \"`Awaited`: maximum concurrency must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_block_on() {
+        let problem = Awaited::new(Remote::default());
+        assert_eq!(
+            problem.cost(&vec![1.0, 2.0]).unwrap().to_ne_bytes(),
+            5.0f64.to_ne_bytes()
+        );
+        assert_eq!(problem.gradient(&vec![1.0, 2.0]).unwrap(), vec![2.0, 4.0]);
+        assert_eq!(problem.inner().max_in_flight.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_bulk_bounded_concurrency() {
+        let problem = Awaited::new(Remote::default())
+            .with_max_concurrency(3)
+            .unwrap();
+        let params: Vec<Vec<f64>> = (0..20).map(|i| vec![f64::from(i), 1.0]).collect();
+        let costs = problem.bulk_cost(&params).unwrap();
+        for (p, c) in params.iter().zip(costs.iter()) {
+            assert_eq!(c.to_ne_bytes(), (p[0] * p[0] + 1.0).to_ne_bytes());
+        }
+        assert_eq!(problem.inner().max_in_flight.load(Ordering::SeqCst), 3);
+        assert_eq!(problem.inner().in_flight.load(Ordering::SeqCst), 0);
+
+        let grads = problem.bulk_gradient(&params).unwrap();
+        for (p, g) in params.iter().zip(grads.iter()) {
+            assert_eq!(*g, vec![2.0 * p[0], 2.0]);
+        }
+    }
+
+    #[test]
+    fn test_bulk_error() {
+        let problem = Awaited::new(Remote::default());
+        let params = vec![vec![1.0], vec![f64::NAN], vec![2.0]];
+        assert_error!(
+            problem.bulk_cost(&params),
+            ArgminError,
+            "Non-finite value: \"`Remote`: NaN parameter.\""
+        );
+    }
+
+    #[test]
+    fn test_particleswarm() {
+        let problem = Awaited::new(Remote::default())
+            .with_max_concurrency(4)
+            .unwrap();
+        let solver = ParticleSwarm::new((vec![-2.0, -2.0], vec![2.0, 2.0]), 20);
+        let res = Executor::new(problem, solver)
+            .configure(|state| state.max_iters(10).counting(true))
+            .run()
+            .unwrap();
+        assert_eq!(res.state().get_func_counts()["cost_count"], 20 * 11);
+        assert!(res.state().get_best_cost() < 0.5);
+        let problem = res.problem.problem.unwrap();
+        assert_eq!(problem.inner().max_in_flight.load(Ordering::SeqCst), 4);
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Automatic differentiation
+//!
+//! Exact derivatives via forward-mode automatic differentiation based on the dual numbers of the
+//! [`num-dual`](https://docs.rs/num-dual) crate. Requires the `autodiff` feature.
+//!
+//! The cost function is written once in terms of a generic scalar type implementing [`DualNum`]
+//! (see [`AutoDiffCostFunction`]). Wrapping it into [`WithAutoDiff`] provides [`CostFunction`],
+//! [`Gradient`] and [`Hessian`], which allows using it with all gradient-based solvers as well as
+//! Newton methods, without implementing any derivatives by hand.
+//!
+//! Each gradient requires `n` evaluations of the cost function with dual numbers, each Hessian
+//! `n (n + 1) / 2` evaluations with hyper-dual numbers. Hessian-vector products are available via
+//! [`WithAutoDiff::hessian_vec_product`] and require `n` evaluations with hyper-dual numbers.
+//!
+//! # Example
+//!
+//! ```
+//! # use argmin::core::autodiff::{AutoDiffCostFunction, DualNum, WithAutoDiff};
+//! # use argmin::core::{Error, Executor, State};
+//! # use argmin::solver::linesearch::MoreThuenteLineSearch;
+//! # use argmin::solver::quasinewton::BFGS;
+//! struct Rosenbrock {}
+//!
+//! impl AutoDiffCostFunction for Rosenbrock {
+//!     type Param = Vec<f64>;
+//!     type Float = f64;
+//!
+//!     fn cost_generic<D: DualNum<f64> + Copy>(&self, p: &[D]) -> Result<D, Error> {
+//!         let (x, y) = (p[0], p[1]);
+//!         Ok((-x + 1.0).powi(2) + (y - x.powi(2)).powi(2) * 100.0)
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), Error> {
+//! let solver = BFGS::new(MoreThuenteLineSearch::new());
+//! let res = Executor::new(WithAutoDiff::new(Rosenbrock {}), solver)
+//!     .configure(|state| {
+//!         state
+//!             .param(vec![-1.2, 1.0])
+//!             .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+//!             .max_iters(100)
+//!     })
+//!     .run()?;
+//! # let best = res.state.get_best_param().unwrap();
+//! # assert!((best[0] - 1.0).abs() < 1e-6);
+//! # assert!((best[1] - 1.0).abs() < 1e-6);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::{ArgminFloat, CostFunction, Error, Gradient, Hessian};
+use num_dual::{Dual, HyperDual};
+pub use num_dual::{DualNum, DualNumFloat};
+use std::ops::IndexMut;
+
+/// Cost function which is generic over the scalar type.
+///
+/// The parameter vector is passed as a slice of dual numbers. Implementations must only use
+/// operations provided by [`DualNum`] (arithmetic, `powi`, `sqrt`, `exp`, `sin`, ...) such that
+/// derivatives can be propagated.
+pub trait AutoDiffCostFunction {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the real-valued scalars
+    type Float;
+
+    /// Compute cost function for the scalar type `D`
+    fn cost_generic<D: DualNum<Self::Float> + Copy>(&self, param: &[D]) -> Result<D, Error>;
+}
+
+/// Provides [`CostFunction`], [`Gradient`] and [`Hessian`] via automatic differentiation for a
+/// problem implementing [`AutoDiffCostFunction`].
+///
+/// The Hessian is returned as `Vec<Vec<F>>`.
+///
+/// See the [module documentation](`crate::core::autodiff`) for details.
+#[derive(Clone, Debug)]
+pub struct WithAutoDiff<O> {
+    /// Wrapped problem
+    op: O,
+}
+
+impl<O> WithAutoDiff<O> {
+    /// Constructs a new `WithAutoDiff` which wraps `op`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::autodiff::WithAutoDiff;
+    /// # struct UserDefinedProblem {}
+    /// let problem = WithAutoDiff::new(UserDefinedProblem {});
+    /// ```
+    pub fn new(op: O) -> Self {
+        WithAutoDiff { op }
+    }
+
+    /// Returns a reference to the wrapped problem.
+    pub fn inner(&self) -> &O {
+        &self.op
+    }
+
+    /// Returns the wrapped problem.
+    pub fn into_inner(self) -> O {
+        self.op
+    }
+}
+
+impl<O, P, F> WithAutoDiff<O>
+where
+    O: AutoDiffCostFunction<Param = P, Float = F>,
+    P: Clone + IndexMut<usize, Output = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat + DualNumFloat + DualNum<F>,
+{
+    /// Computes the product of the Hessian at `param` with `vec`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::autodiff::{AutoDiffCostFunction, DualNum, WithAutoDiff};
+    /// # use argmin::core::Error;
+    /// struct Quadratic {}
+    ///
+    /// impl AutoDiffCostFunction for Quadratic {
+    ///     type Param = Vec<f64>;
+    ///     type Float = f64;
+    ///
+    ///     fn cost_generic<D: DualNum<f64> + Copy>(&self, p: &[D]) -> Result<D, Error> {
+    ///         Ok(p[0] * p[0] + p[0] * p[1] * 3.0)
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = WithAutoDiff::new(Quadratic {});
+    /// // H = [[2, 3], [3, 0]]
+    /// let hv = problem.hessian_vec_product(&vec![1.0, 1.0], &vec![1.0, 2.0])?;
+    /// assert_eq!(hv, vec![8.0, 3.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hessian_vec_product(&self, param: &P, vec: &P) -> Result<P, Error> {
+        let mut z: Vec<HyperDual<F, F>> = param
+            .into_iter()
+            .zip(vec)
+            .map(|(x, v)| HyperDual::new(*x, F::zero(), *v, F::zero()))
+            .collect();
+        let mut hv = param.clone();
+        for i in 0..z.len() {
+            z[i].eps1 = F::one();
+            hv[i] = self.op.cost_generic(&z)?.eps1eps2;
+            z[i].eps1 = F::zero();
+        }
+        Ok(hv)
+    }
+}
+
+impl<O, P, F> CostFunction for WithAutoDiff<O>
+where
+    O: AutoDiffCostFunction<Param = P, Float = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat + DualNumFloat + DualNum<F>,
+{
+    type Param = P;
+    type Output = F;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let x: Vec<F> = param.into_iter().copied().collect();
+        self.op.cost_generic(&x)
+    }
+}
+
+impl<O, P, F> Gradient for WithAutoDiff<O>
+where
+    O: AutoDiffCostFunction<Param = P, Float = F>,
+    P: Clone + IndexMut<usize, Output = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat + DualNumFloat + DualNum<F>,
+{
+    type Param = P;
+    type Gradient = P;
+
+    /// Computes the gradient via `n` evaluations of the cost function with dual numbers.
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let mut z: Vec<Dual<F, F>> = param.into_iter().map(|x| Dual::from_re(*x)).collect();
+        let mut grad = param.clone();
+        for i in 0..z.len() {
+            z[i].eps = F::one();
+            grad[i] = self.op.cost_generic(&z)?.eps;
+            z[i].eps = F::zero();
+        }
+        Ok(grad)
+    }
+}
+
+impl<O, P, F> Hessian for WithAutoDiff<O>
+where
+    O: AutoDiffCostFunction<Param = P, Float = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat + DualNumFloat + DualNum<F>,
+{
+    type Param = P;
+    type Hessian = Vec<Vec<F>>;
+
+    /// Computes the Hessian via `n (n + 1) / 2` evaluations of the cost function with hyper-dual
+    /// numbers.
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let mut z: Vec<HyperDual<F, F>> =
+            param.into_iter().map(|x| HyperDual::from_re(*x)).collect();
+        let n = z.len();
+        let mut hessian = vec![vec![F::zero(); n]; n];
+        for i in 0..n {
+            z[i].eps1 = F::one();
+            for j in i..n {
+                z[j].eps2 = F::one();
+                let h = self.op.cost_generic(&z)?.eps1eps2;
+                hessian[i][j] = h;
+                hessian[j][i] = h;
+                z[j].eps2 = F::zero();
+            }
+            z[i].eps1 = F::zero();
+        }
+        Ok(hessian)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Executor, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::newton::NewtonCG;
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+
+    struct Rosenbrock {}
+
+    impl AutoDiffCostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn cost_generic<D: DualNum<f64> + Copy>(&self, p: &[D]) -> Result<D, Error> {
+            Ok(p.windows(2)
+                .map(|w| (-w[0] + 1.0).powi(2) + (w[1] - w[0].powi(2)).powi(2) * 100.0)
+                .sum())
+        }
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let problem = WithAutoDiff::new(Rosenbrock {});
+        let x = vec![-1.2, 1.0, 0.5];
+
+        assert_relative_eq!(problem.cost(&x).unwrap(), rosenbrock(&x), epsilon = 1e-12);
+
+        let grad = problem.gradient(&x).unwrap();
+        for (g, e) in grad.iter().zip(rosenbrock_derivative(&x).iter()) {
+            assert_relative_eq!(g, e, epsilon = 1e-12);
+        }
+
+        let hessian = problem.hessian(&x).unwrap();
+        let expected = rosenbrock_hessian(&x);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_relative_eq!(hessian[i][j], expected[i][j], epsilon = 1e-12);
+            }
+        }
+
+        let v = vec![1.0, -2.0, 0.5];
+        let hv = problem.hessian_vec_product(&x, &v).unwrap();
+        for i in 0..3 {
+            let expected: f64 = (0..3).map(|j| expected[i][j] * v[j]).sum();
+            assert_relative_eq!(hv[i], expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_newton_cg() {
+        // Convex with the minimum at (1, -2)
+        struct Convex {}
+
+        impl AutoDiffCostFunction for Convex {
+            type Param = Vec<f64>;
+            type Float = f64;
+
+            fn cost_generic<D: DualNum<f64> + Copy>(&self, p: &[D]) -> Result<D, Error> {
+                let (x, y) = (p[0] - 1.0, p[1] + 2.0);
+                Ok(x.exp() - x + y * y * 3.0 + x * y)
+            }
+        }
+
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let res = Executor::new(WithAutoDiff::new(Convex {}), NewtonCG::new(linesearch))
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(20))
+            .run()
+            .unwrap();
+        let best = res.state.get_best_param().unwrap();
+        assert_relative_eq!(best[0], 1.0, epsilon = 1e-8);
+        assert_relative_eq!(best[1], -2.0, epsilon = 1e-8);
+    }
+}
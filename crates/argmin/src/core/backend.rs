@@ -0,0 +1,197 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{CostFunction, Error, SendAlias, SyncAlias};
+use std::borrow::Borrow;
+
+/// Backend which evaluates the cost function elsewhere, for instance in worker processes on
+/// other machines.
+///
+/// A backend receives a batch of parameter vectors, dispatches them to wherever the cost
+/// function is evaluated, gathers the results as they arrive and returns them in the order of the
+/// parameter vectors. Wrap a backend in [`Distributed`] in order to use it with any solver.
+///
+/// A reference implementation which talks to worker processes over TCP is provided by the
+/// `argmin-backend-tcp` crate.
+pub trait EvaluationBackend {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the return value of the cost function
+    type Output;
+
+    /// Evaluates the cost function at all `params` and returns the results in the same order.
+    fn evaluate(&self, params: &[&Self::Param]) -> Result<Vec<Self::Output>, Error>;
+}
+
+/// Adapter which implements [`CostFunction`] for an [`EvaluationBackend`].
+///
+/// `bulk_cost`, which is used by population-based solvers such as
+/// [`ParticleSwarm`](`crate::solver::particleswarm::ParticleSwarm`), hands the entire batch to
+/// the backend. Single evaluations via `cost` are dispatched as batches of size one. Evaluations
+/// are counted by [`Problem`](`crate::core::Problem`) as usual.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Distributed, Error, EvaluationBackend, Executor, State};
+/// # use argmin::solver::particleswarm::ParticleSwarm;
+/// # use argmin_testfunctions::rosenbrock;
+/// struct Workers {}
+///
+/// impl EvaluationBackend for Workers {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn evaluate(&self, params: &[&Self::Param]) -> Result<Vec<Self::Output>, Error> {
+///         // For instance, submit all parameter vectors to a cluster and wait for the results.
+///         Ok(params.iter().map(|p| rosenbrock(p)).collect())
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let solver = ParticleSwarm::new((vec![-2.0, -2.0], vec![2.0, 2.0]), 40);
+/// let res = Executor::new(Distributed::new(Workers {}), solver)
+///     .configure(|state| state.max_iters(10))
+///     .run()?;
+/// # assert!(res.state().get_best_cost() < 1.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Distributed<B> {
+    /// Evaluation backend
+    backend: B,
+}
+
+impl<B> Distributed<B> {
+    /// Construct a new instance of `Distributed`
+    pub fn new(backend: B) -> Self {
+        Distributed { backend }
+    }
+
+    /// Returns a reference to the backend
+    pub fn inner(&self) -> &B {
+        &self.backend
+    }
+
+    /// Returns the backend
+    pub fn into_inner(self) -> B {
+        self.backend
+    }
+}
+
+impl<B: EvaluationBackend> CostFunction for Distributed<B> {
+    type Param = B::Param;
+    type Output = B::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let mut output = self.backend.evaluate(&[param])?;
+        if output.len() != 1 {
+            return Err(argmin_error!(
+                ConditionViolated,
+                format!(
+                    "`Distributed`: backend returned {} results for 1 parameter vector.",
+                    output.len()
+                )
+            ));
+        }
+        Ok(output.remove(0))
+    }
+
+    /// Hands all parameter vectors to the backend at once.
+    fn bulk_cost<P>(&self, params: &[P]) -> Result<Vec<Self::Output>, Error>
+    where
+        P: Borrow<Self::Param> + SyncAlias,
+        Self::Output: SendAlias,
+        Self: SyncAlias,
+    {
+        let params: Vec<&Self::Param> = params.iter().map(Borrow::borrow).collect();
+        let output = self.backend.evaluate(&params)?;
+        if output.len() != params.len() {
+            return Err(argmin_error!(
+                ConditionViolated,
+                format!(
+                    "`Distributed`: backend returned {} results for {} parameter vectors.",
+                    output.len(),
+                    params.len()
+                )
+            ));
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::solver::particleswarm::ParticleSwarm;
+    use std::sync::Mutex;
+
+    /// Records the sizes of the batches and optionally drops the last result
+    #[derive(Default)]
+    struct Recording {
+        batches: Mutex<Vec<usize>>,
+        drop_last: bool,
+    }
+
+    impl EvaluationBackend for Recording {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn evaluate(&self, params: &[&Self::Param]) -> Result<Vec<Self::Output>, Error> {
+            self.batches.lock().unwrap().push(params.len());
+            let mut output: Vec<f64> = params.iter().map(|p| p.iter().sum()).collect();
+            if self.drop_last {
+                output.pop();
+            }
+            Ok(output)
+        }
+    }
+
+    test_trait_impl!(distributed, Distributed<Vec<f64>>);
+
+    #[test]
+    fn test_cost() {
+        let problem = Distributed::new(Recording::default());
+        assert_eq!(
+            problem.cost(&vec![1.0, 2.0]).unwrap().to_ne_bytes(),
+            3.0f64.to_ne_bytes()
+        );
+        let costs = problem
+            .bulk_cost(&[vec![1.0], vec![2.0], vec![3.0]])
+            .unwrap();
+        assert_eq!(costs, vec![1.0, 2.0, 3.0]);
+        assert_eq!(*problem.inner().batches.lock().unwrap(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_wrong_number_of_results() {
+        let problem = Distributed::new(Recording {
+            drop_last: true,
+            ..Default::default()
+        });
+        assert_error!(
+            problem.cost(&vec![1.0]),
+            ArgminError,
+            "Condition violated: \"`Distributed`: backend returned 0 results for 1 parameter vector.\""
+        );
+        assert!(problem.bulk_cost(&[vec![1.0], vec![2.0]]).is_err());
+    }
+
+    #[test]
+    fn test_particleswarm() {
+        let solver = ParticleSwarm::new((vec![-2.0, -2.0], vec![2.0, 2.0]), 10);
+        let res = Executor::new(Distributed::new(Recording::default()), solver)
+            .configure(|state| state.max_iters(5).counting(true))
+            .run()
+            .unwrap();
+        assert_eq!(res.state().get_func_counts()["cost_count"], 60);
+        let problem = res.problem.problem.unwrap();
+        assert_eq!(*problem.inner().batches.lock().unwrap(), vec![10; 6]);
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::Error;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Budget of function evaluations.
+///
+/// The `Executor` compares the function evaluation counts kept by
+/// [`Problem`](`crate::core::Problem`) with the budget after each iteration and stops the run with
+/// [`TerminationReason::MaxEvaluationsReached`](`crate::core::TerminationReason::MaxEvaluationsReached`)
+/// once it is exhausted. Since solvers merge the counts of nested solvers (such as line searches)
+/// into their own, these evaluations are accounted for as well. Note that the budget can be
+/// exceeded within the last iteration, because it is only checked between iterations.
+///
+/// A budget consists of separate limits for individual counters (labeled like the counts, for
+/// instance `cost_count` or `gradient_count`) and/or of a limit on the weighted sum of all counts.
+/// By default all counters have a weight of 1; this allows for instance to count a gradient
+/// evaluation as expensive as `n` cost function evaluations.
+///
+/// The budget is set via
+/// [`Executor::evaluation_budget`](`crate::core::Executor::evaluation_budget`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, EvaluationBudget};
+/// # fn main() -> Result<(), Error> {
+/// // At most 1000 gradient evaluations and at most 5000 evaluations in total, where a gradient
+/// // evaluation counts as much as 4 cost function evaluations.
+/// let budget = EvaluationBudget::new()
+///     .with_limit("gradient_count", 1000)
+///     .with_weight("gradient_count", 4.0)?
+///     .with_total(5000.0)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct EvaluationBudget {
+    /// Limits for individual counters
+    limits: HashMap<String, u64>,
+    /// Weights of individual counters in the total
+    weights: HashMap<String, f64>,
+    /// Limit of the weighted sum of all counts
+    total: Option<f64>,
+}
+
+impl EvaluationBudget {
+    /// Constructs a new, unlimited budget.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::EvaluationBudget;
+    /// let budget = EvaluationBudget::new();
+    /// ```
+    pub fn new() -> Self {
+        EvaluationBudget::default()
+    }
+
+    /// Limits the number of evaluations counted by `counter` (e.g. `cost_count`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::EvaluationBudget;
+    /// let budget = EvaluationBudget::new().with_limit("cost_count", 100);
+    /// ```
+    #[must_use]
+    pub fn with_limit(mut self, counter: &str, max: u64) -> Self {
+        self.limits.insert(counter.to_string(), max);
+        self
+    }
+
+    /// Sets the weight of `counter` in the weighted sum of all counts (default: 1).
+    ///
+    /// The weight must be non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, EvaluationBudget};
+    /// # fn main() -> Result<(), Error> {
+    /// let budget = EvaluationBudget::new().with_weight("hessian_count", 10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_weight(mut self, counter: &str, weight: f64) -> Result<Self, Error> {
+        if weight.is_nan() || weight < 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`EvaluationBudget`: weight must be >= 0."
+            ));
+        }
+        self.weights.insert(counter.to_string(), weight);
+        Ok(self)
+    }
+
+    /// Limits the weighted sum of all counts.
+    ///
+    /// The limit must be non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, EvaluationBudget};
+    /// # fn main() -> Result<(), Error> {
+    /// let budget = EvaluationBudget::new().with_total(500.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_total(mut self, total: f64) -> Result<Self, Error> {
+        if total.is_nan() || total < 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`EvaluationBudget`: total must be >= 0."
+            ));
+        }
+        self.total = Some(total);
+        Ok(self)
+    }
+
+    /// Returns an error if a weight or the total is invalid (for instance after deserialization).
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        for (counter, weight) in self.weights.iter() {
+            EvaluationBudget::new().with_weight(counter, *weight)?;
+        }
+        if let Some(total) = self.total {
+            EvaluationBudget::new().with_total(total)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if one of the limits is reached by `counts`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::EvaluationBudget;
+    /// # use std::collections::HashMap;
+    /// let budget = EvaluationBudget::new().with_limit("cost_count", 10);
+    ///
+    /// let mut counts = HashMap::new();
+    /// counts.insert("cost_count", 9);
+    /// assert!(!budget.exhausted(&counts));
+    /// counts.insert("cost_count", 10);
+    /// assert!(budget.exhausted(&counts));
+    /// ```
+    pub fn exhausted(&self, counts: &HashMap<&'static str, u64>) -> bool {
+        let limit_reached = self
+            .limits
+            .iter()
+            .any(|(counter, max)| counts.get(counter.as_str()).unwrap_or(&0) >= max);
+        if limit_reached {
+            return true;
+        }
+        if let Some(total) = self.total {
+            let sum: f64 = counts
+                .iter()
+                .map(|(counter, count)| {
+                    self.weights.get(*counter).copied().unwrap_or(1.0) * *count as f64
+                })
+                .sum();
+            return sum >= total;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(EvaluationBudget::new()
+            .with_weight("cost_count", -1.0)
+            .is_err());
+        assert!(EvaluationBudget::new().with_total(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_exhausted() {
+        let mut counts = HashMap::new();
+        assert!(!EvaluationBudget::new().exhausted(&counts));
+
+        let budget = EvaluationBudget::new()
+            .with_limit("gradient_count", 5)
+            .with_weight("gradient_count", 2.0)
+            .unwrap()
+            .with_total(12.0)
+            .unwrap();
+        counts.insert("cost_count", 3);
+        counts.insert("gradient_count", 4);
+        // 3 + 2 * 4 = 11
+        assert!(!budget.exhausted(&counts));
+        counts.insert("cost_count", 4);
+        assert!(budget.exhausted(&counts));
+        counts.insert("cost_count", 0);
+        counts.insert("gradient_count", 5);
+        assert!(budget.exhausted(&counts));
+    }
+}
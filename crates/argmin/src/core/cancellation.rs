@@ -0,0 +1,95 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Handle for stopping an optimization run from outside of the `Executor`.
+///
+/// A token is passed to the `Executor` via
+/// [`Executor::cancellation_token`](`crate::core::Executor::cancellation_token`). Clones of the
+/// token share the same flag, therefore a clone can be handed to other threads (for instance a GUI
+/// or a job scheduler) which call [`cancel`](`CancellationToken::cancel`) to request
+/// cancellation. The `Executor` stops gracefully at the next iteration boundary with
+/// [`TerminationReason::Cancelled`](`crate::core::TerminationReason::Cancelled`) and returns
+/// the result obtained so far.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let handle = token.clone();
+///
+/// std::thread::spawn(move || handle.cancel()).join().unwrap();
+/// assert!(token.is_cancelled());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Constructs a new token which is not cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::CancellationToken;
+    /// let token = CancellationToken::new();
+    /// # assert!(!token.is_cancelled());
+    /// ```
+    pub fn new() -> Self {
+        CancellationToken {
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Requests cancellation of all runs using this token (or one of its clones).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::CancellationToken;
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    /// # assert!(token.is_cancelled());
+    /// ```
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if cancellation was requested.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::CancellationToken;
+    /// let token = CancellationToken::new();
+    /// assert!(!token.is_cancelled());
+    /// ```
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    send_sync_test!(cancellation_token, CancellationToken);
+
+    #[test]
+    fn test_clones_share_flag() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+}
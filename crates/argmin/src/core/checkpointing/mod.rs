@@ -17,7 +17,9 @@
 //! Via the `Checkpoint` trait other checkpointing approaches can be implemented.
 //!
 //! The `CheckpointingFrequency` defines how often checkpoints are saved and can be chosen to be
-//! either `Always` (every iteration), `Every(u64)` (every Nth iteration) or `Never`.
+//! either `Always` (every iteration), `Every(u64)` (every Nth iteration), `Time(Duration)` (at
+//! regular wall-clock intervals), `NewBest` (whenever a new best parameter vector was found),
+//! a combination of those (`Hybrid`) or `Never`.
 //!
 //! The following example shows how the `checkpointing` method is used to activate checkpointing.
 //! If no checkpoint is available on disk, an optimization will be started from scratch. If the run
@@ -102,7 +104,8 @@
 //! # }
 //! ```
 
-use crate::core::Error;
+use crate::core::time::Duration;
+use crate::core::{Error, State};
 use std::default::Default;
 use std::fmt::Display;
 
@@ -166,15 +169,17 @@ pub trait Checkpoint<S, I> {
 
     /// Saves a checkpoint when the checkpointing condition is met.
     ///
-    /// Calls [`save`](`Checkpoint::save`) in each iteration (`CheckpointingFrequency::Always`),
-    /// every X iterations (`CheckpointingFrequency::Every(X)`) or never
-    /// (`CheckpointingFrequency::Never`).
-    fn save_cond(&self, solver: &S, state: &I, iter: u64) -> Result<(), Error> {
-        match self.frequency() {
-            CheckpointingFrequency::Always => self.save(solver, state)?,
-            CheckpointingFrequency::Every(it) if iter % it == 0 => self.save(solver, state)?,
-            CheckpointingFrequency::Never | CheckpointingFrequency::Every(_) => {}
-        };
+    /// Calls [`save`](`Checkpoint::save`) whenever the [`CheckpointingFrequency`] returned by
+    /// [`frequency`](`Checkpoint::frequency`) is due (see [`CheckpointingFrequency::is_due`]).
+    /// `iter` is the number of iterations performed so far.
+    fn save_cond(&self, solver: &S, state: &I, iter: u64) -> Result<(), Error>
+    where
+        I: State,
+    {
+        if self.frequency().is_due(state, iter) {
+            enter_span!(DEBUG, "checkpoint", iter);
+            self.save(solver, state)?;
+        }
         Ok(())
     }
 
@@ -187,6 +192,16 @@ pub trait Checkpoint<S, I> {
     ///
     /// Returns enum `CheckpointingFrequency`.
     fn frequency(&self) -> CheckpointingFrequency;
+
+    /// Checks whether checkpoints can be saved with this configuration.
+    ///
+    /// Called by [`ExecutorBuilder`](`crate::core::ExecutorBuilder`) before the `Executor` is
+    /// constructed, such that misconfigurations surface before a potentially long run rather than
+    /// at the first checkpoint. By default, only the [`CheckpointingFrequency`] is validated.
+    /// Implementations which write to storage should additionally check whether it is accessible.
+    fn validate(&self) -> Result<(), Error> {
+        self.frequency().validate()
+    }
 }
 
 /// Defines at which intervals a checkpoint is saved.
@@ -195,6 +210,7 @@ pub trait Checkpoint<S, I> {
 ///
 /// ```
 /// use argmin::core::checkpointing::CheckpointingFrequency;
+/// use std::time::Duration;
 ///
 /// // A checkpoint every 10 iterations
 /// let every_10 = CheckpointingFrequency::Every(10);
@@ -202,6 +218,16 @@ pub trait Checkpoint<S, I> {
 /// // A checkpoint in each iteration
 /// let always = CheckpointingFrequency::Always;
 ///
+/// // A checkpoint every 10 minutes
+/// let every_10_minutes = CheckpointingFrequency::Time(Duration::from_secs(600));
+///
+/// // A checkpoint every 100 iterations and whenever a new best parameter vector was found
+/// let hybrid = CheckpointingFrequency::Hybrid {
+///     every: Some(100),
+///     time: None,
+///     new_best: true,
+/// };
+///
 /// // The default is `CheckpointingFrequency::Always`
 /// assert_eq!(CheckpointingFrequency::default(), CheckpointingFrequency::Always);
 /// ```
@@ -214,6 +240,132 @@ pub enum CheckpointingFrequency {
     /// Create checkpoint in every iteration
     #[default]
     Always,
+    /// Create checkpoint whenever the given wall-clock time has passed since the start of the run
+    /// or since the last time-based checkpoint. This enables the timer of the `Executor`.
+    Time(Duration),
+    /// Create checkpoint whenever a new best parameter vector was found
+    NewBest,
+    /// Create checkpoint whenever any of the given conditions is met
+    Hybrid {
+        /// Create checkpoint every N iterations
+        every: Option<u64>,
+        /// Create checkpoint at regular wall-clock intervals (enables the timer of the `Executor`)
+        time: Option<Duration>,
+        /// Create checkpoint whenever a new best parameter vector was found
+        new_best: bool,
+    },
+}
+
+impl CheckpointingFrequency {
+    /// Returns `true` if a checkpoint is due after `iter` iterations were performed on `state`.
+    ///
+    /// Time-based conditions are evaluated based on the total time and the time required by the
+    /// last iteration as stored in `state`. They are therefore only met if the timer of the
+    /// `Executor` is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// use argmin::core::checkpointing::CheckpointingFrequency;
+    ///
+    /// let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    ///
+    /// assert!(CheckpointingFrequency::Every(10).is_due(&state, 20));
+    /// assert!(!CheckpointingFrequency::Every(10).is_due(&state, 21));
+    /// ```
+    pub fn is_due<I: State>(&self, state: &I, iter: u64) -> bool {
+        match *self {
+            CheckpointingFrequency::Never => false,
+            CheckpointingFrequency::Always => true,
+            CheckpointingFrequency::Every(it) => iter % it == 0,
+            CheckpointingFrequency::Time(duration) => time_due(state, duration),
+            CheckpointingFrequency::NewBest => new_best_due(state, iter),
+            CheckpointingFrequency::Hybrid {
+                every,
+                time,
+                new_best,
+            } => {
+                every.map(|it| iter % it == 0).unwrap_or(false)
+                    || time.map(|d| time_due(state, d)).unwrap_or(false)
+                    || (new_best && new_best_due(state, iter))
+            }
+        }
+    }
+
+    /// Returns an error if the condition can never be met in a meaningful way, i.e. if an interval
+    /// of zero iterations or zero time is given.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::checkpointing::CheckpointingFrequency;
+    ///
+    /// assert!(CheckpointingFrequency::Every(10).validate().is_ok());
+    /// assert!(CheckpointingFrequency::Every(0).validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        let (every, time) = match *self {
+            CheckpointingFrequency::Every(it) => (Some(it), None),
+            CheckpointingFrequency::Time(duration) => (None, Some(duration)),
+            CheckpointingFrequency::Hybrid { every, time, .. } => (every, time),
+            _ => (None, None),
+        };
+        if every == Some(0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CheckpointingFrequency`: number of iterations must be > 0."
+            ));
+        }
+        if time.map(|duration| duration.is_zero()).unwrap_or(false) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CheckpointingFrequency`: time interval must be > 0."
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the condition requires time measurements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::checkpointing::CheckpointingFrequency;
+    /// # use std::time::Duration;
+    ///
+    /// assert!(CheckpointingFrequency::Time(Duration::from_secs(60)).requires_timer());
+    /// assert!(!CheckpointingFrequency::Every(10).requires_timer());
+    /// ```
+    pub fn requires_timer(&self) -> bool {
+        matches!(
+            self,
+            CheckpointingFrequency::Time(_) | CheckpointingFrequency::Hybrid { time: Some(_), .. }
+        )
+    }
+}
+
+/// Checks whether an interval of length `duration` was completed in the last iteration.
+fn time_due<I: State>(state: &I, duration: Duration) -> bool {
+    match (state.get_time(), state.get_iter_time()) {
+        (Some(time), Some(iter_time)) => {
+            let interval = duration.as_nanos();
+            if interval == 0 {
+                return true;
+            }
+            let prev = time.saturating_sub(iter_time).as_nanos() / interval;
+            time.as_nanos() / interval > prev
+        }
+        _ => false,
+    }
+}
+
+/// Checks whether the best parameter vector was found in the last iteration.
+///
+/// `iter` is the number of iterations performed so far, therefore the last iteration has number
+/// `iter - 1`.
+fn new_best_due<I: State>(state: &I, iter: u64) -> bool {
+    iter > 0 && state.get_last_best_iter() == iter - 1
 }
 
 impl Display for CheckpointingFrequency {
@@ -222,6 +374,86 @@ impl Display for CheckpointingFrequency {
             CheckpointingFrequency::Never => write!(f, "Never"),
             CheckpointingFrequency::Every(i) => write!(f, "Every({i})"),
             CheckpointingFrequency::Always => write!(f, "Always"),
+            CheckpointingFrequency::Time(d) => write!(f, "Time({d:?})"),
+            CheckpointingFrequency::NewBest => write!(f, "NewBest"),
+            CheckpointingFrequency::Hybrid {
+                every,
+                time,
+                new_best,
+            } => write!(
+                f,
+                "Hybrid(every: {every:?}, time: {time:?}, new_best: {new_best})"
+            ),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::time::Duration;
+    use crate::core::IterState;
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    #[test]
+    fn test_time_due() {
+        let freq = CheckpointingFrequency::Time(Duration::from_secs(10));
+        let mut state = TestState::new();
+
+        // Timer not enabled
+        state.time(None);
+        assert!(!freq.is_due(&state, 1));
+
+        state.time(Some(Duration::from_secs(9)));
+        state.iter_time(Some(Duration::from_secs(3)));
+        assert!(!freq.is_due(&state, 1));
+
+        state.time(Some(Duration::from_secs(11)));
+        state.iter_time(Some(Duration::from_secs(3)));
+        assert!(freq.is_due(&state, 2));
+
+        state.time(Some(Duration::from_secs(14)));
+        state.iter_time(Some(Duration::from_secs(3)));
+        assert!(!freq.is_due(&state, 3));
+
+        state.time(Some(Duration::from_secs(35)));
+        state.iter_time(Some(Duration::from_secs(21)));
+        assert!(freq.is_due(&state, 4));
+    }
+
+    #[test]
+    fn test_new_best_due() {
+        let freq = CheckpointingFrequency::NewBest;
+        let mut state = TestState::new().param(vec![1.0]).cost(1.0);
+        assert!(!freq.is_due(&state, 0));
+
+        // New best found in iteration 0
+        state.update();
+        state.increment_iter();
+        assert!(freq.is_due(&state, 1));
+
+        // No new best in iteration 1
+        state = state.cost(2.0);
+        state.update();
+        state.increment_iter();
+        assert!(!freq.is_due(&state, 2));
+    }
+
+    #[test]
+    fn test_hybrid_due() {
+        let freq = CheckpointingFrequency::Hybrid {
+            every: Some(5),
+            time: None,
+            new_best: true,
+        };
+        let mut state = TestState::new().param(vec![1.0]).cost(1.0);
+        state.update();
+        state.increment_iter();
+        assert!(freq.is_due(&state, 1));
+        state.increment_iter();
+        assert!(!freq.is_due(&state, 2));
+        assert!(freq.is_due(&state, 5));
+        assert!(!freq.requires_timer());
+    }
+}
@@ -0,0 +1,478 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::numdiff::{gradient, Scheme, StepConfig};
+use crate::core::{ArgminFloat, CostFunction, Error, Gradient, Hessian, SendAlias, SyncAlias};
+use argmin_math::ArgminDot;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::sync::atomic::AtomicU64;
+
+/// Comparison of a single component of an analytic derivative with its finite-difference
+/// approximation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DerivativeComparison<F> {
+    /// Label of the component, such as `gradient[2]` or `hessian[0][1]`
+    pub name: String,
+    /// Value computed by `gradient` or `hessian`
+    pub analytic: F,
+    /// Central-difference approximation
+    pub numeric: F,
+    /// Relative error `|analytic - numeric| / max(|analytic|, |numeric|, 1)`
+    pub error: F,
+}
+
+/// Result of a [`DerivativeCheck`], listing all compared components.
+///
+/// The `Display` implementation prints one line per component.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DerivativeReport<F> {
+    /// Compared components
+    pub comparisons: Vec<DerivativeComparison<F>>,
+}
+
+impl<F: ArgminFloat> DerivativeReport<F> {
+    /// Returns the largest relative error, or `None` if no components were compared.
+    pub fn max_error(&self) -> Option<F> {
+        self.comparisons
+            .iter()
+            .map(|c| c.error)
+            .reduce(|a, b| if b > a || b.is_nan() { b } else { a })
+    }
+
+    fn push(&mut self, name: String, analytic: F, numeric: F) {
+        let scale = analytic.abs().max(numeric.abs()).max(float!(1.0));
+        let error = (analytic - numeric).abs() / scale;
+        self.comparisons.push(DerivativeComparison {
+            name,
+            analytic,
+            numeric,
+            error,
+        });
+    }
+}
+
+impl<F: fmt::Display> fmt::Display for DerivativeReport<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.comparisons.iter() {
+            writeln!(
+                f,
+                "{}: analytic = {}, numeric = {}, relative error = {}",
+                c.name, c.analytic, c.numeric, c.error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks analytic derivatives against central differences.
+///
+/// Hand-written gradients and Hessians are a frequent source of errors which manifest themselves
+/// as solvers that do not converge. `DerivativeCheck` compares each component of
+/// [`Gradient::gradient`] and of [`Hessian::hessian`] with a central-difference approximation
+/// and fails with an
+/// [`ArgminError::ConditionViolated`](`crate::core::ArgminError::ConditionViolated`) error naming
+/// all components whose relative error `|analytic - numeric| / max(|analytic|, |numeric|, 1)`
+/// exceeds the tolerance (default: `1e-6`). The gradient is approximated from cost function
+/// values, the Hessian from gradients; columns of the Hessian are obtained via
+/// `ArgminDot<P, P>` with unit vectors, which is why any Hessian type supported by the Newton
+/// methods can be checked.
+///
+/// [`check_derivatives`] is a shortcut for checking both gradient and Hessian with the default
+/// settings.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{CostFunction, DerivativeCheck, Error, Gradient};
+/// struct Paraboloid {}
+///
+/// impl CostFunction for Paraboloid {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(p[0].powi(2) + 3.0 * p[1].powi(2))
+///     }
+/// }
+///
+/// impl Gradient for Paraboloid {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+///         // Wrong: should be 6 * p[1]
+///         Ok(vec![2.0 * p[0], 3.0 * p[1]])
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let check = DerivativeCheck::new().with_tolerance(1e-6)?;
+/// let res = check.check_gradient(&Paraboloid {}, &vec![1.0, 2.0]);
+/// assert!(res.is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DerivativeCheck<F> {
+    /// Maximum accepted relative error
+    tolerance: F,
+    /// Step size configuration of the central differences
+    config: StepConfig,
+}
+
+impl<F: ArgminFloat> DerivativeCheck<F> {
+    /// Constructs a new `DerivativeCheck` with a tolerance of `1e-6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::DerivativeCheck;
+    /// let check: DerivativeCheck<f64> = DerivativeCheck::new();
+    /// ```
+    pub fn new() -> Self {
+        DerivativeCheck {
+            tolerance: float!(1e-6),
+            config: StepConfig::new(),
+        }
+    }
+
+    /// Sets the maximum accepted relative error.
+    ///
+    /// Must be larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{DerivativeCheck, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let check: DerivativeCheck<f64> = DerivativeCheck::new().with_tolerance(1e-4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tolerance: F) -> Result<Self, Error> {
+        if tolerance.is_nan() || tolerance <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DerivativeCheck`: tolerance must be > 0."
+            ));
+        }
+        self.tolerance = tolerance;
+        Ok(self)
+    }
+
+    /// Sets the relative step size `h` of the central differences (default: `cbrt(EPSILON)`).
+    /// The step in direction `i` is `h * max(|x_i|, 1)`.
+    ///
+    /// Must be larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{DerivativeCheck, Error};
+    /// # fn main() -> Result<(), Error> {
+    /// let check: DerivativeCheck<f64> = DerivativeCheck::new().with_step(1e-5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step(mut self, step: f64) -> Result<Self, Error> {
+        self.config = self.config.with_step(step, "DerivativeCheck")?;
+        Ok(self)
+    }
+
+    /// Compares the gradient of `op` at `param` with central differences of the cost function.
+    ///
+    /// Returns the report if all components are within the tolerance and an error otherwise.
+    pub fn check_gradient<O, P, G>(&self, op: &O, param: &P) -> Result<DerivativeReport<F>, Error>
+    where
+        O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G> + SyncAlias,
+        P: Clone + IndexMut<usize, Output = F> + SyncAlias + SendAlias,
+        F: SyncAlias,
+        for<'a> &'a P: IntoIterator<Item = &'a F>,
+        G: Index<usize, Output = F>,
+    {
+        let analytic = op.gradient(param)?;
+        let numeric = gradient(op, param, Scheme::Central, &self.config, &AtomicU64::new(0))?;
+        let mut report = DerivativeReport {
+            comparisons: vec![],
+        };
+        for i in 0..param.into_iter().count() {
+            report.push(format!("gradient[{i}]"), analytic[i], numeric[i]);
+        }
+        self.evaluate(report)
+    }
+
+    /// Compares the Hessian of `op` at `param` with central differences of the gradient.
+    ///
+    /// Returns the report if all components are within the tolerance and an error otherwise.
+    pub fn check_hessian<O, P, G, H>(&self, op: &O, param: &P) -> Result<DerivativeReport<F>, Error>
+    where
+        O: Gradient<Param = P, Gradient = G> + Hessian<Param = P, Hessian = H>,
+        P: Clone + IndexMut<usize, Output = F>,
+        for<'a> &'a P: IntoIterator<Item = &'a F>,
+        G: Index<usize, Output = F>,
+        H: ArgminDot<P, P>,
+    {
+        let n = param.into_iter().count();
+        let hessian = op.hessian(param)?;
+        let rel_step: F = self.config.relative_step(Scheme::Central);
+        let mut unit = param.clone();
+        for j in 0..n {
+            unit[j] = float!(0.0);
+        }
+        let mut x = param.clone();
+        let mut report = DerivativeReport {
+            comparisons: vec![],
+        };
+        for j in 0..n {
+            let xj = param[j];
+            x[j] = xj + rel_step * xj.abs().max(float!(1.0));
+            let h = x[j] - xj;
+            let g_plus = op.gradient(&x)?;
+            x[j] = xj - h;
+            let g_minus = op.gradient(&x)?;
+            x[j] = xj;
+
+            unit[j] = float!(1.0);
+            let column = hessian.dot(&unit);
+            unit[j] = float!(0.0);
+
+            for i in 0..n {
+                let numeric = (g_plus[i] - g_minus[i]) / (float!(2.0) * h);
+                report.push(format!("hessian[{i}][{j}]"), column[i], numeric);
+            }
+        }
+        self.evaluate(report)
+    }
+
+    /// Checks both gradient and Hessian of `op` at `param`.
+    ///
+    /// Returns the combined report if all components are within the tolerance and an error
+    /// otherwise.
+    pub fn check<O, P, G, H>(&self, op: &O, param: &P) -> Result<DerivativeReport<F>, Error>
+    where
+        O: CostFunction<Param = P, Output = F>
+            + Gradient<Param = P, Gradient = G>
+            + Hessian<Param = P, Hessian = H>
+            + SyncAlias,
+        P: Clone + IndexMut<usize, Output = F> + SyncAlias + SendAlias,
+        F: SyncAlias,
+        for<'a> &'a P: IntoIterator<Item = &'a F>,
+        G: Index<usize, Output = F>,
+        H: ArgminDot<P, P>,
+    {
+        let mut report = self.check_gradient(op, param)?;
+        report
+            .comparisons
+            .extend(self.check_hessian(op, param)?.comparisons);
+        Ok(report)
+    }
+
+    /// Returns `report` if all errors are within the tolerance.
+    fn evaluate(&self, report: DerivativeReport<F>) -> Result<DerivativeReport<F>, Error> {
+        let failed: Vec<String> = report
+            .comparisons
+            .iter()
+            .filter(|c| c.error > self.tolerance || c.error.is_nan())
+            .map(|c| {
+                format!(
+                    "{} (analytic = {}, numeric = {}, relative error = {})",
+                    c.name, c.analytic, c.numeric, c.error
+                )
+            })
+            .collect();
+        if failed.is_empty() {
+            return Ok(report);
+        }
+        Err(argmin_error!(
+            ConditionViolated,
+            format!(
+                "`DerivativeCheck`: {} of {} components exceed the tolerance of {}: {}",
+                failed.len(),
+                report.comparisons.len(),
+                self.tolerance,
+                failed.join("; ")
+            )
+        ))
+    }
+}
+
+impl<F: ArgminFloat> Default for DerivativeCheck<F> {
+    fn default() -> Self {
+        DerivativeCheck::new()
+    }
+}
+
+/// Checks gradient and Hessian of `op` at `param` against central differences with the default
+/// settings of [`DerivativeCheck`].
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{check_derivatives, CostFunction, Error, Gradient, Hessian};
+/// struct Paraboloid {}
+///
+/// impl CostFunction for Paraboloid {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(p[0].powi(2) + 3.0 * p[1].powi(2))
+///     }
+/// }
+///
+/// impl Gradient for Paraboloid {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+///         Ok(vec![2.0 * p[0], 6.0 * p[1]])
+///     }
+/// }
+///
+/// impl Hessian for Paraboloid {
+///     type Param = Vec<f64>;
+///     type Hessian = Vec<Vec<f64>>;
+///
+///     fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+///         Ok(vec![vec![2.0, 0.0], vec![0.0, 6.0]])
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let report = check_derivatives(&Paraboloid {}, &vec![1.0, 2.0])?;
+/// println!("{report}");
+/// # Ok(())
+/// # }
+/// ```
+pub fn check_derivatives<O, P, G, H, F>(op: &O, param: &P) -> Result<DerivativeReport<F>, Error>
+where
+    O: CostFunction<Param = P, Output = F>
+        + Gradient<Param = P, Gradient = G>
+        + Hessian<Param = P, Hessian = H>
+        + SyncAlias,
+    P: Clone + IndexMut<usize, Output = F> + SyncAlias + SendAlias,
+    F: SyncAlias,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    G: Index<usize, Output = F>,
+    H: ArgminDot<P, P>,
+    F: ArgminFloat,
+{
+    DerivativeCheck::new().check(op, param)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+
+    struct Rosenbrock {
+        /// Scales the second component of the gradient
+        grad_error: f64,
+        /// Added to the off-diagonal elements of the Hessian
+        hessian_error: f64,
+    }
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            let mut grad = rosenbrock_derivative(p);
+            grad[1] *= self.grad_error;
+            Ok(grad)
+        }
+    }
+
+    impl Hessian for Rosenbrock {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+            let mut hessian = rosenbrock_hessian(p);
+            hessian[0][1] += self.hessian_error;
+            hessian[1][0] += self.hessian_error;
+            Ok(hessian)
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert_error!(
+            DerivativeCheck::new().with_tolerance(0.0f64),
+            ArgminError,
+            "Invalid parameter: \"`DerivativeCheck`: tolerance must be > 0.\""
+        );
+        assert!(DerivativeCheck::<f64>::new().with_step(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_correct_derivatives() {
+        let op = Rosenbrock {
+            grad_error: 1.0,
+            hessian_error: 0.0,
+        };
+        let report = check_derivatives(&op, &vec![-1.2, 1.0]).unwrap();
+        assert_eq!(report.comparisons.len(), 6);
+        assert_eq!(report.comparisons[0].name, "gradient[0]");
+        assert_eq!(report.comparisons[3].name, "hessian[1][0]");
+        assert!(report.max_error().unwrap() < 1e-6);
+        assert_eq!(report.to_string().lines().count(), 6);
+    }
+
+    #[test]
+    fn test_wrong_gradient() {
+        let op = Rosenbrock {
+            grad_error: 1.1,
+            hessian_error: 0.0,
+        };
+        let err = check_derivatives(&op, &vec![-1.2, 1.0])
+            .unwrap_err()
+            .downcast::<ArgminError>()
+            .unwrap()
+            .to_string();
+        assert!(err.starts_with(
+            "Condition violated: \"`DerivativeCheck`: 1 of 2 components exceed the tolerance"
+        ));
+        assert!(err.contains("gradient[1] (analytic = -96.8, numeric = -88"));
+    }
+
+    #[test]
+    fn test_wrong_hessian() {
+        let op = Rosenbrock {
+            grad_error: 1.0,
+            hessian_error: 1.0,
+        };
+        assert!(DerivativeCheck::new()
+            .check_gradient(&op, &vec![-1.2, 1.0])
+            .is_ok());
+        let err = DerivativeCheck::new()
+            .check_hessian(&op, &vec![-1.2, 1.0])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("2 of 4 components"));
+        assert!(err.contains("hessian[0][1]"));
+        assert!(err.contains("hessian[1][0]"));
+
+        // A large tolerance accepts the error
+        assert!(DerivativeCheck::new()
+            .with_tolerance(1e-1)
+            .unwrap()
+            .check_hessian(&op, &vec![-1.2, 1.0])
+            .is_ok());
+    }
+}
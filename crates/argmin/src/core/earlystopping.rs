@@ -0,0 +1,289 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{CostFunction, Error, State};
+use num_traits::Float;
+use std::fmt;
+
+/// Early stopping based on a validation cost function.
+///
+/// A second cost function (the *validator*, for instance the loss on a validation data set) is
+/// evaluated at the current parameter vector every `frequency` iterations. If the validation cost
+/// did not improve for `patience` consecutive evaluations, the run is stopped with
+/// [`TerminationReason::EarlyStopped`](`crate::core::TerminationReason::EarlyStopped`) and the
+/// state is rolled back to the one with the lowest validation cost. The rolled back state keeps the
+/// total elapsed time and the function evaluation counts of the entire run, but its iteration
+/// number is the one at which the best validation cost was observed.
+///
+/// This is mostly useful for stochastic solvers in machine learning workflows, where the cost
+/// function on the training data keeps decreasing while the model starts to overfit.
+///
+/// Early stopping is enabled via
+/// [`Executor::early_stopping`](`crate::core::Executor::early_stopping`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{CostFunction, EarlyStopping, Error, IterState};
+/// # struct ValidationLoss {}
+/// # impl CostFunction for ValidationLoss {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(p.iter().sum())
+/// #     }
+/// # }
+/// # fn main() -> Result<(), Error> {
+/// // Evaluate the validation loss every 10 iterations and stop if it did not improve for 5
+/// // consecutive evaluations.
+/// let early_stopping: EarlyStopping<IterState<Vec<f64>, (), (), (), (), f64>> =
+///     EarlyStopping::new(ValidationLoss {})
+///         .with_frequency(10)?
+///         .with_patience(5)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct EarlyStopping<I: State> {
+    /// Computes the validation cost
+    validator: Box<dyn FnMut(&I::Param) -> Result<I::Float, Error>>,
+    /// Evaluate the validator every `frequency` iterations
+    frequency: u64,
+    /// Number of consecutive evaluations without improvement before stopping
+    patience: u64,
+    /// Lowest validation cost so far
+    best_cost: Option<I::Float>,
+    /// State with the lowest validation cost so far
+    best_state: Option<I>,
+    /// Number of consecutive evaluations without improvement
+    num_not_improved: u64,
+    /// Copies the state
+    snapshot: fn(&I) -> I,
+}
+
+impl<I> EarlyStopping<I>
+where
+    I: State + Clone,
+{
+    /// Constructs a new `EarlyStopping` instance from a validation cost function.
+    ///
+    /// By default, the validator is evaluated after each iteration and the run is stopped after
+    /// 10 evaluations without improvement.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CostFunction, EarlyStopping, Error, IterState};
+    /// # struct ValidationLoss {}
+    /// # impl CostFunction for ValidationLoss {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = f64;
+    /// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(p.iter().sum())
+    /// #     }
+    /// # }
+    /// let early_stopping: EarlyStopping<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     EarlyStopping::new(ValidationLoss {});
+    /// ```
+    pub fn new<V>(validator: V) -> Self
+    where
+        V: CostFunction<Param = I::Param, Output = I::Float> + 'static,
+    {
+        EarlyStopping {
+            validator: Box::new(move |param| validator.cost(param)),
+            frequency: 1,
+            patience: 10,
+            best_cost: None,
+            best_state: None,
+            num_not_improved: 0,
+            snapshot: I::clone,
+        }
+    }
+}
+
+impl<I: State> EarlyStopping<I> {
+    /// Sets how often (in iterations) the validator is evaluated (default: 1).
+    ///
+    /// Must be larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CostFunction, EarlyStopping, Error, IterState};
+    /// # struct ValidationLoss {}
+    /// # impl CostFunction for ValidationLoss {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = f64;
+    /// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(p.iter().sum())
+    /// #     }
+    /// # }
+    /// # fn main() -> Result<(), Error> {
+    /// let early_stopping: EarlyStopping<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     EarlyStopping::new(ValidationLoss {}).with_frequency(10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_frequency(mut self, frequency: u64) -> Result<Self, Error> {
+        if frequency == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`EarlyStopping`: frequency must be > 0."
+            ));
+        }
+        self.frequency = frequency;
+        Ok(self)
+    }
+
+    /// Sets the number of consecutive evaluations of the validator without improvement after
+    /// which the run is stopped (default: 10).
+    ///
+    /// Must be larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CostFunction, EarlyStopping, Error, IterState};
+    /// # struct ValidationLoss {}
+    /// # impl CostFunction for ValidationLoss {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = f64;
+    /// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(p.iter().sum())
+    /// #     }
+    /// # }
+    /// # fn main() -> Result<(), Error> {
+    /// let early_stopping: EarlyStopping<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     EarlyStopping::new(ValidationLoss {}).with_patience(3)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_patience(mut self, patience: u64) -> Result<Self, Error> {
+        if patience == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`EarlyStopping`: patience must be > 0."
+            ));
+        }
+        self.patience = patience;
+        Ok(self)
+    }
+
+    /// Returns the lowest validation cost observed so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CostFunction, EarlyStopping, Error, IterState};
+    /// # struct ValidationLoss {}
+    /// # impl CostFunction for ValidationLoss {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = f64;
+    /// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(p.iter().sum())
+    /// #     }
+    /// # }
+    /// let early_stopping: EarlyStopping<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     EarlyStopping::new(ValidationLoss {});
+    /// assert!(early_stopping.best_cost().is_none());
+    /// ```
+    pub fn best_cost(&self) -> Option<I::Float> {
+        self.best_cost
+    }
+
+    /// Evaluates the validator if due and returns `true` if the run should be stopped.
+    ///
+    /// Must be called after the iteration number was incremented.
+    pub(crate) fn check(&mut self, state: &I) -> Result<bool, Error> {
+        if !state.get_iter().is_multiple_of(self.frequency) {
+            return Ok(false);
+        }
+        let param = match state.get_param() {
+            Some(param) => param,
+            None => return Ok(false),
+        };
+        let cost = (self.validator)(param)?;
+        match self.best_cost {
+            Some(best_cost) if cost >= best_cost || cost.is_nan() => self.num_not_improved += 1,
+            _ => {
+                self.best_cost = Some(cost);
+                self.best_state = Some((self.snapshot)(state));
+                self.num_not_improved = 0;
+            }
+        }
+        Ok(self.num_not_improved >= self.patience)
+    }
+
+    /// Returns the state with the lowest validation cost.
+    pub(crate) fn take_best_state(&mut self) -> Option<I> {
+        self.best_state.take()
+    }
+}
+
+impl<I: State> fmt::Debug for EarlyStopping<I>
+where
+    I::Float: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EarlyStopping")
+            .field("frequency", &self.frequency)
+            .field("patience", &self.patience)
+            .field("best_cost", &self.best_cost)
+            .field("num_not_improved", &self.num_not_improved)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::IterState;
+    use approx::assert_relative_eq;
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    struct Validator {}
+
+    impl CostFunction for Validator {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p[0])
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let es: EarlyStopping<TestState> = EarlyStopping::new(Validator {});
+        assert!(es.with_frequency(0).is_err());
+        let es: EarlyStopping<TestState> = EarlyStopping::new(Validator {});
+        assert!(es.with_patience(0).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let mut es: EarlyStopping<TestState> = EarlyStopping::new(Validator {})
+            .with_frequency(2)
+            .unwrap()
+            .with_patience(2)
+            .unwrap();
+
+        let mut state = TestState::new();
+        let mut stop = vec![];
+        for validation_cost in [5.0, 4.0, 3.0, 4.0, 3.5, 4.5, 2.0] {
+            state.increment_iter();
+            state = state.param(vec![validation_cost]);
+            stop.push(es.check(&state).unwrap());
+        }
+        // Evaluated in iterations 2 (4.0), 4 (4.0) and 6 (4.5)
+        assert_eq!(stop, vec![false, false, false, false, false, true, false]);
+        assert_relative_eq!(es.best_cost().unwrap(), 4.0, epsilon = f64::EPSILON);
+        let best = es.take_best_state().unwrap();
+        assert_eq!(best.get_iter(), 2);
+        assert_eq!(best.get_param(), Some(&vec![4.0]));
+    }
+}
@@ -10,6 +10,27 @@
 use thiserror::Error;
 
 /// Argmin error type
+///
+/// Errors are passed around as [`Error`](`crate::core::Error`), which can be downcast to
+/// `ArgminError` in order to react to specific kinds of errors.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{ArgminError, Error};
+/// fn handle(err: &Error) -> &'static str {
+///     match err.downcast_ref::<ArgminError>() {
+///         Some(ArgminError::InvalidParameter { .. }) => "fix the configuration",
+///         Some(ArgminError::LineSearchFailed { .. }) => "try a different line search",
+///         Some(_) => "other argmin error",
+///         // Errors returned by the problem (cost function, gradient, ...) are passed through
+///         None => "error of the problem",
+///     }
+/// }
+/// # let err: Error = ArgminError::LineSearchFailed { text: "".to_string() }.into();
+/// # assert_eq!(handle(&err), "try a different line search");
+/// # assert_eq!(handle(&Error::msg("simulation crashed")), "error of the problem");
+/// ```
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum ArgminError {
@@ -41,6 +62,28 @@ pub enum ArgminError {
         text: String,
     },
 
+    /// Indicates that a non-finite value (`NaN` or infinity) occurred
+    #[error("Non-finite value: {text:?}")]
+    NonFiniteValue {
+        /// Text
+        text: String,
+    },
+
+    /// Indicates that a line search was unable to find an acceptable step length
+    #[error("Line search failed: {text:?}")]
+    LineSearchFailed {
+        /// Text
+        text: String,
+    },
+
+    /// Indicates that a linear system could not be solved because its matrix is (numerically)
+    /// singular
+    #[error("Singular matrix: {text:?}")]
+    SingularMatrix {
+        /// Text
+        text: String,
+    },
+
     /// Checkpoint was not found
     #[error("Checkpoint not found: {text:?}")]
     CheckpointNotFound {
@@ -48,6 +91,13 @@ pub enum ArgminError {
         text: String,
     },
 
+    /// Indicates that a checkpoint could not be written or read or is corrupted
+    #[error("Checkpoint failed: {text:?}")]
+    CheckpointFailed {
+        /// Text
+        text: String,
+    },
+
     /// For errors which are likely bugs.
     #[error("Potential bug: {text:?}. This is potentially a bug. Please file a report on https://github.com/argmin-rs/argmin/issues")]
     PotentialBug {
@@ -63,9 +113,45 @@ pub enum ArgminError {
     },
 }
 
+impl ArgminError {
+    /// Returns `true` for errors which may not occur again if the iteration is retried, for
+    /// instance from a perturbed parameter vector: failed line searches, non-finite values and
+    /// singular matrices.
+    ///
+    /// These errors are handled by a [`RecoveryPolicy`](`crate::core::RecoveryPolicy`). Problems
+    /// can mark their own failures as recoverable by returning one of these kinds, for instance
+    /// [`ArgminError::NonFiniteValue`] if a simulation diverged.
+    pub fn is_recoverable(&self) -> bool {
+        matches!(
+            self,
+            ArgminError::LineSearchFailed { .. }
+                | ArgminError::NonFiniteValue { .. }
+                | ArgminError::SingularMatrix { .. }
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     send_sync_test!(error, ArgminError);
+
+    #[test]
+    fn test_downcast() {
+        let err: crate::core::Error = argmin_error!(LineSearchFailed, "no step found");
+        assert!(matches!(
+            err.downcast_ref::<ArgminError>(),
+            Some(ArgminError::LineSearchFailed { text }) if text == "no step found"
+        ));
+        assert_eq!(err.to_string(), "Line search failed: \"no step found\"");
+    }
+
+    #[test]
+    fn test_is_recoverable() {
+        let text = String::new();
+        assert!(ArgminError::NonFiniteValue { text: text.clone() }.is_recoverable());
+        assert!(ArgminError::SingularMatrix { text: text.clone() }.is_recoverable());
+        assert!(!ArgminError::InvalidParameter { text }.is_recoverable());
+    }
 }
@@ -7,14 +7,30 @@
 
 use crate::core::checkpointing::Checkpoint;
 use crate::core::observers::{Observe, ObserverMode, Observers};
+use crate::core::statecodec;
+use crate::core::time::{Duration, Instant};
 use crate::core::{
-    Error, OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
+    CancellationToken, EarlyStopping, Error, EvaluationBudget, History, MaxStepLength,
+    NonFiniteCheck, NonFinitePolicy, OptimizationResult, Problem, RecoveryPolicy, Solver,
+    StallDetection, State, StateCodec, TerminationReason, TerminationStatus, KV,
 };
+use std::io::{Read, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+/// User-defined termination criterion which is evaluated after each iteration
+type TerminationCriterion<I> = Box<dyn FnMut(&I) -> TerminationStatus>;
+
+/// User-defined hook which is called after each iteration with mutable access to state and problem
+type IterationHook<O, I> =
+    Box<dyn FnMut(&mut I, &mut Problem<O>) -> Result<TerminationStatus, Error>>;
+
+/// User-defined function which may modify the problem between iterations and returns whether it
+/// did so
+type ProblemModifier<O, I> = Box<dyn FnMut(&I, &mut O) -> Result<bool, Error>>;
+
 /// Solves an optimization problem with a solver
-pub struct Executor<O, S, I> {
+pub struct Executor<O, S, I: State> {
     /// Solver
     solver: S,
     /// Problem
@@ -25,12 +41,53 @@ pub struct Executor<O, S, I> {
     observers: Observers<I>,
     /// Checkpoint
     checkpoint: Option<Box<dyn Checkpoint<S, I>>>,
-    /// Timeout
-    timeout: Option<std::time::Duration>,
+    /// Indicates whether the checkpoint needs to be loaded at the start of a run
+    load_checkpoint: bool,
+    /// Indicates whether the solver needs to be initialized although the run is resumed
+    reinit_solver: bool,
+    /// Wall-clock time limit
+    max_time: Option<Duration>,
+    /// Target cost function value
+    target_cost: Option<I::Float>,
+    /// User-defined termination criteria
+    termination_criteria: Vec<TerminationCriterion<I>>,
+    /// Token for cancelling the run from outside
+    cancellation_token: Option<CancellationToken>,
+    /// User-defined hooks called after each iteration
+    iteration_hooks: Vec<IterationHook<O, I>>,
+    /// User-defined modification of the problem between iterations
+    problem_modifier: Option<ProblemModifier<O, I>>,
+    /// Early stopping based on a validation cost function
+    early_stopping: Option<EarlyStopping<I>>,
+    /// Detection of stalled progress
+    stall_detection: Option<StallDetection<I::Float>>,
+    /// Budget of function evaluations
+    evaluation_budget: Option<EvaluationBudget>,
+    /// Detection of non-finite values
+    non_finite: Option<NonFiniteCheck<I>>,
+    /// Handling of recoverable errors
+    recovery: Option<RecoveryPolicy<I>>,
     /// Indicates whether Ctrl-C functionality should be active or not
     ctrlc: bool,
     /// Indicates whether to time execution or not
     timer: bool,
+    /// Seed of the deterministic mode (if enabled)
+    deterministic: Option<u64>,
+    /// History of the run (only recorded if enabled)
+    history: Option<History<I>>,
+    /// Thread pool for parallel evaluations
+    #[cfg(feature = "rayon")]
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
+    /// Information about the run once it has been started
+    started: Option<Started>,
+}
+
+/// Information about a run which has been started
+struct Started {
+    /// Point in time at which the run was started (only available if the timer is enabled)
+    total_time: Option<Instant>,
+    /// Set by the Ctrl-C handler
+    interrupt: Arc<AtomicBool>,
 }
 
 impl<O, S, I> Executor<O, S, I>
@@ -67,12 +124,103 @@ where
             state,
             observers: Observers::new(),
             checkpoint: None,
-            timeout: None,
+            load_checkpoint: true,
+            reinit_solver: false,
+            max_time: None,
+            target_cost: None,
+            termination_criteria: vec![],
+            cancellation_token: None,
+            iteration_hooks: vec![],
+            problem_modifier: None,
+            early_stopping: None,
+            stall_detection: None,
+            evaluation_budget: None,
+            non_finite: None,
+            recovery: None,
             ctrlc: true,
             timer: false,
+            deterministic: None,
+            history: None,
+            #[cfg(feature = "rayon")]
+            thread_pool: None,
+            started: None,
         }
     }
 
+    /// Constructs an `Executor` from a user defined problem and a checkpoint.
+    ///
+    /// The solver and the state are restored from `checkpoint`, therefore only the problem needs
+    /// to be provided. The checkpoint is also used for checkpointing the continued run (as if
+    /// [`checkpointing`](`Executor::checkpointing`) was called). Since the internal state of the
+    /// solver is restored, including the state of random number generators of stochastic solvers
+    /// such as [`ParticleSwarm`](`crate::solver::particleswarm::ParticleSwarm`) and
+    /// [`SimulatedAnnealing`](`crate::solver::simulatedannealing::SimulatedAnnealing`), the
+    /// resumed run continues exactly as the original run would have. Note that the state of the
+    /// problem itself is not part of a checkpoint. In contrast to `checkpointing`, the restored
+    /// state can be altered via [`configure`](`Executor::configure`), for instance to increase the
+    /// maximum number of iterations.
+    ///
+    /// Returns an error if the checkpoint does not exist or cannot be loaded. Observers, the
+    /// timer, the time limit, the target cost, termination criteria, iteration hooks,
+    /// early stopping, stall detection, the evaluation budget, the detection of non-finite values
+    /// and the other options need to be configured again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, IterState};
+    /// # #[cfg(feature = "serde1")]
+    /// # use argmin::core::checkpointing::CheckpointingFrequency;
+    /// # use argmin_checkpointing_file::FileCheckpoint;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # #[cfg(feature = "serde1")]
+    /// # {
+    /// # let _ = Executor::new(TestProblem::new(), TestSolver::new())
+    /// #     .configure(|state| state.param(vec![1.0, 0.0]).max_iters(5))
+    /// #     .checkpointing(FileCheckpoint::new(
+    /// #         ".checkpoints",
+    /// #         "from_checkpoint_doctest",
+    /// #         CheckpointingFrequency::Always,
+    /// #     ))
+    /// #     .run()?;
+    /// let checkpoint = FileCheckpoint::new(
+    ///     ".checkpoints",
+    ///     "from_checkpoint_doctest",
+    ///     CheckpointingFrequency::Always,
+    /// );
+    ///
+    /// // The types of solver and state need to be known in order to load the checkpoint
+    /// let executor: Executor<_, TestSolver, IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     Executor::from_checkpoint(TestProblem::new(), checkpoint)?;
+    ///
+    /// let result = executor
+    ///     // Continue for more iterations
+    ///     .configure(|state| state.max_iters(10))
+    ///     .run()?;
+    /// # assert_eq!(result.state.iter, 10);
+    /// # let _ = std::fs::remove_file(".checkpoints/from_checkpoint_doctest.arg");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_checkpoint<C: 'static + Checkpoint<S, I>>(
+        problem: O,
+        checkpoint: C,
+    ) -> Result<Self, Error> {
+        let (solver, state) = checkpoint.load()?.ok_or_else(argmin_error_closure!(
+            CheckpointNotFound,
+            "`Executor::from_checkpoint`: No checkpoint found."
+        ))?;
+        let mut executor = Executor::new(problem, solver)
+            .configure(|_| state)
+            .checkpointing(checkpoint);
+        // Already loaded, this allows users to alter the restored state via `configure`
+        executor.load_checkpoint = false;
+        Ok(executor)
+    }
+
     /// This method gives mutable access to the internal state of the solver. This allows for
     /// initializing the state before running the `Executor`. The options for initialization depend
     /// on the type of state used by the chosen solver. Common types of state are
@@ -104,6 +252,143 @@ where
         self
     }
 
+    /// Warm-starts the run from the result of a previous run.
+    ///
+    /// The solver takes over the information it accumulated during the previous run, such as the
+    /// best parameter vector, the (inverse) Hessian approximation of BFGS-type methods, the
+    /// curvature pairs of L-BFGS, the search direction of nonlinear conjugate gradient methods or
+    /// the radius of trust region methods (see [`Solver::warm_start`]). This is useful for
+    /// repeatedly refitting a model to data which changes only slightly between the runs.
+    ///
+    /// Cost function values and derivatives are recomputed, hence the problem may differ from the
+    /// one of the previous run. Settings of the solver such as tolerances are those of the solver
+    /// passed to [`new`](`Executor::new`). Values set via [`configure`](`Executor::configure`)
+    /// before calling this method, for instance an initial parameter vector, take precedence
+    /// over those of the previous run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CostFunction, Error, Executor, Gradient, State};
+    /// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+    /// # use argmin::solver::quasinewton::LBFGS;
+    /// # use argmin_testfunctions::{rosenbrock_ab, rosenbrock_ab_derivative};
+    /// # struct Model { a: f64 }
+    /// # impl CostFunction for Model {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = f64;
+    /// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(rosenbrock_ab(p, self.a, 100.0))
+    /// #     }
+    /// # }
+    /// # impl Gradient for Model {
+    /// #     type Param = Vec<f64>;
+    /// #     type Gradient = Vec<f64>;
+    /// #     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+    /// #         Ok(rosenbrock_ab_derivative(p, self.a, 100.0))
+    /// #     }
+    /// # }
+    /// # fn main() -> Result<(), Error> {
+    /// let linesearch = MoreThuenteLineSearch::new();
+    /// let previous = Executor::new(Model { a: 1.0 }, LBFGS::new(linesearch.clone(), 7))
+    ///     .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+    ///     .run()?;
+    ///
+    /// // Refit after the model changed slightly
+    /// let result = Executor::new(Model { a: 1.01 }, LBFGS::new(linesearch, 7))
+    ///     .warm_start(previous)?
+    ///     .configure(|state| state.max_iters(100))
+    ///     .run()?;
+    /// # assert!(result.state().get_best_cost() < 1e-8);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn warm_start<O2>(mut self, previous: OptimizationResult<O2, S, I>) -> Result<Self, Error> {
+        let state = self.state.take().unwrap();
+        let state = self
+            .solver
+            .warm_start(previous.solver, previous.state, state)?;
+        self.state = Some(state);
+        Ok(self)
+    }
+
+    /// Writes the current state to `writer` using a hand-rolled binary layout.
+    ///
+    /// Together with [`load_state`](`Executor::load_state`), this allows to interrupt and resume
+    /// runs without `serde`: only the types stored in the state (parameter vector, gradient, ...)
+    /// need to implement [`StateCodec`], which is straightforward to do by hand also for types
+    /// which cannot derive `Serialize`. Called in between [`step`](`Executor::step`)s, the state
+    /// after the most recent iteration is written, otherwise the configured initial state.
+    ///
+    /// Unlike [checkpoints](`crate::core::checkpointing`), the solver is not saved. See
+    /// [`load_state`](`Executor::load_state`) for how runs are resumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, State};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// let mut executor = Executor::new(TestProblem::new(), TestSolver::new())
+    ///     .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(10));
+    ///
+    /// for _ in 0..4 {
+    ///     executor.step()?;
+    /// }
+    ///
+    /// // Usually a file
+    /// let mut saved = vec![];
+    /// executor.save_state(&mut saved)?;
+    ///
+    /// // Later, possibly in another process
+    /// let result = Executor::new(TestProblem::new(), TestSolver::new())
+    ///     .load_state(&mut saved.as_slice())?
+    ///     .run()?;
+    /// # assert_eq!(result.state.get_iter(), 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save_state<W: Write>(&self, writer: &mut W) -> Result<(), Error>
+    where
+        I: StateCodec,
+    {
+        let state = self.state.as_ref().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Executor::save_state`: State not available."
+        ))?;
+        statecodec::write_state(writer, state)
+    }
+
+    /// Replaces the state with one previously written by [`save_state`](`Executor::save_state`).
+    ///
+    /// The run continues from the restored iteration number, parameter vector, function evaluation
+    /// counts, elapsed time and so on. Since the solver is not part of the saved state, it is
+    /// initialized via [`Solver::init`] on the restored state when the run starts: solvers which
+    /// keep all relevant information in the state (such as the inverse Hessian approximation of
+    /// BFGS or the swarm of particle swarm optimization) continue seamlessly, whereas solver
+    /// internals (for instance the memory of L-BFGS or the simplex of Nelder-Mead) start afresh
+    /// from the restored parameter vector. A checkpoint configured via
+    /// [`checkpointing`](`Executor::checkpointing`) is not loaded in addition.
+    ///
+    /// The state can still be altered via [`configure`](`Executor::configure`) afterwards, for
+    /// instance to increase the maximum number of iterations.
+    pub fn load_state<R: Read>(mut self, reader: &mut R) -> Result<Self, Error>
+    where
+        I: StateCodec,
+    {
+        if self.started.is_some() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Executor::load_state`: The run has already been started."
+            ));
+        }
+        self.state = Some(statecodec::read_state(reader)?);
+        self.reinit_solver = true;
+        self.load_checkpoint = false;
+        Ok(self)
+    }
+
     /// Runs the executor by applying the solver to the optimization problem.
     ///
     /// # Example
@@ -129,25 +414,167 @@ where
     /// # }
     /// ```
     pub fn run(mut self) -> Result<OptimizationResult<O, S, I>, Error> {
-        // First, load checkpoint if given.
-        if let Some(checkpoint) = self.checkpoint.as_ref() {
+        #[cfg(feature = "rayon")]
+        let _thread_pool_guard = self.thread_pool_guard()?;
+        enter_span!(INFO, "run", solver = self.solver.name());
+
+        self.start()?;
+        while self.iterate()? {}
+        self.finish()
+    }
+
+    /// Performs a single iteration of the solver.
+    ///
+    /// This allows to drive the optimization loop manually instead of handing over control to
+    /// [`run`](`Executor::run`) until termination, for instance in order to interleave it with
+    /// the event loop of a GUI or with other computations. The first call initializes the solver
+    /// (and loads the checkpoint, if configured) before performing the first iteration.
+    /// Termination criteria, observers, hooks and checkpoints are handled exactly as in `run`.
+    /// Time measurements include the time spent in between calls.
+    ///
+    /// Returns the state after the iteration. Since termination criteria are checked at the
+    /// beginning of an iteration, the call after the last iteration only sets the termination
+    /// status; once the run has terminated (see [`State::terminated`]), calls do not perform any
+    /// iterations anymore. The result is obtained via [`finish`](`Executor::finish`). After an
+    /// error, the executor cannot be used anymore.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, State};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let mut executor = Executor::new(problem, solver)
+    ///     .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(10));
+    ///
+    /// while !executor.step()?.terminated() {
+    ///     // Do something else in between iterations
+    /// }
+    ///
+    /// let result = executor.finish()?;
+    /// # assert_eq!(result.state.get_iter(), 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn step(&mut self) -> Result<&I, Error> {
+        #[cfg(feature = "rayon")]
+        let _thread_pool_guard = self.thread_pool_guard()?;
+
+        self.start()?;
+        self.iterate()?;
+        self.state.as_ref().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Executor`: State not available after iteration."
+        ))
+    }
+
+    /// Finishes a run driven via [`step`](`Executor::step`) and returns the result.
+    ///
+    /// Final observers are called on the current state. If `step` has not been called, the solver
+    /// is initialized without performing any iteration.
+    pub fn finish(mut self) -> Result<OptimizationResult<O, S, I>, Error> {
+        #[cfg(feature = "rayon")]
+        let _thread_pool_guard = self.thread_pool_guard()?;
+
+        self.start()?;
+        let interrupted = self
+            .started
+            .as_ref()
+            .is_some_and(|started| started.interrupt.load(Ordering::SeqCst));
+        let mut state = self.take_state()?;
+
+        if interrupted {
+            // Solver execution has been interrupted manually
+            state = state.terminate_with(TerminationReason::Interrupt);
+        }
+
+        if !self.observers.is_empty() {
+            self.observers.observe_final(&state)?;
+        }
+
+        let mut result = OptimizationResult::new(self.problem, self.solver, state);
+        result.history = self.history;
+        Ok(result)
+    }
+
+    /// Makes the thread pool of the `Executor` (if any) the one used for parallel evaluations on
+    /// the current thread. In deterministic mode, a thread pool with a single thread is used.
+    #[cfg(feature = "rayon")]
+    fn thread_pool_guard(
+        &mut self,
+    ) -> Result<Option<crate::core::parallelization::ThreadPoolGuard>, Error> {
+        let sequential = self
+            .thread_pool
+            .as_ref()
+            .is_some_and(|pool| pool.current_num_threads() == 1);
+        if self.deterministic.is_some() && !sequential {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(1).build()?;
+            self.thread_pool = Some(Arc::new(pool));
+        }
+        Ok(self
+            .thread_pool
+            .clone()
+            .map(crate::core::parallelization::set_thread_pool))
+    }
+
+    /// Loads the checkpoint, sets up timers and the Ctrl-C handler and initializes the solver,
+    /// unless this has been done already.
+    fn start(&mut self) -> Result<(), Error> {
+        if self.started.is_some() {
+            return Ok(());
+        }
+
+        if self.deterministic.is_some() {
+            let time_based_checkpoints = self
+                .checkpoint
+                .as_ref()
+                .is_some_and(|checkpoint| checkpoint.frequency().requires_timer());
+            if self.max_time.is_some() || time_based_checkpoints {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    concat!(
+                        "`Executor`: time limit and time-based checkpointing depend on ",
+                        "the wall clock and cannot be used in deterministic mode."
+                    )
+                ));
+            }
+        }
+
+        // First, load checkpoint if given (unless it was loaded already).
+        if let (Some(checkpoint), true) = (self.checkpoint.as_ref(), self.load_checkpoint) {
             if let Some((solver, state)) = checkpoint.load()? {
                 self.state = Some(state);
                 self.solver = solver;
             }
         }
         let total_time = if self.timer {
-            Some(instant::Instant::now())
+            Some(Instant::now())
         } else {
             None
         };
+        self.problem.deadline(
+            total_time
+                .zip(self.max_time)
+                .map(|(start, max_time)| start + max_time),
+        );
 
-        let state = self.state.take().unwrap();
+        let mut state = self.take_state()?;
+        if !self.timer {
+            // No time is measured, therefore the time must not be reported as zero.
+            state.time(None);
+        }
+        if let Some(target_cost) = self.target_cost {
+            state.set_target_cost(target_cost);
+        }
 
         let interrupt = Arc::new(AtomicBool::new(false));
 
         if self.ctrlc {
-            #[cfg(feature = "ctrlc")]
+            #[cfg(all(feature = "ctrlc", not(target_arch = "wasm32")))]
             {
                 // Set up the Ctrl-C handler
                 let interp = interrupt.clone();
@@ -170,10 +597,18 @@ where
 
         // Only call `init` of `solver` if the current iteration number is 0. This avoids that
         // `init` is called when starting from a checkpoint (because `init` could change the state
-        // of the `solver`, which would overwrite the state restored from the checkpoint).
-        let mut state = if state.get_iter() == 0 {
+        // of the `solver`, which would overwrite the state restored from the checkpoint). A state
+        // restored via `load_state` does not include the solver, which therefore has to be
+        // initialized in any case.
+        let state = if state.get_iter() == 0 || self.reinit_solver {
+            if let (Some(seed), 0) = (self.deterministic, state.get_iter()) {
+                self.solver.reseed(seed);
+            }
+            self.reinit_solver = false;
             let (mut state, kv) = self.solver.init(&mut self.problem, state)?;
-            state.update();
+            if self.solver.track_best() {
+                state.update();
+            }
 
             if !self.observers.is_empty() {
                 let kv = kv.unwrap_or(kv![]);
@@ -188,98 +623,265 @@ where
         } else {
             state
         };
+        self.state = Some(state);
+        self.started = Some(Started {
+            total_time,
+            interrupt,
+        });
+        Ok(())
+    }
+
+    /// Performs an iteration of the solver followed by the check for non-finite values.
+    fn checked_next_iter(&mut self, state: I) -> Result<(I, Option<KV>), Error> {
+        // Keep a copy of the state in case the iteration needs to be rejected
+        let prev_state = match self.non_finite.as_ref() {
+            Some(check) if matches!(check.policy(), NonFinitePolicy::RejectStep) => {
+                Some(check.snapshot(&state))
+            }
+            _ => None,
+        };
 
-        while !interrupt.load(Ordering::SeqCst) {
-            // check first if it has already terminated
-            // This should probably be solved better.
-            // First, check if it isn't already terminated. If it isn't, evaluate the
-            // stopping criteria. If `self.terminate()` is called without the checking
-            // whether it has terminated already, then it may overwrite a termination set
-            // within `next_iter()`!
-            state = if !state.terminated() {
-                let term = self.solver.terminate_internal(&state);
-                if let TerminationStatus::Terminated(reason) = term {
-                    state.terminate_with(reason)
-                } else {
-                    state
+        let (mut state, kv) = self.solver.next_iter(&mut self.problem, state)?;
+
+        if let Some(check) = self.non_finite.as_ref() {
+            if let Some(quantity) = check.find_non_finite(&state) {
+                match check.policy() {
+                    NonFinitePolicy::Abort => {
+                        return Err(argmin_error!(
+                            NonFiniteValue,
+                            format!(
+                                "{} is not finite after iteration {} of `{}`.",
+                                quantity,
+                                state.get_iter(),
+                                self.solver.name()
+                            )
+                        ));
+                    }
+                    NonFinitePolicy::RejectStep => {
+                        let prev_state = prev_state.ok_or_else(argmin_error_closure!(
+                            PotentialBug,
+                            "`Executor`: No copy of the previous state available."
+                        ))?;
+                        state = self.solver.reject_step(prev_state)?;
+                    }
+                    NonFinitePolicy::Clamp(clamp) => clamp(&mut state),
                 }
+            }
+        }
+
+        Ok((state, kv))
+    }
+
+    /// Performs a single iteration of a started run and returns whether the run continues.
+    fn iterate(&mut self) -> Result<bool, Error> {
+        let (total_time, interrupted) = match self.started.as_ref() {
+            Some(started) => (started.total_time, started.interrupt.load(Ordering::SeqCst)),
+            None => {
+                return Err(argmin_error!(
+                    PotentialBug,
+                    "`Executor`: Run has not been started."
+                ))
+            }
+        };
+        let mut state = self.take_state()?;
+        enter_span!(DEBUG, "iteration", iter = state.get_iter());
+        if interrupted {
+            if !state.terminated() {
+                state = state.terminate_with(TerminationReason::Interrupt);
+            }
+            self.state = Some(state);
+            return Ok(false);
+        }
+
+        // check first if it has already terminated
+        // This should probably be solved better.
+        // First, check if it isn't already terminated. If it isn't, evaluate the
+        // stopping criteria. If `self.terminate()` is called without the checking
+        // whether it has terminated already, then it may overwrite a termination set
+        // within `next_iter()`!
+        if let Some(token) = self.cancellation_token.as_ref() {
+            if token.is_cancelled() {
+                state = state.terminate_with(TerminationReason::Cancelled);
+                self.state = Some(state);
+                return Ok(false);
+            }
+        }
+        state = if !state.terminated() {
+            let term = self.solver.terminate_internal(&state);
+            if let TerminationStatus::Terminated(reason) = term {
+                state.terminate_with(reason)
             } else {
                 state
-            };
-            // Now check once more if the algorithm has terminated. If yes, then break.
-            if state.terminated() {
-                break;
             }
+        } else {
+            state
+        };
+        // Now check once more if the algorithm has terminated. If yes, then stop.
+        if state.terminated() {
+            self.state = Some(state);
+            return Ok(false);
+        }
 
-            // Start time measurement
-            let start = if self.timer {
-                Some(instant::Instant::now())
-            } else {
-                None
-            };
+        // Start time measurement
+        let start = if self.timer {
+            Some(Instant::now())
+        } else {
+            None
+        };
+        let operator_time = self.problem.total_time();
 
-            let (state_t, kv) = self.solver.next_iter(&mut self.problem, state)?;
-            state = state_t;
+        // Keep a copy of the state in case the iteration fails with a recoverable error
+        let backup = match self.recovery.as_ref() {
+            Some(recovery) if recovery.needs_snapshot() => Some(recovery.snapshot(&state)),
+            _ => None,
+        };
 
-            state.func_counts(&self.problem);
+        let (state_t, kv) = match self.checked_next_iter(state) {
+            Ok((state, kv)) => match self.recovery.as_mut().map(RecoveryPolicy::succeeded) {
+                Some(retries) if retries > 0 => {
+                    let mut kv = kv.unwrap_or(kv![]);
+                    kv.insert("retries", retries.into());
+                    (state, Some(kv))
+                }
+                _ => (state, kv),
+            },
+            Err(err) => match self.recovery.as_mut() {
+                Some(recovery) => {
+                    let state = recovery.recover(err, backup)?;
+                    (self.solver.restart(&mut self.problem, state)?, None)
+                }
+                None => return Err(err),
+            },
+        };
+        state = state_t;
 
-            // End time measurement
-            let duration = if self.timer {
-                Some(start.unwrap().elapsed())
-            } else {
-                None
-            };
+        state.func_counts(&self.problem);
+
+        // End time measurement
+        let duration = if self.timer {
+            Some(start.unwrap().elapsed())
+        } else {
+            None
+        };
+        state.iter_time(duration);
 
+        if self.solver.track_best() {
             state.update();
+        }
 
-            if !self.observers.is_empty() {
-                let mut log = if let Some(kv) = kv { kv } else { KV::new() };
-
-                if self.timer {
-                    let duration = duration.unwrap();
-                    let tmp = kv!(
-                        "time" => duration.as_secs_f64();
-                    );
-                    log = log.merge(tmp);
+        for hook in self.iteration_hooks.iter_mut() {
+            if let TerminationStatus::Terminated(reason) = hook(&mut state, &mut self.problem)? {
+                if !state.terminated() {
+                    state = state.terminate_with(reason);
                 }
-                self.observers.observe_iter(&state, &log)?;
             }
+        }
 
-            // increment iteration number
-            state.increment_iter();
+        if !self.observers.is_empty() {
+            let mut log = if let Some(kv) = kv { kv } else { KV::new() };
 
-            if let Some(checkpoint) = self.checkpoint.as_ref() {
-                checkpoint.save_cond(&self.solver, &state, state.get_iter())?;
+            if self.timer {
+                let duration = duration.unwrap();
+                let operator_time = self.problem.total_time().saturating_sub(operator_time);
+                let tmp = kv!(
+                    "time" => duration.as_secs_f64();
+                    "operator_time" => operator_time.as_secs_f64();
+                    "solver_time" => duration.saturating_sub(operator_time).as_secs_f64();
+                );
+                log = log.merge(tmp);
             }
+            self.observers.observe_iter(&state, &log)?;
+        }
 
-            if self.timer {
-                // Increase accumulated total_time
-                total_time.map(|total_time| state.time(Some(total_time.elapsed())));
+        if self.timer {
+            // Increase accumulated total_time
+            total_time.map(|total_time| state.time(Some(total_time.elapsed())));
+        }
 
-                // If a timeout is set, check if timeout is reached
-                if let (Some(timeout), Some(total_time)) = (self.timeout, total_time) {
-                    if total_time.elapsed() > timeout {
-                        state = state.terminate_with(TerminationReason::Timeout);
-                    }
-                }
+        if let Some(history) = self.history.as_mut() {
+            history.record(&state);
+        }
+
+        // increment iteration number
+        state.increment_iter();
+
+        if let Some(checkpoint) = self.checkpoint.as_ref() {
+            checkpoint.save_cond(&self.solver, &state, state.get_iter())?;
+        }
+
+        if let (Some(max_time), Some(total_time)) = (self.max_time, total_time) {
+            if !state.terminated() && total_time.elapsed() >= max_time {
+                state = state.terminate_with(TerminationReason::TimeLimitReached);
             }
+        }
 
-            // Check if termination occurred in the meantime
+        for criterion in self.termination_criteria.iter_mut() {
             if state.terminated() {
                 break;
             }
+            if let TerminationStatus::Terminated(reason) = criterion(&state) {
+                state = state.terminate_with(reason);
+            }
         }
 
-        if interrupt.load(Ordering::SeqCst) {
-            // Solver execution has been interrupted manually
-            state = state.terminate_with(TerminationReason::Interrupt);
+        if let Some(budget) = self.evaluation_budget.as_ref() {
+            if !state.terminated() && budget.exhausted(&self.problem.counts) {
+                state = state.terminate_with(TerminationReason::MaxEvaluationsReached);
+            }
         }
 
-        if !self.observers.is_empty() {
-            self.observers.observe_final(&state)?;
+        if let Some(stall_detection) = self.stall_detection.as_mut() {
+            if !state.terminated() && stall_detection.check(state.get_cost()) {
+                state = state.terminate_with(TerminationReason::Stalled);
+            }
+        }
+
+        if let Some(early_stopping) = self.early_stopping.as_mut() {
+            if !state.terminated() && early_stopping.check(&state)? {
+                // Roll back to the state with the best validation cost
+                if let Some(mut best_state) = early_stopping.take_best_state() {
+                    best_state.time(state.get_time());
+                    best_state.func_counts(&self.problem);
+                    state = best_state;
+                }
+                state = state.terminate_with(TerminationReason::EarlyStopped);
+            }
+        }
+
+        // Check if termination occurred in the meantime
+        if state.terminated() {
+            self.state = Some(state);
+            return Ok(false);
+        }
+
+        if let Some(modify) = self.problem_modifier.as_mut() {
+            let problem = self
+                .problem
+                .problem
+                .as_mut()
+                .ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`Executor`: Problem not available for modification."
+                ))?;
+            if modify(&state, problem)? {
+                state = self.solver.problem_changed(&mut self.problem, state)?;
+                state.func_counts(&self.problem);
+                if self.solver.track_best() {
+                    state.update();
+                }
+            }
         }
 
-        Ok(OptimizationResult::new(self.problem, self.solver, state))
+        self.state = Some(state);
+        Ok(true)
+    }
+
+    /// Takes the state out of the executor
+    fn take_state(&mut self) -> Result<I, Error> {
+        self.state.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Executor`: State not available. Was a previous step aborted by an error?"
+        ))
     }
 
     /// Adds an observer to the executor. Observers are required to implement the
@@ -318,6 +920,10 @@ where
 
     /// Configures checkpointing
     ///
+    /// If checkpoints are to be saved at regular wall-clock intervals (see
+    /// [`CheckpointingFrequency`](`crate::core::checkpointing::CheckpointingFrequency`)), the
+    /// timer is enabled automatically.
+    ///
     /// # Example
     ///
     /// ```
@@ -351,13 +957,16 @@ where
     /// ```
     #[must_use]
     pub fn checkpointing<C: 'static + Checkpoint<S, I>>(mut self, checkpoint: C) -> Self {
+        if checkpoint.frequency().requires_timer() {
+            self.timer = true;
+        }
         self.checkpoint = Some(Box::new(checkpoint));
         self
     }
 
     /// Enables or disables CTRL-C handling (default: enabled). The CTRL-C handling gracefully
     /// stops the solver if it is canceled via CTRL-C (SIGINT). Requires the optional `ctrlc`
-    /// feature to be set.
+    /// feature to be set and has no effect on WebAssembly targets.
     ///
     /// Note that this does not work with nested `Executor`s. If a solver executes another solver
     /// internally, the inner solver needs to disable CTRL-C handling.
@@ -385,7 +994,12 @@ where
 
     /// Enables or disables timing of individual iterations (default: false).
     ///
-    /// In case a timeout is set, this will automatically be set to true.
+    /// If enabled, observers receive the duration of each iteration (`time`) split into the time
+    /// spent in methods of the problem (`operator_time`) and the time spent in the solver itself
+    /// (`solver_time`), all in seconds.
+    ///
+    /// In case a time limit or time-based checkpointing is set, this will automatically
+    /// be set to true.
     ///
     /// # Example
     ///
@@ -404,7 +1018,12 @@ where
     /// ```
     #[must_use]
     pub fn timer(mut self, timer: bool) -> Self {
-        if self.timeout.is_none() {
+        let timed_checkpoints = self
+            .checkpoint
+            .as_ref()
+            .map(|checkpoint| checkpoint.frequency().requires_timer())
+            .unwrap_or(false);
+        if self.max_time.is_none() && !timed_checkpoints {
             self.timer = timer;
         }
         self
@@ -412,10 +1031,8 @@ where
 
     /// Sets a timeout for the run.
     ///
-    /// The optimization run is stopped once the timeout is exceeded. Note that the check is
-    /// performed after each iteration, therefore the actual runtime can exceed the the set
-    /// duration.
-    /// This also enables time measurements.
+    /// This is equivalent to [`max_time`](`Executor::max_time`), hence the run is stopped with
+    /// [`TerminationReason::TimeLimitReached`] once the timeout is exceeded.
     ///
     /// # Example
     ///
@@ -428,364 +1045,2037 @@ where
     /// # let problem = TestProblem::new();
     /// #
     /// // Create instance of `Executor` with `problem` and `solver`
+    /// # #[allow(deprecated)]
     /// let executor = Executor::new(problem, solver).timeout(std::time::Duration::from_secs(30));
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+    #[deprecated(note = "use `Executor::max_time` instead")]
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.max_time(timeout)
+    }
+
+    /// Sets a wall-clock time limit for the run.
+    ///
+    /// Once the time budget is exhausted, the run is stopped with
+    /// [`TerminationReason::TimeLimitReached`]. The limit is checked after each iteration.
+    /// Additionally, solvers can query
+    /// [`Problem::time_limit_reached`](`crate::core::Problem::time_limit_reached`) to stop
+    /// long-running loops within an iteration. This also enables time measurements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// // Create instance of `Executor` with `problem` and `solver`
+    /// let executor = Executor::new(problem, solver).max_time(std::time::Duration::from_secs(60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_time(mut self, max_time: Duration) -> Self {
         self.timer = true;
-        self.timeout = Some(timeout);
+        self.max_time = Some(max_time);
         self
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::core::test_utils::{TestProblem, TestSolver};
-    use crate::core::IterState;
-    use approx::assert_relative_eq;
 
-    #[test]
-    fn test_update() {
-        let problem = TestProblem::new();
-        let solver = TestSolver::new();
+    /// Stops the run as soon as the best cost function value is lower than or equal to
+    /// `target_cost`.
+    ///
+    /// This sets the target cost of the state (via [`State::set_target_cost`]) when the run is
+    /// started, which overrides a target cost set via [`configure`](`Executor::configure`) or
+    /// restored from a checkpoint. The target cost is checked by [`Solver::terminate_internal`],
+    /// which terminates the run with [`TerminationReason::TargetCostReached`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// // Stop once a cost of 1e-6 or lower is reached
+    /// let executor = Executor::new(problem, solver).target_cost(1e-6);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn target_cost(mut self, target_cost: I::Float) -> Self {
+        self.target_cost = Some(target_cost);
+        self
+    }
 
+    /// Adds a hook which is called after each iteration with mutable access to the state and the
+    /// problem.
+    ///
+    /// The hook is called after the solver performed an iteration and the best-so-far iterate was
+    /// updated, but before observers are called and checkpoints are written. It can therefore be
+    /// used to log custom data, modify the state (for instance to re-normalize the parameter
+    /// vector) or to access the problem between iterations. Note that the best parameter vector
+    /// stored in the state is not updated again after the hook modified the state.
+    ///
+    /// Returning [`TerminationStatus::Terminated`] stops the run with the given reason, returning
+    /// [`TerminationStatus::NotTerminated`] continues it. Errors are passed on to the caller of
+    /// [`run`](`Executor::run`). Several hooks can be added; they are called in the order in which
+    /// they were added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, IterState, Problem, TerminationStatus};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver).add_iteration_hook(
+    ///     |state: &mut IterState<Vec<f64>, (), (), (), (), f64>, _problem: &mut Problem<_>| {
+    ///         // Normalize the parameter vector
+    ///         if let Some(param) = state.param.as_mut() {
+    ///             let norm = param.iter().map(|x| x * x).sum::<f64>().sqrt();
+    ///             param.iter_mut().for_each(|x| *x /= norm);
+    ///         }
+    ///         Ok(TerminationStatus::NotTerminated)
+    ///     },
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn add_iteration_hook<T>(mut self, hook: T) -> Self
+    where
+        T: FnMut(&mut I, &mut Problem<O>) -> Result<TerminationStatus, Error> + 'static,
+    {
+        self.iteration_hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Allows to modify the problem between iterations.
+    ///
+    /// After each iteration which did not terminate the run, `modify` is called with the current
+    /// state and mutable access to the problem. It can alter the problem in place or replace it
+    /// entirely, for instance to use a finer simulation mesh, to reduce a regularization
+    /// parameter or to move on to the next stage of a curriculum. If `modify` returns `true`,
+    /// the solver is notified via [`Solver::problem_changed`], which by default discards all cost
+    /// function values and derivatives computed from the previous problem and reinitializes the
+    /// solver at the current parameter vector. Evaluation counts are kept and include the
+    /// evaluations of all versions of the problem. Errors are passed on to the caller of
+    /// [`run`](`Executor::run`).
+    ///
+    /// Note that the best parameter vector found for a previous version of the problem is
+    /// replaced in the next iteration, since its cost function value is not comparable to those
+    /// of the modified problem. Only one modifier can be set; calling this method again replaces
+    /// it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CostFunction, Error, Executor, Gradient, IterState, State};
+    /// # use argmin::solver::quasinewton::LBFGS;
+    /// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+    /// /// Least squares problem with ridge regularization
+    /// struct Ridge {
+    ///     lambda: f64,
+    /// }
+    ///
+    /// impl CostFunction for Ridge {
+    ///     type Param = Vec<f64>;
+    ///     type Output = f64;
+    ///
+    ///     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+    ///         Ok((p[0] - 1.0).powi(2) + self.lambda * p[0].powi(2))
+    ///     }
+    /// }
+    ///
+    /// impl Gradient for Ridge {
+    ///     type Param = Vec<f64>;
+    ///     type Gradient = Vec<f64>;
+    ///
+    ///     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+    ///         Ok(vec![2.0 * (p[0] - 1.0) + 2.0 * self.lambda * p[0]])
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+    /// let res = Executor::new(Ridge { lambda: 1.0 }, solver)
+    ///     .configure(|state| state.param(vec![0.0]).max_iters(20))
+    ///     // Anneal the regularization after each iteration until it is small enough
+    ///     .modify_problem(|_state: &IterState<_, _, _, _, _, _>, problem: &mut Ridge| {
+    ///         if problem.lambda > 1e-4 {
+    ///             problem.lambda *= 0.1;
+    ///             Ok(true)
+    ///         } else {
+    ///             Ok(false)
+    ///         }
+    ///     })
+    ///     .run()?;
+    /// # assert!(res.problem.problem.unwrap().lambda < 1e-4);
+    /// # assert!((res.state.get_best_param().unwrap()[0] - 1.0).abs() < 1e-3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn modify_problem<T>(mut self, modify: T) -> Self
+    where
+        T: FnMut(&I, &mut O) -> Result<bool, Error> + 'static,
+    {
+        self.problem_modifier = Some(Box::new(modify));
+        self
+    }
+
+    /// Enables detection of non-finite values after each iteration.
+    ///
+    /// Without this, `NaN`s propagate silently through the computations. See [`NonFiniteCheck`]
+    /// and [`NonFinitePolicy`] for the available checks and behaviors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, NonFiniteCheck, NonFinitePolicy};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver)
+    ///     .non_finite(NonFiniteCheck::new(NonFinitePolicy::Abort).with_param());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn non_finite(mut self, check: NonFiniteCheck<I>) -> Self {
+        self.non_finite = Some(check);
+        self
+    }
+
+    /// Retries iterations which failed with a recoverable error, for instance a failed line
+    /// search or a non-finite cost function value, according to the given [`RecoveryPolicy`].
+    ///
+    /// By default, every error ends the run. Once an iteration succeeds after failed attempts,
+    /// the number of failed attempts is passed to the observers as `retries`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, RecoveryAction, RecoveryPolicy};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver)
+    ///     .recovery(RecoveryPolicy::new(RecoveryAction::Reset).with_max_retries(5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn recovery(mut self, policy: RecoveryPolicy<I>) -> Self {
+        self.recovery = Some(policy);
+        self
+    }
+
+    /// Limits the length of the step taken in each iteration.
+    ///
+    /// The limit is enforced by the line searches of quasi-Newton, Newton-CG, Gauss-Newton and
+    /// nonlinear CG solvers (which do not try step lengths beyond it) and by trust region methods
+    /// (which limit the radius of the trust region accordingly). Other solvers ignore it. See
+    /// [`MaxStepLength`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, MaxStepLength};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver)
+    ///     .max_step_length(MaxStepLength::new().with_absolute(10.0)?.with_relative(1.0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_step_length(mut self, max_step_length: MaxStepLength) -> Self {
+        self.problem.set_max_step_length(Some(max_step_length));
+        self
+    }
+
+    /// Limits the change of each component of the parameter vector per iteration.
+    ///
+    /// `move_limits` holds the maximum absolute change `delta_i > 0` of each component `x_i` of
+    /// the parameter vector in a single iteration, i.e. each step stays within the box
+    /// `[x_i - delta_i, x_i + delta_i]` around the current iterate. This is useful for instance
+    /// for physical models whose parameters must change slowly to keep a simulation stable.
+    ///
+    /// The limits are enforced by the line searches of quasi-Newton, Newton-CG, Gauss-Newton and
+    /// nonlinear CG solvers (which do not try step lengths beyond them) and by trust region
+    /// methods (which shorten their steps accordingly). Other solvers ignore them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// // The first component may change by at most 0.1, the second one by at most 1.0.
+    /// let executor = Executor::new(problem, solver).move_limits(vec![0.1, 1.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn move_limits(mut self, move_limits: I::Param) -> Self
+    where
+        I::Param: Send + Sync + 'static,
+    {
+        self.problem.set_move_limits(Some(move_limits));
+        self
+    }
+
+    /// Sets a budget of function evaluations.
+    ///
+    /// The run is stopped with [`TerminationReason::MaxEvaluationsReached`] once the budget is
+    /// exhausted. See [`EvaluationBudget`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, EvaluationBudget, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver)
+    ///     .evaluation_budget(EvaluationBudget::new().with_limit("cost_count", 1000));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn evaluation_budget(mut self, budget: EvaluationBudget) -> Self {
+        self.evaluation_budget = Some(budget);
+        self
+    }
+
+    /// Enables detection of stalled progress.
+    ///
+    /// Stops the run once the relative improvement of the lowest cost function value over a
+    /// sliding window of iterations falls below a tolerance. See [`StallDetection`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, StallDetection};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver)
+    ///     .stall_detection(StallDetection::new(20, 1e-4)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stall_detection(mut self, stall_detection: StallDetection<I::Float>) -> Self {
+        self.stall_detection = Some(stall_detection);
+        self
+    }
+
+    /// Enables early stopping based on a validation cost function.
+    ///
+    /// See [`EarlyStopping`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CostFunction, EarlyStopping, Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// # struct ValidationLoss {}
+    /// # impl CostFunction for ValidationLoss {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = f64;
+    /// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(p.iter().sum())
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver)
+    ///     .early_stopping(EarlyStopping::new(ValidationLoss {}).with_patience(5)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn early_stopping(mut self, early_stopping: EarlyStopping<I>) -> Self {
+        self.early_stopping = Some(early_stopping);
+        self
+    }
+
+    /// Sets a token which allows for cancelling the run from outside, for instance from another
+    /// thread.
+    ///
+    /// The token is checked before each iteration. Once [`CancellationToken::cancel`] was called
+    /// on the token or one of its clones, the run stops with [`TerminationReason::Cancelled`] and
+    /// the result obtained so far is returned. Observers are called as for any other termination.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CancellationToken, Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let token = CancellationToken::new();
+    ///
+    /// // Pass a clone of `token` to a different thread which calls `token.cancel()` ...
+    ///
+    /// let executor = Executor::new(problem, solver).cancellation_token(token);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Adds a user-defined termination criterion.
+    ///
+    /// The closure is called with the state after each iteration (once the checks of the solver
+    /// and of the `Executor`, such as [`max_time`](`Executor::max_time`), did not stop the
+    /// run). If it returns [`TerminationStatus::Terminated`], the run is stopped with the given
+    /// reason. Several criteria can be added; they are evaluated in the order in which they were
+    /// added. This allows for bespoke stopping criteria without modifying the solver.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, State, TerminationReason, TerminationStatus};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver).add_termination_criterion(|state| {
+    ///     // Stop once the first parameter is negative
+    ///     if state.get_param().map(|p| p[0] < 0.0).unwrap_or(false) {
+    ///         TerminationStatus::Terminated(TerminationReason::SolverExit(
+    ///             "Parameter is negative".to_string(),
+    ///         ))
+    ///     } else {
+    ///         TerminationStatus::NotTerminated
+    ///     }
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn add_termination_criterion<T>(mut self, criterion: T) -> Self
+    where
+        T: FnMut(&I) -> TerminationStatus + 'static,
+    {
+        self.termination_criteria.push(Box::new(criterion));
+        self
+    }
+
+    /// Enables recording of the history of the run (default: disabled).
+    ///
+    /// After each iteration, the cost, the best cost and (if the timer is enabled) the elapsed
+    /// time are stored, optionally together with the gradient norm and the parameter vector (see
+    /// [`History`](`crate::core::History`)). The history is returned as part of the
+    /// [`OptimizationResult`](`crate::core::OptimizationResult`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, History};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let result = Executor::new(problem, solver)
+    ///     .configure(|state| state.param(vec![1.0, 0.0]).max_iters(10))
+    ///     // Record the history including the parameter vectors
+    ///     .history(History::new().params(true))
+    ///     .run()?;
+    ///
+    /// let history = result.history().unwrap();
+    /// # assert_eq!(history.len(), 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn history(mut self, history: History<I>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Enables the deterministic mode, in which repeated runs produce bit-for-bit identical
+    /// results, for instance in tests or when replicating published results.
+    ///
+    /// The random number generators of stochastic solvers such as
+    /// [`ParticleSwarm`](`crate::solver::particleswarm::ParticleSwarm`) and
+    /// [`SimulatedAnnealing`](`crate::solver::simulatedannealing::SimulatedAnnealing`) are reseeded
+    /// from `seed` before the solver is initialized (see [`Solver::reseed`]), unless the run is
+    /// resumed from a checkpoint. If the `rayon` feature is enabled, parallel evaluations (for
+    /// instance of populations) are performed sequentially in a single thread, such that the
+    /// problem is always called in the same order. The results of parallel evaluations are always
+    /// combined in order, even outside of deterministic mode. Since decisions based on the wall
+    /// clock differ between runs, starting a run with a [`max_time`](`Executor::max_time`) or
+    /// time-based checkpointing returns an error. The timer may still be enabled, since measured
+    /// times are only reported.
+    ///
+    /// The problem itself needs to be deterministic as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver).deterministic(1729);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn deterministic(mut self, seed: u64) -> Self {
+        self.deterministic = Some(seed);
+        self
+    }
+
+    /// Sets the thread pool used for parallel evaluations during the run (requires the `rayon`
+    /// feature).
+    ///
+    /// By default, the parallel evaluations in `bulk_*` methods (for instance of populations) and
+    /// in the finite-difference adapters of [`numdiff`](`crate::core::numdiff`) run in the global
+    /// `rayon` thread pool, unless the `Executor` is itself run from within a thread pool. The
+    /// number of threads of the global thread pool can be configured via
+    /// `rayon::ThreadPoolBuilder::build_global` or the `RAYON_NUM_THREADS` environment variable. An
+    /// application which manages its own thread pools can pass one of them here instead. Threads
+    /// used by a BLAS library are configured via the library itself (for instance via
+    /// `OPENBLAS_NUM_THREADS`).
+    ///
+    /// Executors run from within the run of another `Executor` (for instance by solvers which
+    /// run nested solvers) use the same thread pool unless a different one is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// # use std::sync::Arc;
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let pool = Arc::new(rayon::ThreadPoolBuilder::new().num_threads(4).build()?);
+    /// let executor = Executor::new(problem, solver).thread_pool(pool);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn thread_pool(mut self, pool: Arc<rayon::ThreadPool>) -> Self {
+        self.thread_pool = Some(pool);
+        self
+    }
+
+    /// Runs parallel evaluations in a dedicated thread pool with `num_threads` threads (requires
+    /// the `rayon` feature).
+    ///
+    /// See [`thread_pool`](`Executor::thread_pool`) for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver).num_threads(2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn num_threads(self, num_threads: usize) -> Result<Self, Error> {
+        if num_threads == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Executor`: number of threads must be > 0."
+            ));
+        }
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()?;
+        Ok(self.thread_pool(Arc::new(pool)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_utils::{TestProblem, TestSolver};
+    use crate::core::{IterState, RecoveryAction};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_update() {
+        let problem = TestProblem::new();
+        let solver = TestSolver::new();
+
+        let mut executor = Executor::new(problem, solver).configure(
+            |config: IterState<Vec<f64>, (), (), (), (), f64>| config.param(vec![0.0, 0.0]),
+        );
+
+        // 1) Parameter vector changes, but not cost (continues to be `Inf`)
+        let new_param = vec![1.0, 1.0];
+        executor.state = Some(executor.state.take().unwrap().param(new_param.clone()));
+        executor.state.as_mut().unwrap().update();
+        assert_eq!(
+            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
+            new_param
+        );
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_infinite());
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_sign_positive());
+
+        // 2) Parameter vector and cost changes to something better
+        let new_param = vec![2.0, 2.0];
+        let new_cost = 10.0;
+        executor.state = Some(
+            executor
+                .state
+                .take()
+                .unwrap()
+                .param(new_param.clone())
+                .cost(new_cost),
+        );
+        executor.state.as_mut().unwrap().update();
+        assert_eq!(
+            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
+            new_param
+        );
+        assert_relative_eq!(
+            executor.state.as_ref().unwrap().get_best_cost(),
+            new_cost,
+            epsilon = f64::EPSILON
+        );
+
+        // 3) Parameter vector and cost changes to something worse
+        let old_param = executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_param()
+            .unwrap()
+            .clone();
+        let new_param = vec![3.0, 3.0];
+        let old_cost = executor.state.as_ref().unwrap().get_best_cost();
+        let new_cost = old_cost + 1.0;
+        executor.state = Some(
+            executor
+                .state
+                .take()
+                .unwrap()
+                .param(new_param)
+                .cost(new_cost),
+        );
+        executor.state.as_mut().unwrap().update();
+        assert_eq!(
+            executor
+                .state
+                .as_ref()
+                .unwrap()
+                .get_best_param()
+                .unwrap()
+                .clone(),
+            old_param
+        );
+        assert_relative_eq!(
+            executor.state.as_ref().unwrap().get_best_cost(),
+            old_cost,
+            epsilon = f64::EPSILON
+        );
+
+        // 4) `-Inf` is better than `Inf`
+        let solver = TestSolver {};
         let mut executor = Executor::new(problem, solver).configure(
             |config: IterState<Vec<f64>, (), (), (), (), f64>| config.param(vec![0.0, 0.0]),
         );
 
-        // 1) Parameter vector changes, but not cost (continues to be `Inf`)
-        let new_param = vec![1.0, 1.0];
-        executor.state = Some(executor.state.take().unwrap().param(new_param.clone()));
-        executor.state.as_mut().unwrap().update();
+        let new_param = vec![1.0, 1.0];
+        let new_cost = f64::NEG_INFINITY;
+        executor.state = Some(
+            executor
+                .state
+                .take()
+                .unwrap()
+                .param(new_param.clone())
+                .cost(new_cost),
+        );
+        executor.state.as_mut().unwrap().update();
+        assert_eq!(
+            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
+            new_param
+        );
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_infinite());
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_sign_negative());
+
+        // 5) `Inf` is worse than `-Inf`
+        let old_param = executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_param()
+            .unwrap()
+            .clone();
+        let new_param = vec![6.0, 6.0];
+        let new_cost = f64::INFINITY;
+        executor.state = Some(
+            executor
+                .state
+                .take()
+                .unwrap()
+                .param(new_param)
+                .cost(new_cost),
+        );
+        executor.state.as_mut().unwrap().update();
+        assert_eq!(
+            executor
+                .state
+                .as_ref()
+                .unwrap()
+                .get_best_param()
+                .unwrap()
+                .clone(),
+            old_param
+        );
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_infinite());
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_sign_negative());
+    }
+
+    /// The solver's `init` should not be called when started from a checkpoint.
+    /// See https://github.com/argmin-rs/argmin/issues/199.
+    #[test]
+    #[cfg(feature = "serde1")]
+    fn test_checkpointing_solver_initialization() {
+        use std::cell::RefCell;
+
+        use crate::core::{
+            checkpointing::CheckpointingFrequency, test_utils::TestProblem, ArgminFloat,
+            CostFunction,
+        };
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone)]
+        pub struct FakeCheckpoint {
+            pub frequency: CheckpointingFrequency,
+            pub solver: RefCell<Option<OptimizationAlgorithm>>,
+            pub state: RefCell<Option<IterState<Vec<f64>, (), (), (), (), f64>>>,
+        }
+
+        impl Checkpoint<OptimizationAlgorithm, IterState<Vec<f64>, (), (), (), (), f64>>
+            for FakeCheckpoint
+        {
+            fn save(
+                &self,
+                solver: &OptimizationAlgorithm,
+                state: &IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(), Error> {
+                *self.solver.borrow_mut() = Some(solver.clone());
+                *self.state.borrow_mut() = Some(state.clone());
+                Ok(())
+            }
+
+            fn load(
+                &self,
+            ) -> Result<
+                Option<(
+                    OptimizationAlgorithm,
+                    IterState<Vec<f64>, (), (), (), (), f64>,
+                )>,
+                Error,
+            > {
+                if self.solver.borrow().is_none() {
+                    return Ok(None);
+                }
+                Ok(Some((
+                    self.solver.borrow().clone().unwrap(),
+                    self.state.borrow().clone().unwrap(),
+                )))
+            }
+
+            fn frequency(&self) -> CheckpointingFrequency {
+                self.frequency
+            }
+        }
+
+        // Fake optimization algorithm which holds internal state which changes over time
+        #[derive(Clone, Serialize, Deserialize)]
+        struct OptimizationAlgorithm {
+            pub internal_state: u64,
+        }
+
+        // Implement Solver for OptimizationAlgorithm
+        impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for OptimizationAlgorithm
+        where
+            O: CostFunction<Param = P, Output = F>,
+            P: Clone,
+            F: ArgminFloat,
+        {
+            fn name(&self) -> &str {
+                "OptimizationAlgorithm"
+            }
+
+            // Only resets internal_state to 1
+            fn init(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<P, (), (), (), (), F>,
+            ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+                self.internal_state = 1;
+                Ok((state, None))
+            }
+
+            // Increment internal_state
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<P, (), (), (), (), F>,
+            ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+                self.internal_state += 1;
+                Ok((state, None))
+            }
+
+            // Avoid terminating early because param does not change
+            fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+                TerminationStatus::NotTerminated
+            }
+
+            // Avoid terminating early because param does not change
+            fn terminate_internal(
+                &mut self,
+                state: &IterState<P, (), (), (), (), F>,
+            ) -> TerminationStatus {
+                if state.get_iter() >= state.get_max_iters() {
+                    TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+                } else {
+                    TerminationStatus::NotTerminated
+                }
+            }
+        }
+
+        // Create random test problem
+        let problem = TestProblem::new();
+
+        // solver instance
+        let solver = OptimizationAlgorithm { internal_state: 0 };
+
+        // Create a checkpoint
+        let checkpoint = FakeCheckpoint {
+            frequency: CheckpointingFrequency::Always,
+            solver: RefCell::new(None),
+            state: RefCell::new(None),
+        };
+
+        // Create and run executor
+        let executor = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(10))
+            .checkpointing(checkpoint.clone());
+
+        let OptimizationResult { solver, .. } = executor.run().unwrap();
+
+        // internal_state should be 11
+        // (1 from init plus 10 iterations where it is incremented by 1)
+        assert_eq!(solver.internal_state, 11);
+
+        // Create and run solver again
+        let executor = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(10))
+            .checkpointing(checkpoint);
+
+        let OptimizationResult { solver, .. } = executor.run().unwrap();
+
+        // internal_state should still be 11
+        // (1 from init plus 10 iterations where it is incremented by 1)
+        assert_eq!(solver.internal_state, 11);
+
+        // Delete old checkpointing file
+        let _ = std::fs::remove_file(".checkpoints/init_test.arg");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_timeout() {
+        let solver = TestSolver::new();
+        let problem = TestProblem::new();
+        let timeout = Duration::from_secs(2);
+
+        let executor = Executor::new(problem, solver).timer(true);
+        assert!(executor.timer);
+        assert!(executor.max_time.is_none());
+
+        let executor = Executor::new(problem, solver).timer(false);
+        assert!(!executor.timer);
+        assert!(executor.max_time.is_none());
+
+        let executor = Executor::new(problem, solver).timeout(timeout);
+        assert!(executor.timer);
+        assert_eq!(executor.max_time, Some(timeout));
+
+        let executor = Executor::new(problem, solver).timeout(timeout).timer(false);
+        assert!(executor.timer);
+        assert_eq!(executor.max_time, Some(timeout));
+
+        let executor = Executor::new(problem, solver).timer(false).timeout(timeout);
+        assert!(executor.timer);
+        assert_eq!(executor.max_time, Some(timeout));
+    }
+
+    #[test]
+    fn test_max_time() {
+        // Busy-waits until the time limit is exhausted
+        #[derive(Clone, Copy)]
+        struct Spin {}
+
+        impl Solver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> for Spin {
+            fn name(&self) -> &str {
+                "Spin"
+            }
+
+            fn next_iter(
+                &mut self,
+                problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let start = Instant::now();
+                while !problem.time_limit_reached() && start.elapsed() < Duration::from_secs(10) {
+                    std::hint::spin_loop();
+                }
+                Ok((state, None))
+            }
+        }
+
+        let max_time = Duration::from_millis(20);
+
+        let executor = Executor::new(TestProblem::new(), Spin {})
+            .max_time(max_time)
+            .timer(false);
+        assert!(executor.timer);
+        assert_eq!(executor.max_time, Some(max_time));
+
+        let result = Executor::new(TestProblem::new(), Spin {})
+            .configure(|state| state.max_iters(1000))
+            .max_time(max_time)
+            .run()
+            .unwrap();
+        assert_eq!(
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::TimeLimitReached)
+        );
+        assert_eq!(result.state.get_iter(), 1);
+        assert!(result.state.get_time().unwrap() >= max_time);
+    }
+
+    #[test]
+    fn test_target_cost() {
+        // Decreases the cost by one in each iteration
+        #[derive(Clone, Copy)]
+        struct Descent {}
+
+        impl Solver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> for Descent {
+            fn name(&self) -> &str {
+                "Descent"
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let cost = 10.0 - state.get_iter() as f64;
+                Ok((state.param(vec![cost]).cost(cost), None))
+            }
+        }
+
+        // Overrides the target cost of the state
+        let result = Executor::new(TestProblem::new(), Descent {})
+            .configure(|state| state.max_iters(100).target_cost(2.0))
+            .target_cost(7.5)
+            .run()
+            .unwrap();
         assert_eq!(
-            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
-            new_param
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::TargetCostReached)
         );
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_infinite());
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_sign_positive());
+        assert_eq!(result.state.get_iter(), 4);
+        assert_relative_eq!(result.state.get_cost(), 7.0, epsilon = f64::EPSILON);
+    }
 
-        // 2) Parameter vector and cost changes to something better
-        let new_param = vec![2.0, 2.0];
-        let new_cost = 10.0;
-        executor.state = Some(
-            executor
-                .state
-                .take()
-                .unwrap()
-                .param(new_param.clone())
-                .cost(new_cost),
+    #[test]
+    fn test_termination_criteria() {
+        let solver = TestSolver::new();
+        let problem = TestProblem::new();
+
+        // Criteria are evaluated in order; the first one which terminates wins.
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(100))
+            .add_termination_criterion(|_state: &IterState<Vec<f64>, (), (), (), (), f64>| {
+                TerminationStatus::NotTerminated
+            })
+            .add_termination_criterion(|_state| {
+                TerminationStatus::Terminated(TerminationReason::SolverExit("First".to_string()))
+            })
+            .add_termination_criterion(|_state| {
+                TerminationStatus::Terminated(TerminationReason::SolverConverged)
+            })
+            .run()
+            .unwrap();
+        assert_eq!(
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::SolverExit("First".to_string()))
         );
-        executor.state.as_mut().unwrap().update();
+        assert_eq!(result.state.get_iter(), 1);
+
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(100))
+            .add_termination_criterion(|state: &IterState<Vec<f64>, (), (), (), (), f64>| {
+                if state.get_iter() >= 5 {
+                    TerminationStatus::Terminated(TerminationReason::SolverExit(
+                        "Enough".to_string(),
+                    ))
+                } else {
+                    TerminationStatus::NotTerminated
+                }
+            })
+            .run()
+            .unwrap();
         assert_eq!(
-            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
-            new_param
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::SolverExit("Enough".to_string()))
         );
-        assert_relative_eq!(
-            executor.state.as_ref().unwrap().get_best_cost(),
-            new_cost,
-            epsilon = f64::EPSILON
+        assert_eq!(result.state.get_iter(), 5);
+    }
+
+    #[test]
+    fn test_early_stopping() {
+        // Sets the parameter vector to the iteration number
+        #[derive(Clone, Copy)]
+        struct Counter {}
+
+        impl Solver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> for Counter {
+            fn name(&self) -> &str {
+                "Counter"
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let iter = state.get_iter() as f64;
+                Ok((state.param(vec![iter]).cost(-iter), None))
+            }
+        }
+
+        // Minimal at 3.0
+        struct Validator {}
+
+        impl crate::core::CostFunction for Validator {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok((p[0] - 3.0).powi(2))
+            }
+        }
+
+        let result = Executor::new(TestProblem::new(), Counter {})
+            .configure(|state| state.max_iters(100))
+            .early_stopping(EarlyStopping::new(Validator {}).with_patience(2).unwrap())
+            .timer(true)
+            .run()
+            .unwrap();
+        assert_eq!(
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::EarlyStopped)
         );
+        // Rolled back to the iterate with the best validation cost
+        assert_eq!(result.state.get_param(), Some(&vec![3.0]));
+        assert_eq!(result.state.get_iter(), 4);
+        assert!(result.state.get_time().unwrap() > Duration::ZERO);
+    }
 
-        // 3) Parameter vector and cost changes to something worse
-        let old_param = executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_param()
-            .unwrap()
-            .clone();
-        let new_param = vec![3.0, 3.0];
-        let old_cost = executor.state.as_ref().unwrap().get_best_cost();
-        let new_cost = old_cost + 1.0;
-        executor.state = Some(
-            executor
-                .state
-                .take()
-                .unwrap()
-                .param(new_param)
-                .cost(new_cost),
+    #[test]
+    fn test_stall_detection() {
+        // Decreases the cost until it reaches 5.0
+        #[derive(Clone, Copy)]
+        struct Flatten {}
+
+        impl Solver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> for Flatten {
+            fn name(&self) -> &str {
+                "Flatten"
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let cost = (10.0 - state.get_iter() as f64).max(5.0);
+                Ok((state.cost(cost), None))
+            }
+        }
+
+        let result = Executor::new(TestProblem::new(), Flatten {})
+            .configure(|state| state.max_iters(100))
+            .stall_detection(StallDetection::new(3, 1e-3).unwrap())
+            .run()
+            .unwrap();
+        assert_eq!(
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::Stalled)
         );
-        executor.state.as_mut().unwrap().update();
+        assert_eq!(result.state.get_iter(), 9);
+    }
+
+    #[test]
+    fn test_evaluation_budget() {
+        // Evaluates the cost function three times in a nested problem (like a line search) and
+        // the gradient once per iteration.
+        #[derive(Clone, Copy)]
+        struct Nested {}
+
+        impl Solver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> for Nested {
+            fn name(&self) -> &str {
+                "Nested"
+            }
+
+            fn next_iter(
+                &mut self,
+                problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let param = vec![1.0];
+                let mut nested = Problem::new(problem.take_problem().unwrap());
+                for _ in 0..3 {
+                    nested.cost(&param)?;
+                }
+                problem.consume_problem(nested);
+                problem.gradient(&param)?;
+                Ok((state, None))
+            }
+        }
+
+        let result = Executor::new(TestProblem::new(), Nested {})
+            .configure(|state| state.max_iters(100))
+            .evaluation_budget(EvaluationBudget::new().with_limit("cost_count", 7))
+            .run()
+            .unwrap();
         assert_eq!(
-            executor
-                .state
-                .as_ref()
-                .unwrap()
-                .get_best_param()
-                .unwrap()
-                .clone(),
-            old_param
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::MaxEvaluationsReached)
         );
-        assert_relative_eq!(
-            executor.state.as_ref().unwrap().get_best_cost(),
-            old_cost,
-            epsilon = f64::EPSILON
+        assert_eq!(result.state.get_iter(), 3);
+
+        // Each iteration costs 3 + 2 * 1 = 5
+        let budget = EvaluationBudget::new()
+            .with_weight("gradient_count", 2.0)
+            .unwrap()
+            .with_total(10.0)
+            .unwrap();
+        let result = Executor::new(TestProblem::new(), Nested {})
+            .configure(|state| state.max_iters(100))
+            .evaluation_budget(budget)
+            .run()
+            .unwrap();
+        assert_eq!(result.state.get_iter(), 2);
+    }
+
+    #[test]
+    fn test_non_finite() {
+        // Produces a `NaN` cost in the second iteration; rejecting a step resets the parameter
+        #[derive(Clone, Copy)]
+        struct Unstable {
+            rejected: u64,
+        }
+
+        impl Solver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> for Unstable {
+            fn name(&self) -> &str {
+                "Unstable"
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let cost = if state.get_iter() == 1 && self.rejected == 0 {
+                    f64::NAN
+                } else {
+                    1.0
+                };
+                Ok((state.param(vec![cost]).cost(cost), None))
+            }
+
+            fn reject_step(
+                &mut self,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<IterState<Vec<f64>, (), (), (), (), f64>, Error> {
+                self.rejected += 1;
+                Ok(state)
+            }
+        }
+
+        let run = |policy| {
+            Executor::new(TestProblem::new(), Unstable { rejected: 0 })
+                .configure(|state| state.max_iters(3))
+                .non_finite(NonFiniteCheck::new(policy).with_param())
+                .run()
+        };
+
+        let err = run(NonFinitePolicy::Abort).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Non-finite value: \"cost is not finite after iteration 1 of `Unstable`.\""
         );
 
-        // 4) `-Inf` is better than `Inf`
-        let solver = TestSolver {};
-        let mut executor = Executor::new(problem, solver).configure(
-            |config: IterState<Vec<f64>, (), (), (), (), f64>| config.param(vec![0.0, 0.0]),
+        let result = run(NonFinitePolicy::RejectStep).unwrap();
+        assert_eq!(result.solver.rejected, 1);
+        assert_eq!(result.state.get_iter(), 3);
+        assert_eq!(result.state.get_param(), Some(&vec![1.0]));
+
+        let result = run(NonFinitePolicy::Clamp(|state| {
+            state.clamp_cost();
+            state.param = Some(vec![0.0]);
+        }))
+        .unwrap();
+        assert_eq!(result.state.get_iter(), 3);
+        assert!(result.state.get_cost().is_finite());
+
+        // Solvers need to support rejecting steps
+        let err = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![f64::NAN]).max_iters(3))
+            .non_finite(NonFiniteCheck::new(NonFinitePolicy::RejectStep).with_param())
+            .run()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Not implemented: \"`TestSolver` does not support rejecting steps.\""
         );
+    }
 
-        let new_param = vec![1.0, 1.0];
-        let new_cost = f64::NEG_INFINITY;
-        executor.state = Some(
-            executor
-                .state
-                .take()
-                .unwrap()
-                .param(new_param.clone())
-                .cost(new_cost),
+    #[test]
+    fn test_recovery() {
+        // Fails in the second iteration unless it was restarted before
+        #[derive(Clone, Copy)]
+        struct Fragile {
+            restarts: u64,
+        }
+
+        impl Solver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> for Fragile {
+            fn name(&self) -> &str {
+                "Fragile"
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                if state.get_iter() == 1 && self.restarts == 0 {
+                    return Err(argmin_error!(LineSearchFailed, "no step found"));
+                }
+                let param = state.get_param().unwrap()[0] + 1.0;
+                Ok((state.param(vec![param]).cost(param), None))
+            }
+
+            fn restart(
+                &mut self,
+                _problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<IterState<Vec<f64>, (), (), (), (), f64>, Error> {
+                self.restarts += 1;
+                Ok(state.restart())
+            }
+        }
+
+        let run = |action| {
+            Executor::new(TestProblem::new(), Fragile { restarts: 0 })
+                .configure(|state| state.param(vec![0.0]).max_iters(4))
+                .recovery(RecoveryPolicy::new(action))
+                .run()
+        };
+
+        let err = run(RecoveryAction::Abort).err().unwrap();
+        assert_eq!(err.to_string(), "Line search failed: \"no step found\"");
+
+        // The failed iteration counts as an iteration
+        let result = run(RecoveryAction::Reset).unwrap();
+        assert_eq!(result.solver.restarts, 1);
+        assert_eq!(result.state.get_iter(), 4);
+        assert_eq!(result.state.get_param(), Some(&vec![3.0]));
+
+        let result = run(RecoveryAction::Perturb(
+            |state: &mut IterState<Vec<f64>, (), (), (), (), f64>| {
+                state.param = Some(vec![10.0]);
+            },
+        ))
+        .unwrap();
+        assert_eq!(result.state.get_param(), Some(&vec![12.0]));
+    }
+
+    #[test]
+    fn test_deterministic() {
+        #[derive(Clone, Copy)]
+        struct Seeded {
+            seed: Option<u64>,
+        }
+
+        impl Solver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> for Seeded {
+            fn name(&self) -> &str {
+                "Seeded"
+            }
+
+            fn reseed(&mut self, seed: u64) {
+                self.seed = Some(seed);
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                Ok((state, None))
+            }
+        }
+
+        let executor = || {
+            Executor::new(TestProblem::new(), Seeded { seed: None })
+                .configure(|state| state.param(vec![1.0]).max_iters(2))
+                .deterministic(1729)
+        };
+
+        let result = executor().run().unwrap();
+        assert_eq!(result.solver.seed, Some(1729));
+
+        let result = Executor::new(TestProblem::new(), Seeded { seed: None })
+            .configure(|state| state.param(vec![1.0]).max_iters(2))
+            .run()
+            .unwrap();
+        assert_eq!(result.solver.seed, None);
+
+        // Not reseeded when continuing a run
+        let result = executor()
+            .configure(|mut state| {
+                state.increment_iter();
+                state
+            })
+            .run()
+            .unwrap();
+        assert_eq!(result.solver.seed, None);
+
+        // Wall-clock dependent options are rejected
+        let message = concat!(
+            "Invalid parameter: \"`Executor`: time limit and time-based checkpointing depend on ",
+            "the wall clock and cannot be used in deterministic mode.\""
         );
-        executor.state.as_mut().unwrap().update();
+        #[allow(deprecated)]
+        let err = executor()
+            .timeout(Duration::from_secs(1))
+            .run()
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), message);
+        let err = executor()
+            .max_time(Duration::from_secs(1))
+            .run()
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), message);
+    }
+
+    #[test]
+    fn test_cancellation_token() {
+        let solver = TestSolver::new();
+        let problem = TestProblem::new();
+
+        let token = CancellationToken::new();
+        let handle = token.clone();
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(100))
+            .cancellation_token(token)
+            .add_termination_criterion(move |state: &IterState<Vec<f64>, (), (), (), (), f64>| {
+                // Simulates an external request in the third iteration
+                if state.get_iter() == 3 {
+                    handle.cancel();
+                }
+                TerminationStatus::NotTerminated
+            })
+            .run()
+            .unwrap();
         assert_eq!(
-            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
-            new_param
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::Cancelled)
         );
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_infinite());
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_sign_negative());
+        assert_eq!(result.state.get_iter(), 3);
+        assert_eq!(result.state.get_param(), Some(&vec![1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_step() {
+        let solver = TestSolver::new();
+        let problem = TestProblem::new();
 
-        // 5) `Inf` is worse than `-Inf`
-        let old_param = executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_param()
-            .unwrap()
-            .clone();
-        let new_param = vec![6.0, 6.0];
-        let new_cost = f64::INFINITY;
-        executor.state = Some(
-            executor
-                .state
-                .take()
-                .unwrap()
-                .param(new_param)
-                .cost(new_cost),
+        let mut executor = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(5));
+        let mut steps = 0;
+        while !executor.step().unwrap().terminated() {
+            steps += 1;
+        }
+        assert_eq!(steps, 5);
+
+        // Further steps do not perform any iterations
+        let state = executor.step().unwrap();
+        assert_eq!(state.get_iter(), 5);
+        assert_eq!(
+            state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::MaxItersReached)
         );
-        executor.state.as_mut().unwrap().update();
+
+        let stepped = executor.finish().unwrap();
+        let run = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(5))
+            .run()
+            .unwrap();
+        assert_eq!(stepped.state.get_iter(), run.state.get_iter());
         assert_eq!(
-            executor
-                .state
-                .as_ref()
-                .unwrap()
-                .get_best_param()
-                .unwrap()
-                .clone(),
-            old_param
+            stepped.state.get_termination_status(),
+            run.state.get_termination_status()
         );
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_infinite());
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_sign_negative());
+
+        // The state can be configured in between steps
+        let mut executor = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(2));
+        executor.step().unwrap();
+        let mut executor = executor.configure(|state| state.max_iters(3));
+        while !executor.step().unwrap().terminated() {}
+        let result = executor.finish().unwrap();
+        assert_eq!(result.state.get_iter(), 3);
+
+        // Finishing without any step only initializes the solver
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(5))
+            .finish()
+            .unwrap();
+        assert_eq!(result.state.get_iter(), 0);
     }
 
-    /// The solver's `init` should not be called when started from a checkpoint.
-    /// See https://github.com/argmin-rs/argmin/issues/199.
     #[test]
-    #[cfg(feature = "serde1")]
-    fn test_checkpointing_solver_initialization() {
-        use std::cell::RefCell;
+    fn test_iteration_hooks() {
+        let solver = TestSolver::new();
+        let problem = TestProblem::new();
 
-        use crate::core::{
-            checkpointing::CheckpointingFrequency, test_utils::TestProblem, ArgminFloat,
-            CostFunction,
-        };
-        use serde::{Deserialize, Serialize};
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(100))
+            .add_iteration_hook(
+                |state: &mut IterState<Vec<f64>, (), (), (), (), f64>,
+                 problem: &mut Problem<TestProblem>| {
+                    state.param.as_mut().unwrap()[0] += 1.0;
+                    problem.counts.insert("hook_count", state.get_iter() + 1);
+                    Ok(TerminationStatus::NotTerminated)
+                },
+            )
+            .add_iteration_hook(|state, _problem| {
+                if state.get_iter() == 2 {
+                    Ok(TerminationStatus::Terminated(
+                        TerminationReason::SolverExit("Hook".to_string()),
+                    ))
+                } else {
+                    Ok(TerminationStatus::NotTerminated)
+                }
+            })
+            .run()
+            .unwrap();
+        assert_eq!(
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::SolverExit("Hook".to_string()))
+        );
+        assert_eq!(result.state.get_iter(), 3);
+        assert_eq!(result.state.get_param(), Some(&vec![4.0, 2.0]));
+        assert_eq!(result.problem.counts.get("hook_count"), Some(&3));
+
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(100))
+            .add_iteration_hook(
+                |_state: &mut IterState<Vec<f64>, (), (), (), (), f64>,
+                 _problem: &mut Problem<TestProblem>| {
+                    Err(argmin_error!(PotentialBug, "Hook failed"))
+                },
+            )
+            .run();
+        assert!(result.is_err());
+    }
 
-        #[derive(Clone)]
-        pub struct FakeCheckpoint {
-            pub frequency: CheckpointingFrequency,
-            pub solver: RefCell<Option<OptimizationAlgorithm>>,
-            pub state: RefCell<Option<IterState<Vec<f64>, (), (), (), (), f64>>>,
+    #[test]
+    fn test_modify_problem() {
+        use crate::core::{CostFunction, Gradient};
+        use crate::solver::linesearch::MoreThuenteLineSearch;
+        use crate::solver::quasinewton::LBFGS;
+        use approx::assert_relative_eq;
+        use argmin_testfunctions::{rosenbrock_ab, rosenbrock_ab_derivative};
+
+        /// Minimum at `(a, a^2)`
+        struct Rosenbrock {
+            a: f64,
         }
 
-        impl Checkpoint<OptimizationAlgorithm, IterState<Vec<f64>, (), (), (), (), f64>>
-            for FakeCheckpoint
-        {
-            fn save(
-                &self,
-                solver: &OptimizationAlgorithm,
-                state: &IterState<Vec<f64>, (), (), (), (), f64>,
-            ) -> Result<(), Error> {
-                *self.solver.borrow_mut() = Some(solver.clone());
-                *self.state.borrow_mut() = Some(state.clone());
-                Ok(())
-            }
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
 
-            fn load(
-                &self,
-            ) -> Result<
-                Option<(
-                    OptimizationAlgorithm,
-                    IterState<Vec<f64>, (), (), (), (), f64>,
-                )>,
-                Error,
-            > {
-                if self.solver.borrow().is_none() {
-                    return Ok(None);
-                }
-                Ok(Some((
-                    self.solver.borrow().clone().unwrap(),
-                    self.state.borrow().clone().unwrap(),
-                )))
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(rosenbrock_ab(p, self.a, 100.0))
             }
+        }
 
-            fn frequency(&self) -> CheckpointingFrequency {
-                self.frequency
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(rosenbrock_ab_derivative(p, self.a, 100.0))
             }
         }
 
-        // Fake optimization algorithm which holds internal state which changes over time
-        #[derive(Clone, Serialize, Deserialize)]
-        struct OptimizationAlgorithm {
-            pub internal_state: u64,
+        // The problem is changed once the solver is close to the first minimum. The best
+        // parameter vector must belong to the modified problem.
+        let mut modifications = 0;
+        let res = Executor::new(
+            Rosenbrock { a: 1.0 },
+            LBFGS::new(MoreThuenteLineSearch::new(), 7),
+        )
+        .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(200).counting(true))
+        .modify_problem(
+            move |state: &IterState<_, _, _, _, _, _>, problem: &mut Rosenbrock| {
+                if state.get_iter() == 30 {
+                    modifications += 1;
+                    assert_eq!(modifications, 1);
+                    *problem = Rosenbrock { a: 1.5 };
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            },
+        )
+        .run()
+        .unwrap();
+        assert!(res.state.get_iter() > 30);
+        let best_param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(best_param[0], 1.5, epsilon = 1e-4);
+        assert_relative_eq!(best_param[1], 2.25, epsilon = 1e-4);
+        // Evaluations of both versions of the problem are counted
+        let counts = res.state.get_func_counts();
+        assert_eq!(counts["cost_count"], res.problem.counts["cost_count"]);
+        assert_eq!(
+            counts["gradient_count"],
+            res.problem.counts["gradient_count"]
+        );
+
+        // Errors are passed on
+        let res = Executor::new(
+            Rosenbrock { a: 1.0 },
+            LBFGS::new(MoreThuenteLineSearch::new(), 7),
+        )
+        .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(10))
+        .modify_problem(
+            |_state: &IterState<_, _, _, _, _, _>, _problem: &mut Rosenbrock| {
+                Err(argmin_error!(PotentialBug, "Modification failed"))
+            },
+        )
+        .run();
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_track_best() {
+        // Moves away from the optimum in each iteration
+        #[derive(Clone, Copy)]
+        struct Ascent {
+            track_best: bool,
         }
 
-        // Implement Solver for OptimizationAlgorithm
-        impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for OptimizationAlgorithm
-        where
-            O: CostFunction<Param = P, Output = F>,
-            P: Clone,
-            F: ArgminFloat,
-        {
+        impl Solver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> for Ascent {
             fn name(&self) -> &str {
-                "OptimizationAlgorithm"
+                "Ascent"
             }
 
-            // Only resets internal_state to 1
-            fn init(
+            fn next_iter(
                 &mut self,
-                _problem: &mut Problem<O>,
-                state: IterState<P, (), (), (), (), F>,
-            ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
-                self.internal_state = 1;
-                Ok((state, None))
+                _problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let iter = state.get_iter() as f64;
+                Ok((state.param(vec![iter]).cost(iter), None))
+            }
+
+            fn track_best(&self) -> bool {
+                self.track_best
+            }
+        }
+
+        let problem = TestProblem::new();
+
+        let result = Executor::new(problem, Ascent { track_best: true })
+            .configure(|state| state.max_iters(5))
+            .run()
+            .unwrap();
+        assert_eq!(result.state.get_param(), Some(&vec![4.0]));
+        assert_eq!(result.state.get_best_param(), Some(&vec![0.0]));
+        assert_relative_eq!(result.state.get_best_cost(), 0.0, epsilon = f64::EPSILON);
+        assert_eq!(result.state.get_last_best_iter(), 0);
+
+        let result = Executor::new(problem, Ascent { track_best: false })
+            .configure(|state| state.max_iters(5))
+            .run()
+            .unwrap();
+        assert_eq!(result.state.get_param(), Some(&vec![4.0]));
+        assert!(result.state.get_best_param().is_none());
+        assert!(result.state.get_best_cost().is_infinite());
+    }
+
+    #[test]
+    fn test_timings() {
+        let solver = TestSolver::new();
+        let problem = TestProblem::new();
+
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(5))
+            .run()
+            .unwrap();
+        assert!(result.state.get_iter_time().is_none());
+
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(5))
+            .timer(true)
+            .run()
+            .unwrap();
+        let total = result.state.get_time().unwrap();
+        assert!(result.state.get_iter_time().unwrap() <= total);
+        assert_eq!(result.operator_time(), Duration::ZERO);
+        assert_eq!(result.solver_time(), Some(total));
+    }
+
+    #[test]
+    fn test_timings_observer() {
+        // Spends time in the cost function of the problem
+        #[derive(Clone, Copy)]
+        struct Sleepy {}
+
+        impl Solver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> for Sleepy {
+            fn name(&self) -> &str {
+                "Sleepy"
             }
 
-            // Increment internal_state
             fn next_iter(
                 &mut self,
-                _problem: &mut Problem<O>,
-                state: IterState<P, (), (), (), (), F>,
-            ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
-                self.internal_state += 1;
+                problem: &mut Problem<TestProblem>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                problem.problem("cost_count", |_| {
+                    std::thread::sleep(Duration::from_millis(5));
+                    Ok(())
+                })?;
                 Ok((state, None))
             }
+        }
 
-            // Avoid terminating early because param does not change
-            fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
-                TerminationStatus::NotTerminated
-            }
+        struct Recorder(Arc<std::sync::Mutex<Vec<KV>>>);
 
-            // Avoid terminating early because param does not change
-            fn terminate_internal(
-                &mut self,
-                state: &IterState<P, (), (), (), (), F>,
-            ) -> TerminationStatus {
-                if state.get_iter() >= state.get_max_iters() {
-                    TerminationStatus::Terminated(TerminationReason::MaxItersReached)
-                } else {
-                    TerminationStatus::NotTerminated
-                }
+        impl<I> Observe<I> for Recorder {
+            fn observe_iter(&mut self, _state: &I, kv: &KV) -> Result<(), Error> {
+                self.0.lock().unwrap().push(kv.clone());
+                Ok(())
             }
         }
 
-        // Create random test problem
+        let kvs = Arc::new(std::sync::Mutex::new(vec![]));
+        let result = Executor::new(TestProblem::new(), Sleepy {})
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(3))
+            .add_observer(Recorder(Arc::clone(&kvs)), ObserverMode::Always)
+            .timer(true)
+            .run()
+            .unwrap();
+        assert!(result.operator_time() >= Duration::from_millis(15));
+
+        let kvs = kvs.lock().unwrap();
+        assert_eq!(kvs.len(), 3);
+        for kv in kvs.iter() {
+            let time = kv.get("time").unwrap().get_float().unwrap();
+            let operator_time = kv.get("operator_time").unwrap().get_float().unwrap();
+            let solver_time = kv.get("solver_time").unwrap().get_float().unwrap();
+            assert!(operator_time >= 0.005);
+            assert_relative_eq!(operator_time + solver_time, time, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_history() {
+        let solver = TestSolver::new();
         let problem = TestProblem::new();
 
-        // solver instance
-        let solver = OptimizationAlgorithm { internal_state: 0 };
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(5))
+            .run()
+            .unwrap();
+        assert!(result.history.is_none());
+
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(5))
+            .history(History::new().params(true))
+            .timer(true)
+            .run()
+            .unwrap();
+        let history = result.history.unwrap();
+        assert_eq!(history.len(), 5);
+        let mut prev_time = Duration::ZERO;
+        for (i, entry) in history.entries().iter().enumerate() {
+            assert_eq!(entry.iter, i as u64);
+            assert_eq!(entry.param, Some(vec![1.0, 2.0]));
+            assert!(entry.gradient_norm.is_none());
+            // Time elapsed until the end of the iteration
+            let time = entry.time.unwrap();
+            assert!(time > Duration::ZERO);
+            assert!(time >= prev_time);
+            prev_time = time;
+        }
+        assert_eq!(result.state.get_time(), Some(prev_time));
+
+        let result = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(5))
+            .history(History::new())
+            .timer(false)
+            .run()
+            .unwrap();
+        let history = result.history.unwrap();
+        assert_eq!(history.len(), 5);
+        for entry in history.entries() {
+            assert!(entry.time.is_none());
+        }
+    }
 
-        // Create a checkpoint
-        let checkpoint = FakeCheckpoint {
-            frequency: CheckpointingFrequency::Always,
-            solver: RefCell::new(None),
-            state: RefCell::new(None),
-        };
+    #[test]
+    fn test_warm_start() {
+        use crate::core::{CostFunction, Gradient};
+        use crate::solver::linesearch::MoreThuenteLineSearch;
+        use crate::solver::quasinewton::{BFGS, LBFGS};
+        use argmin_testfunctions::{rosenbrock_ab, rosenbrock_ab_derivative};
+
+        /// Minimum at `(a, a^2)`
+        struct Rosenbrock {
+            a: f64,
+        }
 
-        // Create and run executor
-        let executor = Executor::new(problem, solver)
-            .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(10))
-            .checkpointing(checkpoint.clone());
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
 
-        let OptimizationResult { solver, .. } = executor.run().unwrap();
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(rosenbrock_ab(p, self.a, 100.0))
+            }
+        }
 
-        // internal_state should be 11
-        // (1 from init plus 10 iterations where it is incremented by 1)
-        assert_eq!(solver.internal_state, 11);
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
 
-        // Create and run solver again
-        let executor = Executor::new(problem, solver)
-            .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(10))
-            .checkpointing(checkpoint);
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(rosenbrock_ab_derivative(p, self.a, 100.0))
+            }
+        }
 
-        let OptimizationResult { solver, .. } = executor.run().unwrap();
+        // BFGS takes over the parameter vector and the inverse Hessian
+        let previous = Executor::new(
+            Rosenbrock { a: 1.0 },
+            BFGS::new(MoreThuenteLineSearch::new()),
+        )
+        .configure(|state| {
+            state
+                .param(vec![-1.2, 1.0])
+                .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+                .max_iters(10)
+        })
+        .run()
+        .unwrap();
+        let best_param = previous.state.get_best_param().unwrap().clone();
+        let inv_hessian = previous.state.get_inv_hessian().unwrap().clone();
+        let executor = Executor::new(
+            Rosenbrock { a: 1.1 },
+            BFGS::new(MoreThuenteLineSearch::new()),
+        )
+        .warm_start(previous)
+        .unwrap();
+        let state = executor.state.as_ref().unwrap();
+        assert_eq!(state.get_param(), Some(&best_param));
+        assert_eq!(state.get_inv_hessian(), Some(&inv_hessian));
+        assert!(state.get_gradient().is_none());
+        assert!(state.get_cost().is_infinite());
+        assert_eq!(state.get_iter(), 0);
+
+        // A parameter vector set before takes precedence
+        let previous = Executor::new(
+            Rosenbrock { a: 1.0 },
+            BFGS::new(MoreThuenteLineSearch::new()),
+        )
+        .configure(|state| {
+            state
+                .param(vec![-1.2, 1.0])
+                .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+                .max_iters(1)
+        })
+        .run()
+        .unwrap();
+        let executor = Executor::new(
+            Rosenbrock { a: 1.1 },
+            BFGS::new(MoreThuenteLineSearch::new()),
+        )
+        .configure(|state| state.param(vec![0.5, 0.5]))
+        .warm_start(previous)
+        .unwrap();
+        assert_eq!(
+            executor.state.as_ref().unwrap().get_param(),
+            Some(&vec![0.5, 0.5])
+        );
 
-        // internal_state should still be 11
-        // (1 from init plus 10 iterations where it is incremented by 1)
-        assert_eq!(solver.internal_state, 11);
+        // L-BFGS converges faster on a slightly changed problem when the curvature pairs are
+        // taken over
+        let solver = LBFGS::new(MoreThuenteLineSearch::new(), 7);
+        let previous = Executor::new(Rosenbrock { a: 1.0 }, solver.clone())
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+            .run()
+            .unwrap();
+        let best_param = previous.state.get_best_param().unwrap().clone();
+        let cold = Executor::new(Rosenbrock { a: 1.05 }, solver.clone())
+            .configure(|state| state.param(best_param).max_iters(100))
+            .run()
+            .unwrap();
+        let warm = Executor::new(Rosenbrock { a: 1.05 }, solver)
+            .warm_start(previous)
+            .unwrap()
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            warm.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert!(warm.state.get_iter() < cold.state.get_iter());
+    }
 
-        // Delete old checkpointing file
-        let _ = std::fs::remove_file(".checkpoints/init_test.arg");
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_thread_pool() {
+        use crate::core::{CostFunction, WithCentralDiff};
+        use crate::solver::linesearch::MoreThuenteLineSearch;
+        use crate::solver::particleswarm::ParticleSwarm;
+        use crate::solver::quasinewton::LBFGS;
+        use std::sync::Mutex;
+
+        /// Records the number of threads of the thread pool each evaluation runs in
+        #[derive(Default)]
+        struct Recording {
+            num_threads: Mutex<Vec<usize>>,
+        }
+
+        impl CostFunction for Recording {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                self.num_threads
+                    .lock()
+                    .unwrap()
+                    .push(rayon::current_num_threads());
+                Ok(argmin_testfunctions::sphere(p))
+            }
+        }
+
+        assert_error!(
+            Executor::new(TestProblem::new(), TestSolver::new()).num_threads(0),
+            crate::core::ArgminError,
+            "Invalid parameter: \"`Executor`: number of threads must be > 0.\""
+        );
+
+        // Population evaluation
+        let solver = ParticleSwarm::new((vec![-1.0, -1.0], vec![1.0, 1.0]), 10);
+        let res = Executor::new(Recording::default(), solver)
+            .num_threads(3)
+            .unwrap()
+            .configure(|state| state.max_iters(2))
+            .run()
+            .unwrap();
+        let num_threads = res
+            .problem
+            .problem
+            .unwrap()
+            .num_threads
+            .into_inner()
+            .unwrap();
+        assert_eq!(num_threads, vec![3; 30]);
+
+        // Finite differences, also within the nested line search
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+        let solver = LBFGS::new(MoreThuenteLineSearch::new(), 3);
+        let res = Executor::new(WithCentralDiff::new(Recording::default()), solver)
+            .thread_pool(pool)
+            .configure(|state| state.param(vec![1.0, -0.5]).max_iters(2))
+            .run()
+            .unwrap();
+        let problem = res.problem.problem.unwrap().into_inner();
+        let num_threads = problem.num_threads.into_inner().unwrap();
+        // Single cost function evaluations run on the thread of the `Executor`
+        let global = rayon::current_num_threads();
+        assert!(num_threads.iter().filter(|&&n| n == 2).count() >= 8);
+        assert!(num_threads.iter().all(|&n| n == 2 || n == global));
     }
 
     #[test]
-    fn test_timeout() {
-        let solver = TestSolver::new();
-        let problem = TestProblem::new();
-        let timeout = std::time::Duration::from_secs(2);
+    fn test_save_and_load_state() {
+        use crate::core::{CostFunction, Gradient};
+        use crate::solver::gradientdescent::SteepestDescent;
+        use crate::solver::linesearch::{condition::ArmijoCondition, BacktrackingLineSearch};
 
-        let executor = Executor::new(problem, solver).timer(true);
-        assert!(executor.timer);
-        assert!(executor.timeout.is_none());
+        struct Quadratic;
 
-        let executor = Executor::new(problem, solver).timer(false);
-        assert!(!executor.timer);
-        assert!(executor.timeout.is_none());
+        impl CostFunction for Quadratic {
+            type Param = Vec<f64>;
+            type Output = f64;
 
-        let executor = Executor::new(problem, solver).timeout(timeout);
-        assert!(executor.timer);
-        assert_eq!(executor.timeout, Some(timeout));
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p[0].powi(2) + 10.0 * p[1].powi(2))
+            }
+        }
 
-        let executor = Executor::new(problem, solver).timeout(timeout).timer(false);
-        assert!(executor.timer);
-        assert_eq!(executor.timeout, Some(timeout));
+        impl Gradient for Quadratic {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
 
-        let executor = Executor::new(problem, solver).timer(false).timeout(timeout);
-        assert!(executor.timer);
-        assert_eq!(executor.timeout, Some(timeout));
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![2.0 * p[0], 20.0 * p[1]])
+            }
+        }
+
+        let solver = || {
+            SteepestDescent::new(BacktrackingLineSearch::new(
+                ArmijoCondition::new(0.0001).unwrap(),
+            ))
+        };
+
+        let full = Executor::new(Quadratic, solver())
+            .configure(|state| state.param(vec![1.0, 1.0]).max_iters(10).counting(true))
+            .run()
+            .unwrap();
+
+        let mut executor = Executor::new(Quadratic, solver())
+            .configure(|state| state.param(vec![1.0, 1.0]).max_iters(10).counting(true));
+        for _ in 0..4 {
+            executor.step().unwrap();
+        }
+        let mut saved = vec![];
+        executor.save_state(&mut saved).unwrap();
+
+        // Loading into a started run fails
+        assert!(executor.load_state(&mut saved.as_slice()).is_err());
+
+        let resumed = Executor::new(Quadratic, solver())
+            .load_state(&mut saved.as_slice())
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(resumed.state.get_iter(), 10);
+        assert_eq!(resumed.state.get_param(), full.state.get_param());
+        assert_eq!(
+            resumed.state.get_best_cost().to_ne_bytes(),
+            full.state.get_best_cost().to_ne_bytes()
+        );
+
+        // Invalid input
+        assert!(Executor::new(Quadratic, solver())
+            .load_state(&mut [0u8; 4].as_slice())
+            .is_err());
     }
 }
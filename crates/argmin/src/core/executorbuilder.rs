@@ -0,0 +1,701 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::checkpointing::Checkpoint;
+use crate::core::observers::{Observe, ObserverMode};
+use crate::core::time::Duration;
+use crate::core::{
+    ArgminFloat, Error, EvaluationBudget, Executor, MaxStepLength, Solver, StallDetection, State,
+    TerminationReason, TerminationStatus,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Configuration of an [`Executor`].
+///
+/// Holds the settings of a run which do not depend on the types of the problem and the solver.
+/// With the `serde1` feature enabled, the configuration can be (de)serialized, which allows for
+/// storing it alongside the results of an experiment and for reproducing the run later. Fields
+/// which are missing in the serialized configuration are set to their default values.
+///
+/// Settings which consist of closures or depend on the type of the state, namely observers,
+/// checkpoints, early stopping, checks for non-finite values, recovery policies, move limits and
+/// additional termination criteria, are not part of the configuration. Observers and checkpoints
+/// are set on the [`ExecutorBuilder`], the others on the [`Executor`] returned by
+/// [`ExecutorBuilder::build`].
+///
+/// The configuration is applied via [`ExecutorBuilder::from_config`] and validated by
+/// [`ExecutorBuilder::build`].
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::ExecutorConfig;
+/// # use std::time::Duration;
+/// let config = ExecutorConfig {
+///     max_iters: Some(100),
+///     max_time: Some(Duration::from_secs(60)),
+///     ..ExecutorConfig::<f64>::default()
+/// };
+/// assert!(config.validate().is_ok());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", serde(default))]
+pub struct ExecutorConfig<F> {
+    /// Maximum number of iterations (in addition to the one stored in the state)
+    pub max_iters: Option<u64>,
+    /// Target cost function value (see [`Executor::target_cost`])
+    pub target_cost: Option<F>,
+    /// Wall-clock time limit (see [`Executor::max_time`]), also accepted as `timeout` when
+    /// deserializing
+    #[cfg_attr(feature = "serde1", serde(alias = "timeout"))]
+    pub max_time: Option<Duration>,
+    /// Enables timing of individual iterations (see [`Executor::timer`])
+    pub timer: bool,
+    /// Enables Ctrl-C handling (see [`Executor::ctrlc`])
+    pub ctrlc: bool,
+    /// Stall detection (see [`Executor::stall_detection`])
+    pub stall_detection: Option<StallDetection<F>>,
+    /// Budget of function evaluations (see [`Executor::evaluation_budget`])
+    pub evaluation_budget: Option<EvaluationBudget>,
+    /// Maximum step length (see [`Executor::max_step_length`])
+    pub max_step_length: Option<MaxStepLength>,
+    /// Seed of the deterministic mode (see [`Executor::deterministic`])
+    pub deterministic: Option<u64>,
+}
+
+impl<F> Default for ExecutorConfig<F> {
+    /// Constructs a configuration without any limits, with timer disabled and Ctrl-C handling
+    /// enabled, which corresponds to the defaults of [`Executor`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorConfig;
+    /// let config: ExecutorConfig<f64> = ExecutorConfig::default();
+    /// # assert_eq!(config.max_iters, None);
+    /// # assert_eq!(config.target_cost, None);
+    /// # assert_eq!(config.max_time, None);
+    /// # assert!(!config.timer);
+    /// # assert!(config.ctrlc);
+    /// # assert_eq!(config.stall_detection, None);
+    /// # assert_eq!(config.evaluation_budget, None);
+    /// # assert_eq!(config.max_step_length, None);
+    /// # assert_eq!(config.deterministic, None);
+    /// ```
+    fn default() -> Self {
+        ExecutorConfig {
+            max_iters: None,
+            target_cost: None,
+            max_time: None,
+            timer: false,
+            ctrlc: true,
+            stall_detection: None,
+            evaluation_budget: None,
+            max_step_length: None,
+            deterministic: None,
+        }
+    }
+}
+
+impl<F: ArgminFloat> ExecutorConfig<F> {
+    /// Returns an error if one of the settings is invalid.
+    ///
+    /// The maximum number of iterations and the time limit must be larger than 0
+    /// and the target cost must not be `NaN`. Stall detection, evaluation budget and maximum step
+    /// length are checked like in their constructors, which matters for deserialized
+    /// configurations. A time limit cannot be combined with the deterministic mode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorConfig;
+    /// let config = ExecutorConfig {
+    ///     max_iters: Some(0),
+    ///     ..ExecutorConfig::<f64>::default()
+    /// };
+    /// assert!(config.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.max_iters == Some(0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ExecutorConfig`: max_iters must be > 0."
+            ));
+        }
+        if self.target_cost.map(|cost| cost.is_nan()).unwrap_or(false) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ExecutorConfig`: target_cost must not be NaN."
+            ));
+        }
+        if self.max_time.map(|time| time.is_zero()).unwrap_or(false) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ExecutorConfig`: max_time must be > 0."
+            ));
+        }
+        if self.max_time.is_some() && self.deterministic.is_some() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ExecutorConfig`: max_time cannot be used in deterministic mode."
+            ));
+        }
+        if let Some(stall_detection) = &self.stall_detection {
+            stall_detection.validate()?;
+        }
+        if let Some(evaluation_budget) = &self.evaluation_budget {
+            evaluation_budget.validate()?;
+        }
+        if let Some(max_step_length) = &self.max_step_length {
+            max_step_length.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Builder for an [`Executor`] which validates the configuration before constructing it.
+///
+/// In contrast to configuring the [`Executor`] directly, invalid settings (such as a time limit of
+/// zero or a checkpoint directory which is not writable) are reported by [`build`] as an error
+/// before the run is started. The settings which do not depend on the types of problem and solver
+/// can be provided as an [`ExecutorConfig`] via [`from_config`], for instance deserialized from a
+/// file for reproducible experiments.
+///
+/// Checkpoints are validated via [`Checkpoint::validate`].
+///
+/// [`build`]: ExecutorBuilder::build
+/// [`from_config`]: ExecutorBuilder::from_config
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, ExecutorBuilder};
+/// # use argmin::core::test_utils::{TestSolver, TestProblem};
+/// # use std::time::Duration;
+/// # fn main() -> Result<(), Error> {
+/// # let solver = TestSolver::new();
+/// # let problem = TestProblem::new();
+/// let result = ExecutorBuilder::new(problem, solver)
+///     .configure(|state| state.param(vec![1.0, 2.0]))
+///     .max_iters(10)
+///     .max_time(Duration::from_secs(60))
+///     .build()?
+///     .run()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ExecutorBuilder<O, S, I: State> {
+    /// Executor the configuration is applied to
+    executor: Executor<O, S, I>,
+    /// Configuration
+    config: ExecutorConfig<I::Float>,
+    /// First error encountered while validating a checkpoint
+    error: Option<Error>,
+}
+
+impl<O, S, I> ExecutorBuilder<O, S, I>
+where
+    S: Solver<O, I>,
+    I: State,
+{
+    /// Constructs an `ExecutorBuilder` from a user defined problem and a solver with the default
+    /// configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new());
+    /// ```
+    pub fn new(problem: O, solver: S) -> Self {
+        ExecutorBuilder::from_config(problem, solver, ExecutorConfig::default())
+    }
+
+    /// Constructs an `ExecutorBuilder` from a user defined problem, a solver and a configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ExecutorBuilder, ExecutorConfig};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let config = ExecutorConfig {
+    ///     max_iters: Some(100),
+    ///     ..ExecutorConfig::default()
+    /// };
+    /// let builder = ExecutorBuilder::from_config(TestProblem::new(), TestSolver::new(), config);
+    /// ```
+    pub fn from_config(problem: O, solver: S, config: ExecutorConfig<I::Float>) -> Self {
+        ExecutorBuilder {
+            executor: Executor::new(problem, solver),
+            config,
+            error: None,
+        }
+    }
+
+    /// Returns the configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new()).max_iters(10);
+    /// assert_eq!(builder.config().max_iters, Some(10));
+    /// ```
+    pub fn config(&self) -> &ExecutorConfig<I::Float> {
+        &self.config
+    }
+
+    /// Sets the maximum number of iterations.
+    ///
+    /// This limit applies in addition to the maximum number of iterations stored in the state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new()).max_iters(10);
+    /// ```
+    #[must_use]
+    pub fn max_iters(mut self, max_iters: u64) -> Self {
+        self.config.max_iters = Some(max_iters);
+        self
+    }
+
+    /// Sets the target cost function value (see [`Executor::target_cost`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new()).target_cost(1e-6);
+    /// ```
+    #[must_use]
+    pub fn target_cost(mut self, target_cost: I::Float) -> Self {
+        self.config.target_cost = Some(target_cost);
+        self
+    }
+
+    /// Sets a wall-clock time limit (see [`Executor::max_time`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// # use std::time::Duration;
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+    ///     .max_time(Duration::from_secs(60));
+    /// ```
+    #[must_use]
+    pub fn max_time(mut self, max_time: Duration) -> Self {
+        self.config.max_time = Some(max_time);
+        self
+    }
+
+    /// Enables or disables timing of individual iterations (see [`Executor::timer`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new()).timer(true);
+    /// ```
+    #[must_use]
+    pub fn timer(mut self, timer: bool) -> Self {
+        self.config.timer = timer;
+        self
+    }
+
+    /// Enables or disables Ctrl-C handling (see [`Executor::ctrlc`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new()).ctrlc(false);
+    /// ```
+    #[must_use]
+    pub fn ctrlc(mut self, ctrlc: bool) -> Self {
+        self.config.ctrlc = ctrlc;
+        self
+    }
+
+    /// Enables stall detection (see [`Executor::stall_detection`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, ExecutorBuilder, StallDetection};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// # fn main() -> Result<(), Error> {
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+    ///     .stall_detection(StallDetection::new(50, 1e-3)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stall_detection(mut self, stall_detection: StallDetection<I::Float>) -> Self {
+        self.config.stall_detection = Some(stall_detection);
+        self
+    }
+
+    /// Sets a budget of function evaluations (see [`Executor::evaluation_budget`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{EvaluationBudget, ExecutorBuilder};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+    ///     .evaluation_budget(EvaluationBudget::new().with_limit("cost_count", 1000));
+    /// ```
+    #[must_use]
+    pub fn evaluation_budget(mut self, evaluation_budget: EvaluationBudget) -> Self {
+        self.config.evaluation_budget = Some(evaluation_budget);
+        self
+    }
+
+    /// Limits the length of the step taken in a single iteration (see
+    /// [`Executor::max_step_length`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, ExecutorBuilder, MaxStepLength};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// # fn main() -> Result<(), Error> {
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+    ///     .max_step_length(MaxStepLength::new().with_absolute(10.0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_step_length(mut self, max_step_length: MaxStepLength) -> Self {
+        self.config.max_step_length = Some(max_step_length);
+        self
+    }
+
+    /// Enables the deterministic mode with the given seed (see [`Executor::deterministic`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new()).deterministic(1729);
+    /// ```
+    #[must_use]
+    pub fn deterministic(mut self, seed: u64) -> Self {
+        self.config.deterministic = Some(seed);
+        self
+    }
+
+    /// Configures the initial state (see [`Executor::configure`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+    ///     .configure(|state| state.param(vec![1.0, 2.0]));
+    /// ```
+    #[must_use]
+    pub fn configure<F: FnOnce(I) -> I>(mut self, init: F) -> Self {
+        self.executor = self.executor.configure(init);
+        self
+    }
+
+    /// Adds an observer (see [`Executor::add_observer`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::observers::ObserverMode;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// # use argmin_observer_slog::SlogLogger;
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+    ///     .add_observer(SlogLogger::term(), ObserverMode::Always);
+    /// ```
+    #[must_use]
+    pub fn add_observer<OBS: Observe<I> + 'static>(
+        mut self,
+        observer: OBS,
+        mode: ObserverMode,
+    ) -> Self {
+        self.executor = self.executor.add_observer(observer, mode);
+        self
+    }
+
+    /// Configures checkpointing (see [`Executor::checkpointing`]).
+    ///
+    /// The checkpoint is validated via [`Checkpoint::validate`]; errors are reported by
+    /// [`build`](`ExecutorBuilder::build`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # #[cfg(feature = "serde1")]
+    /// # use argmin::core::checkpointing::CheckpointingFrequency;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// # use argmin_checkpointing_file::FileCheckpoint;
+    /// # #[cfg(feature = "serde1")]
+    /// let checkpoint = FileCheckpoint::new(".checkpoints", "builder", CheckpointingFrequency::Always);
+    /// # #[cfg(feature = "serde1")]
+    /// let builder = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+    ///     .checkpointing(checkpoint);
+    /// ```
+    #[must_use]
+    pub fn checkpointing<C: 'static + Checkpoint<S, I>>(mut self, checkpoint: C) -> Self {
+        if let Err(e) = checkpoint.validate() {
+            self.error.get_or_insert(e);
+        }
+        self.executor = self.executor.checkpointing(checkpoint);
+        self
+    }
+
+    /// Validates the configuration and constructs the [`Executor`].
+    ///
+    /// Returns the first error encountered while validating the checkpoint or the
+    /// [`ExecutorConfig`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ExecutorBuilder;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// # use std::time::Duration;
+    /// let executor = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+    ///     .max_time(Duration::from_secs(0))
+    ///     .build();
+    /// assert!(executor.is_err());
+    /// ```
+    pub fn build(self) -> Result<Executor<O, S, I>, Error>
+    where
+        I: 'static,
+    {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        self.config.validate()?;
+
+        let ExecutorConfig {
+            max_iters,
+            target_cost,
+            max_time,
+            timer,
+            ctrlc,
+            stall_detection,
+            evaluation_budget,
+            max_step_length,
+            deterministic,
+        } = self.config;
+        let mut executor = self.executor.timer(timer).ctrlc(ctrlc);
+        if let Some(max_iters) = max_iters {
+            executor = executor.add_termination_criterion(move |state: &I| {
+                if state.get_iter() >= max_iters {
+                    TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+                } else {
+                    TerminationStatus::NotTerminated
+                }
+            });
+        }
+        if let Some(target_cost) = target_cost {
+            executor = executor.target_cost(target_cost);
+        }
+        if let Some(max_time) = max_time {
+            executor = executor.max_time(max_time);
+        }
+        if let Some(stall_detection) = stall_detection {
+            executor = executor.stall_detection(stall_detection);
+        }
+        if let Some(evaluation_budget) = evaluation_budget {
+            executor = executor.evaluation_budget(evaluation_budget);
+        }
+        if let Some(max_step_length) = max_step_length {
+            executor = executor.max_step_length(max_step_length);
+        }
+        if let Some(seed) = deterministic {
+            executor = executor.deterministic(seed);
+        }
+        Ok(executor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::checkpointing::CheckpointingFrequency;
+    use crate::core::test_utils::{TestProblem, TestSolver};
+    use crate::core::IterState;
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    struct TestCheckpoint {
+        frequency: CheckpointingFrequency,
+    }
+
+    impl Checkpoint<TestSolver, TestState> for TestCheckpoint {
+        fn save(&self, _solver: &TestSolver, _state: &TestState) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn load(&self) -> Result<Option<(TestSolver, TestState)>, Error> {
+            Ok(None)
+        }
+
+        fn frequency(&self) -> CheckpointingFrequency {
+            self.frequency
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let config: ExecutorConfig<f64> = ExecutorConfig::default();
+        assert!(config.validate().is_ok());
+        for config in [
+            ExecutorConfig {
+                max_iters: Some(0),
+                ..config.clone()
+            },
+            ExecutorConfig {
+                target_cost: Some(f64::NAN),
+                ..config.clone()
+            },
+            ExecutorConfig {
+                max_time: Some(Duration::ZERO),
+                ..config.clone()
+            },
+            ExecutorConfig {
+                max_time: Some(Duration::from_secs(1)),
+                deterministic: Some(1729),
+                ..config.clone()
+            },
+        ] {
+            assert!(config.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        let result = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0f64]).max_iters(100))
+            .max_iters(5)
+            .ctrlc(false)
+            .build()
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(result.state.get_iter(), 5);
+        assert_eq!(
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+        );
+    }
+
+    #[test]
+    fn test_build_evaluation_budget() {
+        let result = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0f64]).max_iters(100))
+            // `TestSolver` does not evaluate the cost function, hence this budget is exhausted
+            // immediately.
+            .evaluation_budget(EvaluationBudget::new().with_limit("cost_count", 0))
+            .ctrlc(false)
+            .build()
+            .unwrap()
+            .run()
+            .unwrap();
+        assert_eq!(result.state.get_iter(), 1);
+        assert_eq!(
+            result.state.get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::MaxEvaluationsReached)
+        );
+    }
+
+    #[test]
+    fn test_invalid_checkpoint() {
+        let executor = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+            .configure(|state: TestState| state.param(vec![1.0]))
+            .checkpointing(TestCheckpoint {
+                frequency: CheckpointingFrequency::Every(0),
+            })
+            .build();
+        assert!(executor.is_err());
+
+        let executor = ExecutorBuilder::new(TestProblem::new(), TestSolver::new())
+            .configure(|state: TestState| state.param(vec![1.0]))
+            .checkpointing(TestCheckpoint {
+                frequency: CheckpointingFrequency::Every(10),
+            })
+            .build();
+        assert!(executor.is_ok());
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_deserialize_config() {
+        let config: ExecutorConfig<f64> =
+            serde_json::from_str(r#"{"max_iters": 20, "timer": true}"#).unwrap();
+        assert_eq!(
+            config,
+            ExecutorConfig {
+                max_iters: Some(20),
+                timer: true,
+                ..ExecutorConfig::default()
+            }
+        );
+
+        let config: ExecutorConfig<f64> =
+            serde_json::from_str(r#"{"timeout": {"secs": 5, "nanos": 0}}"#).unwrap();
+        assert_eq!(config.max_time, Some(Duration::from_secs(5)));
+
+        let config: ExecutorConfig<f64> = serde_json::from_str(
+            r#"{
+                "stall_detection": {"window": 10, "tolerance": 1e-6},
+                "evaluation_budget": {"limits": {"cost_count": 100}, "weights": {}, "total": null},
+                "max_step_length": {"absolute": 10.0, "relative": 1.0},
+                "deterministic": 1729
+            }"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.stall_detection,
+            Some(StallDetection::new(10, 1e-6).unwrap())
+        );
+        assert_eq!(
+            config.evaluation_budget,
+            Some(EvaluationBudget::new().with_limit("cost_count", 100))
+        );
+        assert_eq!(
+            config.max_step_length,
+            Some(
+                MaxStepLength::new()
+                    .with_absolute(10.0)
+                    .unwrap()
+                    .with_relative(1.0)
+                    .unwrap()
+            )
+        );
+        assert_eq!(config.deterministic, Some(1729));
+
+        let config: ExecutorConfig<f64> =
+            serde_json::from_str(r#"{"stall_detection": {"window": 0, "tolerance": 1e-6}}"#)
+                .unwrap();
+        assert!(config.validate().is_err());
+    }
+}
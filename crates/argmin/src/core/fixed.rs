@@ -0,0 +1,361 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{CostFunction, Error, Gradient, Hessian};
+use std::ops::{Index, IndexMut};
+
+/// Keeps selected components of the parameter vector frozen at given values.
+///
+/// The solver only sees the reduced parameter vector consisting of the free components, in
+/// ascending order of their index. Before each evaluation, the full parameter vector is
+/// reassembled from the reduced parameter vector and the values of the fixed components.
+/// Gradients and Hessians of the wrapped problem are restricted to the free components.
+///
+/// All parameter vectors seen by the solver, including the initial parameter vector and the
+/// parameter vectors in the final state, are reduced parameter vectors. Use
+/// [`to_reduced`](`FixedParameters::to_reduced`) and [`to_full`](`FixedParameters::to_full`) to
+/// convert between both.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{CostFunction, Error, Executor, FixedParameters, Gradient, State};
+/// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+/// # use argmin::solver::quasinewton::LBFGS;
+/// # use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+/// # struct Model {}
+/// # impl CostFunction for Model {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(rosenbrock(p))
+/// #     }
+/// # }
+/// # impl Gradient for Model {
+/// #     type Param = Vec<f64>;
+/// #     type Gradient = Vec<f64>;
+/// #     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+/// #         Ok(rosenbrock_derivative(p))
+/// #     }
+/// # }
+/// # fn main() -> Result<(), Error> {
+/// let init_param = vec![-1.2, 1.0, 1.0];
+/// // Only fit the first and the last parameter
+/// let problem = FixedParameters::new(Model {}, init_param.clone()).fix(1, 1.0)?;
+/// let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+///     .run()?;
+/// let problem = res.problem.problem.as_ref().unwrap();
+/// let best = problem.to_full(res.state().get_best_param().unwrap())?;
+/// # assert_eq!(best.len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FixedParameters<O, P> {
+    /// Wrapped problem
+    op: O,
+    /// Full parameter vector which holds the values of the fixed components
+    values: P,
+    /// Whether the individual components are fixed
+    fixed: Vec<bool>,
+    /// Indices of the free components
+    free: Vec<usize>,
+}
+
+impl<O, P, F> FixedParameters<O, P>
+where
+    P: Clone + IndexMut<usize, Output = F> + FromIterator<F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: Copy,
+{
+    /// Constructs a new `FixedParameters` which wraps `op`.
+    ///
+    /// `values` determines the length of the full parameter vector. Initially, all components
+    /// are free.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::FixedParameters;
+    /// # struct UserDefinedProblem {}
+    /// let problem = FixedParameters::new(UserDefinedProblem {}, vec![1.0, 2.0, 3.0]);
+    /// ```
+    pub fn new(op: O, values: P) -> Self {
+        let n = values.into_iter().count();
+        FixedParameters {
+            op,
+            values,
+            fixed: vec![false; n],
+            free: (0..n).collect(),
+        }
+    }
+
+    /// Freezes component `index` at `value`.
+    ///
+    /// `index` must be smaller than the length of the full parameter vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, FixedParameters};
+    /// # struct UserDefinedProblem {}
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = FixedParameters::new(UserDefinedProblem {}, vec![1.0, 2.0, 3.0])
+    ///     .fix(0, 0.5)?
+    ///     .fix(2, 3.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fix(mut self, index: usize, value: F) -> Result<Self, Error> {
+        if index >= self.fixed.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`FixedParameters`: index {index} out of bounds for parameter vector of length {}.",
+                    self.fixed.len()
+                )
+            ));
+        }
+        self.values[index] = value;
+        self.fixed[index] = true;
+        self.free.retain(|&i| i != index);
+        Ok(self)
+    }
+
+    /// Returns the indices of the free components in ascending order.
+    pub fn free(&self) -> &[usize] {
+        &self.free
+    }
+
+    /// Extracts the free components of the full parameter vector `param`.
+    ///
+    /// This is useful in order to obtain the initial parameter vector for the solver.
+    pub fn to_reduced(&self, param: &P) -> Result<P, Error> {
+        self.check_len(param, self.fixed.len())?;
+        Ok(self.free.iter().map(|&i| param[i]).collect())
+    }
+
+    /// Reassembles the full parameter vector from the reduced parameter vector `param` and the
+    /// values of the fixed components.
+    pub fn to_full(&self, param: &P) -> Result<P, Error> {
+        self.check_len(param, self.free.len())?;
+        let mut full = self.values.clone();
+        for (k, &i) in self.free.iter().enumerate() {
+            full[i] = param[k];
+        }
+        Ok(full)
+    }
+
+    /// Returns an error if `param` is not of length `n`.
+    fn check_len(&self, param: &P, n: usize) -> Result<(), Error> {
+        let len = param.into_iter().count();
+        if len != n {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`FixedParameters`: expected parameter vector of length {n}, got {len}.")
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the wrapped problem
+    pub fn inner(&self) -> &O {
+        &self.op
+    }
+
+    /// Returns the wrapped problem
+    pub fn into_inner(self) -> O {
+        self.op
+    }
+}
+
+impl<O, P, F> CostFunction for FixedParameters<O, P>
+where
+    O: CostFunction<Param = P>,
+    P: Clone + IndexMut<usize, Output = F> + FromIterator<F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: Copy,
+{
+    type Param = P;
+    type Output = O::Output;
+
+    /// Evaluates the wrapped cost function at the full parameter vector.
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.op.cost(&self.to_full(param)?)
+    }
+
+    fn parallelize(&self) -> bool {
+        self.op.parallelize()
+    }
+}
+
+impl<O, P, G, F> Gradient for FixedParameters<O, P>
+where
+    O: Gradient<Param = P, Gradient = G>,
+    P: Clone + IndexMut<usize, Output = F> + FromIterator<F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    G: Index<usize, Output = F> + FromIterator<F>,
+    F: Copy,
+{
+    type Param = P;
+    type Gradient = G;
+
+    /// Returns the free components of the gradient at the full parameter vector.
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let grad = self.op.gradient(&self.to_full(param)?)?;
+        Ok(self.free.iter().map(|&i| grad[i]).collect())
+    }
+
+    fn parallelize(&self) -> bool {
+        Gradient::parallelize(&self.op)
+    }
+}
+
+impl<O, P, H, R, F> Hessian for FixedParameters<O, P>
+where
+    O: Hessian<Param = P, Hessian = H>,
+    P: Clone + IndexMut<usize, Output = F> + FromIterator<F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    H: Index<usize, Output = R> + FromIterator<R>,
+    R: Index<usize, Output = F> + FromIterator<F>,
+    F: Copy,
+{
+    type Param = P;
+    type Hessian = H;
+
+    /// Returns the rows and columns of the Hessian at the full parameter vector which belong to
+    /// the free components.
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let hessian = self.op.hessian(&self.to_full(param)?)?;
+        Ok(self
+            .free
+            .iter()
+            .map(|&i| self.free.iter().map(|&j| hessian[i][j]).collect())
+            .collect())
+    }
+
+    fn parallelize(&self) -> bool {
+        Hessian::parallelize(&self.op)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{check_derivatives, ArgminError, Executor, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::quasinewton::LBFGS;
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(rosenbrock_derivative(p))
+        }
+    }
+
+    impl Hessian for Rosenbrock {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(rosenbrock_hessian(p))
+        }
+    }
+
+    test_trait_impl!(fixed_parameters, FixedParameters<Vec<f64>, Vec<f64>>);
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert_error!(
+            FixedParameters::new(Rosenbrock {}, vec![1.0, 1.0]).fix(2, 1.0),
+            ArgminError,
+            "Invalid parameter: \"`FixedParameters`: index 2 out of bounds for parameter vector of length 2.\""
+        );
+        let problem = FixedParameters::new(Rosenbrock {}, vec![1.0, 1.0])
+            .fix(0, 1.0)
+            .unwrap();
+        assert_error!(
+            problem.cost(&vec![1.0, 1.0]),
+            ArgminError,
+            "Invalid parameter: \"`FixedParameters`: expected parameter vector of length 1, got 2.\""
+        );
+    }
+
+    #[test]
+    fn test_reduced_and_full() {
+        let problem = FixedParameters::new(Rosenbrock {}, vec![1.0, 2.0, 3.0, 4.0])
+            .fix(2, -3.0)
+            .unwrap()
+            .fix(0, -1.0)
+            .unwrap();
+        assert_eq!(problem.free(), &[1, 3]);
+        assert_eq!(
+            problem.to_reduced(&vec![5.0, 6.0, 7.0, 8.0]).unwrap(),
+            vec![6.0, 8.0]
+        );
+        assert_eq!(
+            problem.to_full(&vec![6.0, 8.0]).unwrap(),
+            vec![-1.0, 6.0, -3.0, 8.0]
+        );
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let problem = FixedParameters::new(Rosenbrock {}, vec![0.3, 0.0, -0.4, 0.0])
+            .fix(0, 0.3)
+            .unwrap()
+            .fix(2, -0.4)
+            .unwrap();
+        let param = vec![0.7, 1.1];
+        check_derivatives(&problem, &param).unwrap();
+        let grad = problem.gradient(&param).unwrap();
+        let full_grad = rosenbrock_derivative(&[0.3f64, 0.7, -0.4, 1.1]);
+        assert_eq!(grad, vec![full_grad[1], full_grad[3]]);
+        let hessian = problem.hessian(&param).unwrap();
+        let full_hessian = rosenbrock_hessian(&[0.3f64, 0.7, -0.4, 1.1]);
+        assert_eq!(
+            hessian[0][1].to_ne_bytes(),
+            full_hessian[1][3].to_ne_bytes()
+        );
+    }
+
+    #[test]
+    fn test_lbfgs() {
+        // With the first component fixed at 0.5, the minimum is at x[1] = 0.25
+        let problem = FixedParameters::new(Rosenbrock {}, vec![0.5, -1.0])
+            .fix(0, 0.5)
+            .unwrap();
+        let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+        let res = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![-1.0]).max_iters(50))
+            .run()
+            .unwrap();
+        let problem = res.problem.problem.as_ref().unwrap();
+        let best = problem
+            .to_full(res.state().get_best_param().unwrap())
+            .unwrap();
+        assert_eq!(best[0].to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_relative_eq!(best[1], 0.25, epsilon = 1e-6);
+    }
+}
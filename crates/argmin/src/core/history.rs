@@ -0,0 +1,303 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::time::Duration;
+use crate::core::{ArgminFloat, IterState, State};
+use argmin_math::ArgminL2Norm;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Values recorded for a single iteration.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct HistoryEntry<P, F> {
+    /// Iteration number
+    pub iter: u64,
+    /// Cost function value of the current parameter vector
+    pub cost: F,
+    /// Best cost function value so far
+    pub best_cost: F,
+    /// Norm of the gradient (only available if an extractor was provided)
+    pub gradient_norm: Option<F>,
+    /// Current parameter vector (only available if recording of parameters is enabled)
+    pub param: Option<P>,
+    /// Time elapsed since the start of the run (only available if the timer is enabled)
+    pub time: Option<Duration>,
+}
+
+/// Opt-in record of the progress of an optimization run.
+///
+/// If enabled via [`Executor::history`](`crate::core::Executor::history`), the `Executor` stores
+/// one [`HistoryEntry`] per iteration and returns the history as part of the
+/// [`OptimizationResult`](`crate::core::OptimizationResult`). This is useful for plotting
+/// convergence curves without writing a custom observer.
+///
+/// By default only the iteration number, the current and best cost and the elapsed time are
+/// recorded. Since the gradient is not accessible for arbitrary states, recording the gradient norm
+/// requires an extractor function (see [`History::gradient_norm`]); for
+/// [`IterState`](`crate::core::IterState`), [`History::with_gradient_norm`] provides one which
+/// computes the L2 norm of the current gradient. Parameter vectors are only stored if
+/// [`History::params`] is enabled, because this may require a lot of memory.
+pub struct History<I: State> {
+    /// Recorded entries
+    entries: Vec<HistoryEntry<I::Param, I::Float>>,
+    /// Copies the parameter vector from the state (only set if parameters are stored)
+    params: Option<fn(&I) -> Option<I::Param>>,
+    /// Computes the gradient norm from the state
+    gradient_norm: Option<fn(&I) -> Option<I::Float>>,
+}
+
+impl<I: State> History<I> {
+    /// Constructs a new, empty `History`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{History, IterState};
+    /// let history: History<IterState<Vec<f64>, (), (), (), (), f64>> = History::new();
+    /// # assert!(history.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        History {
+            entries: vec![],
+            params: None,
+            gradient_norm: None,
+        }
+    }
+
+    /// Sets a function which computes the gradient norm from the state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{History, IterState};
+    /// # use argmin_math::ArgminL2Norm;
+    /// let history: History<IterState<Vec<f64>, Vec<f64>, (), (), (), f64>> =
+    ///     History::new().gradient_norm(|state| state.get_gradient().map(|g| g.l2_norm()));
+    /// ```
+    #[must_use]
+    pub fn gradient_norm(mut self, extractor: fn(&I) -> Option<I::Float>) -> Self {
+        self.gradient_norm = Some(extractor);
+        self
+    }
+
+    /// Records the current iteration.
+    pub(crate) fn record(&mut self, state: &I) {
+        self.entries.push(HistoryEntry {
+            iter: state.get_iter(),
+            cost: state.get_cost(),
+            best_cost: state.get_best_cost(),
+            gradient_norm: self.gradient_norm.and_then(|extractor| extractor(state)),
+            param: self.params.and_then(|extractor| extractor(state)),
+            time: state.get_time(),
+        });
+    }
+
+    /// Returns the recorded entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{History, IterState};
+    /// # let history: History<IterState<Vec<f64>, (), (), (), (), f64>> = History::new();
+    /// for entry in history.entries() {
+    ///     println!("{}: {}", entry.iter, entry.cost);
+    /// }
+    /// ```
+    pub fn entries(&self) -> &[HistoryEntry<I::Param, I::Float>] {
+        &self.entries
+    }
+
+    /// Consumes the `History` and returns the recorded entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{History, IterState};
+    /// # let history: History<IterState<Vec<f64>, (), (), (), (), f64>> = History::new();
+    /// let entries = history.into_entries();
+    /// # assert!(entries.is_empty());
+    /// ```
+    pub fn into_entries(self) -> Vec<HistoryEntry<I::Param, I::Float>> {
+        self.entries
+    }
+
+    /// Returns the cost function values of all recorded iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{History, IterState};
+    /// # let history: History<IterState<Vec<f64>, (), (), (), (), f64>> = History::new();
+    /// let costs: Vec<f64> = history.costs();
+    /// ```
+    pub fn costs(&self) -> Vec<I::Float> {
+        self.entries.iter().map(|entry| entry.cost).collect()
+    }
+
+    /// Returns the number of recorded iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{History, IterState};
+    /// # let history: History<IterState<Vec<f64>, (), (), (), (), f64>> = History::new();
+    /// let num_entries = history.len();
+    /// # assert_eq!(num_entries, 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no iteration was recorded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{History, IterState};
+    /// # let history: History<IterState<Vec<f64>, (), (), (), (), f64>> = History::new();
+    /// let is_empty = history.is_empty();
+    /// # assert!(is_empty);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<I> History<I>
+where
+    I: State,
+    I::Param: Clone,
+{
+    /// Enables or disables storing the parameter vector of each iteration (default: disabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{History, IterState};
+    /// let history: History<IterState<Vec<f64>, (), (), (), (), f64>> = History::new().params(true);
+    /// ```
+    #[must_use]
+    pub fn params(mut self, params: bool) -> Self {
+        self.params = if params {
+            Some(|state| state.get_param().cloned())
+        } else {
+            None
+        };
+        self
+    }
+}
+
+impl<P, G, J, H, R, F> History<IterState<P, G, J, H, R, F>>
+where
+    P: Clone,
+    G: ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    /// Records the L2 norm of the gradient stored in [`IterState`](`crate::core::IterState`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{History, IterState};
+    /// let history: History<IterState<Vec<f64>, Vec<f64>, (), (), (), f64>> =
+    ///     History::new().with_gradient_norm();
+    /// ```
+    #[must_use]
+    pub fn with_gradient_norm(self) -> Self {
+        self.gradient_norm(|state| state.get_gradient().map(|grad| grad.l2_norm()))
+    }
+}
+
+impl<I: State> Default for History<I> {
+    fn default() -> Self {
+        History::new()
+    }
+}
+
+impl<I> Clone for History<I>
+where
+    I: State,
+    I::Param: Clone,
+{
+    fn clone(&self) -> Self {
+        History {
+            entries: self.entries.clone(),
+            params: self.params,
+            gradient_norm: self.gradient_norm,
+        }
+    }
+}
+
+impl<I> fmt::Debug for History<I>
+where
+    I: State,
+    I::Param: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("History")
+            .field("entries", &self.entries)
+            .field("params", &self.params.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    type TestState = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+
+    #[test]
+    fn test_record() {
+        let mut history: History<TestState> = History::new();
+        let state = TestState::new()
+            .param(vec![1.0, 2.0])
+            .gradient(vec![3.0, 4.0])
+            .cost(2.0);
+        history.record(&state);
+
+        assert_eq!(
+            history.entries(),
+            &[HistoryEntry {
+                iter: 0,
+                cost: 2.0,
+                best_cost: f64::INFINITY,
+                gradient_norm: None,
+                param: None,
+                time: state.get_time(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_record_gradient_norm_and_params() {
+        let mut history: History<TestState> = History::new().params(true).with_gradient_norm();
+        let mut state = TestState::new()
+            .param(vec![1.0, 2.0])
+            .gradient(vec![3.0, 4.0])
+            .cost(2.0);
+        state.update();
+        history.record(&state);
+        state.increment_iter();
+        history.record(&state.cost(1.0));
+
+        let entries = history.into_entries();
+        assert_eq!(entries.len(), 2);
+        assert_relative_eq!(
+            entries[0].gradient_norm.unwrap(),
+            5.0,
+            epsilon = f64::EPSILON
+        );
+        assert_eq!(entries[0].param, Some(vec![1.0, 2.0]));
+        assert_relative_eq!(entries[0].best_cost, 2.0, epsilon = f64::EPSILON);
+        assert_eq!(entries[1].iter, 1);
+        assert_relative_eq!(entries[1].cost, 1.0, epsilon = f64::EPSILON);
+    }
+}
@@ -82,6 +82,16 @@ macro_rules! float {
     };
 }
 
+/// Enters a `tracing` span at the given level which is exited at the end of the current scope.
+///
+/// Does nothing unless the `tracing` feature is enabled.
+macro_rules! enter_span {
+    ($level:ident, $($span:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $($span)*).entered();
+    };
+}
+
 /// Creates the `bulk_X` methods.
 #[macro_export]
 macro_rules! bulk {
@@ -99,7 +109,10 @@ macro_rules! bulk {
                 "in cases where it is preferable to parallelize only certain parts. ",
                 "Note that even if `parallelize` is set to false, the parameter vectors and the ",
                 "problem are still required to be `Send` and `Sync`. Those bounds are linked to ",
-                "the `rayon` feature. This method can be overwritten.",
+                "the `rayon` feature. This method can be overwritten, for instance if the ",
+                "problem can evaluate many parameter vectors at once in a vectorized way or on a ",
+                "GPU. Population-based solvers and the finite-difference adapters in ",
+                "`argmin::core::numdiff` evaluate all their parameter vectors with a single call.",
             )]
             fn [<bulk_ $method_name>]<P>(&self, params: &[P]) -> Result<Vec<$output>, Error>
             where
@@ -110,7 +123,9 @@ macro_rules! bulk {
                 #[cfg(feature = "rayon")]
                 {
                     if self.parallelize() {
-                        params.par_iter().map(|p| self.$method_name(p.borrow())).collect()
+                        $crate::core::parallelization::install(|| {
+                            params.par_iter().map(|p| self.$method_name(p.borrow())).collect()
+                        })
                     } else {
                         params.iter().map(|p| self.$method_name(p.borrow())).collect()
                     }
@@ -0,0 +1,218 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use argmin_math::ArgminL2Norm;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum length of the step taken in a single iteration.
+///
+/// The maximum step length is the minimum of an absolute limit and a limit relative to the norm
+/// of the current parameter vector `x`:
+///
+/// `min(absolute, relative * max(||x||, 1))`
+///
+/// The norm of `x` is bounded from below by 1 such that steps away from the origin are possible.
+/// Both limits are infinite by default.
+///
+/// The limit is set via
+/// [`Executor::max_step_length`](`crate::core::Executor::max_step_length`) and is stored in the
+/// [`Problem`](`crate::core::Problem`). Quasi-Newton, Newton-CG, Gauss-Newton and nonlinear CG
+/// solvers pass it on to their line search via
+/// [`LineSearch::max_step_length`](`crate::core::LineSearch::max_step_length`), which limits the
+/// step lengths it tries accordingly. Trust region methods limit the radius of the trust
+/// region. Other solvers ignore it.
+///
+/// Bad initial (inverse) Hessian approximations can lead to huge steps in early iterations, which
+/// may move the parameter vector into regions where the cost function is not defined or
+/// overflows. Limiting the step length avoids this.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, MaxStepLength};
+/// # fn main() -> Result<(), Error> {
+/// // Steps are at most 10 long and at most 100% of the norm of the parameter vector.
+/// let max_step_length = MaxStepLength::new().with_absolute(10.0)?.with_relative(1.0)?;
+/// # assert_eq!(max_step_length.max_step_length(&vec![0.0f64, 0.0]), 1.0);
+/// # assert_eq!(max_step_length.max_step_length(&vec![3.0f64, 4.0]), 5.0);
+/// # assert_eq!(max_step_length.max_step_length(&vec![30.0f64, 40.0]), 10.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MaxStepLength {
+    /// Absolute limit
+    absolute: f64,
+    /// Limit relative to the norm of the parameter vector
+    relative: f64,
+}
+
+impl Default for MaxStepLength {
+    fn default() -> Self {
+        MaxStepLength {
+            absolute: f64::INFINITY,
+            relative: f64::INFINITY,
+        }
+    }
+}
+
+impl MaxStepLength {
+    /// Constructs a new maximum step length without any limits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::MaxStepLength;
+    /// let max_step_length = MaxStepLength::new();
+    /// ```
+    pub fn new() -> Self {
+        MaxStepLength::default()
+    }
+
+    /// Sets the absolute limit of the step length.
+    ///
+    /// Must be positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, MaxStepLength};
+    /// # fn main() -> Result<(), Error> {
+    /// let max_step_length = MaxStepLength::new().with_absolute(10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_absolute(mut self, absolute: f64) -> Result<Self, Error> {
+        if absolute.is_nan() || absolute <= 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MaxStepLength`: absolute limit must be > 0."
+            ));
+        }
+        self.absolute = absolute;
+        Ok(self)
+    }
+
+    /// Sets the limit of the step length relative to the norm of the parameter vector (which is
+    /// bounded from below by 1).
+    ///
+    /// Must be positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, MaxStepLength};
+    /// # fn main() -> Result<(), Error> {
+    /// let max_step_length = MaxStepLength::new().with_relative(100.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_relative(mut self, relative: f64) -> Result<Self, Error> {
+        if relative.is_nan() || relative <= 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MaxStepLength`: relative limit must be > 0."
+            ));
+        }
+        self.relative = relative;
+        Ok(self)
+    }
+
+    /// Returns an error if one of the limits is invalid (for instance after deserialization).
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        MaxStepLength::new()
+            .with_absolute(self.absolute)?
+            .with_relative(self.relative)
+            .map(|_| ())
+    }
+
+    /// Returns the maximum step length at the parameter vector `param`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, MaxStepLength};
+    /// # fn main() -> Result<(), Error> {
+    /// let max_step_length = MaxStepLength::new().with_relative(0.5)?;
+    /// assert_eq!(max_step_length.max_step_length(&vec![3.0f64, 4.0]), 2.5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_step_length<P, F>(&self, param: &P) -> F
+    where
+        P: ArgminL2Norm<F>,
+        F: ArgminFloat,
+    {
+        let absolute = F::from_f64(self.absolute).unwrap_or_else(F::infinity);
+        if self.relative.is_infinite() {
+            return absolute;
+        }
+        let relative = F::from_f64(self.relative).unwrap_or_else(F::infinity);
+        absolute.min(relative * param.l2_norm().max(float!(1.0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+
+    send_sync_test!(max_step_length, MaxStepLength);
+
+    #[test]
+    fn test_new() {
+        let MaxStepLength { absolute, relative } = MaxStepLength::new();
+        assert!(absolute.is_infinite());
+        assert!(relative.is_infinite());
+        assert!(MaxStepLength::new()
+            .max_step_length::<_, f64>(&vec![1e300, 1e300])
+            .is_infinite());
+    }
+
+    #[test]
+    fn test_max_step_length() {
+        let max_step_length = MaxStepLength::new()
+            .with_absolute(10.0)
+            .unwrap()
+            .with_relative(2.0)
+            .unwrap();
+        for (param, expected) in [
+            (vec![0.0f64, 0.0], 2.0f64),
+            (vec![0.3, 0.4], 2.0),
+            (vec![3.0, 4.0], 10.0),
+            (vec![-1.5, 2.0], 5.0),
+            (vec![30.0, 40.0], 10.0),
+        ] {
+            let res: f64 = max_step_length.max_step_length(&param);
+            assert_eq!(res.to_ne_bytes(), expected.to_ne_bytes());
+        }
+
+        // Only an absolute limit
+        let max_step_length = MaxStepLength::new().with_absolute(3.0).unwrap();
+        let res: f32 = max_step_length.max_step_length(&vec![1e10f32]);
+        assert_eq!(res.to_ne_bytes(), 3.0f32.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_invalid_limits() {
+        for limit in [0.0, -1.0, f64::NAN] {
+            assert_error!(
+                MaxStepLength::new().with_absolute(limit),
+                ArgminError,
+                "Invalid parameter: \"`MaxStepLength`: absolute limit must be > 0.\""
+            );
+            assert_error!(
+                MaxStepLength::new().with_relative(limit),
+                ArgminError,
+                "Invalid parameter: \"`MaxStepLength`: relative limit must be > 0.\""
+            );
+        }
+    }
+}
@@ -0,0 +1,507 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, CostFunction, Error, Gradient, Hessian};
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Cached result of an evaluation
+struct Entry {
+    /// Hash of `key`
+    hash: u64,
+    /// Components of the parameter vector
+    key: Vec<f64>,
+    /// Result of the evaluation
+    value: Box<dyn Any + Send>,
+}
+
+/// Least recently used results of one kind of evaluation, most recent first
+#[derive(Default)]
+struct Cache {
+    entries: VecDeque<Entry>,
+}
+
+/// Caches the results of cost function, gradient and Hessian evaluations.
+///
+/// Line searches and solvers frequently evaluate the same parameter vector more than once, for
+/// instance at the end of a line search and at the beginning of the next iteration. `Memoized`
+/// wraps a problem and remembers the `capacity` most recent results (default: `4`) of `cost`,
+/// `gradient` and `hessian` separately. Repeated evaluations at the same parameter vector are
+/// answered from the cache without calling the wrapped problem.
+///
+/// By default, parameter vectors are looked up via a hash of their components and must match
+/// exactly. With [`with_tolerance`](`Memoized::with_tolerance`), parameter vectors `x` and `y`
+/// match if `|x_i - y_i| <= tol * max(|y_i|, 1)` for all components; in this case the cache is
+/// searched linearly.
+///
+/// [`Problem`](`crate::core::Problem`) counts all calls of the wrapped functions, including those
+/// answered from the cache. The number of cache hits and misses is available via
+/// [`hit_count`](`Memoized::hit_count`) and [`miss_count`](`Memoized::miss_count`).
+///
+/// Cloning a `Memoized` clones the wrapped problem, but not the cache.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{CostFunction, Error, Executor, Gradient, Memoized, State};
+/// # use argmin::solver::gradientdescent::SteepestDescent;
+/// # use argmin::solver::linesearch::{condition::ArmijoCondition, BacktrackingLineSearch};
+/// # use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+/// struct Rosenbrock {}
+///
+/// impl CostFunction for Rosenbrock {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(rosenbrock(p))
+///     }
+/// }
+///
+/// impl Gradient for Rosenbrock {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+///         Ok(rosenbrock_derivative(p))
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let problem = Memoized::new(Rosenbrock {}).with_capacity(8)?;
+/// let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(1e-4)?);
+/// let solver = SteepestDescent::new(linesearch);
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+///     .run()?;
+///
+/// let hits = res.problem.problem.as_ref().unwrap().hit_count();
+/// # assert!(hits > 0);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Memoized<O> {
+    /// Wrapped problem
+    op: O,
+    /// Number of results kept per kind of evaluation
+    capacity: usize,
+    /// Relative tolerance for matching parameter vectors
+    tolerance: f64,
+    /// Results of `cost`
+    cost_cache: Mutex<Cache>,
+    /// Results of `gradient`
+    gradient_cache: Mutex<Cache>,
+    /// Results of `hessian`
+    hessian_cache: Mutex<Cache>,
+    /// Number of evaluations answered from the cache
+    hits: AtomicU64,
+    /// Number of evaluations forwarded to the wrapped problem
+    misses: AtomicU64,
+}
+
+impl<O> Memoized<O> {
+    /// Constructs a new `Memoized` which wraps `op`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Memoized;
+    /// # struct UserDefinedProblem {}
+    /// let problem = Memoized::new(UserDefinedProblem {});
+    /// ```
+    pub fn new(op: O) -> Self {
+        Memoized {
+            op,
+            capacity: 4,
+            tolerance: 0.0,
+            cost_cache: Mutex::new(Cache::default()),
+            gradient_cache: Mutex::new(Cache::default()),
+            hessian_cache: Mutex::new(Cache::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets the number of results kept for each of `cost`, `gradient` and `hessian`
+    /// (default: `4`).
+    ///
+    /// Must be larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Memoized};
+    /// # struct UserDefinedProblem {}
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = Memoized::new(UserDefinedProblem {}).with_capacity(16)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_capacity(mut self, capacity: usize) -> Result<Self, Error> {
+        if capacity == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Memoized`: capacity must be > 0."
+            ));
+        }
+        self.capacity = capacity;
+        Ok(self)
+    }
+
+    /// Sets the relative tolerance for matching parameter vectors (default: `0`, i.e. exact
+    /// matches only).
+    ///
+    /// Must be non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Memoized};
+    /// # struct UserDefinedProblem {}
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = Memoized::new(UserDefinedProblem {}).with_tolerance(1e-12)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tolerance: f64) -> Result<Self, Error> {
+        if tolerance.is_nan() || tolerance < 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Memoized`: tolerance must be >= 0."
+            ));
+        }
+        self.tolerance = tolerance;
+        Ok(self)
+    }
+
+    /// Returns the number of evaluations answered from the cache.
+    pub fn hit_count(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of evaluations forwarded to the wrapped problem.
+    pub fn miss_count(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Removes all cached results.
+    ///
+    /// Required if the wrapped problem changes, for instance after modifying it via
+    /// [`Problem::problem`](`crate::core::Problem::problem`).
+    pub fn clear(&self) {
+        for cache in [&self.cost_cache, &self.gradient_cache, &self.hessian_cache] {
+            cache.lock().unwrap().entries.clear();
+        }
+    }
+
+    /// Returns a reference to the wrapped problem.
+    pub fn inner(&self) -> &O {
+        &self.op
+    }
+
+    /// Returns the wrapped problem.
+    pub fn into_inner(self) -> O {
+        self.op
+    }
+
+    /// Returns the cached result for `param` or computes, caches and returns it via `eval`.
+    fn memoize<P, F, V>(
+        &self,
+        cache: &Mutex<Cache>,
+        param: &P,
+        eval: impl FnOnce(&P) -> Result<V, Error>,
+    ) -> Result<V, Error>
+    where
+        for<'a> &'a P: IntoIterator<Item = &'a F>,
+        F: ArgminFloat,
+        V: Clone + Send + 'static,
+    {
+        let key: Vec<f64> = param
+            .into_iter()
+            .map(|x| {
+                let x = x.to_f64().unwrap();
+                // Treat -0.0 and 0.0 alike
+                if x == 0.0 {
+                    0.0
+                } else {
+                    x
+                }
+            })
+            .collect();
+        let mut hasher = DefaultHasher::new();
+        for x in key.iter() {
+            x.to_bits().hash(&mut hasher);
+        }
+        let hash = hasher.finish();
+
+        {
+            let mut cache = cache.lock().unwrap();
+            let position = cache.entries.iter().position(|entry| {
+                if self.tolerance > 0.0 {
+                    entry.key.len() == key.len()
+                        && entry
+                            .key
+                            .iter()
+                            .zip(key.iter())
+                            .all(|(a, b)| (a - b).abs() <= self.tolerance * a.abs().max(1.0))
+                } else {
+                    entry.hash == hash && entry.key == key
+                }
+            });
+            if let Some(position) = position {
+                let entry = cache.entries.remove(position).unwrap();
+                let value = entry.value.downcast_ref::<V>().cloned();
+                cache.entries.push_front(entry);
+                if let Some(value) = value {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return Ok(value);
+                }
+            }
+        }
+
+        // The lock is not held during the evaluation, which may itself be parallelized.
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value = eval(param)?;
+        let mut cache = cache.lock().unwrap();
+        cache.entries.push_front(Entry {
+            hash,
+            key,
+            value: Box::new(value.clone()),
+        });
+        cache.entries.truncate(self.capacity);
+        Ok(value)
+    }
+}
+
+impl<O: Clone> Clone for Memoized<O> {
+    fn clone(&self) -> Self {
+        Memoized {
+            op: self.op.clone(),
+            capacity: self.capacity,
+            tolerance: self.tolerance,
+            cost_cache: Mutex::new(Cache::default()),
+            gradient_cache: Mutex::new(Cache::default()),
+            hessian_cache: Mutex::new(Cache::default()),
+            hits: AtomicU64::new(self.hit_count()),
+            misses: AtomicU64::new(self.miss_count()),
+        }
+    }
+}
+
+impl<O: std::fmt::Debug> std::fmt::Debug for Memoized<O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Memoized")
+            .field("op", &self.op)
+            .field("capacity", &self.capacity)
+            .field("tolerance", &self.tolerance)
+            .field("hits", &self.hit_count())
+            .field("misses", &self.miss_count())
+            .finish()
+    }
+}
+
+impl<O, P, F> CostFunction for Memoized<O>
+where
+    O: CostFunction<Param = P>,
+    O::Output: Clone + Send + 'static,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = O::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.memoize(&self.cost_cache, param, |p| self.op.cost(p))
+    }
+
+    fn parallelize(&self) -> bool {
+        self.op.parallelize()
+    }
+}
+
+impl<O, P, F> Gradient for Memoized<O>
+where
+    O: Gradient<Param = P>,
+    O::Gradient: Clone + Send + 'static,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Gradient = O::Gradient;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.memoize(&self.gradient_cache, param, |p| self.op.gradient(p))
+    }
+
+    fn parallelize(&self) -> bool {
+        self.op.parallelize()
+    }
+}
+
+impl<O, P, F> Hessian for Memoized<O>
+where
+    O: Hessian<Param = P>,
+    O::Hessian: Clone + Send + 'static,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Hessian = O::Hessian;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        self.memoize(&self.hessian_cache, param, |p| self.op.hessian(p))
+    }
+
+    fn parallelize(&self) -> bool {
+        self.op.parallelize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::gradientdescent::SteepestDescent;
+    use crate::solver::linesearch::{condition::ArmijoCondition, BacktrackingLineSearch};
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+
+    #[derive(Clone, Default)]
+    struct Rosenbrock {
+        /// Number of evaluations of the cost function
+        cost_evals: std::sync::Arc<AtomicU64>,
+    }
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            self.cost_evals.fetch_add(1, Ordering::Relaxed);
+            Ok(rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(rosenbrock_derivative(p))
+        }
+    }
+
+    impl Hessian for Rosenbrock {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(rosenbrock_hessian(p))
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert_error!(
+            Memoized::new(Rosenbrock::default()).with_capacity(0),
+            ArgminError,
+            "Invalid parameter: \"`Memoized`: capacity must be > 0.\""
+        );
+        assert_error!(
+            Memoized::new(Rosenbrock::default()).with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`Memoized`: tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_exact() {
+        let problem = Memoized::new(Rosenbrock::default())
+            .with_capacity(2)
+            .unwrap();
+        let (a, b, c) = (vec![1.0, 2.0], vec![0.0, 1.0], vec![-0.0, 1.0]);
+        assert_relative_eq!(problem.cost(&a).unwrap(), 100.0);
+        assert_relative_eq!(problem.cost(&b).unwrap(), 101.0);
+        // -0.0 and 0.0 match
+        assert_relative_eq!(problem.cost(&c).unwrap(), 101.0);
+        assert_relative_eq!(problem.cost(&a).unwrap(), 100.0);
+        assert_eq!(problem.inner().cost_evals.load(Ordering::Relaxed), 2);
+        assert_eq!(problem.hit_count(), 2);
+        assert_eq!(problem.miss_count(), 2);
+
+        // Least recently used entry `b` is evicted
+        problem.cost(&vec![3.0, 2.0]).unwrap();
+        problem.cost(&a).unwrap();
+        problem.cost(&b).unwrap();
+        assert_eq!(problem.inner().cost_evals.load(Ordering::Relaxed), 4);
+
+        // Slightly different parameter vectors do not match
+        problem.cost(&vec![1.0 + 1e-15, 2.0]).unwrap();
+        assert_eq!(problem.inner().cost_evals.load(Ordering::Relaxed), 5);
+
+        problem.clear();
+        problem.cost(&b).unwrap();
+        assert_eq!(problem.inner().cost_evals.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let problem = Memoized::new(Rosenbrock::default())
+            .with_tolerance(1e-10)
+            .unwrap();
+        let cost = problem.cost(&vec![1.0, 2.0]).unwrap();
+        assert_eq!(
+            problem.cost(&vec![1.0 + 1e-12, 2.0]).unwrap().to_ne_bytes(),
+            cost.to_ne_bytes()
+        );
+        problem.cost(&vec![1.0 + 1e-8, 2.0]).unwrap();
+        assert_eq!(problem.inner().cost_evals.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_separate_caches() {
+        let problem = Memoized::new(Rosenbrock::default());
+        let x = vec![-1.2, 1.0];
+        let grad = problem.gradient(&x).unwrap();
+        let hessian = problem.hessian(&x).unwrap();
+        assert_eq!(problem.miss_count(), 2);
+        assert_eq!(problem.gradient(&x).unwrap(), grad);
+        assert_eq!(problem.hessian(&x).unwrap(), hessian);
+        assert_eq!(problem.hit_count(), 2);
+        problem.cost(&x).unwrap();
+        assert_eq!(problem.miss_count(), 3);
+    }
+
+    #[test]
+    fn test_steepest_descent() {
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+        let solver = SteepestDescent::new(linesearch);
+        let res = Executor::new(Memoized::new(Rosenbrock::default()), solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(10))
+            .run()
+            .unwrap();
+
+        // The cost at the end of each line search is evaluated again at the beginning of the next
+        // iteration
+        let problem = res.problem.problem.as_ref().unwrap();
+        let counts = &res.problem.counts;
+        assert_eq!(problem.hit_count(), 9);
+        assert_eq!(
+            problem.hit_count() + problem.miss_count(),
+            counts["cost_count"] + counts["gradient_count"]
+        );
+        assert_eq!(
+            problem.inner().cost_evals.load(Ordering::Relaxed),
+            counts["cost_count"] - 9
+        );
+    }
+}
@@ -13,42 +13,118 @@
 /// Macros
 #[macro_use]
 pub mod macros;
+/// Asynchronous evaluation of problems
+mod asynceval;
+/// Automatic differentiation
+#[cfg(feature = "autodiff")]
+pub mod autodiff;
+/// Dispatching evaluations to external backends
+mod backend;
+/// Budgets of function evaluations
+mod budget;
+/// Cooperative cancellation of optimization runs
+mod cancellation;
 pub mod checkpointing;
+/// Checking analytic derivatives against finite differences
+mod derivativecheck;
+/// Early stopping based on a validation cost function
+mod earlystopping;
 /// Error handling
 mod errors;
 /// Executor
 mod executor;
+/// Builder for the executor with validated configuration
+mod executorbuilder;
+/// Fixed components of the parameter vector
+mod fixed;
 /// Trait alias for float types
 mod float;
+/// Recording of the history of an optimization run
+mod history;
 /// Key value data structure
 mod kv;
+/// Maximum step length per iteration
+mod maxstep;
+/// Caching of evaluations
+mod memoize;
+/// Averaging of noisy cost functions
+mod noiseaveraging;
+/// Detection of non-finite values
+mod nonfinite;
+/// Finite-difference derivatives
+pub mod numdiff;
 pub mod observers;
 /// Trait alias for `Send` and `Sync`
-mod parallelization;
+pub(crate) mod parallelization;
 /// Traits and structs for defining and handling optimization problems
 mod problem;
+/// Handling of recoverable errors
+mod recovery;
+/// Structured summary of an optimization run
+mod report;
 /// Definition of the return type of the solvers
 mod result;
+/// Diagonal scaling of the parameter vector
+mod scaled;
 /// `Solver` trait
 mod solver;
+/// Detection of stalled progress
+mod stalldetection;
 /// iteration state
 mod state;
+/// Serde-free binary encoding of states
+mod statecodec;
+/// Adapter for problems with mutable state
+mod stateful;
 /// Definition of termination reasons
 mod termination;
 /// Convenience utilities for testing
 pub mod test_utils;
+/// Wall-clock time
+pub mod time;
+/// Convergence tolerances shared by solvers
+mod tolerance;
+/// Reparameterization of problems
+mod transform;
 
 pub use crate::solver::conjugategradient::beta::NLCGBetaUpdate;
 pub use crate::solver::linesearch::LineSearch;
 pub use crate::solver::trustregion::TrustRegionRadius;
 pub use anyhow::Error;
+pub use asynceval::{block_on, AsyncCostFunction, AsyncGradient, Awaited};
+pub use backend::{Distributed, EvaluationBackend};
+pub use budget::EvaluationBudget;
+pub use cancellation::CancellationToken;
+pub use derivativecheck::{
+    check_derivatives, DerivativeCheck, DerivativeComparison, DerivativeReport,
+};
+pub use earlystopping::EarlyStopping;
 pub use errors::ArgminError;
 pub use executor::Executor;
+pub use executorbuilder::{ExecutorBuilder, ExecutorConfig};
+pub use fixed::FixedParameters;
 pub use float::ArgminFloat;
+pub use history::{History, HistoryEntry};
 pub use kv::{KvValue, KV};
+pub use maxstep::MaxStepLength;
+pub use memoize::Memoized;
+pub use noiseaveraging::NoiseAveraged;
+pub use nonfinite::{NonFiniteCheck, NonFinitePolicy};
+pub use numdiff::{
+    ComplexCostFunction, WithCentralDiff, WithComplexStep, WithForwardDiff, WithHessianFromCost,
+    WithHessianFromGradient,
+};
 pub use parallelization::{SendAlias, SyncAlias};
 pub use problem::{CostFunction, Gradient, Hessian, Jacobian, LinearProgram, Operator, Problem};
+pub use recovery::{RecoveryAction, RecoveryPolicy};
+pub use report::Report;
 pub use result::OptimizationResult;
+pub use scaled::Scaled;
 pub use solver::Solver;
-pub use state::{IterState, LinearProgramState, PopulationState, State};
+pub use stalldetection::StallDetection;
+pub use state::{DerivativeFreeState, IterState, LinearProgramState, PopulationState, State};
+pub use statecodec::StateCodec;
+pub use stateful::{CostFunctionMut, GradientMut, HessianMut, OperatorMut, Stateful};
 pub use termination::{TerminationReason, TerminationStatus};
+pub use tolerance::ToleranceConfig;
+pub use transform::Transform;
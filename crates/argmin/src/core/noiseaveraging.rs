@@ -0,0 +1,347 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, CostFunction, Error, KV};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Averages a noisy cost function over several evaluations.
+///
+/// Simulation-based objectives return a different value each time they are evaluated at the same
+/// parameter vector. Deterministic solvers such as Nelder-Mead are easily misled by this noise.
+/// `NoiseAveraged` evaluates the wrapped cost function `samples` times (default: `10`) per call
+/// of `cost` and returns the mean, which reduces the standard deviation of the noise by a factor
+/// of `sqrt(samples)`.
+///
+/// If enabled via [`track_variance`](`NoiseAveraged::track_variance`), the sample variance of
+/// the most recent evaluation is kept and available via [`variance`](`NoiseAveraged::variance`)
+/// and [`kv`](`NoiseAveraged::kv`).
+///
+/// The number of samples can be adapted between iterations via
+/// [`set_samples`](`NoiseAveraged::set_samples`), for instance from an iteration hook (see
+/// [`Executor::add_iteration_hook`](`crate::core::Executor::add_iteration_hook`)) in order to
+/// increase the accuracy as the solver approaches the optimum.
+///
+/// [`Problem`](`crate::core::Problem`) counts one evaluation per call of `cost`; the number of
+/// evaluations of the wrapped cost function is available via
+/// [`cost_count`](`NoiseAveraged::cost_count`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{CostFunction, Error, Executor, NoiseAveraged, State, TerminationStatus};
+/// # use argmin::solver::neldermead::NelderMead;
+/// # struct Simulation {}
+/// # impl CostFunction for Simulation {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(p[0].powi(2) + p[1].powi(2))
+/// #     }
+/// # }
+/// # fn main() -> Result<(), Error> {
+/// let problem = NoiseAveraged::new(Simulation {})
+///     .with_samples(20)?
+///     .track_variance(true);
+/// let solver = NelderMead::new(vec![vec![1.0, 1.0], vec![1.5, 1.0], vec![1.0, 1.5]]);
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.max_iters(100))
+///     // Use more samples in later iterations
+///     .add_iteration_hook(|state, problem| {
+///         if state.get_iter() == 50 {
+///             problem.problem.as_mut().unwrap().set_samples(100)?;
+///         }
+///         Ok(TerminationStatus::NotTerminated)
+///     })
+///     .run()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NoiseAveraged<O> {
+    /// Wrapped problem
+    op: O,
+    /// Number of evaluations per call of `cost`
+    samples: u64,
+    /// Whether the sample variance is computed
+    track_variance: bool,
+    /// Number of samples and sample variance of the most recent evaluation
+    variance: Mutex<Option<(u64, f64)>>,
+    /// Number of evaluations of the wrapped cost function
+    cost_count: AtomicU64,
+}
+
+impl<O> NoiseAveraged<O> {
+    /// Constructs a new `NoiseAveraged` which wraps `op`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::NoiseAveraged;
+    /// # struct UserDefinedProblem {}
+    /// let problem = NoiseAveraged::new(UserDefinedProblem {});
+    /// ```
+    pub fn new(op: O) -> Self {
+        NoiseAveraged {
+            op,
+            samples: 10,
+            track_variance: false,
+            variance: Mutex::new(None),
+            cost_count: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets the number of evaluations per call of `cost` (default: `10`).
+    ///
+    /// Must be larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, NoiseAveraged};
+    /// # struct UserDefinedProblem {}
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = NoiseAveraged::new(UserDefinedProblem {}).with_samples(50)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_samples(mut self, samples: u64) -> Result<Self, Error> {
+        self.set_samples(samples)?;
+        Ok(self)
+    }
+
+    /// Changes the number of evaluations per call of `cost`.
+    ///
+    /// Must be larger than 0.
+    pub fn set_samples(&mut self, samples: u64) -> Result<(), Error> {
+        if samples == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NoiseAveraged`: number of samples must be > 0."
+            ));
+        }
+        self.samples = samples;
+        Ok(())
+    }
+
+    /// Sets whether the sample variance of each evaluation is computed (default: `false`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::NoiseAveraged;
+    /// # struct UserDefinedProblem {}
+    /// let problem = NoiseAveraged::new(UserDefinedProblem {}).track_variance(true);
+    /// ```
+    #[must_use]
+    pub fn track_variance(mut self, track_variance: bool) -> Self {
+        self.track_variance = track_variance;
+        self
+    }
+
+    /// Returns the number of evaluations per call of `cost`.
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Returns the sample variance of the most recent evaluation.
+    ///
+    /// Returns `None` if variance tracking is disabled, if `cost` was not called yet or if only
+    /// one sample is drawn per evaluation.
+    pub fn variance(&self) -> Option<f64> {
+        self.variance.lock().unwrap().map(|(_, variance)| variance)
+    }
+
+    /// Returns the number of samples and, if available, the sample variance and the standard
+    /// error of the mean of the most recent evaluation as `noise_samples`, `noise_variance` and
+    /// `noise_std_err`. Without variance, `noise_samples` is the current number of samples.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CostFunction, Error, NoiseAveraged};
+    /// # struct Constant {}
+    /// # impl CostFunction for Constant {
+    /// #     type Param = f64;
+    /// #     type Output = f64;
+    /// #     fn cost(&self, _p: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(1.0)
+    /// #     }
+    /// # }
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = NoiseAveraged::new(Constant {}).track_variance(true);
+    /// problem.cost(&0.0)?;
+    /// let kv = problem.kv();
+    /// assert_eq!(kv.get("noise_samples").unwrap().get_uint(), Some(10));
+    /// assert_eq!(kv.get("noise_variance").unwrap().get_float(), Some(0.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn kv(&self) -> KV {
+        match *self.variance.lock().unwrap() {
+            Some((samples, variance)) => kv!(
+                "noise_samples" => samples;
+                "noise_variance" => variance;
+                "noise_std_err" => (variance / samples as f64).sqrt();
+            ),
+            None => kv!("noise_samples" => self.samples;),
+        }
+    }
+
+    /// Returns the number of evaluations of the wrapped cost function.
+    pub fn cost_count(&self) -> u64 {
+        self.cost_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns a reference to the wrapped problem.
+    pub fn inner(&self) -> &O {
+        &self.op
+    }
+
+    /// Returns the wrapped problem.
+    pub fn into_inner(self) -> O {
+        self.op
+    }
+}
+
+impl<O: Clone> Clone for NoiseAveraged<O> {
+    fn clone(&self) -> Self {
+        NoiseAveraged {
+            op: self.op.clone(),
+            samples: self.samples,
+            track_variance: self.track_variance,
+            variance: Mutex::new(*self.variance.lock().unwrap()),
+            cost_count: AtomicU64::new(self.cost_count()),
+        }
+    }
+}
+
+impl<O, F> CostFunction for NoiseAveraged<O>
+where
+    O: CostFunction<Output = F>,
+    F: ArgminFloat,
+{
+    type Param = O::Param;
+    type Output = F;
+
+    /// Returns the mean of `samples` evaluations of the wrapped cost function.
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        // Welford's algorithm
+        let mut mean: F = float!(0.0);
+        let mut m2: F = float!(0.0);
+        for k in 1..=self.samples {
+            self.cost_count.fetch_add(1, Ordering::Relaxed);
+            let c = self.op.cost(param)?;
+            let delta = c - mean;
+            mean = mean + delta / float!(k as f64);
+            m2 = m2 + delta * (c - mean);
+        }
+        if self.track_variance {
+            *self.variance.lock().unwrap() = if self.samples > 1 {
+                let variance = m2 / float!((self.samples - 1) as f64);
+                Some((self.samples, variance.to_f64().unwrap()))
+            } else {
+                None
+            };
+        }
+        Ok(mean)
+    }
+
+    fn parallelize(&self) -> bool {
+        self.op.parallelize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::solver::neldermead::NelderMead;
+    use approx::assert_relative_eq;
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    /// Adds uniformly distributed noise in `[-noise, noise]` to a paraboloid
+    struct Noisy {
+        noise: f64,
+        rng: Mutex<Xoshiro256PlusPlus>,
+    }
+
+    impl Noisy {
+        fn new(noise: f64) -> Self {
+            Noisy {
+                noise,
+                rng: Mutex::new(Xoshiro256PlusPlus::seed_from_u64(42)),
+            }
+        }
+    }
+
+    impl CostFunction for Noisy {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let noise = self.rng.lock().unwrap().gen_range(-self.noise..=self.noise);
+            Ok((p[0] - 1.0).powi(2) + (p[1] + 0.5).powi(2) + noise)
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert_error!(
+            NoiseAveraged::new(Noisy::new(1.0)).with_samples(0),
+            ArgminError,
+            "Invalid parameter: \"`NoiseAveraged`: number of samples must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_mean_and_variance() {
+        let problem = NoiseAveraged::new(Noisy::new(1.0))
+            .with_samples(10000)
+            .unwrap()
+            .track_variance(true);
+        assert!(problem.variance().is_none());
+        let cost = problem.cost(&vec![1.0, -0.5]).unwrap();
+        assert_relative_eq!(cost, 0.0, epsilon = 0.05);
+        // Variance of the uniform distribution on [-1, 1]
+        assert_relative_eq!(problem.variance().unwrap(), 1.0 / 3.0, epsilon = 0.02);
+        assert_eq!(problem.cost_count(), 10000);
+
+        let kv = problem.kv();
+        assert_eq!(kv.get("noise_samples").unwrap().get_uint(), Some(10000));
+        assert_relative_eq!(
+            kv.get("noise_std_err").unwrap().get_float().unwrap(),
+            (1.0f64 / 3.0 / 10000.0).sqrt(),
+            epsilon = 1e-3
+        );
+
+        let mut problem = problem;
+        problem.set_samples(1).unwrap();
+        problem.cost(&vec![1.0, -0.5]).unwrap();
+        assert!(problem.variance().is_none());
+        assert!(problem.kv().get("noise_variance").is_none());
+    }
+
+    #[test]
+    fn test_nelder_mead() {
+        let solver = NelderMead::new(vec![vec![0.0, 0.0], vec![0.5, 0.0], vec![0.0, 0.5]]);
+        let problem = NoiseAveraged::new(Noisy::new(0.05))
+            .with_samples(200)
+            .unwrap();
+        let res = Executor::new(problem, solver)
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        let best = res.state.get_best_param().unwrap();
+        assert_relative_eq!(best[0], 1.0, epsilon = 0.1);
+        assert_relative_eq!(best[1], -0.5, epsilon = 0.1);
+
+        let problem = res.problem.problem.as_ref().unwrap();
+        assert_eq!(problem.cost_count(), 200 * res.problem.counts["cost_count"]);
+    }
+}
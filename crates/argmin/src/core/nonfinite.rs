@@ -0,0 +1,248 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, IterState, State};
+use argmin_math::ArgminL1Norm;
+use num_traits::Float;
+use std::fmt;
+
+/// Behavior in case an iteration produced non-finite values.
+pub enum NonFinitePolicy<I> {
+    /// Stop the run with an
+    /// [`ArgminError::NonFiniteValue`](`crate::core::ArgminError::NonFiniteValue`) error which
+    /// names the offending quantity and the iteration.
+    Abort,
+    /// Discard the iteration and restore the state from before the iteration. The solver is
+    /// informed via [`Solver::reject_step`](`crate::core::Solver::reject_step`), which allows
+    /// solvers with a step size (such as the radius of trust region methods) to shrink it. Fails if
+    /// the solver does not support rejecting steps.
+    RejectStep,
+    /// Repair the state with the given function, for instance
+    /// [`IterState::clamp_cost`](`crate::core::IterState::clamp_cost`).
+    Clamp(fn(&mut I)),
+}
+
+impl<I> Clone for NonFinitePolicy<I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I> Copy for NonFinitePolicy<I> {}
+
+impl<I> fmt::Debug for NonFinitePolicy<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NonFinitePolicy::Abort => f.write_str("Abort"),
+            NonFinitePolicy::RejectStep => f.write_str("RejectStep"),
+            NonFinitePolicy::Clamp(_) => f.write_str("Clamp"),
+        }
+    }
+}
+
+/// Detection of non-finite values (`NaN` or infinity) after each iteration.
+///
+/// By default, only the cost function value is checked, since parameter vectors and gradients are
+/// not accessible for arbitrary states. Since the states in argmin initialize the cost function
+/// value with positive infinity and some solvers never evaluate the cost function, only `NaN` and
+/// negative infinity are treated as non-finite cost function values. Further checks can be added
+/// with [`NonFiniteCheck::check`]; for [`IterState`](`crate::core::IterState`),
+/// [`NonFiniteCheck::with_param`] and [`NonFiniteCheck::with_gradient`] provide checks of the
+/// parameter vector and of the gradient. What happens if a non-finite value is detected is defined
+/// by the [`NonFinitePolicy`].
+///
+/// The check is enabled via [`Executor::non_finite`](`crate::core::Executor::non_finite`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{IterState, NonFiniteCheck, NonFinitePolicy};
+/// let check: NonFiniteCheck<IterState<Vec<f64>, Vec<f64>, (), (), (), f64>> =
+///     NonFiniteCheck::new(NonFinitePolicy::Abort)
+///         .with_param()
+///         .with_gradient();
+/// ```
+pub struct NonFiniteCheck<I: State> {
+    /// Behavior in case of non-finite values
+    policy: NonFinitePolicy<I>,
+    /// Checks which return `true` if the labeled quantity is finite
+    checks: Vec<(&'static str, fn(&I) -> bool)>,
+    /// Copies the state
+    snapshot: fn(&I) -> I,
+}
+
+impl<I> NonFiniteCheck<I>
+where
+    I: State + Clone,
+{
+    /// Constructs a new `NonFiniteCheck` which checks the cost function value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, NonFiniteCheck, NonFinitePolicy};
+    /// let check: NonFiniteCheck<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     NonFiniteCheck::new(NonFinitePolicy::Clamp(IterState::clamp_cost));
+    /// ```
+    pub fn new(policy: NonFinitePolicy<I>) -> Self {
+        NonFiniteCheck {
+            policy,
+            checks: vec![("cost", |state| {
+                let cost = state.get_cost();
+                !cost.is_nan() && cost != I::Float::neg_infinity()
+            })],
+            snapshot: I::clone,
+        }
+    }
+}
+
+impl<I: State> NonFiniteCheck<I> {
+    /// Adds a check of a quantity labeled `name`; `is_finite` returns `true` if it is finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, NonFiniteCheck, NonFinitePolicy};
+    /// let check: NonFiniteCheck<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     NonFiniteCheck::new(NonFinitePolicy::Abort).check("best cost", |state| {
+    ///         state.get_best_cost().is_finite()
+    ///     });
+    /// ```
+    #[must_use]
+    pub fn check(mut self, name: &'static str, is_finite: fn(&I) -> bool) -> Self {
+        self.checks.push((name, is_finite));
+        self
+    }
+
+    /// Returns the policy.
+    pub(crate) fn policy(&self) -> NonFinitePolicy<I> {
+        self.policy
+    }
+
+    /// Returns the label of the first quantity which is not finite.
+    pub(crate) fn find_non_finite(&self, state: &I) -> Option<&'static str> {
+        self.checks
+            .iter()
+            .find(|(_, is_finite)| !is_finite(state))
+            .map(|(name, _)| *name)
+    }
+
+    /// Copies `state` such that it can be restored later.
+    pub(crate) fn snapshot(&self, state: &I) -> I {
+        (self.snapshot)(state)
+    }
+}
+
+impl<P, G, J, H, R, F> NonFiniteCheck<IterState<P, G, J, H, R, F>>
+where
+    P: Clone + ArgminL1Norm<F>,
+    F: ArgminFloat,
+{
+    /// Checks the parameter vector stored in [`IterState`](`crate::core::IterState`).
+    ///
+    /// The parameter vector is considered finite if its L1 norm is finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, NonFiniteCheck, NonFinitePolicy};
+    /// let check: NonFiniteCheck<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     NonFiniteCheck::new(NonFinitePolicy::Abort).with_param();
+    /// ```
+    #[must_use]
+    pub fn with_param(self) -> Self {
+        self.check("parameter vector", |state| {
+            state
+                .get_param()
+                .map(|param| param.l1_norm().is_finite())
+                .unwrap_or(true)
+        })
+    }
+}
+
+impl<P, G, J, H, R, F> NonFiniteCheck<IterState<P, G, J, H, R, F>>
+where
+    P: Clone,
+    G: ArgminL1Norm<F>,
+    F: ArgminFloat,
+{
+    /// Checks the gradient stored in [`IterState`](`crate::core::IterState`).
+    ///
+    /// The gradient is considered finite if its L1 norm is finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, NonFiniteCheck, NonFinitePolicy};
+    /// let check: NonFiniteCheck<IterState<Vec<f64>, Vec<f64>, (), (), (), f64>> =
+    ///     NonFiniteCheck::new(NonFinitePolicy::Abort).with_gradient();
+    /// ```
+    #[must_use]
+    pub fn with_gradient(self) -> Self {
+        self.check("gradient", |state| {
+            state
+                .get_gradient()
+                .map(|grad| grad.l1_norm().is_finite())
+                .unwrap_or(true)
+        })
+    }
+}
+
+impl<I> fmt::Debug for NonFiniteCheck<I>
+where
+    I: State,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NonFiniteCheck")
+            .field("policy", &self.policy)
+            .field(
+                "checks",
+                &self
+                    .checks
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestState = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+
+    #[test]
+    fn test_find_non_finite() {
+        let check: NonFiniteCheck<TestState> = NonFiniteCheck::new(NonFinitePolicy::Abort)
+            .with_param()
+            .with_gradient();
+
+        let state = TestState::new().cost(1.0);
+        assert_eq!(check.find_non_finite(&state), None);
+
+        // Positive infinity is the initial value of the cost
+        let state = TestState::new();
+        assert_eq!(check.find_non_finite(&state), None);
+
+        let state = TestState::new().cost(f64::NAN);
+        assert_eq!(check.find_non_finite(&state), Some("cost"));
+
+        let state = TestState::new().cost(f64::NEG_INFINITY);
+        assert_eq!(check.find_non_finite(&state), Some("cost"));
+
+        let state = TestState::new().cost(1.0).param(vec![1.0, f64::INFINITY]);
+        assert_eq!(check.find_non_finite(&state), Some("parameter vector"));
+
+        let state = TestState::new()
+            .cost(1.0)
+            .param(vec![1.0])
+            .gradient(vec![f64::NAN]);
+        assert_eq!(check.find_non_finite(&state), Some("gradient"));
+    }
+}
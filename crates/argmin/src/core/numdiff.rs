@@ -0,0 +1,1362 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Finite-difference derivatives
+//!
+//! Adapters which provide [`Gradient`] for problems which only implement [`CostFunction`]. This
+//! allows using gradient-based solvers with black-box cost functions.
+//!
+//! * [`WithForwardDiff`]: `g_i = (f(x + h_i e_i) - f(x)) / h_i`, which requires `n + 1` cost
+//!   function evaluations per gradient.
+//! * [`WithCentralDiff`]: `g_i = (f(x + h_i e_i) - f(x - h_i e_i)) / (2 h_i)`, which requires `2n`
+//!   cost function evaluations per gradient, but is considerably more accurate.
+//! * [`WithComplexStep`]: `g_i = Im(f(x + i h e_i)) / h`, which requires `n` evaluations of the
+//!   cost function with complex-valued parameters (see [`ComplexCostFunction`]) and yields
+//!   derivatives accurate to machine precision.
+//!
+//! For finite differences, the step in direction `i` is `h_i = h * max(|x_i|, 1)`, where the relative step size `h`
+//! defaults to `sqrt(EPSILON)` for forward differences and to `cbrt(EPSILON)` for central
+//! differences. It can be changed via `with_step`. Optionally, the step can be chosen adaptively
+//! via `with_adaptive_step`: Starting from `h_i`, the step is halved until two successive
+//! estimates of the derivative agree up to a given tolerance.
+//!
+//! Similarly, [`WithHessianFromGradient`] and [`WithHessianFromCost`] provide [`Hessian`] via
+//! finite differences of the gradient or of the cost function, respectively, which enables
+//! Newton and trust region methods for problems without analytic Hessian. Both can exploit a
+//! known sparsity pattern of the Hessian to reduce the number of evaluations.
+//!
+//! With a fixed step, all adapters assemble the perturbed parameter vectors (the stencil) and
+//! evaluate them with a single call of `bulk_cost` or `bulk_gradient` of the wrapped problem.
+//! Problems which can evaluate many parameter vectors at once (vectorized or on a GPU) may
+//! therefore overwrite these methods. Note that this keeps `O(n)` copies of the parameter vector
+//! in memory (`O(n^2)` for [`WithHessianFromCost`]). By default, the `bulk_*` methods evaluate
+//! in parallel if the `rayon` feature is enabled, unless `parallelize` of the wrapped problem
+//! returns `false`. With an adaptive step, [`WithForwardDiff`] and [`WithCentralDiff`] process
+//! the components in parallel instead. In either case, parallel evaluation requires the wrapped
+//! problem to be `Sync`.
+//!
+//! Evaluations of the cost function via `cost` are forwarded to the wrapped problem and counted
+//! by [`Problem`](`crate::core::Problem`) as usual. Evaluations needed to compute the gradient
+//! are counted separately by the adapter and are available via `cost_count`. In order to account
+//! for them in an [`EvaluationBudget`](`crate::core::EvaluationBudget`), the `gradient_count` can
+//! be weighted with the number of cost function evaluations per gradient.
+//!
+//! # Example
+//!
+//! ```
+//! # use argmin::core::{CostFunction, Error, Executor, State, WithCentralDiff};
+//! # use argmin::solver::linesearch::MoreThuenteLineSearch;
+//! # use argmin::solver::quasinewton::LBFGS;
+//! # use argmin_testfunctions::rosenbrock;
+//! struct Rosenbrock {}
+//!
+//! impl CostFunction for Rosenbrock {
+//!     type Param = Vec<f64>;
+//!     type Output = f64;
+//!
+//!     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+//!         Ok(rosenbrock(p))
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), Error> {
+//! let problem = WithCentralDiff::new(Rosenbrock {});
+//! let solver = LBFGS::new(MoreThuenteLineSearch::new(), 7);
+//! let res = Executor::new(problem, solver)
+//!     .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+//!     .run()?;
+//!
+//! // Cost function evaluations performed to compute gradients
+//! let fd_evals = res.problem.problem.as_ref().unwrap().cost_count();
+//! # assert!(fd_evals > 0);
+//! # let best = res.state.get_best_param().unwrap();
+//! # assert!((best[0] - 1.0).abs() < 1e-4);
+//! # assert!((best[1] - 1.0).abs() < 1e-4);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::{ArgminFloat, CostFunction, Error, Gradient, Hessian, SendAlias, SyncAlias};
+use num_complex::Complex;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::ops::{Index, IndexMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Finite-difference scheme
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Scheme {
+    Forward,
+    Central,
+}
+
+/// Step size configuration shared by the adapters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct StepConfig {
+    /// Relative step size; the default of the scheme is used if `None`
+    step: Option<f64>,
+    /// Tolerance and maximum number of halvings of the adaptive step
+    adaptive: Option<(f64, u64)>,
+}
+
+impl StepConfig {
+    pub(crate) fn new() -> Self {
+        StepConfig {
+            step: None,
+            adaptive: None,
+        }
+    }
+
+    pub(crate) fn with_step(mut self, step: f64, name: &str) -> Result<Self, Error> {
+        if step.is_nan() || step <= 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{name}`: step size must be > 0.")
+            ));
+        }
+        self.step = Some(step);
+        Ok(self)
+    }
+
+    fn with_adaptive_step(
+        mut self,
+        tolerance: f64,
+        max_halvings: u64,
+        name: &str,
+    ) -> Result<Self, Error> {
+        if tolerance.is_nan() || tolerance <= 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{name}`: tolerance must be > 0.")
+            ));
+        }
+        if max_halvings == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{name}`: maximum number of halvings must be > 0.")
+            ));
+        }
+        self.adaptive = Some((tolerance, max_halvings));
+        Ok(self)
+    }
+
+    /// Returns the relative step size, which defaults to `sqrt(EPSILON)` for forward and to
+    /// `cbrt(EPSILON)` for central differences.
+    pub(crate) fn relative_step<F: ArgminFloat>(&self, scheme: Scheme) -> F {
+        match (self.step, scheme) {
+            (Some(step), _) => float!(step),
+            (None, Scheme::Forward) => F::epsilon().sqrt(),
+            (None, Scheme::Central) => F::epsilon().cbrt(),
+        }
+    }
+}
+
+/// Evaluates `func` for all components `0..n` and collects the results.
+///
+/// `func` is passed a scratch copy of `param` which it must restore before returning. If the
+/// `rayon` feature is enabled and `parallel` is `true`, the components are processed in parallel
+/// with one scratch copy per thread.
+fn map_components<P, T, Func>(
+    param: &P,
+    n: usize,
+    parallel: bool,
+    func: Func,
+) -> Result<Vec<T>, Error>
+where
+    P: Clone + SendAlias + SyncAlias,
+    T: SendAlias,
+    Func: Fn(&mut P, usize) -> Result<T, Error> + SyncAlias + SendAlias,
+{
+    #[cfg(feature = "rayon")]
+    {
+        if parallel {
+            crate::core::parallelization::install(|| {
+                (0..n)
+                    .into_par_iter()
+                    .map_init(|| param.clone(), |x, i| func(x, i))
+                    .collect()
+            })
+        } else {
+            let mut x = param.clone();
+            (0..n).map(|i| func(&mut x, i)).collect()
+        }
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        let _ = parallel;
+        let mut x = param.clone();
+        (0..n).map(|i| func(&mut x, i)).collect()
+    }
+}
+
+/// Computes the gradient of the cost function of `op` at `param` and adds the number of cost
+/// function evaluations to `count`.
+///
+/// The components are computed in parallel if the `rayon` feature is enabled and `op`
+/// does not opt out via `parallelize`.
+pub(crate) fn gradient<O, P, F>(
+    op: &O,
+    param: &P,
+    scheme: Scheme,
+    config: &StepConfig,
+    count: &AtomicU64,
+) -> Result<P, Error>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + IndexMut<usize, Output = F> + SyncAlias + SendAlias,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat + SyncAlias,
+{
+    let cost = |x: &P| {
+        count.fetch_add(1, Ordering::Relaxed);
+        op.cost(x)
+    };
+    let rel_step: F = config.relative_step(scheme);
+    let n = param.into_iter().count();
+    if config.adaptive.is_none() {
+        // Evaluate the entire stencil with a single call of `bulk_cost`
+        let mut stencil = Vec::with_capacity(2 * n + 1);
+        let mut steps = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut x = param.clone();
+            x[i] = param[i] + rel_step * param[i].abs().max(float!(1.0));
+            // Use the step which is actually representable
+            let h = x[i] - param[i];
+            if scheme == Scheme::Central {
+                let mut x_minus = param.clone();
+                x_minus[i] = param[i] - h;
+                stencil.push(x);
+                stencil.push(x_minus);
+            } else {
+                stencil.push(x);
+            }
+            steps.push(h);
+        }
+        if scheme == Scheme::Forward {
+            stencil.push(param.clone());
+        }
+        count.fetch_add(stencil.len() as u64, Ordering::Relaxed);
+        let costs = op.bulk_cost(&stencil)?;
+        let mut grad = param.clone();
+        for (i, h) in steps.into_iter().enumerate() {
+            grad[i] = match scheme {
+                Scheme::Forward => (costs[i] - costs[n]) / h,
+                Scheme::Central => (costs[2 * i] - costs[2 * i + 1]) / (float!(2.0) * h),
+            };
+        }
+        return Ok(grad);
+    }
+    let f0 = match scheme {
+        Scheme::Forward => Some(cost(param)?),
+        Scheme::Central => None,
+    };
+    let derivatives = map_components(param, n, op.parallelize(), |x: &mut P, i| {
+        let xi = param[i];
+        let estimate = |x: &mut P, h: F| -> Result<F, Error> {
+            x[i] = xi + h;
+            // Use the step which is actually representable
+            let h = x[i] - xi;
+            let f_plus = cost(x)?;
+            let d = match f0 {
+                Some(f0) => (f_plus - f0) / h,
+                None => {
+                    x[i] = xi - h;
+                    let f_minus = cost(x)?;
+                    (f_plus - f_minus) / (float!(2.0) * h)
+                }
+            };
+            x[i] = xi;
+            Ok(d)
+        };
+        let mut h = rel_step * xi.abs().max(float!(1.0));
+        let mut d = estimate(x, h)?;
+        if let Some((tolerance, max_halvings)) = config.adaptive {
+            for _ in 0..max_halvings {
+                h = h / float!(2.0);
+                let d_new = estimate(x, h)?;
+                let converged =
+                    (d_new - d).abs() <= float!(tolerance) * d_new.abs().max(float!(1.0));
+                d = d_new;
+                if converged {
+                    break;
+                }
+            }
+        }
+        Ok(d)
+    })?;
+    let mut grad = param.clone();
+    for (i, d) in derivatives.into_iter().enumerate() {
+        grad[i] = d;
+    }
+    Ok(grad)
+}
+
+macro_rules! make_adapter {
+    ($name:ident) => {
+        impl<O> $name<O> {
+            #[doc = concat!("Constructs a new `", stringify!($name), "` which wraps `op`.")]
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!("# use argmin::core::", stringify!($name), ";")]
+            /// # struct UserDefinedProblem {}
+            #[doc = concat!("let problem = ", stringify!($name), "::new(UserDefinedProblem {});")]
+            /// ```
+            pub fn new(op: O) -> Self {
+                $name {
+                    op,
+                    config: StepConfig::new(),
+                    cost_count: AtomicU64::new(0),
+                }
+            }
+
+            /// Returns the number of cost function evaluations performed to compute gradients.
+            ///
+            /// Evaluations of `cost` are not included; these are counted by
+            /// [`Problem`](`crate::core::Problem`).
+            pub fn cost_count(&self) -> u64 {
+                self.cost_count.load(Ordering::Relaxed)
+            }
+
+            /// Returns a reference to the wrapped problem.
+            pub fn inner(&self) -> &O {
+                &self.op
+            }
+
+            /// Returns the wrapped problem.
+            pub fn into_inner(self) -> O {
+                self.op
+            }
+        }
+
+        impl<O: Clone> Clone for $name<O> {
+            fn clone(&self) -> Self {
+                $name {
+                    op: self.op.clone(),
+                    config: self.config,
+                    cost_count: AtomicU64::new(self.cost_count()),
+                }
+            }
+        }
+
+        impl<O: CostFunction> CostFunction for $name<O> {
+            type Param = O::Param;
+            type Output = O::Output;
+
+            fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+                self.op.cost(param)
+            }
+
+            fn parallelize(&self) -> bool {
+                self.op.parallelize()
+            }
+        }
+    };
+}
+
+macro_rules! make_finite_diff {
+    ($name:ident, $scheme:expr, $default:literal, $evals:literal) => {
+        make_adapter!($name);
+
+        impl<O> $name<O> {
+            #[doc = concat!(
+                "Sets the relative step size `h` (default: `", $default, "`). ",
+                "The step in direction `i` is `h * max(|x_i|, 1)`."
+            )]
+            ///
+            /// Must be larger than 0.
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!("# use argmin::core::{Error, ", stringify!($name), "};")]
+            /// # struct UserDefinedProblem {}
+            /// # fn main() -> Result<(), Error> {
+            #[doc = concat!(
+                "let problem = ", stringify!($name), "::new(UserDefinedProblem {}).with_step(1e-6)?;"
+            )]
+            /// # Ok(())
+            /// # }
+            /// ```
+            pub fn with_step(mut self, step: f64) -> Result<Self, Error> {
+                self.config = self.config.with_step(step, stringify!($name))?;
+                Ok(self)
+            }
+
+            /// Enables adaptive steps.
+            ///
+            /// Starting from the step set via `with_step`, the step is halved until the estimates
+            /// of the derivative of two successive steps `d_k` and `d_{k+1}` fulfill
+            /// `|d_{k+1} - d_k| <= tolerance * max(|d_{k+1}|, 1)`, but at most `max_halvings`
+            /// times. Each halving requires additional cost function evaluations. Since
+            /// round-off errors grow with decreasing steps, the initial step should be chosen
+            /// larger than the default.
+            ///
+            /// `tolerance` and `max_halvings` must be larger than 0.
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!("# use argmin::core::{Error, ", stringify!($name), "};")]
+            /// # struct UserDefinedProblem {}
+            /// # fn main() -> Result<(), Error> {
+            #[doc = concat!("let problem = ", stringify!($name), "::new(UserDefinedProblem {})")]
+            ///     .with_step(1e-2)?
+            ///     .with_adaptive_step(1e-6, 10)?;
+            /// # Ok(())
+            /// # }
+            /// ```
+            pub fn with_adaptive_step(
+                mut self,
+                tolerance: f64,
+                max_halvings: u64,
+            ) -> Result<Self, Error> {
+                self.config =
+                    self.config
+                        .with_adaptive_step(tolerance, max_halvings, stringify!($name))?;
+                Ok(self)
+            }
+        }
+
+        impl<O, P, F> Gradient for $name<O>
+        where
+            O: CostFunction<Param = P, Output = F> + SyncAlias,
+            P: Clone + IndexMut<usize, Output = F> + SyncAlias + SendAlias,
+            for<'a> &'a P: IntoIterator<Item = &'a F>,
+            F: ArgminFloat + SyncAlias,
+        {
+            type Param = P;
+            type Gradient = P;
+
+            #[doc = concat!(
+                "Computes the gradient via ", $evals, " evaluations of the cost function ",
+                "(plus those required for adaptive steps)."
+            )]
+            fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+                gradient(&self.op, param, $scheme, &self.config, &self.cost_count)
+            }
+        }
+    };
+}
+
+/// Provides a [`Gradient`] via forward differences for a problem implementing [`CostFunction`].
+///
+/// See the [module documentation](`crate::core::numdiff`) for details.
+#[derive(Debug)]
+pub struct WithForwardDiff<O> {
+    /// Wrapped problem
+    op: O,
+    /// Step size configuration
+    config: StepConfig,
+    /// Number of cost function evaluations performed to compute gradients
+    cost_count: AtomicU64,
+}
+
+/// Provides a [`Gradient`] via central differences for a problem implementing [`CostFunction`].
+///
+/// See the [module documentation](`crate::core::numdiff`) for details.
+#[derive(Debug)]
+pub struct WithCentralDiff<O> {
+    /// Wrapped problem
+    op: O,
+    /// Step size configuration
+    config: StepConfig,
+    /// Number of cost function evaluations performed to compute gradients
+    cost_count: AtomicU64,
+}
+
+make_finite_diff!(WithForwardDiff, Scheme::Forward, "sqrt(EPSILON)", "`n + 1`");
+make_finite_diff!(WithCentralDiff, Scheme::Central, "cbrt(EPSILON)", "`2n`");
+
+/// Evaluation of the cost function for complex-valued parameter vectors.
+///
+/// Required for complex-step differentiation via [`WithComplexStep`]. The implementation must be
+/// the analytic continuation of the real-valued cost function, i.e. it must compute the same
+/// expression with complex arithmetic. Operations which are not complex-analytic, such as `abs`,
+/// comparisons of real parts or taking the real part, must be avoided.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{ComplexCostFunction, Error};
+/// use num_complex::Complex;
+///
+/// struct Model {}
+///
+/// impl ComplexCostFunction for Model {
+///     type Float = f64;
+///
+///     // f(x) = exp(x_0) * x_1^2
+///     fn cost_complex(&self, param: &[Complex<f64>]) -> Result<Complex<f64>, Error> {
+///         Ok(param[0].exp() * param[1] * param[1])
+///     }
+/// }
+/// ```
+pub trait ComplexCostFunction {
+    /// Type of the real and imaginary parts
+    type Float;
+
+    /// Compute cost function for complex-valued parameters
+    fn cost_complex(&self, param: &[Complex<Self::Float>]) -> Result<Complex<Self::Float>, Error>;
+}
+
+/// Provides a [`Gradient`] via complex-step differentiation for a problem implementing
+/// [`CostFunction`] and [`ComplexCostFunction`].
+///
+/// The derivative in direction `i` is computed as `g_i = Im(f(x + i h e_i)) / h`. Since no
+/// difference of function values is involved, there is no subtractive cancellation and the step
+/// `h` can be chosen tiny (default: `1e-20`), which yields derivatives accurate to machine
+/// precision. Each gradient requires `n` evaluations of
+/// [`cost_complex`](`ComplexCostFunction::cost_complex`).
+///
+/// See the [module documentation](`crate::core::numdiff`) for details on counting of evaluations.
+#[derive(Debug)]
+pub struct WithComplexStep<O> {
+    /// Wrapped problem
+    op: O,
+    /// Step size configuration
+    config: StepConfig,
+    /// Number of cost function evaluations performed to compute gradients
+    cost_count: AtomicU64,
+}
+
+make_adapter!(WithComplexStep);
+
+impl<O> WithComplexStep<O> {
+    /// Sets the step `h` (default: `1e-20`).
+    ///
+    /// Must be larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, WithComplexStep};
+    /// # struct UserDefinedProblem {}
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = WithComplexStep::new(UserDefinedProblem {}).with_step(1e-30)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step(mut self, step: f64) -> Result<Self, Error> {
+        self.config = self.config.with_step(step, "WithComplexStep")?;
+        Ok(self)
+    }
+}
+
+impl<O, P, F> Gradient for WithComplexStep<O>
+where
+    O: CostFunction<Param = P, Output = F> + ComplexCostFunction<Float = F>,
+    P: Clone + IndexMut<usize, Output = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Gradient = P;
+
+    /// Computes the gradient via `n` evaluations of the complex-valued cost function.
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let h: F = float!(self.config.step.unwrap_or(1e-20));
+        let mut z: Vec<Complex<F>> = param
+            .into_iter()
+            .map(|x| Complex::new(*x, float!(0.0)))
+            .collect();
+        let mut grad = param.clone();
+        for i in 0..z.len() {
+            z[i].im = h;
+            self.cost_count.fetch_add(1, Ordering::Relaxed);
+            grad[i] = self.op.cost_complex(&z)?.im / h;
+            z[i].im = float!(0.0);
+        }
+        Ok(grad)
+    }
+}
+
+/// Returns the rows of the nonzero elements of each column of a symmetric `n x n` matrix with
+/// the nonzero elements `pattern` (or of a dense matrix if `pattern` is `None`). The pattern is
+/// symmetrized and always contains the diagonal.
+fn sparsity_structure(
+    pattern: Option<&[(usize, usize)]>,
+    n: usize,
+    name: &str,
+) -> Result<Vec<Vec<usize>>, Error> {
+    let Some(pattern) = pattern else {
+        return Ok(vec![(0..n).collect(); n]);
+    };
+    let mut columns: Vec<Vec<usize>> = (0..n).map(|j| vec![j]).collect();
+    for &(i, j) in pattern {
+        if i >= n || j >= n {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`{name}`: index ({i}, {j}) of sparsity pattern out of bounds for n = {n}."
+                )
+            ));
+        }
+        columns[j].push(i);
+        columns[i].push(j);
+    }
+    for column in columns.iter_mut() {
+        column.sort_unstable();
+        column.dedup();
+    }
+    Ok(columns)
+}
+
+/// Partitions the columns into groups which do not have nonzero elements in the same row via
+/// greedy graph coloring. All columns of a group can be estimated with a single evaluation.
+fn color_columns(columns: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = columns.len();
+    // Columns which have a nonzero element in a given row
+    let mut rows: Vec<Vec<usize>> = vec![vec![]; n];
+    for (j, column) in columns.iter().enumerate() {
+        for &i in column {
+            rows[i].push(j);
+        }
+    }
+    let mut colors: Vec<Option<usize>> = vec![None; n];
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for (j, column) in columns.iter().enumerate() {
+        let mut forbidden = vec![false; groups.len()];
+        for &i in column {
+            for &k in rows[i].iter() {
+                if let Some(c) = colors[k] {
+                    forbidden[c] = true;
+                }
+            }
+        }
+        match forbidden.iter().position(|f| !f) {
+            Some(c) => {
+                colors[j] = Some(c);
+                groups[c].push(j);
+            }
+            None => {
+                colors[j] = Some(groups.len());
+                groups.push(vec![j]);
+            }
+        }
+    }
+    groups
+}
+
+macro_rules! make_hessian_adapter {
+    ($name:ident, $count:ident, $count_doc:literal, $default:literal) => {
+        impl<O> $name<O> {
+            #[doc = concat!("Constructs a new `", stringify!($name), "` which wraps `op`.")]
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!("# use argmin::core::", stringify!($name), ";")]
+            /// # struct UserDefinedProblem {}
+            #[doc = concat!("let problem = ", stringify!($name), "::new(UserDefinedProblem {});")]
+            /// ```
+            pub fn new(op: O) -> Self {
+                $name {
+                    op,
+                    config: StepConfig::new(),
+                    sparsity: None,
+                    $count: AtomicU64::new(0),
+                }
+            }
+
+            #[doc = concat!(
+                "Sets the relative step size `h` (default: `", $default, "`). ",
+                "The step in direction `i` is `h * max(|x_i|, 1)`."
+            )]
+            ///
+            /// Must be larger than 0.
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!("# use argmin::core::{Error, ", stringify!($name), "};")]
+            /// # struct UserDefinedProblem {}
+            /// # fn main() -> Result<(), Error> {
+            #[doc = concat!(
+                "let problem = ", stringify!($name), "::new(UserDefinedProblem {}).with_step(1e-5)?;"
+            )]
+            /// # Ok(())
+            /// # }
+            /// ```
+            pub fn with_step(mut self, step: f64) -> Result<Self, Error> {
+                self.config = self.config.with_step(step, stringify!($name))?;
+                Ok(self)
+            }
+
+            /// Sets the sparsity pattern of the Hessian as a list of indices `(i, j)` of
+            /// nonzero elements.
+            ///
+            /// The pattern is symmetrized and always includes the diagonal; all other elements
+            /// are assumed to be zero.
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!("# use argmin::core::", stringify!($name), ";")]
+            /// # struct UserDefinedProblem {}
+            /// // Tridiagonal Hessian of a problem with three parameters
+            #[doc = concat!("let problem = ", stringify!($name), "::new(UserDefinedProblem {})")]
+            ///     .with_sparsity(vec![(0, 1), (1, 2)]);
+            /// ```
+            #[must_use]
+            pub fn with_sparsity(mut self, pattern: Vec<(usize, usize)>) -> Self {
+                self.sparsity = Some(pattern);
+                self
+            }
+
+            #[doc = concat!("Returns the number of ", $count_doc, " performed to compute Hessians.")]
+            ///
+            /// Evaluations of `cost` and `gradient` are not included; these are counted by
+            /// [`Problem`](`crate::core::Problem`).
+            pub fn $count(&self) -> u64 {
+                self.$count.load(Ordering::Relaxed)
+            }
+
+            /// Returns a reference to the wrapped problem.
+            pub fn inner(&self) -> &O {
+                &self.op
+            }
+
+            /// Returns the wrapped problem.
+            pub fn into_inner(self) -> O {
+                self.op
+            }
+        }
+
+        impl<O: Clone> Clone for $name<O> {
+            fn clone(&self) -> Self {
+                $name {
+                    op: self.op.clone(),
+                    config: self.config,
+                    sparsity: self.sparsity.clone(),
+                    $count: AtomicU64::new(self.$count()),
+                }
+            }
+        }
+
+        impl<O: CostFunction> CostFunction for $name<O> {
+            type Param = O::Param;
+            type Output = O::Output;
+
+            fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+                self.op.cost(param)
+            }
+
+            fn parallelize(&self) -> bool {
+                self.op.parallelize()
+            }
+        }
+
+        impl<O: Gradient> Gradient for $name<O> {
+            type Param = O::Param;
+            type Gradient = O::Gradient;
+
+            fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+                self.op.gradient(param)
+            }
+
+            fn parallelize(&self) -> bool {
+                self.op.parallelize()
+            }
+        }
+    };
+}
+
+/// Provides a [`Hessian`] via forward differences of the gradient for a problem implementing
+/// [`Gradient`].
+///
+/// Column `j` of the Hessian is computed as `(g(x + h_j e_j) - g(x)) / h_j` and the result is
+/// symmetrized. Without sparsity pattern, this requires `n + 1` gradient evaluations. If a
+/// sparsity pattern is provided via [`with_sparsity`](`WithHessianFromGradient::with_sparsity`),
+/// columns without nonzero elements in the same row are grouped via graph coloring and
+/// estimated together, which reduces the number of gradient evaluations to the number of groups
+/// plus one. For a tridiagonal Hessian, for instance, four gradient evaluations suffice
+/// independent of `n`.
+///
+/// The Hessian is returned as `Vec<Vec<F>>`.
+///
+/// See the [module documentation](`crate::core::numdiff`) for details on counting of evaluations.
+#[derive(Debug)]
+pub struct WithHessianFromGradient<O> {
+    /// Wrapped problem
+    op: O,
+    /// Step size configuration
+    config: StepConfig,
+    /// Indices of the nonzero elements of the Hessian
+    sparsity: Option<Vec<(usize, usize)>>,
+    /// Number of gradient evaluations performed to compute Hessians
+    gradient_count: AtomicU64,
+}
+
+make_hessian_adapter!(
+    WithHessianFromGradient,
+    gradient_count,
+    "gradient evaluations",
+    "sqrt(EPSILON)"
+);
+
+impl<O, P, G, F> Hessian for WithHessianFromGradient<O>
+where
+    O: Gradient<Param = P, Gradient = G> + SyncAlias,
+    P: Clone + IndexMut<usize, Output = F> + SyncAlias,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    G: Index<usize, Output = F> + SendAlias,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Hessian = Vec<Vec<F>>;
+
+    /// Returns the sparsity pattern provided via `with_sparsity`.
+    fn hessian_sparsity(&self) -> Option<Vec<(usize, usize)>> {
+        self.sparsity.clone()
+    }
+
+    /// Computes the Hessian via `n + 1` gradient evaluations, or fewer if a sparsity pattern is
+    /// provided.
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let n = param.into_iter().count();
+        let columns = sparsity_structure(self.sparsity.as_deref(), n, "WithHessianFromGradient")?;
+        let groups = if self.sparsity.is_some() {
+            color_columns(&columns)
+        } else {
+            (0..n).map(|j| vec![j]).collect()
+        };
+        let rel_step: F = self.config.relative_step(Scheme::Forward);
+        // The unperturbed parameter vector comes first, followed by one per group of columns
+        let mut stencil = vec![param.clone()];
+        let mut steps = vec![float!(0.0); n];
+        for group in groups.iter() {
+            let mut x = param.clone();
+            for &j in group {
+                x[j] = param[j] + rel_step * param[j].abs().max(float!(1.0));
+                // Use the step which is actually representable
+                steps[j] = x[j] - param[j];
+            }
+            stencil.push(x);
+        }
+        self.gradient_count
+            .fetch_add(stencil.len() as u64, Ordering::Relaxed);
+        let gradients = self.op.bulk_gradient(&stencil)?;
+        let g0 = &gradients[0];
+        let mut hessian = vec![vec![float!(0.0); n]; n];
+        for (group, g) in groups.iter().zip(gradients.iter().skip(1)) {
+            for &j in group {
+                for &i in columns[j].iter() {
+                    hessian[i][j] = (g[i] - g0[i]) / steps[j];
+                }
+            }
+        }
+        for i in 1..n {
+            let (upper, lower) = hessian.split_at_mut(i);
+            for (j, row_j) in upper.iter_mut().enumerate() {
+                let h = (lower[0][j] + row_j[i]) / float!(2.0);
+                lower[0][j] = h;
+                row_j[i] = h;
+            }
+        }
+        Ok(hessian)
+    }
+}
+
+/// Provides a [`Hessian`] via second-order finite differences of the cost function for a
+/// problem implementing [`CostFunction`].
+///
+/// The element `(i, j)` of the Hessian is computed as
+/// `(f(x + h_i e_i + h_j e_j) - f(x + h_i e_i) - f(x + h_j e_j) + f(x)) / (h_i h_j)`, which
+/// requires `1 + n + n (n + 1) / 2` cost function evaluations. If a sparsity pattern is provided
+/// via [`with_sparsity`](`WithHessianFromCost::with_sparsity`), only the nonzero elements are
+/// evaluated. If gradients are available, [`WithHessianFromGradient`] is considerably more
+/// accurate and cheaper.
+///
+/// The Hessian is returned as `Vec<Vec<F>>`. Gradients are forwarded to the wrapped problem,
+/// which makes it possible to combine this adapter with [`WithCentralDiff`] in order to use
+/// Newton methods on black-box cost functions.
+///
+/// See the [module documentation](`crate::core::numdiff`) for details on counting of evaluations.
+#[derive(Debug)]
+pub struct WithHessianFromCost<O> {
+    /// Wrapped problem
+    op: O,
+    /// Step size configuration
+    config: StepConfig,
+    /// Indices of the nonzero elements of the Hessian
+    sparsity: Option<Vec<(usize, usize)>>,
+    /// Number of cost function evaluations performed to compute Hessians
+    cost_count: AtomicU64,
+}
+
+make_hessian_adapter!(
+    WithHessianFromCost,
+    cost_count,
+    "cost function evaluations",
+    "cbrt(EPSILON)"
+);
+
+impl<O, P, F> Hessian for WithHessianFromCost<O>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + IndexMut<usize, Output = F> + SyncAlias,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Hessian = Vec<Vec<F>>;
+
+    /// Returns the sparsity pattern provided via `with_sparsity`.
+    fn hessian_sparsity(&self) -> Option<Vec<(usize, usize)>> {
+        self.sparsity.clone()
+    }
+
+    /// Computes the Hessian via `1 + n + n (n + 1) / 2` cost function evaluations, or fewer if a
+    /// sparsity pattern is provided.
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let n = param.into_iter().count();
+        let columns = sparsity_structure(self.sparsity.as_deref(), n, "WithHessianFromCost")?;
+        let rel_step: F = self.config.relative_step(Scheme::Central);
+        // Stencil: `x`, `x + h_i e_i` for all `i` and `x + h_i e_i + h_j e_j` for all nonzero
+        // elements `(i, j)` with `i <= j`
+        let mut stencil = vec![param.clone()];
+        let mut steps = vec![float!(0.0); n];
+        for i in 0..n {
+            let mut x = param.clone();
+            x[i] = param[i] + rel_step * param[i].abs().max(float!(1.0));
+            // Use the step which is actually representable
+            steps[i] = x[i] - param[i];
+            stencil.push(x);
+        }
+        let mut pairs = vec![];
+        for (j, column) in columns.iter().enumerate() {
+            for &i in column.iter().filter(|&&i| i <= j) {
+                let mut x = param.clone();
+                x[i] = x[i] + steps[i];
+                x[j] = x[j] + steps[j];
+                stencil.push(x);
+                pairs.push((i, j));
+            }
+        }
+        self.cost_count
+            .fetch_add(stencil.len() as u64, Ordering::Relaxed);
+        let costs = self.op.bulk_cost(&stencil)?;
+        let (f0, f_single, f_pairs) = (costs[0], &costs[1..=n], &costs[n + 1..]);
+        let mut hessian = vec![vec![float!(0.0); n]; n];
+        for (&(i, j), f_ij) in pairs.iter().zip(f_pairs.iter()) {
+            let h = (*f_ij - f_single[i] - f_single[j] + f0) / (steps[i] * steps[j]);
+            hessian[i][j] = h;
+            hessian[j][i] = h;
+        }
+        Ok(hessian)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, Problem, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::newton::NewtonCG;
+    use crate::solver::quasinewton::LBFGS;
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+
+    #[derive(Clone)]
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(rosenbrock_derivative(p))
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert_error!(
+            WithForwardDiff::new(Rosenbrock {}).with_step(0.0),
+            ArgminError,
+            "Invalid parameter: \"`WithForwardDiff`: step size must be > 0.\""
+        );
+        assert_error!(
+            WithCentralDiff::new(Rosenbrock {}).with_adaptive_step(-1.0, 10),
+            ArgminError,
+            "Invalid parameter: \"`WithCentralDiff`: tolerance must be > 0.\""
+        );
+        assert_error!(
+            WithCentralDiff::new(Rosenbrock {}).with_adaptive_step(1e-6, 0),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`WithCentralDiff`: ",
+                "maximum number of halvings must be > 0.\""
+            )
+        );
+        assert!(WithForwardDiff::new(Rosenbrock {})
+            .with_step(f64::NAN)
+            .is_err());
+    }
+
+    #[test]
+    fn test_forward_diff() {
+        let problem = WithForwardDiff::new(Rosenbrock {});
+        let x = vec![-1.2, 1.0, 0.5];
+        let grad = problem.gradient(&x).unwrap();
+        let expected = rosenbrock_derivative(&x);
+        for (g, e) in grad.iter().zip(expected.iter()) {
+            assert_relative_eq!(g, e, max_relative = 1e-6);
+        }
+        assert_eq!(problem.cost_count(), 4);
+    }
+
+    #[test]
+    fn test_central_diff() {
+        let problem = WithCentralDiff::new(Rosenbrock {});
+        let x = vec![-1.2, 1.0, 0.5];
+        let grad = problem.gradient(&x).unwrap();
+        let expected = rosenbrock_derivative(&x);
+        for (g, e) in grad.iter().zip(expected.iter()) {
+            assert_relative_eq!(g, e, max_relative = 1e-9);
+        }
+        assert_eq!(problem.cost_count(), 6);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_matches_sequential() {
+        #[derive(Clone)]
+        struct Sequential {}
+
+        impl CostFunction for Sequential {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(rosenbrock(p))
+            }
+
+            fn parallelize(&self) -> bool {
+                false
+            }
+        }
+
+        let x: Vec<f64> = (0..50).map(|i| f64::from(i) / 25.0 - 1.0).collect();
+        let parallel = WithCentralDiff::new(Rosenbrock {})
+            .with_adaptive_step(1e-8, 5)
+            .unwrap();
+        let sequential = WithCentralDiff::new(Sequential {})
+            .with_adaptive_step(1e-8, 5)
+            .unwrap();
+        let grad_par = parallel.gradient(&x).unwrap();
+        let grad_seq = sequential.gradient(&x).unwrap();
+        for (p, s) in grad_par.iter().zip(grad_seq.iter()) {
+            assert_eq!(p.to_ne_bytes(), s.to_ne_bytes());
+        }
+        assert_eq!(parallel.cost_count(), sequential.cost_count());
+    }
+
+    /// Evaluates Rosenbrock and counts the calls of `bulk_cost`
+    #[derive(Default)]
+    struct Vectorized {
+        bulk_calls: AtomicU64,
+    }
+
+    impl CostFunction for Vectorized {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(rosenbrock(p))
+        }
+
+        fn bulk_cost<P>(&self, params: &[P]) -> Result<Vec<Self::Output>, Error>
+        where
+            P: std::borrow::Borrow<Self::Param> + SyncAlias,
+            Self::Output: SendAlias,
+            Self: SyncAlias,
+        {
+            self.bulk_calls.fetch_add(1, Ordering::Relaxed);
+            Ok(params.iter().map(|p| rosenbrock(p.borrow())).collect())
+        }
+    }
+
+    #[test]
+    fn test_bulk_cost_used() {
+        let x = vec![-1.2, 1.0, 0.5];
+        let problem = WithForwardDiff::new(Vectorized::default());
+        let grad = problem.gradient(&x).unwrap();
+        for (g, e) in grad.iter().zip(rosenbrock_derivative(&x).iter()) {
+            assert_relative_eq!(g, e, max_relative = 1e-6);
+        }
+        assert_eq!(problem.inner().bulk_calls.load(Ordering::Relaxed), 1);
+        assert_eq!(problem.cost_count(), 4);
+
+        let problem = WithHessianFromCost::new(Vectorized::default());
+        let hessian = problem.hessian(&x).unwrap();
+        for (h, e) in hessian.iter().zip(rosenbrock_hessian(&x).iter()) {
+            for (h, e) in h.iter().zip(e.iter()) {
+                assert_relative_eq!(h, e, epsilon = 1e-3, max_relative = 1e-4);
+            }
+        }
+        assert_eq!(problem.inner().bulk_calls.load(Ordering::Relaxed), 1);
+        assert_eq!(problem.cost_count(), 1 + 3 + 6);
+
+        // The adaptive step requires sequential evaluations
+        let problem = WithCentralDiff::new(Vectorized::default())
+            .with_adaptive_step(1e-8, 5)
+            .unwrap();
+        problem.gradient(&x).unwrap();
+        assert_eq!(problem.inner().bulk_calls.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_adaptive_step() {
+        // A large step gives a poor estimate, which is improved by halving the step.
+        let x = vec![-1.2, 1.0];
+        let expected = rosenbrock_derivative(&x);
+
+        let fixed = WithCentralDiff::new(Rosenbrock {}).with_step(0.1).unwrap();
+        let grad = fixed.gradient(&x).unwrap();
+        assert!((grad[0] - expected[0]).abs() > 1e-1);
+
+        let adaptive = WithCentralDiff::new(Rosenbrock {})
+            .with_step(0.1)
+            .unwrap()
+            .with_adaptive_step(1e-8, 20)
+            .unwrap();
+        let grad = adaptive.gradient(&x).unwrap();
+        for (g, e) in grad.iter().zip(expected.iter()) {
+            assert_relative_eq!(g, e, max_relative = 1e-6);
+        }
+        assert!(adaptive.cost_count() > 4);
+    }
+
+    #[test]
+    fn test_cost_forwarded() {
+        let mut problem = Problem::new(WithForwardDiff::new(Rosenbrock {}));
+        let x = vec![1.0, 1.0];
+        assert_relative_eq!(problem.cost(&x).unwrap(), 0.0);
+        problem.gradient(&x).unwrap();
+        assert_eq!(problem.counts["cost_count"], 1);
+        assert_eq!(problem.counts["gradient_count"], 1);
+        assert_eq!(problem.problem.as_ref().unwrap().cost_count(), 3);
+    }
+
+    #[test]
+    fn test_ndarray() {
+        struct Sphere {}
+
+        impl CostFunction for Sphere {
+            type Param = ndarray::Array1<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p.iter().map(|x| x * x).sum())
+            }
+        }
+
+        let grad = WithCentralDiff::new(Sphere {})
+            .gradient(&ndarray::array![1.0, -2.0])
+            .unwrap();
+        assert_relative_eq!(grad[0], 2.0, epsilon = 1e-8);
+        assert_relative_eq!(grad[1], -4.0, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_lbfgs() {
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let solver = LBFGS::new(linesearch, 7);
+        let res = Executor::new(WithForwardDiff::new(Rosenbrock {}), solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+            .run()
+            .unwrap();
+
+        let best = res.state.get_best_param().unwrap();
+        assert_relative_eq!(best[0], 1.0, epsilon = 1e-4);
+        assert_relative_eq!(best[1], 1.0, epsilon = 1e-4);
+
+        let problem = res.problem.problem.as_ref().unwrap();
+        // Each gradient requires 3 evaluations of the cost function
+        assert_eq!(
+            problem.cost_count(),
+            3 * res.problem.counts["gradient_count"]
+        );
+    }
+
+    struct Analytic {}
+
+    impl CostFunction for Analytic {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p[0].exp() * p[1].sin() / (p[0] * p[0] + p[1] * p[1]).sqrt())
+        }
+    }
+
+    impl ComplexCostFunction for Analytic {
+        type Float = f64;
+
+        fn cost_complex(&self, p: &[Complex<f64>]) -> Result<Complex<f64>, Error> {
+            Ok(p[0].exp() * p[1].sin() / (p[0] * p[0] + p[1] * p[1]).sqrt())
+        }
+    }
+
+    #[test]
+    fn test_complex_step() {
+        let problem = WithComplexStep::new(Analytic {});
+        let x = vec![1.5, 0.5];
+        let grad = problem.gradient(&x).unwrap();
+
+        let (a, b) = (x[0], x[1]);
+        let r = (a * a + b * b).sqrt();
+        let f = a.exp() * b.sin() / r;
+        let expected = [
+            f * (1.0 - a / (r * r)),
+            a.exp() * b.cos() / r - f * b / (r * r),
+        ];
+        assert_relative_eq!(grad[0], expected[0], max_relative = 1e-15);
+        assert_relative_eq!(grad[1], expected[1], max_relative = 1e-15);
+        assert_eq!(problem.cost_count(), 2);
+
+        // The step can be chosen arbitrarily small
+        let grad = WithComplexStep::new(Analytic {})
+            .with_step(1e-200)
+            .unwrap()
+            .gradient(&x)
+            .unwrap();
+        assert_relative_eq!(grad[0], expected[0], max_relative = 1e-15);
+        assert_relative_eq!(grad[1], expected[1], max_relative = 1e-15);
+
+        assert_error!(
+            WithComplexStep::new(Analytic {}).with_step(-1e-20),
+            ArgminError,
+            "Invalid parameter: \"`WithComplexStep`: step size must be > 0.\""
+        );
+    }
+
+    fn assert_hessian(hessian: &[Vec<f64>], x: &[f64], max_relative: f64) {
+        let expected = rosenbrock_hessian(x);
+        for (row, expected_row) in hessian.iter().zip(expected.iter()) {
+            for (h, e) in row.iter().zip(expected_row.iter()) {
+                assert_relative_eq!(h, e, epsilon = 1e-3, max_relative = max_relative);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hessian_from_gradient() {
+        let x = vec![-1.2, 1.0, 0.5, 0.8, -0.3, 1.1];
+
+        let dense = WithHessianFromGradient::new(Rosenbrock {});
+        assert_hessian(&dense.hessian(&x).unwrap(), &x, 1e-4);
+        assert_eq!(dense.gradient_count(), 7);
+        assert_eq!(dense.hessian_sparsity(), None);
+
+        // Tridiagonal Hessian: three groups of columns
+        let pattern: Vec<_> = (0..5).map(|i| (i, i + 1)).collect();
+        let sparse = WithHessianFromGradient::new(Rosenbrock {}).with_sparsity(pattern.clone());
+        assert_hessian(&sparse.hessian(&x).unwrap(), &x, 1e-4);
+        assert_eq!(sparse.gradient_count(), 4);
+        assert_eq!(sparse.hessian_sparsity(), Some(pattern));
+    }
+
+    #[test]
+    fn test_hessian_from_cost() {
+        let x = vec![-1.2, 1.0, 0.5, 0.8];
+
+        let dense = WithHessianFromCost::new(Rosenbrock {});
+        assert_hessian(&dense.hessian(&x).unwrap(), &x, 1e-2);
+        assert_eq!(dense.cost_count(), 1 + 4 + 10);
+
+        let pattern = (0..3).map(|i| (i + 1, i)).collect();
+        let sparse = WithHessianFromCost::new(Rosenbrock {}).with_sparsity(pattern);
+        assert_hessian(&sparse.hessian(&x).unwrap(), &x, 1e-2);
+        assert_eq!(sparse.cost_count(), 1 + 4 + 7);
+    }
+
+    #[test]
+    fn test_invalid_sparsity() {
+        assert_error!(
+            WithHessianFromGradient::new(Rosenbrock {})
+                .with_sparsity(vec![(0, 2)])
+                .hessian(&vec![1.0, 1.0]),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`WithHessianFromGradient`: ",
+                "index (0, 2) of sparsity pattern out of bounds for n = 2.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_color_columns() {
+        // Arrow-shaped pattern: the first column couples with all others
+        let pattern: Vec<(usize, usize)> = (1..5).map(|i| (0, i)).collect();
+        let columns = sparsity_structure(Some(&pattern), 5, "test").unwrap();
+        let groups = color_columns(&columns);
+        assert_eq!(groups.len(), 5);
+
+        let pattern: Vec<(usize, usize)> = (0..7).map(|i| (i, i + 1)).collect();
+        let columns = sparsity_structure(Some(&pattern), 8, "test").unwrap();
+        assert_eq!(
+            color_columns(&columns),
+            vec![vec![0, 3, 6], vec![1, 4, 7], vec![2, 5]]
+        );
+    }
+
+    #[test]
+    fn test_newton_cg() {
+        // Convex with the minimum at (1, -2)
+        struct Convex {}
+
+        impl CostFunction for Convex {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                let (x, y) = (p[0] - 1.0, p[1] + 2.0);
+                Ok(x.exp() - x + 3.0 * y * y + x * y)
+            }
+        }
+
+        impl Gradient for Convex {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                let (x, y) = (p[0] - 1.0, p[1] + 2.0);
+                Ok(vec![x.exp() - 1.0 + y, 6.0 * y + x])
+            }
+        }
+
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let problem = WithHessianFromGradient::new(Convex {});
+        let res = Executor::new(problem, NewtonCG::new(linesearch))
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(20))
+            .run()
+            .unwrap();
+        let best = res.state.get_best_param().unwrap();
+        assert_relative_eq!(best[0], 1.0, epsilon = 1e-6);
+        assert_relative_eq!(best[1], -2.0, epsilon = 1e-6);
+        // Each Hessian requires 3 gradient evaluations
+        assert_eq!(
+            res.problem.problem.as_ref().unwrap().gradient_count(),
+            3 * res.problem.counts["hessian_count"]
+        );
+    }
+}
@@ -48,3 +48,44 @@ impl<T> SyncAlias for T where T: Sync {}
 
 #[cfg(not(feature = "rayon"))]
 impl<T> SyncAlias for T {}
+
+#[cfg(feature = "rayon")]
+std::thread_local! {
+    /// Thread pool of the `Executor` running on the current thread
+    static THREAD_POOL: std::cell::RefCell<Option<std::sync::Arc<rayon::ThreadPool>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Runs `func` in the thread pool set by the `Executor` running on the current thread.
+///
+/// If no thread pool is set, `func` is run directly, which means that parallel iterators use the
+/// thread pool they are called from (usually the global `rayon` thread pool).
+#[cfg(feature = "rayon")]
+pub(crate) fn install<T: Send, F: FnOnce() -> T + Send>(func: F) -> T {
+    match THREAD_POOL.with(|pool| pool.borrow().clone()) {
+        Some(pool) => pool.install(func),
+        None => func(),
+    }
+}
+
+/// Restores the previous thread pool of the current thread when dropped.
+#[cfg(feature = "rayon")]
+pub(crate) struct ThreadPoolGuard {
+    previous: Option<std::sync::Arc<rayon::ThreadPool>>,
+}
+
+#[cfg(feature = "rayon")]
+impl Drop for ThreadPoolGuard {
+    fn drop(&mut self) {
+        THREAD_POOL.with(|pool| *pool.borrow_mut() = self.previous.take());
+    }
+}
+
+/// Makes `pool` the thread pool used by [`install`] on the current thread until the returned
+/// guard is dropped.
+#[cfg(feature = "rayon")]
+pub(crate) fn set_thread_pool(pool: std::sync::Arc<rayon::ThreadPool>) -> ThreadPoolGuard {
+    ThreadPoolGuard {
+        previous: THREAD_POOL.with(|current| current.borrow_mut().replace(pool)),
+    }
+}
@@ -5,22 +5,34 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::{ArgminFloat, Error, SendAlias, SyncAlias};
+use crate::core::time::{Duration, Instant};
+use crate::core::{ArgminFloat, Error, MaxStepLength, SendAlias, SyncAlias};
+use argmin_math::ArgminL2Norm;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
+use std::any::Any;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Wrapper around problems defined by users.
 ///
 /// Keeps track of how many times methods such as `apply`, `cost`, `gradient`, `jacobian`,
-/// `hessian`, `anneal` and so on are called. It is used to pass the problem from one iteration of
-/// a solver to the next.
+/// `hessian`, `anneal` and so on are called and how much time is spent in them. It is used to
+/// pass the problem from one iteration of a solver to the next.
 #[derive(Clone, Debug, Default)]
 pub struct Problem<O> {
     /// Problem defined by user
     pub problem: Option<O>,
     /// Keeps track of how often methods of `problem` have been called.
     pub counts: HashMap<&'static str, u64>,
+    /// Keeps track of the time spent in methods of `problem` (labeled like `counts`).
+    pub timings: HashMap<&'static str, Duration>,
+    /// Point in time at which the time limit of the run is exhausted (set by the `Executor`)
+    deadline: Option<Instant>,
+    /// Maximum step length per iteration (set by the `Executor`)
+    max_step: Option<MaxStepLength>,
+    /// Per-component move limits (set by the `Executor`, of the type of the parameter vector)
+    move_limits: Option<Arc<dyn Any + Send + Sync>>,
 }
 
 impl<O> Problem<O> {
@@ -39,17 +51,23 @@ impl<O> Problem<O> {
     /// #
     /// # assert_eq!(wrapped_problem.problem.unwrap(), UserDefinedProblem {});
     /// # assert_eq!(wrapped_problem.counts, HashMap::new());
+    /// # assert_eq!(wrapped_problem.timings, HashMap::new());
     /// ```
     pub fn new(problem: O) -> Self {
         Problem {
             problem: Some(problem),
             counts: HashMap::new(),
+            timings: HashMap::new(),
+            deadline: None,
+            max_step: None,
+            move_limits: None,
         }
     }
 
     /// Gives access to the stored `problem` via the closure `func` and keeps track of how many
-    /// times the function has been called. The function counts will be passed to observers labeled
-    /// with `counts_string`. Per convention, `counts_string` is chosen as `<something>_count`.
+    /// times the function has been called and how long the calls took. The function counts will be
+    /// passed to observers labeled with `counts_string`. Per convention, `counts_string` is chosen
+    /// as `<something>_count`.
     ///
     /// # Example
     ///
@@ -58,7 +76,8 @@ impl<O> Problem<O> {
     /// # let mut problem = Problem::new(TestProblem::new());
     /// # let param = vec![1.0f64, 0.0];
     /// let cost = problem.problem("cost_count", |problem| problem.cost(&param));
-    /// # assert_eq!(problem.counts["cost_count"], 1)
+    /// # assert_eq!(problem.counts["cost_count"], 1);
+    /// # assert!(problem.timings.contains_key("cost_count"));
     /// ```
     ///
     /// This is typically used when designing a trait which optimization problems need to implement
@@ -76,6 +95,7 @@ impl<O> Problem<O> {
     /// # pub struct Problem<O> {
     /// #     pub problem: Option<O>,
     /// #     pub counts: HashMap<&'static str, u64>,
+    /// #     pub timings: HashMap<&'static str, std::time::Duration>,
     /// # }
     /// # impl<O> Problem<O> {
     /// #     pub fn problem<T, F: FnOnce(&O) -> Result<T, Error>>(
@@ -114,7 +134,11 @@ impl<O> Problem<O> {
     /// #         Ok(())
     /// #     }
     /// # }
-    /// # let mut problem = Problem { problem: Some(TestProblem {}), counts: HashMap::new() };
+    /// # let mut problem = Problem {
+    /// #     problem: Some(TestProblem {}),
+    /// #     counts: HashMap::new(),
+    /// #     timings: HashMap::new(),
+    /// # };
     /// # let param = ();
     /// let new_param = problem.anneal(&param, 1.0f64);
     /// # assert_eq!(problem.counts["anneal_count"], 1)
@@ -131,7 +155,7 @@ impl<O> Problem<O> {
     ) -> Result<T, Error> {
         let count = self.counts.entry(counts_string).or_insert(0);
         *count += 1;
-        func(self.problem.as_ref().unwrap())
+        self.timed(counts_string, func)
     }
 
     /// Gives access to the stored `problem` via the closure `func` and keeps track of how many
@@ -148,7 +172,20 @@ impl<O> Problem<O> {
     ) -> Result<T, Error> {
         let count = self.counts.entry(counts_string).or_insert(0);
         *count += num_param_vecs as u64;
-        func(self.problem.as_ref().unwrap())
+        self.timed(counts_string, func)
+    }
+
+    /// Calls `func` on the stored problem and adds the elapsed time to the timings.
+    fn timed<T, F: FnOnce(&O) -> Result<T, Error>>(
+        &mut self,
+        counts_string: &'static str,
+        func: F,
+    ) -> Result<T, Error> {
+        enter_span!(TRACE, "operator", method = counts_string);
+        let start = Instant::now();
+        let res = func(self.problem.as_ref().unwrap());
+        *self.timings.entry(counts_string).or_default() += start.elapsed();
+        res
     }
 
     /// Returns the internally stored problem and replaces it with `None`.
@@ -216,7 +253,8 @@ impl<O> Problem<O> {
         self.consume_func_counts(other);
     }
 
-    /// Consumes another instance of `Problem` by summing ob the function evaluation counts.
+    /// Consumes another instance of `Problem` by summing up the function evaluation counts and
+    /// timings.
     /// In contrast to `consume_problem`, the internally stored `problem` remains untouched.
     /// Therefore the two internally stored problems do not need to be of the same type.
     ///
@@ -261,9 +299,12 @@ impl<O> Problem<O> {
             let count = self.counts.entry(k).or_insert(0);
             *count += v
         }
+        for (k, v) in other.timings.iter() {
+            *self.timings.entry(k).or_default() += *v;
+        }
     }
 
-    /// Resets the function evaluation counts to zero.
+    /// Resets the function evaluation counts and timings to zero.
     ///
     /// # Example
     ///
@@ -292,6 +333,122 @@ impl<O> Problem<O> {
         for (_, v) in self.counts.iter_mut() {
             *v = 0;
         }
+        for (_, v) in self.timings.iter_mut() {
+            *v = Duration::ZERO;
+        }
+    }
+
+    /// Sets the point in time at which the time limit of the run is exhausted.
+    pub(crate) fn deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    /// Returns `true` if the wall-clock time limit set via
+    /// [`Executor::max_time`](`crate::core::Executor::max_time`) is exhausted.
+    ///
+    /// The `Executor` checks the time limit only between iterations. Solvers with long-running
+    /// loops within a single iteration (for instance evaluating a large population one individual
+    /// at a time) can call this method to stop early. If no time limit is set, this always returns
+    /// `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Problem;
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// let problem = Problem::new(UserDefinedProblem {});
+    /// assert!(!problem.time_limit_reached());
+    /// ```
+    pub fn time_limit_reached(&self) -> bool {
+        self.deadline
+            .map(|deadline| Instant::now() >= deadline)
+            .unwrap_or(false)
+    }
+
+    /// Sets the maximum step length per iteration.
+    pub(crate) fn set_max_step_length(&mut self, max_step: Option<MaxStepLength>) {
+        self.max_step = max_step;
+    }
+
+    /// Returns the maximum step length at the parameter vector `param` as set via
+    /// [`Executor::max_step_length`](`crate::core::Executor::max_step_length`), or `None` if the
+    /// step length is not limited.
+    ///
+    /// Solvers with a line search pass it on via
+    /// [`LineSearch::max_step_length`](`crate::core::LineSearch::max_step_length`), trust region
+    /// methods limit the radius accordingly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Problem;
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// let problem = Problem::new(UserDefinedProblem {});
+    /// assert!(problem.max_step_length::<_, f64>(&vec![1.0f64, 2.0]).is_none());
+    /// ```
+    pub fn max_step_length<P, F>(&self, param: &P) -> Option<F>
+    where
+        P: ArgminL2Norm<F>,
+        F: ArgminFloat,
+    {
+        self.max_step
+            .as_ref()
+            .map(|max_step| max_step.max_step_length(param))
+    }
+
+    /// Sets the per-component move limits.
+    pub(crate) fn set_move_limits<P>(&mut self, move_limits: Option<P>)
+    where
+        P: Send + Sync + 'static,
+    {
+        self.move_limits = move_limits.map(|move_limits| Arc::new(move_limits) as _);
+    }
+
+    /// Returns the per-component move limits as set via
+    /// [`Executor::move_limits`](`crate::core::Executor::move_limits`), or `None` if no move
+    /// limits were set or if they are not of type `P`.
+    ///
+    /// Solvers with a line search pass them on via
+    /// [`LineSearch::move_limits`](`crate::core::LineSearch::move_limits`), trust region methods
+    /// shorten their steps accordingly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Problem;
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// let problem = Problem::new(UserDefinedProblem {});
+    /// assert!(problem.move_limits::<Vec<f64>>().is_none());
+    /// ```
+    pub fn move_limits<P>(&self) -> Option<&P>
+    where
+        P: 'static,
+    {
+        self.move_limits
+            .as_ref()
+            .and_then(|move_limits| move_limits.downcast_ref::<P>())
+    }
+
+    /// Returns the total time spent in methods of the problem.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{test_utils::TestProblem, Problem, CostFunction};
+    /// # let mut problem = Problem::new(TestProblem::new());
+    /// # let param = vec![1.0f64, 0.0];
+    /// let cost = problem.problem("cost_count", |problem| problem.cost(&param));
+    /// let time = problem.total_time();
+    /// # assert_eq!(time, problem.timings["cost_count"]);
+    /// ```
+    pub fn total_time(&self) -> Duration {
+        self.timings.values().sum()
     }
 
     /// Returns the internally stored user defined problem by consuming `Self`.
@@ -383,6 +540,10 @@ pub trait CostFunction {
 
 /// Defines the computation of the gradient.
 ///
+/// For complex parameter vectors `z` and a real-valued cost function `f`, the gradient is
+/// expected to be `df/dRe(z) + i * df/dIm(z)`, which equals twice the Wirtinger derivative
+/// `df/dz*` and is the direction of steepest ascent.
+///
 /// # Example
 ///
 /// ```
@@ -441,6 +602,40 @@ pub trait Hessian {
     /// Compute Hessian
     fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error>;
 
+    /// Returns the sparsity pattern of the Hessian as a list of indices `(i, j)` of nonzero
+    /// elements, or `None` if the Hessian is dense (default).
+    ///
+    /// This is purely informational and allows users of the problem to assemble sparse Hessians
+    /// (for instance a `CsMat` of the `sprs` backend, which Newton and trust region methods can
+    /// solve via a sparse `LDL^T` decomposition) or to reduce the number of evaluations needed
+    /// to approximate the Hessian.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::{Hessian, Error};
+    /// # fn compute_hessian(_a: &[f64]) -> Vec<Vec<f64>> { vec![] }
+    ///
+    /// struct Chain {}
+    ///
+    /// impl Hessian for Chain {
+    ///     type Param = Vec<f64>;
+    ///     type Hessian = Vec<Vec<f64>>;
+    ///
+    ///     fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+    ///         Ok(compute_hessian(&param))
+    ///     }
+    ///
+    ///     /// Tridiagonal Hessian of a problem with three parameters
+    ///     fn hessian_sparsity(&self) -> Option<Vec<(usize, usize)>> {
+    ///         Some(vec![(0, 0), (0, 1), (1, 1), (1, 2), (2, 2)])
+    ///     }
+    /// }
+    /// ```
+    fn hessian_sparsity(&self) -> Option<Vec<(usize, usize)>> {
+        None
+    }
+
     bulk!(hessian, Self::Param, Self::Hessian);
 }
 
@@ -814,6 +1009,37 @@ impl<O: Hessian> Problem<O> {
         self.problem("hessian_count", |problem| problem.hessian(param))
     }
 
+    /// Returns the sparsity pattern of the Hessian as defined by `hessian_sparsity` of the
+    /// `Hessian` trait. Returns `None` if the Hessian is dense or if the problem has been taken.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Hessian, Error};
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Hessian for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Hessian = Vec<Vec<f64>>;
+    /// #
+    /// #     fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+    /// #         Ok(vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]])
+    /// #     }
+    /// #
+    /// #     fn hessian_sparsity(&self) -> Option<Vec<(usize, usize)>> {
+    /// #         Some(vec![(0, 0), (1, 1)])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Hessian`.
+    /// let problem = Problem::new(UserDefinedProblem {});
+    ///
+    /// assert_eq!(problem.hessian_sparsity(), Some(vec![(0, 0), (1, 1)]));
+    /// ```
+    pub fn hessian_sparsity(&self) -> Option<Vec<(usize, usize)>> {
+        self.problem.as_ref().and_then(|p| p.hessian_sparsity())
+    }
+
     /// Calls `bulk_hessian` defined in the `Hessian` trait and keeps track of the number of
     /// evaluations.
     ///
@@ -0,0 +1,201 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminError, Error, State};
+use std::fmt;
+
+/// Behavior in case an iteration failed with a recoverable error.
+pub enum RecoveryAction<I> {
+    /// Return the error.
+    Abort,
+    /// Restore the state from before the failed iteration and restart the solver via
+    /// [`Solver::restart`](`crate::core::Solver::restart`), which discards information the
+    /// solver has accumulated (for instance the curvature pairs of L-BFGS).
+    Reset,
+    /// Like [`Reset`](`RecoveryAction::Reset`), but the restored state is modified with the given
+    /// function first, for instance by perturbing the parameter vector.
+    Perturb(fn(&mut I)),
+}
+
+impl<I> Clone for RecoveryAction<I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I> Copy for RecoveryAction<I> {}
+
+impl<I> fmt::Debug for RecoveryAction<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecoveryAction::Abort => f.write_str("Abort"),
+            RecoveryAction::Reset => f.write_str("Reset"),
+            RecoveryAction::Perturb(_) => f.write_str("Perturb"),
+        }
+    }
+}
+
+/// Handling of recoverable errors during a run.
+///
+/// Errors are recoverable if they are an [`ArgminError`] for which
+/// [`is_recoverable`](`ArgminError::is_recoverable`) returns `true`, such as failed line
+/// searches, singular matrices and non-finite values (including those detected by a
+/// [`NonFiniteCheck`](`crate::core::NonFiniteCheck`) with
+/// [`NonFinitePolicy::Abort`](`crate::core::NonFinitePolicy::Abort`)). All other errors end the
+/// run as usual. If an iteration fails with a recoverable error, the [`RecoveryAction`] is taken
+/// and the run continues with the next iteration. A failed iteration counts towards the maximum
+/// number of iterations. After `max_retries` (default: `3`) consecutive failures, the error is
+/// returned.
+///
+/// The policy is enabled via [`Executor::recovery`](`crate::core::Executor::recovery`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{IterState, RecoveryAction, RecoveryPolicy};
+/// type State = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+///
+/// let policy: RecoveryPolicy<State> =
+///     RecoveryPolicy::new(RecoveryAction::Perturb(|state: &mut State| {
+///         if let Some(param) = state.param.as_mut() {
+///             param.iter_mut().for_each(|x| *x *= 1.01);
+///         }
+///     }))
+///     .with_max_retries(5);
+/// ```
+pub struct RecoveryPolicy<I: State> {
+    /// Behavior in case of recoverable errors
+    action: RecoveryAction<I>,
+    /// Maximum number of consecutive failed iterations
+    max_retries: u64,
+    /// Number of consecutive failed iterations so far
+    retries: u64,
+    /// Copies the state
+    snapshot: fn(&I) -> I,
+}
+
+impl<I> RecoveryPolicy<I>
+where
+    I: State + Clone,
+{
+    /// Constructs a new `RecoveryPolicy` which takes `action` in case of recoverable errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, RecoveryAction, RecoveryPolicy};
+    /// let policy: RecoveryPolicy<IterState<Vec<f64>, Vec<f64>, (), (), (), f64>> =
+    ///     RecoveryPolicy::new(RecoveryAction::Reset);
+    /// ```
+    pub fn new(action: RecoveryAction<I>) -> Self {
+        RecoveryPolicy {
+            action,
+            max_retries: 3,
+            retries: 0,
+            snapshot: I::clone,
+        }
+    }
+}
+
+impl<I: State> RecoveryPolicy<I> {
+    /// Sets the maximum number of consecutive failed iterations (default: `3`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, RecoveryAction, RecoveryPolicy};
+    /// let policy: RecoveryPolicy<IterState<Vec<f64>, Vec<f64>, (), (), (), f64>> =
+    ///     RecoveryPolicy::new(RecoveryAction::Reset).with_max_retries(10);
+    /// ```
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: u64) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Returns `true` if a copy of the state needs to be kept before each iteration.
+    pub(crate) fn needs_snapshot(&self) -> bool {
+        !matches!(self.action, RecoveryAction::Abort)
+    }
+
+    /// Copies `state` such that it can be restored later.
+    pub(crate) fn snapshot(&self, state: &I) -> I {
+        (self.snapshot)(state)
+    }
+
+    /// Called after a successful iteration. Returns the number of failed attempts before it.
+    pub(crate) fn succeeded(&mut self) -> u64 {
+        std::mem::replace(&mut self.retries, 0)
+    }
+
+    /// Decides whether the iteration which failed with `err` is retried. If so, `state` (the
+    /// state before the failed iteration) is prepared for the retry and returned.
+    pub(crate) fn recover(&mut self, err: Error, state: Option<I>) -> Result<I, Error> {
+        let recoverable = err
+            .downcast_ref::<ArgminError>()
+            .is_some_and(ArgminError::is_recoverable);
+        let mut state = match state {
+            Some(state) if recoverable && self.retries < self.max_retries => state,
+            _ => return Err(err),
+        };
+        self.retries += 1;
+        if let RecoveryAction::Perturb(perturb) = self.action {
+            perturb(&mut state);
+        }
+        Ok(state)
+    }
+}
+
+impl<I> fmt::Debug for RecoveryPolicy<I>
+where
+    I: State,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecoveryPolicy")
+            .field("action", &self.action)
+            .field("max_retries", &self.max_retries)
+            .field("retries", &self.retries)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::IterState;
+
+    type TestState = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+
+    #[test]
+    fn test_recover() {
+        let mut policy: RecoveryPolicy<TestState> =
+            RecoveryPolicy::new(RecoveryAction::Perturb(|state: &mut TestState| {
+                state.param = Some(vec![2.0]);
+            }))
+            .with_max_retries(2);
+        let state = || Some(TestState::new().param(vec![1.0]));
+        let failure = || argmin_error!(LineSearchFailed, "no step found");
+
+        let recovered = policy.recover(failure(), state()).unwrap();
+        assert_eq!(recovered.get_param(), Some(&vec![2.0]));
+        assert!(policy.recover(failure(), state()).is_ok());
+        // Too many consecutive failures
+        assert!(policy.recover(failure(), state()).is_err());
+        assert_eq!(policy.succeeded(), 2);
+        assert!(policy.recover(failure(), state()).is_ok());
+
+        // Errors which are not recoverable
+        let err = policy
+            .recover(argmin_error!(InvalidParameter, "wrong"), state())
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "Invalid parameter: \"wrong\"");
+        assert!(policy
+            .recover(Error::msg("problem failed"), state())
+            .is_err());
+    }
+}
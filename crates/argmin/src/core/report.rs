@@ -0,0 +1,53 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::time::Duration;
+use crate::core::TerminationStatus;
+#[cfg(feature = "serde1")]
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Structured summary of an optimization run.
+///
+/// Created via [`OptimizationResult::to_report`](`crate::core::OptimizationResult::to_report`).
+/// It borrows from the result and contains the termination status, the final and best parameter
+/// vectors and cost function values, the function evaluation counts, timings and the
+/// configuration of the solver.
+///
+/// With the `serde1` feature enabled, the report implements `Serialize` and can therefore be
+/// written in any format supported by serde, for instance as JSON via `serde_json`. This is
+/// useful for archiving the results of many runs in a machine-readable way.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize))]
+pub struct Report<'a, S, P, F> {
+    /// Name of the solver
+    pub solver: &'a str,
+    /// Configuration of the solver
+    pub solver_config: &'a S,
+    /// Termination status
+    pub termination_status: &'a TerminationStatus,
+    /// Final parameter vector
+    pub param: Option<&'a P>,
+    /// Cost function value of the final parameter vector
+    pub cost: F,
+    /// Best parameter vector
+    pub best_param: Option<&'a P>,
+    /// Best cost function value
+    pub best_cost: F,
+    /// Total number of iterations
+    pub iters: u64,
+    /// Iteration in which the best parameter vector was found
+    pub last_best_iter: u64,
+    /// Function evaluation counts
+    pub func_counts: &'a HashMap<&'static str, u64>,
+    /// Total time measured by the `Executor` (requires the timer to be enabled)
+    pub time: Option<Duration>,
+    /// Time spent in methods of the problem
+    pub operator_time: Duration,
+    /// Time spent in the solver itself (requires the timer to be enabled)
+    pub solver_time: Option<Duration>,
+}
@@ -5,27 +5,32 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::{ArgminFloat, Problem, Solver, State};
+use crate::core::time::Duration;
+use crate::core::{ArgminFloat, History, Problem, Report, Solver, State, KV};
 use num_traits::{Float, FromPrimitive};
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt;
 
 /// Result of an optimization returned by after running an `Executor`.
 ///
-/// Consists of the problem and the final state of the solver.
-/// Both can be accessed via deconstructing or via the methods
-/// [`problem`](`OptimizationResult::problem`) and [`state`](`OptimizationResult::state`).
-#[derive(Clone)]
-pub struct OptimizationResult<O, S, I> {
+/// Consists of the problem, the solver, the final state of the solver and, if enabled via
+/// [`Executor::history`](`crate::core::Executor::history`), the history of the run.
+/// All can be accessed via deconstructing or via the methods
+/// [`problem`](`OptimizationResult::problem`), [`state`](`OptimizationResult::state`) and
+/// [`history`](`OptimizationResult::history`).
+pub struct OptimizationResult<O, S, I: State> {
     /// Problem
     pub problem: Problem<O>,
     /// Solver
     pub solver: S,
     /// Iteration state
     pub state: I,
+    /// History of the run (only available if enabled)
+    pub history: Option<History<I>>,
 }
 
-impl<O, S, I> OptimizationResult<O, S, I> {
+impl<O, S, I: State> OptimizationResult<O, S, I> {
     /// Constructs a new instance of `OptimizationResult` from a `problem` and a `state`.
     ///
     /// # Example
@@ -43,7 +48,7 @@ impl<O, S, I> OptimizationResult<O, S, I> {
     /// let solver = SomeSolver {};
     ///
     /// let result = OptimizationResult::new(Problem::new(rosenbrock), solver, state);
-    /// # let OptimizationResult { mut problem, solver, state } = result;
+    /// # let OptimizationResult { mut problem, solver, state, .. } = result;
     /// # assert_eq!(problem.take_problem().unwrap(), TestProblem::new());
     /// # assert_eq!(solver, SomeSolver {});
     /// ```
@@ -52,6 +57,7 @@ impl<O, S, I> OptimizationResult<O, S, I> {
             problem,
             solver,
             state,
+            history: None,
         }
     }
 
@@ -95,6 +101,82 @@ impl<O, S, I> OptimizationResult<O, S, I> {
         &self.solver
     }
 
+    /// Returns the internals exposed by the solver via [`Solver::internals`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Executor, Error, State};
+    /// # use argmin::solver::trustregion::{CauchyPoint, TrustRegion};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver = TrustRegion::new(CauchyPoint::new());
+    /// let result = Executor::new(TestProblem::new(), solver)
+    ///     .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(3))
+    ///     .run()?;
+    /// let radius = result.internals().get("radius").unwrap().get_float();
+    /// # assert!(radius.is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn internals(&self) -> KV
+    where
+        S: Solver<O, I>,
+    {
+        self.solver.internals()
+    }
+
+    /// Returns structured information about why the run terminated.
+    ///
+    /// Contains the termination reason as `reason`, merged with the diagnostics of the solver (see
+    /// [`Solver::termination_diagnostics`]), for instance which convergence tolerance was met and
+    /// with which values, and the internals exposed by the solver (see [`Solver::internals`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CostFunction, Error, Executor, Gradient, State};
+    /// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+    /// # use argmin::solver::quasinewton::LBFGS;
+    /// # use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+    /// # struct Rosenbrock {}
+    /// # impl CostFunction for Rosenbrock {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = f64;
+    /// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(rosenbrock(p))
+    /// #     }
+    /// # }
+    /// # impl Gradient for Rosenbrock {
+    /// #     type Param = Vec<f64>;
+    /// #     type Gradient = Vec<f64>;
+    /// #     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+    /// #         Ok(rosenbrock_derivative(p))
+    /// #     }
+    /// # }
+    /// # fn main() -> Result<(), Error> {
+    /// let solver = LBFGS::new(MoreThuenteLineSearch::new(), 7);
+    /// let result = Executor::new(Rosenbrock {}, solver)
+    ///     .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+    ///     .run()?;
+    /// let diagnostics = result.termination_diagnostics();
+    /// // Which tolerance was met and with which values
+    /// let grad_norm = diagnostics.get("grad_norm").unwrap().get_float();
+    /// let grad_tol = diagnostics.get("grad_tol").unwrap().get_float();
+    /// # assert_eq!(diagnostics.get("reason").unwrap().get_string().unwrap(), "Solver converged");
+    /// # assert!(grad_norm.unwrap() < grad_tol.unwrap());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn termination_diagnostics(&self) -> KV
+    where
+        S: Solver<O, I>,
+    {
+        kv!("reason" => self.state.get_termination_status().to_string();)
+            .merge(self.solver.termination_diagnostics(&self.state))
+            .merge(self.solver.internals())
+    }
+
     /// Returns a reference to the stored state.
     ///
     /// # Example
@@ -114,6 +196,175 @@ impl<O, S, I> OptimizationResult<O, S, I> {
     pub fn state(&self) -> &I {
         &self.state
     }
+
+    /// Returns the number of evaluations of each function of the problem (such as `cost_count`,
+    /// `gradient_count`, `hessian_count`, ...).
+    ///
+    /// The counts are always tracked by [`Problem`], independent of whether counting is enabled in
+    /// the state, and include evaluations performed by nested solvers such as line searches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, OptimizationResult, IterState, State};
+    /// #
+    /// # struct Rosenbrock {}
+    /// # let solver = ();
+    /// #
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// #
+    /// # let mut problem = Problem::new(Rosenbrock {});
+    /// # problem.counts.insert("cost_count", 12);
+    /// # let result = OptimizationResult::new(problem, solver, state);
+    /// #
+    /// let cost_count = result.func_counts().get("cost_count").copied().unwrap_or(0);
+    /// # assert_eq!(cost_count, 12);
+    /// ```
+    pub fn func_counts(&self) -> &HashMap<&'static str, u64> {
+        &self.problem.counts
+    }
+
+    /// Returns the time spent in methods of the problem (such as `cost`, `gradient`, ...),
+    /// including calls by nested solvers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, OptimizationResult, IterState, State};
+    /// #
+    /// # struct Rosenbrock {}
+    /// # let solver = ();
+    /// #
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// #
+    /// # let mut problem = Problem::new(Rosenbrock {});
+    /// # problem.timings.insert("cost_count", std::time::Duration::from_secs(2));
+    /// # let result = OptimizationResult::new(problem, solver, state);
+    /// #
+    /// let operator_time = result.operator_time();
+    /// # assert_eq!(operator_time, std::time::Duration::from_secs(2));
+    /// ```
+    pub fn operator_time(&self) -> Duration {
+        self.problem.total_time()
+    }
+
+    /// Returns the time spent in the solver itself, which is the total time minus the time spent in
+    /// methods of the problem. Note that the total time is only measured if the timer of the
+    /// `Executor` is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, OptimizationResult, IterState, State};
+    /// #
+    /// # struct Rosenbrock {}
+    /// # let solver = ();
+    /// #
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # state.time(Some(std::time::Duration::from_secs(5)));
+    /// #
+    /// # let mut problem = Problem::new(Rosenbrock {});
+    /// # problem.timings.insert("cost_count", std::time::Duration::from_secs(2));
+    /// # let result = OptimizationResult::new(problem, solver, state);
+    /// #
+    /// let solver_time = result.solver_time();
+    /// # assert_eq!(solver_time, Some(std::time::Duration::from_secs(3)));
+    /// ```
+    pub fn solver_time(&self) -> Option<Duration> {
+        self.state
+            .get_time()
+            .map(|time| time.saturating_sub(self.operator_time()))
+    }
+
+    /// Returns a reference to the recorded history, if available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, OptimizationResult, IterState, History, State};
+    /// #
+    /// # struct Rosenbrock {}
+    /// # let solver = ();
+    /// #
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// #
+    /// # let result = OptimizationResult::new(Problem::new(Rosenbrock {}), solver, state);
+    /// #
+    /// let history: Option<&History<IterState<Vec<f64>, (), (), (), (), f64>>> = result.history();
+    /// # assert!(history.is_none());
+    /// ```
+    pub fn history(&self) -> Option<&History<I>> {
+        self.history.as_ref()
+    }
+}
+
+impl<O, S, I> OptimizationResult<O, S, I>
+where
+    S: Solver<O, I>,
+    I: State,
+{
+    /// Returns a structured summary of the run (see [`Report`]).
+    ///
+    /// With the `serde1` feature enabled, the report can be serialized, for instance to JSON.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, Report};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let result = Executor::new(problem, solver)
+    ///     .configure(|state| state.param(vec![1.0, 0.0]).max_iters(10))
+    ///     .timer(true)
+    ///     .run()?;
+    ///
+    /// let report = result.to_report();
+    /// # assert_eq!(report.solver, "TestSolver");
+    /// # assert_eq!(report.iters, 10);
+    ///
+    /// # #[cfg(feature = "serde1")]
+    /// let json = serde_json::to_string_pretty(&report)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_report(&self) -> Report<'_, S, I::Param, I::Float> {
+        Report {
+            solver: self.solver.name(),
+            solver_config: &self.solver,
+            termination_status: self.state.get_termination_status(),
+            param: self.state.get_param(),
+            cost: self.state.get_cost(),
+            best_param: self.state.get_best_param(),
+            best_cost: self.state.get_best_cost(),
+            iters: self.state.get_iter(),
+            last_best_iter: self.state.get_last_best_iter(),
+            func_counts: self.func_counts(),
+            time: self.state.get_time(),
+            operator_time: self.operator_time(),
+            solver_time: self.solver_time(),
+        }
+    }
+}
+
+impl<O, S, I> Clone for OptimizationResult<O, S, I>
+where
+    O: Clone,
+    S: Clone,
+    I: State + Clone,
+    I::Param: Clone,
+{
+    fn clone(&self) -> Self {
+        OptimizationResult {
+            problem: self.problem.clone(),
+            solver: self.solver.clone(),
+            state: self.state.clone(),
+            history: self.history.clone(),
+        }
+    }
 }
 
 impl<O, S, I> std::fmt::Display for OptimizationResult<O, S, I>
@@ -144,6 +395,12 @@ where
         )?;
         if let Some(time) = self.state.get_time() {
             writeln!(f, "    time:          {time:?}")?;
+            writeln!(f, "      (operator):  {:?}", self.problem.total_time())?;
+            writeln!(
+                f,
+                "      (solver):    {:?}",
+                time.saturating_sub(self.problem.total_time())
+            )?;
         }
         Ok(())
     }
@@ -202,5 +459,63 @@ mod tests {
         OptimizationResult<TestProblem, TestSolver, IterState<(), (), (), (), (), f64>>
     );
 
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_report_json() {
+        use crate::core::Executor;
+
+        let result = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(3))
+            .run()
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::to_value(result.to_report()).unwrap();
+        assert_eq!(json["solver"], "TestSolver");
+        assert_eq!(json["iters"], 3);
+        assert_eq!(json["param"], serde_json::json!([1.0, 2.0]));
+        assert_eq!(
+            json["termination_status"],
+            serde_json::json!({"Terminated": "MaxItersReached"})
+        );
+    }
+
+    #[test]
+    fn test_solver_time() {
+        use crate::core::Executor;
+
+        for timer in [false, true] {
+            let result = Executor::new(TestProblem::new(), TestSolver::new())
+                .configure(|state| state.param(vec![1.0, 2.0]).max_iters(3))
+                .timer(timer)
+                .run()
+                .unwrap();
+            assert_eq!(result.state.get_time().is_some(), timer);
+            assert_eq!(result.solver_time().is_some(), timer);
+            assert_eq!(format!("{result}").contains("time:"), timer);
+        }
+    }
+
+    #[test]
+    fn test_report_time() {
+        use crate::core::Executor;
+
+        let result = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(3))
+            .run()
+            .unwrap();
+        let report = result.to_report();
+        assert!(report.time.is_none());
+        assert!(report.solver_time.is_none());
+
+        let result = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(3))
+            .timer(true)
+            .run()
+            .unwrap();
+        let report = result.to_report();
+        assert!(report.time.unwrap() > Duration::ZERO);
+        assert!(report.solver_time.is_some());
+    }
+
     // TODO: More tests, in particular the checking that the output is as intended.
 }
@@ -0,0 +1,362 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, CostFunction, Error, Gradient, Hessian};
+use std::ops::IndexMut;
+
+/// Diagonal scaling of the parameter vector.
+///
+/// Line searches, trust regions and stopping criteria implicitly assume that all components of
+/// the parameter vector are of similar magnitude. Physical models often violate this assumption,
+/// for instance if one parameter is a length in meters and another one is a concentration in the
+/// order of `1e-9`. `Scaled` lets the solver operate on `y = D x` instead of `x`, where
+/// `D = diag(d)` should be chosen such that all components of `y` are of order one. The gradient
+/// and Hessian seen by the solver are `D^-1 g(x)` and `D^-1 H(x) D^-1`, respectively.
+/// Consequently, Hessian approximations of quasi-Newton methods are built in the scaled space as
+/// well.
+///
+/// All parameter vectors seen by the solver, including the initial parameter vector and the
+/// parameter vectors in the final state, live in the scaled space. Use
+/// [`to_scaled`](`Scaled::to_scaled`) and [`to_unscaled`](`Scaled::to_unscaled`) to convert
+/// between both spaces.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{CostFunction, Error, Executor, Gradient, Scaled, State};
+/// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+/// # use argmin::solver::quasinewton::LBFGS;
+/// # struct Model {}
+/// # impl CostFunction for Model {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok((p[0] - 2.0).powi(2) + (p[1] * 1e6 - 3.0).powi(2))
+/// #     }
+/// # }
+/// # impl Gradient for Model {
+/// #     type Param = Vec<f64>;
+/// #     type Gradient = Vec<f64>;
+/// #     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+/// #         Ok(vec![2.0 * (p[0] - 2.0), 2e6 * (p[1] * 1e6 - 3.0)])
+/// #     }
+/// # }
+/// # fn main() -> Result<(), Error> {
+/// // The second parameter is of order 1e-6
+/// let problem = Scaled::from_typical_values(Model {}, vec![1.0, 1e-6])?;
+/// let init_param = problem.to_scaled(&vec![1.0, 1e-6])?;
+/// let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.param(init_param).max_iters(20))
+///     .run()?;
+/// let problem = res.problem.problem.as_ref().unwrap();
+/// let best = problem.to_unscaled(res.state().get_best_param().unwrap())?;
+/// # assert!((best[1] - 3e-6).abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Scaled<O, F> {
+    /// Wrapped problem
+    op: O,
+    /// Diagonal of the scaling matrix
+    scales: Vec<F>,
+}
+
+impl<O, F: ArgminFloat> Scaled<O, F> {
+    /// Constructs a new `Scaled` which wraps `op` and lets the solver operate on `y = D x` with
+    /// `D = diag(scales)`.
+    ///
+    /// All scales must be finite and nonzero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Scaled};
+    /// # struct UserDefinedProblem {}
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = Scaled::new(UserDefinedProblem {}, vec![1.0, 1e6])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(op: O, scales: Vec<F>) -> Result<Self, Error> {
+        if scales.iter().any(|d| !d.is_finite() || *d == float!(0.0)) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Scaled`: scales must be finite and != 0."
+            ));
+        }
+        Ok(Scaled { op, scales })
+    }
+
+    /// Constructs a new `Scaled` from the typical magnitudes of the parameters, i.e. with
+    /// `d_i = 1 / |typical_i|`.
+    ///
+    /// All typical values must be finite and nonzero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Scaled};
+    /// # struct UserDefinedProblem {}
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = Scaled::from_typical_values(UserDefinedProblem {}, vec![1.0, 1e-6])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_typical_values(op: O, typical: Vec<F>) -> Result<Self, Error> {
+        if typical.iter().any(|x| !x.is_finite() || *x == float!(0.0)) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Scaled`: typical values must be finite and != 0."
+            ));
+        }
+        let scales = typical.into_iter().map(|x| float!(1.0) / x.abs()).collect();
+        Scaled::new(op, scales)
+    }
+
+    /// Returns the diagonal of the scaling matrix.
+    pub fn scales(&self) -> &[F] {
+        &self.scales
+    }
+
+    /// Returns an error if `param` does not have one component per scale.
+    fn check_len<P>(&self, param: &P) -> Result<(), Error>
+    where
+        for<'a> &'a P: IntoIterator<Item = &'a F>,
+    {
+        let len = param.into_iter().count();
+        if len != self.scales.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`Scaled`: expected parameter vector of length {}, got {len}.",
+                    self.scales.len()
+                )
+            ));
+        }
+        Ok(())
+    }
+
+    /// Computes `D x`.
+    ///
+    /// This is needed in order to provide an initial parameter vector to the solver.
+    pub fn to_scaled<P>(&self, param: &P) -> Result<P, Error>
+    where
+        P: Clone + IndexMut<usize, Output = F>,
+        for<'a> &'a P: IntoIterator<Item = &'a F>,
+    {
+        self.check_len(param)?;
+        let mut y = param.clone();
+        for (i, d) in self.scales.iter().enumerate() {
+            y[i] = y[i] * *d;
+        }
+        Ok(y)
+    }
+
+    /// Computes `D^-1 y`.
+    ///
+    /// This is needed in order to interpret the parameter vectors in the state, for instance the
+    /// best parameter vector after the run.
+    pub fn to_unscaled<P>(&self, param: &P) -> Result<P, Error>
+    where
+        P: Clone + IndexMut<usize, Output = F>,
+        for<'a> &'a P: IntoIterator<Item = &'a F>,
+    {
+        self.check_len(param)?;
+        let mut x = param.clone();
+        for (i, d) in self.scales.iter().enumerate() {
+            x[i] = x[i] / *d;
+        }
+        Ok(x)
+    }
+
+    /// Returns a reference to the wrapped problem
+    pub fn inner(&self) -> &O {
+        &self.op
+    }
+
+    /// Returns the wrapped problem
+    pub fn into_inner(self) -> O {
+        self.op
+    }
+}
+
+impl<O, P, F> CostFunction for Scaled<O, F>
+where
+    O: CostFunction<Param = P>,
+    P: Clone + IndexMut<usize, Output = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = O::Output;
+
+    /// Evaluates the wrapped cost function at `D^-1 param`.
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.op.cost(&self.to_unscaled(param)?)
+    }
+
+    fn parallelize(&self) -> bool {
+        self.op.parallelize()
+    }
+}
+
+impl<O, P, G, F> Gradient for Scaled<O, F>
+where
+    O: Gradient<Param = P, Gradient = G>,
+    P: Clone + IndexMut<usize, Output = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    G: IndexMut<usize, Output = F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Gradient = G;
+
+    /// Computes `D^-1 g(D^-1 param)`.
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let mut grad = self.op.gradient(&self.to_unscaled(param)?)?;
+        for (i, d) in self.scales.iter().enumerate() {
+            grad[i] = grad[i] / *d;
+        }
+        Ok(grad)
+    }
+
+    fn parallelize(&self) -> bool {
+        Gradient::parallelize(&self.op)
+    }
+}
+
+impl<O, P, H, R, F> Hessian for Scaled<O, F>
+where
+    O: Hessian<Param = P, Hessian = H>,
+    P: Clone + IndexMut<usize, Output = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    H: IndexMut<usize, Output = R>,
+    R: IndexMut<usize, Output = F> + ?Sized,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Hessian = H;
+
+    /// Computes `D^-1 H(D^-1 param) D^-1`.
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let mut hessian = self.op.hessian(&self.to_unscaled(param)?)?;
+        for (i, di) in self.scales.iter().enumerate() {
+            for (j, dj) in self.scales.iter().enumerate() {
+                hessian[i][j] = hessian[i][j] / (*di * *dj);
+            }
+        }
+        Ok(hessian)
+    }
+
+    fn parallelize(&self) -> bool {
+        Hessian::parallelize(&self.op)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{check_derivatives, ArgminError, Executor, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::quasinewton::LBFGS;
+    use approx::assert_relative_eq;
+
+    /// Quadratic whose components differ by many orders of magnitude
+    #[derive(Clone)]
+    struct BadlyScaled {}
+
+    impl CostFunction for BadlyScaled {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((p[0] - 1.0).powi(2) + (1e4 * p[1] - 2.0).powi(2) + p[0] * p[1] * 1e4)
+        }
+    }
+
+    impl Gradient for BadlyScaled {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(vec![
+                2.0 * (p[0] - 1.0) + p[1] * 1e4,
+                2e4 * (1e4 * p[1] - 2.0) + p[0] * 1e4,
+            ])
+        }
+    }
+
+    impl Hessian for BadlyScaled {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(vec![vec![2.0, 1e4], vec![1e4, 2e8]])
+        }
+    }
+
+    test_trait_impl!(scaled, Scaled<Vec<f64>, f64>);
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert_error!(
+            Scaled::new(BadlyScaled {}, vec![1.0, 0.0]),
+            ArgminError,
+            "Invalid parameter: \"`Scaled`: scales must be finite and != 0.\""
+        );
+        assert_error!(
+            Scaled::from_typical_values(BadlyScaled {}, vec![f64::NAN, 1.0]),
+            ArgminError,
+            "Invalid parameter: \"`Scaled`: typical values must be finite and != 0.\""
+        );
+        let problem = Scaled::new(BadlyScaled {}, vec![1.0, 1e4]).unwrap();
+        assert_error!(
+            problem.cost(&vec![1.0]),
+            ArgminError,
+            "Invalid parameter: \"`Scaled`: expected parameter vector of length 2, got 1.\""
+        );
+    }
+
+    #[test]
+    fn test_scaling() {
+        let problem = Scaled::from_typical_values(BadlyScaled {}, vec![-2.0, 1e-4]).unwrap();
+        assert_eq!(problem.scales(), &[0.5, 1e4]);
+        let x = vec![3.0, 2e-4];
+        let y = problem.to_scaled(&x).unwrap();
+        assert_relative_eq!(y[0], 1.5);
+        assert_relative_eq!(y[1], 2.0);
+        let x_roundtrip = problem.to_unscaled(&y).unwrap();
+        assert_relative_eq!(x_roundtrip[0], x[0]);
+        assert_relative_eq!(x_roundtrip[1], x[1]);
+        assert_relative_eq!(problem.cost(&y).unwrap(), BadlyScaled {}.cost(&x).unwrap());
+        check_derivatives(&problem, &y).unwrap();
+        // The Hessian is well conditioned in the scaled space
+        let hessian = problem.hessian(&y).unwrap();
+        assert_relative_eq!(hessian[0][0], 8.0);
+        assert_relative_eq!(hessian[1][1], 2.0);
+    }
+
+    #[test]
+    fn test_lbfgs() {
+        let problem = Scaled::new(BadlyScaled {}, vec![1.0, 1e4]).unwrap();
+        let init_param = problem.to_scaled(&vec![0.0, 0.0]).unwrap();
+        let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+        let res = Executor::new(problem, solver)
+            .configure(|state| state.param(init_param).max_iters(20))
+            .run()
+            .unwrap();
+        let problem = res.problem.problem.as_ref().unwrap();
+        let best = problem
+            .to_unscaled(res.state().get_best_param().unwrap())
+            .unwrap();
+        // Minimum of the quadratic
+        assert_relative_eq!(best[0], 0.0, epsilon = 1e-6);
+        assert_relative_eq!(best[1], 2e-4, epsilon = 1e-10);
+    }
+}
@@ -114,4 +114,106 @@ pub trait Solver<O, I: State> {
     fn terminate(&mut self, _state: &I) -> TerminationStatus {
         TerminationStatus::NotTerminated
     }
+
+    /// Called by the [`Executor`](`crate::core::Executor`) if an iteration produced non-finite
+    /// values and [`NonFinitePolicy::RejectStep`](`crate::core::NonFinitePolicy::RejectStep`) is
+    /// used.
+    ///
+    /// `state` is the state before the rejected iteration. Solvers with a step size (for
+    /// instance the radius of trust region methods) should reduce it, such that the next iteration
+    /// does not produce the same step again. Returns the state with which the run is continued.
+    /// The default implementation returns an error since rejecting a step is not meaningful for
+    /// every solver.
+    fn reject_step(&mut self, _state: I) -> Result<I, Error> {
+        Err(argmin_error!(
+            NotImplemented,
+            format!("`{}` does not support rejecting steps.", self.name())
+        ))
+    }
+
+    /// Prepares a warm start from the solver `previous` and its final state `previous_state` of
+    /// an earlier run.
+    ///
+    /// Called by [`Executor::warm_start`](`crate::core::Executor::warm_start`). `state` is the
+    /// initial state of the new run; the returned state is used instead. Solvers which accumulate
+    /// information about the problem in their own fields, for instance the curvature pairs of
+    /// L-BFGS or the radius of trust region methods, should take it over from `previous`. The
+    /// default implementation only takes over information held by the state (see
+    /// [`State::warm_start`]).
+    fn warm_start(&mut self, _previous: Self, previous_state: I, state: I) -> Result<I, Error>
+    where
+        Self: Sized,
+    {
+        Ok(state.warm_start(previous_state))
+    }
+
+    /// Called by the [`Executor`](`crate::core::Executor`) after the problem was modified between
+    /// two iterations (see [`Executor::modify_problem`](`crate::core::Executor::modify_problem`)).
+    ///
+    /// Returns the state with which the run is continued. The default implementation discards all
+    /// information in `state` which was computed from the previous problem (see
+    /// [`State::problem_changed`]) and calls [`init`](`Solver::init`) again, which recomputes
+    /// cost function value and derivatives at the current parameter vector. Solvers which
+    /// accumulate information about the problem in their own fields, for instance the curvature
+    /// pairs of L-BFGS, should discard it as well.
+    fn problem_changed(&mut self, problem: &mut Problem<O>, state: I) -> Result<I, Error> {
+        let (state, _) = self.init(problem, state.problem_changed())?;
+        Ok(state)
+    }
+
+    /// Called by the [`Executor`](`crate::core::Executor`) to retry after an iteration failed
+    /// with a recoverable error (see [`RecoveryPolicy`](`crate::core::RecoveryPolicy`)).
+    ///
+    /// `state` is the state before the failed iteration, possibly perturbed. Returns the state
+    /// with which the run is continued. The default implementation discards cost function value
+    /// and derivatives (see [`State::restart`]) and calls [`init`](`Solver::init`) again. As in
+    /// [`problem_changed`](`Solver::problem_changed`), solvers which accumulate information in
+    /// their own fields should discard it, since it may have caused the failure.
+    fn restart(&mut self, problem: &mut Problem<O>, state: I) -> Result<I, Error> {
+        let (state, _) = self.init(problem, state.restart())?;
+        Ok(state)
+    }
+
+    /// Called by the [`Executor`](`crate::core::Executor`) in deterministic mode (see
+    /// [`Executor::deterministic`](`crate::core::Executor::deterministic`)) before the solver is
+    /// initialized.
+    ///
+    /// Stochastic solvers should reseed their random number generators from `seed`. It is not
+    /// called when a run is resumed from a checkpoint, since the state of the random number
+    /// generators is restored from it. The default implementation does nothing.
+    fn reseed(&mut self, _seed: u64) {}
+
+    /// Indicates whether the [`Executor`](`crate::core::Executor`) keeps track of the best
+    /// parameter vector and cost function value seen so far (default: `true`).
+    ///
+    /// If enabled, [`State::update`] is called after initialization and after each iteration,
+    /// which replaces the best-so-far iterate whenever the current one is better. This is
+    /// important for non-monotone solvers, where the last iterate is not necessarily the best one.
+    /// Solvers which maintain the best-so-far iterate themselves can opt out by returning
+    /// `false`; they are then responsible for updating it in the state.
+    fn track_best(&self) -> bool {
+        true
+    }
+
+    /// Explains why the solver terminated in the final state `state` (default: empty).
+    ///
+    /// Solvers with convergence tolerances report which criterion was met and with which values
+    /// (see [`ToleranceConfig::diagnostics`](`crate::core::ToleranceConfig::diagnostics`)).
+    /// Available after a run via
+    /// [`OptimizationResult::termination_diagnostics`](`crate::core::OptimizationResult::termination_diagnostics`).
+    fn termination_diagnostics(&self, _state: &I) -> KV {
+        KV::new()
+    }
+
+    /// Exposes selected internals of the solver, for instance the current radius of trust region
+    /// methods (default: empty).
+    ///
+    /// After a run, these are available via
+    /// [`OptimizationResult::internals`](`crate::core::OptimizationResult::internals`). Matrices
+    /// such as the (inverse) Hessian approximation of quasi-Newton methods are not part of this
+    /// but are kept in the state instead (see for instance
+    /// [`IterState::get_inv_hessian`](`crate::core::IterState::get_inv_hessian`)).
+    fn internals(&self) -> KV {
+        KV::new()
+    }
 }
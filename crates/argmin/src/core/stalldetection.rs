@@ -0,0 +1,144 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Termination criterion which detects stalled progress.
+///
+/// The lowest cost function value observed so far is tracked over a sliding window of `window`
+/// iterations. The run is stopped with
+/// [`TerminationReason::Stalled`](`crate::core::TerminationReason::Stalled`) once the relative
+/// improvement of this value over the window,
+///
+/// `(c_{k-window} - c_k) / max(|c_{k-window}|, EPSILON)`,
+///
+/// falls below `tolerance`. Since only the lowest cost is considered, this criterion is robust
+/// against noisy objectives, for which checks of the change of the cost between two consecutive
+/// iterations fire either too early or never.
+///
+/// Stall detection is enabled via
+/// [`Executor::stall_detection`](`crate::core::Executor::stall_detection`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, StallDetection};
+/// # fn main() -> Result<(), Error> {
+/// // Stop if the best cost improved by less than 0.1% within the last 50 iterations
+/// let stall_detection: StallDetection<f64> = StallDetection::new(50, 1e-3)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct StallDetection<F> {
+    /// Size of the sliding window in iterations
+    window: usize,
+    /// Relative improvement below which the run is stopped
+    tolerance: F,
+    /// Lowest cost so far, recorded in each of the last `window + 1` iterations
+    #[cfg_attr(feature = "serde1", serde(default = "VecDeque::new"))]
+    best_costs: VecDeque<F>,
+}
+
+impl<F: ArgminFloat> StallDetection<F> {
+    /// Constructs a new instance of `StallDetection`.
+    ///
+    /// `window` must be larger than 0 and `tolerance` must be non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, StallDetection};
+    /// # fn main() -> Result<(), Error> {
+    /// let stall_detection: StallDetection<f64> = StallDetection::new(10, 1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(window: u64, tolerance: F) -> Result<Self, Error> {
+        if window == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`StallDetection`: window must be > 0."
+            ));
+        }
+        if tolerance.is_nan() || tolerance < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`StallDetection`: tolerance must be >= 0."
+            ));
+        }
+        Ok(StallDetection {
+            window: window as usize,
+            tolerance,
+            best_costs: VecDeque::with_capacity(window as usize + 1),
+        })
+    }
+
+    /// Returns an error if the parameters are invalid (for instance after deserialization).
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        StallDetection::new(self.window as u64, self.tolerance).map(|_| ())
+    }
+
+    /// Records the cost of the current iteration and returns `true` if progress stalled.
+    pub(crate) fn check(&mut self, cost: F) -> bool {
+        let best = match self.best_costs.back() {
+            Some(&best) if cost >= best || cost.is_nan() => best,
+            _ => cost,
+        };
+        self.best_costs.push_back(best);
+        if self.best_costs.len() > self.window + 1 {
+            self.best_costs.pop_front();
+        }
+        if self.best_costs.len() <= self.window {
+            return false;
+        }
+        let old = self.best_costs[0];
+        if !old.is_finite() || !best.is_finite() {
+            return false;
+        }
+        let improvement = (old - best) / old.abs().max(F::epsilon());
+        improvement < self.tolerance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(StallDetection::new(0, 1e-3f64).is_err());
+        assert!(StallDetection::new(10, -1e-3f64).is_err());
+        assert!(StallDetection::new(10, f64::NAN).is_err());
+        assert!(StallDetection::new(10, 0.0f64).is_ok());
+    }
+
+    #[test]
+    fn test_check() {
+        let mut stall = StallDetection::new(2, 0.1f64).unwrap();
+        // Noise above the best cost does not count
+        let stalled: Vec<bool> = [10.0, 8.0, 12.0, 7.9, 15.0, 7.8]
+            .into_iter()
+            .map(|cost| stall.check(cost))
+            .collect();
+        // Windows: 10 -> 8 (20%), 8 -> 7.9 (1.25%), 8 -> 7.9 (1.25%), 7.9 -> 7.8 (1.3%)
+        assert_eq!(stalled, vec![false, false, false, true, true, true]);
+    }
+
+    #[test]
+    fn test_infinite_costs() {
+        let mut stall = StallDetection::new(1, 0.1f64).unwrap();
+        assert!(!stall.check(f64::INFINITY));
+        assert!(!stall.check(f64::INFINITY));
+        assert!(!stall.check(1.0));
+        assert!(stall.check(1.0));
+    }
+}
@@ -5,6 +5,7 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use crate::core::time::Duration;
 use crate::core::{ArgminFloat, Problem, State, TerminationReason, TerminationStatus};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -73,6 +74,9 @@ pub struct IterState<P, G, J, H, R, F> {
     pub residuals: Option<R>,
     /// Value of residuals from previous call to apply
     pub prev_residuals: Option<R>,
+    /// Auxiliary data published by solvers, such as statistics of the model
+    #[cfg_attr(feature = "serde1", serde(default))]
+    pub aux: HashMap<String, F>,
     /// Current iteration
     pub iter: u64,
     /// Iteration number of last best cost
@@ -84,11 +88,30 @@ pub struct IterState<P, G, J, H, R, F> {
     /// Update evaluation counts?
     pub counting_enabled: bool,
     /// Time required so far
-    pub time: Option<instant::Duration>,
+    pub time: Option<Duration>,
+    /// Time required by the last iteration
+    pub iter_time: Option<Duration>,
     /// Status of optimization execution
     pub termination_status: TerminationStatus,
 }
 
+/// [`IterState`] for solvers which neither use gradients, Jacobians nor Hessians
+///
+/// Derivative-free solvers such as [`NelderMead`](`crate::solver::neldermead::NelderMead`) or
+/// [`SimulatedAnnealing`](`crate::solver::simulatedannealing::SimulatedAnnealing`) only keep track
+/// of parameter vectors and cost function values. This alias spares them (and their users) from
+/// spelling out the unused types as `()`.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{DerivativeFreeState, IterState, State};
+/// let state: DerivativeFreeState<Vec<f64>, f64> = DerivativeFreeState::new();
+/// let state: IterState<Vec<f64>, (), (), (), (), f64> = state;
+/// # assert!(state.get_param().is_none());
+/// ```
+pub type DerivativeFreeState<P, F> = IterState<P, (), (), (), (), F>;
+
 impl<P, G, J, H, R, F> IterState<P, G, J, H, R, F>
 where
     Self: State<Float = F>,
@@ -312,6 +335,27 @@ where
         self
     }
 
+    /// Set auxiliary data entry. An existing entry with the same key is overwritten.
+    ///
+    /// Solvers can use this to make additional quantities (for instance statistics of the model
+    /// such as the condition number of a matrix) available to observers and to the final result.
+    /// Entries are kept from one iteration to the next and are part of checkpoints.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # assert!(state.aux.is_empty());
+    /// let state = state.aux("step_length", 0.5);
+    /// # assert_eq!(state.aux["step_length"].to_ne_bytes(), 0.5f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn aux<T: AsRef<str>>(mut self, key: T, value: F) -> Self {
+        self.aux.insert(key.as_ref().to_string(), value);
+        self
+    }
+
     /// Returns the current cost function value
     ///
     /// # Example
@@ -388,6 +432,28 @@ where
         self.target_cost
     }
 
+    /// Replaces a non-finite cost function value with the largest (`NaN` and positive infinity)
+    /// or smallest (negative infinity) finite value.
+    ///
+    /// Intended to be used with
+    /// [`NonFinitePolicy::Clamp`](`crate::core::NonFinitePolicy::Clamp`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new().cost(f64::NAN);
+    /// state.clamp_cost();
+    /// assert_eq!(state.get_cost().to_ne_bytes(), f64::MAX.to_ne_bytes());
+    /// ```
+    pub fn clamp_cost(&mut self) {
+        if self.cost.is_nan() || self.cost == F::infinity() {
+            self.cost = F::max_value();
+        } else if self.cost == F::neg_infinity() {
+            self.cost = F::min_value();
+        }
+    }
+
     /// Moves the current parameter vector out and replaces it internally with `None`
     ///
     /// # Example
@@ -972,6 +1038,22 @@ where
         self.prev_residuals.take()
     }
 
+    /// Returns the auxiliary data entry with the given key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # let state = state.aux("step_length", 0.5);
+    /// let step_length = state.get_aux("step_length");
+    /// # assert_eq!(step_length.unwrap().to_ne_bytes(), 0.5f64.to_ne_bytes());
+    /// # assert!(state.get_aux("other").is_none());
+    /// ```
+    pub fn get_aux<T: AsRef<str>>(&self, key: T) -> Option<F> {
+        self.aux.get(key.as_ref()).copied()
+    }
+
     /// Overrides state of counting function executions (default: false)
     /// ```
     /// # use argmin::core::{IterState, State};
@@ -1002,8 +1084,7 @@ where
     /// # Example
     ///
     /// ```
-    /// # extern crate instant;
-    /// # use instant;
+    /// # use argmin::core::time::Duration;
     /// # use argmin::core::{IterState, State, ArgminFloat, TerminationStatus};
     /// let state: IterState<Vec<f64>, Vec<f64>, Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<f64>, f64> = IterState::new();
     /// # assert!(state.param.is_none());
@@ -1027,7 +1108,7 @@ where
     /// # assert_eq!(state.last_best_iter, 0);
     /// # assert_eq!(state.max_iters, u64::MAX);
     /// # assert_eq!(state.counts.len(), 0);
-    /// # assert_eq!(state.time.unwrap(), instant::Duration::new(0, 0));
+    /// # assert_eq!(state.time.unwrap(), Duration::new(0, 0));
     /// # assert_eq!(state.termination_status, TerminationStatus::NotTerminated);
     /// ```
     fn new() -> Self {
@@ -1051,12 +1132,14 @@ where
             prev_jacobian: None,
             residuals: None,
             prev_residuals: None,
+            aux: HashMap::new(),
             iter: 0,
             last_best_iter: 0,
             max_iters: u64::MAX,
             counts: HashMap::new(),
             counting_enabled: false,
-            time: Some(instant::Duration::new(0, 0)),
+            time: Some(Duration::new(0, 0)),
+            iter_time: None,
             termination_status: TerminationStatus::NotTerminated,
         }
     }
@@ -1186,18 +1269,33 @@ where
     /// # Example
     ///
     /// ```
-    /// # extern crate instant;
-    /// # use instant;
+    /// # use argmin::core::time::Duration;
     /// # use argmin::core::{IterState, State, ArgminFloat, TerminationReason};
     /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
-    /// let state = state.time(Some(instant::Duration::new(0, 12)));
-    /// # assert_eq!(state.time.unwrap(), instant::Duration::new(0, 12));
+    /// let state = state.time(Some(Duration::new(0, 12)));
+    /// # assert_eq!(state.time.unwrap(), Duration::new(0, 12));
     /// ```
-    fn time(&mut self, time: Option<instant::Duration>) -> &mut Self {
+    fn time(&mut self, time: Option<Duration>) -> &mut Self {
         self.time = time;
         self
     }
 
+    /// Sets the time required by the last iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::time::Duration;
+    /// # use argmin::core::{IterState, State, ArgminFloat};
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// let state = state.iter_time(Some(Duration::new(0, 12)));
+    /// # assert_eq!(state.iter_time.unwrap(), Duration::new(0, 12));
+    /// ```
+    fn iter_time(&mut self, time: Option<Duration>) -> &mut Self {
+        self.iter_time = time;
+        self
+    }
+
     /// Returns current cost function value.
     ///
     /// # Example
@@ -1243,6 +1341,21 @@ where
         self.target_cost
     }
 
+    /// Sets the target cost function value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State, ArgminFloat};
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// state.set_target_cost(12.0);
+    /// # assert_eq!(state.target_cost.to_ne_bytes(), 12.0f64.to_ne_bytes());
+    /// ```
+    fn set_target_cost(&mut self, target_cost: Self::Float) -> &mut Self {
+        self.target_cost = target_cost;
+        self
+    }
+
     /// Returns current number of iterations.
     ///
     /// # Example
@@ -1324,17 +1437,30 @@ where
     /// # Example
     ///
     /// ```
-    /// # extern crate instant;
-    /// # use instant;
+    /// # use argmin::core::time::Duration;
     /// # use argmin::core::{IterState, State, ArgminFloat};
     /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
     /// let time = state.get_time();
-    /// # assert_eq!(time.unwrap(), instant::Duration::new(0, 0));
+    /// # assert_eq!(time.unwrap(), Duration::new(0, 0));
     /// ```
-    fn get_time(&self) -> Option<instant::Duration> {
+    fn get_time(&self) -> Option<Duration> {
         self.time
     }
 
+    /// Returns the time required by the last iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State, ArgminFloat};
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// let iter_time = state.get_iter_time();
+    /// # assert!(iter_time.is_none());
+    /// ```
+    fn get_iter_time(&self) -> Option<Duration> {
+        self.iter_time
+    }
+
     /// Increments the number of iterations by one
     ///
     /// # Example
@@ -1419,6 +1545,93 @@ where
     fn is_best(&self) -> bool {
         self.last_best_iter == self.iter
     }
+
+    /// Takes over the best parameter vector (or the current one if no best parameter vector is
+    /// available), the Hessian and the inverse Hessian from `previous` unless they are already
+    /// set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// let previous: IterState<Vec<f64>, (), (), Vec<Vec<f64>>, (), f64> = IterState::new()
+    ///     .param(vec![1.0, 2.0])
+    ///     .inv_hessian(vec![vec![2.0, 0.0], vec![0.0, 3.0]]);
+    /// let state = IterState::new().warm_start(previous);
+    /// # assert_eq!(state.get_param(), Some(&vec![1.0, 2.0]));
+    /// # assert_eq!(state.get_inv_hessian(), Some(&vec![vec![2.0, 0.0], vec![0.0, 3.0]]));
+    /// # assert!(state.get_cost().is_infinite());
+    /// ```
+    fn warm_start(mut self, previous: Self) -> Self {
+        if self.param.is_none() {
+            self.param = previous.best_param.or(previous.param);
+        }
+        if self.hessian.is_none() {
+            self.hessian = previous.hessian;
+        }
+        if self.inv_hessian.is_none() {
+            self.inv_hessian = previous.inv_hessian;
+        }
+        self
+    }
+
+    /// Resets the cost function values to `+inf` and discards gradient, Hessian, Jacobian and
+    /// residuals. Parameter vectors, the inverse Hessian and the best cost function value are
+    /// kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// let mut state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64> = IterState::new()
+    ///     .param(vec![1.0, 2.0])
+    ///     .gradient(vec![0.5, 0.5])
+    ///     .cost(3.0);
+    /// state.update();
+    /// let state = state.restart();
+    /// # assert_eq!(state.get_param(), Some(&vec![1.0, 2.0]));
+    /// # assert!(state.get_gradient().is_none());
+    /// # assert!(state.get_cost().is_infinite());
+    /// assert_eq!(state.get_best_cost().to_ne_bytes(), 3.0f64.to_ne_bytes());
+    /// ```
+    fn restart(mut self) -> Self {
+        self.cost = F::infinity();
+        self.prev_cost = F::infinity();
+        self.grad = None;
+        self.prev_grad = None;
+        self.hessian = None;
+        self.prev_hessian = None;
+        self.jacobian = None;
+        self.prev_jacobian = None;
+        self.residuals = None;
+        self.prev_residuals = None;
+        self
+    }
+
+    /// Resets the cost function values and the best cost function value to `+inf` and discards
+    /// gradient, Hessian, Jacobian and residuals. Parameter vectors and the inverse Hessian are
+    /// kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// let state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64> = IterState::new()
+    ///     .param(vec![1.0, 2.0])
+    ///     .gradient(vec![0.5, 0.5])
+    ///     .cost(3.0);
+    /// let state = state.problem_changed();
+    /// # assert_eq!(state.get_param(), Some(&vec![1.0, 2.0]));
+    /// # assert!(state.get_gradient().is_none());
+    /// # assert!(state.get_cost().is_infinite());
+    /// # assert!(state.get_best_cost().is_infinite());
+    /// ```
+    fn problem_changed(self) -> Self {
+        let mut state = self.restart();
+        state.best_cost = F::infinity();
+        state.prev_best_cost = F::infinity();
+        state
+    }
 }
 
 #[cfg(test)]
@@ -1472,6 +1685,7 @@ mod tests {
         assert!(state.get_prev_jacobian().is_none());
         assert!(state.get_residuals().is_none());
         assert!(state.get_prev_residuals().is_none());
+        assert!(state.aux.is_empty());
         assert_eq!(state.get_iter(), 0);
 
         assert!(state.is_best());
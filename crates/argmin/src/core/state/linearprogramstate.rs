@@ -5,6 +5,7 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use crate::core::time::Duration;
 use crate::core::{ArgminFloat, Problem, State, TerminationReason, TerminationStatus};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -59,7 +60,9 @@ pub struct LinearProgramState<P, F> {
     /// Update evaluation counts?
     pub counting_enabled: bool,
     /// Time required so far
-    pub time: Option<instant::Duration>,
+    pub time: Option<Duration>,
+    /// Time required by the last iteration
+    pub iter_time: Option<Duration>,
     /// Status of optimization execution
     pub termination_status: TerminationStatus,
 }
@@ -183,8 +186,7 @@ where
     /// # Example
     ///
     /// ```
-    /// # extern crate instant;
-    /// # use instant;
+    /// # use argmin::core::time::Duration;
     /// # use std::collections::HashMap;
     /// # use argmin::core::TerminationStatus;
     /// use argmin::core::{LinearProgramState, State};
@@ -203,7 +205,7 @@ where
     /// # assert_eq!(state.last_best_iter, 0);
     /// # assert_eq!(state.max_iters, u64::MAX);
     /// # assert_eq!(state.counts, HashMap::new());
-    /// # assert_eq!(state.time.unwrap(), instant::Duration::new(0, 0));
+    /// # assert_eq!(state.time.unwrap(), Duration::new(0, 0));
     /// # assert_eq!(state.termination_status, TerminationStatus::NotTerminated);
     /// ```
     fn new() -> Self {
@@ -222,7 +224,8 @@ where
             max_iters: u64::MAX,
             counts: HashMap::new(),
             counting_enabled: false,
-            time: Some(instant::Duration::new(0, 0)),
+            time: Some(Duration::new(0, 0)),
+            iter_time: None,
             termination_status: TerminationStatus::NotTerminated,
         }
     }
@@ -351,18 +354,33 @@ where
     /// # Example
     ///
     /// ```
-    /// # extern crate instant;
-    /// # use instant;
+    /// # use argmin::core::time::Duration;
     /// # use argmin::core::{LinearProgramState, State, ArgminFloat, TerminationReason};
     /// # let mut state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
-    /// let state = state.time(Some(instant::Duration::new(0, 12)));
-    /// # assert_eq!(state.time.unwrap(), instant::Duration::new(0, 12));
+    /// let state = state.time(Some(Duration::new(0, 12)));
+    /// # assert_eq!(state.time.unwrap(), Duration::new(0, 12));
     /// ```
-    fn time(&mut self, time: Option<instant::Duration>) -> &mut Self {
+    fn time(&mut self, time: Option<Duration>) -> &mut Self {
         self.time = time;
         self
     }
 
+    /// Sets the time required by the last iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::time::Duration;
+    /// # use argmin::core::{LinearProgramState, State, ArgminFloat};
+    /// # let mut state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
+    /// let state = state.iter_time(Some(Duration::new(0, 12)));
+    /// # assert_eq!(state.iter_time.unwrap(), Duration::new(0, 12));
+    /// ```
+    fn iter_time(&mut self, time: Option<Duration>) -> &mut Self {
+        self.iter_time = time;
+        self
+    }
+
     /// Returns current cost function value.
     ///
     /// # Example
@@ -408,6 +426,21 @@ where
         self.target_cost
     }
 
+    /// Sets the target cost function value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{LinearProgramState, State, ArgminFloat};
+    /// # let mut state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
+    /// state.set_target_cost(12.0);
+    /// # assert_eq!(state.target_cost.to_ne_bytes(), 12.0f64.to_ne_bytes());
+    /// ```
+    fn set_target_cost(&mut self, target_cost: Self::Float) -> &mut Self {
+        self.target_cost = target_cost;
+        self
+    }
+
     /// Returns current number of iterations.
     ///
     /// # Example
@@ -489,17 +522,30 @@ where
     /// # Example
     ///
     /// ```
-    /// # extern crate instant;
-    /// # use instant;
+    /// # use argmin::core::time::Duration;
     /// # use argmin::core::{LinearProgramState, State, ArgminFloat};
     /// # let mut state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
     /// let time = state.get_time();
-    /// # assert_eq!(time.unwrap(), instant::Duration::new(0, 0));
+    /// # assert_eq!(time.unwrap(), Duration::new(0, 0));
     /// ```
-    fn get_time(&self) -> Option<instant::Duration> {
+    fn get_time(&self) -> Option<Duration> {
         self.time
     }
 
+    /// Returns the time required by the last iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{LinearProgramState, State, ArgminFloat};
+    /// # let mut state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
+    /// let iter_time = state.get_iter_time();
+    /// # assert!(iter_time.is_none());
+    /// ```
+    fn get_iter_time(&self) -> Option<Duration> {
+        self.iter_time
+    }
+
     /// Increments the number of iterations by one
     ///
     /// # Example
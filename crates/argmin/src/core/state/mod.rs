@@ -9,10 +9,11 @@ pub mod iterstate;
 pub mod linearprogramstate;
 pub mod populationstate;
 
-pub use iterstate::IterState;
+pub use iterstate::{DerivativeFreeState, IterState};
 pub use linearprogramstate::LinearProgramState;
 pub use populationstate::PopulationState;
 
+use crate::core::time::Duration;
 use crate::core::{ArgminFloat, Problem, TerminationReason, TerminationStatus};
 use std::collections::HashMap;
 
@@ -34,6 +35,7 @@ use std::collections::HashMap;
 /// * the current number of iterations
 /// * how often each function of the problem has been called
 /// * the time required since the beginning of the optimization until the current point in time
+/// * the time required by the last iteration
 /// * the status of optimization execution ([`TerminationStatus`])
 ///
 /// Since the state in general changes for each iteration, "current" refers to the current
@@ -83,6 +85,9 @@ pub trait State {
     /// Returns target cost
     fn get_target_cost(&self) -> Self::Float;
 
+    /// Set target cost
+    fn set_target_cost(&mut self, target_cost: Self::Float) -> &mut Self;
+
     /// Set all function evaluation counts to the evaluation counts of another operator
     /// wrapped in `Problem`.
     fn func_counts<O>(&mut self, problem: &Problem<O>);
@@ -91,10 +96,16 @@ pub trait State {
     fn get_func_counts(&self) -> &HashMap<String, u64>;
 
     /// Set time required since the beginning of the optimization until the current iteration
-    fn time(&mut self, time: Option<instant::Duration>) -> &mut Self;
+    fn time(&mut self, time: Option<Duration>) -> &mut Self;
 
     /// Get time passed since the beginning of the optimization until the current iteration
-    fn get_time(&self) -> Option<instant::Duration>;
+    fn get_time(&self) -> Option<Duration>;
+
+    /// Set time required by the last iteration
+    fn iter_time(&mut self, time: Option<Duration>) -> &mut Self;
+
+    /// Get time required by the last iteration
+    fn get_iter_time(&self) -> Option<Duration>;
 
     /// Returns iteration number where the last best parameter vector was found
     fn get_last_best_iter(&self) -> u64;
@@ -120,4 +131,49 @@ pub trait State {
             TerminationStatus::Terminated(_)
         )
     }
+
+    /// Takes over information from the final state `previous` of an earlier run which is useful
+    /// to warm-start a new run (see [`Executor::warm_start`](`crate::core::Executor::warm_start`)).
+    ///
+    /// Information already present in `self`, for instance an initial parameter vector provided
+    /// by the user, takes precedence. Cost function values and derivatives are not taken over,
+    /// since the problem may have changed in between. The default implementation returns `self`
+    /// unaltered.
+    #[must_use]
+    fn warm_start(self, _previous: Self) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Discards information which was computed from the problem at the current parameter vector,
+    /// such that the solver recomputes it when it is restarted after a failed iteration (see
+    /// [`RecoveryPolicy`](`crate::core::RecoveryPolicy`)).
+    ///
+    /// In contrast to [`problem_changed`](`State::problem_changed`), the best parameter vector and
+    /// the best cost function value are kept. The default implementation returns `self`
+    /// unaltered.
+    #[must_use]
+    fn restart(self) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Discards information which was computed from the problem before it was modified via
+    /// [`Executor::modify_problem`](`crate::core::Executor::modify_problem`).
+    ///
+    /// Parameter vectors are kept, cost function values and derivatives are discarded such that
+    /// the solver recomputes them for the modified problem. The best cost function value is reset
+    /// as well, since it is not comparable to cost function values of the modified problem. The
+    /// default implementation returns `self` unaltered.
+    #[must_use]
+    fn problem_changed(self) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
 }
@@ -5,6 +5,7 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use crate::core::time::Duration;
 use crate::core::{ArgminFloat, Problem, State, TerminationReason, TerminationStatus};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -50,6 +51,9 @@ pub struct PopulationState<P, F> {
     pub target_cost: F,
     /// All members of the population
     pub population: Option<Vec<P>>,
+    /// Cost function values of all members of the population
+    #[cfg_attr(feature = "serde1", serde(default))]
+    pub population_costs: Option<Vec<F>>,
     /// Current iteration
     pub iter: u64,
     /// Iteration number of last best cost
@@ -61,7 +65,9 @@ pub struct PopulationState<P, F> {
     /// Update evaluation counts?
     pub counting_enabled: bool,
     /// Time required so far
-    pub time: Option<instant::Duration>,
+    pub time: Option<Duration>,
+    /// Time required by the last iteration
+    pub iter_time: Option<Duration>,
     /// Status of optimization execution
     pub termination_status: TerminationStatus,
 }
@@ -165,6 +171,26 @@ where
         self
     }
 
+    /// Set the cost function values of all members of the population
+    ///
+    /// Population-based solvers publish the costs of all members here, in the same order as the
+    /// population, such that observers can access them without knowing the type of the members.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{PopulationState, State};
+    /// # let state: PopulationState<Vec<f64>, f64> = PopulationState::new();
+    /// # assert!(state.population_costs.is_none());
+    /// let state = state.population_costs(vec![1.0, 2.0]);
+    /// # assert_eq!(state.population_costs, Some(vec![1.0, 2.0]));
+    /// ```
+    #[must_use]
+    pub fn population_costs(mut self, costs: Vec<F>) -> Self {
+        self.population_costs = Some(costs);
+        self
+    }
+
     /// Set maximum number of iterations
     ///
     /// # Example
@@ -432,6 +458,38 @@ where
         self.population.take()
     }
 
+    /// Returns a reference to the cost function values of all members of the population
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{PopulationState, State};
+    /// # let state: PopulationState<Vec<f64>, f64> = PopulationState::new();
+    /// # let state = state.population_costs(vec![1.0, 2.0]);
+    /// let costs = state.get_population_costs();
+    /// # assert_eq!(costs, Some(&vec![1.0, 2.0]));
+    /// ```
+    pub fn get_population_costs(&self) -> Option<&Vec<F>> {
+        self.population_costs.as_ref()
+    }
+
+    /// Takes the cost function values of all members of the population and replaces them
+    /// internally with `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{PopulationState, State};
+    /// # let state: PopulationState<Vec<f64>, f64> = PopulationState::new();
+    /// # let mut state = state.population_costs(vec![1.0, 2.0]);
+    /// let costs = state.take_population_costs();
+    /// # assert_eq!(costs, Some(vec![1.0, 2.0]));
+    /// # assert!(state.population_costs.is_none());
+    /// ```
+    pub fn take_population_costs(&mut self) -> Option<Vec<F>> {
+        self.population_costs.take()
+    }
+
     /// Overrides state of counting function executions (default: false)
     /// ```
     /// # use argmin::core::{State, PopulationState};
@@ -462,8 +520,7 @@ where
     /// # Example
     ///
     /// ```
-    /// # extern crate instant;
-    /// # use instant;
+    /// # use argmin::core::time::Duration;
     /// # use argmin::core::{PopulationState, State, ArgminFloat, TerminationStatus};
     /// let state: PopulationState<Vec<f64>, f64> = PopulationState::new();
     /// # assert!(state.individual.is_none());
@@ -480,7 +537,7 @@ where
     /// # assert_eq!(state.last_best_iter, 0);
     /// # assert_eq!(state.max_iters, u64::MAX);
     /// # assert_eq!(state.counts.len(), 0);
-    /// # assert_eq!(state.time.unwrap(), instant::Duration::new(0, 0));
+    /// # assert_eq!(state.time.unwrap(), Duration::new(0, 0));
     /// # assert_eq!(state.termination_status, TerminationStatus::NotTerminated);
     /// ```
     fn new() -> Self {
@@ -495,12 +552,14 @@ where
             prev_best_cost: F::infinity(),
             target_cost: F::neg_infinity(),
             population: None,
+            population_costs: None,
             iter: 0,
             last_best_iter: 0,
             max_iters: u64::MAX,
             counts: HashMap::new(),
             counting_enabled: false,
-            time: Some(instant::Duration::new(0, 0)),
+            time: Some(Duration::new(0, 0)),
+            iter_time: None,
             termination_status: TerminationStatus::NotTerminated,
         }
     }
@@ -630,18 +689,33 @@ where
     /// # Example
     ///
     /// ```
-    /// # extern crate instant;
-    /// # use instant;
+    /// # use argmin::core::time::Duration;
     /// # use argmin::core::{PopulationState, State, ArgminFloat, TerminationReason};
     /// # let mut state: PopulationState<Vec<f64>, f64> = PopulationState::new();
-    /// let state = state.time(Some(instant::Duration::new(0, 12)));
-    /// # assert_eq!(state.time.unwrap(), instant::Duration::new(0, 12));
+    /// let state = state.time(Some(Duration::new(0, 12)));
+    /// # assert_eq!(state.time.unwrap(), Duration::new(0, 12));
     /// ```
-    fn time(&mut self, time: Option<instant::Duration>) -> &mut Self {
+    fn time(&mut self, time: Option<Duration>) -> &mut Self {
         self.time = time;
         self
     }
 
+    /// Sets the time required by the last iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::time::Duration;
+    /// # use argmin::core::{PopulationState, State, ArgminFloat};
+    /// # let mut state: PopulationState<Vec<f64>, f64> = PopulationState::new();
+    /// let state = state.iter_time(Some(Duration::new(0, 12)));
+    /// # assert_eq!(state.iter_time.unwrap(), Duration::new(0, 12));
+    /// ```
+    fn iter_time(&mut self, time: Option<Duration>) -> &mut Self {
+        self.iter_time = time;
+        self
+    }
+
     /// Returns current cost function value.
     ///
     /// # Example
@@ -687,6 +761,21 @@ where
         self.target_cost
     }
 
+    /// Sets the target cost function value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{PopulationState, State, ArgminFloat};
+    /// # let mut state: PopulationState<Vec<f64>, f64> = PopulationState::new();
+    /// state.set_target_cost(12.0);
+    /// # assert_eq!(state.target_cost.to_ne_bytes(), 12.0f64.to_ne_bytes());
+    /// ```
+    fn set_target_cost(&mut self, target_cost: Self::Float) -> &mut Self {
+        self.target_cost = target_cost;
+        self
+    }
+
     /// Returns current number of iterations.
     ///
     /// # Example
@@ -768,17 +857,30 @@ where
     /// # Example
     ///
     /// ```
-    /// # extern crate instant;
-    /// # use instant;
+    /// # use argmin::core::time::Duration;
     /// # use argmin::core::{PopulationState, State, ArgminFloat};
     /// # let mut state: PopulationState<Vec<f64>, f64> = PopulationState::new();
     /// let time = state.get_time();
-    /// # assert_eq!(time.unwrap(), instant::Duration::new(0, 0));
+    /// # assert_eq!(time.unwrap(), Duration::new(0, 0));
     /// ```
-    fn get_time(&self) -> Option<instant::Duration> {
+    fn get_time(&self) -> Option<Duration> {
         self.time
     }
 
+    /// Returns the time required by the last iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{PopulationState, State, ArgminFloat};
+    /// # let mut state: PopulationState<Vec<f64>, f64> = PopulationState::new();
+    /// let iter_time = state.get_iter_time();
+    /// # assert!(iter_time.is_none());
+    /// ```
+    fn get_iter_time(&self) -> Option<Duration> {
+        self.iter_time
+    }
+
     /// Increments the number of iterations by one
     ///
     /// # Example
@@ -863,6 +965,32 @@ where
     fn is_best(&self) -> bool {
         self.last_best_iter == self.iter
     }
+
+    /// Resets the cost function values and the best cost function value to `+inf` and discards
+    /// the costs of the members of the population. The population itself is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{PopulationState, State};
+    /// let state: PopulationState<Vec<f64>, f64> = PopulationState::new()
+    ///     .population(vec![vec![1.0], vec![2.0]])
+    ///     .population_costs(vec![1.0, 4.0])
+    ///     .cost(1.0);
+    /// let state = state.problem_changed();
+    /// # assert_eq!(state.get_population().unwrap().len(), 2);
+    /// # assert!(state.get_population_costs().is_none());
+    /// # assert!(state.get_cost().is_infinite());
+    /// # assert!(state.get_best_cost().is_infinite());
+    /// ```
+    fn problem_changed(mut self) -> Self {
+        self.cost = F::infinity();
+        self.prev_cost = F::infinity();
+        self.best_cost = F::infinity();
+        self.prev_best_cost = F::infinity();
+        self.population_costs = None;
+        self
+    }
 }
 
 // TODO: Tests? Actually doc tests should already cover everything.
@@ -0,0 +1,570 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::time::Duration;
+use crate::core::{Error, IterState, PopulationState, TerminationReason, TerminationStatus};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::{Read, Write};
+
+/// Magic bytes at the beginning of a state written by [`write_state`]
+const MAGIC: &[u8; 8] = b"ARGMINST";
+
+/// Version of the binary layout written by [`write_state`]
+const VERSION: u32 = 1;
+
+/// Hand-rolled binary encoding of states and their components
+///
+/// This is the serde-free counterpart of checkpointing: it is used by
+/// [`Executor::save_state`](`crate::core::Executor::save_state`) and
+/// [`Executor::load_state`](`crate::core::Executor::load_state`) to interrupt and resume runs
+/// whose parameter vectors (or gradients, Hessians, ...) cannot derive `Serialize`. Only the
+/// parameter types have to implement this trait, implementations for [`IterState`] and
+/// [`PopulationState`] are provided.
+///
+/// The layout is little endian throughout: integers are written as `u64` (counts, lengths,
+/// iteration numbers), floats with their IEEE 754 bytes, `bool`s as a single byte and `Option`s
+/// as a tag byte followed by the value if present. Sequences and strings are prefixed with their
+/// length. Maps are written as a sequence of key/value pairs sorted by key, such that identical
+/// states always result in identical bytes.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, StateCodec};
+/// # use std::io::{Read, Write};
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// impl StateCodec for Point {
+///     fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+///         self.x.encode(writer)?;
+///         self.y.encode(writer)
+///     }
+///
+///     fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+///         Ok(Point {
+///             x: f64::decode(reader)?,
+///             y: f64::decode(reader)?,
+///         })
+///     }
+/// }
+/// # let mut bytes = vec![];
+/// # Point { x: 1.0, y: 2.0 }.encode(&mut bytes)?;
+/// # let point = Point::decode(&mut bytes.as_slice())?;
+/// # assert_eq!((point.x, point.y), (1.0, 2.0));
+/// # Ok::<(), Error>(())
+/// ```
+pub trait StateCodec: Sized {
+    /// Writes `self` to `writer`
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error>;
+
+    /// Reads a value from `reader`
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error>;
+}
+
+/// Writes header and state
+pub(crate) fn write_state<W: Write + ?Sized, I: StateCodec>(
+    writer: &mut W,
+    state: &I,
+) -> Result<(), Error> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    state.encode(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a state written by [`write_state`] after checking the header
+pub(crate) fn read_state<R: Read + ?Sized, I: StateCodec>(reader: &mut R) -> Result<I, Error> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(argmin_error!(
+            CheckpointFailed,
+            "`Executor::load_state`: Input is not a state saved by `Executor::save_state`."
+        ));
+    }
+    let mut version = [0u8; 4];
+    reader.read_exact(&mut version)?;
+    let version = u32::from_le_bytes(version);
+    if version != VERSION {
+        return Err(argmin_error!(
+            CheckpointFailed,
+            format!(
+                "`Executor::load_state`: Unsupported state format version {version}, expected {VERSION}."
+            )
+        ));
+    }
+    I::decode(reader)
+}
+
+/// Reads a length prefix
+fn decode_len<R: Read + ?Sized>(reader: &mut R) -> Result<usize, Error> {
+    let len = u64::decode(reader)?;
+    usize::try_from(len).map_err(|_| {
+        argmin_error!(
+            CheckpointFailed,
+            format!("`StateCodec`: Length {len} exceeds the address space.")
+        )
+    })
+}
+
+macro_rules! impl_state_codec_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl StateCodec for $t {
+                fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+                    writer.write_all(&self.to_le_bytes())?;
+                    Ok(())
+                }
+
+                fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+                    let mut bytes = [0u8; std::mem::size_of::<$t>()];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(<$t>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_state_codec_primitive!(f32, f64, i32, i64, u32, u64);
+
+impl StateCodec for usize {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        (*self as u64).encode(writer)
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        decode_len(reader)
+    }
+}
+
+impl StateCodec for bool {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(&[u8::from(*self)])?;
+        Ok(())
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        match byte[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(argmin_error!(
+                CheckpointFailed,
+                format!("`StateCodec`: Invalid boolean {b}.")
+            )),
+        }
+    }
+}
+
+impl StateCodec for () {
+    fn encode<W: Write + ?Sized>(&self, _writer: &mut W) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn decode<R: Read + ?Sized>(_reader: &mut R) -> Result<Self, Error> {
+        Ok(())
+    }
+}
+
+impl StateCodec for String {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.len().encode(writer)?;
+        writer.write_all(self.as_bytes())?;
+        Ok(())
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        let len = decode_len(reader)?;
+        let mut bytes = vec![];
+        reader.take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(argmin_error!(
+                CheckpointFailed,
+                "`StateCodec`: Unexpected end of input."
+            ));
+        }
+        String::from_utf8(bytes).map_err(|_| {
+            argmin_error!(CheckpointFailed, "`StateCodec`: String is not valid UTF-8.")
+        })
+    }
+}
+
+impl<T: StateCodec> StateCodec for Option<T> {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.is_some().encode(writer)?;
+        if let Some(value) = self {
+            value.encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        if bool::decode(reader)? {
+            Ok(Some(T::decode(reader)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: StateCodec> StateCodec for Vec<T> {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.len().encode(writer)?;
+        self.iter().try_for_each(|value| value.encode(writer))
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        let len = decode_len(reader)?;
+        // The length is not trusted for allocating upfront since the input may be corrupted.
+        let mut values = Vec::with_capacity(len.min(1024));
+        for _ in 0..len {
+            values.push(T::decode(reader)?);
+        }
+        Ok(values)
+    }
+}
+
+impl<A: StateCodec, B: StateCodec> StateCodec for (A, B) {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.0.encode(writer)?;
+        self.1.encode(writer)
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        Ok((A::decode(reader)?, B::decode(reader)?))
+    }
+}
+
+impl<K, V> StateCodec for HashMap<K, V>
+where
+    K: StateCodec + Ord + Hash,
+    V: StateCodec,
+{
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries.len().encode(writer)?;
+        entries.into_iter().try_for_each(|(key, value)| {
+            key.encode(writer)?;
+            value.encode(writer)
+        })
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        Ok(Vec::<(K, V)>::decode(reader)?.into_iter().collect())
+    }
+}
+
+impl StateCodec for Duration {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.as_secs().encode(writer)?;
+        self.subsec_nanos().encode(writer)
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        let secs = u64::decode(reader)?;
+        let nanos = u32::decode(reader)?;
+        if nanos >= 1_000_000_000 {
+            return Err(argmin_error!(
+                CheckpointFailed,
+                format!("`StateCodec`: Invalid number of nanoseconds {nanos}.")
+            ));
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+impl StateCodec for TerminationStatus {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        let (tag, text): (u32, Option<&String>) = match self {
+            TerminationStatus::NotTerminated => (0, None),
+            TerminationStatus::Terminated(reason) => match reason {
+                TerminationReason::MaxItersReached => (1, None),
+                TerminationReason::TargetCostReached => (2, None),
+                TerminationReason::Interrupt => (3, None),
+                TerminationReason::SolverConverged => (4, None),
+                TerminationReason::Timeout => (5, None),
+                TerminationReason::TimeLimitReached => (6, None),
+                TerminationReason::Cancelled => (7, None),
+                TerminationReason::EarlyStopped => (8, None),
+                TerminationReason::Stalled => (9, None),
+                TerminationReason::MaxEvaluationsReached => (10, None),
+                TerminationReason::SolverExit(text) => (11, Some(text)),
+            },
+        };
+        tag.encode(writer)?;
+        if let Some(text) = text {
+            text.encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        let reason = match u32::decode(reader)? {
+            0 => return Ok(TerminationStatus::NotTerminated),
+            1 => TerminationReason::MaxItersReached,
+            2 => TerminationReason::TargetCostReached,
+            3 => TerminationReason::Interrupt,
+            4 => TerminationReason::SolverConverged,
+            5 => TerminationReason::Timeout,
+            6 => TerminationReason::TimeLimitReached,
+            7 => TerminationReason::Cancelled,
+            8 => TerminationReason::EarlyStopped,
+            9 => TerminationReason::Stalled,
+            10 => TerminationReason::MaxEvaluationsReached,
+            11 => TerminationReason::SolverExit(String::decode(reader)?),
+            tag => {
+                return Err(argmin_error!(
+                    CheckpointFailed,
+                    format!("`StateCodec`: Unknown termination status {tag}.")
+                ))
+            }
+        };
+        Ok(TerminationStatus::Terminated(reason))
+    }
+}
+
+impl<P, G, J, H, R, F> StateCodec for IterState<P, G, J, H, R, F>
+where
+    P: StateCodec,
+    G: StateCodec,
+    J: StateCodec,
+    H: StateCodec,
+    R: StateCodec,
+    F: StateCodec,
+{
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.param.encode(writer)?;
+        self.prev_param.encode(writer)?;
+        self.best_param.encode(writer)?;
+        self.prev_best_param.encode(writer)?;
+        self.cost.encode(writer)?;
+        self.prev_cost.encode(writer)?;
+        self.best_cost.encode(writer)?;
+        self.prev_best_cost.encode(writer)?;
+        self.target_cost.encode(writer)?;
+        self.grad.encode(writer)?;
+        self.prev_grad.encode(writer)?;
+        self.hessian.encode(writer)?;
+        self.prev_hessian.encode(writer)?;
+        self.inv_hessian.encode(writer)?;
+        self.prev_inv_hessian.encode(writer)?;
+        self.jacobian.encode(writer)?;
+        self.prev_jacobian.encode(writer)?;
+        self.residuals.encode(writer)?;
+        self.prev_residuals.encode(writer)?;
+        self.aux.encode(writer)?;
+        self.iter.encode(writer)?;
+        self.last_best_iter.encode(writer)?;
+        self.max_iters.encode(writer)?;
+        self.counts.encode(writer)?;
+        self.counting_enabled.encode(writer)?;
+        self.time.encode(writer)?;
+        self.iter_time.encode(writer)?;
+        self.termination_status.encode(writer)
+    }
+
+    fn decode<RD: Read + ?Sized>(reader: &mut RD) -> Result<Self, Error> {
+        Ok(IterState {
+            param: StateCodec::decode(reader)?,
+            prev_param: StateCodec::decode(reader)?,
+            best_param: StateCodec::decode(reader)?,
+            prev_best_param: StateCodec::decode(reader)?,
+            cost: StateCodec::decode(reader)?,
+            prev_cost: StateCodec::decode(reader)?,
+            best_cost: StateCodec::decode(reader)?,
+            prev_best_cost: StateCodec::decode(reader)?,
+            target_cost: StateCodec::decode(reader)?,
+            grad: StateCodec::decode(reader)?,
+            prev_grad: StateCodec::decode(reader)?,
+            hessian: StateCodec::decode(reader)?,
+            prev_hessian: StateCodec::decode(reader)?,
+            inv_hessian: StateCodec::decode(reader)?,
+            prev_inv_hessian: StateCodec::decode(reader)?,
+            jacobian: StateCodec::decode(reader)?,
+            prev_jacobian: StateCodec::decode(reader)?,
+            residuals: StateCodec::decode(reader)?,
+            prev_residuals: StateCodec::decode(reader)?,
+            aux: StateCodec::decode(reader)?,
+            iter: StateCodec::decode(reader)?,
+            last_best_iter: StateCodec::decode(reader)?,
+            max_iters: StateCodec::decode(reader)?,
+            counts: StateCodec::decode(reader)?,
+            counting_enabled: StateCodec::decode(reader)?,
+            time: StateCodec::decode(reader)?,
+            iter_time: StateCodec::decode(reader)?,
+            termination_status: StateCodec::decode(reader)?,
+        })
+    }
+}
+
+impl<P, F> StateCodec for PopulationState<P, F>
+where
+    P: StateCodec,
+    F: StateCodec,
+{
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.individual.encode(writer)?;
+        self.prev_individual.encode(writer)?;
+        self.best_individual.encode(writer)?;
+        self.prev_best_individual.encode(writer)?;
+        self.cost.encode(writer)?;
+        self.prev_cost.encode(writer)?;
+        self.best_cost.encode(writer)?;
+        self.prev_best_cost.encode(writer)?;
+        self.target_cost.encode(writer)?;
+        self.population.encode(writer)?;
+        self.population_costs.encode(writer)?;
+        self.iter.encode(writer)?;
+        self.last_best_iter.encode(writer)?;
+        self.max_iters.encode(writer)?;
+        self.counts.encode(writer)?;
+        self.counting_enabled.encode(writer)?;
+        self.time.encode(writer)?;
+        self.iter_time.encode(writer)?;
+        self.termination_status.encode(writer)
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        Ok(PopulationState {
+            individual: StateCodec::decode(reader)?,
+            prev_individual: StateCodec::decode(reader)?,
+            best_individual: StateCodec::decode(reader)?,
+            prev_best_individual: StateCodec::decode(reader)?,
+            cost: StateCodec::decode(reader)?,
+            prev_cost: StateCodec::decode(reader)?,
+            best_cost: StateCodec::decode(reader)?,
+            prev_best_cost: StateCodec::decode(reader)?,
+            target_cost: StateCodec::decode(reader)?,
+            population: StateCodec::decode(reader)?,
+            population_costs: StateCodec::decode(reader)?,
+            iter: StateCodec::decode(reader)?,
+            last_best_iter: StateCodec::decode(reader)?,
+            max_iters: StateCodec::decode(reader)?,
+            counts: StateCodec::decode(reader)?,
+            counting_enabled: StateCodec::decode(reader)?,
+            time: StateCodec::decode(reader)?,
+            iter_time: StateCodec::decode(reader)?,
+            termination_status: StateCodec::decode(reader)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::State;
+
+    fn roundtrip<T: StateCodec>(value: &T) -> T {
+        let mut bytes = vec![];
+        value.encode(&mut bytes).unwrap();
+        let mut reader = bytes.as_slice();
+        let decoded = T::decode(&mut reader).unwrap();
+        assert!(reader.is_empty());
+        decoded
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(roundtrip(&1.5f64).to_ne_bytes(), 1.5f64.to_ne_bytes());
+        assert_eq!(roundtrip(&-2.5f32).to_ne_bytes(), (-2.5f32).to_ne_bytes());
+        assert!(roundtrip(&f64::NAN).is_nan());
+        assert_eq!(
+            roundtrip(&f64::NEG_INFINITY).to_ne_bytes(),
+            f64::NEG_INFINITY.to_ne_bytes()
+        );
+        assert_eq!(roundtrip(&42u64), 42);
+        assert_eq!(roundtrip(&7usize), 7);
+        assert!(roundtrip(&true));
+        assert_eq!(roundtrip(&"äöü".to_string()), "äöü");
+        assert_eq!(roundtrip(&Some(vec![1.0f64, 2.0])), Some(vec![1.0, 2.0]));
+        assert_eq!(roundtrip(&None::<f64>), None);
+        assert_eq!(
+            roundtrip(&Duration::new(3, 999_999_999)),
+            Duration::new(3, 999_999_999)
+        );
+        let status = TerminationStatus::Terminated(TerminationReason::SolverExit("x".into()));
+        assert_eq!(roundtrip(&status), status);
+    }
+
+    #[test]
+    fn test_iterstate() {
+        let mut state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> = IterState::new()
+            .param(vec![1.0, 2.0])
+            .gradient(vec![0.5, -0.5])
+            .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+            .cost(3.0)
+            .max_iters(10)
+            .counting(true);
+        state.update();
+        state.increment_iter();
+        state.counts.insert("cost_count".to_string(), 4);
+        state.counts.insert("gradient_count".to_string(), 2);
+        state.aux.insert("radius".to_string(), 0.25);
+        state.time = Some(Duration::from_millis(1500));
+        let state = state.terminate_with(TerminationReason::MaxItersReached);
+        assert_eq!(roundtrip(&state), state);
+    }
+
+    #[test]
+    fn test_populationstate() {
+        let state: PopulationState<Vec<f64>, f64> = PopulationState::new()
+            .individual(vec![1.0])
+            .population(vec![vec![1.0], vec![2.0]])
+            .population_costs(vec![1.0, 4.0])
+            .cost(1.0);
+        assert_eq!(roundtrip(&state), state);
+    }
+
+    #[test]
+    fn test_deterministic_map_layout() {
+        let a: HashMap<String, u64> = (0..20).map(|i| (i.to_string(), i)).collect();
+        let b: HashMap<String, u64> = (0..20).rev().map(|i| (i.to_string(), i)).collect();
+        let (mut bytes_a, mut bytes_b) = (vec![], vec![]);
+        a.encode(&mut bytes_a).unwrap();
+        b.encode(&mut bytes_b).unwrap();
+        assert_eq!(bytes_a, bytes_b);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        let mut bytes = vec![];
+        write_state(&mut bytes, &state).unwrap();
+
+        // Truncated input
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(
+            read_state::<_, IterState<Vec<f64>, (), (), (), (), f64>>(&mut &truncated[..]).is_err()
+        );
+
+        // Wrong magic bytes
+        let mut wrong = bytes.clone();
+        wrong[0] = b'X';
+        assert!(
+            read_state::<_, IterState<Vec<f64>, (), (), (), (), f64>>(&mut wrong.as_slice())
+                .is_err()
+        );
+
+        // Unsupported version
+        let mut wrong = bytes.clone();
+        wrong[8] = 2;
+        let err = read_state::<_, IterState<Vec<f64>, (), (), (), (), f64>>(&mut wrong.as_slice())
+            .unwrap_err();
+        assert!(err.to_string().contains("version 2"));
+
+        // Invalid boolean
+        assert!(bool::decode(&mut [2u8].as_slice()).is_err());
+    }
+}
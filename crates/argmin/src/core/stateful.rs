@@ -0,0 +1,303 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{CostFunction, Error, Gradient, Hessian, Operator};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Version of [`Operator`] which takes `&mut self`.
+///
+/// Wrap the problem in [`Stateful`] in order to use it with solvers.
+pub trait OperatorMut {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the return value of the operator
+    type Output;
+
+    /// Applies the operator to parameters
+    fn apply(&mut self, param: &Self::Param) -> Result<Self::Output, Error>;
+}
+
+/// Version of [`CostFunction`] which takes `&mut self`.
+///
+/// Wrap the problem in [`Stateful`] in order to use it with solvers.
+pub trait CostFunctionMut {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the return value of the cost function
+    type Output;
+
+    /// Compute cost function
+    fn cost(&mut self, param: &Self::Param) -> Result<Self::Output, Error>;
+}
+
+/// Version of [`Gradient`] which takes `&mut self`.
+///
+/// Wrap the problem in [`Stateful`] in order to use it with solvers.
+pub trait GradientMut {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the gradient
+    type Gradient;
+
+    /// Compute gradient
+    fn gradient(&mut self, param: &Self::Param) -> Result<Self::Gradient, Error>;
+}
+
+/// Version of [`Hessian`] which takes `&mut self`.
+///
+/// Wrap the problem in [`Stateful`] in order to use it with solvers.
+pub trait HessianMut {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the Hessian
+    type Hessian;
+
+    /// Compute Hessian
+    fn hessian(&mut self, param: &Self::Param) -> Result<Self::Hessian, Error>;
+}
+
+/// Adapter which allows to use problems with mutable state with all solvers.
+///
+/// Problems which hold caches, file handles or sessions of a simulator can implement
+/// [`OperatorMut`], [`CostFunctionMut`], [`GradientMut`] and [`HessianMut`], which take
+/// `&mut self`, instead of resorting to interior mutability. `Stateful` implements the
+/// corresponding traits [`Operator`], [`CostFunction`], [`Gradient`] and [`Hessian`] by
+/// serializing all calls via a mutex. Consequently, evaluations are never performed in parallel
+/// and `parallelize` returns `false` for all `bulk_*` methods.
+///
+/// Evaluations are counted by [`Problem`](`crate::core::Problem`) as usual. After the run, the
+/// problem including its state can be retrieved via
+/// [`into_inner`](`Stateful::into_inner`). Cloning a `Stateful` clones the problem and its state.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{CostFunctionMut, Error, Executor, GradientMut, State, Stateful};
+/// # use argmin::solver::gradientdescent::SteepestDescent;
+/// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+/// # use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+/// /// Keeps a log of all parameter vectors it was evaluated at
+/// struct Logging {
+///     log: Vec<Vec<f64>>,
+/// }
+///
+/// impl CostFunctionMut for Logging {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn cost(&mut self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         self.log.push(p.clone());
+///         Ok(rosenbrock(p))
+///     }
+/// }
+///
+/// impl GradientMut for Logging {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&mut self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+///         Ok(rosenbrock_derivative(p))
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let problem = Stateful::new(Logging { log: vec![] });
+/// let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(10))
+///     .run()?;
+/// let logging = res.problem.problem.unwrap().into_inner();
+/// # assert!(!logging.log.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Stateful<O> {
+    /// Wrapped problem
+    op: Mutex<O>,
+}
+
+impl<O> Stateful<O> {
+    /// Construct a new instance of `Stateful`
+    pub fn new(op: O) -> Self {
+        Stateful { op: Mutex::new(op) }
+    }
+
+    /// Returns a mutable reference to the wrapped problem
+    pub fn get_mut(&mut self) -> &mut O {
+        self.op.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the wrapped problem
+    pub fn into_inner(self) -> O {
+        self.op.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the wrapped problem
+    ///
+    /// If a previous evaluation panicked, the state of the problem is used as it was left.
+    fn lock(&self) -> MutexGuard<'_, O> {
+        self.op.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<O: Clone> Clone for Stateful<O> {
+    fn clone(&self) -> Self {
+        Stateful::new(self.lock().clone())
+    }
+}
+
+impl<O: OperatorMut> Operator for Stateful<O> {
+    type Param = O::Param;
+    type Output = O::Output;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.lock().apply(param)
+    }
+
+    fn parallelize(&self) -> bool {
+        false
+    }
+}
+
+impl<O: CostFunctionMut> CostFunction for Stateful<O> {
+    type Param = O::Param;
+    type Output = O::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.lock().cost(param)
+    }
+
+    fn parallelize(&self) -> bool {
+        false
+    }
+}
+
+impl<O: GradientMut> Gradient for Stateful<O> {
+    type Param = O::Param;
+    type Gradient = O::Gradient;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.lock().gradient(param)
+    }
+
+    fn parallelize(&self) -> bool {
+        false
+    }
+}
+
+impl<O: HessianMut> Hessian for Stateful<O> {
+    type Param = O::Param;
+    type Hessian = O::Hessian;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        self.lock().hessian(param)
+    }
+
+    fn parallelize(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Executor, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::quasinewton::LBFGS;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+
+    /// Counts its evaluations in plain fields
+    #[derive(Clone, Default)]
+    struct Counting {
+        applies: u64,
+        costs: u64,
+        gradients: u64,
+        hessians: u64,
+    }
+
+    impl OperatorMut for Counting {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&mut self, p: &Self::Param) -> Result<Self::Output, Error> {
+            self.applies += 1;
+            Ok(p.iter().map(|x| 2.0 * x).collect())
+        }
+    }
+
+    impl CostFunctionMut for Counting {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&mut self, p: &Self::Param) -> Result<Self::Output, Error> {
+            self.costs += 1;
+            Ok(rosenbrock(p))
+        }
+    }
+
+    impl GradientMut for Counting {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&mut self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            self.gradients += 1;
+            Ok(rosenbrock_derivative(p))
+        }
+    }
+
+    impl HessianMut for Counting {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&mut self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+            self.hessians += 1;
+            Ok(rosenbrock_hessian(p))
+        }
+    }
+
+    test_trait_impl!(stateful, Stateful<Vec<f64>>);
+
+    #[test]
+    fn test_state() {
+        let mut problem = Stateful::new(Counting::default());
+        assert_eq!(problem.apply(&vec![1.0, 2.0]).unwrap(), vec![2.0, 4.0]);
+        problem.cost(&vec![1.0, 2.0]).unwrap();
+        problem.bulk_cost(&[vec![1.0], vec![1.0, 2.0]]).unwrap();
+        problem.gradient(&vec![1.0, 2.0]).unwrap();
+        problem.hessian(&vec![1.0, 2.0]).unwrap();
+        assert_eq!(problem.get_mut().costs, 3);
+
+        // Cloning clones the state
+        let clone = problem.clone();
+        clone.cost(&vec![1.0, 2.0]).unwrap();
+        assert_eq!(problem.get_mut().costs, 3);
+        let counting = clone.into_inner();
+        assert_eq!(
+            (
+                counting.applies,
+                counting.costs,
+                counting.gradients,
+                counting.hessians
+            ),
+            (1, 4, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_counts_match_problem() {
+        let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+        let res = Executor::new(Stateful::new(Counting::default()), solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(20).counting(true))
+            .run()
+            .unwrap();
+        let counts = res.state().get_func_counts().clone();
+        let counting = res.problem.problem.unwrap().into_inner();
+        assert_eq!(counts["cost_count"], counting.costs);
+        assert_eq!(counts["gradient_count"], counting.gradients);
+    }
+}
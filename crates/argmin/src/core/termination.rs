@@ -32,6 +32,11 @@ impl TerminationStatus {
     /// assert!(TerminationStatus::Terminated(TerminationReason::SolverConverged).terminated());
     /// assert!(TerminationStatus::Terminated(TerminationReason::Interrupt).terminated());
     /// assert!(TerminationStatus::Terminated(TerminationReason::Timeout).terminated());
+    /// assert!(TerminationStatus::Terminated(TerminationReason::TimeLimitReached).terminated());
+    /// assert!(TerminationStatus::Terminated(TerminationReason::Cancelled).terminated());
+    /// assert!(TerminationStatus::Terminated(TerminationReason::EarlyStopped).terminated());
+    /// assert!(TerminationStatus::Terminated(TerminationReason::Stalled).terminated());
+    /// assert!(TerminationStatus::Terminated(TerminationReason::MaxEvaluationsReached).terminated());
     /// assert!(TerminationStatus::Terminated(TerminationReason::SolverExit("Exit reason".to_string())).terminated());
     /// ```
     pub fn terminated(&self) -> bool {
@@ -49,8 +54,12 @@ impl std::fmt::Display for TerminationStatus {
 }
 
 /// Reasons for optimization algorithms to stop
+///
+/// New reasons may be added in the future, therefore matches on this enum must include a wildcard
+/// arm.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum TerminationReason {
     /// Reached maximum number of iterations
     MaxItersReached,
@@ -61,7 +70,20 @@ pub enum TerminationReason {
     /// Converged
     SolverConverged,
     /// Timeout reached
+    ///
+    /// No longer reported by the `Executor`, which stops runs with `TimeLimitReached` instead.
     Timeout,
+    /// Wall-clock time limit set via `Executor::max_time` (or `Executor::timeout`) reached
+    TimeLimitReached,
+    /// Cancelled via a `CancellationToken`
+    Cancelled,
+    /// Validation cost stopped improving (see `EarlyStopping`)
+    EarlyStopped,
+    /// Relative improvement of the cost over a window of iterations too small (see
+    /// `StallDetection`)
+    Stalled,
+    /// Budget of function evaluations exhausted (see `EvaluationBudget`)
+    MaxEvaluationsReached,
     /// Solver exit with given reason
     SolverExit(String),
 }
@@ -95,6 +117,26 @@ impl TerminationReason {
     ///     "Timeout reached"
     /// );
     /// assert_eq!(
+    ///     TerminationReason::TimeLimitReached.text(),
+    ///     "Time limit reached"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::Cancelled.text(),
+    ///     "Cancelled"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::EarlyStopped.text(),
+    ///     "Validation cost stopped improving"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::Stalled.text(),
+    ///     "Progress stalled"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::MaxEvaluationsReached.text(),
+    ///     "Maximum number of function evaluations reached"
+    /// );
+    /// assert_eq!(
     ///     TerminationReason::SolverExit("Aborted".to_string()).text(),
     ///     "Aborted"
     /// );
@@ -106,6 +148,13 @@ impl TerminationReason {
             TerminationReason::Interrupt => "Interrupt",
             TerminationReason::SolverConverged => "Solver converged",
             TerminationReason::Timeout => "Timeout reached",
+            TerminationReason::TimeLimitReached => "Time limit reached",
+            TerminationReason::Cancelled => "Cancelled",
+            TerminationReason::EarlyStopped => "Validation cost stopped improving",
+            TerminationReason::Stalled => "Progress stalled",
+            TerminationReason::MaxEvaluationsReached => {
+                "Maximum number of function evaluations reached"
+            }
             TerminationReason::SolverExit(reason) => reason.as_ref(),
         }
     }
@@ -339,3 +339,36 @@ impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for TestSolver {
         Ok((state, None))
     }
 }
+
+/// Checkpoint which keeps the serialized solver and state in memory
+///
+/// Clones share the stored checkpoint, which allows resuming a run in a new `Executor`.
+#[cfg(all(test, feature = "serde1"))]
+#[derive(Clone, Default)]
+pub(crate) struct MemoryCheckpoint {
+    /// Serialized solver and state
+    data: std::rc::Rc<std::cell::RefCell<Option<Vec<u8>>>>,
+}
+
+#[cfg(all(test, feature = "serde1"))]
+impl<S, I> crate::core::checkpointing::Checkpoint<S, I> for MemoryCheckpoint
+where
+    S: Serialize + serde::de::DeserializeOwned,
+    I: Serialize + serde::de::DeserializeOwned,
+{
+    fn save(&self, solver: &S, state: &I) -> Result<(), Error> {
+        *self.data.borrow_mut() = Some(bincode::serialize(&(solver, state))?);
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Option<(S, I)>, Error> {
+        match self.data.borrow().as_ref() {
+            Some(data) => Ok(Some(bincode::deserialize(data)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn frequency(&self) -> crate::core::checkpointing::CheckpointingFrequency {
+        crate::core::checkpointing::CheckpointingFrequency::Always
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Wall-clock time used for timing optimization runs.
+//!
+//! [`std::time::Instant`] panics on `wasm32-unknown-unknown`. On this target, [`Instant`] is
+//! therefore based on `Performance.now()` of the browser (via the `web-time` crate); on all other
+//! targets it is a re-export of [`std::time::Instant`]. [`Duration`] is always
+//! [`std::time::Duration`].
+//!
+//! # Example
+//!
+//! ```
+//! use argmin::core::time::{Duration, Instant};
+//!
+//! let start = Instant::now();
+//! let elapsed: Duration = start.elapsed();
+//! # assert!(elapsed >= Duration::ZERO);
+//! ```
+
+pub use web_time::{Duration, Instant};
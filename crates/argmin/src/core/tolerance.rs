@@ -0,0 +1,418 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error, KV};
+use argmin_math::{ArgminL2Norm, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Convergence tolerances shared by solvers.
+///
+/// Solvers stop with
+/// [`TerminationReason::SolverConverged`](`crate::core::TerminationReason::SolverConverged`) once
+/// one of the following criteria is met:
+///
+/// * Gradient: `||g|| < tol_grad`
+/// * Cost: `|f_{k-1} - f_k| < tol_cost`
+/// * Parameter vector: `||x_k - x_{k-1}|| < tol_param`
+///
+/// If relative tolerances are enabled via [`relative`](`ToleranceConfig::relative`), the
+/// right-hand sides are multiplied by `max(|f_k|, 1)`, `max(|f_{k-1}|, |f_k|, 1)` and
+/// `max(||x_k||, 1)`, respectively. This makes the criteria independent of the scaling of the
+/// problem while avoiding overly strict tolerances close to zero.
+///
+/// A tolerance of zero disables the corresponding criterion. Solvers only evaluate the criteria
+/// for the quantities available to them; for instance, Gauss-Newton methods do not compute a
+/// gradient and therefore ignore the gradient tolerance. The defaults of the individual solvers
+/// are documented there; all solvers accept a `ToleranceConfig` via a `with_tolerances` method.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, ToleranceConfig};
+/// # fn main() -> Result<(), Error> {
+/// let tol: ToleranceConfig<f64> = ToleranceConfig::new()
+///     .with_grad(1e-6)?
+///     .with_cost(1e-10)?
+///     .with_param(1e-8)?
+///     .relative(true);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ToleranceConfig<F> {
+    /// Tolerance on the norm of the gradient
+    grad: F,
+    /// Tolerance on the change of the cost function value
+    cost: F,
+    /// Tolerance on the change of the parameter vector
+    param: F,
+    /// Whether tolerances are relative
+    relative: bool,
+}
+
+impl<F: ArgminFloat> ToleranceConfig<F> {
+    /// Constructs a new `ToleranceConfig` with absolute tolerances of `sqrt(EPSILON)` on the
+    /// gradient and `EPSILON` on the cost. The parameter tolerance is disabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ToleranceConfig;
+    /// let tol: ToleranceConfig<f64> = ToleranceConfig::new();
+    /// # assert_eq!(tol.grad().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+    /// # assert_eq!(tol.cost().to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+    /// # assert_eq!(tol.param().to_ne_bytes(), 0.0f64.to_ne_bytes());
+    /// # assert!(!tol.is_relative());
+    /// ```
+    pub fn new() -> Self {
+        ToleranceConfig::with_defaults(F::epsilon().sqrt(), F::epsilon())
+    }
+
+    /// Constructs absolute tolerances on gradient and cost without validation.
+    pub(crate) fn with_defaults(grad: F, cost: F) -> Self {
+        ToleranceConfig {
+            grad,
+            cost,
+            param: float!(0.0),
+            relative: false,
+        }
+    }
+
+    /// Sets the tolerance on the norm of the gradient.
+    ///
+    /// Must be non-negative; zero disables the criterion.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// let tol: ToleranceConfig<f64> = ToleranceConfig::new().with_grad(1e-6)?;
+    /// # assert_eq!(tol.grad().to_ne_bytes(), 1e-6f64.to_ne_bytes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_grad(mut self, grad: F) -> Result<Self, Error> {
+        self.grad = check_tolerance(grad, "gradient")?;
+        Ok(self)
+    }
+
+    /// Sets the tolerance on the change of the cost function value.
+    ///
+    /// Must be non-negative; zero disables the criterion.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// let tol: ToleranceConfig<f64> = ToleranceConfig::new().with_cost(1e-10)?;
+    /// # assert_eq!(tol.cost().to_ne_bytes(), 1e-10f64.to_ne_bytes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_cost(mut self, cost: F) -> Result<Self, Error> {
+        self.cost = check_tolerance(cost, "cost")?;
+        Ok(self)
+    }
+
+    /// Sets the tolerance on the change of the parameter vector.
+    ///
+    /// Must be non-negative; zero disables the criterion.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// let tol: ToleranceConfig<f64> = ToleranceConfig::new().with_param(1e-8)?;
+    /// # assert_eq!(tol.param().to_ne_bytes(), 1e-8f64.to_ne_bytes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_param(mut self, param: F) -> Result<Self, Error> {
+        self.param = check_tolerance(param, "parameter")?;
+        Ok(self)
+    }
+
+    /// Sets whether the tolerances are relative (default: `false`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ToleranceConfig;
+    /// let tol: ToleranceConfig<f64> = ToleranceConfig::new().relative(true);
+    /// # assert!(tol.is_relative());
+    /// ```
+    #[must_use]
+    pub fn relative(mut self, relative: bool) -> Self {
+        self.relative = relative;
+        self
+    }
+
+    /// Returns the tolerance on the norm of the gradient.
+    pub fn grad(&self) -> F {
+        self.grad
+    }
+
+    /// Returns the tolerance on the change of the cost function value.
+    pub fn cost(&self) -> F {
+        self.cost
+    }
+
+    /// Returns the tolerance on the change of the parameter vector.
+    pub fn param(&self) -> F {
+        self.param
+    }
+
+    /// Returns `true` if the tolerances are relative.
+    pub fn is_relative(&self) -> bool {
+        self.relative
+    }
+
+    /// Returns `true` if the norm of the gradient `grad_norm` is below the tolerance.
+    ///
+    /// `cost` is the current cost function value, which is only required for relative
+    /// tolerances.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// let tol: ToleranceConfig<f64> = ToleranceConfig::new().with_grad(1e-6)?;
+    /// assert!(tol.grad_converged(1e-7, 100.0));
+    /// assert!(!tol.relative(true).grad_converged(1e-3, 100.0));
+    /// assert!(tol.relative(true).grad_converged(1e-5, 100.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn grad_converged(&self, grad_norm: F, cost: F) -> bool {
+        grad_norm < self.grad * self.scale(cost.abs())
+    }
+
+    /// Returns `true` if the change from `prev_cost` to `cost` is below the tolerance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// let tol: ToleranceConfig<f64> = ToleranceConfig::new().with_cost(1e-6)?;
+    /// assert!(!tol.cost_converged(1000.0, 999.9999));
+    /// assert!(tol.relative(true).cost_converged(1000.0, 999.9999));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cost_converged(&self, prev_cost: F, cost: F) -> bool {
+        (prev_cost - cost).abs() < self.cost * self.scale(prev_cost.abs().max(cost.abs()))
+    }
+
+    /// Returns `true` if the step from `prev_param` to `param` is below the tolerance.
+    ///
+    /// Returns `false` if the criterion is disabled or if one of the parameter vectors is not
+    /// available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// let tol: ToleranceConfig<f64> = ToleranceConfig::new().with_param(1e-6)?;
+    /// assert!(tol.param_converged(Some(&vec![1.0, 2.0]), Some(&vec![1.0, 2.0 + 1e-7])));
+    /// assert!(!tol.param_converged(Some(&vec![1.0, 2.0]), Some(&vec![1.0, 2.1])));
+    /// assert!(!tol.param_converged(None, Some(&vec![1.0, 2.0])));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn param_converged<P>(&self, prev_param: Option<&P>, param: Option<&P>) -> bool
+    where
+        P: ArgminSub<P, P> + ArgminL2Norm<F>,
+    {
+        if self.param <= float!(0.0) {
+            return false;
+        }
+        match (prev_param, param) {
+            (Some(prev_param), Some(param)) => {
+                param.sub(prev_param).l2_norm() < self.param * self.scale(param.l2_norm())
+            }
+            _ => false,
+        }
+    }
+
+    /// Reports for each enabled criterion the measured quantity, the effective tolerance (after
+    /// scaling, if relative tolerances are used) and whether the criterion is met.
+    ///
+    /// This is used by solvers to implement
+    /// [`Solver::termination_diagnostics`](`crate::core::Solver::termination_diagnostics`).
+    /// Criteria are reported as `grad_norm`/`grad_tol`/`grad_converged`,
+    /// `cost_change`/`cost_tol`/`cost_converged` and `param_change`/`param_tol`/`param_converged`.
+    /// Criteria which are disabled or for which the required quantities are not available are
+    /// omitted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// let tol: ToleranceConfig<f64> = ToleranceConfig::new().with_grad(1e-6)?.with_cost(1e-8)?;
+    /// let kv = tol.diagnostics(Some(1e-3), 2.0, 2.0 - 1e-9, Some(&vec![1.0]), Some(&vec![1.1]));
+    /// assert_eq!(kv.get("grad_converged").unwrap().get_bool(), Some(false));
+    /// assert_eq!(kv.get("cost_converged").unwrap().get_bool(), Some(true));
+    /// assert_eq!(kv.get("cost_tol").unwrap().get_float(), Some(1e-8));
+    /// // The parameter tolerance is disabled by default
+    /// assert!(kv.get("param_change").is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diagnostics<P>(
+        &self,
+        grad_norm: Option<F>,
+        prev_cost: F,
+        cost: F,
+        prev_param: Option<&P>,
+        param: Option<&P>,
+    ) -> KV
+    where
+        P: ArgminSub<P, P> + ArgminL2Norm<F>,
+    {
+        let mut kv = KV::new();
+        if let (true, Some(grad_norm)) = (self.grad > float!(0.0), grad_norm) {
+            kv.insert("grad_norm", grad_norm.into());
+            kv.insert("grad_tol", (self.grad * self.scale(cost.abs())).into());
+            kv.insert(
+                "grad_converged",
+                self.grad_converged(grad_norm, cost).into(),
+            );
+        }
+        if self.cost > float!(0.0) && prev_cost.is_finite() && cost.is_finite() {
+            kv.insert("cost_change", (prev_cost - cost).abs().into());
+            kv.insert(
+                "cost_tol",
+                (self.cost * self.scale(prev_cost.abs().max(cost.abs()))).into(),
+            );
+            kv.insert(
+                "cost_converged",
+                self.cost_converged(prev_cost, cost).into(),
+            );
+        }
+        if let (true, Some(prev_param), Some(param)) = (self.param > float!(0.0), prev_param, param)
+        {
+            kv.insert("param_change", param.sub(prev_param).l2_norm().into());
+            kv.insert(
+                "param_tol",
+                (self.param * self.scale(param.l2_norm())).into(),
+            );
+            kv.insert(
+                "param_converged",
+                self.param_converged(Some(prev_param), Some(param)).into(),
+            );
+        }
+        kv
+    }
+
+    /// Returns the factor the tolerances are multiplied with.
+    fn scale(&self, magnitude: F) -> F {
+        if self.relative {
+            magnitude.max(float!(1.0))
+        } else {
+            float!(1.0)
+        }
+    }
+}
+
+impl<F: ArgminFloat> Default for ToleranceConfig<F> {
+    fn default() -> Self {
+        ToleranceConfig::new()
+    }
+}
+
+/// Returns `tol` if it is non-negative.
+fn check_tolerance<F: ArgminFloat>(tol: F, name: &str) -> Result<F, Error> {
+    if tol.is_nan() || tol < float!(0.0) {
+        return Err(argmin_error!(
+            InvalidParameter,
+            format!("`ToleranceConfig`: {name} tolerance must be >= 0.")
+        ));
+    }
+    Ok(tol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_invalid_parameters() {
+        let tol: ToleranceConfig<f64> = ToleranceConfig::new();
+        assert_error!(
+            tol.with_grad(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`ToleranceConfig`: gradient tolerance must be >= 0.\""
+        );
+        assert!(tol.with_cost(f64::NAN).is_err());
+        assert!(tol.with_param(-1e-10).is_err());
+        assert!(tol.with_param(0.0).is_ok());
+    }
+
+    #[test]
+    fn test_disabled() {
+        let tol: ToleranceConfig<f64> = ToleranceConfig::new()
+            .with_grad(0.0)
+            .unwrap()
+            .with_cost(0.0)
+            .unwrap();
+        assert!(!tol.grad_converged(0.0, 1.0));
+        assert!(!tol.cost_converged(1.0, 1.0));
+        assert!(!tol.param_converged(Some(&vec![1.0]), Some(&vec![1.0])));
+    }
+
+    #[test]
+    fn test_relative_param() {
+        let tol: ToleranceConfig<f64> = ToleranceConfig::new()
+            .with_param(1e-6)
+            .unwrap()
+            .relative(true);
+        // Step of 1e-4 relative to a norm of 1000
+        assert!(tol.param_converged(Some(&vec![1000.0]), Some(&vec![1000.0001])));
+        // Tolerances are not scaled below 1
+        assert!(!tol.param_converged(Some(&vec![0.001]), Some(&vec![0.0010011])));
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let tol: ToleranceConfig<f64> = ToleranceConfig::new()
+            .with_param(1e-6)
+            .unwrap()
+            .relative(true);
+        // Costs are not finite before the first iteration
+        let kv = tol.diagnostics(None, f64::INFINITY, 1.0, Some(&vec![1000.0]), None);
+        assert!(kv.get("grad_norm").is_none());
+        assert!(kv.get("cost_change").is_none());
+        assert!(kv.get("param_change").is_none());
+
+        let kv = tol.diagnostics(
+            Some(1.0),
+            2.0,
+            1.0,
+            Some(&vec![1000.0]),
+            Some(&vec![1000.0001]),
+        );
+        assert_eq!(kv.get("grad_converged").unwrap().get_bool(), Some(false));
+        assert_eq!(kv.get("cost_converged").unwrap().get_bool(), Some(false));
+        assert_eq!(kv.get("param_converged").unwrap().get_bool(), Some(true));
+        assert_relative_eq!(
+            kv.get("param_tol").unwrap().get_float().unwrap(),
+            1e-6 * 1000.0001,
+            epsilon = 1e-12
+        );
+    }
+}
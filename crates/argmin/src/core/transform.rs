@@ -0,0 +1,552 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, CostFunction, Error, Gradient, Hessian};
+use std::ops::{Index, IndexMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Invertible map from the solver space to the user space of a single parameter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Reparameterization<F> {
+    /// `x = exp(y)`
+    Log,
+    /// `x = lower + (upper - lower) / (1 + exp(-y))`
+    Logit { lower: F, upper: F },
+    /// `x = scale * y + offset`
+    Affine { scale: F, offset: F },
+}
+
+impl<F: ArgminFloat> Reparameterization<F> {
+    /// Returns `x` as well as the first and second derivative of `x` with respect to `y`.
+    fn forward(&self, y: F) -> (F, F, F) {
+        match *self {
+            Reparameterization::Log => {
+                let x = y.exp();
+                (x, x, x)
+            }
+            Reparameterization::Logit { lower, upper } => {
+                // Numerically stable logistic function
+                let s = if y >= float!(0.0) {
+                    float!(1.0) / (float!(1.0) + (-y).exp())
+                } else {
+                    let e = y.exp();
+                    e / (float!(1.0) + e)
+                };
+                let width = upper - lower;
+                let d1 = width * s * (float!(1.0) - s);
+                (lower + width * s, d1, d1 * (float!(1.0) - float!(2.0) * s))
+            }
+            Reparameterization::Affine { scale, offset } => {
+                (scale * y + offset, scale, float!(0.0))
+            }
+        }
+    }
+
+    /// Returns `y` for a given `x`.
+    fn inverse(&self, index: usize, x: F) -> Result<F, Error> {
+        match *self {
+            Reparameterization::Log => {
+                if x.is_nan() || x <= float!(0.0) {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        format!("`Transform`: parameter {index} must be > 0, got {x}.")
+                    ));
+                }
+                Ok(x.ln())
+            }
+            Reparameterization::Logit { lower, upper } => {
+                if x.is_nan() || x <= lower || x >= upper {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        format!(
+                            "`Transform`: parameter {index} must be in ({lower}, {upper}), got {x}."
+                        )
+                    ));
+                }
+                Ok(((x - lower) / (upper - x)).ln())
+            }
+            Reparameterization::Affine { scale, offset } => Ok((x - offset) / scale),
+        }
+    }
+}
+
+/// Reparameterizes a problem in order to let unconstrained solvers respect positivity or box
+/// constraints.
+///
+/// The solver operates on unconstrained parameters `y`, which are mapped component-wise to the
+/// parameters `x = g(y)` of the wrapped problem:
+///
+/// * [`log`](`Transform::log`): `x_i = exp(y_i)`, hence `x_i > 0`.
+/// * [`logit`](`Transform::logit`): `x_i = lower + (upper - lower) / (1 + exp(-y_i))`, hence
+///   `lower < x_i < upper`.
+/// * [`affine`](`Transform::affine`): `x_i = scale * y_i + offset`, which is useful for
+///   parameters of very different magnitudes.
+///
+/// Components without a reparameterization are passed through unchanged. Gradient and Hessian
+/// are transformed via the chain rule. Since the second derivative of `log` and `logit` does not
+/// vanish, the Hessian requires an additional evaluation of the gradient of the wrapped problem,
+/// which is counted separately and available via [`gradient_count`](`Transform::gradient_count`).
+///
+/// All parameter vectors seen by the solver, including the initial parameter vector and the
+/// parameter vectors in the final state, live in the solver space. Use
+/// [`to_solver`](`Transform::to_solver`) and [`to_user`](`Transform::to_user`) to convert between
+/// both spaces.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{CostFunction, Error, Executor, Gradient, State, Transform};
+/// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+/// # use argmin::solver::quasinewton::LBFGS;
+/// /// Not defined for `x[0] <= 0`
+/// struct Problem {}
+///
+/// impl CostFunction for Problem {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(p[0] - p[0].ln() + p[1].powi(2))
+///     }
+/// }
+///
+/// impl Gradient for Problem {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+///         Ok(vec![1.0 - 1.0 / p[0], 2.0 * p[1]])
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let problem = Transform::new(Problem {}).log(0);
+/// let init_param = problem.to_solver(&vec![5.0, 1.0])?;
+/// let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.param(init_param).max_iters(50))
+///     .run()?;
+/// let problem = res.problem.problem.as_ref().unwrap();
+/// let best = problem.to_user(res.state().get_best_param().unwrap())?;
+/// # assert!((best[0] - 1.0).abs() < 1e-4);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Transform<O, F> {
+    /// Wrapped problem
+    op: O,
+    /// Reparameterizations of the individual components, sorted by index
+    maps: Vec<(usize, Reparameterization<F>)>,
+    /// Number of gradient evaluations needed for the Hessian
+    gradient_count: AtomicU64,
+}
+
+impl<O, F: ArgminFloat> Transform<O, F> {
+    /// Constructs a new `Transform` which wraps `op` and passes all components through unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Transform;
+    /// # struct UserDefinedProblem {}
+    /// let problem: Transform<_, f64> = Transform::new(UserDefinedProblem {});
+    /// ```
+    pub fn new(op: O) -> Self {
+        Transform {
+            op,
+            maps: vec![],
+            gradient_count: AtomicU64::new(0),
+        }
+    }
+
+    /// Constrains component `index` to be positive via `x = exp(y)`.
+    ///
+    /// Replaces any previous reparameterization of this component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Transform;
+    /// # struct UserDefinedProblem {}
+    /// let problem: Transform<_, f64> = Transform::new(UserDefinedProblem {}).log(0).log(2);
+    /// ```
+    #[must_use]
+    pub fn log(mut self, index: usize) -> Self {
+        self.set(index, Reparameterization::Log);
+        self
+    }
+
+    /// Constrains component `index` to the open interval `(lower, upper)` via
+    /// `x = lower + (upper - lower) / (1 + exp(-y))`.
+    ///
+    /// Both bounds must be finite and `lower` must be smaller than `upper`. Replaces any previous
+    /// reparameterization of this component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Transform};
+    /// # struct UserDefinedProblem {}
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = Transform::new(UserDefinedProblem {}).logit(1, -1.0, 1.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn logit(mut self, index: usize, lower: F, upper: F) -> Result<Self, Error> {
+        if !lower.is_finite() || !upper.is_finite() || lower >= upper {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Transform`: bounds must be finite and lower bound must be < upper bound."
+            ));
+        }
+        self.set(index, Reparameterization::Logit { lower, upper });
+        Ok(self)
+    }
+
+    /// Scales component `index` via `x = scale * y + offset`.
+    ///
+    /// `scale` must be finite and nonzero and `offset` must be finite. Replaces any previous
+    /// reparameterization of this component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Transform};
+    /// # struct UserDefinedProblem {}
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = Transform::new(UserDefinedProblem {}).affine(0, 1e3, 0.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn affine(mut self, index: usize, scale: F, offset: F) -> Result<Self, Error> {
+        if !scale.is_finite() || scale == float!(0.0) || !offset.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Transform`: scale must be finite and != 0 and offset must be finite."
+            ));
+        }
+        self.set(index, Reparameterization::Affine { scale, offset });
+        Ok(self)
+    }
+
+    /// Sets the reparameterization of component `index`.
+    fn set(&mut self, index: usize, map: Reparameterization<F>) {
+        match self.maps.binary_search_by_key(&index, |(i, _)| *i) {
+            Ok(pos) => self.maps[pos].1 = map,
+            Err(pos) => self.maps.insert(pos, (index, map)),
+        }
+    }
+
+    /// Returns an error if a reparameterized component is out of bounds of a parameter vector
+    /// of length `n`.
+    fn check_len(&self, n: usize) -> Result<(), Error> {
+        match self.maps.last() {
+            Some(&(index, _)) if index >= n => Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`Transform`: index {index} out of bounds for parameter vector of length {n}."
+                )
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Maps `param` from the solver space to the user space and returns the first and second
+    /// derivatives of the reparameterized components.
+    fn forward<P>(&self, param: &P) -> Result<(P, Vec<(usize, F, F)>), Error>
+    where
+        P: Clone + IndexMut<usize, Output = F>,
+        for<'a> &'a P: IntoIterator<Item = &'a F>,
+    {
+        self.check_len(param.into_iter().count())?;
+        let mut x = param.clone();
+        let mut derivatives = Vec::with_capacity(self.maps.len());
+        for &(i, map) in self.maps.iter() {
+            let (xi, d1, d2) = map.forward(param[i]);
+            x[i] = xi;
+            derivatives.push((i, d1, d2));
+        }
+        Ok((x, derivatives))
+    }
+
+    /// Maps `param` from the solver space to the user space.
+    ///
+    /// This is needed in order to interpret the parameter vectors in the state, for instance the
+    /// best parameter vector after the run.
+    pub fn to_user<P>(&self, param: &P) -> Result<P, Error>
+    where
+        P: Clone + IndexMut<usize, Output = F>,
+        for<'a> &'a P: IntoIterator<Item = &'a F>,
+    {
+        Ok(self.forward(param)?.0)
+    }
+
+    /// Maps `param` from the user space to the solver space.
+    ///
+    /// This is needed in order to provide an initial parameter vector to the solver. Returns an
+    /// error if `param` violates one of the constraints.
+    pub fn to_solver<P>(&self, param: &P) -> Result<P, Error>
+    where
+        P: Clone + IndexMut<usize, Output = F>,
+        for<'a> &'a P: IntoIterator<Item = &'a F>,
+    {
+        self.check_len(param.into_iter().count())?;
+        let mut y = param.clone();
+        for &(i, map) in self.maps.iter() {
+            y[i] = map.inverse(i, param[i])?;
+        }
+        Ok(y)
+    }
+
+    /// Returns the number of gradient evaluations needed for the computation of Hessians.
+    pub fn gradient_count(&self) -> u64 {
+        self.gradient_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns a reference to the wrapped problem
+    pub fn inner(&self) -> &O {
+        &self.op
+    }
+
+    /// Returns the wrapped problem
+    pub fn into_inner(self) -> O {
+        self.op
+    }
+}
+
+impl<O: Clone, F: Clone> Clone for Transform<O, F> {
+    fn clone(&self) -> Self {
+        Transform {
+            op: self.op.clone(),
+            maps: self.maps.clone(),
+            gradient_count: AtomicU64::new(self.gradient_count.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl<O, P, F> CostFunction for Transform<O, F>
+where
+    O: CostFunction<Param = P>,
+    P: Clone + IndexMut<usize, Output = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = O::Output;
+
+    /// Evaluates the wrapped cost function at `g(param)`.
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.op.cost(&self.to_user(param)?)
+    }
+
+    fn parallelize(&self) -> bool {
+        self.op.parallelize()
+    }
+}
+
+impl<O, P, G, F> Gradient for Transform<O, F>
+where
+    O: Gradient<Param = P, Gradient = G>,
+    P: Clone + IndexMut<usize, Output = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    G: IndexMut<usize, Output = F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Gradient = G;
+
+    /// Computes `dg/dy * grad f(g(param))` component-wise.
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let (x, derivatives) = self.forward(param)?;
+        let mut grad = self.op.gradient(&x)?;
+        for (i, d1, _) in derivatives {
+            grad[i] = grad[i] * d1;
+        }
+        Ok(grad)
+    }
+
+    fn parallelize(&self) -> bool {
+        Gradient::parallelize(&self.op)
+    }
+}
+
+impl<O, P, G, H, R, F> Hessian for Transform<O, F>
+where
+    O: Gradient<Param = P, Gradient = G> + Hessian<Param = P, Hessian = H>,
+    P: Clone + IndexMut<usize, Output = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    G: Index<usize, Output = F>,
+    H: IndexMut<usize, Output = R>,
+    R: IndexMut<usize, Output = F> + ?Sized,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Hessian = H;
+
+    /// Computes `J H(g(param)) J + diag(d2g/dy2 * grad f(g(param)))`, where `J = diag(dg/dy)`.
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let n = param.into_iter().count();
+        let (x, derivatives) = self.forward(param)?;
+        let mut hessian = self.op.hessian(&x)?;
+        for &(i, d1, _) in derivatives.iter() {
+            for j in 0..n {
+                hessian[i][j] = hessian[i][j] * d1;
+                hessian[j][i] = hessian[j][i] * d1;
+            }
+        }
+        if derivatives.iter().any(|&(_, _, d2)| d2 != float!(0.0)) {
+            self.gradient_count.fetch_add(1, Ordering::Relaxed);
+            let grad = self.op.gradient(&x)?;
+            for (i, _, d2) in derivatives {
+                hessian[i][i] = hessian[i][i] + grad[i] * d2;
+            }
+        }
+        Ok(hessian)
+    }
+
+    fn parallelize(&self) -> bool {
+        Hessian::parallelize(&self.op)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{check_derivatives, ArgminError, Executor, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::quasinewton::LBFGS;
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+
+    #[derive(Clone)]
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(rosenbrock_derivative(p))
+        }
+    }
+
+    impl Hessian for Rosenbrock {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(rosenbrock_hessian(p))
+        }
+    }
+
+    test_trait_impl!(transform, Transform<Vec<f64>, f64>);
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert_error!(
+            Transform::new(Rosenbrock {}).logit(0, 1.0, 1.0),
+            ArgminError,
+            "Invalid parameter: \"`Transform`: bounds must be finite and lower bound must be < upper bound.\""
+        );
+        assert!(Transform::new(Rosenbrock {})
+            .logit(0, f64::NEG_INFINITY, 1.0)
+            .is_err());
+        assert_error!(
+            Transform::new(Rosenbrock {}).affine(0, 0.0, 1.0),
+            ArgminError,
+            "Invalid parameter: \"`Transform`: scale must be finite and != 0 and offset must be finite.\""
+        );
+        let problem = Transform::new(Rosenbrock {}).log(2);
+        assert_error!(
+            problem.cost(&vec![1.0, 1.0]),
+            ArgminError,
+            "Invalid parameter: \"`Transform`: index 2 out of bounds for parameter vector of length 2.\""
+        );
+        let problem = Transform::new(Rosenbrock {}).log(0);
+        assert_error!(
+            problem.to_solver(&vec![0.0, 1.0]),
+            ArgminError,
+            "Invalid parameter: \"`Transform`: parameter 0 must be > 0, got 0.\""
+        );
+        let problem = Transform::new(Rosenbrock {}).logit(1, 0.0, 2.0).unwrap();
+        assert!(problem.to_solver(&vec![1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let problem = Transform::new(Rosenbrock {})
+            .log(0)
+            .logit(1, -1.0, 3.0)
+            .unwrap()
+            .affine(2, -4.0, 2.0)
+            .unwrap()
+            // Replaces the previous reparameterization
+            .log(2);
+        let x = vec![0.3, 2.5, 7.0, -5.0];
+        let y = problem.to_solver(&x).unwrap();
+        assert_relative_eq!(y[0], 0.3f64.ln());
+        assert_relative_eq!(y[3], -5.0);
+        let x_roundtrip = problem.to_user(&y).unwrap();
+        for (a, b) in x.iter().zip(x_roundtrip.iter()) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+        // Bounds are respected even for extreme values
+        let x = problem.to_user(&vec![800.0, -800.0, 0.0, 0.0]).unwrap();
+        assert!(x[0].is_infinite());
+        assert_relative_eq!(x[1], -1.0);
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let problem = Transform::new(Rosenbrock {})
+            .log(0)
+            .logit(1, -2.0, 3.0)
+            .unwrap()
+            .affine(2, 0.5, 1.0)
+            .unwrap();
+        check_derivatives(&problem, &vec![0.2, -0.7, 0.4, 1.3]).unwrap();
+        assert_eq!(problem.gradient_count(), 1);
+
+        // No additional gradient evaluation for affine transformations
+        let problem = Transform::new(Rosenbrock {}).affine(0, 2.0, 0.0).unwrap();
+        check_derivatives(&problem, &vec![0.2, -0.7]).unwrap();
+        assert_eq!(problem.gradient_count(), 0);
+    }
+
+    #[test]
+    fn test_box_constraints() {
+        // Unconstrained minimum at (1, 1) lies outside of the box
+        let problem = Transform::new(Rosenbrock {})
+            .logit(0, -0.5, 0.5)
+            .unwrap()
+            .logit(1, -0.5, 0.5)
+            .unwrap();
+        let init_param = problem.to_solver(&vec![-0.2, 0.2]).unwrap();
+        let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+        let res = Executor::new(problem, solver)
+            .configure(|state| state.param(init_param).max_iters(100))
+            .run()
+            .unwrap();
+        let problem = res.problem.problem.as_ref().unwrap();
+        let best = problem
+            .to_user(res.state().get_best_param().unwrap())
+            .unwrap();
+        assert!(best.iter().all(|x| (-0.5..0.5).contains(x)));
+        assert_relative_eq!(best[0], 0.5, epsilon = 1e-3);
+        assert_relative_eq!(best[1], 0.25, epsilon = 1e-3);
+    }
+}
@@ -30,6 +30,9 @@
 //!
 //! # Highlights
 //!
+//! * High-level interface (`argmin::minimize`, requires the `minimize` feature)
+//! * [Projections onto convex sets](`crate::projection`)
+//! * [Proximal operators](`crate::prox`)
 //! * [Checkpointing](`crate::core::checkpointing`)
 //! * [Observers](`crate::core::observers`)
 //!
@@ -45,32 +48,48 @@
 //!   - [Cauchy point method](`crate::solver::trustregion::CauchyPoint`)
 //!   - [Dogleg method](`crate::solver::trustregion::Dogleg`)
 //!   - [Steihaug method](`crate::solver::trustregion::Steihaug`)
+//!   - [GLTR method](`crate::solver::trustregion::GLTR`)
 //!   
 //! - [Steepest descent](`crate::solver::gradientdescent::SteepestDescent`)
 //!
+//! - [Gradient descent with Polyak step size](`crate::solver::gradientdescent::PolyakGradientDescent`)
+//!
 //! - [Conjugate gradient methods](`crate::solver::conjugategradient`)
 //!   - [Conjugate gradient method](`crate::solver::conjugategradient::ConjugateGradient`)
 //!   - [Nonlinear conjugate gradient method](`crate::solver::conjugategradient::NonlinearConjugateGradient`)
 //!
+//! - [Krylov subspace methods for linear systems](`crate::solver::linearsystem`)
+//!   - [GMRES(m)](`crate::solver::linearsystem::GMRES`)
+//!   - [MINRES](`crate::solver::linearsystem::MINRES`)
+//!   - [LSQR](`crate::solver::linearsystem::LSQR`)
+//!   - [LSMR](`crate::solver::linearsystem::LSMR`)
+//!
 //! - [Newton methods](`crate::solver::newton`)
 //!   - [Newton's method](`crate::solver::newton::Newton`)
 //!   - [Newton-CG](solver/newton/newton_cg/struct.NewtonCG.html)
 //!
 //! - [Quasi-Newton methods](`crate::solver::quasinewton`)
 //!   - [BFGS](`crate::solver::quasinewton::BFGS`)
+//!   - [BFGS with direct Hessian approximation](`crate::solver::quasinewton::DirectBFGS`)
 //!   - [L-BFGS](`crate::solver::quasinewton::LBFGS`)
 //!   - [DFP](`crate::solver::quasinewton::DFP`)
+//!   - [Broyden class](`crate::solver::quasinewton::BroydenClass`)
 //!   - [SR1](`crate::solver::quasinewton::SR1`)
 //!   - [SR1-TrustRegion](`crate::solver::quasinewton::SR1TrustRegion`)
 //!
 //! - [Gauss-Newton methods](`crate::solver::gaussnewton`)
 //!   - [Gauss-Newton method](`crate::solver::gaussnewton::GaussNewton`)
 //!   - [Gauss-Newton method with linesearch](`crate::solver::gaussnewton::GaussNewtonLS`)
+//!   - [Structured BFGS method](`crate::solver::gaussnewton::StructuredBFGS`)
 //!
 //! - [Golden-section search](`crate::solver::goldensectionsearch::GoldenSectionSearch`)
 //!
 //! - [Landweber iteration](`crate::solver::landweber::Landweber`)
 //!
+//! - [Nonnegative least squares](`crate::solver::nnls::Nnls`)
+//!
+//! - [Jacobian-free Newton-Krylov method](`crate::solver::newtonkrylov::NewtonKrylov`)
+//!
 //! - [Brent's methods](`crate::solver::brent`)
 //!   - [Brent's minimization method](`crate::solver::brent::BrentOpt`)
 //!   - [Brent's root finding method](`crate::solver::brent::BrentRoot`)
@@ -121,6 +140,13 @@ pub mod core;
 /// Solvers
 pub mod solver;
 
+#[cfg(feature = "minimize")]
+pub mod minimize;
+
+pub mod projection;
+
+pub mod prox;
+
 #[cfg(test)]
 #[cfg(feature = "_ndarrayl")]
 mod tests;
@@ -0,0 +1,813 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # High-level interface
+//!
+//! [`minimize`] minimizes a function of a `Vec<f64>` with a single call. The algorithm is
+//! selected via [`Algorithm`] (either directly or from a string such as `"lbfgs"`) and all
+//! settings, such as tolerances, bounds and limits on the number of iterations and function
+//! evaluations, are collected in [`Options`]. The solver, line search and [`Executor`] are
+//! constructed internally.
+//!
+//! This module requires the `minimize` feature, which enables the `vec` feature of `argmin-math`.
+//!
+//! The cost function and optionally its gradient are given as closures via [`Objective`]. If no
+//! gradient is provided, gradient-based algorithms approximate it via central differences (see
+//! [`WithCentralDiff`]).
+//!
+//! Bounds are handled natively by Particle Swarm Optimization. For all other algorithms, bounded
+//! components are reparameterized via [`Transform::logit`], which requires the initial parameter
+//! vector to lie strictly within the bounds.
+//!
+//! This interface covers the common case of unconstrained or box-constrained minimization of
+//! functions of `Vec<f64>`. Other parameter types, solvers, observers and checkpointing require
+//! setting up solvers and the [`Executor`] directly.
+//!
+//! # Example
+//!
+//! ```
+//! # use argmin::core::Error;
+//! use argmin::minimize::{minimize, Algorithm, Objective, Options};
+//! use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+//!
+//! # fn main() -> Result<(), Error> {
+//! let objective = Objective::new(|x: &[f64]| rosenbrock(x))
+//!     .with_gradient(|x: &[f64]| rosenbrock_derivative(x));
+//! let options = Options {
+//!     tol_grad: Some(1e-10),
+//!     ..Options::new(Algorithm::Lbfgs)
+//! };
+//! let res = minimize(objective, &[-1.2, 1.0], &options)?;
+//! # assert!((res.param[0] - 1.0).abs() < 1e-4);
+//! # assert!((res.param[1] - 1.0).abs() < 1e-4);
+//!
+//! // Derivative-free and with bounds
+//! let options = Options {
+//!     bounds: Some((vec![-2.0, -2.0], vec![2.0, 2.0])),
+//!     max_evals: Some(2000),
+//!     ..Options::new("nelder_mead".parse()?)
+//! };
+//! let res = minimize(Objective::new(|x: &[f64]| rosenbrock(x)), &[-1.2, 1.0], &options)?;
+//! println!("{:?} after {} evaluations", res.param, res.cost_count);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::{
+    CostFunction, Error, EvaluationBudget, Executor, Gradient, IterState, PopulationState, Solver,
+    State, SyncAlias, TerminationStatus, Transform, WithCentralDiff,
+};
+use crate::solver::conjugategradient::{beta::PolakRibiere, NonlinearConjugateGradient};
+use crate::solver::gradientdescent::SteepestDescent;
+use crate::solver::linesearch::MoreThuenteLineSearch;
+use crate::solver::neldermead::{InitialSimplex, NelderMead};
+use crate::solver::particleswarm::{Particle, ParticleSwarm};
+use crate::solver::quasinewton::{BFGS, LBFGS};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Algorithms available via [`minimize`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", serde(rename_all = "snake_case"))]
+pub enum Algorithm {
+    /// [`SteepestDescent`] with More-Thuente line search (`"steepest_descent"`)
+    SteepestDescent,
+    /// [`NonlinearConjugateGradient`] with Polak-Ribiere update and More-Thuente line search
+    /// (`"conjugate_gradient"`)
+    ConjugateGradient,
+    /// [`BFGS`] with More-Thuente line search (`"bfgs"`)
+    Bfgs,
+    /// [`LBFGS`] with More-Thuente line search (`"lbfgs"`)
+    Lbfgs,
+    /// [`NelderMead`] (`"nelder_mead"`)
+    NelderMead,
+    /// [`ParticleSwarm`] (`"particle_swarm"`), requires bounds
+    ParticleSwarm,
+}
+
+impl Algorithm {
+    /// All algorithms
+    pub const ALL: [Algorithm; 6] = [
+        Algorithm::SteepestDescent,
+        Algorithm::ConjugateGradient,
+        Algorithm::Bfgs,
+        Algorithm::Lbfgs,
+        Algorithm::NelderMead,
+        Algorithm::ParticleSwarm,
+    ];
+
+    /// Returns the name which is accepted by [`FromStr`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::minimize::Algorithm;
+    /// assert_eq!(Algorithm::NelderMead.name(), "nelder_mead");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::SteepestDescent => "steepest_descent",
+            Algorithm::ConjugateGradient => "conjugate_gradient",
+            Algorithm::Bfgs => "bfgs",
+            Algorithm::Lbfgs => "lbfgs",
+            Algorithm::NelderMead => "nelder_mead",
+            Algorithm::ParticleSwarm => "particle_swarm",
+        }
+    }
+
+    /// Returns `true` if the algorithm requires the gradient.
+    pub fn uses_gradient(&self) -> bool {
+        !matches!(self, Algorithm::NelderMead | Algorithm::ParticleSwarm)
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    /// Parses the name of an algorithm (see [`Algorithm::name`]); `-` is accepted instead of `_`
+    /// and case is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Error;
+    /// # use argmin::minimize::Algorithm;
+    /// # fn main() -> Result<(), Error> {
+    /// let algorithm: Algorithm = "L-BFGS".parse()?;
+    /// assert_eq!(algorithm, Algorithm::Lbfgs);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.to_lowercase().replace('-', "_");
+        let normalized = match normalized.as_str() {
+            "l_bfgs" => "lbfgs",
+            "nelder_mead" | "neldermead" => "nelder_mead",
+            other => other,
+        };
+        Algorithm::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name() == normalized)
+            .ok_or_else(|| {
+                let names: Vec<_> = Algorithm::ALL.iter().map(|a| a.name()).collect();
+                argmin_error!(
+                    InvalidParameter,
+                    format!(
+                        "`minimize`: unknown algorithm `{s}`, expected one of {}.",
+                        names.join(", ")
+                    )
+                )
+            })
+    }
+}
+
+/// Settings of [`minimize`].
+///
+/// Settings which do not apply to the selected algorithm are ignored. With the `serde1` feature
+/// enabled, the options can be (de)serialized; missing fields are set to their default values.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::minimize::{Algorithm, Options};
+/// # use std::time::Duration;
+/// let options = Options {
+///     max_iters: 500,
+///     tol_grad: Some(1e-8),
+///     max_time: Some(Duration::from_secs(10)),
+///     ..Options::new(Algorithm::Bfgs)
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", serde(default))]
+pub struct Options {
+    /// Algorithm (default: [`Algorithm::Lbfgs`])
+    pub algorithm: Algorithm,
+    /// Maximum number of iterations (default: 1000)
+    pub max_iters: u64,
+    /// Maximum number of evaluations of the cost function. Computing a gradient counts as one
+    /// evaluation if the gradient is provided and as `2n` evaluations if it is approximated via
+    /// finite differences. The limit is checked after each iteration and may therefore be exceeded
+    /// slightly.
+    pub max_evals: Option<u64>,
+    /// Stops once the cost function value is below this value
+    pub target_cost: Option<f64>,
+    /// Wall-clock time limit
+    pub max_time: Option<Duration>,
+    /// Tolerance on the norm of the gradient (`bfgs` and `lbfgs`)
+    pub tol_grad: Option<f64>,
+    /// Tolerance on the change of the cost function value (`bfgs` and `lbfgs`) or on the
+    /// standard deviation of the cost function values of the simplex (`nelder_mead`)
+    pub tol_cost: Option<f64>,
+    /// Lower and upper bounds, which must be finite (required by `particle_swarm`)
+    pub bounds: Option<(Vec<f64>, Vec<f64>)>,
+    /// Number of stored iterations of `lbfgs` (default: 7)
+    pub lbfgs_memory: usize,
+    /// Number of particles of `particle_swarm`, must be larger than 0 (default: 40)
+    pub num_particles: usize,
+    /// Seed for stochastic algorithms, which enables the deterministic mode of the executor (see
+    /// [`Executor::deterministic`]). Cannot be combined with `max_time`.
+    pub seed: Option<u64>,
+}
+
+impl Options {
+    /// Constructs default options for `algorithm`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::minimize::{Algorithm, Options};
+    /// let options = Options::new(Algorithm::NelderMead);
+    /// # assert_eq!(options.algorithm, Algorithm::NelderMead);
+    /// # assert_eq!(options.max_iters, 1000);
+    /// ```
+    pub fn new(algorithm: Algorithm) -> Self {
+        Options {
+            algorithm,
+            ..Options::default()
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            algorithm: Algorithm::Lbfgs,
+            max_iters: 1000,
+            max_evals: None,
+            target_cost: None,
+            max_time: None,
+            tol_grad: None,
+            tol_cost: None,
+            bounds: None,
+            lbfgs_memory: 7,
+            num_particles: 40,
+            seed: None,
+        }
+    }
+}
+
+/// Function to be minimized by [`minimize`], consisting of a cost function and optionally its
+/// gradient.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::minimize::Objective;
+/// let objective = Objective::new(|x: &[f64]| x[0].powi(2) + 2.0 * x[1].powi(2))
+///     .with_gradient(|x: &[f64]| vec![2.0 * x[0], 4.0 * x[1]]);
+/// ```
+pub struct Objective<C, G = fn(&[f64]) -> Vec<f64>> {
+    /// Cost function
+    cost: C,
+    /// Gradient
+    gradient: Option<G>,
+}
+
+impl<C> Objective<C> {
+    /// Constructs an objective from a cost function.
+    pub fn new(cost: C) -> Self
+    where
+        C: Fn(&[f64]) -> f64,
+    {
+        Objective {
+            cost,
+            gradient: None,
+        }
+    }
+}
+
+impl<C, G> Objective<C, G> {
+    /// Adds the gradient of the cost function.
+    pub fn with_gradient<G2>(self, gradient: G2) -> Objective<C, G2>
+    where
+        G2: Fn(&[f64]) -> Vec<f64>,
+    {
+        Objective {
+            cost: self.cost,
+            gradient: Some(gradient),
+        }
+    }
+}
+
+/// Result of [`minimize`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Minimum {
+    /// Algorithm used
+    pub algorithm: Algorithm,
+    /// Best parameter vector found
+    pub param: Vec<f64>,
+    /// Cost function value of `param`
+    pub cost: f64,
+    /// Number of iterations
+    pub iters: u64,
+    /// Number of cost function evaluations, including those of finite differences
+    pub cost_count: u64,
+    /// Number of gradient evaluations, either of the provided gradient or via finite differences
+    pub gradient_count: u64,
+    /// Reason for the termination of the run
+    pub termination_status: TerminationStatus,
+}
+
+/// Cost function given as closure
+struct CostClosure<C>(C);
+
+impl<C: Fn(&[f64]) -> f64> CostFunction for CostClosure<C> {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok((self.0)(param))
+    }
+}
+
+/// Problem which uses the provided gradient or central differences otherwise
+struct UserProblem<C, G> {
+    /// Cost function wrapped for finite differences
+    fd: WithCentralDiff<CostClosure<C>>,
+    /// Gradient
+    gradient: Option<G>,
+}
+
+impl<C: Fn(&[f64]) -> f64, G> CostFunction for UserProblem<C, G> {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.fd.cost(param)
+    }
+}
+
+impl<C, G> Gradient for UserProblem<C, G>
+where
+    C: Fn(&[f64]) -> f64 + SyncAlias,
+    G: Fn(&[f64]) -> Vec<f64>,
+{
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let Some(gradient) = self.gradient.as_ref() else {
+            return self.fd.gradient(param);
+        };
+        let grad = gradient(param);
+        if grad.len() != param.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`minimize`: gradient has {} elements, expected {}.",
+                    grad.len(),
+                    param.len()
+                )
+            ));
+        }
+        Ok(grad)
+    }
+}
+
+type Wrapped<C, G> = Transform<UserProblem<C, G>, f64>;
+
+/// Gives access to the best parameter vector of the states of the available solvers.
+trait BestParam: State<Float = f64> {
+    fn best_param_vec(&self) -> Option<&Vec<f64>>;
+}
+
+impl<G, H> BestParam for IterState<Vec<f64>, G, (), H, (), f64>
+where
+    Self: State<Param = Vec<f64>, Float = f64>,
+{
+    fn best_param_vec(&self) -> Option<&Vec<f64>> {
+        self.get_best_param()
+    }
+}
+
+impl BestParam for PopulationState<Particle<Vec<f64>, f64>, f64> {
+    fn best_param_vec(&self) -> Option<&Vec<f64>> {
+        self.get_best_param().map(|particle| &particle.position)
+    }
+}
+
+/// Minimizes `objective` starting from `x0` with the algorithm and settings given in `options`.
+///
+/// Returns an error if the options are invalid or if the solver fails. See the
+/// [module documentation](`crate::minimize`) for details and an example.
+pub fn minimize<C, G>(
+    objective: Objective<C, G>,
+    x0: &[f64],
+    options: &Options,
+) -> Result<Minimum, Error>
+where
+    C: Fn(&[f64]) -> f64 + SyncAlias,
+    G: Fn(&[f64]) -> Vec<f64> + SyncAlias,
+{
+    let n = x0.len();
+    if n == 0 {
+        return Err(argmin_error!(
+            InvalidParameter,
+            "`minimize`: initial parameter vector must not be empty."
+        ));
+    }
+    let algorithm = options.algorithm;
+    let has_gradient = objective.gradient.is_some();
+    let mut problem: Wrapped<C, G> = Transform::new(UserProblem {
+        fd: WithCentralDiff::new(CostClosure(objective.cost)),
+        gradient: objective.gradient,
+    });
+    if algorithm == Algorithm::ParticleSwarm && options.num_particles == 0 {
+        return Err(argmin_error!(
+            InvalidParameter,
+            "`minimize`: `num_particles` must be > 0."
+        ));
+    }
+    if let Some((lower, upper)) = options.bounds.as_ref() {
+        check_bounds(x0, lower, upper)?;
+        if algorithm != Algorithm::ParticleSwarm {
+            for i in 0..n {
+                problem = problem.logit(i, lower[i], upper[i])?;
+            }
+        }
+    }
+    let init_param = problem.to_solver(&x0.to_vec())?;
+
+    let mut budget = None;
+    if let Some(max_evals) = options.max_evals {
+        let weight = if has_gradient { 1.0 } else { 2.0 * n as f64 };
+        budget = Some(
+            EvaluationBudget::new()
+                .with_weight("gradient_count", weight)?
+                .with_total(max_evals as f64)?,
+        );
+    }
+    let max_iters = options.max_iters;
+
+    match algorithm {
+        Algorithm::SteepestDescent => {
+            let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+            execute(
+                problem,
+                solver,
+                options,
+                budget,
+                |state: IterState<_, _, _, _, _, _>| state.param(init_param).max_iters(max_iters),
+            )
+        }
+        Algorithm::ConjugateGradient => {
+            let solver =
+                NonlinearConjugateGradient::new(MoreThuenteLineSearch::new(), PolakRibiere::new())
+                    .restart_orthogonality(0.1);
+            execute(
+                problem,
+                solver,
+                options,
+                budget,
+                |state: IterState<_, _, _, _, _, _>| state.param(init_param).max_iters(max_iters),
+            )
+        }
+        Algorithm::Bfgs => {
+            let mut solver = BFGS::new(MoreThuenteLineSearch::new());
+            if let Some(tol_grad) = options.tol_grad {
+                solver = solver.with_tolerance_grad(tol_grad)?;
+            }
+            if let Some(tol_cost) = options.tol_cost {
+                solver = solver.with_tolerance_cost(tol_cost)?;
+            }
+            let inv_hessian: Vec<Vec<f64>> = (0..n)
+                .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+                .collect();
+            execute(
+                problem,
+                solver,
+                options,
+                budget,
+                |state: IterState<_, _, _, _, _, _>| {
+                    state
+                        .param(init_param)
+                        .inv_hessian(inv_hessian)
+                        .max_iters(max_iters)
+                },
+            )
+        }
+        Algorithm::Lbfgs => {
+            let mut solver = LBFGS::new(MoreThuenteLineSearch::new(), options.lbfgs_memory);
+            if let Some(tol_grad) = options.tol_grad {
+                solver = solver.with_tolerance_grad(tol_grad)?;
+            }
+            if let Some(tol_cost) = options.tol_cost {
+                solver = solver.with_tolerance_cost(tol_cost)?;
+            }
+            execute(
+                problem,
+                solver,
+                options,
+                budget,
+                |state: IterState<_, _, _, _, _, _>| state.param(init_param).max_iters(max_iters),
+            )
+        }
+        Algorithm::NelderMead => {
+            let mut solver = NelderMead::from_initial_point(init_param, InitialSimplex::default())?;
+            if let Some(tol_cost) = options.tol_cost {
+                solver = solver.with_sd_tolerance(tol_cost)?;
+            }
+            execute(
+                problem,
+                solver,
+                options,
+                budget,
+                |state: IterState<_, (), (), (), (), _>| state.max_iters(max_iters),
+            )
+        }
+        Algorithm::ParticleSwarm => {
+            let bounds = options.bounds.clone().ok_or_else(argmin_error_closure!(
+                InvalidParameter,
+                "`minimize`: `particle_swarm` requires bounds."
+            ))?;
+            let solver = ParticleSwarm::new(bounds, options.num_particles);
+            execute(
+                problem,
+                solver,
+                options,
+                budget,
+                |state: PopulationState<_, _>| state.max_iters(max_iters),
+            )
+        }
+    }
+}
+
+/// Checks that lengths of `lower` and `upper` match `x0` and that `x0` lies within the bounds.
+fn check_bounds(x0: &[f64], lower: &[f64], upper: &[f64]) -> Result<(), Error> {
+    if lower.len() != x0.len() || upper.len() != x0.len() {
+        return Err(argmin_error!(
+            InvalidParameter,
+            format!(
+                "`minimize`: bounds have {} and {} elements, expected {}.",
+                lower.len(),
+                upper.len(),
+                x0.len()
+            )
+        ));
+    }
+    for (i, ((&x, &l), &u)) in x0.iter().zip(lower).zip(upper).enumerate() {
+        if !(l.is_finite() && u.is_finite() && l < u) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`minimize`: bounds of parameter {i} must be finite with lower < upper.")
+            ));
+        }
+        if !(l < x && x < u) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`minimize`: initial parameter {i} must be in ({l}, {u}), got {x}.")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Runs `solver` on `problem` with the settings shared by all algorithms.
+fn execute<C, G, S, I>(
+    problem: Wrapped<C, G>,
+    solver: S,
+    options: &Options,
+    budget: Option<EvaluationBudget>,
+    configure: impl FnOnce(I) -> I,
+) -> Result<Minimum, Error>
+where
+    C: Fn(&[f64]) -> f64,
+    S: Solver<Wrapped<C, G>, I>,
+    I: BestParam,
+{
+    let mut executor = Executor::new(problem, solver).configure(configure);
+    if let Some(target_cost) = options.target_cost {
+        executor = executor.target_cost(target_cost);
+    }
+    if let Some(max_time) = options.max_time {
+        executor = executor.max_time(max_time);
+    }
+    if let Some(seed) = options.seed {
+        executor = executor.deterministic(seed);
+    }
+    if let Some(budget) = budget {
+        executor = executor.evaluation_budget(budget);
+    }
+    let res = executor.run()?;
+
+    let problem = res
+        .problem
+        .problem
+        .as_ref()
+        .ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`minimize`: problem was not returned by the executor."
+        ))?;
+    let state = res.state();
+    let best = state.best_param_vec().ok_or_else(argmin_error_closure!(
+        PotentialBug,
+        "`minimize`: no best parameter vector in final state."
+    ))?;
+    let counts = res.func_counts();
+    let count = |name: &str| counts.get(name).copied().unwrap_or(0);
+    Ok(Minimum {
+        algorithm: options.algorithm,
+        param: problem.to_user(best)?,
+        cost: state.get_best_cost(),
+        iters: state.get_iter(),
+        cost_count: count("cost_count") + problem.inner().fd.cost_count(),
+        gradient_count: count("gradient_count"),
+        termination_status: state.get_termination_status().clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::TerminationReason;
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+
+    fn quadratic(x: &[f64]) -> f64 {
+        (x[0] - 1.0).powi(2) + 10.0 * (x[1] + 0.5).powi(2)
+    }
+
+    fn quadratic_derivative(x: &[f64]) -> Vec<f64> {
+        vec![2.0 * (x[0] - 1.0), 20.0 * (x[1] + 0.5)]
+    }
+
+    #[test]
+    fn test_algorithm_from_str() {
+        for algorithm in Algorithm::ALL {
+            assert_eq!(algorithm.name().parse::<Algorithm>().unwrap(), algorithm);
+            assert_eq!(algorithm.to_string(), algorithm.name());
+        }
+        assert_eq!("L-BFGS".parse::<Algorithm>().unwrap(), Algorithm::Lbfgs);
+        assert_eq!(
+            "Nelder-Mead".parse::<Algorithm>().unwrap(),
+            Algorithm::NelderMead
+        );
+        assert_eq!(
+            "cobyla".parse::<Algorithm>().unwrap_err().to_string(),
+            concat!(
+                "Invalid parameter: \"`minimize`: unknown algorithm `cobyla`, expected one of ",
+                "steepest_descent, conjugate_gradient, bfgs, lbfgs, nelder_mead, ",
+                "particle_swarm.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_all_algorithms() {
+        for algorithm in Algorithm::ALL {
+            let options = Options {
+                bounds: Some((vec![-5.0, -5.0], vec![5.0, 5.0])),
+                target_cost: Some(1e-8),
+                seed: Some(42),
+                ..Options::new(algorithm)
+            };
+            let objective = Objective::new(quadratic).with_gradient(quadratic_derivative);
+            let res = minimize(objective, &[-1.0, 2.0], &options).unwrap();
+            assert_eq!(res.algorithm, algorithm);
+            assert!(res.cost < 1e-8, "{algorithm}: {}", res.cost);
+            assert_relative_eq!(res.param[0], 1.0, epsilon = 1e-3);
+            assert_relative_eq!(res.param[1], -0.5, epsilon = 1e-3);
+            assert_relative_eq!(res.cost, quadratic(&res.param), epsilon = 1e-12);
+            assert_eq!(
+                res.termination_status,
+                TerminationStatus::Terminated(TerminationReason::TargetCostReached)
+            );
+            assert!(res.cost_count > 0);
+            assert_eq!(res.gradient_count > 0, algorithm.uses_gradient());
+        }
+    }
+
+    #[test]
+    fn test_finite_differences() {
+        let options = Options {
+            tol_grad: Some(1e-8),
+            ..Options::new(Algorithm::Lbfgs)
+        };
+        let analytic = minimize(
+            Objective::new(rosenbrock).with_gradient(rosenbrock_derivative),
+            &[-1.2, 1.0],
+            &options,
+        )
+        .unwrap();
+        let numeric = minimize(Objective::new(rosenbrock), &[-1.2, 1.0], &options).unwrap();
+        for res in [&analytic, &numeric] {
+            assert_relative_eq!(res.param[0], 1.0, epsilon = 1e-4);
+            assert_relative_eq!(res.param[1], 1.0, epsilon = 1e-4);
+        }
+        // Each gradient requires 4 cost function evaluations
+        assert!(numeric.cost_count >= analytic.cost_count + 4 * numeric.gradient_count);
+    }
+
+    #[test]
+    fn test_bounds() {
+        // Unconstrained minimum at (1, -0.5)
+        for algorithm in Algorithm::ALL {
+            let options = Options {
+                bounds: Some((vec![2.0, -1.0], vec![3.0, 1.0])),
+                seed: Some(42),
+                max_iters: 200,
+                ..Options::new(algorithm)
+            };
+            let res = minimize(Objective::new(quadratic), &[2.5, 0.5], &options).unwrap();
+            assert!(res.param[0] >= 2.0 && res.param[0] < 2.01, "{algorithm}");
+            assert_relative_eq!(res.param[1], -0.5, epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_max_evals() {
+        let options = Options {
+            max_evals: Some(100),
+            ..Options::new(Algorithm::NelderMead)
+        };
+        let res = minimize(Objective::new(rosenbrock), &[-1.2, 1.0], &options).unwrap();
+        assert_eq!(
+            res.termination_status,
+            TerminationStatus::Terminated(TerminationReason::MaxEvaluationsReached)
+        );
+        assert!(res.cost_count >= 100 && res.cost_count < 110);
+    }
+
+    #[test]
+    fn test_seed() {
+        let options = Options {
+            bounds: Some((vec![-5.0, -5.0], vec![5.0, 5.0])),
+            max_iters: 10,
+            seed: Some(7),
+            ..Options::new(Algorithm::ParticleSwarm)
+        };
+        let a = minimize(Objective::new(rosenbrock), &[0.0, 0.0], &options).unwrap();
+        let b = minimize(Objective::new(rosenbrock), &[0.0, 0.0], &options).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let cases = [
+            (
+                Options::new(Algorithm::ParticleSwarm),
+                "`minimize`: `particle_swarm` requires bounds.",
+            ),
+            (
+                Options {
+                    bounds: Some((vec![-1.0], vec![1.0])),
+                    ..Options::default()
+                },
+                "`minimize`: bounds have 1 and 1 elements, expected 2.",
+            ),
+            (
+                Options {
+                    bounds: Some((vec![-1.0, 1.0], vec![1.0, 1.0])),
+                    ..Options::default()
+                },
+                "`minimize`: bounds of parameter 1 must be finite with lower < upper.",
+            ),
+            (
+                Options {
+                    bounds: Some((vec![-1.0, -1.0], vec![1.0, 0.5])),
+                    ..Options::default()
+                },
+                "`minimize`: initial parameter 1 must be in (-1, 0.5), got 0.5.",
+            ),
+            (
+                Options {
+                    bounds: Some((vec![-1.0, -1.0], vec![1.0, 1.0])),
+                    num_particles: 0,
+                    ..Options::new(Algorithm::ParticleSwarm)
+                },
+                "`minimize`: `num_particles` must be > 0.",
+            ),
+        ];
+        for (options, msg) in cases {
+            let err = minimize(Objective::new(quadratic), &[0.0, 0.5], &options).unwrap_err();
+            assert_eq!(err.to_string(), format!("Invalid parameter: \"{msg}\""));
+        }
+        let err = minimize(
+            Objective::new(quadratic).with_gradient(|_: &[f64]| vec![1.0]),
+            &[0.0, 0.5],
+            &Options::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid parameter: \"`minimize`: gradient has 1 elements, expected 2.\""
+        );
+    }
+}
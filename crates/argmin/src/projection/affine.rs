@@ -0,0 +1,149 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::Error;
+use crate::projection::Projection;
+use argmin_math::{ArgminDot, ArgminSolve, ArgminSub, ArgminTranspose};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Affine set
+///
+/// The set `{x : Ax = b}` with a matrix `A` with full row rank and a vector `b`.
+///
+/// The projection is
+///
+/// `P(x) = x - A^T (A A^T)^{-1} (Ax - b)`.
+///
+/// `A^T` and `A A^T` are computed once during construction; each projection requires a solution
+/// of a linear system with `A A^T`, which has as many rows as there are constraints.
+///
+/// Note that the matrix product of the `Vec<Vec<_>>` backend currently only supports square
+/// matrices; for fewer constraints than variables, use the `ndarray` or `nalgebra` backends.
+///
+/// # Example
+///
+/// ```
+/// use argmin::projection::{AffineSet, Projection};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// // x_0 + 2 x_1 = 1 and x_1 = 2
+/// let affine = AffineSet::new(vec![vec![1.0f64, 2.0], vec![0.0, 1.0]], vec![1.0, 2.0]);
+/// let x = affine.project(&vec![0.0, 0.0])?;
+/// # assert!((x[0] + 3.0).abs() < 1e-12);
+/// # assert!((x[1] - 2.0).abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct AffineSet<M, B> {
+    /// Matrix `A`
+    matrix: M,
+    /// Transpose of `A`
+    transposed: M,
+    /// `A A^T`
+    gram: M,
+    /// Right hand side `b`
+    rhs: B,
+}
+
+impl<M, B> AffineSet<M, B>
+where
+    M: Clone + ArgminTranspose<M> + ArgminDot<M, M>,
+{
+    /// Construct a new instance of [`AffineSet`]
+    ///
+    /// The rows of `matrix` must be linearly independent, otherwise `A A^T` is singular.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::projection::AffineSet;
+    /// let affine = AffineSet::new(vec![vec![1.0f64, 0.0], vec![0.0, 1.0]], vec![1.0, 2.0]);
+    /// ```
+    pub fn new(matrix: M, rhs: B) -> Self {
+        let transposed = matrix.clone().t();
+        let gram = matrix.dot(&transposed);
+        AffineSet {
+            matrix,
+            transposed,
+            gram,
+            rhs,
+        }
+    }
+}
+
+impl<P, M, B> Projection<P> for AffineSet<M, B>
+where
+    P: ArgminSub<P, P>,
+    M: ArgminDot<P, B> + ArgminDot<B, P> + ArgminSolve<B, B>,
+    B: ArgminSub<B, B>,
+{
+    fn project(&self, param: &P) -> Result<P, Error> {
+        let residual = self.matrix.dot(param).sub(&self.rhs);
+        let multipliers = self.gram.solve(&residual)?;
+        Ok(param.sub(&self.transposed.dot(&multipliers)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new() {
+        let AffineSet {
+            matrix,
+            transposed,
+            gram,
+            rhs,
+        } = AffineSet::new(vec![vec![1.0f64, 2.0], vec![0.0, 1.0]], vec![1.0, 2.0]);
+        assert_eq!(matrix, vec![vec![1.0, 2.0], vec![0.0, 1.0]]);
+        assert_eq!(transposed, vec![vec![1.0, 0.0], vec![2.0, 1.0]]);
+        assert_eq!(gram, vec![vec![5.0, 2.0], vec![2.0, 1.0]]);
+        assert_eq!(rhs, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_project() {
+        // The set consists of the single point (-3, 2)
+        let affine = AffineSet::new(vec![vec![1.0f64, 2.0], vec![0.0, 1.0]], vec![1.0, 2.0]);
+        let p = affine.project(&vec![3.0, -1.0]).unwrap();
+        assert_relative_eq!(p[0], -3.0, epsilon = 1e-12);
+        assert_relative_eq!(p[1], 2.0, epsilon = 1e-12);
+    }
+
+    #[cfg(feature = "_ndarrayl")]
+    #[test]
+    fn test_project_underdetermined() {
+        use ndarray::{array, Array1};
+
+        let affine = AffineSet::new(
+            array![[1.0f64, 2.0, 0.0], [0.0, 1.0, 1.0]],
+            array![1.0, 2.0],
+        );
+        let x: Array1<f64> = array![3.0, -1.0, 4.0];
+        let p = affine.project(&x).unwrap();
+
+        // Feasible
+        assert_relative_eq!(p[0] + 2.0 * p[1], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(p[1] + p[2], 2.0, epsilon = 1e-12);
+
+        // x - p is orthogonal to the null space of A, which is spanned by (2, -1, 1)
+        let d = &x - &p;
+        assert_relative_eq!(2.0 * d[0] - d[1] + d[2], 0.0, epsilon = 1e-12);
+
+        // Points within the set are not changed
+        let q = affine.project(&p).unwrap();
+        for (qi, pi) in q.iter().zip(p.iter()) {
+            assert_relative_eq!(qi, pi, epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::Error;
+use crate::projection::Projection;
+use argmin_math::ArgminMinMax;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Box
+///
+/// The set `{x : lower <= x <= upper}`, where the inequalities hold element-wise. The projection
+/// clamps each element to its bounds. Infinite bounds are allowed; `lower` must not be larger than
+/// `upper` in any element.
+///
+/// # Example
+///
+/// ```
+/// use argmin::projection::{Bounds, Projection};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let bounds = Bounds::new(vec![0.0f64, -1.0], vec![1.0, f64::INFINITY]);
+/// let x = bounds.project(&vec![2.0, -3.0])?;
+/// # assert_eq!(x, vec![1.0, -1.0]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Bounds<P> {
+    /// Lower bound
+    lower: P,
+    /// Upper bound
+    upper: P,
+}
+
+impl<P> Bounds<P> {
+    /// Construct a new instance of [`Bounds`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::projection::Bounds;
+    /// let bounds = Bounds::new(vec![0.0f64, 0.0], vec![1.0, 1.0]);
+    /// ```
+    pub fn new(lower: P, upper: P) -> Self {
+        Bounds { lower, upper }
+    }
+}
+
+impl<P> Projection<P> for Bounds<P>
+where
+    P: ArgminMinMax,
+{
+    fn project(&self, param: &P) -> Result<P, Error> {
+        Ok(P::min(&P::max(param, &self.lower), &self.upper))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project() {
+        let bounds = Bounds::new(vec![0.0f64, -1.0, -2.0], vec![1.0, 1.0, f64::INFINITY]);
+        let x = bounds.project(&vec![2.0, 0.5, -3.0]).unwrap();
+        assert_eq!(x, vec![1.0, 0.5, -2.0]);
+        let x = bounds.project(&vec![-1.0, -5.0, 1e10]).unwrap();
+        assert_eq!(x, vec![0.0, -1.0, 1e10]);
+    }
+}
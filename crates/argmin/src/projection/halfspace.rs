@@ -0,0 +1,151 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use crate::projection::Projection;
+use argmin_math::{ArgminDot, ArgminScaledSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Half-space
+///
+/// The set `{x : a^T x <= b}` with normal vector `a` and offset `b`.
+///
+/// Points violating the inequality are moved along `a` onto the bounding hyperplane:
+///
+/// `P(x) = x - (a^T x - b) / (a^T a) * a`
+///
+/// # Example
+///
+/// ```
+/// use argmin::projection::{HalfSpace, Projection};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// // x_0 + x_1 <= 1
+/// let halfspace = HalfSpace::new(vec![1.0f64, 1.0], 1.0)?;
+/// let x = halfspace.project(&vec![1.0, 2.0])?;
+/// # assert!(x[0].abs() < 1e-12);
+/// # assert!((x[1] - 1.0).abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct HalfSpace<P, F> {
+    /// Normal vector `a`
+    normal: P,
+    /// Offset `b`
+    offset: F,
+    /// `a^T a`
+    normal_sq: F,
+}
+
+impl<P, F> HalfSpace<P, F>
+where
+    P: ArgminDot<P, F>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`HalfSpace`]
+    ///
+    /// The normal vector `normal` must not vanish and `offset` must be finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::projection::HalfSpace;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let halfspace = HalfSpace::new(vec![1.0f64, 0.0], 2.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(normal: P, offset: F) -> Result<Self, Error> {
+        let normal_sq = normal.dot(&normal);
+        if normal_sq <= float!(0.0) || !normal_sq.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HalfSpace`: normal vector must be finite and must not vanish."
+            ));
+        }
+        if !offset.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HalfSpace`: offset must be finite."
+            ));
+        }
+        Ok(HalfSpace {
+            normal,
+            offset,
+            normal_sq,
+        })
+    }
+}
+
+impl<P, F> Projection<P> for HalfSpace<P, F>
+where
+    P: Clone + ArgminDot<P, F> + ArgminScaledSub<P, F, P>,
+    F: ArgminFloat,
+{
+    fn project(&self, param: &P) -> Result<P, Error> {
+        let violation = self.normal.dot(param) - self.offset;
+        if violation <= float!(0.0) {
+            return Ok(param.clone());
+        }
+        Ok(param.scaled_sub(&(violation / self.normal_sq), &self.normal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new() {
+        let HalfSpace {
+            normal,
+            offset,
+            normal_sq,
+        } = HalfSpace::new(vec![3.0f64, 4.0], 1.0).unwrap();
+        assert_eq!(normal, vec![3.0, 4.0]);
+        assert_eq!(offset.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(normal_sq.to_ne_bytes(), 25.0f64.to_ne_bytes());
+
+        for normal in [vec![0.0f64, 0.0], vec![f64::NAN, 1.0]] {
+            let res = HalfSpace::new(normal, 1.0);
+            assert_error!(
+                res,
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`HalfSpace`: normal vector must be finite and must not ",
+                    "vanish.\""
+                )
+            );
+        }
+
+        let res = HalfSpace::new(vec![1.0f64, 0.0], f64::INFINITY);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`HalfSpace`: offset must be finite.\""
+        );
+    }
+
+    #[test]
+    fn test_project() {
+        let halfspace = HalfSpace::new(vec![3.0f64, 4.0], 5.0).unwrap();
+        let x = halfspace.project(&vec![1.0, 0.0]).unwrap();
+        assert_eq!(x, vec![1.0, 0.0]);
+        let x = halfspace.project(&vec![4.0, 4.0]).unwrap();
+        // a^T x - b = 23
+        assert_relative_eq!(x[0], 4.0 - 23.0 * 3.0 / 25.0, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 4.0 - 23.0 * 4.0 / 25.0, epsilon = 1e-12);
+        assert_relative_eq!(3.0 * x[0] + 4.0 * x[1], 5.0, epsilon = 1e-12);
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use crate::projection::{simplex::project_simplex, Projection};
+use argmin_math::{
+    ArgminAdd, ArgminL1Norm, ArgminLInfNorm, ArgminMinMax, ArgminMul, ArgminSignum, ArgminSub,
+    ArgminZeroLike,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # L1 ball
+///
+/// The set `{x : ||x||_1 <= radius}`.
+///
+/// Points outside of the ball are projected by projecting their absolute values onto the simplex
+/// of the same radius (see [`Simplex`](`crate::projection::Simplex`)) and restoring the signs
+/// afterwards (Duchi et al., 2008).
+///
+/// # Example
+///
+/// ```
+/// use argmin::projection::{L1Ball, Projection};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let ball = L1Ball::new(1.0f64)?;
+/// let x = ball.project(&vec![2.0, -1.0])?;
+/// # assert!((x[0] - 1.0).abs() < 1e-12);
+/// # assert!(x[1].abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ## References
+///
+/// Duchi, J., Shalev-Shwartz, S., Singer, Y., Chandra, T. (2008): Efficient projections onto
+/// the l1-ball for learning in high dimensions. ICML 2008, 272–279.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct L1Ball<F> {
+    /// Radius of the ball
+    radius: F,
+}
+
+impl<F> L1Ball<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`L1Ball`]
+    ///
+    /// `radius` must be finite and larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::projection::L1Ball;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ball = L1Ball::new(1.0f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(radius: F) -> Result<Self, Error> {
+        if radius <= float!(0.0) || !radius.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`L1Ball`: radius must be finite and larger than 0."
+            ));
+        }
+        Ok(L1Ball { radius })
+    }
+}
+
+impl<P, F> Projection<P> for L1Ball<F>
+where
+    P: Clone
+        + ArgminAdd<F, P>
+        + ArgminSub<F, P>
+        + ArgminMul<P, P>
+        + ArgminMinMax
+        + ArgminSignum
+        + ArgminZeroLike
+        + ArgminL1Norm<F>
+        + ArgminLInfNorm<F>,
+    F: ArgminFloat,
+{
+    fn project(&self, param: &P) -> Result<P, Error> {
+        if param.l1_norm() <= self.radius {
+            return Ok(param.clone());
+        }
+        let signs = param.clone().signum();
+        let abs = param.mul(&signs);
+        Ok(project_simplex(&abs, self.radius).mul(&signs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new() {
+        let L1Ball { radius } = L1Ball::new(2.0f64).unwrap();
+        assert_eq!(radius.to_ne_bytes(), 2.0f64.to_ne_bytes());
+
+        for radius in [0.0f64, -1.0, f64::INFINITY, f64::NAN] {
+            let res = L1Ball::new(radius);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`L1Ball`: radius must be finite and larger than 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_project() {
+        let ball = L1Ball::new(1.0f64).unwrap();
+
+        // Points within the ball are not changed
+        let x = ball.project(&vec![0.2, -0.3, 0.0]).unwrap();
+        assert_eq!(x, vec![0.2, -0.3, 0.0]);
+
+        let x = ball.project(&vec![2.0, -1.0]).unwrap();
+        assert_relative_eq!(x[0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 0.0, epsilon = 1e-12);
+
+        let x = ball.project(&vec![-1.0, 1.5, 0.0, -0.25]).unwrap();
+        assert_relative_eq!(x[0], -0.25, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 0.75, epsilon = 1e-12);
+        assert_relative_eq!(x[2], 0.0, epsilon = 1e-12);
+        assert_relative_eq!(x[3], 0.0, epsilon = 1e-12);
+        assert_relative_eq!(
+            x.iter().map(|xi| xi.abs()).sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use crate::projection::Projection;
+use argmin_math::{ArgminL2Norm, ArgminScaledAdd, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # L2 ball
+///
+/// The set `{x : ||x - center||_2 <= radius}`. The center defaults to the origin.
+///
+/// Points outside of the ball are moved towards the center onto the boundary of the ball.
+///
+/// # Example
+///
+/// ```
+/// use argmin::projection::{L2Ball, Projection};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let ball = L2Ball::new(1.0f64)?.with_center(vec![1.0, 0.0]);
+/// let x = ball.project(&vec![1.0, 3.0])?;
+/// # assert!((x[0] - 1.0).abs() < 1e-12);
+/// # assert!((x[1] - 1.0).abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct L2Ball<P, F> {
+    /// Center of the ball (`None`: origin)
+    center: Option<P>,
+    /// Radius of the ball
+    radius: F,
+}
+
+impl<P, F> L2Ball<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`L2Ball`] centered at the origin
+    ///
+    /// `radius` must be finite and larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::projection::L2Ball;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ball: L2Ball<Vec<f64>, f64> = L2Ball::new(1.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(radius: F) -> Result<Self, Error> {
+        if radius <= float!(0.0) || !radius.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`L2Ball`: radius must be finite and larger than 0."
+            ));
+        }
+        Ok(L2Ball {
+            center: None,
+            radius,
+        })
+    }
+
+    /// Set the center of the ball
+    ///
+    /// Defaults to the origin.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::projection::L2Ball;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ball = L2Ball::new(1.0f64)?.with_center(vec![1.0, 2.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_center(mut self, center: P) -> Self {
+        self.center = Some(center);
+        self
+    }
+}
+
+impl<P, F> Projection<P> for L2Ball<P, F>
+where
+    P: Clone + ArgminSub<P, P> + ArgminScaledAdd<P, F, P> + ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    fn project(&self, param: &P) -> Result<P, Error> {
+        let (offset, norm) = match self.center.as_ref() {
+            Some(center) => {
+                let offset = param.sub(center);
+                let norm = offset.l2_norm();
+                (Some((center, offset)), norm)
+            }
+            None => (None, param.l2_norm()),
+        };
+        if norm <= self.radius {
+            return Ok(param.clone());
+        }
+        let factor = self.radius / norm;
+        Ok(match offset {
+            // center + radius / ||x - center|| * (x - center)
+            Some((center, offset)) => center.scaled_add(&factor, &offset),
+            // x - (1 - radius / ||x||) * x
+            None => param.scaled_add(&(factor - float!(1.0)), param),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new() {
+        let L2Ball { center, radius }: L2Ball<Vec<f64>, f64> = L2Ball::new(2.0).unwrap();
+        assert!(center.is_none());
+        assert_eq!(radius.to_ne_bytes(), 2.0f64.to_ne_bytes());
+
+        for radius in [0.0f64, -1.0, f64::INFINITY, f64::NAN] {
+            let res: Result<L2Ball<Vec<f64>, f64>, _> = L2Ball::new(radius);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`L2Ball`: radius must be finite and larger than 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_center() {
+        let ball = L2Ball::new(2.0f64).unwrap().with_center(vec![1.0, 2.0]);
+        assert_eq!(ball.center, Some(vec![1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_project() {
+        let ball = L2Ball::new(5.0f64).unwrap();
+        let x = ball.project(&vec![1.0, 2.0]).unwrap();
+        assert_eq!(x, vec![1.0, 2.0]);
+        let x = ball.project(&vec![6.0, 8.0]).unwrap();
+        assert_relative_eq!(x[0], 3.0, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 4.0, epsilon = 1e-12);
+
+        let ball = ball.with_center(vec![1.0, -1.0]);
+        let x = ball.project(&vec![2.0, 0.0]).unwrap();
+        assert_eq!(x, vec![2.0, 0.0]);
+        let x = ball.project(&vec![7.0, 7.0]).unwrap();
+        assert_relative_eq!(x[0], 4.0, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 3.0, epsilon = 1e-12);
+    }
+}
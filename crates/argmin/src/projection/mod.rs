@@ -0,0 +1,77 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Projections onto convex sets
+//!
+//! A projection maps a parameter vector `x` onto the closest point (in the Euclidean norm) of a
+//! closed convex set `C`:
+//!
+//! `P_C(x) = argmin_{y \in C} ||y - x||_2`
+//!
+//! All projections implement the [`Projection`] trait, which allows constrained solvers such as
+//! projected gradient methods to be written independently of the feasible set.
+//!
+//! The following sets are available:
+//!
+//! - [`Bounds`]: Boxes `l <= x <= u`
+//! - [`Simplex`]: The probability simplex `{x : x >= 0, sum(x) = r}`
+//! - [`L1Ball`]: `{x : ||x||_1 <= r}`
+//! - [`L2Ball`]: `{x : ||x - c||_2 <= r}`
+//! - [`HalfSpace`]: `{x : a^T x <= b}`
+//! - [`AffineSet`]: `{x : Ax = b}`
+//!
+//! All projections are generic over the math backend and operate on the parameter vector type
+//! directly.
+//!
+//! # Example
+//!
+//! ```
+//! use argmin::projection::{Projection, Simplex};
+//! # use argmin::core::Error;
+//!
+//! # fn main() -> Result<(), Error> {
+//! let simplex = Simplex::new(1.0f64)?;
+//! let x = simplex.project(&vec![0.5, 1.0, -0.5])?;
+//! # assert!((x[0] - 0.25).abs() < 1e-12);
+//! # assert!((x[1] - 0.75).abs() < 1e-12);
+//! # assert!(x[2].abs() < 1e-12);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## References
+//!
+//! Duchi, J., Shalev-Shwartz, S., Singer, Y., Chandra, T. (2008): Efficient projections onto
+//! the l1-ball for learning in high dimensions. ICML 2008, 272–279.
+//!
+//! Condat, L. (2016): Fast projection onto the simplex and the l1 ball. Math. Program. 158,
+//! 575–585.
+
+mod affine;
+mod bounds;
+mod halfspace;
+mod l1ball;
+mod l2ball;
+mod simplex;
+
+pub use self::affine::AffineSet;
+pub use self::bounds::Bounds;
+pub use self::halfspace::HalfSpace;
+pub use self::l1ball::L1Ball;
+pub use self::l2ball::L2Ball;
+pub use self::simplex::Simplex;
+
+use crate::core::Error;
+
+/// Projection onto a closed convex set
+///
+/// Implementors compute the point of the set which is closest to `param` in the Euclidean norm.
+/// Points which already lie within the set are returned unchanged.
+pub trait Projection<P> {
+    /// Project `param` onto the set
+    fn project(&self, param: &P) -> Result<P, Error>;
+}
@@ -0,0 +1,182 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use crate::projection::Projection;
+use argmin_math::{
+    ArgminAdd, ArgminL1Norm, ArgminLInfNorm, ArgminMinMax, ArgminSignum, ArgminSub, ArgminZeroLike,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Probability simplex
+///
+/// The set `{x : x >= 0, sum(x) = radius}`. For `radius = 1` this is the probability simplex.
+///
+/// The projection is `max(x - tau, 0)`, where the threshold `tau` is computed with Michelot's
+/// method as described by Condat (2016): Starting from a lower bound, `tau` is repeatedly replaced
+/// by the threshold which would be correct if the current support were the final one. This
+/// increases `tau` monotonically and terminates after at most `n` iterations; in practice only a
+/// few are needed. Each iteration is a handful of vector operations, so no sorting is required.
+///
+/// ## References
+///
+/// Michelot, C. (1986): A finite algorithm for finding the projection of a point onto the
+/// canonical simplex of R^n. J. Optim. Theory Appl. 50, 195–200.
+///
+/// Condat, L. (2016): Fast projection onto the simplex and the l1 ball. Math. Program. 158,
+/// 575–585.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Simplex<F> {
+    /// Sum of the elements
+    radius: F,
+}
+
+impl<F> Simplex<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`Simplex`]
+    ///
+    /// `radius` is the sum of the elements of all points in the set and must be larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::projection::Simplex;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let simplex = Simplex::new(1.0f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(radius: F) -> Result<Self, Error> {
+        if radius <= float!(0.0) || !radius.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Simplex`: radius must be finite and larger than 0."
+            ));
+        }
+        Ok(Simplex { radius })
+    }
+}
+
+impl<P, F> Projection<P> for Simplex<F>
+where
+    P: ArgminAdd<F, P>
+        + ArgminSub<F, P>
+        + ArgminMinMax
+        + ArgminSignum
+        + ArgminZeroLike
+        + ArgminL1Norm<F>
+        + ArgminLInfNorm<F>,
+    F: ArgminFloat,
+{
+    fn project(&self, param: &P) -> Result<P, Error> {
+        Ok(project_simplex(param, self.radius))
+    }
+}
+
+/// Projects `x` onto `{y : y >= 0, sum(y) = radius}`
+pub(super) fn project_simplex<P, F>(x: &P, radius: F) -> P
+where
+    P: ArgminAdd<F, P>
+        + ArgminSub<F, P>
+        + ArgminMinMax
+        + ArgminSignum
+        + ArgminZeroLike
+        + ArgminL1Norm<F>
+        + ArgminLInfNorm<F>,
+    F: ArgminFloat,
+{
+    let zeros = x.zero_like();
+    // With this threshold, all elements are in the support and `tau` is a lower bound of the
+    // optimal threshold.
+    let mut tau = -x.linf_norm() - radius;
+    let mut budget = None;
+    loop {
+        let shifted = x.sub(&tau);
+        let projected = P::max(&shifted, &zeros);
+        // Number of elements `>= tau`: `(sign(x - tau) + 1) / 2` is 1 for these and 0 otherwise.
+        let support = shifted.signum().add(&float!(1.0)).l1_norm() / float!(2.0);
+        // In the first iteration all elements are in the support, which bounds the iterations.
+        let iters_left = budget.get_or_insert(support);
+        let new_tau = tau + (projected.l1_norm() - radius) / support;
+        if new_tau.is_nan() || new_tau <= tau || *iters_left < float!(0.0) {
+            return projected;
+        }
+        *iters_left = *iters_left - float!(1.0);
+        tau = new_tau;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new() {
+        let Simplex { radius } = Simplex::new(2.0f64).unwrap();
+        assert_eq!(radius.to_ne_bytes(), 2.0f64.to_ne_bytes());
+
+        for radius in [0.0f64, -1.0, f64::INFINITY, f64::NAN] {
+            let res = Simplex::new(radius);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`Simplex`: radius must be finite and larger than 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_project() {
+        let simplex = Simplex::new(1.0f64).unwrap();
+        let x = simplex.project(&vec![0.5, 1.0, -0.5]).unwrap();
+        assert_relative_eq!(x[0], 0.25, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 0.75, epsilon = 1e-12);
+        assert_relative_eq!(x[2], 0.0, epsilon = 1e-12);
+
+        // Points within the set are not changed
+        let x = simplex.project(&vec![0.2, 0.3, 0.5]).unwrap();
+        assert_relative_eq!(x[0], 0.2, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 0.3, epsilon = 1e-12);
+        assert_relative_eq!(x[2], 0.5, epsilon = 1e-12);
+
+        // Sum below the radius
+        let x = simplex.project(&vec![0.0, 0.0, 0.0, 0.0]).unwrap();
+        for xi in x {
+            assert_relative_eq!(xi, 0.25, epsilon = 1e-12);
+        }
+
+        let x = Simplex::new(3.0f64)
+            .unwrap()
+            .project(&vec![10.0, -4.0, 1.0])
+            .unwrap();
+        assert_relative_eq!(x[0], 3.0, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 0.0, epsilon = 1e-12);
+        assert_relative_eq!(x[2], 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_project_optimality() {
+        // The projection onto the simplex is `max(x - tau, 0)` with `sum(max(x - tau, 0)) = r`
+        let x: Vec<f64> = (0..50)
+            .map(|i| ((i * 37 % 50) as f64 - 25.0) / 7.0)
+            .collect();
+        let p = Simplex::new(2.5f64).unwrap().project(&x).unwrap();
+        assert_relative_eq!(p.iter().sum::<f64>(), 2.5, epsilon = 1e-12);
+        let (i, _) = p.iter().enumerate().find(|(_, pi)| **pi > 0.0).unwrap();
+        let tau = x[i] - p[i];
+        for (xi, pi) in x.iter().zip(p.iter()) {
+            assert_relative_eq!(*pi, (xi - tau).max(0.0), epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use crate::prox::{l1::soft_threshold, Prox};
+use argmin_math::{
+    ArgminAdd, ArgminDot, ArgminL1Norm, ArgminMinMax, ArgminMul, ArgminSub, ArgminZeroLike,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Elastic net penalty
+///
+/// The penalty `l1 ||x||_1 + l2/2 ||x||_2^2`, which combines the sparsity of the L1 penalty with
+/// the stability of Tikhonov regularization.
+///
+/// The proximal operator is soft-thresholding followed by a scaling:
+///
+/// `prox_{t g}(x)_i = sign(x_i) max(|x_i| - t l1, 0) / (1 + t l2)`.
+///
+/// # Example
+///
+/// ```
+/// use argmin::prox::{ElasticNet, Prox};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let elastic_net = ElasticNet::new(1.0f64, 1.0)?;
+/// let x = elastic_net.prox(&vec![3.0, -0.5], 1.0)?;
+/// # assert_eq!(x, vec![1.0, 0.0]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ## References
+///
+/// Zou, H., Hastie, T. (2005): Regularization and variable selection via the elastic net.
+/// J. R. Stat. Soc. B 67, 301–320.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ElasticNet<F> {
+    /// Weight of the L1 term
+    l1: F,
+    /// Weight of the squared L2 term
+    l2: F,
+}
+
+impl<F> ElasticNet<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`ElasticNet`]
+    ///
+    /// Both weights `l1` and `l2` must be finite and non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::prox::ElasticNet;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let elastic_net = ElasticNet::new(0.1f64, 0.01)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(l1: F, l2: F) -> Result<Self, Error> {
+        if l1 < float!(0.0) || !l1.is_finite() || l2 < float!(0.0) || !l2.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ElasticNet`: weights must be finite and non-negative."
+            ));
+        }
+        Ok(ElasticNet { l1, l2 })
+    }
+}
+
+impl<P, F> Prox<P, F> for ElasticNet<F>
+where
+    P: ArgminAdd<F, P>
+        + ArgminSub<F, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminMinMax
+        + ArgminZeroLike
+        + ArgminDot<P, F>
+        + ArgminL1Norm<F>,
+    F: ArgminFloat,
+{
+    fn prox(&self, param: &P, step: F) -> Result<P, Error> {
+        Ok(soft_threshold(param, step * self.l1)
+            .mul(&(float!(1.0) / (float!(1.0) + step * self.l2))))
+    }
+
+    fn penalty(&self, param: &P) -> Result<F, Error> {
+        Ok(self.l1 * param.l1_norm() + self.l2 * param.dot(param) / float!(2.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new() {
+        let ElasticNet { l1, l2 } = ElasticNet::new(0.5f64, 0.25).unwrap();
+        assert_eq!(l1.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(l2.to_ne_bytes(), 0.25f64.to_ne_bytes());
+
+        for (l1, l2) in [
+            (-1.0f64, 0.0),
+            (0.0, -1.0),
+            (f64::NAN, 0.0),
+            (0.0, f64::INFINITY),
+        ] {
+            let res = ElasticNet::new(l1, l2);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`ElasticNet`: weights must be finite and non-negative.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_prox() {
+        let elastic_net = ElasticNet::new(0.5f64, 1.5).unwrap();
+        let x = elastic_net.prox(&vec![2.0, -3.0, 0.5], 2.0).unwrap();
+        // soft-thresholding by 1, then division by 4
+        assert_relative_eq!(x[0], 0.25, epsilon = f64::EPSILON);
+        assert_relative_eq!(x[1], -0.5, epsilon = f64::EPSILON);
+        assert_relative_eq!(x[2], 0.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_penalty() {
+        let elastic_net = ElasticNet::new(0.5f64, 2.0).unwrap();
+        // 0.5 * 3 + 2.0 * 5 / 2
+        assert_relative_eq!(
+            elastic_net.penalty(&vec![2.0, -1.0]).unwrap(),
+            6.5,
+            epsilon = f64::EPSILON
+        );
+    }
+}
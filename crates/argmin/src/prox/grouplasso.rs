@@ -0,0 +1,195 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use crate::prox::Prox;
+use argmin_math::{ArgminL1Norm, ArgminL2Norm, ArgminMul, ArgminScaledSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Group lasso penalty
+///
+/// The penalty `lambda sum_g sqrt(|g|) ||x_g||_2`, where `x_g` are the elements of `x` which
+/// belong to group `g` and `|g|` is the size of the group. It promotes solutions in which entire
+/// groups vanish.
+///
+/// Groups are given as masks of the same type as the parameter vector, with ones for the elements
+/// in the group and zeros otherwise. Groups must not overlap; elements which do not belong to any
+/// group are not penalized.
+///
+/// The proximal operator shrinks each group as a whole (block soft-thresholding):
+///
+/// `prox_{t g}(x)_g = max(1 - t lambda sqrt(|g|) / ||x_g||_2, 0) x_g`.
+///
+/// # Example
+///
+/// ```
+/// use argmin::prox::{GroupLasso, Prox};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let groups = vec![vec![1.0f64, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+/// let group_lasso = GroupLasso::new(groups, 1.0)?;
+/// let x = group_lasso.prox(&vec![3.0, 4.0, 0.5], 1.0)?;
+/// # assert!((x[0] - 3.0 * (1.0 - 2.0f64.sqrt() / 5.0)).abs() < 1e-12);
+/// # assert!((x[1] - 4.0 * (1.0 - 2.0f64.sqrt() / 5.0)).abs() < 1e-12);
+/// # assert_eq!(x[2], 0.0);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ## References
+///
+/// Yuan, M., Lin, Y. (2006): Model selection and estimation in regression with grouped
+/// variables. J. R. Stat. Soc. B 68, 49–67.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GroupLasso<P, F> {
+    /// Masks of the groups
+    groups: Vec<P>,
+    /// `lambda sqrt(|g|)` for each group
+    weights: Vec<F>,
+}
+
+impl<P, F> GroupLasso<P, F>
+where
+    P: ArgminL1Norm<F>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`GroupLasso`]
+    ///
+    /// `groups` are non-overlapping masks with ones for the elements in the group and zeros
+    /// otherwise. Groups must not be empty. `lambda` must be finite and non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::prox::GroupLasso;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let groups = vec![vec![1.0f64, 1.0, 0.0, 0.0], vec![0.0, 0.0, 1.0, 1.0]];
+    /// let group_lasso = GroupLasso::new(groups, 0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(groups: Vec<P>, lambda: F) -> Result<Self, Error> {
+        if lambda < float!(0.0) || !lambda.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GroupLasso`: lambda must be finite and non-negative."
+            ));
+        }
+        let weights = groups
+            .iter()
+            .map(|group| {
+                let size = group.l1_norm();
+                if size > float!(0.0) {
+                    Ok(lambda * size.sqrt())
+                } else {
+                    Err(argmin_error!(
+                        InvalidParameter,
+                        "`GroupLasso`: groups must not be empty."
+                    ))
+                }
+            })
+            .collect::<Result<Vec<F>, Error>>()?;
+        Ok(GroupLasso { groups, weights })
+    }
+}
+
+impl<P, F> Prox<P, F> for GroupLasso<P, F>
+where
+    P: Clone + ArgminMul<P, P> + ArgminScaledSub<P, F, P> + ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    fn prox(&self, param: &P, step: F) -> Result<P, Error> {
+        let mut out = param.clone();
+        for (group, weight) in self.groups.iter().zip(self.weights.iter()) {
+            let x_g = param.mul(group);
+            let norm = x_g.l2_norm();
+            let threshold = step * *weight;
+            // Fraction of `x_g` which is removed
+            let shrink = if norm > threshold {
+                threshold / norm
+            } else {
+                float!(1.0)
+            };
+            out = out.scaled_sub(&shrink, &x_g);
+        }
+        Ok(out)
+    }
+
+    fn penalty(&self, param: &P) -> Result<F, Error> {
+        Ok(self
+            .groups
+            .iter()
+            .zip(self.weights.iter())
+            .fold(float!(0.0), |acc, (group, weight)| {
+                acc + *weight * param.mul(group).l2_norm()
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new() {
+        let groups = vec![
+            vec![1.0f64, 1.0, 1.0, 1.0, 0.0],
+            vec![0.0, 0.0, 0.0, 0.0, 1.0],
+        ];
+        let GroupLasso { groups: g, weights } = GroupLasso::new(groups.clone(), 0.5).unwrap();
+        assert_eq!(g, groups);
+        assert_relative_eq!(weights[0], 1.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(weights[1], 0.5, epsilon = f64::EPSILON);
+
+        for lambda in [-1.0f64, f64::INFINITY, f64::NAN] {
+            let res = GroupLasso::new(groups.clone(), lambda);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`GroupLasso`: lambda must be finite and non-negative.\""
+            );
+        }
+
+        let res = GroupLasso::new(vec![vec![1.0f64, 0.0], vec![0.0, 0.0]], 1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`GroupLasso`: groups must not be empty.\""
+        );
+    }
+
+    #[test]
+    fn test_prox() {
+        let groups = vec![vec![1.0f64, 1.0, 0.0, 0.0], vec![0.0, 0.0, 1.0, 0.0]];
+        let group_lasso = GroupLasso::new(groups, 0.5f64.sqrt()).unwrap();
+        // Thresholds: 2 * sqrt(0.5) * sqrt(2) = 2 and 2 * sqrt(0.5)
+        let x = group_lasso.prox(&vec![3.0, 4.0, 1.0, -7.0], 2.0).unwrap();
+        assert_relative_eq!(x[0], 3.0 * 0.6, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 4.0 * 0.6, epsilon = 1e-12);
+        assert_relative_eq!(x[2], 0.0, epsilon = 1e-12);
+        // Not in any group
+        assert_relative_eq!(x[3], -7.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_penalty() {
+        let groups = vec![vec![1.0f64, 1.0, 0.0, 0.0], vec![0.0, 0.0, 1.0, 0.0]];
+        let group_lasso = GroupLasso::new(groups, 2.0).unwrap();
+        // 2 * sqrt(2) * 5 + 2 * 1
+        assert_relative_eq!(
+            group_lasso.penalty(&vec![3.0, 4.0, -1.0, 7.0]).unwrap(),
+            10.0 * 2.0f64.sqrt() + 2.0,
+            epsilon = 1e-12
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use crate::projection::Projection;
+use crate::prox::Prox;
+use argmin_math::{ArgminL2Norm, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Indicator function of a convex set
+///
+/// The penalty which is `0` within a closed convex set and infinite outside of it. This turns
+/// proximal gradient methods into projected gradient methods.
+///
+/// The set is given by any of the projections of the [`projection`](`crate::projection`) module,
+/// for instance [`Bounds`](`crate::projection::Bounds`) for box constraints. The proximal operator
+/// is the projection onto the set and does not depend on the step length.
+///
+/// Since projections may be subject to rounding errors, a point is considered to be within the
+/// set if its distance to its projection is at most `sqrt(EPSILON) * max(||x||_2, 1)`.
+///
+/// # Example
+///
+/// ```
+/// use argmin::projection::Bounds;
+/// use argmin::prox::{Indicator, Prox};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let indicator = Indicator::new(Bounds::new(vec![0.0f64, 0.0], vec![1.0, 1.0]));
+/// let x = indicator.prox(&vec![2.0, 0.5], 1.0)?;
+/// # assert_eq!(x, vec![1.0, 0.5]);
+/// # assert_eq!(indicator.penalty(&x)?, 0.0);
+/// # assert_eq!(indicator.penalty(&vec![2.0, 0.5])?, f64::INFINITY);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Indicator<S> {
+    /// The convex set
+    set: S,
+}
+
+impl<S> Indicator<S> {
+    /// Construct a new instance of [`Indicator`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::projection::Bounds;
+    /// # use argmin::prox::Indicator;
+    /// let indicator = Indicator::new(Bounds::new(vec![0.0f64, 0.0], vec![1.0, 1.0]));
+    /// ```
+    pub fn new(set: S) -> Self {
+        Indicator { set }
+    }
+}
+
+impl<P, F, S> Prox<P, F> for Indicator<S>
+where
+    S: Projection<P>,
+    P: ArgminSub<P, P> + ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    fn prox(&self, param: &P, _step: F) -> Result<P, Error> {
+        self.set.project(param)
+    }
+
+    fn penalty(&self, param: &P) -> Result<F, Error> {
+        let distance = param.sub(&self.set.project(param)?).l2_norm();
+        let tol = F::epsilon().sqrt() * param.l2_norm().max(float!(1.0));
+        Ok(if distance <= tol {
+            float!(0.0)
+        } else {
+            F::infinity()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projection::{Bounds, Simplex};
+
+    #[test]
+    fn test_prox() {
+        let indicator = Indicator::new(Bounds::new(vec![0.0f64, -1.0], vec![1.0, 1.0]));
+        let x = indicator.prox(&vec![2.0, -3.0], 1.0).unwrap();
+        assert_eq!(x, vec![1.0, -1.0]);
+        let x = indicator.prox(&vec![0.5, 0.5], 10.0).unwrap();
+        assert_eq!(x, vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_penalty() {
+        let indicator = Indicator::new(Bounds::new(vec![0.0f64, -1.0], vec![1.0, 1.0]));
+        assert_eq!(
+            indicator.penalty(&vec![0.5, 0.5]).unwrap().to_ne_bytes(),
+            0.0f64.to_ne_bytes()
+        );
+        assert_eq!(
+            indicator.penalty(&vec![0.5, 1.5]).unwrap().to_ne_bytes(),
+            f64::INFINITY.to_ne_bytes()
+        );
+
+        // Rounding errors of the projection
+        let indicator = Indicator::new(Simplex::new(1.0f64).unwrap());
+        let x = vec![0.1, 0.2, 0.3, 0.4];
+        assert_eq!(
+            indicator.penalty(&x).unwrap().to_ne_bytes(),
+            0.0f64.to_ne_bytes()
+        );
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use crate::prox::Prox;
+use argmin_math::{ArgminAdd, ArgminL1Norm, ArgminMinMax, ArgminSub, ArgminZeroLike};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # L1 penalty
+///
+/// The penalty `lambda ||x||_1`, which promotes sparse solutions (Lasso).
+///
+/// The proximal operator is the element-wise soft-thresholding
+///
+/// `prox_{t g}(x)_i = sign(x_i) max(|x_i| - t lambda, 0)`.
+///
+/// # Example
+///
+/// ```
+/// use argmin::prox::{Prox, L1};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let l1 = L1::new(1.0f64)?;
+/// let x = l1.prox(&vec![3.0, -0.5], 1.0)?;
+/// # assert_eq!(x, vec![2.0, 0.0]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct L1<F> {
+    /// Weight of the penalty
+    lambda: F,
+}
+
+impl<F> L1<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`L1`]
+    ///
+    /// `lambda` must be finite and non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::prox::L1;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let l1 = L1::new(0.1f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(lambda: F) -> Result<Self, Error> {
+        if lambda < float!(0.0) || !lambda.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`L1`: lambda must be finite and non-negative."
+            ));
+        }
+        Ok(L1 { lambda })
+    }
+}
+
+impl<P, F> Prox<P, F> for L1<F>
+where
+    P: ArgminAdd<F, P>
+        + ArgminSub<F, P>
+        + ArgminSub<P, P>
+        + ArgminMinMax
+        + ArgminZeroLike
+        + ArgminL1Norm<F>,
+    F: ArgminFloat,
+{
+    fn prox(&self, param: &P, step: F) -> Result<P, Error> {
+        Ok(soft_threshold(param, step * self.lambda))
+    }
+
+    fn penalty(&self, param: &P) -> Result<F, Error> {
+        Ok(self.lambda * param.l1_norm())
+    }
+}
+
+/// Shrinks each element of `x` towards zero by `threshold`: `x - min(max(x, -threshold), threshold)`
+pub(super) fn soft_threshold<P, F>(x: &P, threshold: F) -> P
+where
+    P: ArgminAdd<F, P> + ArgminSub<F, P> + ArgminSub<P, P> + ArgminMinMax + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    let zeros = x.zero_like();
+    let clamped = P::min(&P::max(x, &zeros.sub(&threshold)), &zeros.add(&threshold));
+    x.sub(&clamped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new() {
+        let L1 { lambda } = L1::new(0.5f64).unwrap();
+        assert_eq!(lambda.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert!(L1::new(0.0f64).is_ok());
+
+        for lambda in [-1.0f64, f64::INFINITY, f64::NAN] {
+            let res = L1::new(lambda);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`L1`: lambda must be finite and non-negative.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_prox() {
+        let l1 = L1::new(0.5f64).unwrap();
+        let x = l1.prox(&vec![2.0, -2.0, 0.5, -0.5, 0.0], 2.0).unwrap();
+        let expected = [1.0, -1.0, 0.0, 0.0, 0.0];
+        for (xi, ei) in x.iter().zip(expected.iter()) {
+            assert_relative_eq!(xi, ei, epsilon = f64::EPSILON);
+        }
+
+        // lambda = 0: identity
+        let x = L1::new(0.0f64)
+            .unwrap()
+            .prox(&vec![2.0, -0.1], 1.0)
+            .unwrap();
+        assert_eq!(x, vec![2.0, -0.1]);
+    }
+
+    #[test]
+    fn test_penalty() {
+        let l1 = L1::new(0.5f64).unwrap();
+        assert_relative_eq!(
+            l1.penalty(&vec![2.0, -1.0, 0.0]).unwrap(),
+            1.5,
+            epsilon = f64::EPSILON
+        );
+    }
+}
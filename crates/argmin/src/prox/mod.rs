@@ -0,0 +1,70 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Proximal operators
+//!
+//! The proximal operator of a (possibly nonsmooth) convex penalty `g` with step length `t > 0` is
+//!
+//! `prox_{t g}(x) = argmin_y g(y) + 1/(2t) ||y - x||_2^2`.
+//!
+//! Proximal gradient methods minimize `f(x) + g(x)` with a smooth `f` by alternating gradient
+//! steps on `f` with evaluations of the proximal operator of `g`. All penalties implement the
+//! [`Prox`] trait, which also reports the value of the penalty such that the full objective
+//! `f(x) + g(x)` can be tracked.
+//!
+//! The following penalties are available:
+//!
+//! - [`L1`]: `lambda ||x||_1` (soft-thresholding)
+//! - [`ElasticNet`]: `l1 ||x||_1 + l2/2 ||x||_2^2`
+//! - [`GroupLasso`]: `lambda sum_g sqrt(|g|) ||x_g||_2` for disjoint groups `g`
+//! - [`Indicator`]: Indicator function of a convex set, for instance a box (see
+//!   [`projection`](`crate::projection`))
+//! - [`NuclearNorm`]: `lambda sum_i sigma_i(X)` for matrix parameters
+//!
+//! # Example
+//!
+//! ```
+//! use argmin::prox::{Prox, L1};
+//! # use argmin::core::Error;
+//!
+//! # fn main() -> Result<(), Error> {
+//! let l1 = L1::new(0.5f64)?;
+//! let x = l1.prox(&vec![2.0, -0.5, 0.1], 2.0)?;
+//! # assert_eq!(x, vec![1.0, 0.0, 0.0]);
+//! let penalty = l1.penalty(&x)?;
+//! # assert_eq!(penalty, 0.5);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## References
+//!
+//! Parikh, N., Boyd, S. (2014): Proximal algorithms. Foundations and Trends in Optimization 1,
+//! 127–239.
+
+mod elasticnet;
+mod grouplasso;
+mod indicator;
+mod l1;
+mod nuclearnorm;
+
+pub use self::elasticnet::ElasticNet;
+pub use self::grouplasso::GroupLasso;
+pub use self::indicator::Indicator;
+pub use self::l1::L1;
+pub use self::nuclearnorm::NuclearNorm;
+
+use crate::core::Error;
+
+/// Proximal operator of a convex penalty
+pub trait Prox<P, F> {
+    /// Evaluate the proximal operator of `step` times the penalty at `param`
+    fn prox(&self, param: &P, step: F) -> Result<P, Error>;
+
+    /// Evaluate the penalty at `param`
+    fn penalty(&self, param: &P) -> Result<F, Error>;
+}
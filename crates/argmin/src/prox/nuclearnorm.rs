@@ -0,0 +1,150 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use crate::prox::Prox;
+use argmin_math::ArgminSvd;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Nuclear norm penalty
+///
+/// The penalty `lambda ||X||_* = lambda sum_i sigma_i(X)` for matrix parameters `X`, where
+/// `sigma_i(X)` are the singular values of `X`. It promotes low-rank solutions, for instance in
+/// matrix completion.
+///
+/// The proximal operator soft-thresholds the singular values (singular value thresholding):
+///
+/// `prox_{t g}(X) = U max(S - t lambda, 0) V^T`, where `X = U S V^T`.
+///
+/// Requires the matrix type to implement [`ArgminSvd`].
+///
+/// # Example
+///
+/// ```
+/// use argmin::prox::{NuclearNorm, Prox};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let nuclear_norm = NuclearNorm::new(1.0f64)?;
+/// let x = nuclear_norm.prox(&vec![vec![3.0, 0.0], vec![0.0, 0.5]], 1.0)?;
+/// # assert!((x[0][0] - 2.0).abs() < 1e-12);
+/// # assert!(x[1][1].abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ## References
+///
+/// Cai, J.-F., Candès, E. J., Shen, Z. (2010): A singular value thresholding algorithm for matrix
+/// completion. SIAM J. Optim. 20, 1956–1982.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NuclearNorm<F> {
+    /// Weight of the penalty
+    lambda: F,
+}
+
+impl<F> NuclearNorm<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`NuclearNorm`]
+    ///
+    /// `lambda` must be finite and non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::prox::NuclearNorm;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nuclear_norm = NuclearNorm::new(0.1f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(lambda: F) -> Result<Self, Error> {
+        if lambda < float!(0.0) || !lambda.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NuclearNorm`: lambda must be finite and non-negative."
+            ));
+        }
+        Ok(NuclearNorm { lambda })
+    }
+}
+
+impl<P, F> Prox<P, F> for NuclearNorm<F>
+where
+    P: ArgminSvd<F>,
+    F: ArgminFloat,
+{
+    fn prox(&self, param: &P, step: F) -> Result<P, Error> {
+        let threshold = step * self.lambda;
+        param.map_singular_values(|sigma| (sigma - threshold).max(float!(0.0)))
+    }
+
+    fn penalty(&self, param: &P) -> Result<F, Error> {
+        Ok(self.lambda
+            * param
+                .singular_values()?
+                .into_iter()
+                .fold(float!(0.0), |acc, sigma| acc + sigma))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new() {
+        let NuclearNorm { lambda } = NuclearNorm::new(0.5f64).unwrap();
+        assert_eq!(lambda.to_ne_bytes(), 0.5f64.to_ne_bytes());
+
+        for lambda in [-1.0f64, f64::INFINITY, f64::NAN] {
+            let res = NuclearNorm::new(lambda);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`NuclearNorm`: lambda must be finite and non-negative.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_prox() {
+        // Rank one matrix u v^T with u = (1, 2, 2)/3 * 6, v = (0.6, 0.8): singular value 6
+        let x = vec![vec![1.2, 1.6], vec![2.4, 3.2], vec![2.4, 3.2]];
+        let nuclear_norm = NuclearNorm::new(1.0f64).unwrap();
+        let p = nuclear_norm.prox(&x, 2.0).unwrap();
+        // Singular value shrinks from 6 to 4
+        for (pi, xi) in p.iter().zip(x.iter()) {
+            for (pij, xij) in pi.iter().zip(xi.iter()) {
+                assert_relative_eq!(*pij, xij * 4.0 / 6.0, epsilon = 1e-12);
+            }
+        }
+        // Large thresholds remove everything
+        let p = nuclear_norm.prox(&x, 7.0).unwrap();
+        for pij in p.iter().flatten() {
+            assert_relative_eq!(*pij, 0.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_penalty() {
+        let nuclear_norm = NuclearNorm::new(0.5f64).unwrap();
+        let x = vec![vec![3.0, 0.0], vec![4.0, 5.0]];
+        assert_relative_eq!(
+            nuclear_norm.penalty(&x).unwrap(),
+            0.5 * (45.0f64.sqrt() + 5.0f64.sqrt()),
+            epsilon = 1e-12
+        );
+    }
+}
@@ -6,7 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, TerminationReason, KV,
+    ArgminFloat, CostFunction, DerivativeFreeState, Error, Problem, Solver, State,
+    TerminationReason, KV,
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -97,7 +98,7 @@ impl<F: ArgminFloat> BrentOpt<F> {
     }
 }
 
-impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for BrentOpt<F>
+impl<O, F> Solver<O, DerivativeFreeState<F, F>> for BrentOpt<F>
 where
     O: CostFunction<Param = F, Output = F>,
     F: ArgminFloat,
@@ -110,8 +111,8 @@ where
         &mut self,
         problem: &mut Problem<O>,
         // BrentOpt maintains its own state
-        state: IterState<F, (), (), (), (), F>,
-    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        state: DerivativeFreeState<F, F>,
+    ) -> Result<(DerivativeFreeState<F, F>, Option<KV>), Error> {
         let u = self.a + self.c * (self.b - self.a);
         self.v = u;
         self.w = u;
@@ -127,8 +128,8 @@ where
         &mut self,
         problem: &mut Problem<O>,
         // BrentOpt maintains its own state
-        state: IterState<F, (), (), (), (), F>,
-    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        state: DerivativeFreeState<F, F>,
+    ) -> Result<(DerivativeFreeState<F, F>, Option<KV>), Error> {
         let two = float!(2f64);
         let tol = self.eps * self.x.abs() + self.t;
         let m = (self.a + self.b) / two;
@@ -6,7 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, TerminationReason, KV,
+    ArgminFloat, CostFunction, DerivativeFreeState, Error, Problem, Solver, State,
+    TerminationReason, KV,
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -78,7 +79,7 @@ impl<F: ArgminFloat> BrentRoot<F> {
     }
 }
 
-impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for BrentRoot<F>
+impl<O, F> Solver<O, DerivativeFreeState<F, F>> for BrentRoot<F>
 where
     O: CostFunction<Param = F, Output = F>,
     F: ArgminFloat,
@@ -91,8 +92,8 @@ where
         &mut self,
         problem: &mut Problem<O>,
         // BrentRoot maintains its own state
-        state: IterState<F, (), (), (), (), F>,
-    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        state: DerivativeFreeState<F, F>,
+    ) -> Result<(DerivativeFreeState<F, F>, Option<KV>), Error> {
         self.fa = problem.cost(&self.a)?;
         self.fb = problem.cost(&self.b)?;
         if self.fa * self.fb > float!(0.0) {
@@ -109,8 +110,8 @@ where
         &mut self,
         problem: &mut Problem<O>,
         // BrentRoot maintains its own state
-        state: IterState<F, (), (), (), (), F>,
-    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        state: DerivativeFreeState<F, F>,
+    ) -> Result<(DerivativeFreeState<F, F>, Option<KV>), Error> {
         if (self.fb > float!(0.0) && self.fc > float!(0.0))
             || self.fb < float!(0.0) && self.fc < float!(0.0)
         {
@@ -213,8 +214,8 @@ mod tests {
         let mut solver: BrentRoot<f64> = BrentRoot::new(min, max, tol);
         let mut problem: Problem<Quadratic> = Problem::new(Quadratic {});
 
-        let result: Result<(IterState<f64, (), (), (), (), f64>, Option<KV>), Error> =
-            solver.init(&mut problem, IterState::new());
+        let result: Result<(DerivativeFreeState<f64, f64>, Option<KV>), Error> =
+            solver.init(&mut problem, DerivativeFreeState::new());
 
         // Check if the initialization fails and we get the correct error message
         assert!(result.is_err());
@@ -233,8 +234,8 @@ mod tests {
         let mut solver: BrentRoot<f64> = BrentRoot::new(min, max, tol);
         let mut problem: Problem<Quadratic> = Problem::new(Quadratic {});
 
-        let result: Result<(IterState<f64, (), (), (), (), f64>, Option<KV>), Error> =
-            solver.init(&mut problem, IterState::new());
+        let result: Result<(DerivativeFreeState<f64, f64>, Option<KV>), Error> =
+            solver.init(&mut problem, DerivativeFreeState::new());
 
         // Check if the initialization fails and we get the correct error message
         assert!(result.is_err());
@@ -253,8 +254,8 @@ mod tests {
         let mut solver: BrentRoot<f64> = BrentRoot::new(min, max, tol);
         let mut problem: Problem<Quadratic> = Problem::new(Quadratic {});
 
-        let result: Result<(IterState<f64, (), (), (), (), f64>, Option<KV>), Error> =
-            solver.init(&mut problem, IterState::new());
+        let result: Result<(DerivativeFreeState<f64, f64>, Option<KV>), Error> =
+            solver.init(&mut problem, DerivativeFreeState::new());
 
         // Check if the initialization is successful
         assert!(result.is_ok());
@@ -6,7 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{ArgminFloat, Error, IterState, Operator, Problem, Solver, State, KV};
-use argmin_math::{ArgminConj, ArgminDot, ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminSub};
+use argmin_math::{ArgminDot, ArgminL2Norm, ArgminMul, ArgminScaledAddAssign, ArgminSub};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -15,10 +15,16 @@ use serde::{Deserialize, Serialize};
 /// A solver for systems of linear equations with a symmetric and positive-definite matrix.
 ///
 /// Solves systems of the form `A * x = b` where `x` and `b` are vectors and `A` is a symmetric and
-/// positive-definite matrix.
+/// positive-definite matrix. For complex vectors, `A` must be Hermitian and positive-definite; in
+/// this case, the real-valued inner product `Re(x^H y)` is used as the dot product.
 ///
 /// Requires an initial parameter vector.
 ///
+/// The step length along each search direction is computed exactly from the residual and the
+/// product of `A` with the search direction, hence no line search is needed. For quadratic cost
+/// functions minimized with other solvers, [`ExactLineSearch`](`crate::solver::linesearch::ExactLineSearch`)
+/// computes the same step length.
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`Operator`].
@@ -87,8 +93,8 @@ where
 impl<P, O, R, F> Solver<O, IterState<P, (), (), (), R, F>> for ConjugateGradient<P, F>
 where
     O: Operator<Param = P, Output = P>,
-    P: Clone + ArgminDot<P, F> + ArgminSub<P, R> + ArgminScaledAdd<P, F, P> + ArgminConj,
-    R: ArgminMul<F, R> + ArgminMul<F, P> + ArgminConj + ArgminDot<R, F> + ArgminScaledAdd<P, F, R>,
+    P: Clone + ArgminDot<P, F> + ArgminSub<P, R> + ArgminScaledAddAssign<P, F>,
+    R: ArgminMul<F, R> + ArgminMul<F, P> + ArgminDot<R, F> + ArgminScaledAddAssign<P, F>,
     F: ArgminFloat + ArgminL2Norm<F>,
 {
     fn name(&self) -> &str {
@@ -110,7 +116,7 @@ where
         let ap = problem.apply(init_param)?;
         let r0: R = self.b.sub(&ap).mul(&(float!(-1.0)));
         self.p = Some(r0.mul(&(float!(-1.0))));
-        self.rtr = r0.dot(&r0.conj());
+        self.rtr = r0.dot(&r0);
         Ok((state.residuals(r0), None))
     }
 
@@ -124,24 +130,26 @@ where
             PotentialBug,
             "`ConjugateGradient`: Field `p` not set"
         ))?;
-        let r = state.take_residuals().ok_or_else(argmin_error_closure!(
+        let mut r = state.take_residuals().ok_or_else(argmin_error_closure!(
             PotentialBug,
             "`ConjugateGradient`: Residuals in `state` not set"
         ))?;
 
         let apk = problem.apply(&p)?;
-        let alpha = self.rtr.div(p.dot(&apk.conj()));
+        let alpha = self.rtr.div(p.dot(&apk));
         let state_param = state.get_param().ok_or_else(argmin_error_closure!(
             PotentialBug,
             "`ConjugateGradient`: Parameter vector in `state` not set"
         ))?;
-        let new_param = state_param.scaled_add(&alpha, &p);
-        let r = r.scaled_add(&alpha, &apk);
-        let rtr_n = r.dot(&r.conj());
+        let mut new_param = state_param.clone();
+        new_param.scaled_add_assign(&alpha, &p);
+        r.scaled_add_assign(&alpha, &apk);
+        let rtr_n = r.dot(&r);
         let beta = rtr_n.div(self.rtr);
         self.rtr = rtr_n;
-        let p_n = <R as ArgminMul<F, P>>::mul(&r, &(float!(-1.0))).scaled_add(&beta, &p);
-        let norm = r.dot(&r.conj()).l2_norm();
+        let mut p_n = <R as ArgminMul<F, P>>::mul(&r, &(float!(-1.0)));
+        p_n.scaled_add_assign(&beta, &p);
+        let norm = r.dot(&r).l2_norm();
 
         self.p = Some(p_n);
         self.p_prev = Some(p);
@@ -156,7 +164,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{test_utils::TestProblem, ArgminError};
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
     use approx::assert_relative_eq;
 
     test_trait_impl!(conjugate_gradient, ConjugateGradient<Vec<f64>, f64>);
@@ -322,4 +330,36 @@ mod tests {
         assert_relative_eq!(norm, state.get_cost());
         assert_relative_eq!(new_param, state.get_param().unwrap()[0]);
     }
+
+    #[test]
+    fn test_complex() {
+        use num_complex::Complex;
+
+        // Hermitian and positive-definite matrix [[2, i], [-i, 2]]
+        struct Hermitian {}
+
+        impl Operator for Hermitian {
+            type Param = Vec<Complex<f64>>;
+            type Output = Vec<Complex<f64>>;
+
+            fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+                let i = Complex::new(0.0, 1.0);
+                Ok(vec![2.0 * x[0] + i * x[1], -i * x[0] + 2.0 * x[1]])
+            }
+        }
+
+        let b = vec![Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)];
+        let cg: ConjugateGradient<_, f64> = ConjugateGradient::new(b);
+        let res = Executor::new(Hermitian {}, cg)
+            .configure(|state| state.param(vec![Complex::new(0.0, 0.0); 2]).max_iters(2))
+            .run()
+            .unwrap();
+
+        let x = res.state.get_param().unwrap();
+        // x = [2 / 3, i / 3]
+        assert_relative_eq!(x[0].re, 2.0 / 3.0, epsilon = 1e-12);
+        assert_relative_eq!(x[0].im, 0.0, epsilon = 1e-12);
+        assert_relative_eq!(x[1].re, 0.0, epsilon = 1e-12);
+        assert_relative_eq!(x[1].im, 1.0 / 3.0, epsilon = 1e-12);
+    }
 }
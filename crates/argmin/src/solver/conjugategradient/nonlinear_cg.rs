@@ -9,7 +9,7 @@ use crate::core::{
     ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch, NLCGBetaUpdate,
     OptimizationResult, Problem, Solver, State, KV,
 };
-use argmin_math::{ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul};
+use argmin_math::{ArgminDot, ArgminL2Norm, ArgminMul, ArgminMulAssign, ArgminSubAssign};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -122,8 +122,8 @@ impl<O, P, G, L, B, F> Solver<O, IterState<P, G, (), (), (), F>>
     for NonlinearConjugateGradient<P, L, B, F>
 where
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
-    P: Clone + ArgminAdd<P, P> + ArgminMul<F, P>,
-    G: Clone + ArgminMul<F, P> + ArgminDot<G, F> + ArgminL2Norm<F>,
+    P: Clone + ArgminMulAssign<F> + ArgminSubAssign<G> + ArgminL2Norm<F> + 'static,
+    G: Clone + ArgminMul<F, P> + ArgminDot<G, F> + ArgminDot<P, F> + ArgminL2Norm<F>,
     L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
     B: NLCGBetaUpdate<G, P, F>,
     F: ArgminFloat,
@@ -146,16 +146,33 @@ where
         ))?;
         let cost = problem.cost(param)?;
         let grad = problem.gradient(param)?;
-        self.p = Some(grad.mul(&(float!(-1.0))));
+        // A search direction taken over via `warm_start` is only kept if it is a descent
+        // direction.
+        self.p = match self.p.take() {
+            Some(p) if grad.dot(&p) < float!(0.0) => Some(p),
+            _ => Some(grad.mul(&(float!(-1.0)))),
+        };
         Ok((state.cost(cost).gradient(grad), None))
     }
 
+    /// Takes over the search direction of `previous` in addition to the information held by the
+    /// state.
+    fn warm_start(
+        &mut self,
+        previous: Self,
+        previous_state: IterState<P, G, (), (), (), F>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<IterState<P, G, (), (), (), F>, Error> {
+        self.p = previous.p;
+        Ok(state.warm_start(previous_state))
+    }
+
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
         mut state: IterState<P, G, (), (), (), F>,
     ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
-        let p = self.p.as_ref().ok_or_else(argmin_error_closure!(
+        let p = self.p.as_mut().ok_or_else(argmin_error_closure!(
             PotentialBug,
             "`NonlinearConjugateGradient`: Field `p` not set"
         ))?;
@@ -171,22 +188,31 @@ where
 
         // Linesearch
         self.linesearch.search_direction(p.clone());
+        if let Some(max_step_length) = problem.max_step_length(&xk) {
+            self.linesearch.max_step_length(max_step_length)?;
+        }
+        if let Some(move_limits) = problem.move_limits::<P>() {
+            self.linesearch.move_limits(move_limits);
+        }
 
         // Run solver
         let OptimizationResult {
             problem: line_problem,
             state: mut line_state,
             ..
-        } = Executor::new(
-            problem.take_problem().ok_or_else(argmin_error_closure!(
-                PotentialBug,
-                "`NonlinearConjugateGradient`: Failed to take `problem` for line search"
-            ))?,
-            self.linesearch.clone(),
-        )
-        .configure(|state| state.param(xk).gradient(grad.clone()).cost(cur_cost))
-        .ctrlc(false)
-        .run()?;
+        } = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(
+                problem.take_problem().ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`NonlinearConjugateGradient`: Failed to take `problem` for line search"
+                ))?,
+                self.linesearch.clone(),
+            )
+            .configure(|state| state.param(xk).gradient(grad.clone()).cost(cur_cost))
+            .ctrlc(false)
+            .run()?
+        };
 
         // takes care of the counts of function evaluations
         problem.consume_problem(line_problem);
@@ -196,8 +222,11 @@ where
             "`NonlinearConjugateGradient`: No `param` returned by line search"
         ))?;
 
-        // Update of beta
-        let new_grad = problem.gradient(&xk1)?;
+        // Update of beta; reuses the gradient at `xk1` if the line search computed it
+        let new_grad = line_state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&xk1))?;
 
         let restart_orthogonality = match self.restart_orthogonality {
             Some(v) => new_grad.dot(&grad).abs() / new_grad.l2_norm().powi(2) >= v,
@@ -214,7 +243,8 @@ where
         }
 
         // Update of p
-        self.p = Some(new_grad.mul(&(float!(-1.0))).add(&p.mul(&self.beta)));
+        p.mul_assign(&self.beta);
+        p.sub_assign(&new_grad);
 
         // Housekeeping
         let cost = problem.cost(&xk1)?;
@@ -363,6 +393,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_warm_start() {
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let mut previous: NonlinearConjugateGradient<Vec<f64>, _, _, f64> =
+            NonlinearConjugateGradient::new(linesearch.clone(), PolakRibiere::new());
+        previous.p = Some(vec![-1.0, 0.0]);
+        let mut nlcg: NonlinearConjugateGradient<Vec<f64>, _, _, f64> =
+            NonlinearConjugateGradient::new(linesearch, PolakRibiere::new());
+        let state =
+            <NonlinearConjugateGradient<_, _, _, f64> as Solver<TestProblem, _>>::warm_start(
+                &mut nlcg,
+                previous.clone(),
+                IterState::new().param(vec![3.0, 4.0]),
+                IterState::new(),
+            )
+            .unwrap();
+        assert_eq!(state.get_param(), Some(&vec![3.0, 4.0]));
+
+        // The search direction is kept since it is a descent direction
+        nlcg.init(&mut Problem::new(TestProblem::new()), state.clone())
+            .unwrap();
+        assert_eq!(nlcg.p, Some(vec![-1.0, 0.0]));
+
+        // Otherwise, it is reset to the steepest descent direction
+        nlcg.p = Some(vec![1.0, 0.0]);
+        nlcg.init(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+        assert_eq!(nlcg.p, Some(vec![-3.0, -4.0]));
+    }
+
     #[test]
     fn test_next_iter_p_not_set() {
         let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
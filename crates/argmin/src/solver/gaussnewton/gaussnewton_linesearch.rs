@@ -6,10 +6,11 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, Jacobian, LineSearch,
-    Operator, OptimizationResult, Problem, Solver, TerminationReason, TerminationStatus, KV,
+    ArgminError, ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, Jacobian,
+    LineSearch, Operator, OptimizationResult, Problem, Solver, State, TerminationReason,
+    TerminationStatus, ToleranceConfig, KV,
 };
-use argmin_math::{ArgminDot, ArgminInv, ArgminL2Norm, ArgminMul, ArgminTranspose};
+use argmin_math::{ArgminDot, ArgminL2Norm, ArgminMul, ArgminSolve, ArgminSub, ArgminTranspose};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -19,6 +20,10 @@ use serde::{Deserialize, Serialize};
 ///
 /// Requires an initial parameter vector.
 ///
+/// The convergence tolerances (the gradient tolerance is not used), including a tolerance on the change of the parameter vector
+/// (disabled by default) and relative instead of absolute tolerances, can be set via
+/// [`with_tolerances`](`GaussNewtonLS::with_tolerances`) (see [`ToleranceConfig`]).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`Operator`] and [`Jacobian`].
@@ -32,8 +37,8 @@ use serde::{Deserialize, Serialize};
 pub struct GaussNewtonLS<L, F> {
     /// linesearch
     linesearch: L,
-    /// Tolerance for the stopping criterion based on cost difference
-    tol: F,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
 }
 
 impl<L, F: ArgminFloat> GaussNewtonLS<L, F> {
@@ -49,7 +54,7 @@ impl<L, F: ArgminFloat> GaussNewtonLS<L, F> {
     pub fn new(linesearch: L) -> Self {
         GaussNewtonLS {
             linesearch,
-            tol: F::epsilon().sqrt(),
+            tol: ToleranceConfig::with_defaults(float!(0.0), F::epsilon().sqrt()),
         }
     }
 
@@ -75,23 +80,39 @@ impl<L, F: ArgminFloat> GaussNewtonLS<L, F> {
                 "Gauss-Newton-Linesearch: tol must be positive."
             ));
         }
-        self.tol = tol;
+        self.tol = self.tol.with_cost(tol)?;
         Ok(self)
     }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::GaussNewtonLS;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let gauss_newton_ls = GaussNewtonLS::new(linesearch).with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
 }
 
 impl<O, L, F, P, G, J, U, R> Solver<O, IterState<P, G, J, (), R, F>> for GaussNewtonLS<L, F>
 where
     O: Operator<Param = P, Output = U> + Jacobian<Param = P, Jacobian = J>,
-    P: Clone + ArgminMul<F, P>,
+    P: Clone + ArgminSub<P, P> + ArgminMul<F, P> + ArgminL2Norm<F> + 'static,
     G: Clone,
     U: ArgminL2Norm<F>,
-    J: Clone
-        + ArgminTranspose<J>
-        + ArgminInv<J>
-        + ArgminDot<J, J>
-        + ArgminDot<G, P>
-        + ArgminDot<U, G>,
+    J: Clone + ArgminTranspose<J> + ArgminDot<J, J> + ArgminDot<U, G> + ArgminSolve<G, P>,
     L: Clone + LineSearch<P, F> + Solver<LineSearchProblem<O, F>, IterState<P, G, (), (), R, F>>,
     F: ArgminFloat,
     R: Clone,
@@ -117,25 +138,43 @@ where
         let jacobian_t = jacobian.clone().t();
         let grad = jacobian_t.dot(&residuals);
 
-        let p: P = jacobian_t.dot(&jacobian).inv()?.dot(&grad);
+        // Solve the normal equations (J^T J) p = J^T r
+        let p: P =
+            jacobian_t
+                .dot(&jacobian)
+                .solve(&grad)
+                .map_err(|e| ArgminError::SingularMatrix {
+                    text: format!("`GaussNewtonLS`: Normal equations could not be solved: {e}"),
+                })?;
 
         self.linesearch.search_direction(p.mul(&(float!(-1.0))));
+        if let Some(max_step_length) = problem.max_step_length(&param) {
+            self.linesearch.max_step_length(max_step_length)?;
+        }
+        if let Some(move_limits) = problem.move_limits::<P>() {
+            self.linesearch.move_limits(move_limits);
+        }
 
         // perform linesearch
         let OptimizationResult {
             problem: mut line_problem,
             state: mut linesearch_state,
             ..
-        } = Executor::new(
-            LineSearchProblem::new(problem.take_problem().ok_or_else(argmin_error_closure!(
-                PotentialBug,
-                "`GaussNewtonLS`: Failed to take `problem` for line search"
-            ))?),
-            self.linesearch.clone(),
-        )
-        .configure(|config| config.param(param).gradient(grad).cost(residuals.l2_norm()))
-        .ctrlc(false)
-        .run()?;
+        } = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(
+                LineSearchProblem::new(problem.take_problem().ok_or_else(
+                    argmin_error_closure!(
+                        PotentialBug,
+                        "`GaussNewtonLS`: Failed to take `problem` for line search"
+                    ),
+                )?),
+                self.linesearch.clone(),
+            )
+            .configure(|config| config.param(param).gradient(grad).cost(residuals.l2_norm()))
+            .ctrlc(false)
+            .run()?
+        };
 
         // Here we cannot use `consume_problem` because the problem we need is hidden inside a
         // `LineSearchProblem` hidden inside a `Problem`. Therefore we have to split this in two
@@ -166,8 +205,24 @@ where
         ))
     }
 
+    fn termination_diagnostics(&self, state: &IterState<P, G, J, (), R, F>) -> KV {
+        self.tol.diagnostics(
+            None,
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
+    }
+
     fn terminate(&mut self, state: &IterState<P, G, J, (), R, F>) -> TerminationStatus {
-        if (state.get_prev_cost() - state.get_cost()).abs() < self.tol {
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         TerminationStatus::NotTerminated
@@ -247,7 +302,7 @@ mod tests {
         } = GaussNewtonLS::<_, f64>::new(MyLinesearch {});
 
         assert_eq!(ls, MyLinesearch {});
-        assert_eq!(t.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(t.cost().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
     }
 
     #[test]
@@ -258,7 +313,7 @@ mod tests {
         let GaussNewtonLS { tol: t1, .. } =
             GaussNewtonLS::new(linesearch).with_tolerance(tol1).unwrap();
 
-        assert_eq!(t1.to_ne_bytes(), tol1.to_ne_bytes());
+        assert_eq!(t1.cost().to_ne_bytes(), tol1.to_ne_bytes());
     }
 
     #[test]
@@ -6,10 +6,10 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, Error, IterState, Jacobian, Operator, Problem, Solver, State, TerminationReason,
-    TerminationStatus, KV,
+    ArgminError, ArgminFloat, Error, IterState, Jacobian, Operator, Problem, Solver, State,
+    TerminationReason, TerminationStatus, ToleranceConfig, KV,
 };
-use argmin_math::{ArgminDot, ArgminInv, ArgminL2Norm, ArgminMul, ArgminSub, ArgminTranspose};
+use argmin_math::{ArgminDot, ArgminL2Norm, ArgminMul, ArgminSolve, ArgminSub, ArgminTranspose};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -19,10 +19,22 @@ use serde::{Deserialize, Serialize};
 ///
 /// Requires an initial parameter vector.
 ///
+/// The convergence tolerances (the gradient tolerance is not used), including a tolerance on the change of the parameter vector
+/// (disabled by default) and relative instead of absolute tolerances, can be set via
+/// [`with_tolerances`](`GaussNewton::with_tolerances`) (see [`ToleranceConfig`]).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`Operator`] and [`Jacobian`].
 ///
+/// The normal equations `J^T J p = J^T r` are solved via `ArgminSolve`, which allows for sparse
+/// Jacobians (for instance with the `sprs` backend of `argmin-math`).
+///
+/// At the end of each iteration, the Jacobian at the new parameter vector is stored in the state
+/// alongside the residuals, where it is reused in the next iteration. After the run, it is
+/// therefore available via [`get_jacobian`](`IterState::get_jacobian`) (for instance for
+/// estimating the covariance of the parameters) without recomputation.
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
@@ -32,8 +44,8 @@ use serde::{Deserialize, Serialize};
 pub struct GaussNewton<F> {
     /// gamma
     gamma: F,
-    /// Tolerance for the stopping criterion based on cost difference
-    tol: F,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
 }
 
 impl<F: ArgminFloat> GaussNewton<F> {
@@ -48,7 +60,7 @@ impl<F: ArgminFloat> GaussNewton<F> {
     pub fn new() -> Self {
         GaussNewton {
             gamma: float!(1.0),
-            tol: F::epsilon().sqrt(),
+            tol: ToleranceConfig::with_defaults(float!(0.0), F::epsilon().sqrt()),
         }
     }
 
@@ -98,9 +110,29 @@ impl<F: ArgminFloat> GaussNewton<F> {
                 "Gauss-Newton: tol must be positive."
             ));
         }
-        self.tol = tol;
+        self.tol = self.tol.with_cost(tol)?;
         Ok(self)
     }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::GaussNewton;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let gauss_newton = GaussNewton::new().with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
 }
 
 impl<F: ArgminFloat> Default for GaussNewton<F> {
@@ -112,14 +144,9 @@ impl<F: ArgminFloat> Default for GaussNewton<F> {
 impl<O, P, J, R, F> Solver<O, IterState<P, (), J, (), R, F>> for GaussNewton<F>
 where
     O: Operator<Param = P, Output = R> + Jacobian<Param = P, Jacobian = J>,
-    P: Clone + ArgminSub<P, P> + ArgminMul<F, P>,
+    P: Clone + ArgminSub<P, P> + ArgminMul<F, P> + ArgminL2Norm<F>,
     R: ArgminL2Norm<F>,
-    J: Clone
-        + ArgminTranspose<J>
-        + ArgminInv<J>
-        + ArgminDot<J, J>
-        + ArgminDot<R, P>
-        + ArgminDot<P, P>,
+    J: Clone + ArgminTranspose<J> + ArgminDot<J, J> + ArgminDot<R, P> + ArgminSolve<P, P>,
     F: ArgminFloat,
 {
     fn name(&self) -> &str {
@@ -149,8 +176,9 @@ where
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
-        state: IterState<P, (), J, (), R, F>,
+        mut state: IterState<P, (), J, (), R, F>,
     ) -> Result<(IterState<P, (), J, (), R, F>, Option<KV>), Error> {
+        let jacobian = state.take_jacobian();
         let param = state.get_param().ok_or_else(argmin_error_closure!(
             PotentialBug,
             "`GaussNewton`: `param` not set"
@@ -159,25 +187,57 @@ where
             PotentialBug,
             "`GaussNewton`: `residuals` not set"
         ))?;
-        let jacobian = problem.jacobian(param)?;
+        // The Jacobian at the current parameter vector is already available in the state from the
+        // previous iteration, unless this is the first iteration.
+        let jacobian = match jacobian {
+            Some(jacobian) => jacobian,
+            None => problem.jacobian(param)?,
+        };
 
+        // Solve the normal equations (J^T J) p = J^T r
         let p = jacobian
             .clone()
             .t()
             .dot(&jacobian)
-            .inv()?
-            .dot(&jacobian.t().dot(residuals));
+            .solve(&jacobian.t().dot(residuals))
+            .map_err(|e| ArgminError::SingularMatrix {
+                text: format!("`GaussNewton`: Normal equations could not be solved: {e}"),
+            })?;
 
         let new_param = param.sub(&p.mul(&self.gamma));
         let residuals = problem.apply(&new_param)?;
+        let jacobian = problem.jacobian(&new_param)?;
 
         let cost = residuals.l2_norm();
 
-        Ok((state.param(new_param).residuals(residuals).cost(cost), None))
+        Ok((
+            state
+                .param(new_param)
+                .residuals(residuals)
+                .jacobian(jacobian)
+                .cost(cost),
+            None,
+        ))
+    }
+
+    fn termination_diagnostics(&self, state: &IterState<P, (), J, (), R, F>) -> KV {
+        self.tol.diagnostics(
+            None,
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
     }
 
     fn terminate(&mut self, state: &IterState<P, (), J, (), R, F>) -> TerminationStatus {
-        if (state.get_prev_cost() - state.get_cost()).abs() < self.tol {
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         TerminationStatus::NotTerminated
@@ -190,8 +250,6 @@ mod tests {
     use crate::core::ArgminError;
     #[cfg(feature = "_ndarrayl")]
     use crate::core::Executor;
-    #[cfg(feature = "_ndarrayl")]
-    use approx::assert_relative_eq;
 
     test_trait_impl!(gauss_newton_method, GaussNewton<f64>);
 
@@ -200,7 +258,7 @@ mod tests {
         let GaussNewton { tol: t, gamma: g } = GaussNewton::<f64>::new();
 
         assert_eq!(g.to_ne_bytes(), (1.0f64).to_ne_bytes());
-        assert_eq!(t.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(t.cost().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
     }
 
     #[test]
@@ -209,7 +267,7 @@ mod tests {
 
         let GaussNewton { tol: t, .. } = GaussNewton::new().with_tolerance(tol1).unwrap();
 
-        assert_eq!(t.to_ne_bytes(), tol1.to_ne_bytes());
+        assert_eq!(t.cost().to_ne_bytes(), tol1.to_ne_bytes());
     }
 
     #[test]
@@ -375,6 +433,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sparse_jacobian() {
+        use approx::assert_relative_eq;
+        use ndarray::Array1;
+        use sprs::{CsMat, TriMat};
+
+        // Linear least squares problem with residuals r(x) = J x - y and bidiagonal J
+        struct LeastSquares {
+            jacobian: CsMat<f64>,
+            y: Array1<f64>,
+        }
+
+        impl Operator for LeastSquares {
+            type Param = Array1<f64>;
+            type Output = Array1<f64>;
+
+            fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(&self.jacobian * p - &self.y)
+            }
+        }
+
+        impl Jacobian for LeastSquares {
+            type Param = Array1<f64>;
+            type Jacobian = CsMat<f64>;
+
+            fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+                Ok(self.jacobian.clone())
+            }
+        }
+
+        let n = 100;
+        let mut jacobian = TriMat::new((n + 1, n));
+        for i in 0..n {
+            jacobian.add_triplet(i, i, 2.0);
+            jacobian.add_triplet(i + 1, i, -1.0);
+        }
+        let jacobian: CsMat<f64> = jacobian.to_csr();
+        let x = Array1::from_shape_fn(n, |i| (i as f64).sin());
+        let y = &jacobian * &x;
+
+        // Consistent linear problems are solved in a single iteration
+        let solver: GaussNewton<f64> = GaussNewton::new();
+        let param = crate::core::Executor::new(LeastSquares { jacobian, y }, solver)
+            .configure(|config| config.param(Array1::zeros(n)).max_iters(1))
+            .run()
+            .unwrap()
+            .state
+            .take_best_param()
+            .unwrap();
+        for i in 0..n {
+            assert_relative_eq!(param[i], x[i], epsilon = 1e-10);
+        }
+    }
+
     #[cfg(feature = "_ndarrayl")]
     #[test]
     fn test_solver() {
@@ -487,4 +599,50 @@ mod tests {
         // Assert that cost matches residual:
         assert_relative_eq!(state.get_residuals().unwrap().l2_norm(), state.get_cost());
     }
+
+    #[test]
+    fn test_jacobian_in_state() {
+        use crate::core::{Executor, State};
+        use approx::assert_relative_eq;
+        use ndarray::{array, Array1};
+        use sprs::CsMat;
+
+        struct Problem {}
+
+        impl Operator for Problem {
+            type Param = Array1<f64>;
+            type Output = Array1<f64>;
+
+            fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(array![p[0].powi(2) - 2.0, p[1] - 2.0])
+            }
+        }
+
+        impl Jacobian for Problem {
+            type Param = Array1<f64>;
+            type Jacobian = CsMat<f64>;
+
+            fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+                Ok(CsMat::new(
+                    (2, 2),
+                    vec![0, 1, 2],
+                    vec![0, 1],
+                    vec![2.0 * p[0], 1.0],
+                ))
+            }
+        }
+
+        let res = Executor::new(Problem {}, GaussNewton::new())
+            .configure(|config| config.param(array![1.0, 0.0]).max_iters(3).counting(true))
+            .run()
+            .unwrap();
+        let state = res.state();
+        let param = state.get_param().unwrap();
+        let jacobian = state.get_jacobian().unwrap();
+        assert_relative_eq!(*jacobian.get(0, 0).unwrap(), 2.0 * param[0]);
+        assert_relative_eq!(*jacobian.get(1, 1).unwrap(), 1.0);
+        // The Jacobian of the previous iteration is reused, hence one evaluation per iteration
+        // plus one for the initial parameter vector.
+        assert_eq!(state.get_func_counts()["jacobian_count"], 4);
+    }
 }
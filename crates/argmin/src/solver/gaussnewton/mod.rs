@@ -9,6 +9,7 @@
 //!
 //! * [Gauss-Newton method](`GaussNewton`)
 //! * [Gauss-Newton method with line search](`GaussNewtonLS`)
+//! * [Structured BFGS method](`StructuredBFGS`)
 //!
 //! ## Reference
 //!
@@ -17,6 +18,8 @@
 
 mod gaussnewton_linesearch;
 mod gaussnewton_method;
+mod structured_bfgs;
 
 pub use gaussnewton_linesearch::GaussNewtonLS;
 pub use gaussnewton_method::GaussNewton;
+pub use structured_bfgs::StructuredBFGS;
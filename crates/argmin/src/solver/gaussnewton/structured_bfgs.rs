@@ -0,0 +1,747 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminError, ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, Jacobian,
+    LineSearch, Operator, OptimizationResult, Problem, Solver, State, TerminationReason,
+    TerminationStatus, ToleranceConfig, KV,
+};
+use argmin_math::{
+    ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminSolve, ArgminSub, ArgminTranspose,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Structured BFGS method for nonlinear least squares
+///
+/// Quasi-Newton method for problems `min 1/2 ||r(x)||^2` which exploits the structure of the
+/// Hessian
+///
+/// `J^T J + sum_i r_i(x) H_i(x)`,
+///
+/// where `J` is the Jacobian of the residuals `r` and `H_i` the Hessian of `r_i`. The Gauss-Newton
+/// part `J^T J` is computed exactly, while the second-order term is approximated by a matrix `A`,
+/// which starts at zero and is updated such that the model Hessian `J^T J + A` satisfies the
+/// structured secant equation
+///
+/// `(J_{k+1}^T J_{k+1} + A_{k+1}) s_k = J_{k+1}^T J_{k+1} s_k + (J_{k+1} - J_k)^T r_{k+1}`
+///
+/// via a BFGS update (Dennis, Martinez and Tapia, 1989). In each iteration the search direction is
+/// obtained by solving `(J^T J + A) p = -J^T r` and the step length by a line search on
+/// `1/2 ||r||^2`. If this does not yield a descent direction, `A` is reset to zero and the
+/// Gauss-Newton direction is used instead.
+///
+/// Gauss-Newton methods converge slowly (or not at all) if the residuals at the solution are
+/// large, whereas the plain [`BFGS`](`crate::solver::quasinewton::BFGS`) method has to learn the
+/// Gauss-Newton part as well. Before each update, `A` is multiplied with the sizing factor
+/// `min(1, |s^T y#| / |s^T A s|)` with `y# = (J_{k+1} - J_k)^T r_{k+1}` (Dennis, Gay and Welsch,
+/// 1981), which shrinks the second-order term on small-residual problems and retains the fast
+/// local convergence of Gauss-Newton there. Sizing can be disabled via
+/// [`with_sizing`](`StructuredBFGS::with_sizing`).
+///
+/// Requires an initial parameter vector. The cost stored in the state is `1/2 ||r||^2`.
+///
+/// The convergence tolerances on the norm of the gradient `J^T r` and on the change of the cost
+/// can be set via [`with_tolerance_grad`](`StructuredBFGS::with_tolerance_grad`) and
+/// [`with_tolerance_cost`](`StructuredBFGS::with_tolerance_cost`). All convergence tolerances,
+/// including a tolerance on the change of the parameter vector (disabled by default) and relative
+/// instead of absolute tolerances, can be set via
+/// [`with_tolerances`](`StructuredBFGS::with_tolerances`) (see [`ToleranceConfig`]).
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`] and [`Jacobian`].
+///
+/// ## References
+///
+/// John E. Dennis, Jr., Héctor J. Martínez and Richard A. Tapia (1989). Convergence theory for
+/// the structured BFGS secant method with an application to nonlinear least squares. Journal of
+/// Optimization Theory and Applications 61, 161–178.
+///
+/// John E. Dennis, Jr., David M. Gay and Roy E. Welsch (1981). An adaptive nonlinear
+/// least-squares algorithm. ACM Transactions on Mathematical Software 7, 348–368.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct StructuredBFGS<L, J, F> {
+    /// line search
+    linesearch: L,
+    /// Approximation of the second-order term of the Hessian
+    second_order: Option<J>,
+    /// Whether the second-order term is sized before each update
+    sizing: bool,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
+}
+
+impl<L, J, F> StructuredBFGS<L, J, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`StructuredBFGS`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::StructuredBFGS;
+    /// # let linesearch = ();
+    /// let sbfgs: StructuredBFGS<_, Vec<Vec<f64>>, f64> = StructuredBFGS::new(linesearch);
+    /// ```
+    pub fn new(linesearch: L) -> Self {
+        StructuredBFGS {
+            linesearch,
+            second_order: None,
+            sizing: true,
+            tol: ToleranceConfig::new(),
+        }
+    }
+
+    /// Enables or disables the sizing of the second-order term before each update.
+    ///
+    /// Defaults to `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::StructuredBFGS;
+    /// # let linesearch = ();
+    /// let sbfgs: StructuredBFGS<_, Vec<Vec<f64>>, f64> =
+    ///     StructuredBFGS::new(linesearch).with_sizing(false);
+    /// ```
+    #[must_use]
+    pub fn with_sizing(mut self, sizing: bool) -> Self {
+        self.sizing = sizing;
+        self
+    }
+
+    /// The algorithm stops if the norm of the gradient `J^T r` is below `tol_grad`.
+    ///
+    /// The provided value must be non-negative. Defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::StructuredBFGS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let sbfgs: StructuredBFGS<_, Vec<Vec<f64>>, f64> =
+    ///     StructuredBFGS::new(linesearch).with_tolerance_grad(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_grad(mut self, tol_grad: F) -> Result<Self, Error> {
+        if tol_grad < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`StructuredBFGS`: gradient tolerance must be >= 0."
+            ));
+        }
+        self.tol = self.tol.with_grad(tol_grad)?;
+        Ok(self)
+    }
+
+    /// Sets tolerance for the stopping criterion based on the change of the cost
+    ///
+    /// The provided value must be non-negative. Defaults to `EPSILON`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::StructuredBFGS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let sbfgs: StructuredBFGS<_, Vec<Vec<f64>>, f64> =
+    ///     StructuredBFGS::new(linesearch).with_tolerance_cost(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_cost(mut self, tol_cost: F) -> Result<Self, Error> {
+        if tol_cost < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`StructuredBFGS`: cost tolerance must be >= 0."
+            ));
+        }
+        self.tol = self.tol.with_cost(tol_cost)?;
+        Ok(self)
+    }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::StructuredBFGS;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let sbfgs: StructuredBFGS<_, Vec<Vec<f64>>, f64> =
+    ///     StructuredBFGS::new(linesearch).with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+}
+
+impl<O, L, P, U, J, F> Solver<O, IterState<P, P, J, (), U, F>> for StructuredBFGS<L, J, F>
+where
+    O: Operator<Param = P, Output = U> + Jacobian<Param = P, Jacobian = J>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminAdd<P, P>
+        + ArgminMul<F, P>
+        + ArgminDot<P, F>
+        + ArgminDot<P, J>
+        + ArgminL2Norm<F>
+        + 'static,
+    U: Clone + ArgminL2Norm<F>,
+    J: Clone
+        + ArgminTranspose<J>
+        + ArgminDot<J, J>
+        + ArgminDot<U, P>
+        + ArgminDot<P, P>
+        + ArgminAdd<J, J>
+        + ArgminSub<J, J>
+        + ArgminMul<F, J>
+        + ArgminSolve<P, P>,
+    L: Clone + LineSearch<P, F> + Solver<LeastSquaresProblem<O, F>, IterState<P, P, (), (), (), F>>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "Structured BFGS"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, J, (), U, F>,
+    ) -> Result<(IterState<P, P, J, (), U, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`StructuredBFGS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let residuals = problem.apply(&param)?;
+        let jacobian = problem.jacobian(&param)?;
+        let grad = jacobian.clone().t().dot(&residuals);
+        let cost = float!(0.5) * residuals.l2_norm().powi(2);
+        self.second_order = None;
+        Ok((
+            state
+                .param(param)
+                .residuals(residuals)
+                .jacobian(jacobian)
+                .gradient(grad)
+                .cost(cost),
+            None,
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, J, (), U, F>,
+    ) -> Result<(IterState<P, P, J, (), U, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`StructuredBFGS`: Parameter vector in state not set."
+        ))?;
+        let jacobian = state.take_jacobian().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`StructuredBFGS`: Jacobian in state not set."
+        ))?;
+        let grad = state.take_gradient().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`StructuredBFGS`: Gradient in state not set."
+        ))?;
+        let cur_cost = state.get_cost();
+        let jacobian_t = jacobian.clone().t();
+        let gauss_newton: J = jacobian_t.dot(&jacobian);
+        let neg_grad = grad.mul(&float!(-1.0));
+
+        // Solve (J^T J + A) p = -J^T r and fall back to Gauss-Newton if this fails or does not
+        // yield a descent direction.
+        let direction = self
+            .second_order
+            .as_ref()
+            .and_then(|a| gauss_newton.add(a).solve(&neg_grad).ok())
+            .filter(|p: &P| {
+                let slope: F = p.dot(&grad);
+                slope < float!(0.0)
+            });
+        let direction = match direction {
+            Some(p) => p,
+            None => {
+                self.second_order = None;
+                gauss_newton
+                    .solve(&neg_grad)
+                    .map_err(|e| ArgminError::SingularMatrix {
+                        text: format!(
+                            "`StructuredBFGS`: Normal equations could not be solved: {e}"
+                        ),
+                    })?
+            }
+        };
+
+        self.linesearch.search_direction(direction);
+        if let Some(max_step_length) = problem.max_step_length(&param) {
+            self.linesearch.max_step_length(max_step_length)?;
+        }
+        if let Some(move_limits) = problem.move_limits::<P>() {
+            self.linesearch.move_limits(move_limits);
+        }
+
+        // perform linesearch
+        let OptimizationResult {
+            problem: mut line_problem,
+            state: mut linesearch_state,
+            ..
+        } = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(
+                LeastSquaresProblem::new(problem.take_problem().ok_or_else(
+                    argmin_error_closure!(
+                        PotentialBug,
+                        "`StructuredBFGS`: Failed to take `problem` for line search"
+                    ),
+                )?),
+                self.linesearch.clone(),
+            )
+            .configure(|config| config.param(param.clone()).gradient(grad).cost(cur_cost))
+            .ctrlc(false)
+            .run()?
+        };
+
+        // The problem is hidden inside a `LeastSquaresProblem` inside a `Problem`, therefore it
+        // cannot be recovered via `consume_problem`.
+        problem.problem = Some(
+            line_problem
+                .take_problem()
+                .ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`StructuredBFGS`: Failed to take `problem` from line search"
+                ))?
+                .problem,
+        );
+        problem.consume_func_counts(line_problem);
+
+        let xk1 = linesearch_state
+            .take_param()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`StructuredBFGS`: No parameters returned by line search."
+            ))?;
+        let next_cost = linesearch_state.get_cost();
+
+        let residuals = problem.apply(&xk1)?;
+        let next_jacobian = problem.jacobian(&xk1)?;
+        let next_jacobian_t = next_jacobian.clone().t();
+        let next_grad: P = next_jacobian_t.dot(&residuals);
+        let next_gauss_newton: J = next_jacobian_t.dot(&next_jacobian);
+
+        // Structured secant update: y# = (J_{k+1} - J_k)^T r_{k+1}
+        let sk = xk1.sub(&param);
+        let y_sharp = next_grad.sub(&jacobian_t.dot(&residuals));
+        let second_order = self.second_order.take().map(|a| {
+            if !self.sizing {
+                return a;
+            }
+            let sas: F = sk.dot(&a.dot(&sk));
+            if sas == float!(0.0) {
+                return a;
+            }
+            let sy_sharp: F = sk.dot(&y_sharp);
+            let tau = (sy_sharp / sas).abs().min(float!(1.0));
+            a.mul(&tau)
+        });
+        let model = match second_order.as_ref() {
+            Some(a) => next_gauss_newton.add(a),
+            None => next_gauss_newton.clone(),
+        };
+        let bs: P = model.dot(&sk);
+        let sbs: F = sk.dot(&bs);
+        let yk = next_gauss_newton.dot(&sk).add(&y_sharp);
+        let yksk: F = yk.dot(&sk);
+        self.second_order = if yksk > float!(0.0) && sbs > float!(0.0) {
+            let bsbs: J = bs.dot(&bs);
+            let ykyk: J = yk.dot(&yk);
+            let update = ykyk
+                .mul(&(float!(1.0) / yksk))
+                .sub(&bsbs.mul(&(float!(1.0) / sbs)));
+            Some(match second_order {
+                Some(a) => a.add(&update),
+                None => update,
+            })
+        } else {
+            second_order
+        };
+
+        Ok((
+            state
+                .param(xk1)
+                .residuals(residuals)
+                .jacobian(next_jacobian)
+                .gradient(next_grad)
+                .cost(next_cost),
+            None,
+        ))
+    }
+
+    fn termination_diagnostics(&self, state: &IterState<P, P, J, (), U, F>) -> KV {
+        self.tol.diagnostics(
+            state.get_gradient().map(|grad| grad.l2_norm()),
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
+    }
+
+    fn terminate(&mut self, state: &IterState<P, P, J, (), U, F>) -> TerminationStatus {
+        if let Some(grad) = state.get_gradient() {
+            if self.tol.grad_converged(grad.l2_norm(), state.get_cost()) {
+                return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+            }
+        }
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+/// Least squares problem `1/2 ||r(x)||^2` with gradient `J^T r` on which the line search of
+/// [`StructuredBFGS`] operates
+#[doc(hidden)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LeastSquaresProblem<O, F> {
+    problem: O,
+    _phantom: std::marker::PhantomData<F>,
+}
+
+impl<O, F> LeastSquaresProblem<O, F> {
+    /// Construct a new [`LeastSquaresProblem`]
+    fn new(operator: O) -> Self {
+        LeastSquaresProblem {
+            problem: operator,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<O, P, U, F> CostFunction for LeastSquaresProblem<O, F>
+where
+    O: Operator<Param = P, Output = U>,
+    U: ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = F;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(float!(0.5) * self.problem.apply(p)?.l2_norm().powi(2))
+    }
+}
+
+impl<O, P, U, J, F> Gradient for LeastSquaresProblem<O, F>
+where
+    O: Operator<Param = P, Output = U> + Jacobian<Param = P, Jacobian = J>,
+    J: ArgminTranspose<J> + ArgminDot<U, P>,
+{
+    type Param = P;
+    type Gradient = P;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(self.problem.jacobian(p)?.t().dot(&self.problem.apply(p)?))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::let_unit_value)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use crate::solver::linesearch::{condition::ArmijoCondition, BacktrackingLineSearch};
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        structured_bfgs,
+        StructuredBFGS<
+            BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64>,
+            Vec<Vec<f64>>,
+            f64,
+        >
+    );
+
+    type Backtracking<P> = BacktrackingLineSearch<P, P, ArmijoCondition<f64>, f64>;
+
+    fn linesearch<P>() -> Backtracking<P> {
+        BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap())
+    }
+
+    #[test]
+    fn test_new() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct MyFakeLineSearch {}
+
+        let StructuredBFGS {
+            linesearch,
+            second_order,
+            sizing,
+            tol,
+        } = StructuredBFGS::<_, Vec<Vec<f64>>, f64>::new(MyFakeLineSearch {});
+
+        assert_eq!(linesearch, MyFakeLineSearch {});
+        assert!(second_order.is_none());
+        assert!(sizing);
+        assert_eq!(tol.grad().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(tol.cost().to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+
+        let sbfgs = StructuredBFGS::<_, Vec<Vec<f64>>, f64>::new(()).with_sizing(false);
+        assert!(!sbfgs.sizing);
+    }
+
+    #[test]
+    fn test_with_tolerance_grad() {
+        for tol in [1e-6, 0.0, 1.0] {
+            let sbfgs = StructuredBFGS::<_, Vec<Vec<f64>>, f64>::new(())
+                .with_tolerance_grad(tol)
+                .unwrap();
+            assert_eq!(sbfgs.tol.grad().to_ne_bytes(), tol.to_ne_bytes());
+        }
+
+        for tol in [-f64::EPSILON, -1.0] {
+            let res = StructuredBFGS::<_, Vec<Vec<f64>>, f64>::new(()).with_tolerance_grad(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`StructuredBFGS`: gradient tolerance must be >= 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_tolerance_cost() {
+        for tol in [1e-6, 0.0, 1.0] {
+            let sbfgs = StructuredBFGS::<_, Vec<Vec<f64>>, f64>::new(())
+                .with_tolerance_cost(tol)
+                .unwrap();
+            assert_eq!(sbfgs.tol.cost().to_ne_bytes(), tol.to_ne_bytes());
+        }
+
+        for tol in [-f64::EPSILON, -1.0] {
+            let res = StructuredBFGS::<_, Vec<Vec<f64>>, f64>::new(()).with_tolerance_cost(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`StructuredBFGS`: cost tolerance must be >= 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        struct Fake {}
+
+        impl Operator for Fake {
+            type Param = Vec<f64>;
+            type Output = Vec<f64>;
+
+            fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p.clone())
+            }
+        }
+
+        impl Jacobian for Fake {
+            type Param = Vec<f64>;
+            type Jacobian = Vec<Vec<f64>>;
+
+            fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+                Ok(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+            }
+        }
+
+        let mut sbfgs: StructuredBFGS<_, Vec<Vec<f64>>, f64> =
+            StructuredBFGS::new(linesearch::<Vec<f64>>());
+        let res = sbfgs.init(&mut Problem::new(Fake {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`StructuredBFGS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    /// Zero-residual system `x_0^2 + x_1 = 3`, `x_0 - x_1 = -1` with solution (1, 2)
+    struct ZeroResidual {}
+
+    impl Operator for ZeroResidual {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(vec![p[0].powi(2) + p[1] - 3.0, p[0] - p[1] + 1.0])
+        }
+    }
+
+    impl Jacobian for ZeroResidual {
+        type Param = Vec<f64>;
+        type Jacobian = Vec<Vec<f64>>;
+
+        fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(vec![vec![2.0 * p[0], 1.0], vec![1.0, -1.0]])
+        }
+    }
+
+    #[test]
+    fn test_zero_residual() {
+        let solver: StructuredBFGS<_, Vec<Vec<f64>>, f64> =
+            StructuredBFGS::new(linesearch::<Vec<f64>>());
+        let res = Executor::new(ZeroResidual {}, solver)
+            .configure(|state| state.param(vec![2.0, 0.5]).max_iters(50))
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 2.0, epsilon = 1e-6);
+    }
+
+    #[cfg(feature = "_ndarrayl")]
+    mod large_residual {
+        use super::*;
+        use crate::solver::quasinewton::BFGS;
+        use ndarray::{Array1, Array2};
+
+        /// Fit of `x_0 exp(x_1 t)` to data which it cannot reproduce
+        struct ExpFit {
+            t: Vec<f64>,
+            y: Vec<f64>,
+        }
+
+        impl ExpFit {
+            fn new() -> Self {
+                ExpFit {
+                    t: vec![0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0],
+                    y: vec![2.0, -1.0, 4.0, 0.5, 6.0, 2.0, 9.0],
+                }
+            }
+        }
+
+        impl Operator for ExpFit {
+            type Param = Array1<f64>;
+            type Output = Array1<f64>;
+
+            fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(self
+                    .t
+                    .iter()
+                    .zip(self.y.iter())
+                    .map(|(t, y)| p[0] * (p[1] * t).exp() - y)
+                    .collect())
+            }
+        }
+
+        impl Jacobian for ExpFit {
+            type Param = Array1<f64>;
+            type Jacobian = Array2<f64>;
+
+            fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+                let mut jac = Array2::zeros((self.t.len(), 2));
+                for (i, t) in self.t.iter().enumerate() {
+                    let e = (p[1] * t).exp();
+                    jac[[i, 0]] = e;
+                    jac[[i, 1]] = p[0] * t * e;
+                }
+                Ok(jac)
+            }
+        }
+
+        impl CostFunction for ExpFit {
+            type Param = Array1<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(0.5 * self.apply(p)?.l2_norm().powi(2))
+            }
+        }
+
+        impl Gradient for ExpFit {
+            type Param = Array1<f64>;
+            type Gradient = Array1<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(self.jacobian(p)?.t().dot(&self.apply(p)?))
+            }
+        }
+
+        #[test]
+        fn test_large_residual() {
+            let solver: StructuredBFGS<_, Array2<f64>, f64> =
+                StructuredBFGS::new(linesearch::<Array1<f64>>())
+                    .with_tolerance_grad(1e-8)
+                    .unwrap();
+            let res = Executor::new(ExpFit::new(), solver)
+                .configure(|state| state.param(Array1::from(vec![1.0, 0.5])).max_iters(100))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            let sbfgs_iters = res.state.get_iter();
+            let param = res.state.get_best_param().unwrap().clone();
+
+            // Stationary point with nonzero residuals
+            let problem = ExpFit::new();
+            let grad = problem
+                .jacobian(&param)
+                .unwrap()
+                .t()
+                .dot(&problem.apply(&param).unwrap());
+            assert!(grad.iter().all(|g| g.abs() < 1e-5));
+            assert!(res.state.get_best_cost() > 1.0);
+
+            // Plain BFGS has to approximate the Gauss-Newton part as well
+            let solver: BFGS<_, f64> = BFGS::new(linesearch::<Array1<f64>>())
+                .with_tolerance_grad(1e-8)
+                .unwrap();
+            let res = Executor::new(ExpFit::new(), solver)
+                .configure(|state| {
+                    state
+                        .param(Array1::from(vec![1.0, 0.5]))
+                        .inv_hessian(Array2::eye(2))
+                        .max_iters(100)
+                })
+                .run()
+                .unwrap();
+            assert!(res.state.get_iter() > sbfgs_iters);
+        }
+    }
+}
@@ -17,7 +17,7 @@
 //! <https://en.wikipedia.org/wiki/Golden-section_search>
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, TerminationReason,
+    ArgminFloat, CostFunction, DerivativeFreeState, Error, Problem, Solver, TerminationReason,
     TerminationStatus, KV,
 };
 #[cfg(feature = "serde1")]
@@ -134,7 +134,7 @@ where
     }
 }
 
-impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for GoldenSectionSearch<F>
+impl<O, F> Solver<O, DerivativeFreeState<F, F>> for GoldenSectionSearch<F>
 where
     O: CostFunction<Param = F, Output = F>,
     F: ArgminFloat,
@@ -146,8 +146,8 @@ where
     fn init(
         &mut self,
         problem: &mut Problem<O>,
-        mut state: IterState<F, (), (), (), (), F>,
-    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        mut state: DerivativeFreeState<F, F>,
+    ) -> Result<(DerivativeFreeState<F, F>, Option<KV>), Error> {
         let init_estimate = state.take_param().ok_or_else(argmin_error_closure!(
             NotInitialized,
             concat!(
@@ -183,8 +183,8 @@ where
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
-        state: IterState<F, (), (), (), (), F>,
-    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        state: DerivativeFreeState<F, F>,
+    ) -> Result<(DerivativeFreeState<F, F>, Option<KV>), Error> {
         if self.f2 < self.f1 {
             self.x0 = self.x1;
             self.x1 = self.x2;
@@ -205,7 +205,7 @@ where
         }
     }
 
-    fn terminate(&mut self, _state: &IterState<F, (), (), (), (), F>) -> TerminationStatus {
+    fn terminate(&mut self, _state: &DerivativeFreeState<F, F>) -> TerminationStatus {
         if self.tolerance * (self.x1.abs() + self.x2.abs()) >= (self.x3 - self.x0).abs() {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
@@ -321,7 +321,10 @@ mod tests {
     #[test]
     fn test_init_param_not_initialized() {
         let mut gss = GoldenSectionSearch::new(-2.5f64, 3.0f64).unwrap();
-        let res = gss.init(&mut Problem::new(GssTestProblem {}), IterState::new());
+        let res = gss.init(
+            &mut Problem::new(GssTestProblem {}),
+            DerivativeFreeState::new(),
+        );
         assert_error!(
             res,
             ArgminError,
@@ -337,7 +340,7 @@ mod tests {
         let mut gss = GoldenSectionSearch::new(-2.5f64, 3.0f64).unwrap();
         let res = gss.init(
             &mut Problem::new(GssTestProblem {}),
-            IterState::new().param(5.0f64),
+            DerivativeFreeState::new().param(5.0f64),
         );
         assert_error!(
             res,
@@ -355,7 +358,7 @@ mod tests {
         let (state, kv) = gss
             .init(
                 &mut Problem::new(problem.clone()),
-                IterState::new().param(-0.5f64),
+                DerivativeFreeState::new().param(-0.5f64),
             )
             .unwrap();
 
@@ -409,7 +412,7 @@ mod tests {
         gss.x3 = 3.0f64;
 
         let (state, kv) = gss
-            .next_iter(&mut problem, IterState::new().param(-0.5f64))
+            .next_iter(&mut problem, DerivativeFreeState::new().param(-0.5f64))
             .unwrap();
 
         assert!(kv.is_none());
@@ -460,7 +463,7 @@ mod tests {
         gss.x3 = 3.0f64;
 
         let (state, kv) = gss
-            .next_iter(&mut problem, IterState::new().param(-0.5f64))
+            .next_iter(&mut problem, DerivativeFreeState::new().param(-0.5f64))
             .unwrap();
 
         assert!(kv.is_none());
@@ -9,11 +9,19 @@
 //!
 //! [`SteepestDescent`]
 //!
+//! [`PolyakGradientDescent`]
+//!
+//! Decoupled weight decay and gradient clipping: [`GradientModifiers`]
+//!
 //! ## Reference
 //!
 //! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+mod modifiers;
+mod polyak;
 mod steepestdescent;
 
+pub use self::modifiers::{GradientModifier, GradientModifiers};
+pub use self::polyak::PolyakGradientDescent;
 pub use self::steepestdescent::*;
@@ -0,0 +1,328 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error, KV};
+use argmin_math::{
+    ArgminAdd, ArgminL2Norm, ArgminMinMax, ArgminMul, ArgminScaledSub, ArgminZeroLike,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Modification of gradients and steps of first-order solvers
+///
+/// Implemented for `()`, which leaves gradients and steps untouched and does not impose any
+/// requirements on the parameter vector and gradient, and for [`GradientModifiers`].
+pub trait GradientModifier<P, G> {
+    /// Clips `grad`, which may be negated, and records metrics in `kv`
+    fn clip_gradient(&self, grad: G, kv: &mut KV) -> G;
+
+    /// Returns `true` if [`apply_weight_decay`](`GradientModifier::apply_weight_decay`) changes
+    /// the parameter vector
+    fn has_weight_decay(&self) -> bool;
+
+    /// Applies weight decay based on the parameter vector `prev_param` at the beginning of the
+    /// iteration to the parameter vector `param` after the step and records metrics in `kv`
+    fn apply_weight_decay(&self, param: P, prev_param: &P, kv: &mut KV) -> P;
+}
+
+impl<P, G> GradientModifier<P, G> for () {
+    fn clip_gradient(&self, grad: G, _kv: &mut KV) -> G {
+        grad
+    }
+
+    fn has_weight_decay(&self) -> bool {
+        false
+    }
+
+    fn apply_weight_decay(&self, param: P, _prev_param: &P, _kv: &mut KV) -> P {
+        param
+    }
+}
+
+/// # Decoupled weight decay and gradient clipping
+///
+/// Gradients are first clipped elementwise to `[-clip_value, clip_value]` and then rescaled such
+/// that their norm does not exceed `clip_norm`. Weight decay is decoupled from the gradient step
+/// (as in AdamW): after the step, the parameter vector is reduced by `weight_decay` times the
+/// parameter vector at the beginning of the iteration, independent of the step length.
+///
+/// The norms of the gradient before and after clipping (`gradient_norm`,
+/// `clipped_gradient_norm`) and of the weight decay step (`weight_decay_norm`) are reported in
+/// each iteration.
+///
+/// Usually configured via the corresponding methods of the solvers, for instance
+/// [`SteepestDescent::with_weight_decay`](`crate::solver::gradientdescent::SteepestDescent::with_weight_decay`).
+///
+/// ## Reference
+///
+/// Ilya Loshchilov and Frank Hutter (2019). Decoupled Weight Decay Regularization. ICLR 2019.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GradientModifiers<F> {
+    /// Weight decay factor
+    weight_decay: F,
+    /// Maximum norm of the gradient
+    clip_norm: Option<F>,
+    /// Maximum absolute value of each element of the gradient
+    clip_value: Option<F>,
+}
+
+impl<F: ArgminFloat> GradientModifiers<F> {
+    /// Construct a new instance of [`GradientModifiers`] which leaves gradients and steps
+    /// untouched
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::GradientModifiers;
+    /// let modifiers: GradientModifiers<f64> = GradientModifiers::new();
+    /// ```
+    pub fn new() -> Self {
+        GradientModifiers {
+            weight_decay: float!(0.0),
+            clip_norm: None,
+            clip_value: None,
+        }
+    }
+
+    /// Set weight decay factor
+    ///
+    /// Must be in `[0, 1)` and defaults to `0` (no weight decay).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::GradientModifiers;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let modifiers = GradientModifiers::new().with_weight_decay(0.01f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_weight_decay(mut self, weight_decay: F) -> Result<Self, Error> {
+        if weight_decay < float!(0.0) || weight_decay >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GradientModifiers`: Weight decay must be in [0, 1)."
+            ));
+        }
+        self.weight_decay = weight_decay;
+        Ok(self)
+    }
+
+    /// Set maximum norm of the gradient
+    ///
+    /// Must be larger than 0. By default, gradients are not clipped by norm.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::GradientModifiers;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let modifiers = GradientModifiers::new().with_clip_norm(10.0f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_clip_norm(mut self, max_norm: F) -> Result<Self, Error> {
+        if max_norm <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GradientModifiers`: Maximum gradient norm must be larger than 0."
+            ));
+        }
+        self.clip_norm = Some(max_norm);
+        Ok(self)
+    }
+
+    /// Set maximum absolute value of each element of the gradient
+    ///
+    /// Must be larger than 0. By default, gradients are not clipped by value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::GradientModifiers;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let modifiers = GradientModifiers::new().with_clip_value(1.0f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_clip_value(mut self, max_value: F) -> Result<Self, Error> {
+        if max_value <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GradientModifiers`: Maximum gradient value must be larger than 0."
+            ));
+        }
+        self.clip_value = Some(max_value);
+        Ok(self)
+    }
+}
+
+impl<F: ArgminFloat> Default for GradientModifiers<F> {
+    fn default() -> Self {
+        GradientModifiers::new()
+    }
+}
+
+impl<F: ArgminFloat> From<()> for GradientModifiers<F> {
+    fn from(_: ()) -> Self {
+        GradientModifiers::new()
+    }
+}
+
+impl<P, G, F> GradientModifier<P, G> for GradientModifiers<F>
+where
+    P: ArgminScaledSub<P, F, P> + ArgminL2Norm<F>,
+    G: ArgminL2Norm<F> + ArgminMul<F, G> + ArgminMinMax + ArgminZeroLike + ArgminAdd<F, G>,
+    F: ArgminFloat,
+{
+    fn clip_gradient(&self, grad: G, kv: &mut KV) -> G {
+        if self.clip_norm.is_none() && self.clip_value.is_none() {
+            return grad;
+        }
+        let norm = grad.l2_norm();
+        let grad = match self.clip_value {
+            Some(max_value) => {
+                let zeros = grad.zero_like();
+                let upper = zeros.add(&max_value);
+                let lower = zeros.add(&(-max_value));
+                G::min(&G::max(&grad, &lower), &upper)
+            }
+            None => grad,
+        };
+        let clipped_norm = grad.l2_norm();
+        let grad = match self.clip_norm {
+            Some(max_norm) if clipped_norm > max_norm => grad.mul(&(max_norm / clipped_norm)),
+            _ => grad,
+        };
+        kv.insert("gradient_norm", norm.into());
+        kv.insert("clipped_gradient_norm", grad.l2_norm().into());
+        grad
+    }
+
+    fn has_weight_decay(&self) -> bool {
+        self.weight_decay > float!(0.0)
+    }
+
+    fn apply_weight_decay(&self, param: P, prev_param: &P, kv: &mut KV) -> P {
+        if !<Self as GradientModifier<P, G>>::has_weight_decay(self) {
+            return param;
+        }
+        kv.insert(
+            "weight_decay_norm",
+            (self.weight_decay * prev_param.l2_norm()).into(),
+        );
+        param.scaled_sub(&self.weight_decay, prev_param)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    type Modifier = dyn GradientModifier<Vec<f64>, Vec<f64>>;
+
+    #[test]
+    fn test_unit() {
+        let m: &Modifier = &();
+        let mut kv = KV::new();
+        assert!(!m.has_weight_decay());
+        assert_eq!(m.clip_gradient(vec![3.0, 4.0], &mut kv), vec![3.0, 4.0]);
+        assert_eq!(
+            m.apply_weight_decay(vec![3.0, 4.0], &vec![1.0, 1.0], &mut kv),
+            vec![3.0, 4.0]
+        );
+        assert!(kv.keys().is_empty());
+    }
+
+    #[test]
+    fn test_new() {
+        let m: GradientModifiers<f64> = GradientModifiers::new();
+        assert_eq!(m, GradientModifiers::from(()));
+        let m: &Modifier = &m;
+        let mut kv = KV::new();
+        assert!(!m.has_weight_decay());
+        assert_eq!(m.clip_gradient(vec![3.0, 4.0], &mut kv), vec![3.0, 4.0]);
+        assert_eq!(
+            m.apply_weight_decay(vec![3.0, 4.0], &vec![1.0, 1.0], &mut kv),
+            vec![3.0, 4.0]
+        );
+        assert!(kv.keys().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let m: GradientModifiers<f64> = GradientModifiers::new();
+        assert!(m.with_weight_decay(-0.1).is_err());
+        assert!(m.with_weight_decay(1.0).is_err());
+        assert!(m.with_clip_norm(0.0).is_err());
+        assert!(m.with_clip_value(-1.0).is_err());
+        assert!(m.with_weight_decay(0.0).is_ok());
+    }
+
+    #[test]
+    fn test_clip_norm() {
+        let m = GradientModifiers::new().with_clip_norm(1.0f64).unwrap();
+        let m: &Modifier = &m;
+        let mut kv = KV::new();
+        let grad = m.clip_gradient(vec![3.0, 4.0], &mut kv);
+        assert_relative_eq!(grad[0], 0.6, epsilon = f64::EPSILON);
+        assert_relative_eq!(grad[1], 0.8, epsilon = f64::EPSILON);
+        assert_relative_eq!(
+            kv.get("gradient_norm").unwrap().get_float().unwrap(),
+            5.0,
+            epsilon = f64::EPSILON
+        );
+        assert_relative_eq!(
+            kv.get("clipped_gradient_norm")
+                .unwrap()
+                .get_float()
+                .unwrap(),
+            1.0,
+            epsilon = f64::EPSILON
+        );
+
+        // Gradients with smaller norm are untouched
+        assert_eq!(m.clip_gradient(vec![0.3, 0.4], &mut kv), vec![0.3, 0.4]);
+    }
+
+    #[test]
+    fn test_clip_value_and_norm() {
+        let m = GradientModifiers::new().with_clip_value(2.0f64).unwrap();
+        let mut kv = KV::new();
+        assert_eq!(
+            (&m as &Modifier).clip_gradient(vec![3.0, -4.0, 1.0], &mut kv),
+            vec![2.0, -2.0, 1.0]
+        );
+
+        let m = m.with_clip_norm(1.5).unwrap();
+        let grad = (&m as &Modifier).clip_gradient(vec![3.0, -4.0, 1.0], &mut kv);
+        assert_relative_eq!(grad[0], 1.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(grad[1], -1.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(grad[2], 0.5, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_weight_decay() {
+        let m = GradientModifiers::new().with_weight_decay(0.1f64).unwrap();
+        let m: &Modifier = &m;
+        assert!(m.has_weight_decay());
+        let mut kv = KV::new();
+        let param = m.apply_weight_decay(vec![3.0, 4.0], &vec![30.0, 40.0], &mut kv);
+        assert_relative_eq!(param[0], 0.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(param[1], 0.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(
+            kv.get("weight_decay_norm").unwrap().get_float().unwrap(),
+            5.0,
+            epsilon = f64::EPSILON
+        );
+    }
+}
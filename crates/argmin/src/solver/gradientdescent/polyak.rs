@@ -0,0 +1,344 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, IterState, Problem, Solver, State,
+    TerminationReason, KV,
+};
+use argmin_math::{ArgminL2Norm, ArgminScaledSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Gradient descent with Polyak step size
+///
+/// Takes steps in the direction of the negative gradient with the step length
+///
+/// `alpha_k = (f(x_k) - f^*) / ||\nabla f(x_k)||^2`,
+///
+/// where `f^*` is the optimal cost function value. No line search is needed, which makes this
+/// method attractive for convex problems where `f^*` is known (for instance `0` for
+/// interpolating least squares problems) or can be estimated.
+///
+/// If `f^*` is known, the solver is constructed with [`new`](`PolyakGradientDescent::new`). It
+/// terminates once the cost function value reaches `f^*`.
+///
+/// Otherwise, [`adaptive`](`PolyakGradientDescent::adaptive`) estimates `f^*` online with the
+/// path-based target level method: the target level is the best cost function value found so far
+/// minus an offset `delta`. Once the cost function value has decreased by at least `delta / 2`, a
+/// new target level is computed with the same offset. If instead the iterates travel a total
+/// distance of more than `path_bound` without such a decrease, the target level was too
+/// optimistic and a new one is computed with half the offset.
+///
+/// The solver also terminates if the gradient vanishes. Step length and target level are
+/// reported in each iteration.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## References
+///
+/// Boris T. Polyak (1987). Introduction to Optimization. Optimization Software, New York.
+///
+/// Jean-Louis Goffin and Krzysztof C. Kiwiel (1999). Convergence of a simple subgradient level
+/// method. Math. Program. 85, 207-211.
+///
+/// Angelia Nedić and Dimitri P. Bertsekas (2001). Incremental subgradient methods for
+/// nondifferentiable optimization. SIAM J. Optim. 12(1), 109-138.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct PolyakGradientDescent<F> {
+    /// Known optimal cost function value
+    optimal_cost: Option<F>,
+    /// Offset of the target level from the best cost function value
+    delta: F,
+    /// Maximum distance travelled without sufficient decrease
+    path_bound: F,
+    /// Distance travelled since the target level was last updated
+    path: F,
+    /// Best cost function value when the target level was last updated
+    record: Option<F>,
+}
+
+impl<F: ArgminFloat> PolyakGradientDescent<F> {
+    /// Construct a new instance of [`PolyakGradientDescent`] with known optimal cost function
+    /// value `optimal_cost`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::PolyakGradientDescent;
+    /// let polyak: PolyakGradientDescent<f64> = PolyakGradientDescent::new(0.0);
+    /// ```
+    pub fn new(optimal_cost: F) -> Self {
+        PolyakGradientDescent {
+            optimal_cost: Some(optimal_cost),
+            delta: float!(0.0),
+            path_bound: F::infinity(),
+            path: float!(0.0),
+            record: None,
+        }
+    }
+
+    /// Construct a new instance of [`PolyakGradientDescent`] which estimates the optimal cost
+    /// function value online, starting with the offset `delta` of the target level
+    ///
+    /// `delta` should be of the order of the expected distance between the initial and the optimal
+    /// cost function value and `path_bound` of the order of the distance between the initial
+    /// parameter vector and the minimizer. Both must be larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::PolyakGradientDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let polyak: PolyakGradientDescent<f64> = PolyakGradientDescent::adaptive(1.0, 10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn adaptive(delta: F, path_bound: F) -> Result<Self, Error> {
+        if delta <= float!(0.0) || !delta.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PolyakGradientDescent`: delta must be larger than 0."
+            ));
+        }
+        if path_bound <= float!(0.0) || !path_bound.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PolyakGradientDescent`: path bound must be larger than 0."
+            ));
+        }
+        Ok(PolyakGradientDescent {
+            optimal_cost: None,
+            delta,
+            path_bound,
+            path: float!(0.0),
+            record: None,
+        })
+    }
+}
+
+impl<O, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for PolyakGradientDescent<F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
+    P: Clone + ArgminScaledSub<G, F, P>,
+    G: ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "Polyak Gradient Descent"
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`PolyakGradientDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        // Reuse cost and gradient computed at the end of the previous iteration
+        let (cost, grad) = match state.take_gradient() {
+            Some(grad) if state.get_cost().is_finite() => (state.get_cost(), grad),
+            _ => (problem.cost(&param)?, problem.gradient(&param)?),
+        };
+
+        let level = match self.optimal_cost {
+            Some(optimal_cost) => optimal_cost,
+            None => {
+                let best_cost = cost.min(state.get_best_cost());
+                let record = match self.record {
+                    // Sufficient decrease
+                    Some(record) if cost <= record - self.delta / float!(2.0) => {
+                        self.path = float!(0.0);
+                        best_cost
+                    }
+                    // Target level was too low
+                    Some(_) if self.path > self.path_bound => {
+                        self.path = float!(0.0);
+                        self.delta = self.delta / float!(2.0);
+                        best_cost
+                    }
+                    Some(record) => record,
+                    None => best_cost,
+                };
+                self.record = Some(record);
+                record - self.delta
+            }
+        };
+
+        let grad_norm = grad.l2_norm();
+        if cost <= level || grad_norm <= float!(0.0) {
+            return Ok((
+                state
+                    .param(param)
+                    .cost(cost)
+                    .gradient(grad)
+                    .terminate_with(TerminationReason::SolverConverged),
+                None,
+            ));
+        }
+
+        let step_length = (cost - level) / grad_norm.powi(2);
+        self.path = self.path + step_length * grad_norm;
+        let new_param = param.scaled_sub(&step_length, &grad);
+        let new_cost = problem.cost(&new_param)?;
+        let new_grad = problem.gradient(&new_param)?;
+
+        Ok((
+            state.param(new_param).cost(new_cost).gradient(new_grad),
+            Some(kv!("step_length" => step_length; "target_cost" => level;)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(polyak, PolyakGradientDescent<f64>);
+
+    struct Quadratic {}
+
+    impl CostFunction for Quadratic {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p[0].powi(2) + 4.0 * p[1].powi(2) + 1.0)
+        }
+    }
+
+    impl Gradient for Quadratic {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(vec![2.0 * p[0], 8.0 * p[1]])
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let polyak: PolyakGradientDescent<f64> = PolyakGradientDescent::new(1.0);
+        assert_eq!(polyak.optimal_cost, Some(1.0));
+        assert_eq!(polyak.record, None);
+    }
+
+    #[test]
+    fn test_adaptive() {
+        let polyak: PolyakGradientDescent<f64> =
+            PolyakGradientDescent::adaptive(2.0, 10.0).unwrap();
+        assert_eq!(polyak.optimal_cost, None);
+        assert_eq!(polyak.delta.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(polyak.path_bound.to_ne_bytes(), 10.0f64.to_ne_bytes());
+
+        for delta in [0.0, -1.0, f64::INFINITY] {
+            assert_error!(
+                PolyakGradientDescent::adaptive(delta, 10.0),
+                ArgminError,
+                "Invalid parameter: \"`PolyakGradientDescent`: delta must be larger than 0.\""
+            );
+        }
+        for path_bound in [0.0, -1.0, f64::NAN] {
+            assert_error!(
+                PolyakGradientDescent::adaptive(1.0, path_bound),
+                ArgminError,
+                "Invalid parameter: \"`PolyakGradientDescent`: path bound must be larger than 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_iter_param_not_initialized() {
+        let mut polyak = PolyakGradientDescent::new(0.0);
+        let res = polyak.next_iter(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`PolyakGradientDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter() {
+        let mut polyak = PolyakGradientDescent::new(1.0);
+        let mut problem = Problem::new(Quadratic {});
+        let (state, kv) = polyak
+            .next_iter(&mut problem, IterState::new().param(vec![1.0, 0.0]))
+            .unwrap();
+        // alpha = (2 - 1) / 4
+        let kv = kv.unwrap();
+        assert_relative_eq!(
+            kv.get("step_length").unwrap().get_float().unwrap(),
+            0.25,
+            epsilon = f64::EPSILON
+        );
+        assert_eq!(state.get_param().unwrap(), &vec![0.5, 0.0]);
+        assert_relative_eq!(state.get_cost(), 1.25, epsilon = f64::EPSILON);
+        assert_eq!(state.get_gradient().unwrap(), &vec![1.0, 0.0]);
+        assert_eq!(problem.counts["cost_count"], 2);
+        assert_eq!(problem.counts["gradient_count"], 2);
+
+        // Cost and gradient are reused
+        let (state, _) = polyak.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_param().unwrap(), &vec![0.25, 0.0]);
+        assert_eq!(problem.counts["cost_count"], 3);
+        assert_eq!(problem.counts["gradient_count"], 3);
+    }
+
+    #[test]
+    fn test_terminates_at_optimum() {
+        let mut polyak = PolyakGradientDescent::new(1.0);
+        let (state, kv) = polyak
+            .next_iter(
+                &mut Problem::new(Quadratic {}),
+                IterState::new().param(vec![0.0, 0.0]),
+            )
+            .unwrap();
+        assert!(kv.is_none());
+        assert_eq!(
+            state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_eq!(state.get_param().unwrap(), &vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_known_optimum() {
+        let res = Executor::new(Quadratic {}, PolyakGradientDescent::new(1.0))
+            .configure(|state| state.param(vec![3.0, -2.0]).max_iters(200))
+            .run()
+            .unwrap();
+        let best_param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(best_param[0], 0.0, epsilon = 1e-6);
+        assert_relative_eq!(best_param[1], 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_adaptive_optimum() {
+        let res = Executor::new(
+            Quadratic {},
+            PolyakGradientDescent::adaptive(1.0, 5.0).unwrap(),
+        )
+        .configure(|state| state.param(vec![3.0, -2.0]).max_iters(500))
+        .run()
+        .unwrap();
+        assert_relative_eq!(res.state.get_best_cost(), 1.0, epsilon = 1e-6);
+    }
+}
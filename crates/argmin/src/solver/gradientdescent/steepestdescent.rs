@@ -9,6 +9,7 @@ use crate::core::{
     ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
     OptimizationResult, Problem, Solver, State, KV,
 };
+use crate::solver::gradientdescent::{GradientModifier, GradientModifiers};
 use argmin_math::ArgminMul;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -18,19 +19,32 @@ use serde::{Deserialize, Serialize};
 /// Iteratively takes steps in the direction of the strongest negative gradient. In each iteration,
 /// a line search is used to obtain an appropriate step length.
 ///
+/// The search direction can be stabilized by clipping the gradient by value
+/// ([`with_gradient_clipping_value`](`SteepestDescent::with_gradient_clipping_value`)) and by norm
+/// ([`with_gradient_clipping_norm`](`SteepestDescent::with_gradient_clipping_norm`)). Decoupled
+/// weight decay ([`with_weight_decay`](`SteepestDescent::with_weight_decay`)) reduces the
+/// parameter vector after each line search independent of the step length (see
+/// [`GradientModifiers`]). These options can be combined.
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
 ///
+/// Weight decay and gradient clipping additionally require the parameter vector and the gradient
+/// to implement the math traits listed in the implementation of [`GradientModifier`] for
+/// [`GradientModifiers`].
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct SteepestDescent<L> {
+pub struct SteepestDescent<L, M = ()> {
     /// line search
     linesearch: L,
+    /// weight decay and gradient clipping
+    modifiers: M,
 }
 
 impl<L> SteepestDescent<L> {
@@ -46,16 +60,116 @@ impl<L> SteepestDescent<L> {
     /// let sd = SteepestDescent::new(linesearch);
     /// ```
     pub fn new(linesearch: L) -> Self {
-        SteepestDescent { linesearch }
+        SteepestDescent {
+            linesearch,
+            modifiers: (),
+        }
     }
 }
 
-impl<O, L, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for SteepestDescent<L>
+impl<L, M> SteepestDescent<L, M> {
+    /// Set decoupled weight decay factor
+    ///
+    /// After each line search, the parameter vector is reduced by `weight_decay` times the
+    /// parameter vector at the beginning of the iteration. Must be in `[0, 1)` and defaults to `0`
+    /// (no weight decay).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::SteepestDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let sd = SteepestDescent::new(linesearch).with_weight_decay(0.01f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_weight_decay<F>(
+        self,
+        weight_decay: F,
+    ) -> Result<SteepestDescent<L, GradientModifiers<F>>, Error>
+    where
+        F: ArgminFloat,
+        M: Into<GradientModifiers<F>>,
+    {
+        Ok(SteepestDescent {
+            linesearch: self.linesearch,
+            modifiers: self.modifiers.into().with_weight_decay(weight_decay)?,
+        })
+    }
+
+    /// Clip the gradient by norm
+    ///
+    /// Gradients with a norm larger than `max_norm` are rescaled to norm `max_norm` before they
+    /// are used as search direction. Must be larger than 0. By default, gradients are not clipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::SteepestDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let sd = SteepestDescent::new(linesearch).with_gradient_clipping_norm(10.0f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_gradient_clipping_norm<F>(
+        self,
+        max_norm: F,
+    ) -> Result<SteepestDescent<L, GradientModifiers<F>>, Error>
+    where
+        F: ArgminFloat,
+        M: Into<GradientModifiers<F>>,
+    {
+        Ok(SteepestDescent {
+            linesearch: self.linesearch,
+            modifiers: self.modifiers.into().with_clip_norm(max_norm)?,
+        })
+    }
+
+    /// Clip the gradient by value
+    ///
+    /// Each element of the gradient is clipped to `[-max_value, max_value]` before it is used as
+    /// search direction. This is applied before clipping by norm. Must be larger than 0. By
+    /// default, gradients are not clipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::SteepestDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let sd = SteepestDescent::new(linesearch)
+    ///     .with_gradient_clipping_value(1.0f64)?
+    ///     .with_weight_decay(0.01)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_gradient_clipping_value<F>(
+        self,
+        max_value: F,
+    ) -> Result<SteepestDescent<L, GradientModifiers<F>>, Error>
+    where
+        F: ArgminFloat,
+        M: Into<GradientModifiers<F>>,
+    {
+        Ok(SteepestDescent {
+            linesearch: self.linesearch,
+            modifiers: self.modifiers.into().with_clip_value(max_value)?,
+        })
+    }
+}
+
+impl<O, L, M, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for SteepestDescent<L, M>
 where
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
     P: Clone,
     G: Clone + ArgminMul<F, G>,
     L: Clone + LineSearch<G, F> + Solver<O, IterState<P, G, (), (), (), F>>,
+    M: GradientModifier<P, G>,
     F: ArgminFloat,
 {
     fn name(&self) -> &str {
@@ -65,7 +179,7 @@ where
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
-        state: IterState<P, G, (), (), (), F>,
+        mut state: IterState<P, G, (), (), (), F>,
     ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
         let param_new = state
             .get_param()
@@ -77,44 +191,72 @@ where
                 )
             ))?
             .clone();
-        let new_cost = problem.cost(&param_new)?;
-        let new_grad = problem.gradient(&param_new)?;
+        // Reuse cost and gradient at the point accepted by the previous line search
+        let (new_cost, new_grad) = match state.take_gradient() {
+            Some(grad) if state.get_cost().is_finite() => (state.get_cost(), grad),
+            _ => (problem.cost(&param_new)?, problem.gradient(&param_new)?),
+        };
 
-        self.linesearch
-            .search_direction(new_grad.mul(&(float!(-1.0))));
+        // Clipping is symmetric, therefore the negative gradient can be clipped directly
+        let mut kv = KV::new();
+        self.linesearch.search_direction(
+            self.modifiers
+                .clip_gradient(new_grad.mul(&(float!(-1.0))), &mut kv),
+        );
+        let prev_param = if self.modifiers.has_weight_decay() {
+            Some(param_new.clone())
+        } else {
+            None
+        };
 
         // Run line search
         let OptimizationResult {
             problem: line_problem,
             state: mut linesearch_state,
             ..
-        } = Executor::new(
-            problem.take_problem().ok_or_else(argmin_error_closure!(
-                PotentialBug,
-                "`SteepestDescent`: Failed to take `problem` for line search"
-            ))?,
-            self.linesearch.clone(),
-        )
-        .configure(|config| config.param(param_new).gradient(new_grad).cost(new_cost))
-        .ctrlc(false)
-        .run()?;
+        } = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(
+                problem.take_problem().ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`SteepestDescent`: Failed to take `problem` for line search"
+                ))?,
+                self.linesearch.clone(),
+            )
+            .configure(|config| config.param(param_new).gradient(new_grad).cost(new_cost))
+            .ctrlc(false)
+            .run()?
+        };
 
         // Get back problem and function evaluation counts
         problem.consume_problem(line_problem);
 
-        Ok((
-            state
-                .param(
-                    linesearch_state
-                        .take_param()
-                        .ok_or_else(argmin_error_closure!(
-                            PotentialBug,
-                            "`GradientDescent`: No `param` returned by line search"
-                        ))?,
-                )
-                .cost(linesearch_state.get_cost()),
-            None,
-        ))
+        let param = linesearch_state
+            .take_param()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`GradientDescent`: No `param` returned by line search"
+            ))?;
+
+        let state = match prev_param {
+            // Weight decay invalidates cost and gradient computed by the line search
+            Some(prev_param) => {
+                let param = self
+                    .modifiers
+                    .apply_weight_decay(param, &prev_param, &mut kv);
+                let cost = problem.cost(&param)?;
+                let grad = problem.gradient(&param)?;
+                state.param(param).cost(cost).gradient(grad)
+            }
+            None => {
+                let state = state.param(param).cost(linesearch_state.get_cost());
+                match linesearch_state.take_gradient() {
+                    Some(grad) => state.gradient(grad),
+                    None => state,
+                }
+            }
+        };
+        Ok((state, (!kv.keys().is_empty()).then_some(kv)))
     }
 }
 
@@ -127,6 +269,7 @@ mod tests {
         condition::ArmijoCondition, BacktrackingLineSearch, MoreThuenteLineSearch,
     };
     use approx::assert_relative_eq;
+    use argmin_math::ArgminScaledAddAssign;
 
     test_trait_impl!(
         steepest_descent,
@@ -137,7 +280,10 @@ mod tests {
     fn test_new() {
         let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
             BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
-        let SteepestDescent { linesearch: ls } = SteepestDescent::new(linesearch.clone());
+        let SteepestDescent {
+            linesearch: ls,
+            modifiers: (),
+        } = SteepestDescent::new(linesearch.clone());
         assert_eq!(ls, linesearch);
     }
 
@@ -217,4 +363,268 @@ mod tests {
         );
         assert_relative_eq!(state.cost, 1.048820000000001, epsilon = f64::EPSILON);
     }
+
+    #[test]
+    fn test_non_serializable_param() {
+        // Neither `Serialize` nor `Deserialize` are required, even with the `serde1` feature.
+        #[derive(Clone, Debug)]
+        struct Angle(f64);
+
+        impl ArgminScaledAddAssign<f64, f64> for Angle {
+            fn scaled_add_assign(&mut self, factor: &f64, vec: &f64) {
+                self.0 += factor * vec;
+            }
+        }
+
+        struct AngleProblem {}
+
+        impl CostFunction for AngleProblem {
+            type Param = Angle;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok((p.0 - 1.0).powi(2))
+            }
+        }
+
+        impl Gradient for AngleProblem {
+            type Param = Angle;
+            type Gradient = f64;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(2.0 * (p.0 - 1.0))
+            }
+        }
+
+        let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let res = Executor::new(AngleProblem {}, SteepestDescent::new(linesearch))
+            .configure(|state| state.param(Angle(0.0)).max_iters(20))
+            .run()
+            .unwrap();
+        assert_relative_eq!(res.state.best_param.unwrap().0, 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_func_counts_include_line_search() {
+        struct SDProblem {}
+
+        impl CostFunction for SDProblem {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p[0].powi(2) + p[1].powi(2))
+            }
+        }
+
+        impl Gradient for SDProblem {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Param, Error> {
+                Ok(vec![2.0 * p[0], 2.0 * p[1]])
+            }
+        }
+
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let result = Executor::new(SDProblem {}, SteepestDescent::new(linesearch))
+            .configure(|state| state.param(vec![1.0, 2.0]).max_iters(3))
+            .run()
+            .unwrap();
+
+        let counts = result.func_counts();
+        assert_eq!(counts["gradient_count"], 3);
+        // All cost function evaluations are performed by the line search
+        assert_eq!(counts["cost_count"], 15);
+    }
+
+    #[test]
+    fn test_gradient_clipping_and_weight_decay() {
+        struct SDProblem {}
+
+        impl CostFunction for SDProblem {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok((p[0] - 10.0).powi(2) + (p[1] - 10.0).powi(2))
+            }
+        }
+
+        impl Gradient for SDProblem {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Param, Error> {
+                Ok(vec![2.0 * (p[0] - 10.0), 2.0 * (p[1] - 10.0)])
+            }
+        }
+
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let mut sd = SteepestDescent::new(linesearch)
+            .with_gradient_clipping_value(5.0)
+            .unwrap()
+            .with_gradient_clipping_norm(1.0)
+            .unwrap()
+            .with_weight_decay(0.1)
+            .unwrap();
+        let mut problem = Problem::new(SDProblem {});
+        let (state, kv) = sd
+            .next_iter(&mut problem, IterState::new().param(vec![0.0, 0.0]))
+            .unwrap();
+
+        let kv = kv.unwrap();
+        assert_relative_eq!(
+            kv.get("gradient_norm").unwrap().get_float().unwrap(),
+            20.0 * 2.0f64.sqrt(),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            kv.get("clipped_gradient_norm")
+                .unwrap()
+                .get_float()
+                .unwrap(),
+            1.0,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            kv.get("weight_decay_norm").unwrap().get_float().unwrap(),
+            0.0,
+            epsilon = f64::EPSILON
+        );
+        // Cost and gradient correspond to the final parameter vector
+        let param = state.get_param().unwrap();
+        assert_relative_eq!(
+            state.get_cost(),
+            problem.problem.as_ref().unwrap().cost(param).unwrap(),
+            epsilon = f64::EPSILON
+        );
+        assert_eq!(
+            state.get_gradient().unwrap(),
+            &problem.problem.as_ref().unwrap().gradient(param).unwrap()
+        );
+
+        // The decay pulls the solution towards the origin
+        let res = Executor::new(SDProblem {}, sd)
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(200))
+            .run()
+            .unwrap();
+        let best_param = res.state.get_best_param().unwrap();
+        assert!(best_param[0] < 10.0 && best_param[0] > 5.0);
+        assert_relative_eq!(best_param[0], best_param[1], epsilon = 1e-8);
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn test_tracing_spans() {
+        use crate::core::checkpointing::{Checkpoint, CheckpointingFrequency};
+        use std::sync::{Arc, Mutex};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        // Records the names of all created spans
+        struct SpanNames(Arc<Mutex<Vec<&'static str>>>);
+
+        impl tracing::Subscriber for SpanNames {
+            fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut names = self.0.lock().unwrap();
+                names.push(span.metadata().name());
+                Id::from_u64(names.len() as u64)
+            }
+
+            fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+            fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+            fn event(&self, _event: &Event<'_>) {}
+
+            fn enter(&self, _span: &Id) {}
+
+            fn exit(&self, _span: &Id) {}
+        }
+
+        struct NoCheckpoint {}
+
+        impl<S, I> Checkpoint<S, I> for NoCheckpoint {
+            fn save(&self, _solver: &S, _state: &I) -> Result<(), Error> {
+                Ok(())
+            }
+
+            fn load(&self) -> Result<Option<(S, I)>, Error> {
+                Ok(None)
+            }
+
+            fn frequency(&self) -> CheckpointingFrequency {
+                CheckpointingFrequency::Every(2)
+            }
+        }
+
+        let names = Arc::new(Mutex::new(vec![]));
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        tracing::subscriber::with_default(SpanNames(Arc::clone(&names)), || {
+            Executor::new(TestProblem::new(), SteepestDescent::new(linesearch))
+                .configure(|state| state.param(vec![1.0, 2.0]).max_iters(3))
+                .checkpointing(NoCheckpoint {})
+                .run()
+                .unwrap();
+        });
+
+        let names = names.lock().unwrap();
+        let count = |name| names.iter().filter(|n| **n == name).count();
+        // Each line search is a nested run
+        assert_eq!(count("run"), 4);
+        assert_eq!(count("line_search"), 3);
+        assert!(count("iteration") > 3);
+        assert!(count("operator") > 3);
+        assert_eq!(count("checkpoint"), 1);
+    }
+
+    #[test]
+    fn test_complex() {
+        use num_complex::Complex;
+
+        // f(z) = |z_0 - (1 + 2i)|^2 + 3 * |z_1 + i|^2 with the gradient df/dRe(z) + i df/dIm(z)
+        struct ComplexProblem {}
+
+        impl CostFunction for ComplexProblem {
+            type Param = Vec<Complex<f64>>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok((p[0] - Complex::new(1.0, 2.0)).norm_sqr()
+                    + 3.0 * (p[1] + Complex::i()).norm_sqr())
+            }
+        }
+
+        impl Gradient for ComplexProblem {
+            type Param = Vec<Complex<f64>>;
+            type Gradient = Vec<Complex<f64>>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![
+                    2.0 * (p[0] - Complex::new(1.0, 2.0)),
+                    6.0 * (p[1] + Complex::i()),
+                ])
+            }
+        }
+
+        let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let res = Executor::new(ComplexProblem {}, SteepestDescent::new(linesearch))
+            .configure(|state| state.param(vec![Complex::new(0.0, 0.0); 2]).max_iters(50))
+            .run()
+            .unwrap();
+
+        let best_param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(best_param[0].re, 1.0, epsilon = 1e-6);
+        assert_relative_eq!(best_param[0].im, 2.0, epsilon = 1e-6);
+        assert_relative_eq!(best_param[1].re, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(best_param[1].im, -1.0, epsilon = 1e-6);
+    }
 }
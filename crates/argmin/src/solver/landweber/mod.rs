@@ -17,8 +17,9 @@
 //!
 //! <https://en.wikipedia.org/wiki/Landweber_iteration>
 
-use crate::core::{ArgminFloat, Error, Gradient, IterState, Problem, Solver, KV};
-use argmin_math::ArgminScaledSub;
+use crate::core::{ArgminFloat, Error, Gradient, IterState, Problem, Solver, State, KV};
+use crate::solver::gradientdescent::{GradientModifier, GradientModifiers};
+use argmin_math::{ArgminL2Norm, ArgminMinMax, ArgminScaledSub, ArgminSub, ArgminZeroLike};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -31,6 +32,20 @@ use serde::{Deserialize, Serialize};
 ///
 /// `x_{k+1} = x_k - omega * \nabla f(x_k)`
 ///
+/// The iteration converges for `0 < omega < 2/L`, where `L` is the Lipschitz constant of the
+/// gradient (for `f(x) = 1/2 ||Ax - b||^2` this is the largest eigenvalue of `A^T A`). Since `L`
+/// is rarely known in practice, it can be estimated via power iteration during initialization
+/// (see [`with_estimated_step`](`Landweber::with_estimated_step`)), in which case `omega` is
+/// interpreted relative to `1/L`.
+///
+/// Optionally, each iterate can be projected onto the nonnegative orthant (see
+/// [`with_nonnegativity`](`Landweber::with_nonnegativity`)), which is a common constraint in
+/// inverse problems such as image reconstruction.
+///
+/// Gradients can be clipped and decoupled weight decay can be applied after each step (see
+/// [`GradientModifiers`] and the corresponding methods, such as
+/// [`with_weight_decay`](`Landweber::with_weight_decay`)). The projection is applied last.
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`Gradient`].
@@ -43,9 +58,17 @@ use serde::{Deserialize, Serialize};
 /// <https://en.wikipedia.org/wiki/Landweber_iteration>
 #[derive(Clone, Copy)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct Landweber<F> {
+pub struct Landweber<F, M = ()> {
     /// omega
     omega: F,
+    /// Step length actually used in the iterations
+    step: F,
+    /// Number of power iterations for estimating the Lipschitz constant (0: no estimation)
+    power_iters: u64,
+    /// Project iterates onto the nonnegative orthant
+    nonnegative: bool,
+    /// weight decay and gradient clipping
+    modifiers: M,
 }
 
 impl<F> Landweber<F> {
@@ -58,21 +81,215 @@ impl<F> Landweber<F> {
     /// let omega: f64 = 0.5;
     /// let landweber = Landweber::new(omega);
     /// ```
-    pub fn new(omega: F) -> Self {
-        Landweber { omega }
+    pub fn new(omega: F) -> Self
+    where
+        F: Copy,
+    {
+        Landweber {
+            omega,
+            step: omega,
+            power_iters: 0,
+            nonnegative: false,
+            modifiers: (),
+        }
+    }
+}
+
+impl<F: ArgminFloat, M> Landweber<F, M> {
+    /// Estimate the Lipschitz constant `L` of the gradient during initialization
+    ///
+    /// `L` is estimated by `power_iters` steps of power iteration on `v -> \nabla f(x_0 + v) -
+    /// \nabla f(x_0)`, starting from the gradient at the initial parameter vector `x_0`. For linear
+    /// problems this is exactly the operator `A^T A`. Each power iteration requires one gradient
+    /// evaluation. The step length is then set to `omega / L`, therefore `omega` must be in
+    /// `(0, 2)`; `1.0` is a good choice. `power_iters` must be larger than 0.
+    ///
+    /// If the gradient at `x_0` vanishes, no estimate is available and `omega` is used as is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::landweber::Landweber;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let landweber = Landweber::new(1.0f64).with_estimated_step(20)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_estimated_step(mut self, power_iters: u64) -> Result<Self, Error> {
+        if self.omega <= float!(0.0) || self.omega >= float!(2.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Landweber`: omega must be in (0, 2) when the step is estimated."
+            ));
+        }
+        if power_iters == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Landweber`: Number of power iterations must be larger than 0."
+            ));
+        }
+        self.power_iters = power_iters;
+        Ok(self)
+    }
+
+    /// Project each iterate onto the nonnegative orthant
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::landweber::Landweber;
+    /// let landweber = Landweber::new(0.5f64).with_nonnegativity(true);
+    /// ```
+    #[must_use]
+    pub fn with_nonnegativity(mut self, nonnegative: bool) -> Self {
+        self.nonnegative = nonnegative;
+        self
+    }
+
+    /// Set decoupled weight decay factor
+    ///
+    /// After each step, the parameter vector is reduced by `weight_decay` times the parameter
+    /// vector at the beginning of the iteration. Must be in `[0, 1)` and defaults to `0` (no weight
+    /// decay).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::landweber::Landweber;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let landweber = Landweber::new(0.5f64).with_weight_decay(0.01)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_weight_decay(
+        self,
+        weight_decay: F,
+    ) -> Result<Landweber<F, GradientModifiers<F>>, Error>
+    where
+        M: Into<GradientModifiers<F>>,
+    {
+        self.map_modifiers(|modifiers| modifiers.into().with_weight_decay(weight_decay))
+    }
+
+    /// Clip the gradient by norm
+    ///
+    /// Gradients with a norm larger than `max_norm` are rescaled to norm `max_norm`. Must be
+    /// larger than 0. By default, gradients are not clipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::landweber::Landweber;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let landweber = Landweber::new(0.5f64).with_gradient_clipping_norm(10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_gradient_clipping_norm(
+        self,
+        max_norm: F,
+    ) -> Result<Landweber<F, GradientModifiers<F>>, Error>
+    where
+        M: Into<GradientModifiers<F>>,
+    {
+        self.map_modifiers(|modifiers| modifiers.into().with_clip_norm(max_norm))
+    }
+
+    /// Clip the gradient by value
+    ///
+    /// Each element of the gradient is clipped to `[-max_value, max_value]`. This is applied before
+    /// clipping by norm. Must be larger than 0. By default, gradients are not clipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::landweber::Landweber;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let landweber = Landweber::new(0.5f64).with_gradient_clipping_value(1.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_gradient_clipping_value(
+        self,
+        max_value: F,
+    ) -> Result<Landweber<F, GradientModifiers<F>>, Error>
+    where
+        M: Into<GradientModifiers<F>>,
+    {
+        self.map_modifiers(|modifiers| modifiers.into().with_clip_value(max_value))
+    }
+
+    /// Replaces the modifiers
+    fn map_modifiers<M2>(
+        self,
+        f: impl FnOnce(M) -> Result<M2, Error>,
+    ) -> Result<Landweber<F, M2>, Error> {
+        Ok(Landweber {
+            omega: self.omega,
+            step: self.step,
+            power_iters: self.power_iters,
+            nonnegative: self.nonnegative,
+            modifiers: f(self.modifiers)?,
+        })
     }
 }
 
-impl<O, F, P, G> Solver<O, IterState<P, G, (), (), (), F>> for Landweber<F>
+impl<O, F, M, P, G> Solver<O, IterState<P, G, (), (), (), F>> for Landweber<F, M>
 where
     O: Gradient<Param = P, Gradient = G>,
-    P: Clone + ArgminScaledSub<G, F, P>,
+    P: Clone + ArgminScaledSub<G, F, P> + ArgminMinMax + ArgminZeroLike,
+    G: Clone + ArgminSub<G, G> + ArgminL2Norm<F>,
+    M: GradientModifier<P, G>,
     F: ArgminFloat,
 {
     fn name(&self) -> &str {
         "Landweber"
     }
 
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        self.step = self.omega;
+        if self.power_iters == 0 {
+            return Ok((state, None));
+        }
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`Landweber` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let grad = problem.gradient(param)?;
+        let mut v = grad.clone();
+        let mut lipschitz = float!(0.0);
+        for _ in 0..self.power_iters {
+            let norm = v.l2_norm();
+            if norm <= float!(0.0) || !norm.is_finite() {
+                break;
+            }
+            // x_0 + v/||v||
+            let shifted = param.scaled_sub(&(float!(-1.0) / norm), &v);
+            v = problem.gradient(&shifted)?.sub(&grad);
+            lipschitz = v.l2_norm();
+        }
+        if lipschitz > float!(0.0) && lipschitz.is_finite() {
+            self.step = self.omega / lipschitz;
+        }
+        Ok((
+            state,
+            Some(kv!("lipschitz" => lipschitz; "omega" => self.step;)),
+        ))
+    }
+
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
@@ -85,9 +302,21 @@ where
                 "Please provide an initial guess via `Executor`s `configure` method."
             )
         ))?;
-        let grad = problem.gradient(&param)?;
-        let new_param = param.scaled_sub(&self.omega, &grad);
-        Ok((state.param(new_param), None))
+        let mut kv = KV::new();
+        let grad = self
+            .modifiers
+            .clip_gradient(problem.gradient(&param)?, &mut kv);
+        let new_param = param.scaled_sub(&self.step, &grad);
+        let mut new_param = self
+            .modifiers
+            .apply_weight_decay(new_param, &param, &mut kv);
+        if self.nonnegative {
+            new_param = P::max(&new_param, &new_param.zero_like());
+        }
+        Ok((
+            state.param(new_param),
+            (!kv.keys().is_empty()).then_some(kv),
+        ))
     }
 }
 
@@ -102,8 +331,105 @@ mod tests {
     #[test]
     fn test_new() {
         let omega_in: f64 = 0.5;
-        let Landweber { omega } = Landweber::new(omega_in);
+        let Landweber {
+            omega,
+            step,
+            power_iters,
+            nonnegative,
+            modifiers: (),
+        } = Landweber::new(omega_in);
         assert_eq!(omega.to_ne_bytes(), omega_in.to_ne_bytes());
+        assert_eq!(step.to_ne_bytes(), omega_in.to_ne_bytes());
+        assert_eq!(power_iters, 0);
+        assert!(!nonnegative);
+    }
+
+    #[test]
+    fn test_with_estimated_step() {
+        let landweber = Landweber::new(1.0f64).with_estimated_step(10).unwrap();
+        assert_eq!(landweber.power_iters, 10);
+
+        for (omega, iters) in [(0.0f64, 10), (2.0, 10), (-1.0, 10), (1.0, 0)] {
+            let res = Landweber::new(omega).with_estimated_step(iters);
+            assert!(res.is_err());
+        }
+    }
+
+    #[test]
+    fn test_init_estimated_step() {
+        struct Linear;
+
+        impl Gradient for Linear {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            // A^T A = diag(4, 1), b = 0
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![4.0 * p[0], p[1]])
+            }
+        }
+
+        let mut landweber = Landweber::new(1.0f64).with_estimated_step(50).unwrap();
+        let mut problem = Problem::new(Linear);
+        let state = IterState::new().param(vec![1.0, 1.0]);
+        let (state, kv) = landweber.init(&mut problem, state).unwrap();
+        let kv = kv.unwrap();
+        assert_relative_eq!(
+            kv.get("lipschitz").unwrap().get_float().unwrap(),
+            4.0,
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(landweber.step, 0.25, epsilon = 1e-8);
+        assert_eq!(problem.counts["gradient_count"], 51);
+        assert_eq!(state.get_param().unwrap(), &vec![1.0, 1.0]);
+
+        // Vanishing gradient: omega is used as is
+        let mut landweber = Landweber::new(1.0f64).with_estimated_step(50).unwrap();
+        let state = IterState::new().param(vec![0.0, 0.0]);
+        landweber.init(&mut problem, state).unwrap();
+        assert_eq!(landweber.step.to_ne_bytes(), 1.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_next_iter_modifiers() {
+        let mut landweber = Landweber::new(0.5f64)
+            .with_gradient_clipping_norm(1.0)
+            .unwrap()
+            .with_weight_decay(0.1)
+            .unwrap();
+        let state = IterState::new().param(vec![3.0, 4.0]);
+        let (state, kv) = landweber
+            .next_iter(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+        // x - 0.5 * x / ||x|| - 0.1 * x
+        let new_param = state.get_param().unwrap();
+        assert_relative_eq!(new_param[0], 2.4, epsilon = 1e-12);
+        assert_relative_eq!(new_param[1], 3.2, epsilon = 1e-12);
+        let kv = kv.unwrap();
+        assert_relative_eq!(
+            kv.get("gradient_norm").unwrap().get_float().unwrap(),
+            5.0,
+            epsilon = f64::EPSILON
+        );
+        assert_relative_eq!(
+            kv.get("weight_decay_norm").unwrap().get_float().unwrap(),
+            0.5,
+            epsilon = f64::EPSILON
+        );
+
+        assert!(Landweber::new(0.5f64).with_weight_decay(1.5).is_err());
+    }
+
+    #[test]
+    fn test_next_iter_nonnegative() {
+        let mut landweber = Landweber::new(2.0f64).with_nonnegativity(true);
+        let state = IterState::new().param(vec![2.0, -4.0]);
+        let (state, _) = landweber
+            .next_iter(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+        let new_param = state.get_param().unwrap();
+        assert_relative_eq!(new_param[0], 0.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(new_param[1], 4.0, epsilon = f64::EPSILON);
     }
 
     #[test]
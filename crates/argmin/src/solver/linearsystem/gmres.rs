@@ -0,0 +1,519 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, Solver, TerminationReason, TerminationStatus,
+    KV,
+};
+use crate::solver::linearsystem::{IdentityPreconditioner, Preconditioner};
+use argmin_math::{
+    ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminScaledSub, ArgminSub,
+    ArgminZeroLike,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Restarted generalized minimal residual method (GMRES(m))
+///
+/// Solves linear systems `A x = b` with a general square matrix `A`.
+///
+/// Starting from the current residual `r = b - A x`, GMRES builds an orthonormal basis of the
+/// Krylov subspace `span{r, A r, A^2 r, ...}` via the Arnoldi process and computes the correction
+/// from this subspace which minimizes the norm of the residual. Since memory and work per step grow
+/// with the dimension of the subspace, the method is restarted after `m` steps (30 by default, see
+/// [`with_restart`](`GMRES::with_restart`)). One iteration of the solver corresponds to one cycle
+/// of up to `m` steps, each of which requires one product of `A` with a vector. Convergence is not
+/// guaranteed for too small `m`.
+///
+/// A [`Preconditioner`] `M` is applied from the right, i.e. GMRES is applied to
+/// `A M^{-1} u = b` with `x = M^{-1} u`, such that the minimized residual is the residual of the
+/// original system.
+///
+/// The cost function value is the norm of the residual `||b - A x||`, which is recomputed at the
+/// end of each cycle; the residual is stored in the state. The algorithm stops when
+/// `||b - A x|| <= tol * ||b||` (`tol` defaults to `sqrt(EPSILON)`, see
+/// [`with_tolerance`](`GMRES::with_tolerance`)).
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`], which returns the product
+/// `A x`.
+///
+/// ## Reference
+///
+/// Saad, Y., Schultz, M. H. (1986): GMRES: A generalized minimal residual algorithm for solving
+/// nonsymmetric linear systems. SIAM J. Sci. Stat. Comput. 7, 856–869.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GMRES<P, M, F> {
+    /// b (right hand side of `A * x = b`)
+    b: P,
+    /// Norm of `b`
+    b_norm: F,
+    /// Number of steps after which the method is restarted
+    restart: usize,
+    /// Preconditioner
+    preconditioner: M,
+    /// Tolerance on the relative residual
+    tol: F,
+}
+
+impl<P, F> GMRES<P, IdentityPreconditioner, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`GMRES`]
+    ///
+    /// Takes `b`, the right hand side of `A * x = b` as input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::GMRES;
+    /// # let b = vec![1.0f64, 1.0];
+    /// let gmres: GMRES<_, _, f64> = GMRES::new(b);
+    /// ```
+    pub fn new(b: P) -> Self {
+        GMRES {
+            b,
+            b_norm: F::nan(),
+            restart: 30,
+            preconditioner: IdentityPreconditioner,
+            tol: F::epsilon().sqrt(),
+        }
+    }
+}
+
+impl<P, M, F> GMRES<P, M, F>
+where
+    F: ArgminFloat,
+{
+    /// Set the number of steps after which the method is restarted
+    ///
+    /// Must be larger than 0 and defaults to 30.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::GMRES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let b = vec![1.0f64, 1.0];
+    /// let gmres: GMRES<_, _, f64> = GMRES::new(b).with_restart(50)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_restart(mut self, restart: usize) -> Result<Self, Error> {
+        if restart == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GMRES`: restart must be > 0."
+            ));
+        }
+        self.restart = restart;
+        Ok(self)
+    }
+
+    /// The algorithm stops if `||b - A x|| <= tol * ||b||`.
+    ///
+    /// Must be non-negative and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::GMRES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let b = vec![1.0f64, 1.0];
+    /// let gmres: GMRES<_, _, f64> = GMRES::new(b).with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GMRES`: tolerance must be >= 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set the preconditioner
+    ///
+    /// Defaults to [`IdentityPreconditioner`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::{DiagonalPreconditioner, GMRES};
+    /// # let b = vec![1.0f64, 1.0];
+    /// let preconditioner = DiagonalPreconditioner::new(vec![4.0f64, 2.0]);
+    /// let gmres: GMRES<_, _, f64> = GMRES::new(b).with_preconditioner(preconditioner);
+    /// ```
+    pub fn with_preconditioner<M2>(self, preconditioner: M2) -> GMRES<P, M2, F> {
+        GMRES {
+            b: self.b,
+            b_norm: self.b_norm,
+            restart: self.restart,
+            preconditioner,
+            tol: self.tol,
+        }
+    }
+}
+
+/// Performs one cycle of right-preconditioned GMRES for `A d = r`, starting from `d = 0`, where
+/// `A` and `M^{-1}` are only available via `apply(v) = A v` and `precondition(v) = M^{-1} v`.
+///
+/// Stops once the residual norm is at most `tol` or after `max_iters` steps. Returns the
+/// correction `d`, the number of steps and the norm of the residual `||r - A d||`.
+pub(crate) fn gmres_cycle<P, F, A, M>(
+    mut apply: A,
+    mut precondition: M,
+    r: &P,
+    tol: F,
+    max_iters: usize,
+) -> Result<(P, usize, F), Error>
+where
+    P: Clone
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminL2Norm<F>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+    A: FnMut(&P) -> Result<P, Error>,
+    M: FnMut(&P) -> Result<P, Error>,
+{
+    let beta = r.l2_norm();
+    if beta <= tol {
+        return Ok((r.zero_like(), 0, beta));
+    }
+
+    // Orthonormal basis of the Krylov subspace
+    let mut basis = vec![r.mul(&(float!(1.0) / beta))];
+    // Columns of the Hessenberg matrix, transformed to upper triangular form by Givens rotations
+    let mut hessenberg: Vec<Vec<F>> = Vec::with_capacity(max_iters);
+    let mut rotations: Vec<(F, F)> = Vec::with_capacity(max_iters);
+    // Right hand side of the least squares problem, transformed by the Givens rotations
+    let mut g = vec![beta];
+    let mut residual_norm = beta;
+
+    for j in 0..max_iters {
+        // Arnoldi step with modified Gram-Schmidt orthogonalization
+        let mut w = apply(&precondition(&basis[j])?)?;
+        let mut h = Vec::with_capacity(j + 2);
+        for v in basis.iter() {
+            let hij = w.dot(v);
+            w = w.scaled_sub(&hij, v);
+            h.push(hij);
+        }
+        let h_next = w.l2_norm();
+        h.push(h_next);
+
+        for (i, &(c, s)) in rotations.iter().enumerate() {
+            let (a, b) = (h[i], h[i + 1]);
+            h[i] = c * a + s * b;
+            h[i + 1] = c * b - s * a;
+        }
+        let denom = h[j].hypot(h[j + 1]);
+        let (c, s) = if denom > float!(0.0) {
+            (h[j] / denom, h[j + 1] / denom)
+        } else {
+            (float!(1.0), float!(0.0))
+        };
+        h[j] = denom;
+        h[j + 1] = float!(0.0);
+        rotations.push((c, s));
+        g.push(-s * g[j]);
+        g[j] = c * g[j];
+        hessenberg.push(h);
+
+        residual_norm = g[j + 1].abs();
+        // A vanishing `h_next` means that the Krylov subspace is invariant under `A` and the
+        // solution lies within it (lucky breakdown).
+        if residual_norm <= tol || h_next <= F::epsilon() * beta {
+            break;
+        }
+        basis.push(w.mul(&(float!(1.0) / h_next)));
+    }
+
+    // Back substitution
+    let k = hessenberg.len();
+    let mut y = vec![float!(0.0); k];
+    for i in (0..k).rev() {
+        let mut sum = g[i];
+        for (l, yl) in y.iter().enumerate().skip(i + 1) {
+            sum = sum - hessenberg[l][i] * *yl;
+        }
+        y[i] = if hessenberg[i][i] != float!(0.0) {
+            sum / hessenberg[i][i]
+        } else {
+            float!(0.0)
+        };
+    }
+    let u = basis
+        .iter()
+        .zip(y.iter())
+        .fold(r.zero_like(), |u, (v, yi)| u.scaled_add(yi, v));
+    Ok((precondition(&u)?, k, residual_norm))
+}
+
+impl<O, P, M, F> Solver<O, IterState<P, (), (), (), P, F>> for GMRES<P, M, F>
+where
+    O: Operator<Param = P, Output = P>,
+    P: Clone
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminL2Norm<F>
+        + ArgminZeroLike,
+    M: Preconditioner<P>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "GMRES"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), P, F>,
+    ) -> Result<(IterState<P, (), (), (), P, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`GMRES` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let residuals = self.b.sub(&problem.apply(&param)?);
+        let cost = residuals.l2_norm();
+        self.b_norm = self.b.l2_norm();
+        Ok((state.param(param).residuals(residuals).cost(cost), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), P, F>,
+    ) -> Result<(IterState<P, (), (), (), P, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`GMRES`: `param` not set"
+        ))?;
+        let residuals = state.take_residuals().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`GMRES`: `residuals` not set"
+        ))?;
+
+        let preconditioner = &self.preconditioner;
+        let (correction, inner_iters, _) = gmres_cycle(
+            |v: &P| problem.apply(v),
+            |v: &P| preconditioner.precondition(v),
+            &residuals,
+            self.tol * self.b_norm,
+            self.restart,
+        )?;
+
+        let new_param = param.add(&correction);
+        // The residual is recomputed to avoid the accumulation of rounding errors across restarts
+        let residuals = self.b.sub(&problem.apply(&new_param)?);
+        let cost = residuals.l2_norm();
+
+        Ok((
+            state.param(new_param).residuals(residuals).cost(cost),
+            Some(kv!("inner_iters" => inner_iters as u64;)),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, (), (), (), P, F>) -> TerminationStatus {
+        if state.get_cost() <= self.tol * self.b_norm {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::solver::linearsystem::DiagonalPreconditioner;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(gmres, GMRES<Vec<f64>, IdentityPreconditioner, f64>);
+
+    /// Product with a dense matrix
+    struct Matrix(Vec<Vec<f64>>);
+
+    impl Operator for Matrix {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self
+                .0
+                .iter()
+                .map(|row| row.iter().zip(x.iter()).map(|(a, x)| a * x).sum())
+                .collect())
+        }
+    }
+
+    /// Nonsymmetric, diagonally dominant tridiagonal matrix of dimension `n`
+    fn convection_diffusion(n: usize) -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| match j as isize - i as isize {
+                        0 => 4.0 + i as f64,
+                        -1 => -1.5,
+                        1 => -0.5,
+                        _ => 0.0,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let GMRES {
+            b,
+            b_norm,
+            restart,
+            preconditioner: _,
+            tol,
+        }: GMRES<_, _, f64> = GMRES::new(vec![1.0f64, 2.0]);
+        assert_eq!(b, vec![1.0, 2.0]);
+        assert!(b_norm.is_nan());
+        assert_eq!(restart, 30);
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_restart() {
+        let GMRES { restart, .. }: GMRES<_, _, f64> =
+            GMRES::new(vec![1.0f64]).with_restart(5).unwrap();
+        assert_eq!(restart, 5);
+
+        let res: Result<GMRES<_, _, f64>, _> = GMRES::new(vec![1.0f64]).with_restart(0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`GMRES`: restart must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let GMRES { tol, .. }: GMRES<_, _, f64> =
+            GMRES::new(vec![1.0f64]).with_tolerance(1e-10).unwrap();
+        assert_eq!(tol.to_ne_bytes(), 1e-10f64.to_ne_bytes());
+
+        let res: Result<GMRES<_, _, f64>, _> = GMRES::new(vec![1.0f64]).with_tolerance(-1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`GMRES`: tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_gmres_cycle() {
+        let a = Matrix(vec![
+            vec![4.0, 1.0, 0.0],
+            vec![-2.0, 3.0, 1.0],
+            vec![0.0, 1.0, 2.0],
+        ]);
+        let b = vec![1.0, 2.0, 3.0];
+        let (x, iters, residual) =
+            gmres_cycle(|v| a.apply(v), |v: &Vec<f64>| Ok(v.clone()), &b, 1e-12, 10).unwrap();
+        assert!(iters <= 3);
+        assert!(residual <= 1e-12);
+        for (ax, bi) in a.apply(&x).unwrap().iter().zip(b.iter()) {
+            assert_relative_eq!(ax, bi, epsilon = 1e-10);
+        }
+
+        // Zero right hand side
+        let (x, iters, _) = gmres_cycle(
+            |v: &Vec<f64>| Ok(v.clone()),
+            |v: &Vec<f64>| Ok(v.clone()),
+            &vec![0.0, 0.0],
+            1e-12,
+            10,
+        )
+        .unwrap();
+        assert_eq!(x, vec![0.0, 0.0]);
+        assert_eq!(iters, 0);
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut gmres: GMRES<_, _, f64> = GMRES::new(vec![1.0f64, 2.0]);
+        let res = gmres.init(
+            &mut Problem::new(Matrix(vec![vec![1.0, 0.0], vec![0.0, 1.0]])),
+            IterState::new(),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`GMRES` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let n = 40;
+        let a = convection_diffusion(n);
+        let x_true: Vec<f64> = (0..n).map(|i| (i as f64).sin()).collect();
+        let b = Matrix(a.clone()).apply(&x_true).unwrap();
+        let diag: Vec<f64> = (0..n).map(|i| a[i][i]).collect();
+
+        // Restarted, unpreconditioned and preconditioned
+        for preconditioned in [false, true] {
+            let gmres: GMRES<_, _, f64> = GMRES::new(b.clone())
+                .with_restart(5)
+                .unwrap()
+                .with_tolerance(1e-12)
+                .unwrap();
+            let init =
+                |state: IterState<_, _, _, _, _, _>| state.param(vec![0.0; n]).max_iters(100);
+            let res = if preconditioned {
+                let gmres = gmres.with_preconditioner(DiagonalPreconditioner::new(diag.clone()));
+                Executor::new(Matrix(a.clone()), gmres)
+                    .configure(init)
+                    .run()
+                    .unwrap()
+                    .state
+            } else {
+                Executor::new(Matrix(a.clone()), gmres)
+                    .configure(init)
+                    .run()
+                    .unwrap()
+                    .state
+            };
+            assert_eq!(
+                res.get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            for (x, xt) in res.get_best_param().unwrap().iter().zip(x_true.iter()) {
+                assert_relative_eq!(x, xt, epsilon = 1e-10);
+            }
+        }
+    }
+}
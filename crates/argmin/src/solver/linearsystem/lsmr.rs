@@ -0,0 +1,533 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, Solver, State, TerminationReason,
+    TerminationStatus, KV,
+};
+use crate::solver::linearsystem::lsqr::{least_squares_converged, normalize, rotation};
+use crate::solver::linearsystem::AdjointOperator;
+use argmin_math::{
+    ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminScaledSub, ArgminSub, ArgminZeroLike,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # LSMR
+///
+/// Solves linear least squares problems `min ||A x - b||^2 + damping^2 ||x - x_0||^2` with a
+/// general (possibly rectangular and rank-deficient) matrix `A`, where `x_0` is the initial
+/// parameter vector. For `damping = 0` (default, see [`with_damping`](`LSMR::with_damping`)), this
+/// is the ordinary least squares problem; damped problems arise for instance as subproblems of
+/// the Levenberg-Marquardt method.
+///
+/// Like [`LSQR`](`super::LSQR`), LSMR is based on the Golub-Kahan bidiagonalization of `A` and
+/// requires one product with `A` and one with `A^T` per iteration. It is analytically equivalent
+/// to MINRES applied to the normal equations, such that the norm of the residual of the normal
+/// equations `||A^T r||` decreases monotonically. This makes it safer to stop early than LSQR,
+/// which is relevant when the least squares problem is a subproblem which only needs to be
+/// solved approximately.
+///
+/// The cost function value is an estimate of the norm of the residual
+/// `||(b - A x, damping (x_0 - x))||`, which is computed by the recurrences. The algorithm stops
+/// when either
+///
+/// * `||r|| <= tol * (||b|| + ||A|| ||x||)` (the system is compatible), or
+/// * `||A^T r|| <= tol * ||A|| ||r||` (`x` solves the least squares problem),
+///
+/// where `||A||` is an estimate of the Frobenius norm of `A` and `tol` defaults to
+/// `sqrt(EPSILON)` (see [`with_tolerance`](`LSMR::with_tolerance`)).
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`], which returns `A v`, and
+/// [`AdjointOperator`], which returns `A^T u`. For matrix-free operators given by closures, see
+/// [`LinearOperator`](`super::LinearOperator`).
+///
+/// ## Reference
+///
+/// Fong, D. C.-L., Saunders, M. A. (2011): LSMR: An iterative algorithm for sparse
+/// least-squares problems. SIAM J. Sci. Comput. 33, 2950–2971.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LSMR<P, R, F> {
+    /// b (right hand side)
+    b: R,
+    /// Norm of `b`
+    b_norm: F,
+    /// Damping parameter
+    damping: F,
+    /// Tolerance
+    tol: F,
+    /// Left Lanczos vector
+    u: Option<R>,
+    /// Right Lanczos vector
+    v: Option<P>,
+    /// Search directions
+    h: Option<P>,
+    hbar: Option<P>,
+    /// Current `alpha` of the bidiagonalization
+    alpha: F,
+    /// Quantities of the rotations which transform the bidiagonal matrix
+    alphabar: F,
+    zeta: F,
+    zetabar: F,
+    rho: F,
+    rhobar: F,
+    cbar: F,
+    sbar: F,
+    /// Quantities of the estimate of the residual norm
+    betadd: F,
+    betad: F,
+    rhodold: F,
+    tautildeold: F,
+    thetatilde: F,
+    d: F,
+    /// Squared estimate of the Frobenius norm of `A`
+    a_norm2: F,
+    /// Estimate of the Frobenius norm of `A`
+    a_norm: F,
+}
+
+impl<P, R, F> LSMR<P, R, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`LSMR`]
+    ///
+    /// Takes `b`, the right hand side of the least squares problem, as input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::LSMR;
+    /// # let b = vec![1.0f64, 1.0, 1.0];
+    /// let lsmr: LSMR<Vec<f64>, _, f64> = LSMR::new(b);
+    /// ```
+    pub fn new(b: R) -> Self {
+        LSMR {
+            b,
+            b_norm: F::nan(),
+            damping: float!(0.0),
+            tol: F::epsilon().sqrt(),
+            u: None,
+            v: None,
+            h: None,
+            hbar: None,
+            alpha: F::nan(),
+            alphabar: F::nan(),
+            zeta: float!(0.0),
+            zetabar: F::nan(),
+            rho: float!(1.0),
+            rhobar: float!(1.0),
+            cbar: float!(1.0),
+            sbar: float!(0.0),
+            betadd: F::nan(),
+            betad: float!(0.0),
+            rhodold: float!(1.0),
+            tautildeold: float!(0.0),
+            thetatilde: float!(0.0),
+            d: float!(0.0),
+            a_norm2: float!(0.0),
+            a_norm: float!(0.0),
+        }
+    }
+
+    /// Set the damping parameter
+    ///
+    /// Must be finite and non-negative. Defaults to 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::LSMR;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let b = vec![1.0f64, 1.0, 1.0];
+    /// let lsmr: LSMR<Vec<f64>, _, f64> = LSMR::new(b).with_damping(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_damping(mut self, damping: F) -> Result<Self, Error> {
+        if damping < float!(0.0) || !damping.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LSMR`: damping must be finite and >= 0."
+            ));
+        }
+        self.damping = damping;
+        Ok(self)
+    }
+
+    /// Set the tolerance of the stopping criteria
+    ///
+    /// Must be non-negative and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::LSMR;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let b = vec![1.0f64, 1.0, 1.0];
+    /// let lsmr: LSMR<Vec<f64>, _, f64> = LSMR::new(b).with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LSMR`: tolerance must be >= 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+impl<O, P, R, F> Solver<O, IterState<P, (), (), (), (), F>> for LSMR<P, R, F>
+where
+    O: Operator<Param = P, Output = R> + AdjointOperator<Param = P, Output = R>,
+    P: Clone
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminL2Norm<F>
+        + ArgminZeroLike,
+    R: ArgminSub<R, R> + ArgminScaledSub<R, F, R> + ArgminMul<F, R> + ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "LSMR"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`LSMR` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        self.b_norm = self.b.l2_norm();
+        let u = self.b.sub(&problem.apply(&param)?);
+        let beta = u.l2_norm();
+        let u = normalize(u, beta);
+        let v = problem.apply_adjoint(&u)?;
+        let alpha = v.l2_norm();
+        let v = normalize(v, alpha);
+
+        self.alpha = alpha;
+        self.alphabar = alpha;
+        self.zeta = float!(0.0);
+        self.zetabar = alpha * beta;
+        self.rho = float!(1.0);
+        self.rhobar = float!(1.0);
+        self.cbar = float!(1.0);
+        self.sbar = float!(0.0);
+        self.betadd = beta;
+        self.betad = float!(0.0);
+        self.rhodold = float!(1.0);
+        self.tautildeold = float!(0.0);
+        self.thetatilde = float!(0.0);
+        self.d = float!(0.0);
+        self.a_norm2 = alpha.powi(2);
+        self.a_norm = float!(0.0);
+        self.hbar = Some(param.zero_like());
+        self.h = Some(v.clone());
+        self.v = Some(v);
+        self.u = Some(u);
+        Ok((state.param(param).cost(beta), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`LSMR`: `param` not set"
+        ))?;
+        let (Some(u), Some(v), Some(h), Some(hbar)) = (
+            self.u.take(),
+            self.v.take(),
+            self.h.take(),
+            self.hbar.take(),
+        ) else {
+            return Err(argmin_error!(
+                PotentialBug,
+                "`LSMR`: Lanczos vectors not set"
+            ));
+        };
+
+        // Golub-Kahan bidiagonalization
+        let u = problem.apply(&v)?.scaled_sub(&self.alpha, &u);
+        let beta = u.l2_norm();
+        let u = normalize(u, beta);
+        let v = problem.apply_adjoint(&u)?.scaled_sub(&beta, &v);
+        let alpha = v.l2_norm();
+        let v = normalize(v, alpha);
+
+        // Eliminate the damping parameter
+        let alphahat = self.alphabar.hypot(self.damping);
+        let (chat, shat) = rotation(self.alphabar, self.damping, alphahat);
+
+        // Transform the bidiagonal matrix to upper bidiagonal form
+        let rhoold = self.rho;
+        self.rho = alphahat.hypot(beta);
+        let (c, s) = rotation(alphahat, beta, self.rho);
+        let thetanew = s * alpha;
+        self.alphabar = c * alpha;
+
+        // Transform the transpose of the upper bidiagonal matrix to upper bidiagonal form
+        let rhobarold = self.rhobar;
+        let zetaold = self.zeta;
+        let thetabar = self.sbar * self.rho;
+        let rhotemp = self.cbar * self.rho;
+        self.rhobar = rhotemp.hypot(thetanew);
+        (self.cbar, self.sbar) = rotation(rhotemp, thetanew, self.rhobar);
+        self.zeta = self.cbar * self.zetabar;
+        self.zetabar = -self.sbar * self.zetabar;
+
+        // Update the search directions and the solution
+        let hbar = h.scaled_sub(&(thetabar * self.rho / (rhoold * rhobarold)), &hbar);
+        let new_param = param.scaled_add(&(self.zeta / (self.rho * self.rhobar)), &hbar);
+        let h = v.scaled_sub(&(thetanew / self.rho), &h);
+
+        // Estimate the norm of the residual
+        let betaacute = chat * self.betadd;
+        let betacheck = -shat * self.betadd;
+        let betahat = c * betaacute;
+        self.betadd = -s * betaacute;
+        let thetatildeold = self.thetatilde;
+        let rhotildeold = self.rhodold.hypot(thetabar);
+        let (ctildeold, stildeold) = rotation(self.rhodold, thetabar, rhotildeold);
+        self.thetatilde = stildeold * self.rhobar;
+        self.rhodold = ctildeold * self.rhobar;
+        self.betad = -stildeold * self.betad + ctildeold * betahat;
+        self.tautildeold = (zetaold - thetatildeold * self.tautildeold) / rhotildeold;
+        let taud = (self.zeta - self.thetatilde * self.tautildeold) / self.rhodold;
+        self.d = self.d + betacheck.powi(2);
+        let r_norm = (self.d + (self.betad - taud).powi(2) + self.betadd.powi(2)).sqrt();
+
+        // Estimate the norm of A
+        self.a_norm2 = self.a_norm2 + beta.powi(2);
+        self.a_norm = self.a_norm2.sqrt();
+        self.a_norm2 = self.a_norm2 + alpha.powi(2);
+
+        self.alpha = alpha;
+        self.u = Some(u);
+        self.v = Some(v);
+        self.h = Some(h);
+        self.hbar = Some(hbar);
+
+        Ok((
+            state.param(new_param).cost(r_norm),
+            Some(kv!(
+                "normal_residual_norm" => self.zetabar.abs();
+                "a_norm" => self.a_norm;
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+        let x_norm = state
+            .get_param()
+            .map(|param| param.l2_norm())
+            .unwrap_or(float!(0.0));
+        if least_squares_converged(
+            self.tol,
+            state.get_cost(),
+            self.zetabar.abs(),
+            self.a_norm,
+            self.b_norm,
+            x_norm,
+        ) {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::linearsystem::LinearOperator;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(lsmr, LSMR<Vec<f64>, Vec<f64>, f64>);
+
+    /// Dense operator of dimension `m x n` with well separated singular values
+    fn operator(
+        m: usize,
+        n: usize,
+    ) -> LinearOperator<
+        Vec<f64>,
+        Vec<f64>,
+        impl Fn(&Vec<f64>) -> Vec<f64>,
+        impl Fn(&Vec<f64>) -> Vec<f64>,
+    > {
+        let a: Vec<Vec<f64>> = (0..m)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        let diag = if i == j { 1.0 + j as f64 } else { 0.0 };
+                        diag + 0.1 * ((i * n + j) as f64).sin()
+                    })
+                    .collect()
+            })
+            .collect();
+        let at: Vec<Vec<f64>> = (0..n)
+            .map(|j| a.iter().map(|row| row[j]).collect())
+            .collect();
+        let dot = |m: &Vec<Vec<f64>>, x: &Vec<f64>| -> Vec<f64> {
+            m.iter()
+                .map(|row| row.iter().zip(x.iter()).map(|(a, x)| a * x).sum())
+                .collect()
+        };
+        LinearOperator::new(move |v| dot(&a, v), move |u| dot(&at, u))
+    }
+
+    /// `A^T (b - A x) - damping^2 x`, the residual of the damped normal equations
+    fn normal_residual<O>(op: &O, b: &[f64], x: &Vec<f64>, damping: f64) -> Vec<f64>
+    where
+        O: Operator<Param = Vec<f64>, Output = Vec<f64>>
+            + AdjointOperator<Param = Vec<f64>, Output = Vec<f64>>,
+    {
+        let r: Vec<f64> = op
+            .apply(x)
+            .unwrap()
+            .iter()
+            .zip(b.iter())
+            .map(|(ax, b)| b - ax)
+            .collect();
+        op.apply_adjoint(&r)
+            .unwrap()
+            .iter()
+            .zip(x.iter())
+            .map(|(atr, x)| atr - damping.powi(2) * x)
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let lsmr: LSMR<Vec<f64>, Vec<f64>, f64> = LSMR::new(vec![1.0, 2.0]);
+        assert_eq!(lsmr.b, vec![1.0, 2.0]);
+        assert_eq!(lsmr.damping.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(lsmr.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(lsmr.u.is_none());
+        assert!(lsmr.v.is_none());
+        assert!(lsmr.h.is_none());
+        assert!(lsmr.hbar.is_none());
+    }
+
+    #[test]
+    fn test_with_damping() {
+        let lsmr: LSMR<Vec<f64>, _, f64> = LSMR::new(vec![1.0]).with_damping(0.5).unwrap();
+        assert_eq!(lsmr.damping.to_ne_bytes(), 0.5f64.to_ne_bytes());
+
+        for damping in [-1.0, f64::INFINITY, f64::NAN] {
+            let res: Result<LSMR<Vec<f64>, _, f64>, _> = LSMR::new(vec![1.0]).with_damping(damping);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`LSMR`: damping must be finite and >= 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let lsmr: LSMR<Vec<f64>, _, f64> = LSMR::new(vec![1.0]).with_tolerance(1e-10).unwrap();
+        assert_eq!(lsmr.tol.to_ne_bytes(), 1e-10f64.to_ne_bytes());
+
+        let res: Result<LSMR<Vec<f64>, _, f64>, _> = LSMR::new(vec![1.0]).with_tolerance(-1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`LSMR`: tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut lsmr: LSMR<Vec<f64>, _, f64> = LSMR::new(vec![1.0, 2.0, 3.0]);
+        let res = lsmr.init(&mut Problem::new(operator(3, 2)), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`LSMR` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let (m, n) = (30, 10);
+        let b: Vec<f64> = (0..m).map(|i| (i as f64).sin()).collect();
+        for damping in [0.0, 0.5, 3.0] {
+            let lsmr: LSMR<Vec<f64>, _, f64> = LSMR::new(b.clone())
+                .with_damping(damping)
+                .unwrap()
+                .with_tolerance(1e-12)
+                .unwrap();
+            let res = Executor::new(operator(m, n), lsmr)
+                .configure(|state| state.param(vec![0.0; n]).max_iters(100))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            let x = res.state.get_best_param().unwrap();
+            let op = operator(m, n);
+            for g in normal_residual(&op, &b, x, damping) {
+                assert_relative_eq!(g, 0.0, epsilon = 1e-9);
+            }
+
+            // Residual norm
+            let r_norm = (op
+                .apply(x)
+                .unwrap()
+                .iter()
+                .zip(b.iter())
+                .map(|(ax, b)| (b - ax).powi(2))
+                .sum::<f64>()
+                + damping.powi(2) * x.iter().map(|x| x * x).sum::<f64>())
+            .sqrt();
+            assert_relative_eq!(res.state.get_best_cost(), r_norm, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_compatible() {
+        // Square, nonsingular system with solution (1, ..., 1)
+        let n = 8;
+        let op = operator(n, n);
+        let b = op.apply(&vec![1.0; n]).unwrap();
+        let lsmr: LSMR<Vec<f64>, _, f64> = LSMR::new(b).with_tolerance(1e-12).unwrap();
+        let res = Executor::new(op, lsmr)
+            .configure(|state| state.param(vec![0.0; n]).max_iters(100))
+            .run()
+            .unwrap();
+        for x in res.state.get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-9);
+        }
+        assert!(res.state.get_best_cost() < 1e-9);
+    }
+}
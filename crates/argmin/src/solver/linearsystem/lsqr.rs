@@ -0,0 +1,499 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, Solver, State, TerminationReason,
+    TerminationStatus, KV,
+};
+use crate::solver::linearsystem::AdjointOperator;
+use argmin_math::{ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminScaledSub, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # LSQR
+///
+/// Solves linear least squares problems `min ||A x - b||^2 + damping^2 ||x - x_0||^2` with a
+/// general (possibly rectangular and rank-deficient) matrix `A`, where `x_0` is the initial
+/// parameter vector. For `damping = 0` (default, see [`with_damping`](`LSQR::with_damping`)), this
+/// is the ordinary least squares problem; damped problems arise for instance as subproblems of
+/// the Levenberg-Marquardt method.
+///
+/// LSQR is based on the Golub-Kahan bidiagonalization of `A` and is analytically equivalent to
+/// the conjugate gradient method applied to the normal equations, but numerically more reliable.
+/// Each iteration requires one product with `A` and one with `A^T`; `A` is never formed, which
+/// makes the method suitable for large and sparse problems. See also [`LSMR`](`super::LSMR`),
+/// which is often preferable when the iterations are stopped early.
+///
+/// The cost function value is an estimate of the norm of the residual
+/// `||(b - A x, damping (x_0 - x))||`, which is computed by the recurrences. The algorithm stops
+/// when either
+///
+/// * `||r|| <= tol * (||b|| + ||A|| ||x||)` (the system is compatible), or
+/// * `||A^T r|| <= tol * ||A|| ||r||` (`x` solves the least squares problem),
+///
+/// where `||A||` is an estimate of the Frobenius norm of `A` and `tol` defaults to
+/// `sqrt(EPSILON)` (see [`with_tolerance`](`LSQR::with_tolerance`)).
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`], which returns `A v`, and
+/// [`AdjointOperator`], which returns `A^T u`. For matrix-free operators given by closures, see
+/// [`LinearOperator`](`super::LinearOperator`).
+///
+/// ## Reference
+///
+/// Paige, C. C., Saunders, M. A. (1982): LSQR: An algorithm for sparse linear equations and
+/// sparse least squares. ACM Trans. Math. Softw. 8, 43–71.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LSQR<P, R, F> {
+    /// b (right hand side)
+    b: R,
+    /// Norm of `b`
+    b_norm: F,
+    /// Damping parameter
+    damping: F,
+    /// Tolerance
+    tol: F,
+    /// Left Lanczos vector
+    u: Option<R>,
+    /// Right Lanczos vector
+    v: Option<P>,
+    /// Search direction
+    w: Option<P>,
+    /// Current `alpha` of the bidiagonalization
+    alpha: F,
+    /// Elements of the bidiagonal matrix and the right hand side transformed by the rotations
+    rhobar: F,
+    phibar: F,
+    /// Accumulated squared contributions of the damping to the residual norm
+    res2: F,
+    /// Estimate of the Frobenius norm of `A`
+    a_norm: F,
+    /// Estimate of `||A^T r||`
+    ar_norm: F,
+}
+
+impl<P, R, F> LSQR<P, R, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`LSQR`]
+    ///
+    /// Takes `b`, the right hand side of the least squares problem, as input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::LSQR;
+    /// # let b = vec![1.0f64, 1.0, 1.0];
+    /// let lsqr: LSQR<Vec<f64>, _, f64> = LSQR::new(b);
+    /// ```
+    pub fn new(b: R) -> Self {
+        LSQR {
+            b,
+            b_norm: F::nan(),
+            damping: float!(0.0),
+            tol: F::epsilon().sqrt(),
+            u: None,
+            v: None,
+            w: None,
+            alpha: F::nan(),
+            rhobar: F::nan(),
+            phibar: F::nan(),
+            res2: float!(0.0),
+            a_norm: float!(0.0),
+            ar_norm: F::nan(),
+        }
+    }
+
+    /// Set the damping parameter
+    ///
+    /// Must be finite and non-negative. Defaults to 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::LSQR;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let b = vec![1.0f64, 1.0, 1.0];
+    /// let lsqr: LSQR<Vec<f64>, _, f64> = LSQR::new(b).with_damping(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_damping(mut self, damping: F) -> Result<Self, Error> {
+        if damping < float!(0.0) || !damping.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LSQR`: damping must be finite and >= 0."
+            ));
+        }
+        self.damping = damping;
+        Ok(self)
+    }
+
+    /// Set the tolerance of the stopping criteria
+    ///
+    /// Must be non-negative and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::LSQR;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let b = vec![1.0f64, 1.0, 1.0];
+    /// let lsqr: LSQR<Vec<f64>, _, f64> = LSQR::new(b).with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LSQR`: tolerance must be >= 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+impl<O, P, R, F> Solver<O, IterState<P, (), (), (), (), F>> for LSQR<P, R, F>
+where
+    O: Operator<Param = P, Output = R> + AdjointOperator<Param = P, Output = R>,
+    P: Clone
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminL2Norm<F>,
+    R: ArgminSub<R, R> + ArgminScaledSub<R, F, R> + ArgminMul<F, R> + ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "LSQR"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`LSQR` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        self.b_norm = self.b.l2_norm();
+        let u = self.b.sub(&problem.apply(&param)?);
+        let beta = u.l2_norm();
+        let u = normalize(u, beta);
+        let v = problem.apply_adjoint(&u)?;
+        let alpha = v.l2_norm();
+        let v = normalize(v, alpha);
+
+        self.alpha = alpha;
+        self.rhobar = alpha;
+        self.phibar = beta;
+        self.res2 = float!(0.0);
+        self.a_norm = float!(0.0);
+        self.ar_norm = alpha * beta;
+        self.w = Some(v.clone());
+        self.v = Some(v);
+        self.u = Some(u);
+        Ok((state.param(param).cost(beta), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`LSQR`: `param` not set"
+        ))?;
+        let (Some(u), Some(v), Some(w)) = (self.u.take(), self.v.take(), self.w.take()) else {
+            return Err(argmin_error!(
+                PotentialBug,
+                "`LSQR`: Lanczos vectors not set"
+            ));
+        };
+
+        // Golub-Kahan bidiagonalization
+        let u = problem.apply(&v)?.scaled_sub(&self.alpha, &u);
+        let beta = u.l2_norm();
+        let u = normalize(u, beta);
+        self.a_norm =
+            (self.a_norm.powi(2) + self.alpha.powi(2) + beta.powi(2) + self.damping.powi(2)).sqrt();
+        let v_new = problem.apply_adjoint(&u)?.scaled_sub(&beta, &v);
+        let alpha = v_new.l2_norm();
+        let v_new = normalize(v_new, alpha);
+
+        // Eliminate the damping parameter
+        let rhobar1 = self.rhobar.hypot(self.damping);
+        let (cs1, sn1) = rotation(self.rhobar, self.damping, rhobar1);
+        let psi = sn1 * self.phibar;
+        let phibar = cs1 * self.phibar;
+
+        // Eliminate the subdiagonal element of the bidiagonal matrix
+        let rho = rhobar1.hypot(beta);
+        let (cs, sn) = rotation(rhobar1, beta, rho);
+        let theta = sn * alpha;
+        self.rhobar = -cs * alpha;
+        let phi = cs * phibar;
+        self.phibar = sn * phibar;
+        let tau = sn * phi;
+
+        // Update the solution and the search direction
+        let new_param = param.scaled_add(&(phi / rho), &w);
+        let w = v_new.scaled_sub(&(theta / rho), &w);
+
+        self.res2 = self.res2 + psi.powi(2);
+        let r_norm = (self.phibar.powi(2) + self.res2).sqrt();
+        self.ar_norm = alpha * tau.abs();
+        self.alpha = alpha;
+        self.u = Some(u);
+        self.v = Some(v_new);
+        self.w = Some(w);
+
+        Ok((
+            state.param(new_param).cost(r_norm),
+            Some(kv!(
+                "normal_residual_norm" => self.ar_norm;
+                "a_norm" => self.a_norm;
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+        let x_norm = state
+            .get_param()
+            .map(|param| param.l2_norm())
+            .unwrap_or(float!(0.0));
+        if least_squares_converged(
+            self.tol,
+            state.get_cost(),
+            self.ar_norm,
+            self.a_norm,
+            self.b_norm,
+            x_norm,
+        ) {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+/// Scales `x` by `1 / norm` unless `norm` vanishes
+pub(super) fn normalize<T, F>(x: T, norm: F) -> T
+where
+    T: ArgminMul<F, T>,
+    F: ArgminFloat,
+{
+    if norm > float!(0.0) {
+        x.mul(&(float!(1.0) / norm))
+    } else {
+        x
+    }
+}
+
+/// Cosine and sine of the Givens rotation which maps `(a, b)` to `(r, 0)` with `r = hypot(a, b)`
+pub(super) fn rotation<F: ArgminFloat>(a: F, b: F, r: F) -> (F, F) {
+    if r > float!(0.0) {
+        (a / r, b / r)
+    } else {
+        (float!(1.0), float!(0.0))
+    }
+}
+
+/// Stopping criteria of LSQR and LSMR: either the system is compatible and the residual is small,
+/// or the residual of the normal equations is small.
+pub(super) fn least_squares_converged<F: ArgminFloat>(
+    tol: F,
+    r_norm: F,
+    ar_norm: F,
+    a_norm: F,
+    b_norm: F,
+    x_norm: F,
+) -> bool {
+    r_norm <= tol * (b_norm + a_norm * x_norm) || ar_norm <= tol * a_norm * r_norm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::linearsystem::LinearOperator;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(lsqr, LSQR<Vec<f64>, Vec<f64>, f64>);
+
+    /// `A = [[1, 2], [3, 4], [5, 6], [7, 8]]`
+    fn matrix() -> Vec<Vec<f64>> {
+        vec![
+            vec![1.0, 2.0],
+            vec![3.0, 4.0],
+            vec![5.0, 6.0],
+            vec![7.0, 8.0],
+        ]
+    }
+
+    fn operator(
+        a: Vec<Vec<f64>>,
+    ) -> LinearOperator<
+        Vec<f64>,
+        Vec<f64>,
+        impl Fn(&Vec<f64>) -> Vec<f64>,
+        impl Fn(&Vec<f64>) -> Vec<f64>,
+    > {
+        let at: Vec<Vec<f64>> = (0..a[0].len())
+            .map(|j| a.iter().map(|row| row[j]).collect())
+            .collect();
+        let dot = |m: &Vec<Vec<f64>>, x: &Vec<f64>| -> Vec<f64> {
+            m.iter()
+                .map(|row| row.iter().zip(x.iter()).map(|(a, x)| a * x).sum())
+                .collect()
+        };
+        LinearOperator::new(move |v| dot(&a, v), move |u| dot(&at, u))
+    }
+
+    /// Solves the damped normal equations `(A^T A + damping^2 I) x = A^T b` for two unknowns
+    fn reference_solution(a: &[Vec<f64>], b: &[f64], damping: f64) -> Vec<f64> {
+        let mut ata = [[0.0; 2]; 2];
+        let mut atb = [0.0; 2];
+        for (row, bi) in a.iter().zip(b.iter()) {
+            for i in 0..2 {
+                atb[i] += row[i] * bi;
+                for j in 0..2 {
+                    ata[i][j] += row[i] * row[j];
+                }
+            }
+        }
+        ata[0][0] += damping.powi(2);
+        ata[1][1] += damping.powi(2);
+        let det = ata[0][0] * ata[1][1] - ata[0][1] * ata[1][0];
+        vec![
+            (ata[1][1] * atb[0] - ata[0][1] * atb[1]) / det,
+            (ata[0][0] * atb[1] - ata[1][0] * atb[0]) / det,
+        ]
+    }
+
+    #[test]
+    fn test_new() {
+        let lsqr: LSQR<Vec<f64>, Vec<f64>, f64> = LSQR::new(vec![1.0, 2.0]);
+        assert_eq!(lsqr.b, vec![1.0, 2.0]);
+        assert_eq!(lsqr.damping.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(lsqr.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(lsqr.u.is_none());
+        assert!(lsqr.v.is_none());
+        assert!(lsqr.w.is_none());
+    }
+
+    #[test]
+    fn test_with_damping() {
+        let lsqr: LSQR<Vec<f64>, _, f64> = LSQR::new(vec![1.0]).with_damping(0.5).unwrap();
+        assert_eq!(lsqr.damping.to_ne_bytes(), 0.5f64.to_ne_bytes());
+
+        for damping in [-1.0, f64::INFINITY, f64::NAN] {
+            let res: Result<LSQR<Vec<f64>, _, f64>, _> = LSQR::new(vec![1.0]).with_damping(damping);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`LSQR`: damping must be finite and >= 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let lsqr: LSQR<Vec<f64>, _, f64> = LSQR::new(vec![1.0]).with_tolerance(1e-10).unwrap();
+        assert_eq!(lsqr.tol.to_ne_bytes(), 1e-10f64.to_ne_bytes());
+
+        let res: Result<LSQR<Vec<f64>, _, f64>, _> = LSQR::new(vec![1.0]).with_tolerance(-1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`LSQR`: tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut lsqr: LSQR<Vec<f64>, _, f64> = LSQR::new(vec![1.0, 2.0, 3.0, 4.0]);
+        let res = lsqr.init(&mut Problem::new(operator(matrix())), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`LSQR` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let b = vec![1.0, 0.0, 2.0, -1.0];
+        for damping in [0.0, 0.5, 3.0] {
+            let lsqr: LSQR<Vec<f64>, _, f64> = LSQR::new(b.clone())
+                .with_damping(damping)
+                .unwrap()
+                .with_tolerance(1e-12)
+                .unwrap();
+            let res = Executor::new(operator(matrix()), lsqr)
+                .configure(|state| state.param(vec![0.0, 0.0]).max_iters(10))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            let x = res.state.get_best_param().unwrap();
+            let x_ref = reference_solution(&matrix(), &b, damping);
+            assert_relative_eq!(x[0], x_ref[0], epsilon = 1e-9);
+            assert_relative_eq!(x[1], x_ref[1], epsilon = 1e-9);
+
+            // Residual norm
+            let r: Vec<f64> = operator(matrix())
+                .apply(x)
+                .unwrap()
+                .iter()
+                .zip(b.iter())
+                .map(|(ax, b)| b - ax)
+                .collect();
+            let r_norm = (r.iter().map(|r| r * r).sum::<f64>()
+                + damping.powi(2) * x.iter().map(|x| x * x).sum::<f64>())
+            .sqrt();
+            assert_relative_eq!(res.state.get_best_cost(), r_norm, epsilon = 1e-9);
+            assert_eq!(
+                res.problem.counts["operator_count"],
+                res.problem.counts["adjoint_count"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_compatible() {
+        // b = A (1, -1), started at the solution of a different problem
+        let b = vec![-1.0, -1.0, -1.0, -1.0];
+        let lsqr: LSQR<Vec<f64>, _, f64> = LSQR::new(b).with_tolerance(1e-12).unwrap();
+        let res = Executor::new(operator(matrix()), lsqr)
+            .configure(|state| state.param(vec![3.0, 2.0]).max_iters(10))
+            .run()
+            .unwrap();
+        let x = res.state.get_best_param().unwrap();
+        assert_relative_eq!(x[0], 1.0, epsilon = 1e-10);
+        assert_relative_eq!(x[1], -1.0, epsilon = 1e-10);
+        assert!(res.state.get_best_cost() < 1e-10);
+    }
+}
@@ -0,0 +1,479 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, Solver, TerminationReason, TerminationStatus,
+    KV,
+};
+use crate::solver::linearsystem::{IdentityPreconditioner, Preconditioner};
+use argmin_math::{
+    ArgminDot, ArgminMul, ArgminScaledAdd, ArgminScaledSub, ArgminSub, ArgminZeroLike,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Minimal residual method (MINRES)
+///
+/// Solves linear systems `A x = b` with a symmetric, possibly indefinite matrix `A`.
+///
+/// MINRES minimizes the norm of the residual over the Krylov subspace
+/// `span{r_0, A r_0, A^2 r_0, ...}` like [`GMRES`](`crate::solver::linearsystem::GMRES`), but the
+/// symmetry of `A` allows building the basis with the three-term Lanczos recurrence. Work and
+/// memory per iteration are therefore constant and no restarts are needed. Each iteration
+/// requires one product of `A` with a vector. In contrast to the
+/// [conjugate gradient method](`crate::solver::conjugategradient::ConjugateGradient`), `A` does
+/// not need to be positive definite, which makes MINRES suitable for saddle point systems, for
+/// instance in interior point methods.
+///
+/// The [`Preconditioner`] `M` must be symmetric and positive definite; an error is returned if
+/// this is found to be violated.
+///
+/// The cost function value is the norm of the residual `||b - A x||` (in the norm induced by
+/// `M^{-1}` if preconditioned), which is updated by the recurrences without additional products
+/// with `A`. The algorithm stops when the residual norm has been reduced by a factor of `tol`
+/// relative to the initial residual norm (`tol` defaults to `sqrt(EPSILON)`, see
+/// [`with_tolerance`](`MINRES::with_tolerance`)).
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`], which returns the product
+/// `A x`.
+///
+/// ## Reference
+///
+/// Paige, C. C., Saunders, M. A. (1975): Solution of sparse indefinite systems of linear
+/// equations. SIAM J. Numer. Anal. 12, 617–629.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MINRES<P, M, F> {
+    /// b (right hand side of `A * x = b`)
+    b: P,
+    /// Preconditioner
+    preconditioner: M,
+    /// Tolerance on the relative residual
+    tol: F,
+    /// Previous Lanczos vector (unnormalized)
+    r1: Option<P>,
+    /// Current Lanczos vector (unnormalized)
+    r2: Option<P>,
+    /// Preconditioned current Lanczos vector
+    y: Option<P>,
+    /// Current search direction
+    w: Option<P>,
+    /// Previous search direction
+    w2: Option<P>,
+    /// Initial residual norm
+    beta1: F,
+    /// Current Lanczos coefficient `beta`
+    beta: F,
+    /// Previous Lanczos coefficient `beta`
+    oldb: F,
+    /// Elements of the tridiagonal matrix transformed by the previous rotations
+    dbar: F,
+    epsln: F,
+    /// Residual norm
+    phibar: F,
+    /// Previous Givens rotation
+    cs: F,
+    sn: F,
+}
+
+impl<P, F> MINRES<P, IdentityPreconditioner, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`MINRES`]
+    ///
+    /// Takes `b`, the right hand side of `A * x = b` as input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::MINRES;
+    /// # let b = vec![1.0f64, 1.0];
+    /// let minres: MINRES<_, _, f64> = MINRES::new(b);
+    /// ```
+    pub fn new(b: P) -> Self {
+        MINRES {
+            b,
+            preconditioner: IdentityPreconditioner,
+            tol: F::epsilon().sqrt(),
+            r1: None,
+            r2: None,
+            y: None,
+            w: None,
+            w2: None,
+            beta1: F::nan(),
+            beta: F::nan(),
+            oldb: float!(0.0),
+            dbar: float!(0.0),
+            epsln: float!(0.0),
+            phibar: F::nan(),
+            cs: float!(-1.0),
+            sn: float!(0.0),
+        }
+    }
+}
+
+impl<P, M, F> MINRES<P, M, F>
+where
+    F: ArgminFloat,
+{
+    /// The algorithm stops if the residual norm is below `tol` times the initial residual norm.
+    ///
+    /// Must be non-negative and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::MINRES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let b = vec![1.0f64, 1.0];
+    /// let minres: MINRES<_, _, f64> = MINRES::new(b).with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MINRES`: tolerance must be >= 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set the preconditioner, which must be symmetric and positive definite
+    ///
+    /// Defaults to [`IdentityPreconditioner`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::{DiagonalPreconditioner, MINRES};
+    /// # let b = vec![1.0f64, 1.0];
+    /// let preconditioner = DiagonalPreconditioner::new(vec![4.0f64, 2.0]);
+    /// let minres: MINRES<_, _, f64> = MINRES::new(b).with_preconditioner(preconditioner);
+    /// ```
+    pub fn with_preconditioner<M2>(self, preconditioner: M2) -> MINRES<P, M2, F> {
+        MINRES {
+            b: self.b,
+            preconditioner,
+            tol: self.tol,
+            r1: self.r1,
+            r2: self.r2,
+            y: self.y,
+            w: self.w,
+            w2: self.w2,
+            beta1: self.beta1,
+            beta: self.beta,
+            oldb: self.oldb,
+            dbar: self.dbar,
+            epsln: self.epsln,
+            phibar: self.phibar,
+            cs: self.cs,
+            sn: self.sn,
+        }
+    }
+}
+
+/// Returns `sqrt(r^T y)` for `y = M^{-1} r`, or an error if `M` is not positive definite.
+fn preconditioned_norm<P, F>(r: &P, y: &P) -> Result<F, Error>
+where
+    P: ArgminDot<P, F>,
+    F: ArgminFloat,
+{
+    let rty = r.dot(y);
+    if rty < float!(0.0) || rty.is_nan() {
+        return Err(argmin_error!(
+            ConditionViolated,
+            "`MINRES`: preconditioner must be positive definite."
+        ));
+    }
+    Ok(rty.sqrt())
+}
+
+impl<O, P, M, F> Solver<O, IterState<P, (), (), (), (), F>> for MINRES<P, M, F>
+where
+    O: Operator<Param = P, Output = P>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminZeroLike,
+    M: Preconditioner<P>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "MINRES"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`MINRES` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let r1 = self.b.sub(&problem.apply(&param)?);
+        let y = self.preconditioner.precondition(&r1)?;
+        self.beta1 = preconditioned_norm(&r1, &y)?;
+        self.beta = self.beta1;
+        self.oldb = float!(0.0);
+        self.dbar = float!(0.0);
+        self.epsln = float!(0.0);
+        self.phibar = self.beta1;
+        self.cs = float!(-1.0);
+        self.sn = float!(0.0);
+        self.w = Some(param.zero_like());
+        self.w2 = Some(param.zero_like());
+        self.r2 = Some(r1.clone());
+        self.r1 = Some(r1);
+        self.y = Some(y);
+        Ok((state.param(param).cost(self.phibar), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`MINRES`: `param` not set"
+        ))?;
+        let (Some(r1), Some(r2), Some(y), Some(w), Some(w2)) = (
+            self.r1.take(),
+            self.r2.take(),
+            self.y.take(),
+            self.w.take(),
+            self.w2.take(),
+        ) else {
+            return Err(argmin_error!(
+                PotentialBug,
+                "`MINRES`: Lanczos vectors not set"
+            ));
+        };
+
+        // Lanczos step
+        let v = y.mul(&(float!(1.0) / self.beta));
+        let mut y = problem.apply(&v)?;
+        if self.oldb > float!(0.0) {
+            y = y.scaled_sub(&(self.beta / self.oldb), &r1);
+        }
+        let alpha = v.dot(&y);
+        let y = y.scaled_sub(&(alpha / self.beta), &r2);
+        let (r1, r2) = (r2, y);
+        let y = self.preconditioner.precondition(&r2)?;
+        self.oldb = self.beta;
+        self.beta = preconditioned_norm(&r2, &y)?;
+
+        // Apply the previous rotation to the new column of the tridiagonal matrix and compute the
+        // rotation which eliminates its subdiagonal element
+        let oldeps = self.epsln;
+        let delta = self.cs * self.dbar + self.sn * alpha;
+        let gbar = self.sn * self.dbar - self.cs * alpha;
+        self.epsln = self.sn * self.beta;
+        self.dbar = -self.cs * self.beta;
+        let gamma = gbar.hypot(self.beta).max(F::epsilon());
+        self.cs = gbar / gamma;
+        self.sn = self.beta / gamma;
+        let phi = self.cs * self.phibar;
+        self.phibar = self.sn * self.phibar;
+
+        // Update the search directions and the solution
+        let w_new = v
+            .scaled_sub(&oldeps, &w2)
+            .scaled_sub(&delta, &w)
+            .mul(&(float!(1.0) / gamma));
+        let new_param = param.scaled_add(&phi, &w_new);
+
+        self.r1 = Some(r1);
+        self.r2 = Some(r2);
+        self.y = Some(y);
+        self.w2 = Some(w);
+        self.w = Some(w_new);
+
+        Ok((
+            state.param(new_param).cost(self.phibar),
+            Some(kv!("alpha" => alpha; "beta" => self.beta;)),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+        // A vanishing `beta` implies a vanishing residual norm.
+        if self.phibar <= self.tol * self.beta1 {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::solver::linearsystem::DiagonalPreconditioner;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(minres, MINRES<Vec<f64>, IdentityPreconditioner, f64>);
+
+    /// Product with a dense matrix
+    struct Matrix(Vec<Vec<f64>>);
+
+    impl Operator for Matrix {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self
+                .0
+                .iter()
+                .map(|row| row.iter().zip(x.iter()).map(|(a, x)| a * x).sum())
+                .collect())
+        }
+    }
+
+    /// Symmetric indefinite tridiagonal matrix of dimension `n`
+    fn indefinite(n: usize) -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| match j as isize - i as isize {
+                        0 => {
+                            if i % 2 == 0 {
+                                3.0 + i as f64
+                            } else {
+                                -3.0 - i as f64
+                            }
+                        }
+                        -1 | 1 => 1.0,
+                        _ => 0.0,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let minres: MINRES<_, _, f64> = MINRES::new(vec![1.0f64, 2.0]);
+        assert_eq!(minres.b, vec![1.0, 2.0]);
+        assert_eq!(minres.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(minres.r1.is_none());
+        assert!(minres.w.is_none());
+        assert!(minres.beta1.is_nan());
+        assert!(minres.phibar.is_nan());
+        assert_eq!(minres.cs.to_ne_bytes(), (-1.0f64).to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let minres: MINRES<_, _, f64> = MINRES::new(vec![1.0f64]).with_tolerance(1e-10).unwrap();
+        assert_eq!(minres.tol.to_ne_bytes(), 1e-10f64.to_ne_bytes());
+
+        let res: Result<MINRES<_, _, f64>, _> = MINRES::new(vec![1.0f64]).with_tolerance(-1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`MINRES`: tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut minres: MINRES<_, _, f64> = MINRES::new(vec![1.0f64, 2.0]);
+        let res = minres.init(
+            &mut Problem::new(Matrix(vec![vec![1.0, 0.0], vec![0.0, 1.0]])),
+            IterState::new(),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`MINRES` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_indefinite_preconditioner() {
+        let minres: MINRES<_, _, f64> = MINRES::new(vec![1.0f64, 2.0]);
+        let mut minres = minres.with_preconditioner(DiagonalPreconditioner::new(vec![-1.0, -1.0]));
+        let res = minres.init(
+            &mut Problem::new(Matrix(vec![vec![1.0, 0.0], vec![0.0, 1.0]])),
+            IterState::new().param(vec![0.0, 0.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"`MINRES`: preconditioner must be positive definite.\""
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let n = 40;
+        let a = indefinite(n);
+        let x_true: Vec<f64> = (0..n).map(|i| (i as f64).cos()).collect();
+        let b = Matrix(a.clone()).apply(&x_true).unwrap();
+        let diag: Vec<f64> = (0..n).map(|i| a[i][i].abs()).collect();
+
+        for preconditioned in [false, true] {
+            let minres: MINRES<_, _, f64> = MINRES::new(b.clone()).with_tolerance(1e-12).unwrap();
+            let init =
+                |state: IterState<_, _, _, _, _, _>| state.param(vec![0.0; n]).max_iters(200);
+            let res = if preconditioned {
+                let minres = minres.with_preconditioner(DiagonalPreconditioner::new(diag.clone()));
+                Executor::new(Matrix(a.clone()), minres)
+                    .configure(init)
+                    .run()
+                    .unwrap()
+                    .state
+            } else {
+                Executor::new(Matrix(a.clone()), minres)
+                    .configure(init)
+                    .run()
+                    .unwrap()
+                    .state
+            };
+            assert_eq!(
+                res.get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            for (x, xt) in res.get_best_param().unwrap().iter().zip(x_true.iter()) {
+                assert_relative_eq!(x, xt, epsilon = 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_iteration() {
+        // For `A = a I`, the solution is found in a single iteration
+        let minres: MINRES<_, _, f64> = MINRES::new(vec![1.0, 2.0]);
+        let res = Executor::new(Matrix(vec![vec![-2.0, 0.0], vec![0.0, -2.0]]), minres)
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(10))
+            .run()
+            .unwrap();
+        assert_eq!(res.state.get_iter(), 1);
+        let x = res.state.get_best_param().unwrap();
+        assert_relative_eq!(x[0], -0.5, epsilon = 1e-14);
+        assert_relative_eq!(x[1], -1.0, epsilon = 1e-14);
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Krylov subspace methods for linear systems
+//!
+//! Iterative solvers for linear systems `A x = b` which only require products of `A` with
+//! vectors, provided via the [`Operator`](`crate::core::Operator`) trait of the problem.
+//!
+//! * [GMRES(m)](`GMRES`) for general (nonsymmetric) matrices
+//! * [MINRES](`MINRES`) for symmetric, possibly indefinite matrices
+//! * [LSQR](`LSQR`) and [LSMR](`LSMR`) for (damped) linear least squares problems
+//!   `min ||A x - b||`, which additionally require products with `A^T` via
+//!   [`AdjointOperator`]. [`LinearOperator`] defines matrix-free operators via closures.
+//!
+//! For symmetric and positive definite matrices, see
+//! [`ConjugateGradient`](`crate::solver::conjugategradient::ConjugateGradient`).
+//!
+//! Both solvers accept a [`Preconditioner`]. Besides running them with an
+//! [`Executor`](`crate::core::Executor`), they can be used as inner solvers of other methods by
+//! calling [`init`](`crate::core::Solver::init`) and [`next_iter`](`crate::core::Solver::next_iter`)
+//! directly, as [`NewtonCG`](`crate::solver::newton::NewtonCG`) does with the conjugate gradient
+//! method.
+//!
+//! ## References
+//!
+//! Saad, Y., Schultz, M. H. (1986): GMRES: A generalized minimal residual algorithm for solving
+//! nonsymmetric linear systems. SIAM J. Sci. Stat. Comput. 7, 856–869.
+//!
+//! Paige, C. C., Saunders, M. A. (1975): Solution of sparse indefinite systems of linear
+//! equations. SIAM J. Numer. Anal. 12, 617–629.
+//!
+//! Paige, C. C., Saunders, M. A. (1982): LSQR: An algorithm for sparse linear equations and
+//! sparse least squares. ACM Trans. Math. Softw. 8, 43–71.
+//!
+//! Fong, D. C.-L., Saunders, M. A. (2011): LSMR: An iterative algorithm for sparse
+//! least-squares problems. SIAM J. Sci. Comput. 33, 2950–2971.
+
+mod gmres;
+mod lsmr;
+mod lsqr;
+mod minres;
+mod operator;
+mod preconditioner;
+
+pub(crate) use self::gmres::gmres_cycle;
+pub use self::gmres::GMRES;
+pub use self::lsmr::LSMR;
+pub use self::lsqr::LSQR;
+pub use self::minres::MINRES;
+pub use self::operator::{AdjointOperator, LinearOperator};
+pub use self::preconditioner::{DiagonalPreconditioner, IdentityPreconditioner, Preconditioner};
@@ -0,0 +1,164 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{Error, Operator, Problem};
+use std::marker::PhantomData;
+
+/// Products with the adjoint (transpose) of a linear operator. Problems which are to be solved
+/// using [`LSQR`](`crate::solver::linearsystem::LSQR`) or
+/// [`LSMR`](`crate::solver::linearsystem::LSMR`) must implement this trait in addition to
+/// [`Operator`].
+pub trait AdjointOperator {
+    /// Type of the parameter vector (domain of `A`)
+    type Param;
+    /// Type of the output of the operator (range of `A`)
+    type Output;
+
+    /// Computes `A^T u`
+    fn apply_adjoint(&self, u: &Self::Output) -> Result<Self::Param, Error>;
+}
+
+/// Wraps a call to `apply_adjoint` defined in the `AdjointOperator` trait and as such allows to
+/// call `apply_adjoint` on an instance of `Problem`. Internally, the number of evaluations of
+/// `apply_adjoint` is counted.
+impl<O: AdjointOperator> Problem<O> {
+    /// Calls `apply_adjoint` defined in the `AdjointOperator` trait and keeps track of the number
+    /// of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Problem};
+    /// # use argmin::solver::linearsystem::AdjointOperator;
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl AdjointOperator for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #
+    /// #     fn apply_adjoint(&self, u: &Self::Output) -> Result<Self::Param, Error> {
+    /// #         Ok(vec![u[0] + u[1]])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `AdjointOperator`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let res = problem1.apply_adjoint(&vec![1.0f64, 2.0]);
+    ///
+    /// assert_eq!(problem1.counts["adjoint_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![3.0]);
+    /// ```
+    pub fn apply_adjoint(&mut self, u: &O::Output) -> Result<O::Param, Error> {
+        self.problem("adjoint_count", |problem| problem.apply_adjoint(u))
+    }
+}
+
+/// Matrix-free linear operator defined by closures which compute the products `A v` and
+/// `A^T u`
+///
+/// Implements [`Operator`] and [`AdjointOperator`].
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{Error, Operator};
+/// use argmin::solver::linearsystem::{AdjointOperator, LinearOperator};
+///
+/// # fn main() -> Result<(), Error> {
+/// // A = [[1, 2], [0, 1], [1, 0]]
+/// let op = LinearOperator::new(
+///     |v: &Vec<f64>| vec![v[0] + 2.0 * v[1], v[1], v[0]],
+///     |u: &Vec<f64>| vec![u[0] + u[2], 2.0 * u[0] + u[1]],
+/// );
+/// assert_eq!(op.apply(&vec![1.0, 1.0])?, vec![3.0, 1.0, 1.0]);
+/// assert_eq!(op.apply_adjoint(&vec![1.0, 1.0, 1.0])?, vec![2.0, 3.0]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct LinearOperator<P, R, A, T> {
+    /// `v -> A v`
+    apply: A,
+    /// `u -> A^T u`
+    apply_adjoint: T,
+    phantom: PhantomData<fn(&P, &R)>,
+}
+
+impl<P, R, A, T> LinearOperator<P, R, A, T>
+where
+    A: Fn(&P) -> R,
+    T: Fn(&R) -> P,
+{
+    /// Construct a new instance of [`LinearOperator`] from closures computing `A v` and `A^T u`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::LinearOperator;
+    /// // A = diag(1, 2)
+    /// let op = LinearOperator::new(
+    ///     |v: &Vec<f64>| vec![v[0], 2.0 * v[1]],
+    ///     |u: &Vec<f64>| vec![u[0], 2.0 * u[1]],
+    /// );
+    /// ```
+    pub fn new(apply: A, apply_adjoint: T) -> Self {
+        LinearOperator {
+            apply,
+            apply_adjoint,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<P, R, A, T> Operator for LinearOperator<P, R, A, T>
+where
+    A: Fn(&P) -> R,
+{
+    type Param = P;
+    type Output = R;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok((self.apply)(param))
+    }
+}
+
+impl<P, R, A, T> AdjointOperator for LinearOperator<P, R, A, T>
+where
+    T: Fn(&R) -> P,
+{
+    type Param = P;
+    type Output = R;
+
+    fn apply_adjoint(&self, u: &Self::Output) -> Result<Self::Param, Error> {
+        Ok((self.apply_adjoint)(u))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_operator() {
+        let op = LinearOperator::new(
+            |v: &Vec<f64>| vec![v[0] + 2.0 * v[1], v[1], v[0]],
+            |u: &Vec<f64>| vec![u[0] + u[2], 2.0 * u[0] + u[1]],
+        );
+        let mut problem = Problem::new(op);
+        assert_eq!(
+            problem.apply(&vec![1.0, -1.0]).unwrap(),
+            vec![-1.0, -1.0, 1.0]
+        );
+        assert_eq!(
+            problem.apply_adjoint(&vec![1.0, 2.0, 3.0]).unwrap(),
+            vec![4.0, 4.0]
+        );
+        assert_eq!(problem.counts["operator_count"], 1);
+        assert_eq!(problem.counts["adjoint_count"], 1);
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::Error;
+use argmin_math::ArgminDiv;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Preconditioner of a linear system `A x = b`
+///
+/// A preconditioner `M` approximates `A` such that systems with `M` are cheap to solve.
+/// [`precondition`](`Preconditioner::precondition`) returns `M^{-1} r`.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::Error;
+/// use argmin::solver::linearsystem::Preconditioner;
+///
+/// /// Scales all components by the same factor
+/// struct Scaling(f64);
+///
+/// impl Preconditioner<Vec<f64>> for Scaling {
+///     fn precondition(&self, r: &Vec<f64>) -> Result<Vec<f64>, Error> {
+///         Ok(r.iter().map(|ri| ri / self.0).collect())
+///     }
+/// }
+/// ```
+pub trait Preconditioner<P> {
+    /// Applies the inverse of the preconditioner to `r`
+    fn precondition(&self, r: &P) -> Result<P, Error>;
+}
+
+/// Identity preconditioner (no preconditioning)
+///
+/// # Example
+///
+/// ```
+/// use argmin::solver::linearsystem::{IdentityPreconditioner, Preconditioner};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let r = IdentityPreconditioner.precondition(&vec![1.0f64, 2.0])?;
+/// # assert_eq!(r, vec![1.0, 2.0]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct IdentityPreconditioner;
+
+impl<P: Clone> Preconditioner<P> for IdentityPreconditioner {
+    fn precondition(&self, r: &P) -> Result<P, Error> {
+        Ok(r.clone())
+    }
+}
+
+/// Diagonal (Jacobi) preconditioner
+///
+/// `M` is a diagonal matrix, typically the diagonal of `A`. All diagonal elements must be
+/// nonzero; for [`MINRES`](`crate::solver::linearsystem::MINRES`), they must be positive.
+///
+/// # Example
+///
+/// ```
+/// use argmin::solver::linearsystem::{DiagonalPreconditioner, Preconditioner};
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let preconditioner = DiagonalPreconditioner::new(vec![2.0f64, 4.0]);
+/// let r = preconditioner.precondition(&vec![1.0, 2.0])?;
+/// # assert_eq!(r, vec![0.5, 0.5]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct DiagonalPreconditioner<P> {
+    /// Diagonal of `M`
+    diag: P,
+}
+
+impl<P> DiagonalPreconditioner<P> {
+    /// Construct a new instance of [`DiagonalPreconditioner`] from the diagonal of `M`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linearsystem::DiagonalPreconditioner;
+    /// let preconditioner = DiagonalPreconditioner::new(vec![2.0f64, 4.0]);
+    /// ```
+    pub fn new(diag: P) -> Self {
+        DiagonalPreconditioner { diag }
+    }
+}
+
+impl<P> Preconditioner<P> for DiagonalPreconditioner<P>
+where
+    P: ArgminDiv<P, P>,
+{
+    fn precondition(&self, r: &P) -> Result<P, Error> {
+        Ok(r.div(&self.diag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_trait_impl!(identity_preconditioner, IdentityPreconditioner);
+    test_trait_impl!(diagonal_preconditioner, DiagonalPreconditioner<Vec<f64>>);
+
+    #[test]
+    fn test_identity() {
+        let r = IdentityPreconditioner
+            .precondition(&vec![1.0f64, -2.0])
+            .unwrap();
+        assert_eq!(r, vec![1.0, -2.0]);
+    }
+
+    #[test]
+    fn test_diagonal() {
+        let preconditioner = DiagonalPreconditioner::new(vec![2.0f64, -4.0]);
+        assert_eq!(preconditioner.diag, vec![2.0, -4.0]);
+        let r = preconditioner.precondition(&vec![1.0, 2.0]).unwrap();
+        assert_eq!(r, vec![0.5, -0.5]);
+    }
+}
@@ -9,8 +9,10 @@ use crate::core::{
     ArgminFloat, CostFunction, Error, Gradient, IterState, LineSearch, Problem, Solver, State,
     TerminationReason, TerminationStatus, KV,
 };
-use crate::solver::linesearch::condition::*;
-use argmin_math::ArgminScaledAdd;
+use crate::solver::linesearch::{condition::*, step_length_limit};
+use argmin_math::{
+    ArgminDiv, ArgminL2Norm, ArgminLInfNorm, ArgminScaledAdd, ArgminScaledAddAssign,
+};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -46,6 +48,10 @@ pub struct BacktrackingLineSearch<P, G, L, F> {
     condition: L,
     /// alpha
     alpha: F,
+    /// maximum length of a step in parameter space
+    max_step_length: F,
+    /// maximum change of each component of the parameter vector
+    move_limits: Option<G>,
 }
 
 impl<P, G, L, F> BacktrackingLineSearch<P, G, L, F>
@@ -72,6 +78,8 @@ where
             rho: float!(0.9),
             condition,
             alpha: float!(1.0),
+            max_step_length: F::infinity(),
+            move_limits: None,
         }
     }
 
@@ -106,6 +114,7 @@ where
 
 impl<P, G, L, F> LineSearch<G, F> for BacktrackingLineSearch<P, G, L, F>
 where
+    G: Clone,
     F: ArgminFloat,
 {
     /// Set search direction
@@ -124,11 +133,28 @@ where
         self.alpha = alpha;
         Ok(())
     }
+
+    /// Set maximum step length
+    fn max_step_length(&mut self, max_step_length: F) -> Result<(), Error> {
+        if max_step_length.is_nan() || max_step_length <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BacktrackingLineSearch`: maximum step length must be > 0."
+            ));
+        }
+        self.max_step_length = max_step_length;
+        Ok(())
+    }
+
+    /// Set move limits
+    fn move_limits(&mut self, move_limits: &G) {
+        self.move_limits = Some(move_limits.clone());
+    }
 }
 
 impl<P, G, L, F> BacktrackingLineSearch<P, G, L, F>
 where
-    P: ArgminScaledAdd<G, F, P>,
+    P: Clone + ArgminScaledAddAssign<G, F>,
     L: LineSearchCondition<G, G, F>,
     IterState<P, G, (), (), (), F>: State<Float = F>,
     F: ArgminFloat,
@@ -143,22 +169,23 @@ where
         O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
         IterState<P, G, (), (), (), F>: State<Float = F>,
     {
-        let new_param = self
+        let mut new_param = self
             .init_param
             .as_ref()
             .ok_or_else(argmin_error_closure!(
                 PotentialBug,
                 "`BacktrackingLineSearch`: Initial parameter vector not set."
             ))?
-            .scaled_add(
-                &self.alpha,
-                self.search_direction
-                    .as_ref()
-                    .ok_or_else(argmin_error_closure!(
-                        PotentialBug,
-                        "`BacktrackingLineSearch`: Search direction not set."
-                    ))?,
-            );
+            .clone();
+        new_param.scaled_add_assign(
+            &self.alpha,
+            self.search_direction
+                .as_ref()
+                .ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`BacktrackingLineSearch`: Search direction not set."
+                ))?,
+        );
 
         let cur_cost = problem.cost(&new_param)?;
 
@@ -177,8 +204,8 @@ where
 
 impl<O, P, G, L, F> Solver<O, IterState<P, G, (), (), (), F>> for BacktrackingLineSearch<P, G, L, F>
 where
-    P: Clone + ArgminScaledAdd<G, F, P>,
-    G: ArgminScaledAdd<G, F, G>,
+    P: Clone + ArgminScaledAddAssign<G, F>,
+    G: ArgminScaledAdd<G, F, G> + ArgminL2Norm<F> + ArgminDiv<G, G> + ArgminLInfNorm<F>,
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
     L: LineSearchCondition<G, G, F>,
     F: ArgminFloat,
@@ -220,6 +247,13 @@ where
             .map(Result::Ok)
             .unwrap_or_else(|| problem.gradient(&init_param))?;
 
+        // Limit the step length in parameter space
+        self.alpha = self.alpha.min(step_length_limit(
+            self.search_direction.as_ref().unwrap(),
+            self.max_step_length,
+            self.move_limits.as_ref(),
+        ));
+
         self.init_param = Some(init_param);
         self.init_grad = Some(init_grad);
         let state = self.backtracking_step(problem, state)?;
@@ -489,6 +523,66 @@ mod tests {
         assert!(data.get_gradient().is_none());
     }
 
+    #[test]
+    fn test_max_step_length() {
+        use crate::core::IterState;
+        use crate::core::Problem;
+
+        let armijo = ArmijoCondition::new(0.01f64).unwrap();
+        let mut ls: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(armijo);
+
+        for max_step_length in [0.0, -1.0, f64::NAN] {
+            assert_error!(
+                ls.max_step_length(max_step_length),
+                ArgminError,
+                "Invalid parameter: \"`BacktrackingLineSearch`: maximum step length must be > 0.\""
+            );
+        }
+
+        ls.initial_step_length(0.8).unwrap();
+        ls.max_step_length(0.4).unwrap();
+        ls.search_direction(vec![2.0f64, 0.0]);
+
+        // Without the limit, the step would be of length 1.6 (see `test_init_armijo`)
+        let (state, _) = ls
+            .init(
+                &mut Problem::new(BTTestProblem {}),
+                IterState::new().param(vec![-1.0, 0.0]),
+            )
+            .unwrap();
+
+        let param = state.get_param().unwrap();
+        assert_relative_eq!(param[0], -0.6, epsilon = f64::EPSILON);
+        assert_relative_eq!(param[1], 0.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_move_limits() {
+        use crate::core::IterState;
+        use crate::core::Problem;
+
+        let armijo = ArmijoCondition::new(0.01f64).unwrap();
+        let mut ls: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(armijo);
+
+        ls.initial_step_length(0.8).unwrap();
+        ls.move_limits(&vec![0.3, 1.0]);
+        ls.search_direction(vec![2.0f64, 0.0]);
+
+        // Without the limits, the first component would change by 1.6 (see `test_init_armijo`)
+        let (state, _) = ls
+            .init(
+                &mut Problem::new(BTTestProblem {}),
+                IterState::new().param(vec![-1.0, 0.0]),
+            )
+            .unwrap();
+
+        let param = state.get_param().unwrap();
+        assert_relative_eq!(param[0], -0.7, epsilon = f64::EPSILON);
+        assert_relative_eq!(param[1], 0.0, epsilon = f64::EPSILON);
+    }
+
     #[test]
     fn test_init_wolfe() {
         use crate::core::IterState;
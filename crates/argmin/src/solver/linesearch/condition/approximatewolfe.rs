@@ -0,0 +1,292 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::LineSearchCondition;
+use crate::core::{ArgminFloat, Error};
+use argmin_math::ArgminDot;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Approximate Wolfe conditions
+///
+/// Accepts a step length if it either satisfies the
+/// [`WolfeCondition`](`crate::solver::linesearch::condition::WolfeCondition`) (with `c1 = delta`
+/// and `c2 = sigma`) or the approximate Wolfe conditions of Hager and Zhang:
+///
+/// `(2*delta - 1) * phi'(0) >= phi'(alpha) >= sigma * phi'(0)`
+///
+/// and
+///
+/// `phi(alpha) <= phi(0) + epsilon * |phi(0)|`,
+///
+/// where `phi(alpha)` is the cost function value at the initial point plus a step of length
+/// `alpha` in search direction.
+///
+/// The sufficient decrease condition of the Wolfe conditions compares cost function values which
+/// are almost identical close to a minimizer. If the cost function is only accurate up to a
+/// certain level (for instance due to rounding errors), this comparison is dominated by noise and
+/// the line search may fail to find an acceptable step length. The approximate Wolfe conditions
+/// replace it with a condition on the directional derivative, which is only required to not
+/// increase the cost function value by more than `epsilon * |phi(0)|`.
+///
+/// ## Reference
+///
+/// William W. Hager and Hongchao Zhang. "A new conjugate gradient method with guaranteed
+/// descent and an efficient line search." SIAM J. Optim. 16(1), 2006, 170-192.
+/// DOI: <https://doi.org/10.1137/030601880>
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ApproximateWolfeCondition<F> {
+    delta: F,
+    sigma: F,
+    epsilon: F,
+}
+
+impl<F> ApproximateWolfeCondition<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`ApproximateWolfeCondition`].
+    ///
+    /// `delta` must be in `(0, 0.5)` and `sigma` must be in `[delta, 1)`. `epsilon` defaults to
+    /// `1e-6` and can be changed via
+    /// [`with_epsilon`](`ApproximateWolfeCondition::with_epsilon`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linesearch::condition::ApproximateWolfeCondition;
+    /// let approximate_wolfe = ApproximateWolfeCondition::new(0.1f64, 0.9f64);
+    /// ```
+    pub fn new(delta: F, sigma: F) -> Result<Self, Error> {
+        if delta <= float!(0.0) || delta >= float!(0.5) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "ApproximateWolfeCondition: Parameter delta must be in (0, 0.5)"
+            ));
+        }
+        if sigma < delta || sigma >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "ApproximateWolfeCondition: Parameter sigma must be in [delta, 1)"
+            ));
+        }
+        Ok(ApproximateWolfeCondition {
+            delta,
+            sigma,
+            epsilon: float!(1e-6),
+        })
+    }
+
+    /// Set the relative tolerance `epsilon` on the increase of the cost function value.
+    ///
+    /// Must be non-negative and defaults to `1e-6`. It should be chosen according to the accuracy
+    /// of the cost function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linesearch::condition::ApproximateWolfeCondition;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let approximate_wolfe = ApproximateWolfeCondition::new(0.1f64, 0.9f64)?.with_epsilon(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_epsilon(mut self, epsilon: F) -> Result<Self, Error> {
+        if epsilon < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "ApproximateWolfeCondition: Parameter epsilon must be >= 0"
+            ));
+        }
+        self.epsilon = epsilon;
+        Ok(self)
+    }
+}
+
+impl<T, G, F> LineSearchCondition<T, G, F> for ApproximateWolfeCondition<F>
+where
+    G: ArgminDot<T, F>,
+    F: ArgminFloat,
+{
+    fn evaluate_condition(
+        &self,
+        current_cost: F,
+        current_gradient: Option<&G>,
+        initial_cost: F,
+        initial_gradient: &G,
+        search_direction: &T,
+        step_length: F,
+    ) -> bool {
+        let dginit = initial_gradient.dot(search_direction);
+        let dg = current_gradient
+            .expect(concat!(
+                "Current gradient not supplied to",
+                " `evaluate_condition` method of `ApproximateWolfeCondition`"
+            ))
+            .dot(search_direction);
+        if dg < self.sigma * dginit {
+            return false;
+        }
+        // Wolfe conditions
+        if current_cost <= initial_cost + self.delta * step_length * dginit {
+            return true;
+        }
+        // Approximate Wolfe conditions
+        dg <= (float!(2.0) * self.delta - float!(1.0)) * dginit
+            && current_cost <= initial_cost + self.epsilon * initial_cost.abs()
+    }
+
+    fn requires_current_gradient(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+
+    test_trait_impl!(approximate_wolfe, ApproximateWolfeCondition<f64>);
+
+    #[test]
+    fn test_approximate_wolfe_new() {
+        let ApproximateWolfeCondition {
+            delta,
+            sigma,
+            epsilon,
+        } = ApproximateWolfeCondition::new(0.1f64, 0.9f64).unwrap();
+        assert_eq!(delta.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(sigma.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert_eq!(epsilon.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        // sigma may be equal to delta
+        assert!(ApproximateWolfeCondition::new(0.2f64, 0.2f64).is_ok());
+
+        // delta
+        for delta in [-1.0f64, 0.0, 0.5, 1.0] {
+            assert_error!(
+                ApproximateWolfeCondition::new(delta, 0.9),
+                ArgminError,
+                "Invalid parameter: \"ApproximateWolfeCondition: Parameter delta must be in (0, 0.5)\""
+            );
+        }
+
+        // sigma
+        for sigma in [-1.0f64, 0.0, 0.05, 1.0, 2.0] {
+            assert_error!(
+                ApproximateWolfeCondition::new(0.1, sigma),
+                ArgminError,
+                "Invalid parameter: \"ApproximateWolfeCondition: Parameter sigma must be in [delta, 1)\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_approximate_wolfe_with_epsilon() {
+        let cond = ApproximateWolfeCondition::new(0.1f64, 0.9f64).unwrap();
+        for eps in [0.0, 1e-12, 1e-2, 1.0] {
+            let cond = cond.with_epsilon(eps).unwrap();
+            assert_eq!(cond.epsilon.to_ne_bytes(), eps.to_ne_bytes());
+        }
+
+        assert_error!(
+            cond.with_epsilon(-1e-6),
+            ArgminError,
+            "Invalid parameter: \"ApproximateWolfeCondition: Parameter epsilon must be >= 0\""
+        );
+    }
+
+    #[test]
+    fn test_approximate_wolfe() {
+        let cond = ApproximateWolfeCondition::new(0.1f64, 0.9f64).unwrap();
+        let f = |x: f64, y: f64| x.powf(2.0) + y.powf(2.0);
+        let g = |x: f64, y: f64| vec![2.0 * x, 2.0 * y];
+        let initial_x = -1.0;
+        let initial_y = -0.0;
+        let search_direction = vec![1.0, 0.0];
+        for (alpha, acc) in [
+            (0.001, false),
+            (0.05, false),
+            (0.1 - f64::EPSILON, false),
+            (0.1, true),
+            (0.5, true),
+            (1.0, true),
+            (1.5, true),
+            (1.7, true),
+            (1.9, false),
+            (2.0, false),
+            (2.3, false),
+        ] {
+            assert_eq!(
+                cond.evaluate_condition(
+                    f(initial_x + alpha, initial_y),
+                    Some(&g(initial_x + alpha, initial_y)),
+                    f(initial_x, initial_y),
+                    &g(initial_x, initial_y),
+                    &search_direction,
+                    alpha,
+                ),
+                acc
+            );
+        }
+    }
+
+    #[test]
+    fn test_approximate_wolfe_noisy_cost() {
+        let cond = ApproximateWolfeCondition::new(0.1f64, 0.9f64).unwrap();
+        let initial_cost = 1.0;
+        let initial_gradient = vec![-2.0, 0.0];
+        let search_direction = vec![1.0, 0.0];
+        let alpha = 1e-8;
+        // The slope has been reduced sufficiently, but due to noise the cost function value did
+        // not decrease. The Wolfe conditions are not satisfied, the approximate Wolfe conditions
+        // are, as long as the increase is below `epsilon * |phi(0)|`.
+        for (cost, acc) in [
+            (initial_cost, true),
+            (initial_cost + 1e-12, true),
+            (initial_cost + 1e-6, true),
+            (initial_cost + 2e-6, false),
+        ] {
+            assert_eq!(
+                cond.evaluate_condition(
+                    cost,
+                    Some(&vec![0.0, 0.0]),
+                    initial_cost,
+                    &initial_gradient,
+                    &search_direction,
+                    alpha,
+                ),
+                acc
+            );
+        }
+
+        // Same as above, but the slope was not reduced sufficiently
+        for dg in [-1.9, 1.7] {
+            assert!(!cond.evaluate_condition(
+                initial_cost,
+                Some(&vec![dg, 0.0]),
+                initial_cost,
+                &initial_gradient,
+                &search_direction,
+                alpha,
+            ));
+        }
+
+        // Approximate Wolfe conditions are disabled with epsilon = 0
+        let cond = cond.with_epsilon(0.0).unwrap();
+        assert!(!cond.evaluate_condition(
+            initial_cost + 1e-12,
+            Some(&vec![0.0, 0.0]),
+            initial_cost,
+            &initial_gradient,
+            &search_direction,
+            alpha,
+        ));
+    }
+}
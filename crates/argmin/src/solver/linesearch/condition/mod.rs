@@ -19,17 +19,20 @@
 //! * [`WolfeCondition`](`condition::WolfeCondition`)
 //! * [`StrongWolfeCondition`](`condition::StrongWolfeCondition`)
 //! * [`GoldsteinCondition`](`condition::GoldsteinCondition`)
+//! * [`ApproximateWolfeCondition`](`condition::ApproximateWolfeCondition`)
 //!
 //! ## Reference
 //!
 //! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
+mod approximatewolfe;
 mod armijo;
 mod goldstein;
 mod strongwolfe;
 mod wolfe;
 
+pub use approximatewolfe::ApproximateWolfeCondition;
 pub use armijo::ArmijoCondition;
 pub use goldstein::GoldsteinCondition;
 pub use strongwolfe::StrongWolfeCondition;
@@ -0,0 +1,555 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, Hessian, IterState, LineSearch, Problem, Solver,
+    State, TerminationReason, KV,
+};
+use crate::solver::linesearch::step_length_limit;
+use argmin_math::{ArgminDiv, ArgminDot, ArgminL2Norm, ArgminLInfNorm, ArgminScaledAddAssign};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Exact line search
+///
+/// Computes the step length which minimizes the quadratic model of the cost function along the
+/// search direction `d` in closed form:
+///
+/// `alpha = -g^T d / d^T H d`,
+///
+/// where `g` is the gradient and `H` the Hessian at the initial point. For quadratic cost
+/// functions this is the exact minimizer along the search direction; for cost functions which
+/// are well approximated by a quadratic model it is the step length of a single Newton step in
+/// one dimension. In contrast to other line searches, no trial steps are evaluated: A single
+/// Hessian-vector product `H d` is required in addition to the gradient at the initial point
+/// (which is reused if it is already known). The cost function is evaluated once at the accepted
+/// point. The line search always terminates after initialization.
+///
+/// The Hessian only needs to be able to compute products with vectors via `ArgminDot`, which
+/// allows for matrix-free implementations of the Hessian.
+///
+/// If a maximum step length or move limits are set via
+/// [`max_step_length`](`LineSearch::max_step_length`) and
+/// [`move_limits`](`LineSearch::move_limits`), the step is shortened accordingly.
+///
+/// The initial step length provided via
+/// [`initial_step_length`](`LineSearch::initial_step_length`) is ignored.
+///
+/// An error is returned if the search direction is an ascent direction or if the curvature
+/// `d^T H d` along the search direction is not positive, since the quadratic model is then
+/// unbounded along the search direction. If the directional derivative `g^T d` vanishes, the
+/// step length is zero.
+///
+/// Note that [`ConjugateGradient`](`crate::solver::conjugategradient::ConjugateGradient`) for
+/// linear systems (and hence convex quadratic problems) already computes this step length
+/// internally and does not require a line search.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`], [`Gradient`] and
+/// [`Hessian`].
+///
+/// ## Reference
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ExactLineSearch<G, F> {
+    /// Search direction
+    search_direction: Option<G>,
+    /// Step length computed in the last line search
+    alpha: F,
+    /// Maximum length of a step in parameter space
+    max_step_length: F,
+    /// Maximum change of each component of the parameter vector
+    move_limits: Option<G>,
+}
+
+impl<G, F> ExactLineSearch<G, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `ExactLineSearch`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linesearch::ExactLineSearch;
+    /// let ls: ExactLineSearch<Vec<f64>, f64> = ExactLineSearch::new();
+    /// ```
+    pub fn new() -> Self {
+        ExactLineSearch {
+            search_direction: None,
+            alpha: F::nan(),
+            max_step_length: F::infinity(),
+            move_limits: None,
+        }
+    }
+}
+
+impl<G, F> Default for ExactLineSearch<G, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        ExactLineSearch::new()
+    }
+}
+
+impl<G, F> LineSearch<G, F> for ExactLineSearch<G, F>
+where
+    G: Clone,
+    F: ArgminFloat,
+{
+    /// Set search direction
+    fn search_direction(&mut self, search_direction: G) {
+        self.search_direction = Some(search_direction);
+    }
+
+    /// Set initial step length
+    ///
+    /// The initial step length is ignored since the step length is computed in closed form.
+    fn initial_step_length(&mut self, alpha: F) -> Result<(), Error> {
+        if alpha <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "LineSearch: Initial alpha must be > 0."
+            ));
+        }
+        Ok(())
+    }
+
+    /// Set maximum step length
+    ///
+    /// Steps which exceed the maximum step length are shortened accordingly.
+    fn max_step_length(&mut self, max_step_length: F) -> Result<(), Error> {
+        if max_step_length.is_nan() || max_step_length <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ExactLineSearch`: maximum step length must be > 0."
+            ));
+        }
+        self.max_step_length = max_step_length;
+        Ok(())
+    }
+
+    /// Set move limits
+    fn move_limits(&mut self, move_limits: &G) {
+        self.move_limits = Some(move_limits.clone());
+    }
+}
+
+impl<O, P, G, H, F> Solver<O, IterState<P, G, (), (), (), F>> for ExactLineSearch<G, F>
+where
+    O: CostFunction<Param = P, Output = F>
+        + Gradient<Param = P, Gradient = G>
+        + Hessian<Param = P, Hessian = H>,
+    P: Clone + ArgminScaledAddAssign<G, F>,
+    G: ArgminDot<G, F> + ArgminL2Norm<F> + ArgminDiv<G, G> + ArgminLInfNorm<F>,
+    H: ArgminDot<G, G>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "Exact line search"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let search_direction = self
+            .search_direction
+            .as_ref()
+            .ok_or_else(argmin_error_closure!(
+                NotInitialized,
+                concat!(
+                    "`ExactLineSearch`: Search direction not initialized. ",
+                    "Call `search_direction` before executing the solver."
+                )
+            ))?;
+
+        let mut param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`ExactLineSearch` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let grad = state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&param))?;
+
+        let dg = grad.dot(search_direction);
+        if dg > float!(0.0) {
+            return Err(argmin_error!(
+                LineSearchFailed,
+                "`ExactLineSearch`: Search direction must be a descent direction."
+            ));
+        }
+
+        // The initial point is already a minimizer along the search direction
+        let curvature = if dg == float!(0.0) {
+            self.alpha = float!(0.0);
+            F::nan()
+        } else {
+            let hd = problem.hessian(&param)?.dot(search_direction);
+            let curvature = search_direction.dot(&hd);
+            if curvature <= float!(0.0) || !curvature.is_finite() {
+                return Err(argmin_error!(
+                    LineSearchFailed,
+                    "`ExactLineSearch`: Curvature along search direction must be positive."
+                ));
+            }
+            self.alpha = (-dg / curvature).min(step_length_limit(
+                search_direction,
+                self.max_step_length,
+                self.move_limits.as_ref(),
+            ));
+            param.scaled_add_assign(&self.alpha, search_direction);
+            curvature
+        };
+        let cost = problem.cost(&param)?;
+
+        Ok((
+            state
+                .param(param)
+                .cost(cost)
+                .terminate_with(TerminationReason::SolverConverged),
+            Some(kv!("alpha" => self.alpha; "curvature" => curvature;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        Ok((state, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::gradientdescent::SteepestDescent;
+    use crate::solver::quasinewton::BFGS;
+    use approx::assert_relative_eq;
+
+    /// `f(x) = 1/2 x^T A x - b^T x` with `A = [[4, 1], [1, 3]]` and `b = [1, 2]`
+    #[derive(Clone)]
+    struct Quadratic {}
+
+    impl Quadratic {
+        const A: [[f64; 2]; 2] = [[4.0, 1.0], [1.0, 3.0]];
+        const B: [f64; 2] = [1.0, 2.0];
+
+        fn ax(p: &[f64]) -> Vec<f64> {
+            Self::A
+                .iter()
+                .map(|row| row[0] * p[0] + row[1] * p[1])
+                .collect()
+        }
+    }
+
+    impl CostFunction for Quadratic {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let ax = Self::ax(p);
+            Ok(0.5 * (p[0] * ax[0] + p[1] * ax[1]) - Self::B[0] * p[0] - Self::B[1] * p[1])
+        }
+    }
+
+    impl Gradient for Quadratic {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            let ax = Self::ax(p);
+            Ok(vec![ax[0] - Self::B[0], ax[1] - Self::B[1]])
+        }
+    }
+
+    impl Hessian for Quadratic {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(Self::A.iter().map(|row| row.to_vec()).collect())
+        }
+    }
+
+    /// Minimizer of `Quadratic`
+    const X_OPT: [f64; 2] = [1.0 / 11.0, 7.0 / 11.0];
+
+    test_trait_impl!(exact_linesearch, ExactLineSearch<Vec<f64>, f64>);
+
+    #[test]
+    fn test_new() {
+        let ExactLineSearch {
+            search_direction,
+            alpha,
+            max_step_length,
+            move_limits,
+        } = ExactLineSearch::<Vec<f64>, f64>::new();
+        assert!(search_direction.is_none());
+        assert!(alpha.is_nan());
+        assert!(max_step_length.is_infinite());
+        assert!(move_limits.is_none());
+    }
+
+    #[test]
+    fn test_initial_step_length() {
+        let mut ls: ExactLineSearch<Vec<f64>, f64> = ExactLineSearch::new();
+        assert!(ls.initial_step_length(1.0).is_ok());
+        assert_error!(
+            ls.initial_step_length(0.0),
+            ArgminError,
+            "Invalid parameter: \"LineSearch: Initial alpha must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_search_direction_not_set() {
+        let mut ls: ExactLineSearch<Vec<f64>, f64> = ExactLineSearch::new();
+        let res = ls.init(
+            &mut Problem::new(Quadratic {}),
+            IterState::new().param(vec![0.0, 0.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`ExactLineSearch`: Search direction not initialized. ",
+                "Call `search_direction` before executing the solver.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_set() {
+        let mut ls: ExactLineSearch<Vec<f64>, f64> = ExactLineSearch::new();
+        ls.search_direction(vec![1.0, 2.0]);
+        let res = ls.init(&mut Problem::new(Quadratic {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`ExactLineSearch` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_not_a_descent_direction() {
+        let mut ls: ExactLineSearch<Vec<f64>, f64> = ExactLineSearch::new();
+        // gradient at the origin is -b
+        ls.search_direction(vec![-1.0, -2.0]);
+        let res = ls.init(
+            &mut Problem::new(Quadratic {}),
+            IterState::new().param(vec![0.0, 0.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Line search failed: \"`ExactLineSearch`: ",
+                "Search direction must be a descent direction.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_negative_curvature() {
+        let mut ls: ExactLineSearch<Vec<f64>, f64> = ExactLineSearch::new();
+        ls.search_direction(vec![1.0f64]);
+        // `f(x) = -x^2`
+        #[derive(Clone)]
+        struct Concave {}
+
+        impl CostFunction for Concave {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(-p[0].powi(2))
+            }
+        }
+
+        impl Gradient for Concave {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![-2.0 * p[0]])
+            }
+        }
+
+        impl Hessian for Concave {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(vec![vec![-2.0]])
+            }
+        }
+
+        let res = ls.init(
+            &mut Problem::new(Concave {}),
+            IterState::new().param(vec![1.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Line search failed: \"`ExactLineSearch`: ",
+                "Curvature along search direction must be positive.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_exact_step() {
+        let mut ls: ExactLineSearch<Vec<f64>, f64> = ExactLineSearch::new();
+        ls.search_direction(vec![1.0, 2.0]);
+        let mut problem = Problem::new(Quadratic {});
+        let (state, kv) = ls
+            .init(&mut problem, IterState::new().param(vec![0.0, 0.0]))
+            .unwrap();
+
+        // g = -b, d = b: alpha = b^T b / b^T A b = 5 / 20
+        assert_relative_eq!(ls.alpha, 0.25, epsilon = f64::EPSILON);
+        let kv = kv.unwrap();
+        assert_relative_eq!(
+            kv.get("alpha").unwrap().get_float().unwrap(),
+            0.25,
+            epsilon = f64::EPSILON
+        );
+        assert_relative_eq!(
+            kv.get("curvature").unwrap().get_float().unwrap(),
+            20.0,
+            epsilon = f64::EPSILON
+        );
+
+        let param = state.get_param().unwrap();
+        assert_relative_eq!(param[0], 0.25, epsilon = f64::EPSILON);
+        assert_relative_eq!(param[1], 0.5, epsilon = f64::EPSILON);
+        assert_relative_eq!(
+            state.get_cost(),
+            Quadratic {}.cost(param).unwrap(),
+            epsilon = f64::EPSILON
+        );
+        assert_eq!(
+            state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+
+        // The gradient at the accepted point is not computed
+        assert!(state.get_gradient().is_none());
+        assert_eq!(problem.counts["gradient_count"], 1);
+        assert_eq!(problem.counts["hessian_count"], 1);
+        assert_eq!(problem.counts["cost_count"], 1);
+    }
+
+    #[test]
+    fn test_max_step_length() {
+        let mut ls: ExactLineSearch<Vec<f64>, f64> = ExactLineSearch::new();
+        for max_step_length in [0.0, -1.0, f64::NAN] {
+            assert_error!(
+                ls.max_step_length(max_step_length),
+                ArgminError,
+                "Invalid parameter: \"`ExactLineSearch`: maximum step length must be > 0.\""
+            );
+        }
+
+        // The exact step (alpha = 0.25, see `test_exact_step`) is longer than allowed
+        ls.max_step_length(0.1 * 5.0f64.sqrt()).unwrap();
+        ls.search_direction(vec![1.0, 2.0]);
+        let (state, _) = ls
+            .init(
+                &mut Problem::new(Quadratic {}),
+                IterState::new().param(vec![0.0, 0.0]),
+            )
+            .unwrap();
+
+        assert_relative_eq!(ls.alpha, 0.1, epsilon = 1e-12);
+        let param = state.get_param().unwrap();
+        assert_relative_eq!(param[0], 0.1, epsilon = 1e-12);
+        assert_relative_eq!(param[1], 0.2, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_reuse_gradient() {
+        let mut ls: ExactLineSearch<Vec<f64>, f64> = ExactLineSearch::new();
+        ls.search_direction(vec![1.0, 2.0]);
+        let mut problem = Problem::new(Quadratic {});
+        ls.init(
+            &mut problem,
+            IterState::new()
+                .param(vec![0.0, 0.0])
+                .gradient(vec![-1.0, -2.0]),
+        )
+        .unwrap();
+        assert!(!problem.counts.contains_key("gradient_count"));
+    }
+
+    #[test]
+    fn test_executor() {
+        let mut ls: ExactLineSearch<Vec<f64>, f64> = ExactLineSearch::new();
+        ls.search_direction(vec![1.0, 2.0]);
+        let res = Executor::new(Quadratic {}, ls)
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(10))
+            .run()
+            .unwrap();
+        assert_eq!(res.state.get_iter(), 0);
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.25, epsilon = f64::EPSILON);
+        assert_relative_eq!(param[1], 0.5, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_steepest_descent() {
+        let solver = SteepestDescent::new(ExactLineSearch::new());
+        let res = Executor::new(Quadratic {}, solver)
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(100))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], X_OPT[0], epsilon = 1e-8);
+        assert_relative_eq!(param[1], X_OPT[1], epsilon = 1e-8);
+        // The nested line searches do not evaluate any trial steps
+        // A single cost function evaluation per line search (in addition to the one of
+        // `SteepestDescent`), no trial steps
+        assert_eq!(res.func_counts()["cost_count"], 2 * res.state.get_iter());
+    }
+
+    #[test]
+    fn test_bfgs_quadratic_termination() {
+        // BFGS with exact line searches finds the minimizer of a quadratic function in at most
+        // `n` iterations.
+        let solver = BFGS::new(ExactLineSearch::new());
+        let res = Executor::new(Quadratic {}, solver)
+            .configure(|state| {
+                state
+                    .param(vec![0.0, 0.0])
+                    .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+                    .max_iters(2)
+            })
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], X_OPT[0], epsilon = 1e-12);
+        assert_relative_eq!(param[1], X_OPT[1], epsilon = 1e-12);
+    }
+}
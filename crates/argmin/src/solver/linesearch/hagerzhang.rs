@@ -9,7 +9,8 @@ use crate::core::{
     ArgminFloat, CostFunction, Error, Gradient, IterState, LineSearch, Problem, Solver,
     TerminationReason, TerminationStatus, KV,
 };
-use argmin_math::{ArgminDot, ArgminScaledAdd};
+use crate::solver::linesearch::step_length_limit;
+use argmin_math::{ArgminDiv, ArgminDot, ArgminL2Norm, ArgminLInfNorm, ArgminScaledAdd};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -87,6 +88,10 @@ pub struct HagerZhangLineSearch<P, G, F> {
     search_direction: Option<G>,
     /// Search direction in 1D
     dginit: F,
+    /// maximum length of a step in parameter space
+    max_step_length: F,
+    /// maximum change of each component of the parameter vector
+    move_limits: Option<G>,
 }
 
 impl<P, G, F> HagerZhangLineSearch<P, G, F>
@@ -132,6 +137,8 @@ where
             search_direction: None,
             dginit: F::nan(),
             finit: F::infinity(),
+            max_step_length: F::infinity(),
+            move_limits: None,
         }
     }
 
@@ -482,7 +489,11 @@ where
     }
 }
 
-impl<P, G, F> LineSearch<G, F> for HagerZhangLineSearch<P, G, F> {
+impl<P, G, F> LineSearch<G, F> for HagerZhangLineSearch<P, G, F>
+where
+    G: Clone,
+    F: ArgminFloat,
+{
     /// Set search direction
     fn search_direction(&mut self, search_direction: G) {
         self.search_direction = Some(search_direction);
@@ -493,13 +504,30 @@ impl<P, G, F> LineSearch<G, F> for HagerZhangLineSearch<P, G, F> {
         self.c_x_init = alpha;
         Ok(())
     }
+
+    /// Set maximum step length
+    fn max_step_length(&mut self, max_step_length: F) -> Result<(), Error> {
+        if max_step_length.is_nan() || max_step_length <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HagerZhangLineSearch`: maximum step length must be > 0."
+            ));
+        }
+        self.max_step_length = max_step_length;
+        Ok(())
+    }
+
+    /// Set move limits
+    fn move_limits(&mut self, move_limits: &G) {
+        self.move_limits = Some(move_limits.clone());
+    }
 }
 
 impl<P, G, O, F> Solver<O, IterState<P, G, (), (), (), F>> for HagerZhangLineSearch<P, G, F>
 where
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
     P: Clone + ArgminDot<G, F> + ArgminScaledAdd<G, F, P>,
-    G: Clone + ArgminDot<G, F>,
+    G: Clone + ArgminDot<G, F> + ArgminL2Norm<F> + ArgminDiv<G, G> + ArgminLInfNorm<F>,
     F: ArgminFloat,
 {
     fn name(&self) -> &str {
@@ -541,9 +569,15 @@ where
                 .unwrap_or_else(|| problem.gradient(self.init_param.as_ref().unwrap()))?,
         );
 
-        self.a_x = self.a_x_init;
-        self.b_x = self.b_x_init;
-        self.c_x = self.c_x_init;
+        // Limit the step length in parameter space
+        let max_x = step_length_limit(
+            self.search_direction.as_ref().unwrap(),
+            self.max_step_length,
+            self.move_limits.as_ref(),
+        );
+        self.b_x = self.b_x_init.min(max_x);
+        self.c_x = self.c_x_init.min(self.b_x);
+        self.a_x = self.a_x_init.min(self.c_x);
 
         self.a_f = self.calc(problem, self.a_x)?;
         self.a_g = self.calc_grad(problem, self.a_x)?;
@@ -678,6 +712,8 @@ mod tests {
             search_direction,
             dginit,
             finit,
+            max_step_length,
+            move_limits,
         } = hzls;
 
         assert_eq!(delta.to_ne_bytes(), 0.1f64.to_ne_bytes());
@@ -708,6 +744,8 @@ mod tests {
         assert!(search_direction.is_none());
         assert!(dginit.is_nan());
         assert!(finit.is_infinite());
+        assert!(max_step_length.is_infinite());
+        assert!(move_limits.is_none());
         assert!(finit.is_sign_positive());
     }
 
@@ -904,6 +942,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_max_step_length() {
+        let mut hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> = HagerZhangLineSearch::new();
+        for max_step_length in [0.0, -1.0, f64::NAN] {
+            assert_error!(
+                hzls.max_step_length(max_step_length),
+                ArgminError,
+                "Invalid parameter: \"`HagerZhangLineSearch`: maximum step length must be > 0.\""
+            );
+        }
+
+        // Steps in parameter space are at most 2.0 long, which corresponds to a step length of 0.5
+        // along the search direction.
+        hzls.max_step_length(2.0).unwrap();
+        hzls.search_direction(vec![4.0f64]);
+        hzls.init(
+            &mut Problem::new(TestProblem::new()),
+            IterState::new().param(vec![1.0f64]),
+        )
+        .unwrap();
+        assert!(hzls.a_x <= 0.5);
+        assert!(hzls.b_x <= 0.5);
+        assert!(hzls.c_x <= 0.5);
+        assert!(hzls.best_x <= 0.5);
+    }
+
     #[test]
     fn test_init_search_direction_not_set() {
         let mut hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> = HagerZhangLineSearch::new();
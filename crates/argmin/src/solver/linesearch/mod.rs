@@ -19,6 +19,7 @@
 //! * [Backtracking line search](`BacktrackingLineSearch`)
 //! * [More-Thuente line search](`MoreThuenteLineSearch`)
 //! * [Hager-Zhang line search](`HagerZhangLineSearch`)
+//! * [Exact line search for quadratic models](`ExactLineSearch`)
 //!
 //! ## References
 //!
@@ -36,13 +37,18 @@
 mod backtracking;
 /// Acceptance conditions
 pub mod condition;
+mod exact;
 mod hagerzhang;
 mod morethuente;
 
 pub use self::backtracking::BacktrackingLineSearch;
+pub use self::exact::ExactLineSearch;
 pub use self::hagerzhang::HagerZhangLineSearch;
 pub use self::morethuente::MoreThuenteLineSearch;
 
+use crate::core::ArgminFloat;
+use argmin_math::{ArgminDiv, ArgminL2Norm, ArgminLInfNorm};
+
 /// # Line search trait
 ///
 /// For a method to be used as a line search, it has to implement this trait.
@@ -50,6 +56,10 @@ pub use self::morethuente::MoreThuenteLineSearch;
 /// It enables the optimization method to set the search direction and the initial step length of
 /// the line search.
 ///
+/// If the final state of a line search contains a gradient, it must be the gradient at the final
+/// parameter vector. Solvers reuse it instead of evaluating the gradient again at the accepted
+/// point.
+///
 /// ## Example
 ///
 /// ```
@@ -84,4 +94,79 @@ pub trait LineSearch<D, F> {
     ///
     /// This indicates the first step length which will be tried.
     fn initial_step_length(&mut self, step_length: F) -> Result<(), crate::core::Error>;
+
+    /// Set the maximum step length
+    ///
+    /// Limits the length `||alpha * d||` of the steps tried by the line search, where `d` is the
+    /// search direction. Solvers call this before each line search if a maximum step length was
+    /// set via [`Executor::max_step_length`](`crate::core::Executor::max_step_length`).
+    ///
+    /// The default implementation ignores the maximum step length.
+    fn max_step_length(&mut self, _max_step_length: F) -> Result<(), crate::core::Error> {
+        Ok(())
+    }
+
+    /// Set per-component move limits
+    ///
+    /// Limits the step length such that the change `|alpha * d_i|` of each component stays within
+    /// `move_limits_i`, where `d` is the search direction. Solvers call this before each line
+    /// search if move limits were set via
+    /// [`Executor::move_limits`](`crate::core::Executor::move_limits`).
+    ///
+    /// The default implementation ignores the move limits.
+    fn move_limits(&mut self, _move_limits: &D) {}
+}
+
+/// Returns the largest step length `alpha` along `search_direction` `d` for which the step
+/// `alpha * d` is not longer than `max_step_length` and for which no component changes by more
+/// than allowed by `move_limits`.
+pub(crate) fn step_length_limit<D, F>(
+    search_direction: &D,
+    max_step_length: F,
+    move_limits: Option<&D>,
+) -> F
+where
+    D: ArgminL2Norm<F> + ArgminDiv<D, D> + ArgminLInfNorm<F>,
+    F: ArgminFloat,
+{
+    let limit = max_step_length / search_direction.l2_norm();
+    match move_limits {
+        Some(move_limits) => limit.min(float!(1.0) / search_direction.div(move_limits).linf_norm()),
+        None => limit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_step_length_limit() {
+        let d = vec![2.0f64, -4.0];
+        assert!(step_length_limit(&d, f64::INFINITY, None).is_infinite());
+        assert_relative_eq!(
+            step_length_limit(&d, 20.0f64.sqrt(), None),
+            1.0,
+            epsilon = f64::EPSILON
+        );
+        // The second component is the limiting one: 4 * alpha <= 1
+        assert_relative_eq!(
+            step_length_limit(&d, f64::INFINITY, Some(&vec![1.0, 1.0])),
+            0.25,
+            epsilon = f64::EPSILON
+        );
+        // The first component is the limiting one: 2 * alpha <= 0.1
+        assert_relative_eq!(
+            step_length_limit(&d, f64::INFINITY, Some(&vec![0.1, 1.0])),
+            0.05,
+            epsilon = f64::EPSILON
+        );
+        // The maximum step length is more restrictive
+        assert_relative_eq!(
+            step_length_limit(&d, 0.1 * 20.0f64.sqrt(), Some(&vec![1.0, 1.0])),
+            0.1,
+            epsilon = f64::EPSILON
+        );
+    }
 }
@@ -13,7 +13,8 @@ use crate::core::{
     ArgminFloat, CostFunction, Error, Gradient, IterState, LineSearch, Problem, Solver, State,
     TerminationReason, KV,
 };
-use argmin_math::{ArgminDot, ArgminScaledAdd};
+use crate::solver::linesearch::step_length_limit;
+use argmin_math::{ArgminDiv, ArgminDot, ArgminL2Norm, ArgminLInfNorm, ArgminScaledAddAssign};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -31,10 +32,17 @@ use serde::{Deserialize, Serialize};
 /// Both values need to be non-negative and `lower < upper`.
 ///
 /// One of the reasons for the algorithm to terminate is when the the relative width of the
-/// uncertainty interval is smaller than a given tolerance (default: `1e-10`). This tolerance can
-/// be set via [`with_width_tolerance`](`MoreThuenteLineSearch::with_width_tolerance`) and must be
+/// uncertainty interval is smaller than a given tolerance (default: `1e-10`, or `EPSILON` if it is
+/// larger, as for `f32`). This tolerance can be set via
+/// [`with_width_tolerance`](`MoreThuenteLineSearch::with_width_tolerance`) and must be
 /// non-negative.
 ///
+/// With [`with_approximate_wolfe`](`MoreThuenteLineSearch::with_approximate_wolfe`), the line
+/// search additionally terminates once the approximate Wolfe conditions of Hager and Zhang are
+/// satisfied (see [`ApproximateWolfeCondition`](`crate::solver::linesearch::condition::ApproximateWolfeCondition`)).
+/// This is recommended if the cost function is only accurate to a level where the sufficient
+/// decrease condition cannot be verified reliably close to the minimizer.
+///
 /// TODO: Add missing stopping criteria!
 ///
 /// ## Requirements on the optimization problem
@@ -68,6 +76,8 @@ pub struct MoreThuenteLineSearch<P, G, F> {
     ftol: F,
     /// c2
     gtol: F,
+    /// epsilon of the approximate Wolfe conditions (disabled if `None`)
+    approx_wolfe_epsilon: Option<F>,
     /// xtrapf
     xtrapf: F,
     /// width of interval
@@ -82,6 +92,10 @@ pub struct MoreThuenteLineSearch<P, G, F> {
     stpmin: F,
     /// stpmax
     stpmax: F,
+    /// maximum length of a step in parameter space
+    max_step_length: F,
+    /// maximum change of each component of the parameter vector
+    move_limits: Option<G>,
     /// current step
     stp: Step<F>,
     /// stx (one endpoint of uncertainty interval)
@@ -148,13 +162,16 @@ where
             dgtest: float!(0.0),
             ftol: float!(1e-4),
             gtol: float!(0.9),
+            approx_wolfe_epsilon: None,
             xtrapf: float!(4.0),
             width: F::nan(),
             width1: F::nan(),
-            xtol: float!(1e-10),
+            xtol: float!(1e-10).max(F::epsilon()),
             alpha: float!(1.0),
             stpmin: F::epsilon().sqrt(),
             stpmax: F::infinity(),
+            max_step_length: F::infinity(),
+            move_limits: None,
             stp: Step::default(),
             stx: Step::default(),
             sty: Step::default(),
@@ -199,6 +216,38 @@ where
         Ok(self)
     }
 
+    /// Additionally accept step lengths which satisfy the approximate Wolfe conditions
+    ///
+    /// The approximate Wolfe conditions
+    /// `(2*c1 - 1) * phi'(0) >= phi'(alpha) >= c2 * phi'(0)` and
+    /// `phi(alpha) <= phi(0) + epsilon * |phi(0)|` are checked in addition to the strong Wolfe
+    /// conditions. They tolerate noise in the cost function up to a relative level of `epsilon`
+    /// (see [`ApproximateWolfeCondition`](`crate::solver::linesearch::condition::ApproximateWolfeCondition`)).
+    ///
+    /// Disabled by default. `epsilon` must be non-negative; `1e-6` is a common choice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+    ///     MoreThuenteLineSearch::new().with_approximate_wolfe(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_approximate_wolfe(mut self, epsilon: F) -> Result<Self, Error> {
+        if epsilon < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MoreThuenteLineSearch`: epsilon must be >= 0.0."
+            ));
+        }
+        self.approx_wolfe_epsilon = Some(epsilon);
+        Ok(self)
+    }
+
     /// Set lower and upper bound of step
     ///
     /// Defaults are `step_min = sqrt(EPS)` and `step_max = INF`.
@@ -239,7 +288,7 @@ where
     /// The algorithm terminates when the relative width of the uncertainty interval is below the
     /// supplied tolerance.
     ///
-    /// Must be non-negative and defaults to `1e-10`.
+    /// Must be non-negative and defaults to `1e-10` (or `EPSILON` if it is larger, as for `f32`).
     ///
     /// # Example
     ///
@@ -275,6 +324,7 @@ where
 
 impl<P, G, F> LineSearch<G, F> for MoreThuenteLineSearch<P, G, F>
 where
+    G: Clone,
     F: ArgminFloat,
 {
     /// Set search direction
@@ -293,13 +343,30 @@ where
         self.alpha = alpha;
         Ok(())
     }
+
+    /// Set maximum step length
+    fn max_step_length(&mut self, max_step_length: F) -> Result<(), Error> {
+        if max_step_length.is_nan() || max_step_length <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MoreThuenteLineSearch`: maximum step length must be > 0."
+            ));
+        }
+        self.max_step_length = max_step_length;
+        Ok(())
+    }
+
+    /// Set move limits
+    fn move_limits(&mut self, move_limits: &G) {
+        self.move_limits = Some(move_limits.clone());
+    }
 }
 
 impl<P, G, O, F> Solver<O, IterState<P, G, (), (), (), F>> for MoreThuenteLineSearch<P, G, F>
 where
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
-    P: Clone + ArgminDot<G, F> + ArgminScaledAdd<G, F, P>,
-    G: Clone + ArgminDot<G, F>,
+    P: Clone + ArgminDot<G, F> + ArgminScaledAddAssign<G, F>,
+    G: Clone + ArgminDot<G, F> + ArgminL2Norm<F> + ArgminDiv<G, G> + ArgminLInfNorm<F>,
     F: ArgminFloat,
 {
     fn name(&self) -> &str {
@@ -350,7 +417,7 @@ where
         // compute search direction in 1D
         if self.dginit >= float!(0.0) {
             return Err(argmin_error!(
-                ConditionViolated,
+                LineSearchFailed,
                 "`MoreThuenteLineSearch`: Search direction must be a descent direction."
             ));
         }
@@ -358,6 +425,17 @@ where
         self.stage1 = true;
         self.brackt = false;
 
+        // Limit the step length in parameter space
+        let stpmax = step_length_limit(
+            self.search_direction.as_ref().unwrap(),
+            self.max_step_length,
+            self.move_limits.as_ref(),
+        );
+        if stpmax < self.stpmax {
+            self.stpmax = stpmax;
+            self.stpmin = self.stpmin.min(stpmax);
+        }
+
         self.dgtest = self.ftol * self.dginit;
         self.width = self.stpmax - self.stpmin;
         self.width1 = float!(2.0) * self.width;
@@ -400,11 +478,8 @@ where
         }
 
         // Evaluate the function and gradient at new stp.x and compute the directional derivative
-        let new_param = self
-            .init_param
-            .as_ref()
-            .unwrap()
-            .scaled_add(&self.stp.x, self.search_direction.as_ref().unwrap());
+        let mut new_param = self.init_param.as_ref().unwrap().clone();
+        new_param.scaled_add_assign(&self.stp.x, self.search_direction.as_ref().unwrap());
         self.f = problem.cost(&new_param)?;
         let new_grad = problem.gradient(&new_param)?;
         let cur_cost = self.f;
@@ -438,6 +513,15 @@ where
             info = 1;
         }
 
+        if let Some(epsilon) = self.approx_wolfe_epsilon {
+            if (float!(2.0) * self.ftol - float!(1.0)) * self.dginit >= dg
+                && dg >= self.gtol * self.dginit
+                && self.f <= self.finit + epsilon * self.finit.abs()
+            {
+                info = 1;
+            }
+        }
+
         if info != 0 {
             return Ok((
                 state
@@ -722,10 +806,18 @@ fn cstep<F: ArgminFloat>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{test_utils::TestProblem, ArgminError};
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor, State};
+    use approx::assert_relative_eq;
 
     test_trait_impl!(morethuente, MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>);
 
+    #[test]
+    fn test_new_f32() {
+        let mtls: MoreThuenteLineSearch<Vec<f32>, Vec<f32>, f32> = MoreThuenteLineSearch::new();
+        // The default width tolerance is below the precision of f32
+        assert_eq!(mtls.xtol.to_ne_bytes(), f32::EPSILON.to_ne_bytes());
+    }
+
     #[test]
     fn test_new() {
         let mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = MoreThuenteLineSearch::new();
@@ -738,6 +830,7 @@ mod tests {
             dgtest,
             ftol,
             gtol,
+            approx_wolfe_epsilon,
             xtrapf,
             width,
             width1,
@@ -745,6 +838,8 @@ mod tests {
             alpha,
             stpmin,
             stpmax,
+            max_step_length,
+            move_limits,
             stp,
             stx,
             sty,
@@ -763,6 +858,7 @@ mod tests {
         assert_eq!(dgtest.to_ne_bytes(), 0.0f64.to_ne_bytes());
         assert_eq!(ftol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
         assert_eq!(gtol.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert!(approx_wolfe_epsilon.is_none());
         assert_eq!(xtrapf.to_ne_bytes(), 4.0f64.to_ne_bytes());
         assert!(width.is_nan());
         assert!(width1.is_nan());
@@ -771,6 +867,8 @@ mod tests {
         assert_eq!(stpmin.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
         assert!(stpmax.is_infinite());
         assert!(stpmax.is_sign_positive());
+        assert!(max_step_length.is_infinite());
+        assert!(move_limits.is_none());
         assert_eq!(stp, Step::default());
         assert_eq!(stx, Step::default());
         assert_eq!(sty, Step::default());
@@ -896,6 +994,128 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_with_approximate_wolfe_correct() {
+        let mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = MoreThuenteLineSearch::new();
+        let res = mtls.with_approximate_wolfe(1e-6);
+        assert!(res.is_ok());
+
+        let mtls = res.unwrap();
+        assert_eq!(
+            mtls.approx_wolfe_epsilon.unwrap().to_ne_bytes(),
+            1e-6f64.to_ne_bytes()
+        );
+    }
+
+    #[test]
+    fn test_with_approximate_wolfe_negative_epsilon() {
+        let mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = MoreThuenteLineSearch::new();
+        let res = mtls.with_approximate_wolfe(-1e-6);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`MoreThuenteLineSearch`: ",
+                "epsilon must be >= 0.0.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_approximate_wolfe_noisy_cost() {
+        /// Quadratic function with minimum at 1.0 whose cost function values are perturbed by
+        /// noise of order 1e-9. The gradient is exact.
+        struct Noisy {}
+
+        impl CostFunction for Noisy {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(1.0 + (p[0] - 1.0).powi(2) + 1e-9 * (1e7 * p[0]).sin())
+            }
+        }
+
+        impl Gradient for Noisy {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![2.0 * (p[0] - 1.0)])
+            }
+        }
+
+        let run = |mut mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>| {
+            mtls.search_direction(vec![1e-5]);
+            Executor::new(Noisy {}, mtls)
+                .configure(|state| state.param(vec![1.0 - 1e-5]).max_iters(100))
+                .run()
+                .unwrap()
+                .state
+        };
+
+        // The exact minimizer is rejected because the noise in the cost function value exceeds
+        // the decrease required by the sufficient decrease condition.
+        let state = run(MoreThuenteLineSearch::new());
+        assert!(state.get_iter() > 1);
+
+        let state = run(MoreThuenteLineSearch::new()
+            .with_approximate_wolfe(1e-6)
+            .unwrap());
+        assert_eq!(state.get_iter(), 1);
+        assert_eq!(
+            state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_relative_eq!(state.get_param().unwrap()[0], 1.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_max_step_length() {
+        struct Quadratic {}
+
+        impl CostFunction for Quadratic {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok((p[0] - 1.0).powi(2))
+            }
+        }
+
+        impl Gradient for Quadratic {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![2.0 * (p[0] - 1.0)])
+            }
+        }
+
+        let mut mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = MoreThuenteLineSearch::new();
+        for max_step_length in [0.0, -1.0, f64::NAN] {
+            assert_error!(
+                mtls.max_step_length(max_step_length),
+                ArgminError,
+                "Invalid parameter: \"`MoreThuenteLineSearch`: maximum step length must be > 0.\""
+            );
+        }
+
+        // The minimizer is one step of length 2 away, but steps are limited to a length of 1.
+        mtls.search_direction(vec![2.0]);
+        mtls.max_step_length(1.0).unwrap();
+        let state = Executor::new(Quadratic {}, mtls)
+            .configure(|state| state.param(vec![-1.0]).max_iters(100))
+            .run()
+            .unwrap()
+            .state;
+        assert_eq!(
+            state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_relative_eq!(state.get_param().unwrap()[0], 0.0, epsilon = f64::EPSILON);
+    }
+
     #[test]
     fn test_init_search_direction_not_set() {
         let mut mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = MoreThuenteLineSearch::new();
@@ -11,9 +11,12 @@ pub mod gaussnewton;
 pub mod goldensectionsearch;
 pub mod gradientdescent;
 pub mod landweber;
+pub mod linearsystem;
 pub mod linesearch;
 pub mod neldermead;
 pub mod newton;
+pub mod newtonkrylov;
+pub mod nnls;
 pub mod particleswarm;
 pub mod quasinewton;
 pub mod simulatedannealing;
@@ -19,13 +19,14 @@
 //! <http://www.scholarpedia.org/article/Nelder-Mead_algorithm#Simplex_transformation_algorithm>
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, TerminationReason,
+    ArgminFloat, CostFunction, DerivativeFreeState, Error, Problem, Solver, TerminationReason,
     TerminationStatus, KV,
 };
 use argmin_math::{ArgminAdd, ArgminMul, ArgminSub};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::ops::IndexMut;
 
 /// # Nelder-Mead method
 ///
@@ -49,6 +50,11 @@ use std::fmt;
 /// 4) Shrink (Parameter `sigma`, defaults to `0.5`, configurable via
 ///    [`with_sigma`](`NelderMead::with_sigma`))
 ///
+/// The initial simplex is either provided in full via [`new`](`NelderMead::new`) or constructed
+/// around an initial point via [`from_initial_point`](`NelderMead::from_initial_point`). The
+/// latter offers several strategies (see [`InitialSimplex`]). A poorly chosen initial simplex,
+/// for instance one which is much too small or degenerate, is a common cause of poor results.
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`].
@@ -295,6 +301,143 @@ where
     }
 }
 
+impl<P, F> NelderMead<P, F>
+where
+    P: Clone + ArgminAdd<P, P> + ArgminSub<P, P> + ArgminMul<F, P> + IndexMut<usize, Output = F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `NelderMead` with an initial simplex built around `x0`
+    ///
+    /// The initial simplex consists of `x0` and `n` further vertices constructed according to
+    /// `simplex` (see [`InitialSimplex`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Error;
+    /// # use argmin::solver::neldermead::{InitialSimplex, NelderMead};
+    /// # fn main() -> Result<(), Error> {
+    /// let nm: NelderMead<Vec<f64>, f64> =
+    ///     NelderMead::from_initial_point(vec![1.0, 0.0, 1e-3], InitialSimplex::default())?;
+    /// let nm: NelderMead<Vec<f64>, f64> = NelderMead::from_initial_point(
+    ///     vec![1.0, 0.0, 1e-3],
+    ///     InitialSimplex::Regular { edge_length: 0.5 },
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_initial_point(x0: P, simplex: InitialSimplex<F>) -> Result<Self, Error> {
+        let n = x0.into_iter().count();
+        if n == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Nelder-Mead`: initial point must have at least one component."
+            ));
+        }
+        let mut params = Vec::with_capacity(n + 1);
+        match simplex {
+            InitialSimplex::Axis { step } => {
+                if step.is_nan() || step <= float!(0.0) {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        "`Nelder-Mead`: step of initial simplex must be > 0."
+                    ));
+                }
+                for i in 0..n {
+                    let mut x = x0.clone();
+                    x[i] = x0[i] + step * x0[i].abs().max(float!(1.0));
+                    params.push(x);
+                }
+            }
+            InitialSimplex::Pfeffer {
+                usual_delta,
+                zero_delta,
+            } => {
+                if usual_delta.is_nan()
+                    || usual_delta <= float!(0.0)
+                    || zero_delta.is_nan()
+                    || zero_delta <= float!(0.0)
+                {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        "`Nelder-Mead`: deltas of initial simplex must be > 0."
+                    ));
+                }
+                for i in 0..n {
+                    let mut x = x0.clone();
+                    x[i] = if x0[i] == float!(0.0) {
+                        zero_delta
+                    } else {
+                        (float!(1.0) + usual_delta) * x0[i]
+                    };
+                    params.push(x);
+                }
+            }
+            InitialSimplex::Regular { edge_length } => {
+                if edge_length.is_nan() || edge_length <= float!(0.0) {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        "`Nelder-Mead`: edge length of initial simplex must be > 0."
+                    ));
+                }
+                let nf: F = float!(n as f64);
+                let root = (nf + float!(1.0)).sqrt();
+                let factor = edge_length / (nf * float!(2.0).sqrt());
+                let p = factor * (root + nf - float!(1.0));
+                let q = factor * (root - float!(1.0));
+                for i in 0..n {
+                    let mut x = x0.clone();
+                    for j in 0..n {
+                        x[j] = x0[j] + if i == j { p } else { q };
+                    }
+                    params.push(x);
+                }
+            }
+        }
+        params.insert(0, x0);
+        Ok(NelderMead::new(params))
+    }
+}
+
+/// Construction of the initial simplex of [`NelderMead`] around an initial point `x0`
+///
+/// See [`NelderMead::from_initial_point`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InitialSimplex<F> {
+    /// Vertices `x0 + step * max(|x0_i|, 1) * e_i`, i.e. steps along the coordinate axes which
+    /// are scaled with the magnitude of the respective component.
+    Axis {
+        /// Relative step size (must be > 0)
+        step: F,
+    },
+    /// Pfeffer's method as used by MATLAB's `fminsearch`: Vertices `x0 + usual_delta * x0_i *
+    /// e_i` for nonzero components and `x0 + zero_delta * e_i` for components which are zero.
+    ///
+    /// The default uses `usual_delta = 0.05` and `zero_delta = 0.00025`.
+    Pfeffer {
+        /// Relative step for nonzero components (must be > 0)
+        usual_delta: F,
+        /// Absolute step for components which are zero (must be > 0)
+        zero_delta: F,
+    },
+    /// Regular simplex whose edges all have length `edge_length` (Spendley, Hext and
+    /// Himsworth), which is suitable if all parameters are of similar magnitude.
+    Regular {
+        /// Length of all edges (must be > 0)
+        edge_length: F,
+    },
+}
+
+impl<F: ArgminFloat> Default for InitialSimplex<F> {
+    fn default() -> Self {
+        InitialSimplex::Pfeffer {
+            usual_delta: float!(0.05),
+            zero_delta: float!(0.00025),
+        }
+    }
+}
+
 #[derive(Debug)]
 enum Action {
     Reflection,
@@ -316,7 +459,7 @@ impl fmt::Display for Action {
     }
 }
 
-impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for NelderMead<P, F>
+impl<O, P, F> Solver<O, DerivativeFreeState<P, F>> for NelderMead<P, F>
 where
     O: CostFunction<Param = P, Output = F>,
     P: Clone + ArgminSub<P, P> + ArgminAdd<P, P> + ArgminMul<F, P>,
@@ -329,8 +472,8 @@ where
     fn init(
         &mut self,
         problem: &mut Problem<O>,
-        state: IterState<P, (), (), (), (), F>,
-    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        state: DerivativeFreeState<P, F>,
+    ) -> Result<(DerivativeFreeState<P, F>, Option<KV>), Error> {
         self.params
             .iter_mut()
             .for_each(|(p, c)| *c = problem.cost(p).unwrap());
@@ -346,8 +489,8 @@ where
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
-        state: IterState<P, (), (), (), (), F>,
-    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        state: DerivativeFreeState<P, F>,
+    ) -> Result<(DerivativeFreeState<P, F>, Option<KV>), Error> {
         let num_param_vecs = self.params.len();
 
         let x0 = self.calculate_centroid();
@@ -415,7 +558,7 @@ where
         ))
     }
 
-    fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+    fn terminate(&mut self, _state: &DerivativeFreeState<P, F>) -> TerminationStatus {
         let n = float!(self.params.len() as f64);
         let c0: F = self.params.iter().map(|(_, c)| *c).sum::<F>() / n;
         let s: F = (float!(1.0) / (n - float!(1.0))
@@ -476,6 +619,82 @@ mod tests {
         assert_eq!(sd_tolerance.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
     }
 
+    #[test]
+    fn test_from_initial_point() {
+        let vertices = |simplex| -> Vec<Vec<f64>> {
+            NelderMead::from_initial_point(vec![2.0, 0.0, -0.5], simplex)
+                .unwrap()
+                .params
+                .into_iter()
+                .map(|(p, _)| p)
+                .collect()
+        };
+
+        let params = vertices(InitialSimplex::Axis { step: 0.1 });
+        assert_eq!(params.len(), 4);
+        assert_eq!(params[0], vec![2.0, 0.0, -0.5]);
+        assert_relative_eq!(params[1][0], 2.2);
+        assert_relative_eq!(params[2][1], 0.1);
+        assert_relative_eq!(params[3][2], -0.4);
+
+        let params = vertices(InitialSimplex::default());
+        assert_relative_eq!(params[1][0], 2.1);
+        assert_relative_eq!(params[2][1], 0.00025);
+        assert_relative_eq!(params[3][2], -0.525);
+        // Only one component differs from the initial point
+        for (i, x) in params.iter().enumerate().skip(1) {
+            for j in (0..3).filter(|&j| j != i - 1) {
+                assert_eq!(x[j].to_ne_bytes(), params[0][j].to_ne_bytes());
+            }
+        }
+
+        let params = vertices(InitialSimplex::Regular { edge_length: 0.5 });
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                let dist = params[i]
+                    .iter()
+                    .zip(params[j].iter())
+                    .map(|(a, b)| (a - b).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+                assert_relative_eq!(dist, 0.5, epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_initial_point_invalid() {
+        assert_error!(
+            NelderMead::<Vec<f64>, f64>::from_initial_point(vec![], InitialSimplex::default()),
+            ArgminError,
+            "Invalid parameter: \"`Nelder-Mead`: initial point must have at least one component.\""
+        );
+        assert_error!(
+            NelderMead::from_initial_point(vec![1.0], InitialSimplex::Axis { step: 0.0 }),
+            ArgminError,
+            "Invalid parameter: \"`Nelder-Mead`: step of initial simplex must be > 0.\""
+        );
+        assert_error!(
+            NelderMead::from_initial_point(
+                vec![1.0],
+                InitialSimplex::Pfeffer {
+                    usual_delta: 0.05,
+                    zero_delta: f64::NAN
+                }
+            ),
+            ArgminError,
+            "Invalid parameter: \"`Nelder-Mead`: deltas of initial simplex must be > 0.\""
+        );
+        assert_error!(
+            NelderMead::from_initial_point(
+                vec![1.0],
+                InitialSimplex::Regular { edge_length: -1.0 }
+            ),
+            ArgminError,
+            "Invalid parameter: \"`Nelder-Mead`: edge length of initial simplex must be > 0.\""
+        );
+    }
+
     #[test]
     fn test_with_sd_tolerance() {
         // correct parameters
@@ -691,7 +910,7 @@ mod tests {
             (vec![-0.5, 2.0], 0.5f64.powi(2) + 2.0f64.powi(2)),
         ];
         let mut nm: NelderMead<_, f64> = NelderMead::new(params);
-        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        let state: DerivativeFreeState<Vec<f64>, f64> = DerivativeFreeState::new();
         let problem = MwProblem {};
         let (state_out, kv) = nm.init(&mut Problem::new(problem), state).unwrap();
 
@@ -722,7 +941,7 @@ mod tests {
     fn test_next_iter_reflection() {
         let params: Vec<Vec<f64>> = vec![vec![-1.0, 0.0], vec![-0.1, 0.65], vec![-0.1, -0.95]];
         let mut nm: NelderMead<_, f64> = NelderMead::new(params);
-        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        let state: DerivativeFreeState<Vec<f64>, f64> = DerivativeFreeState::new();
         let mut problem = Problem::new(MwProblem {});
         let (state, _) = nm.init(&mut problem, state).unwrap();
 
@@ -765,7 +984,7 @@ mod tests {
             vec![-1.0, -1.0 - f64::EPSILON],
         ];
         let mut nm: NelderMead<_, f64> = NelderMead::new(params);
-        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        let state: DerivativeFreeState<Vec<f64>, f64> = DerivativeFreeState::new();
         let mut problem = Problem::new(MwProblem {});
         let (state, _) = nm.init(&mut problem, state).unwrap();
 
@@ -801,7 +1020,7 @@ mod tests {
     fn test_next_iter_contraction_outside() {
         let params: Vec<Vec<f64>> = vec![vec![-1.1, 0.0], vec![-0.1, 1.0], vec![-0.1, -0.5]];
         let mut nm: NelderMead<_, f64> = NelderMead::new(params);
-        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        let state: DerivativeFreeState<Vec<f64>, f64> = DerivativeFreeState::new();
         let mut problem = Problem::new(MwProblem {});
         let (state, _) = nm.init(&mut problem, state).unwrap();
 
@@ -837,7 +1056,7 @@ mod tests {
     fn test_next_iter_contraction_inside() {
         let params: Vec<Vec<f64>> = vec![vec![-1.0, 0.0], vec![0.0, 1.0], vec![0.0, -0.5]];
         let mut nm: NelderMead<_, f64> = NelderMead::new(params);
-        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        let state: DerivativeFreeState<Vec<f64>, f64> = DerivativeFreeState::new();
         let mut problem = Problem::new(MwProblem {});
         let (state, _) = nm.init(&mut problem, state).unwrap();
 
@@ -868,4 +1087,61 @@ mod tests {
         assert_relative_eq!(nm.params[2].0[1], 0.0f64, epsilon = f64::EPSILON);
         assert_relative_eq!(nm.params[2].1, 1.00f64, epsilon = f64::EPSILON);
     }
+
+    #[test]
+    fn test_f32() {
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f32>;
+            type Output = f32;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        let nm: NelderMead<Vec<f32>, f32> =
+            NelderMead::new(vec![vec![-1.2, 1.0], vec![-1.0, 1.2], vec![-0.8, 0.8]])
+                .with_sd_tolerance(1e-6)
+                .unwrap();
+        let res = crate::core::Executor::new(Rosenbrock {}, nm)
+            .configure(|state| state.max_iters(500))
+            .run()
+            .unwrap();
+
+        let best_param = res.state.get_best_param().unwrap();
+        assert!((best_param[0] - 1.0).abs() < 1e-2);
+        assert!((best_param[1] - 1.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_from_initial_point_rosenbrock() {
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        for simplex in [
+            InitialSimplex::Axis { step: 0.1 },
+            InitialSimplex::default(),
+            InitialSimplex::Regular { edge_length: 0.5 },
+        ] {
+            let nm = NelderMead::from_initial_point(vec![-1.2, 1.0, 0.8], simplex)
+                .unwrap()
+                .with_sd_tolerance(1e-10)
+                .unwrap();
+            let res = crate::core::Executor::new(Rosenbrock {}, nm)
+                .configure(|state| state.max_iters(2000))
+                .run()
+                .unwrap();
+            assert!(res.state.get_best_cost() < 1e-6, "{simplex:?}");
+        }
+    }
 }
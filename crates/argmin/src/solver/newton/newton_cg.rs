@@ -7,11 +7,12 @@
 
 use crate::core::{
     ArgminFloat, Error, Executor, Gradient, Hessian, IterState, LineSearch, Operator,
-    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus,
+    ToleranceConfig, KV,
 };
 use crate::solver::conjugategradient::ConjugateGradient;
 use argmin_math::{
-    ArgminConj, ArgminDot, ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminSub, ArgminZeroLike,
+    ArgminDot, ArgminL2Norm, ArgminMul, ArgminScaledAddAssign, ArgminSub, ArgminZeroLike,
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,10 @@ use serde::{Deserialize, Serialize};
 /// The Newton-CG method (also called truncated Newton method) uses a modified CG to approximately
 /// solve the Newton equations. After a search direction is found, a line search is performed.
 ///
+/// All convergence tolerances, including a tolerance on the change of the parameter vector
+/// (disabled by default) and relative instead of absolute tolerances, can be set via
+/// [`with_tolerances`](`NewtonCG::with_tolerances`) (see [`ToleranceConfig`]).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`Gradient`] and [`Hessian`].
@@ -36,8 +41,8 @@ pub struct NewtonCG<L, F> {
     linesearch: L,
     /// curvature_threshold
     curvature_threshold: F,
-    /// Tolerance for the stopping criterion based on cost difference
-    tol: F,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
 }
 
 impl<L, F> NewtonCG<L, F>
@@ -57,7 +62,7 @@ where
         NewtonCG {
             linesearch,
             curvature_threshold: float!(0.0),
-            tol: F::epsilon(),
+            tol: ToleranceConfig::with_defaults(float!(0.0), F::epsilon()),
         }
     }
 
@@ -100,9 +105,30 @@ where
                 "`NewtonCG`: tol must be > 0."
             ));
         }
-        self.tol = tol;
+        self.tol = self.tol.with_cost(tol)?;
         Ok(self)
     }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newton::NewtonCG;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let ncg: NewtonCG<_, f64> = NewtonCG::new(linesearch).with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
 }
 
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for NewtonCG<L, F>
@@ -111,10 +137,11 @@ where
     P: Clone
         + ArgminSub<P, P>
         + ArgminDot<P, F>
-        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledAddAssign<P, F>
         + ArgminMul<F, P>
-        + ArgminConj
-        + ArgminZeroLike,
+        + ArgminL2Norm<F>
+        + ArgminZeroLike
+        + 'static,
     G: ArgminL2Norm<F> + ArgminMul<F, P>,
     H: Clone + ArgminDot<P, P>,
     L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
@@ -186,6 +213,12 @@ where
         // perform line search
         // TODO: Should the algorithm stop when search direction is close to 0?
         self.linesearch.search_direction(x);
+        if let Some(max_step_length) = problem.max_step_length(&param) {
+            self.linesearch.max_step_length(max_step_length)?;
+        }
+        if let Some(move_limits) = problem.move_limits::<P>() {
+            self.linesearch.move_limits(move_limits);
+        }
 
         let line_cost = state.get_cost();
 
@@ -194,27 +227,54 @@ where
             problem: line_problem,
             state: mut linesearch_state,
             ..
-        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
-            .configure(|state| state.param(param).gradient(grad).cost(line_cost))
-            .ctrlc(false)
-            .run()?;
+        } = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+                .configure(|state| state.param(param).gradient(grad).cost(line_cost))
+                .ctrlc(false)
+                .run()?
+        };
 
         problem.consume_problem(line_problem);
 
-        Ok((
-            state
-                .param(linesearch_state.take_param().unwrap())
-                .cost(linesearch_state.get_cost()),
-            None,
-        ))
+        // The gradient at the accepted point is kept (if the line search computed it) and reused
+        // in the next iteration.
+        let state = state
+            .param(linesearch_state.take_param().unwrap())
+            .cost(linesearch_state.get_cost());
+        let state = match linesearch_state.take_gradient() {
+            Some(grad) => state.gradient(grad),
+            None => state,
+        };
+        Ok((state, None))
+    }
+
+    fn termination_diagnostics(&self, state: &IterState<P, G, (), H, (), F>) -> KV {
+        self.tol.diagnostics(
+            state.get_gradient().map(|grad| grad.l2_norm()),
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
     }
 
     fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
-        if (state.get_cost() - state.get_prev_cost()).abs() < self.tol {
-            TerminationStatus::Terminated(TerminationReason::SolverConverged)
-        } else {
-            TerminationStatus::NotTerminated
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        if let Some(grad) = state.get_gradient() {
+            if self.tol.grad_converged(grad.l2_norm(), state.get_cost()) {
+                return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+            }
         }
+        TerminationStatus::NotTerminated
     }
 }
 
@@ -270,7 +330,7 @@ mod tests {
         let NewtonCG { tol: t, .. }: NewtonCG<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, f64> =
             NewtonCG::new(linesearch).with_tolerance(tol1).unwrap();
 
-        assert!((t - tol1).abs() < f64::EPSILON);
+        assert!((t.cost() - tol1).abs() < f64::EPSILON);
     }
 
     #[test]
@@ -286,7 +346,7 @@ mod tests {
         } = ncg;
         assert_eq!(linesearch, ls);
         assert_eq!(curvature_threshold.to_ne_bytes(), 0.0f64.to_ne_bytes());
-        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert_eq!(tol.cost().to_ne_bytes(), f64::EPSILON.to_ne_bytes());
     }
 
     #[test]
@@ -302,7 +362,7 @@ mod tests {
         } = ncg;
         assert_eq!(linesearch, ls);
         assert_eq!(curvature_threshold.to_ne_bytes(), 1e-6f64.to_ne_bytes());
-        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert_eq!(tol.cost().to_ne_bytes(), f64::EPSILON.to_ne_bytes());
     }
 
     #[test]
@@ -310,7 +370,7 @@ mod tests {
         let ls = ();
         for tolerance in [f64::EPSILON, 1.0, 10.0, 100.0] {
             let ncg: NewtonCG<_, f64> = NewtonCG::new(ls).with_tolerance(tolerance).unwrap();
-            assert_eq!(ncg.tol.to_ne_bytes(), tolerance.to_ne_bytes());
+            assert_eq!(ncg.tol.cost().to_ne_bytes(), tolerance.to_ne_bytes());
         }
 
         for tolerance in [-f64::EPSILON, 0.0, -1.0] {
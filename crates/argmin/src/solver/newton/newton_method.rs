@@ -5,8 +5,10 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::{ArgminFloat, Error, Gradient, Hessian, IterState, Problem, Solver, KV};
-use argmin_math::{ArgminDot, ArgminInv, ArgminScaledSub};
+use crate::core::{
+    ArgminError, ArgminFloat, Error, Gradient, Hessian, IterState, Problem, Solver, KV,
+};
+use argmin_math::{ArgminScaledSub, ArgminSolve};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +24,10 @@ use serde::{Deserialize, Serialize};
 ///
 /// The optimization problem is required to implement [`Gradient`] and [`Hessian`].
 ///
+/// The linear system `H p = g` is solved via `ArgminSolve`, which allows for sparse Hessians (for
+/// instance with the `sprs` backend of `argmin-math`, which uses a sparse `LDL^T` decomposition).
+/// The sparsity pattern of the Hessian can be declared via [`Hessian::hessian_sparsity`].
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
@@ -88,7 +94,7 @@ impl<O, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for Newton<F>
 where
     O: Gradient<Param = P, Gradient = G> + Hessian<Param = P, Hessian = H>,
     P: Clone + ArgminScaledSub<P, F, P>,
-    H: ArgminInv<H> + ArgminDot<G, P>,
+    H: ArgminSolve<G, P>,
     F: ArgminFloat,
 {
     fn name(&self) -> &str {
@@ -109,7 +115,14 @@ where
         ))?;
         let grad = problem.gradient(&param)?;
         let hessian = problem.hessian(&param)?;
-        let new_param = param.scaled_sub(&self.gamma, &hessian.inv()?.dot(&grad));
+        let new_param = param.scaled_sub(
+            &self.gamma,
+            &hessian
+                .solve(&grad)
+                .map_err(|e| ArgminError::SingularMatrix {
+                    text: format!("`Newton`: Hessian could not be solved: {e}"),
+                })?,
+        );
         Ok((state.param(new_param), None))
     }
 }
@@ -196,6 +209,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sparse_hessian() {
+        use crate::core::Executor;
+        use approx::assert_relative_eq;
+        use ndarray::Array1;
+        use sprs::{CsMat, TriMat};
+
+        // f(x) = 1/2 x^T A x - x^T b with tridiagonal A
+        struct Quadratic {
+            a: CsMat<f64>,
+            b: Array1<f64>,
+        }
+
+        impl Gradient for Quadratic {
+            type Param = Array1<f64>;
+            type Gradient = Array1<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(&self.a * p - &self.b)
+            }
+        }
+
+        impl Hessian for Quadratic {
+            type Param = Array1<f64>;
+            type Hessian = CsMat<f64>;
+
+            fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(self.a.clone())
+            }
+        }
+
+        let n = 100;
+        let mut a = TriMat::new((n, n));
+        for i in 0..n {
+            a.add_triplet(i, i, 4.0);
+            if i > 0 {
+                a.add_triplet(i, i - 1, -1.0);
+                a.add_triplet(i - 1, i, -1.0);
+            }
+        }
+        let a: CsMat<f64> = a.to_csc();
+        let x = Array1::from_shape_fn(n, |i| i as f64);
+        let b = &a * &x;
+
+        // The minimum of a quadratic function is found in a single iteration
+        let solver: Newton<f64> = Newton::new();
+        let param = Executor::new(Quadratic { a, b }, solver)
+            .configure(|config| config.param(Array1::zeros(n)).max_iters(1))
+            .run()
+            .unwrap()
+            .state
+            .take_best_param()
+            .unwrap();
+        for i in 0..n {
+            assert_relative_eq!(param[i], x[i], epsilon = 1e-10);
+        }
+    }
+
     #[cfg(feature = "_ndarrayl")]
     #[test]
     fn test_solver() {
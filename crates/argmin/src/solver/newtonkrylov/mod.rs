@@ -0,0 +1,557 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Jacobian-free Newton-Krylov method
+//!
+//! Solves nonlinear systems of equations `F(x) = 0`. See [`NewtonKrylov`] for details.
+//!
+//! ## References
+//!
+//! Knoll, D. A., Keyes, D. E. (2004): Jacobian-free Newton–Krylov methods: a survey of approaches
+//! and applications. J. Comput. Phys. 193, 357–397.
+//!
+//! Eisenstat, S. C., Walker, H. F. (1996): Choosing the forcing terms in an inexact Newton
+//! method. SIAM J. Sci. Comput. 17, 16–32.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, Solver, State, TerminationReason,
+    TerminationStatus, ToleranceConfig, KV,
+};
+use crate::solver::linearsystem::gmres_cycle;
+use argmin_math::{
+    ArgminDot, ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminScaledSub, ArgminSub, ArgminZeroLike,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of step length reductions of the backtracking line search
+const MAX_BACKTRACKS: usize = 30;
+
+/// Parameter of the sufficient decrease condition of the backtracking line search
+const SUFFICIENT_DECREASE: f64 = 1e-4;
+
+/// Parameter `gamma` of the forcing terms of Eisenstat and Walker
+const FORCING_GAMMA: f64 = 0.9;
+
+/// # Jacobian-free Newton-Krylov method
+///
+/// Solves nonlinear systems of equations `F(x) = 0` with as many equations as unknowns. In each
+/// iteration, the Newton equations `J(x) d = -F(x)` are solved approximately with one cycle of
+/// [`GMRES`](`crate::solver::linearsystem::GMRES`), starting from `d = 0`. The Jacobian is never
+/// formed: Jacobian-vector products are approximated by forward differences
+///
+/// `J(x) v ≈ (F(x + h v) - F(x)) / h`, with `h = sqrt(EPSILON) * (1 + ||x||) / ||v||`,
+///
+/// which cost one evaluation of `F` each. This makes the method suitable for large systems, in
+/// particular for stiff systems where fixed-point iterations and Broyden-type methods struggle.
+///
+/// GMRES stops once the linear residual satisfies `||J d + F|| <= eta * ||F||` or after
+/// [`krylov_dim`](`NewtonKrylov::with_krylov_dim`) iterations (30 by default). The forcing term
+/// `eta` is either fixed (see [`with_forcing_term`](`NewtonKrylov::with_forcing_term`)) or chosen
+/// adaptively following choice 2 of Eisenstat and Walker (default), which gives loose linear
+/// solves far from the solution and fast local convergence close to it.
+///
+/// The Newton step is globalized by a backtracking line search on `||F||`: The step length is
+/// halved until `||F(x + t d)|| <= (1 - 1e-4 * t * (1 - eta)) ||F(x)||`. If no sufficient decrease
+/// is found after 30 reductions, the solver terminates.
+///
+/// The cost function value is `||F(x)||`, which is also stored as residuals in the state. The
+/// algorithm stops when `||F(x)||` is below a tolerance (`sqrt(EPSILON)` by default, see
+/// [`with_tolerance_residual`](`NewtonKrylov::with_tolerance_residual`)) or when the change of the
+/// cost function value is below `EPSILON`. Further tolerances can be set via
+/// [`with_tolerances`](`NewtonKrylov::with_tolerances`).
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`], which returns `F(x)`. The
+/// output must be of the same type as the parameter vector.
+///
+/// ## References
+///
+/// Knoll, D. A., Keyes, D. E. (2004): Jacobian-free Newton–Krylov methods: a survey of approaches
+/// and applications. J. Comput. Phys. 193, 357–397.
+///
+/// Eisenstat, S. C., Walker, H. F. (1996): Choosing the forcing terms in an inexact Newton
+/// method. SIAM J. Sci. Comput. 17, 16–32.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NewtonKrylov<F> {
+    /// Maximum dimension of the Krylov subspace
+    krylov_dim: usize,
+    /// Fixed forcing term (`None`: Eisenstat-Walker)
+    forcing_term: Option<F>,
+    /// Forcing term of the previous iteration
+    eta: F,
+    /// Upper bound of the adaptive forcing terms
+    eta_max: F,
+    /// Tolerance on `||F(x)||`
+    tol_residual: F,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
+}
+
+impl<F> NewtonKrylov<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`NewtonKrylov`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newtonkrylov::NewtonKrylov;
+    /// let nk: NewtonKrylov<f64> = NewtonKrylov::new();
+    /// ```
+    pub fn new() -> Self {
+        NewtonKrylov {
+            krylov_dim: 30,
+            forcing_term: None,
+            eta: float!(0.9),
+            eta_max: float!(0.9),
+            tol_residual: F::epsilon().sqrt(),
+            tol: ToleranceConfig::with_defaults(float!(0.0), F::epsilon()),
+        }
+    }
+
+    /// Set the maximum dimension of the Krylov subspace, which is the maximum number of GMRES
+    /// iterations per Newton iteration.
+    ///
+    /// Must be larger than 0 and defaults to 30.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newtonkrylov::NewtonKrylov;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nk: NewtonKrylov<f64> = NewtonKrylov::new().with_krylov_dim(50)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_krylov_dim(mut self, krylov_dim: usize) -> Result<Self, Error> {
+        if krylov_dim == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NewtonKrylov`: Krylov subspace dimension must be > 0."
+            ));
+        }
+        self.krylov_dim = krylov_dim;
+        Ok(self)
+    }
+
+    /// Use a fixed forcing term `eta`, the required relative reduction of the linear residual in
+    /// each Newton iteration, instead of the adaptive choice of Eisenstat and Walker.
+    ///
+    /// Must be in `(0, 1)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newtonkrylov::NewtonKrylov;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nk: NewtonKrylov<f64> = NewtonKrylov::new().with_forcing_term(1e-4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_forcing_term(mut self, eta: F) -> Result<Self, Error> {
+        if eta <= float!(0.0) || eta >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NewtonKrylov`: forcing term must be in (0, 1)."
+            ));
+        }
+        self.forcing_term = Some(eta);
+        Ok(self)
+    }
+
+    /// The algorithm stops if `||F(x)||` is below `tol_residual`.
+    ///
+    /// Must be non-negative and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newtonkrylov::NewtonKrylov;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nk: NewtonKrylov<f64> = NewtonKrylov::new().with_tolerance_residual(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_residual(mut self, tol_residual: F) -> Result<Self, Error> {
+        if tol_residual < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NewtonKrylov`: residual tolerance must be >= 0."
+            ));
+        }
+        self.tol_residual = tol_residual;
+        Ok(self)
+    }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// The gradient tolerance is not used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newtonkrylov::NewtonKrylov;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let nk: NewtonKrylov<f64> = NewtonKrylov::new().with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Forcing term for the current iteration (choice 2 of Eisenstat and Walker with safeguards)
+    fn forcing_term(&self, prev_norm: F, norm: F) -> F {
+        if let Some(eta) = self.forcing_term {
+            return eta;
+        }
+        if !prev_norm.is_finite() {
+            return self.eta_max;
+        }
+        let gamma: F = float!(FORCING_GAMMA);
+        let eta = gamma * (norm / prev_norm).powi(2);
+        let safeguard = gamma * self.eta.powi(2);
+        let eta = if safeguard > float!(0.1) {
+            eta.max(safeguard)
+        } else {
+            eta
+        };
+        eta.min(self.eta_max)
+    }
+}
+
+impl<F> Default for NewtonKrylov<F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        NewtonKrylov::new()
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), P, F>> for NewtonKrylov<F>
+where
+    O: Operator<Param = P, Output = P>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminL2Norm<F>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "Newton-Krylov"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), P, F>,
+    ) -> Result<(IterState<P, (), (), (), P, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`NewtonKrylov` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let residuals = problem.apply(&param)?;
+        let cost = residuals.l2_norm();
+        self.eta = self.eta_max;
+        Ok((state.param(param).residuals(residuals).cost(cost), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), P, F>,
+    ) -> Result<(IterState<P, (), (), (), P, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`NewtonKrylov`: `param` not set"
+        ))?;
+        let residuals = state.take_residuals().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`NewtonKrylov`: `residuals` not set"
+        ))?;
+        let norm = state.get_cost();
+        let eta = self.forcing_term(state.get_prev_cost(), norm);
+        self.eta = eta;
+
+        // Solve J d = -F approximately
+        let h_base = F::epsilon().sqrt() * (float!(1.0) + param.l2_norm());
+        let (direction, krylov_iters, linear_residual) = gmres_cycle(
+            |v: &P| {
+                let v_norm = v.l2_norm();
+                if v_norm <= float!(0.0) {
+                    return Ok(v.zero_like());
+                }
+                let h = h_base / v_norm;
+                let perturbed = problem.apply(&param.scaled_add(&h, v))?;
+                Ok(perturbed.sub(&residuals).mul(&(float!(1.0) / h)))
+            },
+            |v: &P| Ok(v.clone()),
+            &residuals.mul(&float!(-1.0)),
+            eta * norm,
+            self.krylov_dim,
+        )?;
+
+        // Backtracking line search on ||F||
+        let mut step_length = float!(1.0);
+        for _ in 0..=MAX_BACKTRACKS {
+            let new_param = param.scaled_add(&step_length, &direction);
+            let new_residuals = problem.apply(&new_param)?;
+            let new_norm = new_residuals.l2_norm();
+            let decrease =
+                float!(1.0) - float!(SUFFICIENT_DECREASE) * step_length * (float!(1.0) - eta);
+            if new_norm <= decrease * norm {
+                return Ok((
+                    state
+                        .param(new_param)
+                        .residuals(new_residuals)
+                        .cost(new_norm),
+                    Some(kv!(
+                        "eta" => eta;
+                        "krylov_iters" => krylov_iters as u64;
+                        "linear_residual" => linear_residual;
+                        "step_length" => step_length;
+                    )),
+                ));
+            }
+            step_length = step_length * float!(0.5);
+        }
+
+        Ok((
+            state
+                .param(param)
+                .residuals(residuals)
+                .terminate_with(TerminationReason::SolverExit(
+                    "Line search failed to reduce the residual norm".to_string(),
+                )),
+            Some(kv!(
+                "eta" => eta;
+                "krylov_iters" => krylov_iters as u64;
+                "linear_residual" => linear_residual;
+            )),
+        ))
+    }
+
+    fn termination_diagnostics(&self, state: &IterState<P, (), (), (), P, F>) -> KV {
+        let mut kv = self.tol.diagnostics(
+            None,
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        );
+        kv.insert("residual_norm", state.get_cost().into());
+        kv.insert("residual_tol", self.tol_residual.into());
+        kv.insert(
+            "residual_converged",
+            (state.get_cost() <= self.tol_residual).into(),
+        );
+        kv
+    }
+
+    fn terminate(&mut self, state: &IterState<P, (), (), (), P, F>) -> TerminationStatus {
+        if state.get_cost() <= self.tol_residual
+            || self
+                .tol
+                .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(newton_krylov, NewtonKrylov<f64>);
+
+    /// `F(x) = (x_0^2 + x_1^2 - 4, x_0 - x_1)`, with roots `±(sqrt(2), sqrt(2))`
+    struct Circle {}
+
+    impl Operator for Circle {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(vec![p[0].powi(2) + p[1].powi(2) - 4.0, p[0] - p[1]])
+        }
+    }
+
+    /// Broyden tridiagonal function
+    struct BroydenTridiagonal {}
+
+    impl Operator for BroydenTridiagonal {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let n = p.len();
+            Ok((0..n)
+                .map(|i| {
+                    let prev = if i > 0 { p[i - 1] } else { 0.0 };
+                    let next = if i + 1 < n { p[i + 1] } else { 0.0 };
+                    (3.0 - 2.0 * p[i]) * p[i] - prev - 2.0 * next + 1.0
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let NewtonKrylov {
+            krylov_dim,
+            forcing_term,
+            eta,
+            eta_max,
+            tol_residual,
+            tol,
+        }: NewtonKrylov<f64> = NewtonKrylov::new();
+
+        assert_eq!(krylov_dim, 30);
+        assert!(forcing_term.is_none());
+        assert_eq!(eta.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert_eq!(eta_max.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert_eq!(
+            tol_residual.to_ne_bytes(),
+            f64::EPSILON.sqrt().to_ne_bytes()
+        );
+        assert_eq!(tol.cost().to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_krylov_dim() {
+        let NewtonKrylov { krylov_dim, .. } =
+            NewtonKrylov::<f64>::new().with_krylov_dim(5).unwrap();
+        assert_eq!(krylov_dim, 5);
+
+        let res = NewtonKrylov::<f64>::new().with_krylov_dim(0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`NewtonKrylov`: Krylov subspace dimension must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_with_forcing_term() {
+        let NewtonKrylov { forcing_term, .. } =
+            NewtonKrylov::<f64>::new().with_forcing_term(0.1).unwrap();
+        assert_eq!(forcing_term, Some(0.1));
+
+        for eta in [0.0, 1.0, -0.5, 2.0] {
+            let res = NewtonKrylov::<f64>::new().with_forcing_term(eta);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`NewtonKrylov`: forcing term must be in (0, 1).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_tolerance_residual() {
+        let NewtonKrylov { tol_residual, .. } = NewtonKrylov::<f64>::new()
+            .with_tolerance_residual(1e-10)
+            .unwrap();
+        assert_eq!(tol_residual.to_ne_bytes(), 1e-10f64.to_ne_bytes());
+
+        let res = NewtonKrylov::<f64>::new().with_tolerance_residual(-1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`NewtonKrylov`: residual tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_forcing_term() {
+        let nk: NewtonKrylov<f64> = NewtonKrylov::new();
+        // First iteration
+        assert_relative_eq!(nk.forcing_term(f64::INFINITY, 1.0), 0.9);
+        // 0.9 * 0.5^2, safeguarded by 0.9 * 0.9^2
+        assert_relative_eq!(nk.forcing_term(1.0, 0.5), 0.729);
+
+        let mut nk = nk;
+        nk.eta = 0.1;
+        assert_relative_eq!(nk.forcing_term(1.0, 0.1), 0.009);
+
+        let nk = nk.with_forcing_term(1e-3).unwrap();
+        assert_relative_eq!(nk.forcing_term(1.0, 0.5), 1e-3);
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut nk: NewtonKrylov<f64> = NewtonKrylov::new();
+        let res = nk.init(&mut Problem::new(Circle {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`NewtonKrylov` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let res = Executor::new(Circle {}, NewtonKrylov::new())
+            .configure(|state| state.param(vec![1.0, 0.5]).max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 2.0f64.sqrt(), epsilon = 1e-7);
+        assert_relative_eq!(param[1], 2.0f64.sqrt(), epsilon = 1e-7);
+
+        let solver = NewtonKrylov::new()
+            .with_krylov_dim(10)
+            .unwrap()
+            .with_tolerance_residual(1e-10)
+            .unwrap();
+        let res = Executor::new(BroydenTridiagonal {}, solver)
+            .configure(|state| state.param(vec![-1.0; 100]).max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert!(res.state.get_best_cost() <= 1e-10);
+    }
+}
@@ -0,0 +1,441 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Nonnegative least squares
+//!
+//! Solves `min ||Ax - b||_2 subject to x >= 0` via a projected gradient method.
+//! See [`Nnls`] for details.
+//!
+//! ## References
+//!
+//! Birgin, E. G., Martínez, J. M., Raydan, M. (2000): Nonmonotone spectral projected gradient
+//! methods on convex sets. SIAM J. Optim. 10, 1196–1211.
+//!
+//! Kim, D., Sra, S., Dhillon, I. S. (2013): A non-monotonic method for large-scale non-negative
+//! least squares. Optim. Methods Softw. 28, 1012–1039.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Jacobian, Operator, Problem, Solver, State, TerminationReason,
+    TerminationStatus, ToleranceConfig, KV,
+};
+use argmin_math::{
+    ArgminDot, ArgminL2Norm, ArgminMinMax, ArgminScaledAdd, ArgminScaledSub, ArgminSub,
+    ArgminTranspose, ArgminZeroLike,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Nonnegative least squares
+///
+/// Solves the linear least squares problem `min ||Ax - b||_2` subject to `x >= 0` (element-wise),
+/// which frequently appears as a subproblem, for instance in alternating least squares for
+/// nonnegative matrix factorization or in ADMM.
+///
+/// The method is a projected gradient method with spectral (Barzilai-Borwein) step lengths: In
+/// each iteration, a gradient step with the current step length `alpha` is projected onto the
+/// nonnegative orthant, which gives a feasible search direction `d`. Along `d`, the quadratic
+/// objective is minimized exactly on the segment which stays feasible. The next step length is the
+/// inverse Rayleigh quotient `d^T d / d^T A^T A d`. The initial step length is the Cauchy step
+/// along the gradient. Each iteration requires one evaluation of the residuals and two
+/// matrix-vector products.
+///
+/// The algorithm stops when the norm of the projected gradient `max(x - g, 0) - x`, where `g` is
+/// the gradient `A^T (Ax - b)`, is below a tolerance (`sqrt(EPSILON)` by default, see
+/// [`with_tolerance_grad`](`Nnls::with_tolerance_grad`)). Tolerances on the change of the cost and
+/// the parameter vector can be set via [`with_tolerances`](`Nnls::with_tolerances`).
+///
+/// The cost function value is the norm of the residuals, as for the
+/// [`GaussNewton`](`crate::solver::gaussnewton::GaussNewton`) method.
+///
+/// Requires an initial parameter vector, which allows warm starts when the solver is used within
+/// other methods. Negative elements of the initial parameter vector are set to zero.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`], which returns the residuals
+/// `Ax - b`, and [`Jacobian`], which returns `A`. Since the problem is linear, the Jacobian is only
+/// computed once during initialization; it is stored in the state.
+///
+/// ## References
+///
+/// Birgin, E. G., Martínez, J. M., Raydan, M. (2000): Nonmonotone spectral projected gradient
+/// methods on convex sets. SIAM J. Optim. 10, 1196–1211.
+///
+/// Kim, D., Sra, S., Dhillon, I. S. (2013): A non-monotonic method for large-scale non-negative
+/// least squares. Optim. Methods Softw. 28, 1012–1039.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Nnls<J, F> {
+    /// Transpose of the Jacobian
+    jacobian_t: Option<J>,
+    /// Spectral step length
+    alpha: F,
+    /// Norm of the projected gradient
+    projected_grad_norm: F,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
+}
+
+impl<J, F> Nnls<J, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`Nnls`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nnls::Nnls;
+    /// let nnls: Nnls<Vec<Vec<f64>>, f64> = Nnls::new();
+    /// ```
+    pub fn new() -> Self {
+        Nnls {
+            jacobian_t: None,
+            alpha: float!(1.0),
+            projected_grad_norm: F::infinity(),
+            tol: ToleranceConfig::with_defaults(F::epsilon().sqrt(), float!(0.0)),
+        }
+    }
+
+    /// The algorithm stops if the norm of the projected gradient is below `tol_grad`.
+    ///
+    /// The provided value must be non-negative. Defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nnls::Nnls;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nnls: Nnls<Vec<Vec<f64>>, f64> = Nnls::new().with_tolerance_grad(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_grad(mut self, tol_grad: F) -> Result<Self, Error> {
+        if tol_grad < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Nnls`: gradient tolerance must be >= 0."
+            ));
+        }
+        self.tol = self.tol.with_grad(tol_grad)?;
+        Ok(self)
+    }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// The gradient tolerance applies to the projected gradient.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nnls::Nnls;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let nnls: Nnls<Vec<Vec<f64>>, f64> = Nnls::new().with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+}
+
+impl<J, F> Default for Nnls<J, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        Nnls::new()
+    }
+}
+
+/// Norm of the projected gradient `max(x - g, 0) - x`
+fn projected_grad_norm<P, F>(param: &P, grad: &P) -> F
+where
+    P: ArgminSub<P, P> + ArgminMinMax + ArgminZeroLike + ArgminL2Norm<F>,
+{
+    P::max(&param.sub(grad), &param.zero_like())
+        .sub(param)
+        .l2_norm()
+}
+
+impl<O, P, J, R, F> Solver<O, IterState<P, P, J, (), R, F>> for Nnls<J, F>
+where
+    O: Operator<Param = P, Output = R> + Jacobian<Param = P, Jacobian = J>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>
+        + ArgminMinMax
+        + ArgminZeroLike
+        + ArgminDot<P, F>
+        + ArgminL2Norm<F>,
+    R: ArgminDot<R, F> + ArgminL2Norm<F>,
+    J: Clone + ArgminTranspose<J> + ArgminDot<P, R> + ArgminDot<R, P>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "NNLS"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, J, (), R, F>,
+    ) -> Result<(IterState<P, P, J, (), R, F>, Option<KV>), Error> {
+        let init_param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`Nnls` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let param = P::max(&init_param, &init_param.zero_like());
+        let residuals = problem.apply(&param)?;
+        let jacobian = problem.jacobian(&param)?;
+        let jacobian_t = jacobian.clone().t();
+        let grad: P = jacobian_t.dot(&residuals);
+
+        // Cauchy step along the gradient
+        let jg: R = jacobian.dot(&grad);
+        let gqg = jg.dot(&jg);
+        let alpha = grad.dot(&grad) / gqg;
+        self.alpha = if gqg > float!(0.0) && alpha.is_finite() {
+            alpha
+        } else {
+            float!(1.0)
+        };
+        self.projected_grad_norm = projected_grad_norm(&param, &grad);
+        self.jacobian_t = Some(jacobian_t);
+
+        let cost = residuals.l2_norm();
+        Ok((
+            state
+                .param(param)
+                .residuals(residuals)
+                .jacobian(jacobian)
+                .gradient(grad)
+                .cost(cost),
+            Some(kv!("alpha" => self.alpha;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, J, (), R, F>,
+    ) -> Result<(IterState<P, P, J, (), R, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Nnls`: `param` not set"
+        ))?;
+        let grad = state.take_gradient().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Nnls`: `gradient` not set"
+        ))?;
+        let jacobian = state.get_jacobian().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Nnls`: `jacobian` not set"
+        ))?;
+        let jacobian_t = self.jacobian_t.as_ref().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Nnls`: transposed Jacobian not set"
+        ))?;
+
+        // Feasible direction towards the projected gradient step
+        let trial = P::max(&param.scaled_sub(&self.alpha, &grad), &param.zero_like());
+        let direction = trial.sub(&param);
+        let slope = grad.dot(&direction);
+        if slope >= float!(0.0) || slope.is_nan() {
+            // The projected gradient vanishes
+            self.projected_grad_norm = float!(0.0);
+            return Ok((state.param(param).gradient(grad), None));
+        }
+
+        // Exact minimization of the quadratic along the direction, restricted to the feasible
+        // segment `[0, 1]`
+        let jd: R = jacobian.dot(&direction);
+        let curvature = jd.dot(&jd);
+        let step = if curvature > float!(0.0) {
+            (-slope / curvature).min(float!(1.0))
+        } else {
+            float!(1.0)
+        };
+        if curvature > float!(0.0) {
+            self.alpha = direction.dot(&direction) / curvature;
+        }
+
+        let new_param = param.scaled_add(&step, &direction);
+        let residuals = problem.apply(&new_param)?;
+        let new_grad: P = jacobian_t.dot(&residuals);
+        self.projected_grad_norm = projected_grad_norm(&new_param, &new_grad);
+        let cost = residuals.l2_norm();
+
+        Ok((
+            state
+                .param(new_param)
+                .residuals(residuals)
+                .gradient(new_grad)
+                .cost(cost),
+            Some(kv!(
+                "step" => step;
+                "alpha" => self.alpha;
+                "projected_gradient_norm" => self.projected_grad_norm;
+            )),
+        ))
+    }
+
+    fn termination_diagnostics(&self, state: &IterState<P, P, J, (), R, F>) -> KV {
+        self.tol.diagnostics(
+            Some(self.projected_grad_norm),
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
+    }
+
+    fn terminate(&mut self, state: &IterState<P, P, J, (), R, F>) -> TerminationStatus {
+        if self
+            .tol
+            .grad_converged(self.projected_grad_norm, state.get_cost())
+            || self
+                .tol
+                .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(nnls, Nnls<Vec<Vec<f64>>, f64>);
+
+    /// `A = [[1, 0], [0, 1], [1, 1]]`, `b = [2, -1, 1]`
+    ///
+    /// The unconstrained minimizer is `(2, -1)`, the constrained one is `(1.5, 0)`.
+    struct TestProblem {}
+
+    impl Operator for TestProblem {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(vec![p[0] - 2.0, p[1] + 1.0, p[0] + p[1] - 1.0])
+        }
+    }
+
+    impl Jacobian for TestProblem {
+        type Param = Vec<f64>;
+        type Jacobian = Vec<Vec<f64>>;
+
+        fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]])
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let Nnls {
+            jacobian_t,
+            alpha,
+            projected_grad_norm,
+            tol,
+        }: Nnls<Vec<Vec<f64>>, f64> = Nnls::new();
+
+        assert!(jacobian_t.is_none());
+        assert_eq!(alpha.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(projected_grad_norm.is_infinite());
+        assert_eq!(tol.grad().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(tol.cost().to_ne_bytes(), 0.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_tolerance_grad() {
+        let Nnls { tol, .. }: Nnls<Vec<Vec<f64>>, f64> =
+            Nnls::new().with_tolerance_grad(1e-4).unwrap();
+        assert_eq!(tol.grad().to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        let res: Result<Nnls<Vec<Vec<f64>>, f64>, _> = Nnls::new().with_tolerance_grad(-1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Nnls`: gradient tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut nnls: Nnls<Vec<Vec<f64>>, f64> = Nnls::new();
+        let res = nnls.init(&mut Problem::new(TestProblem {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Nnls` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut nnls: Nnls<Vec<Vec<f64>>, f64> = Nnls::new();
+        let (state, _) = nnls
+            .init(
+                &mut Problem::new(TestProblem {}),
+                IterState::new().param(vec![-1.0, 1.0]),
+            )
+            .unwrap();
+
+        // Infeasible initial parameter vectors are projected
+        assert_eq!(state.get_param().unwrap(), &vec![0.0, 1.0]);
+        // r = (-2, 2, 0), g = (-2, 2)
+        assert_eq!(state.get_gradient().unwrap(), &vec![-2.0, 2.0]);
+        assert_relative_eq!(state.get_cost(), 8.0f64.sqrt(), epsilon = 1e-12);
+        // Cauchy step: g^T g / ||A g||^2 = 8 / 8
+        assert_relative_eq!(nnls.alpha, 1.0, epsilon = 1e-12);
+        // max(x - g, 0) - x = (2, 0) - (0, 1)
+        assert_relative_eq!(nnls.projected_grad_norm, 5.0f64.sqrt(), epsilon = 1e-12);
+        assert_eq!(
+            nnls.jacobian_t,
+            Some(vec![vec![1.0, 0.0, 1.0], vec![0.0, 1.0, 1.0]])
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        for init_param in [vec![0.0, 0.0], vec![5.0, 5.0], vec![1.5, 0.0]] {
+            let res = Executor::new(TestProblem {}, Nnls::new())
+                .configure(|state| state.param(init_param).max_iters(100))
+                .run()
+                .unwrap();
+
+            assert_eq!(
+                res.state.get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            let param = res.state.get_best_param().unwrap();
+            assert_relative_eq!(param[0], 1.5, epsilon = 1e-8);
+            assert_relative_eq!(param[1], 0.0, epsilon = 1e-8);
+        }
+    }
+}
@@ -21,12 +21,20 @@
 //! \[1\] <https://en.wikipedia.org/wiki/Particle_swarm_optimization>
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, PopulationState, Problem, Solver, SyncAlias, KV,
+    ArgminFloat, CostFunction, Error, PopulationState, Problem, Solver, State, StateCodec,
+    SyncAlias, KV,
+};
+use argmin_math::{
+    ArgminAdd, ArgminL2Norm, ArgminMinMax, ArgminMul, ArgminRandom, ArgminSub, ArgminZeroLike,
 };
-use argmin_math::{ArgminAdd, ArgminMinMax, ArgminMul, ArgminRandom, ArgminSub, ArgminZeroLike};
 use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::io::{Read, Write};
+#[cfg(feature = "rayon")]
+use std::sync::Arc;
 
 /// # Particle Swarm Optimization (PSO)
 ///
@@ -35,7 +43,17 @@ use serde::{Deserialize, Serialize};
 ///
 /// The `rayon` feature enables parallel computation of the cost function. This can be beneficial
 /// for expensive cost functions, but may cause a drop in performance for cheap cost functions. Be
-/// sure to benchmark both parallel and sequential computation.
+/// sure to benchmark both parallel and sequential computation. Parallel evaluation can be turned
+/// off with [`with_parallel`](`ParticleSwarm::with_parallel`) and the number of threads can be
+/// limited with [`with_num_threads`](`ParticleSwarm::with_num_threads`). The results do not depend
+/// on whether the cost function is evaluated in parallel: The random numbers are drawn
+/// sequentially and the costs are assigned to the particles in order, therefore a run with a
+/// seeded RNG is reproducible.
+///
+/// If a time limit is set via [`Executor::max_time`](`crate::core::Executor::max_time`) and
+/// parallel evaluation is turned off, the evaluation of the swarm stops as soon as the time limit
+/// is exhausted. Particles which were not evaluated keep their previous position and
+/// velocity.
 ///
 /// ## Requirements on the optimization problem
 ///
@@ -63,9 +81,18 @@ pub struct ParticleSwarm<P, F, R> {
     num_particles: usize,
     /// Random number generator
     rng_generator: R,
+    /// Evaluate the cost function of the particles in parallel
+    parallel: bool,
+    /// Number of threads used for parallel evaluation (`None` uses the global thread pool)
+    #[cfg(feature = "rayon")]
+    num_threads: Option<usize>,
+    /// Thread pool with `num_threads` threads, built on first use
+    #[cfg(feature = "rayon")]
+    #[cfg_attr(feature = "serde1", serde(skip))]
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
 }
 
-impl<P, F> ParticleSwarm<P, F, rand::rngs::StdRng>
+impl<P, F> ParticleSwarm<P, F, Xoshiro256PlusPlus>
 where
     P: Clone + SyncAlias + ArgminSub<P, P> + ArgminMul<F, P> + ArgminRandom + ArgminZeroLike,
     F: ArgminFloat,
@@ -75,6 +102,7 @@ where
     /// Takes the number of particles and bounds on the search space as inputs. `bounds` is a tuple
     /// `(lower_bound, upper_bound)`, where `lower_bound` and `upper_bound` are of the same type as
     /// the position of a particle (`P`) and of the same length as the problem as dimensions.
+    /// The number of particles must be larger than 0, otherwise the run fails with an error.
     ///
     /// The inertia weight on velocity and the social and cognitive acceleration factors can be
     /// adapted with [`with_inertia_factor`](`ParticleSwarm::with_inertia_factor`),
@@ -102,7 +130,12 @@ where
             weight_social: float!(0.5 + 2.0f64.ln()),
             bounds,
             num_particles,
-            rng_generator: rand::rngs::StdRng::from_entropy(),
+            rng_generator: Xoshiro256PlusPlus::from_entropy(),
+            parallel: true,
+            #[cfg(feature = "rayon")]
+            num_threads: None,
+            #[cfg(feature = "rayon")]
+            thread_pool: None,
         }
     }
 }
@@ -114,7 +147,10 @@ where
 {
     /// Set the random number generator
     ///
-    /// Defaults to `rand::rngs::StdRng::from_entropy()`
+    /// Defaults to `rand_xoshiro::Xoshiro256PlusPlus::from_entropy()`. The state of the RNG is
+    /// part of the solver and is therefore stored in checkpoints (with the `serde1` feature), which
+    /// allows one to resume a run exactly. This requires the RNG to implement `serde::Serialize`
+    /// and `serde::Deserialize`.
     ///
     /// # Example
     /// ```
@@ -138,8 +174,35 @@ where
             bounds: self.bounds,
             num_particles: self.num_particles,
             rng_generator: generator,
+            parallel: self.parallel,
+            #[cfg(feature = "rayon")]
+            num_threads: self.num_threads,
+            #[cfg(feature = "rayon")]
+            thread_pool: self.thread_pool,
         }
     }
+
+    /// Reseeds the random number generator, which makes runs reproducible.
+    ///
+    /// Only available for RNGs which implement `rand::SeedableRng`, such as the default
+    /// `Xoshiro256PlusPlus`.
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin::solver::particleswarm::ParticleSwarm;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let pso: ParticleSwarm<_, f64, _> =
+    ///     ParticleSwarm::new((lower_bound, upper_bound), 40).with_seed(1729);
+    /// ```
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self
+    where
+        R0: SeedableRng,
+    {
+        self.rng_generator = R0::seed_from_u64(seed);
+        self
+    }
 }
 
 impl<P, F, R> ParticleSwarm<P, F, R>
@@ -232,6 +295,104 @@ where
         Ok(self)
     }
 
+    /// Set whether the cost function of the particles is evaluated in parallel
+    ///
+    /// Requires the `rayon` feature, otherwise the particles are always evaluated sequentially.
+    /// Parallel evaluation can also be disabled via `parallelize` of the [`CostFunction`].
+    ///
+    /// Defaults to `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::particleswarm::ParticleSwarm;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let pso: ParticleSwarm<_, f64, _> =
+    ///     ParticleSwarm::new((lower_bound, upper_bound), 40).with_parallel(false);
+    /// ```
+    #[must_use]
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Set the number of threads used to evaluate the cost function of the particles
+    ///
+    /// The particles are evaluated in a dedicated thread pool of the given size instead of the
+    /// global `rayon` thread pool. The thread pool is not part of checkpoints and is rebuilt when
+    /// a run is resumed.
+    ///
+    /// Defaults to the global thread pool.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::particleswarm::ParticleSwarm;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let pso: ParticleSwarm<_, f64, _> =
+    ///     ParticleSwarm::new((lower_bound, upper_bound), 40).with_num_threads(4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn with_num_threads(mut self, num_threads: usize) -> Result<Self, Error> {
+        if num_threads == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ParticleSwarm`: number of threads must be > 0."
+            ));
+        }
+        self.num_threads = Some(num_threads);
+        self.thread_pool = None;
+        Ok(self)
+    }
+
+    /// Computes the cost function values of `positions`, in parallel if enabled
+    ///
+    /// If `interruptible` is set, sequential evaluation (but not bulk evaluation) stops once the
+    /// time limit is exhausted, in which case fewer costs than positions are returned.
+    fn evaluate<O, Q>(
+        &mut self,
+        problem: &mut Problem<O>,
+        positions: &[Q],
+        interruptible: bool,
+    ) -> Result<Vec<F>, Error>
+    where
+        O: CostFunction<Param = P, Output = F> + SyncAlias,
+        Q: Borrow<P> + SyncAlias,
+    {
+        if !self.parallel {
+            let mut costs = Vec::with_capacity(positions.len());
+            for position in positions {
+                if interruptible && problem.time_limit_reached() {
+                    break;
+                }
+                costs.push(problem.cost(position.borrow())?);
+            }
+            return Ok(costs);
+        }
+        #[cfg(feature = "rayon")]
+        if let Some(num_threads) = self.num_threads {
+            let pool = match &self.thread_pool {
+                Some(pool) => Arc::clone(pool),
+                None => {
+                    let pool = rayon::ThreadPoolBuilder::new()
+                        .num_threads(num_threads)
+                        .build()?;
+                    Arc::clone(self.thread_pool.insert(Arc::new(pool)))
+                }
+            };
+            return problem.bulk_problem("cost_count", positions.len(), |op| {
+                pool.install(|| op.bulk_cost(positions))
+            });
+        }
+        problem.bulk_cost(positions)
+    }
+
     /// Initializes all particles randomly and sorts them by their cost function values
     fn initialize_particles<O: CostFunction<Param = P, Output = F> + SyncAlias>(
         &mut self,
@@ -239,7 +400,7 @@ where
     ) -> Result<Vec<Particle<P, F>>, Error> {
         let (positions, velocities) = self.initialize_positions_and_velocities();
 
-        let costs = problem.bulk_cost(&positions)?;
+        let costs = self.evaluate(problem, &positions, false)?;
 
         let mut particles = positions
             .into_iter()
@@ -285,19 +446,31 @@ where
         + ArgminMul<F, P>
         + ArgminZeroLike
         + ArgminRandom
-        + ArgminMinMax,
+        + ArgminMinMax
+        + ArgminL2Norm<F>,
     F: ArgminFloat,
-    R: Rng,
+    R: Rng + SeedableRng,
 {
     fn name(&self) -> &str {
         "Particle Swarm Optimization"
     }
 
+    fn reseed(&mut self, seed: u64) {
+        self.rng_generator = R::seed_from_u64(seed);
+    }
+
     fn init(
         &mut self,
         problem: &mut Problem<O>,
         mut state: PopulationState<Particle<P, F>, F>,
     ) -> Result<(PopulationState<Particle<P, F>, F>, Option<KV>), Error> {
+        if self.num_particles == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ParticleSwarm`: Number of particles must be > 0."
+            ));
+        }
+
         // Users can provide a population or it will be randomly created.
         let particles = match state.take_population() {
             Some(mut particles) if particles.len() == self.num_particles => {
@@ -322,15 +495,43 @@ where
             None => self.initialize_particles(problem)?,
         };
 
+        let kv = population_statistics(&particles);
+        let costs = particles.iter().map(|p| p.cost).collect();
+
         Ok((
             state
                 .individual(particles[0].clone())
                 .cost(particles[0].cost)
+                .population_costs(costs)
                 .population(particles),
-            None,
+            Some(kv),
         ))
     }
 
+    /// Evaluates the cost function of the modified problem at the current positions of all
+    /// particles. The best positions found so far are discarded since their costs are not
+    /// comparable to those of the modified problem.
+    fn problem_changed(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: PopulationState<Particle<P, F>, F>,
+    ) -> Result<PopulationState<Particle<P, F>, F>, Error> {
+        let mut state = state.problem_changed();
+        let particles = state.take_population().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`ParticleSwarm`: No population in state."
+        ))?;
+        let positions: Vec<_> = particles.iter().map(|p| &p.position).collect();
+        let costs = self.evaluate(problem, &positions, false)?;
+        let particles = particles
+            .into_iter()
+            .zip(costs)
+            .map(|(p, cost)| Particle::new(p.position, cost, p.velocity))
+            .collect();
+        let (state, _) = self.init(problem, state.population(particles))?;
+        Ok(state)
+    }
+
     /// Perform one iteration of algorithm
     fn next_iter(
         &mut self,
@@ -349,8 +550,8 @@ where
 
         let zero = P::zero_like(&best_particle.position);
 
-        let positions: Vec<_> = particles
-            .iter_mut()
+        let moves: Vec<(P, P)> = particles
+            .iter()
             .map(|p| {
                 // New velocity is composed of
                 // 1) previous velocity (momentum),
@@ -372,18 +573,25 @@ where
                     P::rand_from_range(&zero, &to_global_optimum, &mut self.rng_generator)
                         .mul(&self.weight_social);
 
-                p.velocity = momentum.add(&pull_to_optimum).add(&pull_to_global_optimum);
-                let new_position = p.position.add(&p.velocity);
+                let velocity = momentum.add(&pull_to_optimum).add(&pull_to_global_optimum);
+                let new_position = p.position.add(&velocity);
 
                 // Limit to search window
-                p.position = P::min(&P::max(&new_position, &self.bounds.0), &self.bounds.1);
-                &p.position
+                let position = P::min(&P::max(&new_position, &self.bounds.0), &self.bounds.1);
+                (velocity, position)
             })
             .collect();
 
-        let costs = problem.bulk_cost(&positions)?;
+        let positions: Vec<&P> = moves.iter().map(|(_, position)| position).collect();
+        let costs = self.evaluate(problem, &positions, true)?;
 
-        for (p, c) in particles.iter_mut().zip(costs.into_iter()) {
+        // Particles which were not evaluated before the time limit was reached are not moved
+        for (p, ((velocity, position), c)) in particles
+            .iter_mut()
+            .zip(moves.into_iter().zip(costs.into_iter()))
+        {
+            p.velocity = velocity;
+            p.position = position;
             p.cost = c;
 
             if p.cost < p.best_cost {
@@ -400,16 +608,60 @@ where
             }
         }
 
+        let kv = population_statistics(&particles);
+        let costs = particles.iter().map(|p| p.cost).collect();
+
         Ok((
             state
                 .individual(best_particle)
                 .cost(best_cost)
+                .population_costs(costs)
                 .population(particles),
-            None,
+            Some(kv),
         ))
     }
 }
 
+/// Computes summary statistics of the swarm which are reported to the observers.
+///
+/// Besides the population size, this contains mean and standard deviation of the costs of all
+/// particles as well as the diversity of the swarm, defined as the mean Euclidean distance of the
+/// particles to the centroid of the swarm.
+fn population_statistics<P, F>(particles: &[Particle<P, F>]) -> KV
+where
+    P: Clone + ArgminAdd<P, P> + ArgminSub<P, P> + ArgminMul<F, P> + ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    let n = F::from_usize(particles.len()).unwrap();
+    let cost_mean = particles
+        .iter()
+        .map(|p| p.cost)
+        .fold(F::zero(), |a, c| a + c)
+        / n;
+    let cost_std = (particles
+        .iter()
+        .map(|p| (p.cost - cost_mean).powi(2))
+        .fold(F::zero(), |a, c| a + c)
+        / n)
+        .sqrt();
+    let centroid = particles[1..]
+        .iter()
+        .fold(particles[0].position.clone(), |a, p| a.add(&p.position))
+        .mul(&(F::one() / n));
+    let diversity = particles
+        .iter()
+        .map(|p| p.position.sub(&centroid).l2_norm())
+        .fold(F::zero(), |a, d| a + d)
+        / n;
+
+    kv!(
+        "population_size" => particles.len() as u64;
+        "cost_mean" => cost_mean;
+        "cost_std" => cost_std;
+        "diversity" => diversity;
+    )
+}
+
 /// A single particle
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
@@ -450,22 +702,42 @@ where
     }
 }
 
+impl<T: StateCodec, F: StateCodec> StateCodec for Particle<T, F> {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        self.position.encode(writer)?;
+        self.velocity.encode(writer)?;
+        self.cost.encode(writer)?;
+        self.best_position.encode(writer)?;
+        self.best_cost.encode(writer)
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> Result<Self, Error> {
+        Ok(Particle {
+            position: T::decode(reader)?,
+            velocity: T::decode(reader)?,
+            cost: F::decode(reader)?,
+            best_position: T::decode(reader)?,
+            best_cost: F::decode(reader)?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{test_utils::TestProblem, ArgminError, State};
+    use crate::core::{test_utils::TestProblem, ArgminError};
     use approx::assert_relative_eq;
 
     test_trait_impl!(
         particleswarm,
-        ParticleSwarm<Vec<f64>, f64, rand::rngs::StdRng>
+        ParticleSwarm<Vec<f64>, f64, Xoshiro256PlusPlus>
     );
 
     #[test]
     fn test_new() {
         let lower_bound: Vec<f64> = vec![-1.0, -1.0];
         let upper_bound: Vec<f64> = vec![1.0, 1.0];
-        let pso: ParticleSwarm<_, f64, rand::rngs::StdRng> =
+        let pso: ParticleSwarm<_, f64, Xoshiro256PlusPlus> =
             ParticleSwarm::new((lower_bound.clone(), upper_bound.clone()), 40);
         let ParticleSwarm {
             weight_inertia,
@@ -585,6 +857,194 @@ mod tests {
         }
     }
 
+    /// Runs PSO with a seeded RNG and returns the best parameter vector and the cost count
+    fn run_seeded(pso: ParticleSwarm<Vec<f64>, f64, Xoshiro256PlusPlus>) -> (Vec<f64>, u64) {
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        let res = crate::core::Executor::new(Rosenbrock {}, pso.with_seed(42))
+            .configure(|state| state.max_iters(20).counting(true))
+            .run()
+            .unwrap();
+        let best = res.state().get_best_param().unwrap().position.clone();
+        (best, res.state().get_func_counts()["cost_count"])
+    }
+
+    #[test]
+    fn test_with_parallel() {
+        let bounds = (vec![-2.0, -2.0], vec![2.0, 2.0]);
+        let pso: ParticleSwarm<_, f64, _> = ParticleSwarm::new(bounds.clone(), 20);
+        assert!(pso.parallel);
+        let pso = pso.with_parallel(false);
+        assert!(!pso.parallel);
+
+        let (best_seq, count_seq) = run_seeded(pso);
+        let (best_par, count_par) = run_seeded(ParticleSwarm::new(bounds, 20));
+        assert_eq!(count_seq, 20 * 21);
+        assert_eq!(count_seq, count_par);
+        for (s, p) in best_seq.iter().zip(best_par.iter()) {
+            assert_eq!(s.to_ne_bytes(), p.to_ne_bytes());
+        }
+    }
+
+    #[test]
+    fn test_time_limit() {
+        use crate::core::{Executor, TerminationReason, TerminationStatus};
+        use std::time::Duration;
+
+        struct Slow {}
+
+        impl CostFunction for Slow {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                std::thread::sleep(Duration::from_millis(2));
+                Ok(p.iter().map(|x| x.powi(2)).sum())
+            }
+        }
+
+        let pso = ParticleSwarm::new((vec![-1.0, -1.0], vec![1.0, 1.0]), 20)
+            .with_seed(42)
+            .with_parallel(false);
+        let res = Executor::new(Slow {}, pso)
+            .configure(|state| state.max_iters(10).counting(true))
+            .max_time(Duration::from_millis(10))
+            .run()
+            .unwrap();
+
+        // The initialization alone exhausts the time limit, therefore the particles are not
+        // evaluated again in the first iteration.
+        assert_eq!(res.state().get_func_counts()["cost_count"], 20);
+        assert_eq!(res.state().get_iter(), 1);
+        assert_eq!(
+            res.state().get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::TimeLimitReached)
+        );
+    }
+
+    #[test]
+    fn test_deterministic() {
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        // Seeded from entropy, but reseeded by the `Executor`
+        let run = || {
+            let pso = ParticleSwarm::new((vec![-2.0, -2.0], vec![2.0, 2.0]), 10);
+            let res = crate::core::Executor::new(Rosenbrock {}, pso)
+                .configure(|state| state.max_iters(10))
+                .deterministic(1729)
+                .run()
+                .unwrap();
+            res.state().get_best_param().unwrap().position.clone()
+        };
+
+        let first = run();
+        let second = run();
+        assert_eq!(
+            first.iter().map(|x| x.to_ne_bytes()).collect::<Vec<_>>(),
+            second.iter().map(|x| x.to_ne_bytes()).collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_resume_from_checkpoint() {
+        use crate::core::test_utils::MemoryCheckpoint;
+        use crate::core::{Executor, TerminationReason, TerminationStatus};
+
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        let run = |checkpoint: Option<MemoryCheckpoint>, stop_at: u64| {
+            let pso = ParticleSwarm::new((vec![-2.0, -2.0], vec![2.0, 2.0]), 10).with_seed(42);
+            let executor = Executor::new(Rosenbrock {}, pso)
+                .configure(|state| state.max_iters(20))
+                .add_termination_criterion(
+                    move |state: &PopulationState<Particle<Vec<f64>, f64>, f64>| {
+                        if state.get_iter() >= stop_at {
+                            TerminationStatus::Terminated(TerminationReason::Interrupt)
+                        } else {
+                            TerminationStatus::NotTerminated
+                        }
+                    },
+                );
+            match checkpoint {
+                Some(checkpoint) => executor.checkpointing(checkpoint),
+                None => executor,
+            }
+            .run()
+            .unwrap()
+        };
+
+        let uninterrupted = run(None, u64::MAX);
+
+        // Interrupted after 7 iterations and resumed from the checkpoint
+        let checkpoint = MemoryCheckpoint::default();
+        run(Some(checkpoint.clone()), 7);
+        let resumed = run(Some(checkpoint), u64::MAX);
+
+        assert_eq!(resumed.state.get_iter(), 20);
+        let positions = |state: &PopulationState<Particle<Vec<f64>, f64>, f64>| {
+            state
+                .get_population()
+                .unwrap()
+                .iter()
+                .flat_map(|particle| particle.position.iter().map(|x| x.to_ne_bytes()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(positions(&resumed.state), positions(&uninterrupted.state));
+        assert_eq!(
+            resumed.state.get_best_cost().to_ne_bytes(),
+            uninterrupted.state.get_best_cost().to_ne_bytes()
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_with_num_threads() {
+        let bounds = (vec![-2.0, -2.0], vec![2.0, 2.0]);
+        let pso: ParticleSwarm<_, f64, _> = ParticleSwarm::new(bounds.clone(), 20);
+        assert_error!(
+            pso.clone().with_num_threads(0),
+            ArgminError,
+            "Invalid parameter: \"`ParticleSwarm`: number of threads must be > 0.\""
+        );
+        let pso = pso.with_num_threads(2).unwrap();
+        assert_eq!(pso.num_threads, Some(2));
+
+        let (best_threads, count_threads) = run_seeded(pso);
+        let (best_seq, count_seq) = run_seeded(ParticleSwarm::new(bounds, 20).with_parallel(false));
+        assert_eq!(count_threads, count_seq);
+        for (t, s) in best_threads.iter().zip(best_seq.iter()) {
+            assert_eq!(t.to_ne_bytes(), s.to_ne_bytes());
+        }
+    }
+
     #[test]
     fn test_initialize_positions_and_velocities() {
         let lower_bound: Vec<f64> = vec![-1.0, -1.0];
@@ -696,6 +1156,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_init_no_particles() {
+        let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+        let upper_bound: Vec<f64> = vec![1.0, 1.0];
+        let mut pso: ParticleSwarm<_, f64, _> = ParticleSwarm::new((lower_bound, upper_bound), 0);
+        for state in [
+            PopulationState::new(),
+            PopulationState::new().population(vec![]),
+        ] {
+            let res = pso.init(&mut Problem::new(TestProblem::new()), state);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`ParticleSwarm`: Number of particles must be > 0.\""
+            );
+        }
+    }
+
     #[test]
     fn test_init_provided_population_correct_size() {
         let lower_bound: Vec<f64> = vec![-1.0, -1.0];
@@ -708,7 +1186,15 @@ mod tests {
         let res = pso.init(&mut Problem::new(TestProblem::new()), state);
         assert!(res.is_ok());
         let (mut state, kv) = res.unwrap();
-        assert!(kv.is_none());
+        let kv = kv.unwrap();
+        assert_eq!(kv.get("population_size").unwrap().get_uint(), Some(2));
+        assert_relative_eq!(kv.get("cost_mean").unwrap().get_float().unwrap(), 11.0);
+        assert_relative_eq!(kv.get("cost_std").unwrap().get_float().unwrap(), 1.0);
+        assert_relative_eq!(
+            kv.get("diversity").unwrap().get_float().unwrap(),
+            0.5f64.sqrt()
+        );
+        assert_eq!(state.get_population_costs(), Some(&vec![10.0, 12.0]));
         assert_eq!(*state.get_param().unwrap(), particle_b);
         let population = state.take_population().unwrap();
         // assert that it was sorted!
@@ -725,12 +1211,42 @@ mod tests {
         let res = pso.init(&mut Problem::new(TestProblem::new()), state);
         assert!(res.is_ok());
         let (mut state, kv) = res.unwrap();
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap().get("population_size").unwrap().get_uint(),
+            Some(40)
+        );
         assert!(state.get_param().is_some());
+        assert_eq!(state.get_population_costs().unwrap().len(), 40);
         let population = state.take_population().unwrap();
         assert_eq!(population.len(), 40);
     }
 
+    #[test]
+    fn test_problem_changed() {
+        let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+        let upper_bound: Vec<f64> = vec![1.0, 1.0];
+        let mut pso: ParticleSwarm<_, f64, _> = ParticleSwarm::new((lower_bound, upper_bound), 2);
+        let state: PopulationState<Particle<Vec<f64>, f64>, f64> = PopulationState::new()
+            .population(vec![
+                Particle::new(vec![1.0, 2.0], 12.0, vec![0.1, 0.3]),
+                Particle::new(vec![0.5, 0.5], 10.0, vec![0.2, 0.4]),
+            ]);
+        let (state, _) = pso
+            .init(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+        let mut state = pso
+            .problem_changed(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+        // `TestProblem` has a cost of 1 everywhere
+        assert_eq!(state.get_population_costs(), Some(&vec![1.0, 1.0]));
+        assert_eq!(state.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(state.get_best_cost().is_infinite());
+        for particle in state.take_population().unwrap() {
+            assert_eq!(particle.best_position, particle.position);
+            assert_eq!(particle.best_cost.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        }
+    }
+
     #[test]
     fn test_next_iter() {
         struct PsoProblem {
@@ -767,9 +1283,13 @@ mod tests {
 
         // next_iter
         for _ in 0..200 {
-            (state, _) = pso.next_iter(&mut problem, state).unwrap();
+            let kv;
+            (state, kv) = pso.next_iter(&mut problem, state).unwrap();
+            assert!(kv.unwrap().get("diversity").is_some());
             let population = state.get_population().unwrap();
             assert_eq!(population.len(), 100);
+            let costs: Vec<f64> = population.iter().map(|p| p.cost).collect();
+            assert_eq!(state.get_population_costs(), Some(&costs));
             for particle in population {
                 for x in particle.position.iter() {
                     assert!(*x <= 1.0);
@@ -7,10 +7,12 @@
 
 use crate::core::{
     ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
-    OptimizationResult, Problem, Solver, TerminationReason, TerminationStatus, KV,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus,
+    ToleranceConfig, KV,
 };
 use argmin_math::{
-    ArgminAdd, ArgminDot, ArgminEye, ArgminL2Norm, ArgminMul, ArgminSub, ArgminTranspose,
+    ArgminAddAssign, ArgminDot, ArgminEye, ArgminL2Norm, ArgminMul, ArgminMulAssign, ArgminSub,
+    ArgminSubAssign, ArgminTranspose,
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -37,6 +39,10 @@ use serde::{Deserialize, Serialize};
 /// other. If the change is below this tolerance (default: `EPSILON`), the algorithm stops. This
 /// parameter can be set via [`with_tolerance_cost`](`BFGS::with_tolerance_cost`).
 ///
+/// All convergence tolerances, including a tolerance on the change of the parameter vector
+/// (disabled by default) and relative instead of absolute tolerances, can be set via
+/// [`with_tolerances`](`BFGS::with_tolerances`) (see [`ToleranceConfig`]).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
@@ -50,10 +56,8 @@ use serde::{Deserialize, Serialize};
 pub struct BFGS<L, F> {
     /// line search
     linesearch: L,
-    /// Tolerance for the stopping criterion based on the change of the norm on the gradient
-    tol_grad: F,
-    /// Tolerance for the stopping criterion based on the change of the cost stopping criterion
-    tol_cost: F,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
 }
 
 impl<L, F> BFGS<L, F>
@@ -72,8 +76,7 @@ where
     pub fn new(linesearch: L) -> Self {
         BFGS {
             linesearch,
-            tol_grad: F::epsilon().sqrt(),
-            tol_cost: F::epsilon(),
+            tol: ToleranceConfig::new(),
         }
     }
 
@@ -99,7 +102,7 @@ where
                 "`BFGS`: gradient tolerance must be >= 0."
             ));
         }
-        self.tol_grad = tol_grad;
+        self.tol = self.tol.with_grad(tol_grad)?;
         Ok(self)
     }
 
@@ -125,26 +128,49 @@ where
                 "`BFGS`: cost tolerance must be >= 0."
             ));
         }
-        self.tol_cost = tol_cost;
+        self.tol = self.tol.with_cost(tol_cost)?;
         Ok(self)
     }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::BFGS;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let bfgs: BFGS<_, f64> = BFGS::new(linesearch).with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
 }
 
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for BFGS<L, F>
 where
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
-    P: Clone + ArgminSub<P, P> + ArgminDot<G, H> + ArgminDot<P, H>,
+    P: Clone + ArgminSub<P, P> + ArgminDot<G, H> + ArgminDot<P, H> + ArgminL2Norm<F>,
     G: Clone
         + ArgminL2Norm<F>
         + ArgminMul<F, P>
-        + ArgminMul<F, G>
+        + ArgminMulAssign<F>
         + ArgminDot<P, F>
-        + ArgminSub<G, G>,
+        + ArgminSub<G, G>
+        + 'static,
     H: ArgminSub<H, H>
+        + ArgminSubAssign<H>
         + ArgminDot<G, G>
         + ArgminDot<H, H>
-        + ArgminAdd<H, H>
-        + ArgminMul<F, H>
+        + ArgminAddAssign<H>
+        + ArgminMulAssign<F>
         + ArgminTranspose<H>
         + ArgminEye,
     L: Clone + LineSearch<G, F> + Solver<O, IterState<P, G, (), (), (), F>>,
@@ -219,24 +245,34 @@ where
             "`BFGS`: Inverse Hessian in state not set."
         ))?;
 
-        let g: G = inv_hessian.dot(&prev_grad).mul(&float!(-1.0));
+        let mut g: G = inv_hessian.dot(&prev_grad);
+        g.mul_assign(&float!(-1.0));
 
         self.linesearch.search_direction(g);
+        if let Some(max_step_length) = problem.max_step_length(&param) {
+            self.linesearch.max_step_length(max_step_length)?;
+        }
+        if let Some(move_limits) = problem.move_limits::<G>() {
+            self.linesearch.move_limits(move_limits);
+        }
 
         // Run solver
         let OptimizationResult {
             problem: line_problem,
             state: mut sub_state,
             ..
-        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
-            .configure(|config| {
-                config
-                    .param(param.clone())
-                    .gradient(prev_grad.clone())
-                    .cost(cur_cost)
-            })
-            .ctrlc(false)
-            .run()?;
+        } = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+                .configure(|config| {
+                    config
+                        .param(param.clone())
+                        .gradient(prev_grad.clone())
+                        .cost(cur_cost)
+                })
+                .ctrlc(false)
+                .run()?
+        };
 
         let xk1 = sub_state.take_param().ok_or_else(argmin_error_closure!(
             PotentialBug,
@@ -248,7 +284,11 @@ where
         // take care of function eval counts
         problem.consume_problem(line_problem);
 
-        let grad = problem.gradient(&xk1)?;
+        // Reuse the gradient at `xk1` if the line search computed it
+        let grad = sub_state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&xk1))?;
 
         let yk = grad.sub(&prev_grad);
 
@@ -258,16 +298,17 @@ where
         let rhok = float!(1.0) / yksk;
 
         let e = inv_hessian.eye_like();
-        let mat1: H = sk.dot(&yk);
-        let mat1 = mat1.mul(&rhok);
+        let mut mat1: H = sk.dot(&yk);
+        mat1.mul_assign(&rhok);
 
         let tmp1 = e.sub(&mat1);
 
         let mat2 = mat1.t();
-        let tmp2 = e.sub(&mat2);
+        let mut tmp2 = e;
+        tmp2.sub_assign(&mat2);
 
-        let sksk: H = sk.dot(&sk);
-        let sksk = sksk.mul(&rhok);
+        let mut sksk: H = sk.dot(&sk);
+        sksk.mul_assign(&rhok);
 
         // if state.get_iter() == 0 {
         //     let ykyk: f64 = yk.dot(&yk);
@@ -275,7 +316,8 @@ where
         //     println!("{:?}", self.inv_hessian);
         // }
 
-        let inv_hessian = tmp1.dot(&inv_hessian.dot(&tmp2)).add(&sksk);
+        let mut inv_hessian: H = tmp1.dot(&inv_hessian.dot(&tmp2));
+        inv_hessian.add_assign(&sksk);
 
         Ok((
             state
@@ -287,11 +329,30 @@ where
         ))
     }
 
+    fn termination_diagnostics(&self, state: &IterState<P, G, (), H, (), F>) -> KV {
+        self.tol.diagnostics(
+            state.get_gradient().map(|grad| grad.l2_norm()),
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
+    }
+
     fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
-        if state.get_gradient().unwrap().l2_norm() < self.tol_grad {
+        if self
+            .tol
+            .grad_converged(state.get_gradient().unwrap().l2_norm(), state.get_cost())
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
-        if (state.get_prev_cost() - state.cost).abs() < self.tol_cost {
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         TerminationStatus::NotTerminated
@@ -315,15 +376,11 @@ mod tests {
         struct MyFakeLineSearch {}
 
         let bfgs: BFGS<_, f64> = BFGS::new(MyFakeLineSearch {});
-        let BFGS {
-            linesearch,
-            tol_grad,
-            tol_cost,
-        } = bfgs;
+        let BFGS { linesearch, tol } = bfgs;
 
         assert_eq!(linesearch, MyFakeLineSearch {});
-        assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
-        assert_eq!(tol_cost.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert_eq!(tol.grad().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(tol.cost().to_ne_bytes(), f64::EPSILON.to_ne_bytes());
     }
 
     #[test]
@@ -338,7 +395,7 @@ mod tests {
             assert!(res.is_ok());
 
             let nm = res.unwrap();
-            assert_eq!(nm.tol_grad.to_ne_bytes(), tol.to_ne_bytes());
+            assert_eq!(nm.tol.grad().to_ne_bytes(), tol.to_ne_bytes());
         }
 
         // incorrect parameters
@@ -365,7 +422,7 @@ mod tests {
             assert!(res.is_ok());
 
             let nm = res.unwrap();
-            assert_eq!(nm.tol_cost.to_ne_bytes(), tol.to_ne_bytes());
+            assert_eq!(nm.tol.cost().to_ne_bytes(), tol.to_ne_bytes());
         }
 
         // incorrect parameters
@@ -499,4 +556,192 @@ mod tests {
             assert_eq!(s.to_ne_bytes(), g.to_ne_bytes());
         }
     }
+
+    #[test]
+    fn test_problem_not_cloned() {
+        use crate::core::{CostFunction, Gradient};
+
+        /// Least squares fit of a constant to a data set; intentionally does not implement `Clone`
+        struct Fit {
+            data: Vec<f64>,
+        }
+
+        impl CostFunction for Fit {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(self.data.iter().map(|d| (p[0] - d).powi(2)).sum())
+            }
+        }
+
+        impl Gradient for Fit {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![self.data.iter().map(|d| 2.0 * (p[0] - d)).sum()])
+            }
+        }
+
+        let data: Vec<f64> = (0..100_000).map(|i| (i % 7) as f64).collect();
+        let ptr = data.as_ptr();
+        let bfgs: BFGS<_, f64> = BFGS::new(MoreThuenteLineSearch::new());
+        let res = Executor::new(Fit { data }, bfgs)
+            .configure(|state| {
+                state
+                    .param(vec![0.0])
+                    .inv_hessian(vec![vec![1e-6]])
+                    .max_iters(10)
+            })
+            .run()
+            .unwrap();
+        assert!((res.state.get_best_param().unwrap()[0] - 3.0).abs() < 1e-2);
+
+        // The problem is moved into the nested line search runs instead of being copied
+        assert_eq!(res.problem.problem.unwrap().data.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_max_step_length() {
+        use crate::core::{CostFunction, Gradient, MaxStepLength, TerminationStatus};
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        #[derive(Clone)]
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(argmin_testfunctions::rosenbrock_derivative(p))
+            }
+        }
+
+        // A bad initial inverse Hessian leads to large steps
+        let run = |max_step_length: Option<MaxStepLength>| {
+            let longest_step = Rc::new(Cell::new(0.0f64));
+            let longest = Rc::clone(&longest_step);
+            let mut prev_param = Some(vec![-1.2, 1.0]);
+            let mut executor =
+                Executor::new(Rosenbrock {}, BFGS::new(MoreThuenteLineSearch::new()))
+                    .configure(|state| {
+                        state
+                            .param(vec![-1.2, 1.0])
+                            .inv_hessian(vec![vec![1e3, 0.0], vec![0.0, 1e3]])
+                            .max_iters(200)
+                    })
+                    .add_iteration_hook(
+                        move |state: &mut IterState<
+                            Vec<f64>,
+                            Vec<f64>,
+                            (),
+                            Vec<Vec<f64>>,
+                            (),
+                            f64,
+                        >,
+                              _problem: &mut Problem<Rosenbrock>| {
+                            let param = state.get_param().unwrap().clone();
+                            if let Some(prev_param) = prev_param.replace(param.clone()) {
+                                let step = param
+                                    .iter()
+                                    .zip(prev_param)
+                                    .map(|(a, b)| (a - b).powi(2))
+                                    .sum::<f64>()
+                                    .sqrt();
+                                longest.set(longest.get().max(step));
+                            }
+                            Ok(TerminationStatus::NotTerminated)
+                        },
+                    );
+            if let Some(max_step_length) = max_step_length {
+                executor = executor.max_step_length(max_step_length);
+            }
+            let res = executor.run().unwrap();
+            (res, longest_step.get())
+        };
+
+        let (_, longest_step) = run(None);
+        assert!(longest_step > 0.5);
+
+        let max_step_length = MaxStepLength::new().with_absolute(0.1).unwrap();
+        let (res, longest_step) = run(Some(max_step_length));
+        assert!(longest_step <= 0.1 + 1e-12);
+        assert!(res.state.get_best_cost() < 1e-10);
+    }
+
+    #[test]
+    fn test_move_limits() {
+        use crate::core::{CostFunction, Gradient, TerminationStatus};
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        #[derive(Clone)]
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(argmin_testfunctions::rosenbrock_derivative(p))
+            }
+        }
+
+        // Largest change of each component in a single iteration
+        let largest_changes = Rc::new(RefCell::new(vec![0.0f64; 2]));
+        let changes = Rc::clone(&largest_changes);
+        let mut prev_param = vec![-1.2, 1.0];
+        let res = Executor::new(Rosenbrock {}, BFGS::new(MoreThuenteLineSearch::new()))
+            .configure(|state| {
+                state
+                    .param(vec![-1.2, 1.0])
+                    .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+                    .max_iters(500)
+            })
+            .move_limits(vec![0.02, 0.1])
+            .add_iteration_hook(
+                move |state: &mut IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64>,
+                      _problem: &mut Problem<Rosenbrock>| {
+                    let param = state.get_param().unwrap().clone();
+                    for (change, (a, b)) in changes
+                        .borrow_mut()
+                        .iter_mut()
+                        .zip(param.iter().zip(prev_param.iter()))
+                    {
+                        *change = change.max((a - b).abs());
+                    }
+                    prev_param = param;
+                    Ok(TerminationStatus::NotTerminated)
+                },
+            )
+            .run()
+            .unwrap();
+
+        let largest_changes = largest_changes.borrow();
+        assert!(largest_changes[0] > 0.0);
+        assert!(largest_changes[0] <= 0.02 + 1e-12);
+        assert!(largest_changes[1] <= 0.1 + 1e-12);
+        assert!(res.state.get_best_cost() < 1e-10);
+    }
 }
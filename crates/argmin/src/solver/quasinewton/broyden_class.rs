@@ -0,0 +1,604 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus,
+    ToleranceConfig, KV,
+};
+use argmin_math::{
+    ArgminDot, ArgminL2Norm, ArgminMul, ArgminMulAssign, ArgminScaledAddAssign, ArgminSub,
+    ArgminSubAssign,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Broyden class of quasi-Newton methods
+///
+/// Quasi-Newton method whose update of the inverse Hessian approximation `H` is a convex
+/// combination of the [`DFP`](`crate::solver::quasinewton::DFP`) and the
+/// [`BFGS`](`crate::solver::quasinewton::BFGS`) updates:
+///
+/// `H_{k+1} = (1 - phi) H_{k+1}^DFP + phi H_{k+1}^BFGS = H_{k+1}^DFP + phi (y_k^T H_k y_k) v_k v_k^T`
+///
+/// with `v_k = s_k / (y_k^T s_k) - H_k y_k / (y_k^T H_k y_k)`. Hence `phi = 0` corresponds to
+/// DFP and `phi = 1` (default) to BFGS. Note that this parameterizes the update of the inverse
+/// Hessian; in the parameterization of the update of the Hessian (as in Nocedal and Wright),
+/// the roles of BFGS and DFP are exchanged.
+///
+/// The parameter can be fixed via [`with_phi`](`BroydenClass::with_phi`) or chosen in each
+/// iteration via [`with_adaptive_phi`](`BroydenClass::with_adaptive_phi`). The adaptive choice
+/// `phi_k = min(1, max(0, (y_k^T s_k) / (y_k^T H_k y_k)))` is a heuristic which uses the BFGS
+/// update if `y_k^T s_k` is at least the value `y_k^T H_k y_k` predicted by the current
+/// approximation and moves towards DFP the smaller the ratio of the two is. The value of
+/// `phi` used in each iteration is reported as `phi` in the KV store. Updates which violate the
+/// curvature condition `y_k^T s_k > 0` are skipped.
+///
+/// The algorithm requires a line search which is provided via the constructor. Additionally an
+/// initial guess for the parameter vector and an initial inverse Hessian is required, which are to
+/// be provided via the [`configure`](`crate::core::Executor::configure`) method of the
+/// [`Executor`](`crate::core::Executor`) (See [`IterState`], in particular [`IterState::param`]
+/// and [`IterState::inv_hessian`]).
+/// In the same way the initial gradient and cost function corresponding to the initial parameter
+/// vector can be provided. If these are not provided, they will be computed during initialization
+/// of the algorithm.
+///
+/// The stopping criteria are the same as for [`BFGS`](`crate::solver::quasinewton::BFGS`) and can
+/// be configured via [`with_tolerance_grad`](`BroydenClass::with_tolerance_grad`),
+/// [`with_tolerance_cost`](`BroydenClass::with_tolerance_cost`) and
+/// [`with_tolerances`](`BroydenClass::with_tolerances`) (see [`ToleranceConfig`]).
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## Reference
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BroydenClass<L, F> {
+    /// line search
+    linesearch: L,
+    /// Weight of the BFGS update; chosen adaptively if `None`
+    phi: Option<F>,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
+}
+
+impl<L, F> BroydenClass<L, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`BroydenClass`] which uses the BFGS update (`phi = 1`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::BroydenClass;
+    /// # let linesearch = ();
+    /// let broyden: BroydenClass<_, f64> = BroydenClass::new(linesearch);
+    /// ```
+    pub fn new(linesearch: L) -> Self {
+        BroydenClass {
+            linesearch,
+            phi: Some(float!(1.0)),
+            tol: ToleranceConfig::new(),
+        }
+    }
+
+    /// Sets the weight `phi` of the BFGS update. `phi = 0` corresponds to DFP and `phi = 1` to
+    /// BFGS.
+    ///
+    /// Must be in `[0, 1]`. Defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::BroydenClass;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let broyden: BroydenClass<_, f64> = BroydenClass::new(linesearch).with_phi(0.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_phi(mut self, phi: F) -> Result<Self, Error> {
+        if !(float!(0.0)..=float!(1.0)).contains(&phi) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BroydenClass`: phi must be in [0, 1]."
+            ));
+        }
+        self.phi = Some(phi);
+        Ok(self)
+    }
+
+    /// Chooses `phi` adaptively in each iteration (see [`BroydenClass`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::BroydenClass;
+    /// # let linesearch = ();
+    /// let broyden: BroydenClass<_, f64> = BroydenClass::new(linesearch).with_adaptive_phi();
+    /// ```
+    #[must_use]
+    pub fn with_adaptive_phi(mut self) -> Self {
+        self.phi = None;
+        self
+    }
+
+    /// The algorithm stops if the norm of the gradient is below `tol_grad`.
+    ///
+    /// The provided value must be non-negative. Defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::BroydenClass;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let broyden: BroydenClass<_, f64> = BroydenClass::new(linesearch).with_tolerance_grad(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_grad(mut self, tol_grad: F) -> Result<Self, Error> {
+        if tol_grad < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BroydenClass`: gradient tolerance must be >= 0."
+            ));
+        }
+        self.tol = self.tol.with_grad(tol_grad)?;
+        Ok(self)
+    }
+
+    /// Sets tolerance for the stopping criterion based on the change of the cost stopping criterion
+    ///
+    /// The provided value must be non-negative. Defaults to `EPSILON`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::BroydenClass;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let broyden: BroydenClass<_, f64> = BroydenClass::new(linesearch).with_tolerance_cost(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_cost(mut self, tol_cost: F) -> Result<Self, Error> {
+        if tol_cost < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BroydenClass`: cost tolerance must be >= 0."
+            ));
+        }
+        self.tol = self.tol.with_cost(tol_cost)?;
+        Ok(self)
+    }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::BroydenClass;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let broyden: BroydenClass<_, f64> = BroydenClass::new(linesearch).with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+}
+
+impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for BroydenClass<L, F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminDot<G, F>
+        + ArgminDot<P, H>
+        + ArgminMul<F, P>
+        + ArgminMulAssign<F>
+        + ArgminL2Norm<F>
+        + 'static,
+    G: Clone + ArgminSub<G, G> + ArgminL2Norm<F> + ArgminDot<P, F>,
+    H: Clone
+        + ArgminSubAssign<H>
+        + ArgminDot<G, P>
+        + ArgminScaledAddAssign<H, F>
+        + ArgminMulAssign<F>,
+    L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "BroydenClass"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), H, (), F>,
+    ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`BroydenClass` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let inv_hessian = state.take_inv_hessian().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`BroydenClass` requires an initial inverse Hessian. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let cost = state.get_cost();
+        let cost = if cost.is_infinite() {
+            problem.cost(&param)?
+        } else {
+            cost
+        };
+
+        let grad = state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&param))?;
+
+        Ok((
+            state
+                .param(param)
+                .cost(cost)
+                .gradient(grad)
+                .inv_hessian(inv_hessian),
+            None,
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), H, (), F>,
+    ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`BroydenClass`: Parameter vector in state not set."
+        ))?;
+        let cost = state.get_cost();
+
+        let prev_grad = state.take_gradient().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`BroydenClass`: Gradient in state not set."
+        ))?;
+
+        let mut inv_hessian = state.take_inv_hessian().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`BroydenClass`: Inverse Hessian in state not set."
+        ))?;
+
+        let mut p: P = inv_hessian.dot(&prev_grad);
+        p.mul_assign(&float!(-1.0));
+
+        self.linesearch.search_direction(p);
+        if let Some(max_step_length) = problem.max_step_length(&param) {
+            self.linesearch.max_step_length(max_step_length)?;
+        }
+        if let Some(move_limits) = problem.move_limits::<P>() {
+            self.linesearch.move_limits(move_limits);
+        }
+
+        let OptimizationResult {
+            problem: line_problem,
+            state: mut linesearch_state,
+            ..
+        } = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+                .configure(|config| {
+                    config
+                        .param(param.clone())
+                        .gradient(prev_grad.clone())
+                        .cost(cost)
+                })
+                .ctrlc(false)
+                .run()?
+        };
+
+        let xk1 = linesearch_state
+            .take_param()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`BroydenClass`: No parameters returned by line search."
+            ))?;
+
+        let next_cost = linesearch_state.get_cost();
+
+        // take care of function eval counts
+        problem.consume_problem(line_problem);
+
+        // Reuse the gradient at `xk1` if the line search computed it
+        let grad = linesearch_state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&xk1))?;
+        let yk = grad.sub(&prev_grad);
+
+        let sk = xk1.sub(&param);
+
+        let yksk: F = yk.dot(&sk);
+        let hkyk: P = inv_hessian.dot(&yk);
+        let ykhkyk: F = hkyk.dot(&yk);
+
+        let phi = self
+            .phi
+            .unwrap_or_else(|| (yksk / ykhkyk).max(float!(0.0)).min(float!(1.0)));
+
+        let hessian_update = yksk > float!(0.0) && ykhkyk > float!(0.0);
+        if hessian_update {
+            // DFP update
+            let sksk: H = sk.dot(&sk);
+            let hkykykhk: H = hkyk.dot(&hkyk);
+            inv_hessian.scaled_add_assign(&(float!(-1.0) / ykhkyk), &hkykykhk);
+            inv_hessian.scaled_add_assign(&(float!(1.0) / yksk), &sksk);
+
+            // Shift towards the BFGS update
+            if phi > float!(0.0) {
+                let vk = sk
+                    .mul(&(float!(1.0) / yksk))
+                    .sub(&hkyk.mul(&(float!(1.0) / ykhkyk)));
+                let vkvk: H = vk.dot(&vk);
+                inv_hessian.scaled_add_assign(&(phi * ykhkyk), &vkvk);
+            }
+        }
+
+        Ok((
+            state
+                .param(xk1)
+                .cost(next_cost)
+                .gradient(grad)
+                .inv_hessian(inv_hessian),
+            Some(kv!("phi" => phi; "hessian_update" => hessian_update;)),
+        ))
+    }
+
+    fn termination_diagnostics(&self, state: &IterState<P, G, (), H, (), F>) -> KV {
+        self.tol.diagnostics(
+            state.get_gradient().map(|grad| grad.l2_norm()),
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
+    }
+
+    fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
+        if self
+            .tol
+            .grad_converged(state.get_gradient().unwrap().l2_norm(), state.get_cost())
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::quasinewton::{BFGS, DFP};
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        broyden_class,
+        BroydenClass<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, f64>
+    );
+
+    #[derive(Clone)]
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(argmin_testfunctions::rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(argmin_testfunctions::rosenbrock_derivative(p))
+        }
+    }
+
+    /// Runs `solver` for `iters` iterations on the Rosenbrock function and returns the final
+    /// inverse Hessian approximation.
+    fn run<S>(solver: S, iters: u64) -> Vec<Vec<f64>>
+    where
+        S: Solver<Rosenbrock, IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64>>,
+    {
+        let mut res = Executor::new(Rosenbrock {}, solver)
+            .configure(|state| {
+                state
+                    .param(vec![-1.2, 1.0])
+                    .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+                    .max_iters(iters)
+            })
+            .run()
+            .unwrap();
+        res.state.take_inv_hessian().unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct MyFakeLineSearch {}
+
+        let broyden: BroydenClass<_, f64> = BroydenClass::new(MyFakeLineSearch {});
+        let BroydenClass {
+            linesearch,
+            phi,
+            tol,
+        } = broyden;
+
+        assert_eq!(linesearch, MyFakeLineSearch {});
+        assert_eq!(phi, Some(1.0));
+        assert_eq!(tol.grad().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(tol.cost().to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_phi() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        for phi in [0.0, 0.3, 1.0] {
+            let broyden: BroydenClass<_, f64> = BroydenClass::new(MyFakeLineSearch {});
+            let res = broyden.with_phi(phi).unwrap();
+            assert_eq!(res.phi, Some(phi));
+        }
+
+        for phi in [-f64::EPSILON, -1.0, 1.0 + f64::EPSILON, 2.0, f64::NAN] {
+            let broyden: BroydenClass<_, f64> = BroydenClass::new(MyFakeLineSearch {});
+            let res = broyden.with_phi(phi);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`BroydenClass`: phi must be in [0, 1].\""
+            );
+        }
+
+        let broyden: BroydenClass<_, f64> = BroydenClass::new(MyFakeLineSearch {});
+        assert_eq!(broyden.with_adaptive_phi().phi, None);
+    }
+
+    #[test]
+    fn test_with_tolerance_grad() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        let broyden: BroydenClass<_, f64> = BroydenClass::new(MyFakeLineSearch {});
+        let res = broyden.with_tolerance_grad(1e-6).unwrap();
+        assert_eq!(res.tol.grad().to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        let broyden: BroydenClass<_, f64> = BroydenClass::new(MyFakeLineSearch {});
+        assert_error!(
+            broyden.with_tolerance_grad(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`BroydenClass`: gradient tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_with_tolerance_cost() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        let broyden: BroydenClass<_, f64> = BroydenClass::new(MyFakeLineSearch {});
+        let res = broyden.with_tolerance_cost(1e-6).unwrap();
+        assert_eq!(res.tol.cost().to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        let broyden: BroydenClass<_, f64> = BroydenClass::new(MyFakeLineSearch {});
+        assert_error!(
+            broyden.with_tolerance_cost(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`BroydenClass`: cost tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut broyden: BroydenClass<_, f64> = BroydenClass::new(MoreThuenteLineSearch::new());
+
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> =
+            IterState::new().param(vec![-1.0, 1.0]);
+        let res = broyden.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`BroydenClass` requires an initial inverse Hessian. Please ",
+                "provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_bfgs_and_dfp() {
+        let iters = 5;
+
+        let expected = run(BFGS::new(MoreThuenteLineSearch::new()), iters);
+        let inv_hessian = run(BroydenClass::new(MoreThuenteLineSearch::new()), iters);
+        for (a, b) in inv_hessian.iter().flatten().zip(expected.iter().flatten()) {
+            assert_relative_eq!(a, b, epsilon = 1e-8, max_relative = 1e-8);
+        }
+
+        let expected = run(DFP::new(MoreThuenteLineSearch::new()), iters);
+        let inv_hessian = run(
+            BroydenClass::new(MoreThuenteLineSearch::new())
+                .with_phi(0.0)
+                .unwrap(),
+            iters,
+        );
+        for (a, b) in inv_hessian.iter().flatten().zip(expected.iter().flatten()) {
+            assert_relative_eq!(a, b, epsilon = 1e-8, max_relative = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        for broyden in [
+            BroydenClass::new(MoreThuenteLineSearch::new())
+                .with_phi(0.5)
+                .unwrap(),
+            BroydenClass::new(MoreThuenteLineSearch::new()).with_adaptive_phi(),
+        ] {
+            let res = Executor::new(Rosenbrock {}, broyden)
+                .configure(|state| {
+                    state
+                        .param(vec![-1.2, 1.0])
+                        .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+                        .max_iters(1000)
+                })
+                .run()
+                .unwrap();
+            let param = res.state.get_best_param().unwrap();
+            assert_relative_eq!(param[0], 1.0, epsilon = 1e-4);
+            assert_relative_eq!(param[1], 1.0, epsilon = 1e-4);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Dense linear algebra for the small matrices (of dimension up to twice the number of stored
+//! pairs) of the compact representations.
+
+use crate::core::{ArgminFloat, Error};
+
+/// Maximum number of sweeps of the Jacobi eigenvalue method
+const MAX_SWEEPS: usize = 100;
+
+/// Solves `a x = b` via Gaussian elimination with partial pivoting.
+pub(super) fn solve<F: ArgminFloat>(a: &[Vec<F>], b: &[F]) -> Result<Vec<F>, Error> {
+    let n = b.len();
+    let mut a: Vec<Vec<F>> = a.to_vec();
+    let mut x: Vec<F> = b.to_vec();
+    let scale = a
+        .iter()
+        .flat_map(|row| row.iter())
+        .fold(float!(0.0), |acc: F, aij| acc.max(aij.abs()));
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| a[i][k].abs().partial_cmp(&a[j][k].abs()).unwrap())
+            .unwrap();
+        let pivot_abs = a[pivot][k].abs();
+        if pivot_abs <= F::epsilon() * scale * float!(n as f64) || pivot_abs.is_nan() {
+            return Err(argmin_error!(
+                SingularMatrix,
+                "Middle matrix of the compact representation is singular"
+            ));
+        }
+        a.swap(k, pivot);
+        x.swap(k, pivot);
+        let (upper, lower) = a.split_at_mut(k + 1);
+        let row_k = &upper[k];
+        for (i, row_i) in lower.iter_mut().enumerate() {
+            let factor = row_i[k] / row_k[k];
+            for (aij, akj) in row_i[k..].iter_mut().zip(row_k[k..].iter()) {
+                *aij = *aij - factor * *akj;
+            }
+            x[k + 1 + i] = x[k + 1 + i] - factor * x[k];
+        }
+    }
+    for k in (0..n).rev() {
+        let mut sum = x[k];
+        for j in (k + 1)..n {
+            sum = sum - a[k][j] * x[j];
+        }
+        x[k] = sum / a[k][k];
+    }
+    Ok(x)
+}
+
+/// Inverse of `a`, computed column by column
+pub(super) fn inverse<F: ArgminFloat>(a: &[Vec<F>]) -> Result<Vec<Vec<F>>, Error> {
+    let n = a.len();
+    let mut inv = vec![vec![float!(0.0); n]; n];
+    for j in 0..n {
+        let mut e = vec![float!(0.0); n];
+        e[j] = float!(1.0);
+        for (i, xi) in solve(a, &e)?.into_iter().enumerate() {
+            inv[i][j] = xi;
+        }
+    }
+    Ok(inv)
+}
+
+/// Eigenvalues and eigenvectors (columns of the returned matrix) of the symmetric matrix `a` via
+/// the cyclic Jacobi method
+pub(super) fn sym_eigen<F: ArgminFloat>(a: &[Vec<F>]) -> (Vec<F>, Vec<Vec<F>>) {
+    let n = a.len();
+    let mut a: Vec<Vec<F>> = a.to_vec();
+    let mut v = identity(n);
+    for _ in 0..MAX_SWEEPS {
+        let off: F = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .fold(float!(0.0), |acc, (i, j)| acc + a[i][j].powi(2));
+        let total: F = a
+            .iter()
+            .flat_map(|row| row.iter())
+            .fold(float!(0.0), |acc, aij| acc + aij.powi(2));
+        if off <= F::epsilon().powi(2) * total {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[p][q] == float!(0.0) {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (float!(2.0) * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta.powi(2) + float!(1.0)).sqrt());
+                let c = float!(1.0) / (t.powi(2) + float!(1.0)).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (top, bottom) = a.split_at_mut(q);
+                for (apk, aqk) in top[p].iter_mut().zip(bottom[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i][i]).collect(), v)
+}
+
+/// Product of two matrices
+pub(super) fn matmul<F: ArgminFloat>(a: &[Vec<F>], b: &[Vec<F>]) -> Vec<Vec<F>> {
+    a.iter()
+        .map(|row| {
+            (0..b[0].len())
+                .map(|j| {
+                    row.iter()
+                        .zip(b.iter())
+                        .fold(float!(0.0), |acc, (aik, bk)| acc + *aik * bk[j])
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Identity matrix of dimension `n`
+pub(super) fn identity<F: ArgminFloat>(n: usize) -> Vec<Vec<F>> {
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if i == j { float!(1.0) } else { float!(0.0) })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns `(min, max)` of the eigenvalues of `sigma I + Psi middle Psi^T`, where `gram` is
+/// `Psi^T Psi` and `middle` is symmetric. `sigma` is included, since it is an eigenvalue whenever
+/// the columns of `Psi` do not span the whole space.
+pub(super) fn eigenvalue_bounds<F: ArgminFloat>(
+    sigma: F,
+    gram: &[Vec<F>],
+    middle: &[Vec<F>],
+) -> (F, F) {
+    if gram.is_empty() {
+        return (sigma, sigma);
+    }
+    // The nonzero eigenvalues of `Psi middle Psi^T` are those of `G^{1/2} middle G^{1/2}`.
+    let (lambda, v) = sym_eigen(gram);
+    let n = lambda.len();
+    let sqrt_gram: Vec<Vec<F>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| {
+                    (0..n).fold(float!(0.0), |acc, k| {
+                        acc + v[i][k] * lambda[k].max(float!(0.0)).sqrt() * v[j][k]
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    let (mu, _) = sym_eigen(&matmul(&matmul(&sqrt_gram, middle), &sqrt_gram));
+    mu.iter().fold((sigma, sigma), |(lo, hi), &mu| {
+        (lo.min(sigma + mu), hi.max(sigma + mu))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_solve() {
+        let a = vec![
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, -1.0, 0.0],
+            vec![3.0, 0.0, 4.0],
+        ];
+        let x = solve(&a, &[6.0, -1.0, 11.0]).unwrap();
+        assert_relative_eq!(x[0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 2.0, epsilon = 1e-12);
+        assert_relative_eq!(x[2], 2.0, epsilon = 1e-12);
+
+        let singular = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        assert!(solve(&singular, &[1.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn test_inverse() {
+        let a = vec![vec![4.0, 1.0], vec![2.0, 3.0]];
+        let inv = inverse(&a).unwrap();
+        let prod = matmul(&a, &inv);
+        for (i, row) in prod.iter().enumerate() {
+            for (j, pij) in row.iter().enumerate() {
+                assert_relative_eq!(*pij, if i == j { 1.0 } else { 0.0 }, epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sym_eigen() {
+        let a = vec![
+            vec![2.0, -1.0, 0.0],
+            vec![-1.0, 2.0, -1.0],
+            vec![0.0, -1.0, 2.0],
+        ];
+        let (lambda, v) = sym_eigen(&a);
+        let mut sorted = lambda.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let sqrt2 = 2.0f64.sqrt();
+        assert_relative_eq!(sorted[0], 2.0 - sqrt2, epsilon = 1e-12);
+        assert_relative_eq!(sorted[1], 2.0, epsilon = 1e-12);
+        assert_relative_eq!(sorted[2], 2.0 + sqrt2, epsilon = 1e-12);
+
+        // A v_k = lambda_k v_k
+        for (k, lk) in lambda.iter().enumerate() {
+            for i in 0..3 {
+                let avk: f64 = (0..3).map(|j| a[i][j] * v[j][k]).sum();
+                assert_relative_eq!(avk, lk * v[i][k], epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_eigenvalue_bounds() {
+        // Psi = (1, 1, 0)^T, middle = 2: sigma I + 2 Psi Psi^T has eigenvalues 1, 1, 5
+        let (lo, hi) = eigenvalue_bounds(1.0, &[vec![2.0]], &[vec![2.0]]);
+        assert_relative_eq!(lo, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(hi, 5.0, epsilon = 1e-12);
+
+        let (lo, hi) = eigenvalue_bounds(1.0, &[vec![2.0]], &[vec![-0.25]]);
+        assert_relative_eq!(lo, 0.5, epsilon = 1e-12);
+        assert_relative_eq!(hi, 1.0, epsilon = 1e-12);
+
+        let (lo, hi) = eigenvalue_bounds::<f64>(3.0, &[], &[]);
+        assert_eq!((lo, hi), (3.0, 3.0));
+    }
+}
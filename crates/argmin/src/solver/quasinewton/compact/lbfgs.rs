@@ -0,0 +1,434 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{dense, PairMemory};
+use crate::core::{ArgminFloat, Error};
+use argmin_math::{ArgminDot, ArgminMul, ArgminScaledAdd};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Compact limited-memory BFGS matrix
+///
+/// Limited-memory BFGS approximation of the Hessian in compact form (Byrd, Nocedal and Schnabel,
+/// 1994):
+///
+/// `B = sigma I - [sigma S, Y] K^{-1} [sigma S, Y]^T`, with `K = [[sigma S^T S, L], [L^T, -D]]`,
+///
+/// where the columns of `S` and `Y` are the `m` most recent pairs `(s_i, y_i)`, `L` is the
+/// strictly lower triangular part of `S^T Y` and `D` its diagonal. The inverse is
+///
+/// `H = gamma I + [S, gamma Y] N [S, gamma Y]^T`, with
+/// `N = [[R^{-T} (D + gamma Y^T Y) R^{-1}, -R^{-T}], [-R^{-1}, 0]]`,
+///
+/// where `R` is the upper triangular part of `S^T Y` and `gamma = 1 / sigma`.
+///
+/// [`hess_vec`](`CompactLBFGS::hess_vec`) and [`inv_hess_vec`](`CompactLBFGS::inv_hess_vec`)
+/// compute `B v` and `H v` and
+/// [`eigenvalue_bounds`](`CompactLBFGS::eigenvalue_bounds`) the extreme eigenvalues of `B`.
+///
+/// Pairs are only stored if they satisfy the curvature condition `s^T y > 0` (relative to
+/// `||s|| ||y||`), which keeps `B` positive definite. By default, `sigma` is set to
+/// `y^T y / s^T y` of the most recent pair, which corresponds to the initial inverse Hessian
+/// approximation of [`LBFGS`](`crate::solver::quasinewton::LBFGS`). A fixed value can be set via
+/// [`with_scaling`](`CompactLBFGS::with_scaling`).
+///
+/// # Example
+///
+/// ```
+/// use argmin::solver::quasinewton::CompactLBFGS;
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut bfgs: CompactLBFGS<Vec<f64>, f64> = CompactLBFGS::new(5);
+/// // Pairs of the quadratic with Hessian diag(1, 4)
+/// bfgs.push(vec![1.0, 0.0], vec![1.0, 0.0]);
+/// bfgs.push(vec![0.0, 1.0], vec![0.0, 4.0]);
+///
+/// let bv = bfgs.hess_vec(&vec![1.0, 1.0])?;
+/// let v = bfgs.inv_hess_vec(&bv)?;
+/// let (lambda_min, lambda_max) = bfgs.eigenvalue_bounds()?;
+/// # assert!((bv[0] - 1.0).abs() < 1e-12);
+/// # assert!((bv[1] - 4.0).abs() < 1e-12);
+/// # assert!((v[0] - 1.0).abs() < 1e-12);
+/// # assert!((v[1] - 1.0).abs() < 1e-12);
+/// # assert!((lambda_min - 1.0).abs() < 1e-12);
+/// # assert!((lambda_max - 4.0).abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ## Reference
+///
+/// Richard H. Byrd, Jorge Nocedal and Robert B. Schnabel (1994). Representations of quasi-Newton
+/// matrices and their use in limited memory methods. Mathematical Programming 63, 129–156.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct CompactLBFGS<P, F> {
+    /// Stored pairs
+    memory: PairMemory<P, F>,
+    /// Fixed scaling of the initial matrix
+    scaling: Option<F>,
+}
+
+impl<P, F> CompactLBFGS<P, F>
+where
+    P: Clone + ArgminDot<P, F> + ArgminScaledAdd<P, F, P> + ArgminMul<F, P>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`CompactLBFGS`] storing at most `m` pairs
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::CompactLBFGS;
+    /// let bfgs: CompactLBFGS<Vec<f64>, f64> = CompactLBFGS::new(5);
+    /// ```
+    pub fn new(m: usize) -> Self {
+        CompactLBFGS {
+            memory: PairMemory::new(m),
+            scaling: None,
+        }
+    }
+
+    /// Set a fixed scaling `sigma` of the initial matrix `sigma I`
+    ///
+    /// `sigma` must be finite and larger than 0. Defaults to `y^T y / s^T y` of the most recent
+    /// pair (and 1 as long as no pair is stored).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::CompactLBFGS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bfgs: CompactLBFGS<Vec<f64>, f64> = CompactLBFGS::new(5).with_scaling(2.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_scaling(mut self, sigma: F) -> Result<Self, Error> {
+        if sigma <= float!(0.0) || !sigma.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CompactLBFGS`: scaling must be finite and larger than 0."
+            ));
+        }
+        self.scaling = Some(sigma);
+        Ok(self)
+    }
+
+    /// Stores the pair `(s, y)` if it satisfies the curvature condition
+    ///
+    /// Returns whether the pair was stored. If the memory is full, the oldest pair is discarded.
+    pub fn push(&mut self, s: P, y: P) -> bool {
+        let sy = s.dot(&y);
+        if sy <= F::epsilon() * (s.dot(&s) * y.dot(&y)).sqrt() || sy.is_nan() {
+            return false;
+        }
+        self.memory.push(s, y);
+        true
+    }
+
+    /// Removes all stored pairs
+    pub fn clear(&mut self) {
+        self.memory.clear();
+    }
+
+    /// Number of stored pairs
+    pub fn len(&self) -> usize {
+        self.memory.len()
+    }
+
+    /// Returns `true` if no pairs are stored
+    pub fn is_empty(&self) -> bool {
+        self.memory.len() == 0
+    }
+
+    /// Scaling `sigma` of the initial matrix `sigma I`
+    pub fn scaling(&self) -> F {
+        match (self.scaling, self.memory.len()) {
+            (Some(sigma), _) => sigma,
+            (None, 0) => float!(1.0),
+            (None, k) => self.memory.yty()[k - 1][k - 1] / self.memory.sty()[k - 1][k - 1],
+        }
+    }
+
+    /// `K = [[sigma S^T S, L], [L^T, -D]]`
+    fn middle(&self, sigma: F) -> Vec<Vec<F>> {
+        let k = self.memory.len();
+        let sts = self.memory.sts();
+        let sty = self.memory.sty();
+        let mut mat = vec![vec![float!(0.0); 2 * k]; 2 * k];
+        for i in 0..k {
+            for j in 0..k {
+                mat[i][j] = sigma * sts[i][j];
+                if i > j {
+                    mat[i][k + j] = sty[i][j];
+                    mat[k + j][i] = sty[i][j];
+                }
+            }
+            mat[k + i][k + i] = -sty[i][i];
+        }
+        mat
+    }
+
+    /// Computes `B v`
+    pub fn hess_vec(&self, v: &P) -> Result<P, Error> {
+        let sigma = self.scaling();
+        if self.is_empty() {
+            return Ok(v.mul(&sigma));
+        }
+        let q: Vec<F> = self
+            .memory
+            .s_dot(v)
+            .into_iter()
+            .map(|x| sigma * x)
+            .chain(self.memory.y_dot(v))
+            .collect();
+        let p = dense::solve(&self.middle(sigma), &q)?;
+        let (p_s, p_y) = p.split_at(self.len());
+        let a: Vec<F> = p_s.iter().map(|&x| -sigma * x).collect();
+        let b: Vec<F> = p_y.iter().map(|&x| -x).collect();
+        Ok(self.memory.combine(sigma, v, &a, &b))
+    }
+
+    /// Computes `H v`, where `H` is the inverse of `B`
+    pub fn inv_hess_vec(&self, v: &P) -> Result<P, Error> {
+        let gamma = float!(1.0) / self.scaling();
+        if self.is_empty() {
+            return Ok(v.mul(&gamma));
+        }
+        let k = self.len();
+        let sty = self.memory.sty();
+        let yty = self.memory.yty();
+        let r: Vec<Vec<F>> = (0..k)
+            .map(|i| {
+                (0..k)
+                    .map(|j| if i <= j { sty[i][j] } else { float!(0.0) })
+                    .collect()
+            })
+            .collect();
+        let r_t: Vec<Vec<F>> = (0..k).map(|i| (0..k).map(|j| r[j][i]).collect()).collect();
+        let b: Vec<F> = self
+            .memory
+            .y_dot(v)
+            .into_iter()
+            .map(|x| gamma * x)
+            .collect();
+        let c = dense::solve(&r, &self.memory.s_dot(v))?;
+        // (D + gamma Y^T Y) c - b
+        let t: Vec<F> = (0..k)
+            .map(|i| {
+                (0..k).fold(sty[i][i] * c[i] - b[i], |acc, j| {
+                    acc + gamma * yty[i][j] * c[j]
+                })
+            })
+            .collect();
+        let p = dense::solve(&r_t, &t)?;
+        let c: Vec<F> = c.iter().map(|&x| -gamma * x).collect();
+        Ok(self.memory.combine(gamma, v, &p, &c))
+    }
+
+    /// Returns the smallest and the largest eigenvalue of `B`
+    ///
+    /// `sigma` is always included, since it is an eigenvalue of `B` as soon as the number of
+    /// parameters exceeds twice the number of stored pairs. Otherwise the returned values are
+    /// lower and upper bounds.
+    pub fn eigenvalue_bounds(&self) -> Result<(F, F), Error> {
+        let sigma = self.scaling();
+        let k = self.len();
+        let sts = self.memory.sts();
+        let sty = self.memory.sty();
+        let yty = self.memory.yty();
+        // Gram matrix of [S, Y]
+        let mut gram = vec![vec![float!(0.0); 2 * k]; 2 * k];
+        for i in 0..k {
+            for j in 0..k {
+                gram[i][j] = sts[i][j];
+                gram[i][k + j] = sty[i][j];
+                gram[k + j][i] = sty[i][j];
+                gram[k + i][k + j] = yty[i][j];
+            }
+        }
+        // -E K^{-1} E with E = diag(sigma I, I)
+        let mut middle = dense::inverse(&self.middle(sigma))?;
+        for (i, row) in middle.iter_mut().enumerate() {
+            for (j, mij) in row.iter_mut().enumerate() {
+                let ei = if i < k { sigma } else { float!(1.0) };
+                let ej = if j < k { sigma } else { float!(1.0) };
+                *mij = -ei * *mij * ej;
+            }
+        }
+        Ok(dense::eigenvalue_bounds(sigma, &gram, &middle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    /// Symmetric positive definite matrix
+    fn hessian() -> Vec<Vec<f64>> {
+        vec![
+            vec![4.0, 1.0, 0.0, 0.5, 0.0],
+            vec![1.0, 3.0, 0.5, 0.0, 0.0],
+            vec![0.0, 0.5, 2.0, 0.0, 0.2],
+            vec![0.5, 0.0, 0.0, 5.0, 1.0],
+            vec![0.0, 0.0, 0.2, 1.0, 1.5],
+        ]
+    }
+
+    fn steps() -> Vec<Vec<f64>> {
+        vec![
+            vec![1.0, 0.0, -1.0, 0.5, 0.0],
+            vec![0.2, 1.0, 0.0, -0.3, 1.0],
+            vec![-0.5, 0.3, 1.0, 0.0, 0.4],
+        ]
+    }
+
+    fn matvec(a: &[Vec<f64>], v: &[f64]) -> Vec<f64> {
+        a.iter()
+            .map(|row| row.iter().zip(v.iter()).map(|(x, y)| x * y).sum())
+            .collect()
+    }
+
+    fn dot(a: &[f64], b: &[f64]) -> f64 {
+        a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+    }
+
+    /// Dense BFGS update of `B = sigma I` with the given pairs
+    fn dense_bfgs(sigma: f64, pairs: &[(Vec<f64>, Vec<f64>)]) -> Vec<Vec<f64>> {
+        let n = pairs[0].0.len();
+        let mut b: Vec<Vec<f64>> = dense::identity(n)
+            .into_iter()
+            .map(|row: Vec<f64>| row.into_iter().map(|x| sigma * x).collect())
+            .collect();
+        for (s, y) in pairs {
+            let bs = matvec(&b, s);
+            let sbs = dot(s, &bs);
+            let sy = dot(s, y);
+            for i in 0..n {
+                for j in 0..n {
+                    b[i][j] += y[i] * y[j] / sy - bs[i] * bs[j] / sbs;
+                }
+            }
+        }
+        b
+    }
+
+    #[test]
+    fn test_new() {
+        let CompactLBFGS { memory, scaling } = CompactLBFGS::<Vec<f64>, f64>::new(3);
+        assert_eq!(memory.len(), 0);
+        assert!(scaling.is_none());
+    }
+
+    #[test]
+    fn test_with_scaling() {
+        let bfgs = CompactLBFGS::<Vec<f64>, f64>::new(3)
+            .with_scaling(2.0)
+            .unwrap();
+        assert_eq!(bfgs.scaling().to_ne_bytes(), 2.0f64.to_ne_bytes());
+
+        for sigma in [0.0, -1.0, f64::INFINITY, f64::NAN] {
+            let res = CompactLBFGS::<Vec<f64>, f64>::new(3).with_scaling(sigma);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`CompactLBFGS`: scaling must be finite and larger than 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_push() {
+        let mut bfgs: CompactLBFGS<Vec<f64>, f64> = CompactLBFGS::new(2);
+        assert!(bfgs.is_empty());
+        assert_eq!(bfgs.scaling().to_ne_bytes(), 1.0f64.to_ne_bytes());
+
+        // Negative curvature is rejected
+        assert!(!bfgs.push(vec![1.0, 0.0], vec![-1.0, 0.0]));
+        assert!(!bfgs.push(vec![1.0, 0.0], vec![0.0, 1.0]));
+        assert!(bfgs.is_empty());
+
+        assert!(bfgs.push(vec![1.0, 0.0], vec![2.0, 0.0]));
+        assert_relative_eq!(bfgs.scaling(), 2.0, epsilon = 1e-12);
+        assert!(bfgs.push(vec![0.0, 1.0], vec![1.0, 3.0]));
+        assert_relative_eq!(bfgs.scaling(), 10.0 / 3.0, epsilon = 1e-12);
+        assert!(bfgs.push(vec![1.0, 1.0], vec![3.0, 4.0]));
+        assert_eq!(bfgs.len(), 2);
+
+        bfgs.clear();
+        assert!(bfgs.is_empty());
+    }
+
+    #[test]
+    fn test_empty() {
+        let bfgs: CompactLBFGS<Vec<f64>, f64> = CompactLBFGS::new(2).with_scaling(2.0).unwrap();
+        assert_eq!(bfgs.hess_vec(&vec![1.0, -1.0]).unwrap(), vec![2.0, -2.0]);
+        assert_eq!(
+            bfgs.inv_hess_vec(&vec![1.0, -1.0]).unwrap(),
+            vec![0.5, -0.5]
+        );
+        let (lo, hi) = bfgs.eigenvalue_bounds().unwrap();
+        assert_eq!(lo.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(hi.to_ne_bytes(), 2.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_against_dense_bfgs() {
+        let a = hessian();
+        let pairs: Vec<(Vec<f64>, Vec<f64>)> = steps()
+            .into_iter()
+            .map(|s| {
+                let y = matvec(&a, &s);
+                (s, y)
+            })
+            .collect();
+        let v = vec![0.3, -1.0, 2.0, 0.5, -0.7];
+
+        for (m, scaling) in [(3, Some(1.5)), (3, None), (2, Some(0.5)), (2, None)] {
+            let mut bfgs: CompactLBFGS<Vec<f64>, f64> = CompactLBFGS::new(m);
+            if let Some(sigma) = scaling {
+                bfgs = bfgs.with_scaling(sigma).unwrap();
+            }
+            for (s, y) in pairs.iter() {
+                assert!(bfgs.push(s.clone(), y.clone()));
+            }
+            assert_eq!(bfgs.len(), m);
+
+            // Only the `m` most recent pairs enter the approximation
+            let b = dense_bfgs(bfgs.scaling(), &pairs[pairs.len() - m..]);
+            let bv = bfgs.hess_vec(&v).unwrap();
+            for (x, y) in bv.iter().zip(matvec(&b, &v).iter()) {
+                assert_relative_eq!(x, y, epsilon = 1e-10);
+            }
+
+            // H is the inverse of B
+            let hbv = bfgs.inv_hess_vec(&bv).unwrap();
+            for (x, y) in hbv.iter().zip(v.iter()) {
+                assert_relative_eq!(x, y, epsilon = 1e-10);
+            }
+            let bhv = bfgs.hess_vec(&bfgs.inv_hess_vec(&v).unwrap()).unwrap();
+            for (x, y) in bhv.iter().zip(v.iter()) {
+                assert_relative_eq!(x, y, epsilon = 1e-10);
+            }
+
+            // With at most 2 pairs in 5 dimensions, the bounds are the extreme eigenvalues
+            if m == 2 {
+                let (lambda, _) = dense::sym_eigen(&b);
+                let lo_dense = lambda.iter().cloned().fold(f64::INFINITY, f64::min);
+                let hi_dense = lambda.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                let (lo, hi) = bfgs.eigenvalue_bounds().unwrap();
+                assert_relative_eq!(lo, lo_dense, epsilon = 1e-10);
+                assert_relative_eq!(hi, hi_dense, epsilon = 1e-10);
+                assert!(lo > 0.0);
+            }
+        }
+    }
+}
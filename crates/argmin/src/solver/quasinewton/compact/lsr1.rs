@@ -0,0 +1,418 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{dense, PairMemory};
+use crate::core::{ArgminFloat, Error};
+use argmin_math::{ArgminDot, ArgminMul, ArgminScaledAdd, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Relative threshold of the SR1 skipping rule
+const SKIP_THRESHOLD: f64 = 1e-8;
+
+/// # Compact limited-memory SR1 matrix
+///
+/// Limited-memory SR1 approximation of the Hessian in compact form (Byrd, Nocedal and Schnabel,
+/// 1994):
+///
+/// `B = sigma I + (Y - sigma S) (D + L + L^T - sigma S^T S)^{-1} (Y - sigma S)^T`,
+///
+/// where the columns of `S` and `Y` are the `m` most recent pairs `(s_i, y_i)`, `L` is the
+/// strictly lower triangular part of `S^T Y` and `D` its diagonal. The inverse is
+///
+/// `H = gamma I + (S - gamma Y) (R + R^T - D - gamma Y^T Y)^{-1} (S - gamma Y)^T`,
+///
+/// where `R` is the upper triangular part of `S^T Y` and `gamma = 1 / sigma`.
+///
+/// [`hess_vec`](`CompactLSR1::hess_vec`) and [`inv_hess_vec`](`CompactLSR1::inv_hess_vec`)
+/// compute `B v` and `H v` and [`eigenvalue_bounds`](`CompactLSR1::eigenvalue_bounds`) the
+/// extreme eigenvalues of `B`. Unlike BFGS, `B` may be indefinite (and singular, in which case
+/// [`inv_hess_vec`](`CompactLSR1::inv_hess_vec`) fails), which makes it a natural model for
+/// trust-region methods.
+///
+/// A pair is skipped if `|s^T (y - B s)| < r ||s|| ||y - B s||` with `r = 1e-8` (Nocedal and
+/// Wright, 2006, eq. 6.26). The scaling `sigma` of the initial matrix defaults to 1 and can be
+/// set via [`with_scaling`](`CompactLSR1::with_scaling`).
+///
+/// # Example
+///
+/// ```
+/// use argmin::solver::quasinewton::CompactLSR1;
+/// # use argmin::core::Error;
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut sr1: CompactLSR1<Vec<f64>, f64> = CompactLSR1::new(5);
+/// // Pairs of the quadratic with Hessian diag(-1, 4)
+/// sr1.push(vec![1.0, 0.0], vec![-1.0, 0.0])?;
+/// sr1.push(vec![0.0, 1.0], vec![0.0, 4.0])?;
+///
+/// let bv = sr1.hess_vec(&vec![1.0, 1.0])?;
+/// let (lambda_min, lambda_max) = sr1.eigenvalue_bounds()?;
+/// # assert!((bv[0] + 1.0).abs() < 1e-12);
+/// # assert!((bv[1] - 4.0).abs() < 1e-12);
+/// # assert!((lambda_min + 1.0).abs() < 1e-12);
+/// # assert!((lambda_max - 4.0).abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ## References
+///
+/// Richard H. Byrd, Jorge Nocedal and Robert B. Schnabel (1994). Representations of quasi-Newton
+/// matrices and their use in limited memory methods. Mathematical Programming 63, 129–156.
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct CompactLSR1<P, F> {
+    /// Stored pairs
+    memory: PairMemory<P, F>,
+    /// Scaling of the initial matrix
+    scaling: F,
+}
+
+impl<P, F> CompactLSR1<P, F>
+where
+    P: Clone + ArgminDot<P, F> + ArgminSub<P, P> + ArgminScaledAdd<P, F, P> + ArgminMul<F, P>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`CompactLSR1`] storing at most `m` pairs
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::CompactLSR1;
+    /// let sr1: CompactLSR1<Vec<f64>, f64> = CompactLSR1::new(5);
+    /// ```
+    pub fn new(m: usize) -> Self {
+        CompactLSR1 {
+            memory: PairMemory::new(m),
+            scaling: float!(1.0),
+        }
+    }
+
+    /// Set the scaling `sigma` of the initial matrix `sigma I`
+    ///
+    /// `sigma` must be finite and larger than 0. Defaults to 1. Changing the scaling removes all
+    /// stored pairs, since the skipping rule depends on it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::CompactLSR1;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let sr1: CompactLSR1<Vec<f64>, f64> = CompactLSR1::new(5).with_scaling(2.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_scaling(mut self, sigma: F) -> Result<Self, Error> {
+        if sigma <= float!(0.0) || !sigma.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CompactLSR1`: scaling must be finite and larger than 0."
+            ));
+        }
+        self.scaling = sigma;
+        self.memory.clear();
+        Ok(self)
+    }
+
+    /// Stores the pair `(s, y)` unless it is rejected by the skipping rule
+    ///
+    /// Returns whether the pair was stored. If the memory is full, the oldest pair is discarded.
+    pub fn push(&mut self, s: P, y: P) -> Result<bool, Error> {
+        let r = y.sub(&self.hess_vec(&s)?);
+        let denom = s.dot(&r).abs();
+        if denom <= float!(SKIP_THRESHOLD) * (s.dot(&s) * r.dot(&r)).sqrt() || denom.is_nan() {
+            return Ok(false);
+        }
+        self.memory.push(s, y);
+        Ok(true)
+    }
+
+    /// Removes all stored pairs
+    pub fn clear(&mut self) {
+        self.memory.clear();
+    }
+
+    /// Number of stored pairs
+    pub fn len(&self) -> usize {
+        self.memory.len()
+    }
+
+    /// Returns `true` if no pairs are stored
+    pub fn is_empty(&self) -> bool {
+        self.memory.len() == 0
+    }
+
+    /// Scaling `sigma` of the initial matrix `sigma I`
+    pub fn scaling(&self) -> F {
+        self.scaling
+    }
+
+    /// `D + L + L^T - sigma S^T S`
+    fn middle(&self) -> Vec<Vec<F>> {
+        let sts = self.memory.sts();
+        let sty = self.memory.sty();
+        let k = self.len();
+        (0..k)
+            .map(|i| {
+                (0..k)
+                    .map(|j| sty[i.max(j)][i.min(j)] - self.scaling * sts[i][j])
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Computes `B v`
+    pub fn hess_vec(&self, v: &P) -> Result<P, Error> {
+        let sigma = self.scaling;
+        if self.is_empty() {
+            return Ok(v.mul(&sigma));
+        }
+        // (Y - sigma S)^T v
+        let q: Vec<F> = self
+            .memory
+            .y_dot(v)
+            .into_iter()
+            .zip(self.memory.s_dot(v))
+            .map(|(yv, sv)| yv - sigma * sv)
+            .collect();
+        let p = dense::solve(&self.middle(), &q)?;
+        let a: Vec<F> = p.iter().map(|&x| -sigma * x).collect();
+        Ok(self.memory.combine(sigma, v, &a, &p))
+    }
+
+    /// Computes `H v`, where `H` is the inverse of `B`
+    ///
+    /// Fails if `B` is singular.
+    pub fn inv_hess_vec(&self, v: &P) -> Result<P, Error> {
+        let gamma = float!(1.0) / self.scaling;
+        if self.is_empty() {
+            return Ok(v.mul(&gamma));
+        }
+        let sty = self.memory.sty();
+        let yty = self.memory.yty();
+        let k = self.len();
+        // R + R^T - D - gamma Y^T Y
+        let middle: Vec<Vec<F>> = (0..k)
+            .map(|i| {
+                (0..k)
+                    .map(|j| sty[i.min(j)][i.max(j)] - gamma * yty[i][j])
+                    .collect()
+            })
+            .collect();
+        // (S - gamma Y)^T v
+        let q: Vec<F> = self
+            .memory
+            .s_dot(v)
+            .into_iter()
+            .zip(self.memory.y_dot(v))
+            .map(|(sv, yv)| sv - gamma * yv)
+            .collect();
+        let p = dense::solve(&middle, &q)?;
+        let b: Vec<F> = p.iter().map(|&x| -gamma * x).collect();
+        Ok(self.memory.combine(gamma, v, &p, &b))
+    }
+
+    /// Returns the smallest and the largest eigenvalue of `B`
+    ///
+    /// `sigma` is always included, since it is an eigenvalue of `B` as soon as the number of
+    /// parameters exceeds the number of stored pairs. Otherwise the returned values are lower and
+    /// upper bounds.
+    pub fn eigenvalue_bounds(&self) -> Result<(F, F), Error> {
+        let sigma = self.scaling;
+        let sts = self.memory.sts();
+        let sty = self.memory.sty();
+        let yty = self.memory.yty();
+        let k = self.len();
+        // Gram matrix of Y - sigma S
+        let gram: Vec<Vec<F>> = (0..k)
+            .map(|i| {
+                (0..k)
+                    .map(|j| {
+                        yty[i][j] - sigma * (sty[j][i] + sty[i][j]) + sigma * sigma * sts[i][j]
+                    })
+                    .collect()
+            })
+            .collect();
+        let middle = dense::inverse(&self.middle())?;
+        Ok(dense::eigenvalue_bounds(sigma, &gram, &middle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    /// Symmetric indefinite matrix
+    fn hessian() -> Vec<Vec<f64>> {
+        vec![
+            vec![4.0, 1.0, 0.0, 0.5, 0.0],
+            vec![1.0, -3.0, 0.5, 0.0, 0.0],
+            vec![0.0, 0.5, 2.0, 0.0, 0.2],
+            vec![0.5, 0.0, 0.0, -1.0, 1.0],
+            vec![0.0, 0.0, 0.2, 1.0, 1.5],
+        ]
+    }
+
+    fn steps() -> Vec<Vec<f64>> {
+        vec![
+            vec![1.0, 0.0, -1.0, 0.5, 0.0],
+            vec![0.2, 1.0, 0.0, -0.3, 1.0],
+            vec![-0.5, 0.3, 1.0, 0.0, 0.4],
+        ]
+    }
+
+    fn matvec(a: &[Vec<f64>], v: &[f64]) -> Vec<f64> {
+        a.iter()
+            .map(|row| row.iter().zip(v.iter()).map(|(x, y)| x * y).sum())
+            .collect()
+    }
+
+    fn dot(a: &[f64], b: &[f64]) -> f64 {
+        a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+    }
+
+    /// Dense SR1 update of `B = sigma I` with the given pairs
+    fn dense_sr1(sigma: f64, pairs: &[(Vec<f64>, Vec<f64>)]) -> Vec<Vec<f64>> {
+        let n = pairs[0].0.len();
+        let mut b: Vec<Vec<f64>> = dense::identity(n)
+            .into_iter()
+            .map(|row: Vec<f64>| row.into_iter().map(|x| sigma * x).collect())
+            .collect();
+        for (s, y) in pairs {
+            let bs = matvec(&b, s);
+            let r: Vec<f64> = y.iter().zip(bs.iter()).map(|(yi, bi)| yi - bi).collect();
+            let rs = dot(&r, s);
+            for i in 0..n {
+                for j in 0..n {
+                    b[i][j] += r[i] * r[j] / rs;
+                }
+            }
+        }
+        b
+    }
+
+    #[test]
+    fn test_new() {
+        let CompactLSR1 { memory, scaling } = CompactLSR1::<Vec<f64>, f64>::new(3);
+        assert_eq!(memory.len(), 0);
+        assert_eq!(scaling.to_ne_bytes(), 1.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_scaling() {
+        let sr1 = CompactLSR1::<Vec<f64>, f64>::new(3)
+            .with_scaling(2.0)
+            .unwrap();
+        assert_eq!(sr1.scaling().to_ne_bytes(), 2.0f64.to_ne_bytes());
+
+        for sigma in [0.0, -1.0, f64::INFINITY, f64::NAN] {
+            let res = CompactLSR1::<Vec<f64>, f64>::new(3).with_scaling(sigma);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`CompactLSR1`: scaling must be finite and larger than 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_push() {
+        let mut sr1: CompactLSR1<Vec<f64>, f64> = CompactLSR1::new(2);
+        assert!(sr1.is_empty());
+
+        // Pairs which are already reproduced by `B` are skipped
+        assert!(!sr1.push(vec![1.0, 0.0], vec![1.0, 0.0]).unwrap());
+        // y - B s orthogonal to s
+        assert!(!sr1.push(vec![1.0, 0.0], vec![1.0, 1.0]).unwrap());
+        assert!(sr1.is_empty());
+
+        assert!(sr1.push(vec![1.0, 0.0], vec![-1.0, 0.0]).unwrap());
+        assert!(sr1.push(vec![0.0, 1.0], vec![0.0, 3.0]).unwrap());
+        // The secant equation is already satisfied for all steps
+        assert!(!sr1.push(vec![1.0, 1.0], vec![-1.0, 3.0]).unwrap());
+        assert_eq!(sr1.len(), 2);
+
+        sr1.clear();
+        assert!(sr1.is_empty());
+    }
+
+    #[test]
+    fn test_empty() {
+        let sr1: CompactLSR1<Vec<f64>, f64> = CompactLSR1::new(2).with_scaling(2.0).unwrap();
+        assert_eq!(sr1.hess_vec(&vec![1.0, -1.0]).unwrap(), vec![2.0, -2.0]);
+        assert_eq!(sr1.inv_hess_vec(&vec![1.0, -1.0]).unwrap(), vec![0.5, -0.5]);
+        let (lo, hi) = sr1.eigenvalue_bounds().unwrap();
+        assert_eq!(lo.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(hi.to_ne_bytes(), 2.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_against_dense_sr1() {
+        let a = hessian();
+        let pairs: Vec<(Vec<f64>, Vec<f64>)> = steps()
+            .into_iter()
+            .map(|s| {
+                let y = matvec(&a, &s);
+                (s, y)
+            })
+            .collect();
+        let v = vec![0.3, -1.0, 2.0, 0.5, -0.7];
+
+        for (m, sigma) in [(3, 1.0), (3, 0.5), (2, 1.0), (2, 2.0)] {
+            let mut sr1: CompactLSR1<Vec<f64>, f64> =
+                CompactLSR1::new(m).with_scaling(sigma).unwrap();
+            for (s, y) in pairs.iter() {
+                assert!(sr1.push(s.clone(), y.clone()).unwrap());
+            }
+            assert_eq!(sr1.len(), m);
+
+            // Only the `m` most recent pairs enter the approximation
+            let b = dense_sr1(sigma, &pairs[pairs.len() - m..]);
+            let bv = sr1.hess_vec(&v).unwrap();
+            for (x, y) in bv.iter().zip(matvec(&b, &v).iter()) {
+                assert_relative_eq!(x, y, epsilon = 1e-10);
+            }
+
+            // Secant equations of the stored pairs
+            for (s, y) in pairs[pairs.len() - m..].iter() {
+                for (x, y) in sr1.hess_vec(s).unwrap().iter().zip(y.iter()) {
+                    assert_relative_eq!(x, y, epsilon = 1e-10);
+                }
+            }
+
+            // H is the inverse of B
+            let hbv = sr1.inv_hess_vec(&bv).unwrap();
+            for (x, y) in hbv.iter().zip(v.iter()) {
+                assert_relative_eq!(x, y, epsilon = 1e-10);
+            }
+
+            // With at most 3 pairs in 5 dimensions, the bounds are the extreme eigenvalues
+            let (lambda, _) = dense::sym_eigen(&b);
+            let lo_dense = lambda.iter().cloned().fold(f64::INFINITY, f64::min);
+            let hi_dense = lambda.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let (lo, hi) = sr1.eigenvalue_bounds().unwrap();
+            assert_relative_eq!(lo, lo_dense, epsilon = 1e-10);
+            assert_relative_eq!(hi, hi_dense, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_negative_curvature() {
+        let mut sr1: CompactLSR1<Vec<f64>, f64> = CompactLSR1::new(5);
+        sr1.push(vec![1.0, 0.0, 0.0], vec![-2.0, 0.0, 0.0]).unwrap();
+        let (lo, hi) = sr1.eigenvalue_bounds().unwrap();
+        assert_relative_eq!(lo, -2.0, epsilon = 1e-12);
+        assert_relative_eq!(hi, 1.0, epsilon = 1e-12);
+    }
+}
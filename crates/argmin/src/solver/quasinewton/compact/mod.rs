@@ -0,0 +1,206 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Compact representations of limited-memory quasi-Newton matrices
+//!
+//! The limited-memory BFGS and SR1 approximations of the Hessian `B` built from the `m` most
+//! recent pairs `s_i = x_{i+1} - x_i`, `y_i = g_{i+1} - g_i` can be written as a scaled identity
+//! plus a low-rank correction
+//!
+//! `B = sigma I + Psi M Psi^T`,
+//!
+//! where `Psi` has at most `2m` columns built from the pairs and `M` is a small dense matrix
+//! (Byrd, Nocedal and Schnabel, 1994). This allows computing products with `B` and its inverse
+//! `H` in `O(mn)` operations and the extreme eigenvalues of `B` from the eigenvalues of a
+//! `2m x 2m` matrix, which is what trust-region and bound-constrained limited-memory methods
+//! require in addition to the two-loop recursion of [`LBFGS`](`super::LBFGS`).
+//!
+//! * [`CompactLBFGS`]
+//! * [`CompactLSR1`]
+//!
+//! ## Reference
+//!
+//! Richard H. Byrd, Jorge Nocedal and Robert B. Schnabel (1994). Representations of quasi-Newton
+//! matrices and their use in limited memory methods. Mathematical Programming 63, 129–156.
+//!
+//! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+
+mod dense;
+mod lbfgs;
+mod lsr1;
+
+pub use self::lbfgs::CompactLBFGS;
+pub use self::lsr1::CompactLSR1;
+
+use crate::core::ArgminFloat;
+use argmin_math::{ArgminDot, ArgminMul, ArgminScaledAdd};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// The `m` most recent pairs `(s_i, y_i)` together with their inner products `S^T S`, `S^T Y` and
+/// `Y^T Y`, which are updated incrementally whenever a pair is added.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub(super) struct PairMemory<P, F> {
+    /// Maximum number of stored pairs
+    m: usize,
+    /// Steps `s_i`, oldest first
+    s: VecDeque<P>,
+    /// Gradient differences `y_i`, oldest first
+    y: VecDeque<P>,
+    /// `S^T S`
+    sts: Vec<Vec<F>>,
+    /// `S^T Y`, i.e. `sty[i][j] = s_i^T y_j`
+    sty: Vec<Vec<F>>,
+    /// `Y^T Y`
+    yty: Vec<Vec<F>>,
+}
+
+impl<P, F> PairMemory<P, F>
+where
+    P: Clone + ArgminDot<P, F> + ArgminScaledAdd<P, F, P> + ArgminMul<F, P>,
+    F: ArgminFloat,
+{
+    /// Construct an empty memory for at most `m` pairs
+    pub(super) fn new(m: usize) -> Self {
+        PairMemory {
+            m,
+            s: VecDeque::with_capacity(m),
+            y: VecDeque::with_capacity(m),
+            sts: Vec::with_capacity(m),
+            sty: Vec::with_capacity(m),
+            yty: Vec::with_capacity(m),
+        }
+    }
+
+    /// Number of stored pairs
+    pub(super) fn len(&self) -> usize {
+        self.s.len()
+    }
+
+    /// Removes all stored pairs
+    pub(super) fn clear(&mut self) {
+        self.s.clear();
+        self.y.clear();
+        self.sts.clear();
+        self.sty.clear();
+        self.yty.clear();
+    }
+
+    /// Stores the pair `(s, y)`, discarding the oldest pair if the memory is full
+    pub(super) fn push(&mut self, s: P, y: P) {
+        if self.m == 0 {
+            return;
+        }
+        if self.len() == self.m {
+            self.s.pop_front();
+            self.y.pop_front();
+            for mat in [&mut self.sts, &mut self.sty, &mut self.yty] {
+                mat.remove(0);
+                for row in mat.iter_mut() {
+                    row.remove(0);
+                }
+            }
+        }
+        let s_s: Vec<F> = self.s.iter().map(|si| si.dot(&s)).collect();
+        let s_y: Vec<F> = self.s.iter().map(|si| si.dot(&y)).collect();
+        let y_s: Vec<F> = self.y.iter().map(|yi| s.dot(yi)).collect();
+        let y_y: Vec<F> = self.y.iter().map(|yi| yi.dot(&y)).collect();
+        for (i, row) in self.sts.iter_mut().enumerate() {
+            row.push(s_s[i]);
+        }
+        for (i, row) in self.sty.iter_mut().enumerate() {
+            row.push(s_y[i]);
+        }
+        for (i, row) in self.yty.iter_mut().enumerate() {
+            row.push(y_y[i]);
+        }
+        let s_sy = s.dot(&y);
+        self.sts.push(s_s.into_iter().chain([s.dot(&s)]).collect());
+        self.sty.push(y_s.into_iter().chain([s_sy]).collect());
+        self.yty.push(y_y.into_iter().chain([y.dot(&y)]).collect());
+        self.s.push_back(s);
+        self.y.push_back(y);
+    }
+
+    /// `S^T S`
+    pub(super) fn sts(&self) -> &[Vec<F>] {
+        &self.sts
+    }
+
+    /// `S^T Y`
+    pub(super) fn sty(&self) -> &[Vec<F>] {
+        &self.sty
+    }
+
+    /// `Y^T Y`
+    pub(super) fn yty(&self) -> &[Vec<F>] {
+        &self.yty
+    }
+
+    /// `S^T v`
+    pub(super) fn s_dot(&self, v: &P) -> Vec<F> {
+        self.s.iter().map(|si| si.dot(v)).collect()
+    }
+
+    /// `Y^T v`
+    pub(super) fn y_dot(&self, v: &P) -> Vec<F> {
+        self.y.iter().map(|yi| yi.dot(v)).collect()
+    }
+
+    /// `scale v + S a + Y b`
+    pub(super) fn combine(&self, scale: F, v: &P, a: &[F], b: &[F]) -> P {
+        let init = v.mul(&scale);
+        let out = self
+            .s
+            .iter()
+            .zip(a.iter())
+            .fold(init, |acc, (si, ai)| acc.scaled_add(ai, si));
+        self.y
+            .iter()
+            .zip(b.iter())
+            .fold(out, |acc, (yi, bi)| acc.scaled_add(bi, yi))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_pair_memory() {
+        let mut memory: PairMemory<Vec<f64>, f64> = PairMemory::new(2);
+        memory.push(vec![1.0, 0.0], vec![2.0, 1.0]);
+        memory.push(vec![0.0, 1.0], vec![1.0, 3.0]);
+        memory.push(vec![1.0, 1.0], vec![3.0, 4.0]);
+        assert_eq!(memory.len(), 2);
+
+        // Only the two most recent pairs are kept
+        assert_eq!(memory.sts(), &[vec![1.0, 1.0], vec![1.0, 2.0]]);
+        assert_eq!(memory.sty(), &[vec![3.0, 4.0], vec![4.0, 7.0]]);
+        assert_eq!(memory.yty(), &[vec![10.0, 15.0], vec![15.0, 25.0]]);
+
+        let v = vec![2.0, -1.0];
+        assert_eq!(memory.s_dot(&v), vec![-1.0, 1.0]);
+        assert_eq!(memory.y_dot(&v), vec![-1.0, 2.0]);
+        let c = memory.combine(2.0, &v, &[1.0, -1.0], &[0.5, 0.0]);
+        assert_relative_eq!(c[0], 4.0 - 1.0 + 0.5, epsilon = 1e-12);
+        assert_relative_eq!(c[1], -2.0 + 0.0 + 1.5, epsilon = 1e-12);
+
+        memory.clear();
+        assert_eq!(memory.len(), 0);
+        assert!(memory.sts().is_empty());
+
+        // Nothing is stored without memory
+        let mut memory: PairMemory<Vec<f64>, f64> = PairMemory::new(0);
+        memory.push(vec![1.0], vec![1.0]);
+        assert_eq!(memory.len(), 0);
+    }
+}
@@ -7,9 +7,12 @@
 
 use crate::core::{
     ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
-    OptimizationResult, Problem, Solver, TerminationReason, TerminationStatus, KV,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus,
+    ToleranceConfig, KV,
+};
+use argmin_math::{
+    ArgminDot, ArgminL2Norm, ArgminMulAssign, ArgminScaledAddAssign, ArgminSub, ArgminSubAssign,
 };
-use argmin_math::{ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminSub};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -31,6 +34,10 @@ use serde::{Deserialize, Serialize};
 /// [`with_tolerance_grad`](`DFP::with_tolerance_grad`): If the norm of the gradient is below
 /// said tolerance, the algorithm stops. It defaults to `sqrt(EPSILON)`.
 ///
+/// All convergence tolerances, including a tolerance on the change of the parameter vector
+/// (disabled by default) and relative instead of absolute tolerances, can be set via
+/// [`with_tolerances`](`DFP::with_tolerances`) (see [`ToleranceConfig`]).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
@@ -44,8 +51,8 @@ use serde::{Deserialize, Serialize};
 pub struct DFP<L, F> {
     /// line search
     linesearch: L,
-    /// Tolerance for the stopping criterion based on the change of the norm on the gradient
-    tol_grad: F,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
 }
 
 impl<L, F> DFP<L, F>
@@ -64,7 +71,7 @@ where
     pub fn new(linesearch: L) -> Self {
         DFP {
             linesearch,
-            tol_grad: F::epsilon().sqrt(),
+            tol: ToleranceConfig::with_defaults(F::epsilon().sqrt(), float!(0.0)),
         }
     }
 
@@ -90,17 +97,48 @@ where
                 "`DFP`: gradient tolerance must be >= 0."
             ));
         }
-        self.tol_grad = tol_grad;
+        self.tol = self.tol.with_grad(tol_grad)?;
         Ok(self)
     }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::DFP;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let dfp: DFP<_, f64> = DFP::new(linesearch).with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
 }
 
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for DFP<L, F>
 where
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
-    P: Clone + ArgminSub<P, P> + ArgminDot<G, F> + ArgminDot<P, H> + ArgminMul<F, P>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminDot<G, F>
+        + ArgminDot<P, H>
+        + ArgminMulAssign<F>
+        + ArgminL2Norm<F>
+        + 'static,
     G: Clone + ArgminSub<G, G> + ArgminL2Norm<F> + ArgminDot<P, F>,
-    H: Clone + ArgminSub<H, H> + ArgminDot<G, P> + ArgminAdd<H, H> + ArgminMul<F, H>,
+    H: Clone
+        + ArgminSubAssign<H>
+        + ArgminDot<G, P>
+        + ArgminScaledAddAssign<H, F>
+        + ArgminMulAssign<F>,
     L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
     F: ArgminFloat,
 {
@@ -167,28 +205,38 @@ where
             "`DFP`: Gradient in state not set."
         ))?;
 
-        let inv_hessian = state.take_inv_hessian().ok_or_else(argmin_error_closure!(
+        let mut inv_hessian = state.take_inv_hessian().ok_or_else(argmin_error_closure!(
             PotentialBug,
             "`DFP`: Inverse Hessian in state not set."
         ))?;
 
-        let p = inv_hessian.dot(&prev_grad).mul(&float!(-1.0));
+        let mut p: P = inv_hessian.dot(&prev_grad);
+        p.mul_assign(&float!(-1.0));
 
         self.linesearch.search_direction(p);
+        if let Some(max_step_length) = problem.max_step_length(&param) {
+            self.linesearch.max_step_length(max_step_length)?;
+        }
+        if let Some(move_limits) = problem.move_limits::<P>() {
+            self.linesearch.move_limits(move_limits);
+        }
 
         let OptimizationResult {
             problem: line_problem,
             state: mut linesearch_state,
             ..
-        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
-            .configure(|config| {
-                config
-                    .param(param.clone())
-                    .gradient(prev_grad.clone())
-                    .cost(cost)
-            })
-            .ctrlc(false)
-            .run()?;
+        } = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+                .configure(|config| {
+                    config
+                        .param(param.clone())
+                        .gradient(prev_grad.clone())
+                        .cost(cost)
+                })
+                .ctrlc(false)
+                .run()?
+        };
 
         let xk1 = linesearch_state
             .take_param()
@@ -202,7 +250,11 @@ where
         // take care of function eval counts
         problem.consume_problem(line_problem);
 
-        let grad = problem.gradient(&xk1)?;
+        // Reuse the gradient at `xk1` if the line search computed it
+        let grad = linesearch_state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&xk1))?;
         let yk = grad.sub(&prev_grad);
 
         let sk = xk1.sub(&param);
@@ -213,10 +265,11 @@ where
 
         let tmp3: P = inv_hessian.dot(&yk);
         let tmp4: F = tmp3.dot(&yk);
-        let tmp3: H = tmp3.dot(&tmp3);
-        let tmp3: H = tmp3.mul(&(float!(1.0) / tmp4));
+        let mut tmp3: H = tmp3.dot(&tmp3);
+        tmp3.mul_assign(&(float!(1.0) / tmp4));
 
-        let inv_hessian = inv_hessian.sub(&tmp3).add(&sksk.mul(&(float!(1.0) / yksk)));
+        inv_hessian.sub_assign(&tmp3);
+        inv_hessian.scaled_add_assign(&(float!(1.0) / yksk), &sksk);
 
         Ok((
             state
@@ -228,8 +281,30 @@ where
         ))
     }
 
+    fn termination_diagnostics(&self, state: &IterState<P, G, (), H, (), F>) -> KV {
+        self.tol.diagnostics(
+            state.get_gradient().map(|grad| grad.l2_norm()),
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
+    }
+
     fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
-        if state.get_gradient().unwrap().l2_norm() < self.tol_grad {
+        if self
+            .tol
+            .grad_converged(state.get_gradient().unwrap().l2_norm(), state.get_cost())
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         TerminationStatus::NotTerminated
@@ -253,13 +328,10 @@ mod tests {
         struct MyFakeLineSearch {}
 
         let dfp: DFP<_, f64> = DFP::new(MyFakeLineSearch {});
-        let DFP {
-            linesearch,
-            tol_grad,
-        } = dfp;
+        let DFP { linesearch, tol } = dfp;
 
         assert_eq!(linesearch, MyFakeLineSearch {});
-        assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(tol.grad().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
     }
 
     #[test]
@@ -274,7 +346,7 @@ mod tests {
             assert!(res.is_ok());
 
             let nm = res.unwrap();
-            assert_eq!(nm.tol_grad.to_ne_bytes(), tol.to_ne_bytes());
+            assert_eq!(nm.tol.grad().to_ne_bytes(), tol.to_ne_bytes());
         }
 
         // incorrect parameters
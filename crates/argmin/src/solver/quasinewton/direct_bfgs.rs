@@ -0,0 +1,569 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminError, ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus,
+    ToleranceConfig, KV,
+};
+use argmin_math::{
+    ArgminAddAssign, ArgminDot, ArgminL2Norm, ArgminMul, ArgminMulAssign, ArgminSolve, ArgminSub,
+    ArgminSubAssign,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # BFGS method with direct Hessian approximation
+///
+/// Variant of the [`BFGS`](`crate::solver::quasinewton::BFGS`) method which maintains an
+/// approximation `B` of the Hessian instead of its inverse. The search direction is obtained by
+/// solving `B p = -g` via `ArgminSolve`, which for the `Vec` and `ndarray` (with
+/// `ndarray-linalg`) backends uses a Cholesky decomposition and two triangular solves, as long as
+/// `B` is symmetric and positive definite. After each step, `B` is updated via
+///
+/// `B_{k+1} = B_k - (B_k s_k s_k^T B_k) / (s_k^T B_k s_k) + (y_k y_k^T) / (y_k^T s_k)`.
+///
+/// The update is skipped if the curvature condition `y_k^T s_k > 0` is violated, which keeps `B`
+/// positive definite. Working with `B` is numerically more stable than updating the inverse and
+/// allows reusing the Hessian approximation in methods which require `B` itself, such as trust
+/// region methods. Rank-two updates of the Cholesky factor are not expressible via the generic
+/// math traits; the factorization is therefore recomputed in each iteration, which is of cubic
+/// complexity in the number of parameters.
+///
+/// The algorithm requires a line search which is provided via the constructor. Additionally an
+/// initial guess for the parameter vector and an initial Hessian approximation (for instance the
+/// identity or an approximate Hessian) is required, which are to be provided via the
+/// [`configure`](`crate::core::Executor::configure`) method of the
+/// [`Executor`](`crate::core::Executor`) (See [`IterState`], in particular [`IterState::param`]
+/// and [`IterState::hessian`]). In the same way the initial gradient and cost function
+/// corresponding to the initial parameter vector can be provided. If these are not provided,
+/// they will be computed during initialization of the algorithm.
+///
+/// The stopping criteria are the same as for [`BFGS`](`crate::solver::quasinewton::BFGS`) and can
+/// be configured via [`with_tolerance_grad`](`DirectBFGS::with_tolerance_grad`),
+/// [`with_tolerance_cost`](`DirectBFGS::with_tolerance_cost`) and
+/// [`with_tolerances`](`DirectBFGS::with_tolerances`) (see [`ToleranceConfig`]).
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## Reference
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct DirectBFGS<L, F> {
+    /// line search
+    linesearch: L,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
+}
+
+impl<L, F> DirectBFGS<L, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`DirectBFGS`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::DirectBFGS;
+    /// # let linesearch = ();
+    /// let bfgs: DirectBFGS<_, f64> = DirectBFGS::new(linesearch);
+    /// ```
+    pub fn new(linesearch: L) -> Self {
+        DirectBFGS {
+            linesearch,
+            tol: ToleranceConfig::new(),
+        }
+    }
+
+    /// The algorithm stops if the norm of the gradient is below `tol_grad`.
+    ///
+    /// The provided value must be non-negative. Defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::DirectBFGS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let bfgs: DirectBFGS<_, f64> = DirectBFGS::new(linesearch).with_tolerance_grad(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_grad(mut self, tol_grad: F) -> Result<Self, Error> {
+        if tol_grad < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DirectBFGS`: gradient tolerance must be >= 0."
+            ));
+        }
+        self.tol = self.tol.with_grad(tol_grad)?;
+        Ok(self)
+    }
+
+    /// Sets tolerance for the stopping criterion based on the change of the cost stopping criterion
+    ///
+    /// The provided value must be non-negative. Defaults to `EPSILON`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::DirectBFGS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let bfgs: DirectBFGS<_, f64> = DirectBFGS::new(linesearch).with_tolerance_cost(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_cost(mut self, tol_cost: F) -> Result<Self, Error> {
+        if tol_cost < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DirectBFGS`: cost tolerance must be >= 0."
+            ));
+        }
+        self.tol = self.tol.with_cost(tol_cost)?;
+        Ok(self)
+    }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::DirectBFGS;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let bfgs: DirectBFGS<_, f64> = DirectBFGS::new(linesearch).with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+}
+
+impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for DirectBFGS<L, F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminDot<P, H>
+        + ArgminMul<F, P>
+        + ArgminL2Norm<F>
+        + 'static,
+    G: Clone + ArgminL2Norm<F> + ArgminDot<P, F> + ArgminDot<G, H> + ArgminSub<G, G>,
+    H: ArgminSolve<G, P>
+        + ArgminDot<P, P>
+        + ArgminAddAssign<H>
+        + ArgminSubAssign<H>
+        + ArgminMulAssign<F>,
+    L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "DirectBFGS"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), H, (), F>,
+    ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`DirectBFGS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let hessian = state.take_hessian().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`DirectBFGS` requires an initial Hessian. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let cost = state.get_cost();
+        let cost = if cost.is_infinite() {
+            problem.cost(&param)?
+        } else {
+            cost
+        };
+
+        let grad = state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&param))?;
+
+        Ok((
+            state
+                .param(param)
+                .cost(cost)
+                .gradient(grad)
+                .hessian(hessian),
+            None,
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), H, (), F>,
+    ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`DirectBFGS`: Parameter vector in state not set."
+        ))?;
+
+        let cur_cost = state.get_cost();
+
+        let prev_grad = state.take_gradient().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`DirectBFGS`: Gradient in state not set."
+        ))?;
+
+        let mut hessian = state.take_hessian().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`DirectBFGS`: Hessian in state not set."
+        ))?;
+
+        // p = -B^-1 g
+        let direction = hessian
+            .solve(&prev_grad)
+            .map_err(|e| ArgminError::SingularMatrix {
+                text: format!("`DirectBFGS`: Hessian approximation could not be solved: {e}"),
+            })?
+            .mul(&float!(-1.0));
+
+        self.linesearch.search_direction(direction);
+        if let Some(max_step_length) = problem.max_step_length(&param) {
+            self.linesearch.max_step_length(max_step_length)?;
+        }
+        if let Some(move_limits) = problem.move_limits::<P>() {
+            self.linesearch.move_limits(move_limits);
+        }
+
+        // Run solver
+        let OptimizationResult {
+            problem: line_problem,
+            state: mut sub_state,
+            ..
+        } = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+                .configure(|config| {
+                    config
+                        .param(param.clone())
+                        .gradient(prev_grad.clone())
+                        .cost(cur_cost)
+                })
+                .ctrlc(false)
+                .run()?
+        };
+
+        let xk1 = sub_state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`DirectBFGS`: No parameters returned by line search."
+        ))?;
+
+        let next_cost = sub_state.get_cost();
+
+        // take care of function eval counts
+        problem.consume_problem(line_problem);
+
+        // Reuse the gradient at `xk1` if the line search computed it
+        let grad = sub_state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&xk1))?;
+
+        let yk = grad.sub(&prev_grad);
+        let sk = xk1.sub(&param);
+
+        let yksk: F = yk.dot(&sk);
+        let bksk: P = hessian.dot(&sk);
+        let skbksk: F = sk.dot(&bksk);
+
+        // Skipping the update if the curvature condition is violated keeps B positive definite
+        let hessian_update = yksk > float!(0.0) && skbksk > float!(0.0);
+        if hessian_update {
+            let mut bkskskbk: H = bksk.dot(&bksk);
+            bkskskbk.mul_assign(&(float!(1.0) / skbksk));
+            hessian.sub_assign(&bkskskbk);
+
+            let mut ykyk: H = yk.dot(&yk);
+            ykyk.mul_assign(&(float!(1.0) / yksk));
+            hessian.add_assign(&ykyk);
+        }
+
+        Ok((
+            state
+                .param(xk1)
+                .cost(next_cost)
+                .gradient(grad)
+                .hessian(hessian),
+            Some(kv!("curvature" => yksk; "hessian_update" => hessian_update;)),
+        ))
+    }
+
+    fn termination_diagnostics(&self, state: &IterState<P, G, (), H, (), F>) -> KV {
+        self.tol.diagnostics(
+            state.get_gradient().map(|grad| grad.l2_norm()),
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
+    }
+
+    fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
+        if self
+            .tol
+            .grad_converged(state.get_gradient().unwrap().l2_norm(), state.get_cost())
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::quasinewton::BFGS;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        direct_bfgs,
+        DirectBFGS<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, f64>
+    );
+
+    #[derive(Clone)]
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(argmin_testfunctions::rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(argmin_testfunctions::rosenbrock_derivative(p))
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct MyFakeLineSearch {}
+
+        let bfgs: DirectBFGS<_, f64> = DirectBFGS::new(MyFakeLineSearch {});
+        let DirectBFGS { linesearch, tol } = bfgs;
+
+        assert_eq!(linesearch, MyFakeLineSearch {});
+        assert_eq!(tol.grad().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(tol.cost().to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_tolerance_grad() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        for tol in [1e-6, 0.0, 1e-2, 1.0, 2.0] {
+            let bfgs: DirectBFGS<_, f64> = DirectBFGS::new(MyFakeLineSearch {});
+            let res = bfgs.with_tolerance_grad(tol).unwrap();
+            assert_eq!(res.tol.grad().to_ne_bytes(), tol.to_ne_bytes());
+        }
+
+        for tol in [-f64::EPSILON, -1.0, -100.0, -42.0] {
+            let bfgs: DirectBFGS<_, f64> = DirectBFGS::new(MyFakeLineSearch {});
+            let res = bfgs.with_tolerance_grad(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`DirectBFGS`: gradient tolerance must be >= 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_tolerance_cost() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        for tol in [1e-6, 0.0, 1e-2, 1.0, 2.0] {
+            let bfgs: DirectBFGS<_, f64> = DirectBFGS::new(MyFakeLineSearch {});
+            let res = bfgs.with_tolerance_cost(tol).unwrap();
+            assert_eq!(res.tol.cost().to_ne_bytes(), tol.to_ne_bytes());
+        }
+
+        for tol in [-f64::EPSILON, -1.0, -100.0, -42.0] {
+            let bfgs: DirectBFGS<_, f64> = DirectBFGS::new(MyFakeLineSearch {});
+            let res = bfgs.with_tolerance_cost(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`DirectBFGS`: cost tolerance must be >= 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init() {
+        let linesearch = MoreThuenteLineSearch::new();
+        let param: Vec<f64> = vec![-1.0, 1.0];
+        let hessian: Vec<Vec<f64>> = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+
+        let mut bfgs: DirectBFGS<_, f64> = DirectBFGS::new(linesearch);
+
+        // Forgot to initialize the parameter vector
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> = IterState::new();
+        let res = bfgs.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`DirectBFGS` requires an initial parameter vector. Please ",
+                "provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        // Forgot initial Hessian
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> =
+            IterState::new().param(param.clone());
+        let res = bfgs.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`DirectBFGS` requires an initial Hessian. Please ",
+                "provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        // All good.
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> = IterState::new()
+            .param(param.clone())
+            .hessian(hessian.clone());
+        let (mut state_out, kv) = bfgs
+            .init(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+
+        assert!(kv.is_none());
+        assert_eq!(state_out.take_param().unwrap(), param);
+        assert_eq!(state_out.take_gradient().unwrap(), param);
+        assert_eq!(state_out.take_hessian().unwrap(), hessian);
+        assert_eq!(state_out.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes())
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let init_param = vec![-1.2, 1.0];
+        let identity = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+
+        let res = Executor::new(Rosenbrock {}, DirectBFGS::new(MoreThuenteLineSearch::new()))
+            .configure(|state| {
+                state
+                    .param(init_param.clone())
+                    .hessian(identity.clone())
+                    .max_iters(100)
+            })
+            .run()
+            .unwrap();
+        let direct_param = res.state.get_best_param().unwrap().clone();
+        assert_relative_eq!(direct_param[0], 1.0, epsilon = 1e-6);
+        assert_relative_eq!(direct_param[1], 1.0, epsilon = 1e-6);
+
+        // The Hessian approximation is symmetric and consistent with the inverse approximation
+        // of `BFGS`, which follows the same iterates in exact arithmetic.
+        let hessian = res.state.get_hessian().unwrap();
+        assert_relative_eq!(hessian[0][1], hessian[1][0], epsilon = 1e-8);
+
+        let res = Executor::new(Rosenbrock {}, BFGS::new(MoreThuenteLineSearch::new()))
+            .configure(|state| state.param(init_param).inv_hessian(identity).max_iters(100))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(direct_param[0], param[0], epsilon = 1e-6);
+        assert_relative_eq!(direct_param[1], param[1], epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_skip_update_negative_curvature() {
+        // Concave in the direction of the first step: the line search returns a point with
+        // negative curvature along the step, which must not be used for the update.
+        struct Concave {}
+
+        impl CostFunction for Concave {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(-p[0].powi(2))
+            }
+        }
+
+        impl Gradient for Concave {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![-2.0 * p[0]])
+            }
+        }
+
+        let hessian = vec![vec![1.0]];
+        let linesearch = crate::solver::linesearch::BacktrackingLineSearch::new(
+            crate::solver::linesearch::condition::ArmijoCondition::new(1e-4).unwrap(),
+        );
+        let mut bfgs: DirectBFGS<_, f64> = DirectBFGS::new(linesearch);
+        let mut problem = Problem::new(Concave {});
+        let state = IterState::new().param(vec![1.0]).hessian(hessian.clone());
+        let (state, _) = bfgs.init(&mut problem, state).unwrap();
+        let (mut state, kv) = bfgs.next_iter(&mut problem, state).unwrap();
+
+        let kv = kv.unwrap();
+        assert_eq!(kv.get("hessian_update").unwrap().get_bool(), Some(false));
+        assert!(kv.get("curvature").unwrap().get_float().unwrap() < 0.0);
+        assert_eq!(state.take_hessian().unwrap(), hessian);
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminError, Error, Hessian};
+use argmin_math::ArgminInv;
+
+/// Computes an initial inverse Hessian for [`BFGS`](`crate::solver::quasinewton::BFGS`),
+/// [`DFP`](`crate::solver::quasinewton::DFP`) and [`SR1`](`crate::solver::quasinewton::SR1`) by
+/// evaluating the Hessian of `problem` once at `param` and inverting it.
+///
+/// Starting from the inverse of an approximate Hessian instead of the identity often
+/// considerably reduces the number of iterations on ill-conditioned problems. Problems without
+/// analytic Hessian can be wrapped in
+/// [`WithHessianFromGradient`](`crate::core::WithHessianFromGradient`) or
+/// [`WithHessianFromCost`](`crate::core::WithHessianFromCost`), which approximate the Hessian via
+/// finite differences. An approximate Hessian which is available otherwise can be inverted
+/// directly via `ArgminInv` and provided via
+/// [`IterState::inv_hessian`](`crate::core::IterState::inv_hessian`).
+///
+/// The Hessian must be positive definite at `param`, otherwise the search directions of
+/// [`BFGS`](`crate::solver::quasinewton::BFGS`) and [`DFP`](`crate::solver::quasinewton::DFP`)
+/// are not guaranteed to be descent directions. Returns an error if the Hessian is singular.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, Executor, Gradient, WithHessianFromGradient};
+/// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+/// # use argmin::solver::quasinewton::{initial_inv_hessian, BFGS};
+/// # #[derive(Clone)]
+/// # struct Rosenbrock {}
+/// # impl argmin::core::CostFunction for Rosenbrock {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(argmin_testfunctions::rosenbrock(p))
+/// #     }
+/// # }
+/// # impl Gradient for Rosenbrock {
+/// #     type Param = Vec<f64>;
+/// #     type Gradient = Vec<f64>;
+/// #     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+/// #         Ok(argmin_testfunctions::rosenbrock_derivative(p))
+/// #     }
+/// # }
+/// # fn main() -> Result<(), Error> {
+/// let init_param = vec![-1.2, 1.0];
+///
+/// // Approximate the Hessian at the initial parameter vector via finite differences
+/// let fd_problem = WithHessianFromGradient::new(Rosenbrock {});
+/// let init_inv_hessian: Vec<Vec<f64>> = initial_inv_hessian(&fd_problem, &init_param)?;
+///
+/// let solver = BFGS::new(MoreThuenteLineSearch::new());
+/// let res = Executor::new(fd_problem.into_inner(), solver)
+///     .configure(|state| {
+///         state
+///             .param(init_param)
+///             .inv_hessian(init_inv_hessian)
+///             .max_iters(100)
+///     })
+///     .run()?;
+/// # assert!(res.state.best_cost < 1e-10);
+/// # Ok(())
+/// # }
+/// ```
+pub fn initial_inv_hessian<O, P, H>(problem: &O, param: &P) -> Result<H, Error>
+where
+    O: Hessian<Param = P, Hessian = H>,
+    H: ArgminInv<H>,
+{
+    let hessian = problem.hessian(param)?;
+    Ok(hessian.inv().map_err(|e| ArgminError::SingularMatrix {
+        text: format!("Initial Hessian could not be inverted: {e}"),
+    })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{CostFunction, Executor, Gradient, State, WithHessianFromGradient};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::quasinewton::{BFGS, SR1};
+    use approx::assert_relative_eq;
+
+    /// Ill-conditioned quadratic function with minimum at `(1, ..., 1)`
+    #[derive(Clone)]
+    struct IllConditioned {}
+
+    impl IllConditioned {
+        fn scale(i: usize) -> f64 {
+            10f64.powi(i as i32)
+        }
+    }
+
+    impl CostFunction for IllConditioned {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let mut cost = 0.0;
+            for (i, w) in p.windows(2).enumerate() {
+                cost += Self::scale(i) * (w[0] - 1.0).powi(2) + (w[0] - w[1]).powi(2);
+            }
+            Ok(cost)
+        }
+    }
+
+    impl Gradient for IllConditioned {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            let mut grad = vec![0.0; p.len()];
+            for (i, w) in p.windows(2).enumerate() {
+                grad[i] += 2.0 * Self::scale(i) * (w[0] - 1.0) + 2.0 * (w[0] - w[1]);
+                grad[i + 1] -= 2.0 * (w[0] - w[1]);
+            }
+            Ok(grad)
+        }
+    }
+
+    #[test]
+    fn test_initial_inv_hessian() {
+        // Hessian: [[2, 0], [0, 8]]
+        struct Diagonal {}
+
+        impl Hessian for Diagonal {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(vec![vec![2.0, 0.0], vec![0.0, 8.0]])
+            }
+        }
+
+        let inv: Vec<Vec<f64>> = initial_inv_hessian(&Diagonal {}, &vec![0.0, 0.0]).unwrap();
+        assert_relative_eq!(inv[0][0], 0.5, epsilon = f64::EPSILON);
+        assert_relative_eq!(inv[0][1], 0.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(inv[1][0], 0.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(inv[1][1], 0.125, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_initial_inv_hessian_singular() {
+        struct Singular {}
+
+        impl Hessian for Singular {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(vec![vec![1.0, 1.0], vec![1.0, 1.0]])
+            }
+        }
+
+        let res: Result<Vec<Vec<f64>>, Error> = initial_inv_hessian(&Singular {}, &vec![0.0, 0.0]);
+        assert_error!(
+            res,
+            ArgminError,
+            "Singular matrix: \"Initial Hessian could not be inverted: Non-invertible matrix\""
+        );
+    }
+
+    #[test]
+    fn test_bfgs_from_finite_difference_hessian() {
+        let n = 6;
+        let init_param = vec![0.0; n];
+        let identity: Vec<Vec<f64>> = (0..n)
+            .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+            .collect();
+        let fd_problem = WithHessianFromGradient::new(IllConditioned {});
+        let init_inv_hessian: Vec<Vec<f64>> =
+            initial_inv_hessian(&fd_problem, &init_param).unwrap();
+
+        let run = |inv_hessian: Vec<Vec<f64>>| {
+            let solver = BFGS::new(MoreThuenteLineSearch::new());
+            let res = Executor::new(IllConditioned {}, solver)
+                .configure(|state| {
+                    state
+                        .param(init_param.clone())
+                        .inv_hessian(inv_hessian)
+                        .max_iters(1000)
+                })
+                .run()
+                .unwrap();
+            for p in res.state.get_best_param().unwrap() {
+                assert_relative_eq!(*p, 1.0, epsilon = 1e-4);
+            }
+            res.state.get_iter()
+        };
+
+        let iters_identity = run(identity);
+        let iters_fd = run(init_inv_hessian);
+        assert!(
+            2 * iters_fd <= iters_identity,
+            "{iters_fd} vs {iters_identity}"
+        );
+    }
+
+    #[test]
+    fn test_sr1_from_finite_difference_hessian() {
+        let init_param = vec![0.0; 4];
+        let fd_problem = WithHessianFromGradient::new(IllConditioned {});
+        let init_inv_hessian: Vec<Vec<f64>> =
+            initial_inv_hessian(&fd_problem, &init_param).unwrap();
+
+        // The Hessian of a quadratic function is exact up to the finite difference error.
+        let solver = SR1::new(MoreThuenteLineSearch::new());
+        let res = Executor::new(fd_problem.into_inner(), solver)
+            .configure(|state| {
+                state
+                    .param(init_param)
+                    .inv_hessian(init_inv_hessian)
+                    .max_iters(10)
+            })
+            .run()
+            .unwrap();
+        for p in res.state.get_best_param().unwrap() {
+            assert_relative_eq!(*p, 1.0, epsilon = 1e-4);
+        }
+    }
+}
@@ -7,11 +7,12 @@
 
 use crate::core::{
     ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
-    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus,
+    ToleranceConfig, KV,
 };
 use argmin_math::{
-    ArgminAdd, ArgminDot, ArgminL1Norm, ArgminL2Norm, ArgminMinMax, ArgminMul, ArgminSignum,
-    ArgminSub, ArgminZeroLike,
+    ArgminAdd, ArgminDot, ArgminL1Norm, ArgminL2Norm, ArgminMinMax, ArgminMul, ArgminMulAssign,
+    ArgminScaledAddAssign, ArgminSignum, ArgminSub, ArgminZeroLike,
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -58,9 +59,16 @@ where
 /// OWL-QN is a method that adapts L-BFGS to L1-regularization. The original L-BFGS requires a
 /// loss function to be differentiable and does not support L1-regularization. Therefore,
 /// this library switches to OWL-QN when L1-regularization is specified. L1-regularization can be
-/// performed via [`with_l1_regularization`](`LBFGS::with_l1_regularization`).
+/// performed via [`with_l1_regularization`](`LBFGS::with_l1_regularization`). OWL-QN is only
+/// meaningful for real-valued parameter vectors.
 ///
-/// TODO: Implement compact representation of BFGS updating (Nocedal/Wright p.230)
+/// The inverse Hessian approximation is applied via the two-loop recursion. Its compact
+/// representation (Nocedal/Wright p.230), which also provides products with the Hessian
+/// approximation itself, is available as [`CompactLBFGS`](`super::CompactLBFGS`).
+///
+/// All convergence tolerances, including a tolerance on the change of the parameter vector
+/// (disabled by default) and relative instead of absolute tolerances, can be set via
+/// [`with_tolerances`](`LBFGS::with_tolerances`) (see [`ToleranceConfig`]).
 ///
 /// ## Requirements on the optimization problem
 ///
@@ -84,14 +92,14 @@ pub struct LBFGS<L, P, G, F> {
     s: VecDeque<P>,
     /// y_{k-1}
     y: VecDeque<G>,
-    /// Tolerance for the stopping criterion based on the change of the norm on the gradient
-    tol_grad: F,
-    /// Tolerance for the stopping criterion based on the change of the cost stopping criterion
-    tol_cost: F,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
     /// Coefficient of L1-regularization
     l1_coeff: Option<F>,
     /// Unregularized gradient used for calculation of `y`.
     l1_prev_unreg_grad: Option<G>,
+    /// Diagonal of the initial inverse Hessian approximation
+    inv_hessian_diag: Option<P>,
 }
 
 impl<L, P, G, F> LBFGS<L, P, G, F>
@@ -113,10 +121,10 @@ where
             m,
             s: VecDeque::with_capacity(m),
             y: VecDeque::with_capacity(m),
-            tol_grad: F::epsilon().sqrt(),
-            tol_cost: F::epsilon(),
+            tol: ToleranceConfig::new(),
             l1_coeff: None,
             l1_prev_unreg_grad: None,
+            inv_hessian_diag: None,
         }
     }
 
@@ -142,7 +150,7 @@ where
                 "`L-BFGS`: gradient tolerance must be >= 0."
             ));
         }
-        self.tol_grad = tol_grad;
+        self.tol = self.tol.with_grad(tol_grad)?;
         Ok(self)
     }
 
@@ -168,10 +176,31 @@ where
                 "`L-BFGS`: cost tolerance must be >= 0."
             ));
         }
-        self.tol_cost = tol_cost;
+        self.tol = self.tol.with_cost(tol_cost)?;
         Ok(self)
     }
 
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LBFGS;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(linesearch, 3).with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+
     /// Activates L1-regularization with coefficient `l1_coeff`.
     ///
     /// Parameter `l1_coeff` must be `> 0.0`.
@@ -196,6 +225,31 @@ where
         self.l1_coeff = Some(l1_coeff);
         Ok(self)
     }
+
+    /// Sets the diagonal of the initial inverse Hessian approximation.
+    ///
+    /// By default, the two-loop recursion starts from the identity scaled by
+    /// `gamma = s^T y / y^T y` of the most recent curvature pair. If a diagonal is provided, it
+    /// is used instead in every iteration. It is typically obtained from the inverse of the
+    /// diagonal of an approximate Hessian at the initial parameter vector, which is supplied by
+    /// the user or computed via finite differences (see
+    /// [`WithHessianFromGradient`](`crate::core::WithHessianFromGradient`)). All elements must be
+    /// positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LBFGS;
+    /// # let linesearch = ();
+    /// // Diagonal of the Hessian: [2.0, 100.0]
+    /// let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+    ///     LBFGS::new(linesearch, 3).with_inv_hessian_diag(vec![0.5, 0.01]);
+    /// ```
+    #[must_use]
+    pub fn with_inv_hessian_diag(mut self, inv_hessian_diag: P) -> Self {
+        self.inv_hessian_diag = Some(inv_hessian_diag);
+        self
+    }
 }
 
 /// Wrapper problem for supporting constrained line search.
@@ -311,12 +365,16 @@ where
         + ArgminAdd<F, P>
         + ArgminDot<G, F>
         + ArgminMul<F, P>
+        + ArgminMulAssign<F>
+        + ArgminScaledAddAssign<P, F>
         + ArgminMul<P, P>
         + ArgminMul<G, P>
         + ArgminL1Norm<F>
+        + ArgminL2Norm<F>
         + ArgminSignum
         + ArgminZeroLike
-        + ArgminMinMax,
+        + ArgminMinMax
+        + 'static,
     G: Clone
         + ArgminL2Norm<F>
         + ArgminSub<G, G>
@@ -324,8 +382,8 @@ where
         + ArgminAdd<P, G>
         + ArgminDot<G, F>
         + ArgminDot<P, F>
-        + ArgminMul<F, G>
         + ArgminMul<F, P>
+        + ArgminScaledAddAssign<G, F>
         + ArgminZeroLike
         + ArgminMinMax,
     L: Clone
@@ -409,15 +467,18 @@ where
             let rho_t = float!(1.0) / yksk;
             let skq: F = sk.dot(&q);
             let alpha_t = skq.mul(rho_t);
-            q = q.sub(&yk.mul(&alpha_t));
+            q.scaled_add_assign(&(-alpha_t), yk);
             rho[cur_m - i - 1] = rho_t;
             alpha[cur_m - i - 1] = alpha_t;
         }
-        let mut r: P = q.mul(&gamma);
+        let mut r: P = match self.inv_hessian_diag.as_ref() {
+            Some(inv_hessian_diag) => inv_hessian_diag.mul(&q),
+            None => q.mul(&gamma),
+        };
         for (i, (sk, yk)) in self.s.iter().zip(self.y.iter()).enumerate() {
             let beta: F = yk.dot(&r);
             let beta = beta.mul(rho[i]);
-            r = r.add(&sk.mul(&(alpha[i] - beta)));
+            r.scaled_add_assign(&(alpha[i] - beta), sk);
         }
 
         let mut line_problem = LineSearchProblem::new(problem.take_problem().unwrap());
@@ -431,21 +492,31 @@ where
             .mul(&r)
             .mul(&float!(-1.0))
         } else {
-            r.mul(&float!(-1.0))
+            r.mul_assign(&float!(-1.0));
+            r
         };
 
         self.linesearch.search_direction(d);
+        if let Some(max_step_length) = problem.max_step_length(&param) {
+            self.linesearch.max_step_length(max_step_length)?;
+        }
+        if let Some(move_limits) = problem.move_limits::<P>() {
+            self.linesearch.move_limits(move_limits);
+        }
 
         // Run line search
-        let linesearch_result = Executor::new(line_problem, self.linesearch.clone())
-            .configure(|config| {
-                config
-                    .param(param.clone())
-                    .gradient(prev_grad.clone())
-                    .cost(cur_cost)
-            })
-            .ctrlc(false)
-            .run();
+        let linesearch_result = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(line_problem, self.linesearch.clone())
+                .configure(|config| {
+                    config
+                        .param(param.clone())
+                        .gradient(prev_grad.clone())
+                        .cost(cur_cost)
+                })
+                .ctrlc(false)
+                .run()
+        };
 
         let OptimizationResult {
             problem: mut line_problem,
@@ -466,23 +537,34 @@ where
 
         let mut xk1 = linesearch_state.take_param().unwrap();
         let next_cost = linesearch_state.get_cost();
+        let next_grad = linesearch_state.take_gradient();
 
         // take back problem and take care of function evaluation counts
         let mut internal_line_problem = line_problem.take_problem().unwrap();
         let xi = internal_line_problem.xi.take();
         problem.problem = Some(internal_line_problem.problem);
         problem.consume_func_counts(line_problem);
-        if let Some(xi) = xi {
-            let zeros = xk1.zero_like();
-            xk1 = P::max(&xk1.mul(&xi).signum(), &zeros).mul(&xk1);
-        }
+        // With L1 regularization, the line search evaluates the pseudo-gradient, and `xk1` is
+        // projected on the orthant afterwards. Otherwise, the gradient of the line search at `xk1`
+        // is reused.
+        let next_grad = match xi {
+            Some(xi) => {
+                let zeros = xk1.zero_like();
+                xk1 = P::max(&xk1.mul(&xi).signum(), &zeros).mul(&xk1);
+                None
+            }
+            None if self.l1_coeff.is_none() => next_grad,
+            None => None,
+        };
 
         if state.get_iter() >= self.m as u64 {
             self.s.pop_front();
             self.y.pop_front();
         }
 
-        let grad = problem.gradient(&xk1)?;
+        let grad = next_grad
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&xk1))?;
 
         self.s.push_back(xk1.sub(&param));
         let grad = if let Some(l1_coeff) = self.l1_coeff {
@@ -503,11 +585,76 @@ where
         ))
     }
 
+    /// Takes over the most recent `m` curvature pairs of `previous` in addition to the
+    /// information held by the state.
+    fn warm_start(
+        &mut self,
+        previous: Self,
+        previous_state: IterState<P, G, (), (), (), F>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<IterState<P, G, (), (), (), F>, Error> {
+        let skip = previous.s.len().saturating_sub(self.m);
+        self.s = previous.s.into_iter().skip(skip).collect();
+        self.y = previous.y.into_iter().skip(skip).collect();
+        Ok(state.warm_start(previous_state))
+    }
+
+    /// Discards the stored curvature pairs in addition to the default behavior.
+    fn problem_changed(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<IterState<P, G, (), (), (), F>, Error> {
+        self.s.clear();
+        self.y.clear();
+        let (state, _) = self.init(problem, state.problem_changed())?;
+        Ok(state)
+    }
+
+    /// Discards the stored curvature pairs in addition to the default behavior.
+    fn restart(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<IterState<P, G, (), (), (), F>, Error> {
+        self.s.clear();
+        self.y.clear();
+        let (state, _) = self.init(problem, state.restart())?;
+        Ok(state)
+    }
+
+    /// Exposes the number of stored curvature pairs `num_pairs` and the memory size `m`.
+    fn internals(&self) -> KV {
+        kv!(
+            "num_pairs" => self.s.len() as u64;
+            "m" => self.m as u64;
+        )
+    }
+
+    fn termination_diagnostics(&self, state: &IterState<P, G, (), (), (), F>) -> KV {
+        self.tol.diagnostics(
+            state.get_gradient().map(|grad| grad.l2_norm()),
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
+    }
+
     fn terminate(&mut self, state: &IterState<P, G, (), (), (), F>) -> TerminationStatus {
-        if state.get_gradient().unwrap().l2_norm() < self.tol_grad {
+        if self
+            .tol
+            .grad_converged(state.get_gradient().unwrap().l2_norm(), state.get_cost())
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
-        if (state.get_prev_cost() - state.get_cost()).abs() < self.tol_cost {
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         TerminationStatus::NotTerminated
@@ -522,6 +669,7 @@ mod tests {
         ArgminError,
     };
     use crate::solver::linesearch::MoreThuenteLineSearch;
+    use approx::assert_relative_eq;
 
     test_trait_impl!(
         lbfgs,
@@ -536,23 +684,24 @@ mod tests {
         let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(MyFakeLineSearch {}, 3);
         let LBFGS {
             linesearch,
-            tol_grad,
-            tol_cost,
+            tol,
             m,
             s,
             y,
             l1_coeff,
             l1_prev_unreg_grad,
+            inv_hessian_diag,
         } = lbfgs;
 
         assert_eq!(linesearch, MyFakeLineSearch {});
-        assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
-        assert_eq!(tol_cost.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert_eq!(tol.grad().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(tol.cost().to_ne_bytes(), f64::EPSILON.to_ne_bytes());
         assert_eq!(m, 3);
         assert!(s.capacity() >= 3);
         assert!(y.capacity() >= 3);
         assert!(l1_coeff.is_none());
         assert!(l1_prev_unreg_grad.is_none());
+        assert!(inv_hessian_diag.is_none());
     }
 
     #[test]
@@ -567,7 +716,7 @@ mod tests {
             assert!(res.is_ok());
 
             let nm = res.unwrap();
-            assert_eq!(nm.tol_grad.to_ne_bytes(), tol.to_ne_bytes());
+            assert_eq!(nm.tol.grad().to_ne_bytes(), tol.to_ne_bytes());
         }
 
         // incorrect parameters
@@ -594,7 +743,7 @@ mod tests {
             assert!(res.is_ok());
 
             let nm = res.unwrap();
-            assert_eq!(nm.tol_cost.to_ne_bytes(), tol.to_ne_bytes());
+            assert_eq!(nm.tol.cost().to_ne_bytes(), tol.to_ne_bytes());
         }
 
         // incorrect parameters
@@ -653,6 +802,44 @@ mod tests {
         assert_eq!(state_out.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes())
     }
 
+    #[test]
+    fn test_warm_start() {
+        let linesearch = MoreThuenteLineSearch::new();
+        let mut previous: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(linesearch.clone(), 5);
+        for i in 0..5 {
+            previous.s.push_back(vec![i as f64]);
+            previous.y.push_back(vec![-(i as f64)]);
+        }
+        let mut lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(linesearch, 3);
+        let state = <LBFGS<_, _, _, f64> as Solver<TestProblem, _>>::warm_start(
+            &mut lbfgs,
+            previous,
+            IterState::new().param(vec![1.0]).gradient(vec![2.0]),
+            IterState::new(),
+        )
+        .unwrap();
+
+        // Only the most recent pairs are taken over
+        assert_eq!(lbfgs.s, vec![vec![2.0], vec![3.0], vec![4.0]]);
+        assert_eq!(lbfgs.y, vec![vec![-2.0], vec![-3.0], vec![-4.0]]);
+        assert_eq!(state.get_param(), Some(&vec![1.0]));
+        assert!(state.get_gradient().is_none());
+    }
+
+    #[test]
+    fn test_internals() {
+        let mut lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+            LBFGS::new(MoreThuenteLineSearch::new(), 3);
+        lbfgs.s.push_back(vec![1.0]);
+        lbfgs.y.push_back(vec![2.0]);
+        let kv = <LBFGS<_, _, _, f64> as Solver<
+            TestProblem,
+            IterState<Vec<f64>, Vec<f64>, (), (), (), f64>,
+        >>::internals(&lbfgs);
+        assert_eq!(kv.get("num_pairs").unwrap().get_uint(), Some(1));
+        assert_eq!(kv.get("m").unwrap().get_uint(), Some(3));
+    }
+
     #[test]
     fn test_init_provided_cost() {
         let linesearch = MoreThuenteLineSearch::new().with_c(1e-4, 0.9).unwrap();
@@ -742,4 +929,135 @@ mod tests {
             assert!((result_param[3]).abs() < 1e-6);
         }
     }
+
+    #[test]
+    fn test_inv_hessian_diag() {
+        // f(x) = sum_i c_i (x_i - 1)^2
+        struct Diagonal {
+            c: Vec<f64>,
+        }
+
+        impl CostFunction for Diagonal {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p.iter()
+                    .zip(self.c.iter())
+                    .map(|(x, c)| c * (x - 1.0).powi(2))
+                    .sum())
+            }
+        }
+
+        impl Gradient for Diagonal {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(p.iter()
+                    .zip(self.c.iter())
+                    .map(|(x, c)| 2.0 * c * (x - 1.0))
+                    .collect())
+            }
+        }
+
+        let c = vec![1.0, 1e2, 1e4];
+        let inv_hessian_diag = c.iter().map(|c| 1.0 / (2.0 * c)).collect();
+
+        let linesearch = MoreThuenteLineSearch::new();
+        let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+            LBFGS::new(linesearch, 3).with_inv_hessian_diag(inv_hessian_diag);
+
+        // With the exact inverse Hessian, the first step is a Newton step
+        let res = Executor::new(Diagonal { c }, lbfgs)
+            .configure(|state| state.param(vec![0.0; 3]).max_iters(1))
+            .run()
+            .unwrap();
+        for p in res.state.param.unwrap() {
+            assert_relative_eq!(p, 1.0, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_f32() {
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f32>;
+            type Output = f32;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f32>;
+            type Gradient = Vec<f32>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(argmin_testfunctions::rosenbrock_derivative(p))
+            }
+        }
+
+        let linesearch: MoreThuenteLineSearch<Vec<f32>, Vec<f32>, f32> =
+            MoreThuenteLineSearch::new();
+        let lbfgs: LBFGS<_, Vec<f32>, Vec<f32>, f32> =
+            LBFGS::new(linesearch, 7).with_tolerance_grad(1e-4).unwrap();
+        let res = Executor::new(Rosenbrock {}, lbfgs)
+            .configure(|state| state.param(vec![-1.2f32, 1.0]).max_iters(100))
+            .run()
+            .unwrap();
+
+        let best_param = res.state.get_best_param().unwrap();
+        assert!((best_param[0] - 1.0).abs() < 1e-2);
+        assert!((best_param[1] - 1.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_complex() {
+        use num_complex::Complex;
+
+        // f(z) = sum_i |z_i - c_i|^2
+        struct Distance {
+            c: Vec<Complex<f64>>,
+        }
+
+        impl CostFunction for Distance {
+            type Param = Vec<Complex<f64>>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p.iter()
+                    .zip(self.c.iter())
+                    .map(|(z, c)| (z - c).norm_sqr())
+                    .sum())
+            }
+        }
+
+        impl Gradient for Distance {
+            type Param = Vec<Complex<f64>>;
+            type Gradient = Vec<Complex<f64>>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(p.iter()
+                    .zip(self.c.iter())
+                    .map(|(z, c)| 2.0 * (z - c))
+                    .collect())
+            }
+        }
+
+        let c = vec![Complex::new(1.0, -2.0), Complex::new(-0.5, 3.0)];
+        let linesearch = MoreThuenteLineSearch::new();
+        let lbfgs: LBFGS<_, Vec<Complex<f64>>, Vec<Complex<f64>>, f64> = LBFGS::new(linesearch, 5);
+        let res = Executor::new(Distance { c: c.clone() }, lbfgs)
+            .configure(|state| state.param(vec![Complex::new(0.0, 0.0); 2]).max_iters(20))
+            .run()
+            .unwrap();
+
+        let best_param = res.state.get_best_param().unwrap();
+        for (z, c) in best_param.iter().zip(c.iter()) {
+            assert!((z - c).norm() < 1e-6);
+        }
+    }
 }
@@ -8,24 +8,46 @@
 //! # Quasi-Newton methods
 //!
 //! * [`BFGS`]
+//! * [`BroydenClass`]
 //! * [`DFP`]
+//! * [`DirectBFGS`]
 //! * [`LBFGS`]
 //! * [`SR1`]
 //! * [`SR1TrustRegion`]
 //!
+//! By default, [`BFGS`], [`DFP`] and [`SR1`] start from the initial inverse Hessian provided via
+//! the state, typically the identity, and [`LBFGS`] starts from a scaled identity. On
+//! ill-conditioned problems, starting from an approximate (inverse) Hessian at the initial
+//! parameter vector often considerably reduces the number of iterations. It can be computed once
+//! from a user-supplied or finite-difference Hessian via [`initial_inv_hessian`], or be provided
+//! as a diagonal via [`LBFGS::with_inv_hessian_diag`].
+//!
+//! [`CompactLBFGS`] and [`CompactLSR1`] provide the compact representations of limited-memory
+//! BFGS and SR1 matrices, including products with the approximate Hessian and its inverse as well
+//! as its extreme eigenvalues, as building blocks for limited-memory trust-region and
+//! bound-constrained methods.
+//!
 //! ## Reference
 //!
 //! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
 
 mod bfgs;
+mod broyden_class;
+mod compact;
 mod dfp;
+mod direct_bfgs;
+mod initialization;
 mod lbfgs;
 mod sr1;
 mod sr1_trustregion;
 
 pub use self::bfgs::BFGS;
+pub use self::broyden_class::BroydenClass;
+pub use self::compact::{CompactLBFGS, CompactLSR1};
 pub use self::dfp::DFP;
+pub use self::direct_bfgs::DirectBFGS;
+pub use self::initialization::initial_inv_hessian;
 pub use self::lbfgs::LBFGS;
 pub use self::sr1::SR1;
 pub use self::sr1_trustregion::SR1TrustRegion;
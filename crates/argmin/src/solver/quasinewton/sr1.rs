@@ -7,9 +7,10 @@
 
 use crate::core::{
     ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
-    OptimizationResult, Problem, Solver, TerminationReason, TerminationStatus, KV,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus,
+    ToleranceConfig, KV,
 };
-use argmin_math::{ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminSub};
+use argmin_math::{ArgminDot, ArgminL2Norm, ArgminMulAssign, ArgminScaledAddAssign, ArgminSub};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +18,10 @@ use serde::{Deserialize, Serialize};
 ///
 /// This method currently has problems: <https://github.com/argmin-rs/argmin/issues/221>.
 ///
+/// All convergence tolerances, including a tolerance on the change of the parameter vector
+/// (disabled by default) and relative instead of absolute tolerances, can be set via
+/// [`with_tolerances`](`SR1::with_tolerances`) (see [`ToleranceConfig`]).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
@@ -32,10 +37,11 @@ pub struct SR1<L, F> {
     denominator_factor: F,
     /// line search
     linesearch: L,
-    /// Tolerance for the stopping criterion based on the change of the norm on the gradient
-    tol_grad: F,
-    /// Tolerance for the stopping criterion based on the change of the cost stopping criterion
-    tol_cost: F,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
+    /// Number of iterations in which the update of the inverse Hessian was skipped
+    #[cfg_attr(feature = "serde1", serde(default))]
+    skipped_updates: u64,
 }
 
 impl<L, F> SR1<L, F>
@@ -55,8 +61,8 @@ where
         SR1 {
             denominator_factor: float!(1e-8),
             linesearch,
-            tol_grad: F::epsilon().sqrt(),
-            tol_cost: F::epsilon(),
+            tol: ToleranceConfig::new(),
+            skipped_updates: 0,
         }
     }
 
@@ -113,7 +119,7 @@ where
                 "`SR1`: gradient tolerance must be >= 0."
             ));
         }
-        self.tol_grad = tol_grad;
+        self.tol = self.tol.with_grad(tol_grad)?;
         Ok(self)
     }
 
@@ -139,9 +145,30 @@ where
                 "`SR1`: cost tolerance must be >= 0."
             ));
         }
-        self.tol_cost = tol_cost;
+        self.tol = self.tol.with_cost(tol_cost)?;
         Ok(self)
     }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::SR1;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let sr1: SR1<_, f64> = SR1::new(linesearch).with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
 }
 
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for SR1<L, F>
@@ -153,9 +180,10 @@ where
         + ArgminDot<P, F>
         + ArgminDot<P, H>
         + ArgminL2Norm<F>
-        + ArgminMul<F, P>,
+        + ArgminMulAssign<F>
+        + 'static,
     G: Clone + ArgminSub<P, P> + ArgminL2Norm<F> + ArgminSub<G, G>,
-    H: ArgminDot<G, P> + ArgminDot<P, P> + ArgminAdd<H, H> + ArgminMul<F, H>,
+    H: ArgminDot<G, P> + ArgminDot<P, P> + ArgminScaledAddAssign<H, F>,
     L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
     F: ArgminFloat,
 {
@@ -226,24 +254,34 @@ where
             "`SR1`: Inverse Hessian in state not set."
         ))?;
 
-        let p = inv_hessian.dot(&prev_grad).mul(&float!(-1.0));
+        let mut p: P = inv_hessian.dot(&prev_grad);
+        p.mul_assign(&float!(-1.0));
 
         self.linesearch.search_direction(p);
+        if let Some(max_step_length) = problem.max_step_length(&param) {
+            self.linesearch.max_step_length(max_step_length)?;
+        }
+        if let Some(move_limits) = problem.move_limits::<P>() {
+            self.linesearch.move_limits(move_limits);
+        }
 
         // Run solver
         let OptimizationResult {
             problem: line_problem,
             state: mut linesearch_state,
             ..
-        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
-            .configure(|config| {
-                config
-                    .param(param.clone())
-                    .gradient(prev_grad.clone())
-                    .cost(cost)
-            })
-            .ctrlc(false)
-            .run()?;
+        } = {
+            enter_span!(DEBUG, "line_search");
+            Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+                .configure(|config| {
+                    config
+                        .param(param.clone())
+                        .gradient(prev_grad.clone())
+                        .cost(cost)
+                })
+                .ctrlc(false)
+                .run()?
+        };
 
         let xk1 = linesearch_state.take_param().unwrap();
         let next_cost = linesearch_state.get_cost();
@@ -251,7 +289,11 @@ where
         // take care of function eval counts
         problem.consume_problem(line_problem);
 
-        let grad = problem.gradient(&xk1)?;
+        // Reuse the gradient at `xk1` if the line search computed it
+        let grad = linesearch_state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&xk1))?;
         let yk = grad.sub(&prev_grad);
 
         let sk = xk1.sub(&param);
@@ -267,7 +309,9 @@ where
         let hessian_update = b.abs() >= self.denominator_factor * sk.l2_norm() * ykmbksk.l2_norm();
 
         if hessian_update {
-            inv_hessian = inv_hessian.add(&a.mul(&(float!(1.0) / b)));
+            inv_hessian.scaled_add_assign(&(float!(1.0) / b), &a);
+        } else {
+            self.skipped_updates += 1;
         }
 
         Ok((
@@ -280,11 +324,36 @@ where
         ))
     }
 
+    /// Exposes the number of iterations `skipped_updates` in which the update of the inverse
+    /// Hessian was skipped.
+    fn internals(&self) -> KV {
+        kv!("skipped_updates" => self.skipped_updates;)
+    }
+
+    fn termination_diagnostics(&self, state: &IterState<P, G, (), H, (), F>) -> KV {
+        self.tol.diagnostics(
+            state.get_gradient().map(|grad| grad.l2_norm()),
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
+    }
+
     fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
-        if state.get_gradient().unwrap().l2_norm() < self.tol_grad {
+        if self
+            .tol
+            .grad_converged(state.get_gradient().unwrap().l2_norm(), state.get_cost())
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
-        if (state.get_prev_cost() - state.cost).abs() < self.tol_cost {
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         TerminationStatus::NotTerminated
@@ -311,14 +380,15 @@ mod tests {
         let SR1 {
             denominator_factor,
             linesearch,
-            tol_grad,
-            tol_cost,
+            tol,
+            skipped_updates,
         } = sr1;
 
         assert_eq!(linesearch, MyFakeLineSearch {});
-        assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
-        assert_eq!(tol_cost.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert_eq!(tol.grad().to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(tol.cost().to_ne_bytes(), f64::EPSILON.to_ne_bytes());
         assert_eq!(denominator_factor.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+        assert_eq!(skipped_updates, 0);
     }
 
     #[test]
@@ -360,7 +430,7 @@ mod tests {
             assert!(res.is_ok());
 
             let nm = res.unwrap();
-            assert_eq!(nm.tol_grad.to_ne_bytes(), tol.to_ne_bytes());
+            assert_eq!(nm.tol.grad().to_ne_bytes(), tol.to_ne_bytes());
         }
 
         // incorrect parameters
@@ -387,7 +457,7 @@ mod tests {
             assert!(res.is_ok());
 
             let nm = res.unwrap();
-            assert_eq!(nm.tol_cost.to_ne_bytes(), tol.to_ne_bytes());
+            assert_eq!(nm.tol.cost().to_ne_bytes(), tol.to_ne_bytes());
         }
 
         // incorrect parameters
@@ -521,4 +591,65 @@ mod tests {
             assert_eq!(s.to_ne_bytes(), g.to_ne_bytes());
         }
     }
+
+    #[test]
+    fn test_func_counts_include_line_search() {
+        use crate::core::{CostFunction, Gradient};
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        /// Counts the actual evaluations independently of `Problem`
+        #[derive(Default)]
+        struct Quadratic {
+            cost_evals: AtomicU64,
+            gradient_evals: AtomicU64,
+        }
+
+        impl CostFunction for Quadratic {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                self.cost_evals.fetch_add(1, Ordering::Relaxed);
+                Ok((p[0] - 1.0).powi(2) + 10.0 * (p[1] + 2.0).powi(2))
+            }
+        }
+
+        impl Gradient for Quadratic {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                self.gradient_evals.fetch_add(1, Ordering::Relaxed);
+                Ok(vec![2.0 * (p[0] - 1.0), 20.0 * (p[1] + 2.0)])
+            }
+        }
+
+        let linesearch = MoreThuenteLineSearch::new();
+        let sr1: SR1<_, f64> = SR1::new(linesearch);
+        let res = Executor::new(Quadratic::default(), sr1)
+            .configure(|state| {
+                state
+                    .param(vec![-1.2, 1.0])
+                    .inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+                    .max_iters(3)
+                    .counting(true)
+            })
+            .run()
+            .unwrap();
+
+        // Evaluations within the nested line search runs are part of the totals
+        let problem = res.problem.problem.as_ref().unwrap();
+        let cost_evals = problem.cost_evals.load(Ordering::Relaxed);
+        let gradient_evals = problem.gradient_evals.load(Ordering::Relaxed);
+        assert!(cost_evals > 3);
+        // The gradient computed by the line search at the accepted point is reused
+        assert_eq!(cost_evals, gradient_evals);
+        assert_eq!(res.problem.counts["cost_count"], cost_evals);
+        assert_eq!(res.problem.counts["gradient_count"], gradient_evals);
+        assert_eq!(res.state.get_func_counts()["cost_count"], cost_evals);
+        assert_eq!(
+            res.state.get_func_counts()["gradient_count"],
+            gradient_evals
+        );
+    }
 }
@@ -7,10 +7,13 @@
 
 use crate::core::{
     ArgminFloat, CostFunction, Error, Executor, Gradient, Hessian, IterState, OptimizationResult,
-    Problem, Solver, TerminationReason, TerminationStatus, TrustRegionRadius, KV,
+    Problem, Solver, State, TerminationReason, TerminationStatus, ToleranceConfig,
+    TrustRegionRadius, KV,
 };
+use crate::solver::trustregion::limit_step;
 use argmin_math::{
-    ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminSub, ArgminWeightedDot, ArgminZeroLike,
+    ArgminAdd, ArgminDiv, ArgminDot, ArgminL2Norm, ArgminLInfNorm, ArgminMul, ArgminSub,
+    ArgminWeightedDot, ArgminZeroLike,
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -22,6 +25,10 @@ use serde::{Deserialize, Serialize};
 /// Hessian are optional and will be computed if not provided.
 /// Requires a [trust region sub problem](`crate::solver::trustregion`).
 ///
+/// All convergence tolerances, including a tolerance on the change of the parameter vector
+/// (disabled by default) and relative instead of absolute tolerances, can be set via
+/// [`with_tolerances`](`SR1TrustRegion::with_tolerances`) (see [`ToleranceConfig`]).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`], [`Gradient`] and
@@ -42,8 +49,11 @@ pub struct SR1TrustRegion<R, F> {
     radius: F,
     /// eta \in (0, 10^-3)
     eta: F,
-    /// Tolerance for the stopping criterion based on the change of the norm on the gradient
-    tol_grad: F,
+    /// Convergence tolerances
+    tol: ToleranceConfig<F>,
+    /// Number of iterations in which the update of the Hessian was skipped
+    #[cfg_attr(feature = "serde1", serde(default))]
+    skipped_updates: u64,
 }
 
 impl<R, F> SR1TrustRegion<R, F>
@@ -70,7 +80,8 @@ where
             subproblem,
             radius: float!(1.0),
             eta: float!(0.5 * 1e-3),
-            tol_grad: float!(1e-3),
+            tol: ToleranceConfig::with_defaults(float!(1e-3), float!(0.0)),
+            skipped_updates: 0,
         }
     }
 
@@ -174,9 +185,30 @@ where
                 "`SR1TrustRegion`: gradient tolerance must be >= 0."
             ));
         }
-        self.tol_grad = tol_grad;
+        self.tol = self.tol.with_grad(tol_grad)?;
         Ok(self)
     }
+
+    /// Sets all convergence tolerances at once, including a tolerance on the change of the
+    /// parameter vector and whether the tolerances are relative (see [`ToleranceConfig`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::SR1TrustRegion;
+    /// # use argmin::core::{Error, ToleranceConfig};
+    /// # fn main() -> Result<(), Error> {
+    /// # let subproblem = ();
+    /// let tol = ToleranceConfig::new().with_param(1e-8)?.relative(true);
+    /// let sr1: SR1TrustRegion<_, f64> = SR1TrustRegion::new(subproblem).with_tolerances(tol);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerances(mut self, tol: ToleranceConfig<F>) -> Self {
+        self.tol = tol;
+        self
+    }
 }
 
 impl<O, R, P, G, B, F> Solver<O, IterState<P, G, (), B, (), F>> for SR1TrustRegion<R, F>
@@ -190,7 +222,11 @@ where
         + ArgminDot<P, F>
         + ArgminDot<P, B>
         + ArgminL2Norm<F>
-        + ArgminZeroLike,
+        + ArgminZeroLike
+        + ArgminMul<F, P>
+        + ArgminDiv<P, P>
+        + ArgminLInfNorm<F>
+        + 'static,
     G: Clone + ArgminL2Norm<F> + ArgminDot<P, F> + ArgminSub<G, P>,
     B: Clone + ArgminDot<P, P> + ArgminAdd<B, B> + ArgminMul<F, B>,
     R: Clone + TrustRegionRadius<F> + Solver<O, IterState<P, G, (), B, (), F>>,
@@ -262,6 +298,10 @@ where
             "`SR1TrustRegion`: Hessian in state not set."
         ))?;
 
+        // The radius must not exceed the maximum step length
+        if let Some(max_step_length) = problem.max_step_length(&xk) {
+            self.radius = self.radius.min(max_step_length);
+        }
         self.subproblem.set_radius(self.radius);
 
         let OptimizationResult {
@@ -284,6 +324,9 @@ where
             "`SR1TrustRegion`: No parameters returned by line search."
         ))?;
 
+        // No component of the step may exceed its move limit
+        let sk = limit_step(sk, problem.move_limits::<P>());
+
         problem.consume_problem(sub_problem);
 
         let xksk = xk.add(&sk);
@@ -327,6 +370,7 @@ where
             let b: F = sk.dot(&ykbksk);
             hessian.add(&a.mul(&(float!(1.0) / b)))
         } else {
+            self.skipped_updates += 1;
             hessian
         };
 
@@ -340,8 +384,51 @@ where
         ))
     }
 
+    /// Takes over the radius of `previous` in addition to the information held by the state,
+    /// which includes the approximation of the Hessian.
+    fn warm_start(
+        &mut self,
+        previous: Self,
+        previous_state: IterState<P, G, (), B, (), F>,
+        state: IterState<P, G, (), B, (), F>,
+    ) -> Result<IterState<P, G, (), B, (), F>, Error> {
+        self.radius = previous.radius;
+        Ok(state.warm_start(previous_state))
+    }
+
+    /// Exposes the current `radius` and the number of iterations `skipped_updates` in which the
+    /// update of the Hessian was skipped.
+    fn internals(&self) -> KV {
+        kv!(
+            "radius" => self.radius;
+            "skipped_updates" => self.skipped_updates;
+        )
+    }
+
+    fn termination_diagnostics(&self, state: &IterState<P, G, (), B, (), F>) -> KV {
+        self.tol.diagnostics(
+            state.get_gradient().map(|grad| grad.l2_norm()),
+            state.get_prev_cost(),
+            state.get_cost(),
+            state.get_prev_param(),
+            state.get_param(),
+        )
+    }
+
     fn terminate(&mut self, state: &IterState<P, G, (), B, (), F>) -> TerminationStatus {
-        if state.get_gradient().unwrap().l2_norm() < self.tol_grad {
+        if self
+            .tol
+            .grad_converged(state.get_gradient().unwrap().l2_norm(), state.get_cost())
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        if self
+            .tol
+            .cost_converged(state.get_prev_cost(), state.get_cost())
+            || self
+                .tol
+                .param_converged(state.get_prev_param(), state.get_param())
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         TerminationStatus::NotTerminated
@@ -367,14 +454,16 @@ mod tests {
             subproblem,
             radius,
             eta,
-            tol_grad,
+            tol,
+            skipped_updates,
         } = sr1;
 
         assert_eq!(denominator_factor.to_ne_bytes(), 1e-8f64.to_ne_bytes());
         assert_eq!(subproblem, MyFakeSubProblem {});
+        assert_eq!(skipped_updates, 0);
         assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
         assert_eq!(eta.to_ne_bytes(), (0.5f64 * 1e-3f64).to_ne_bytes());
-        assert_eq!(tol_grad.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+        assert_eq!(tol.grad().to_ne_bytes(), 1e-3f64.to_ne_bytes());
     }
 
     #[test]
@@ -416,7 +505,7 @@ mod tests {
             assert!(res.is_ok());
 
             let nm = res.unwrap();
-            assert_eq!(nm.tol_grad.to_ne_bytes(), tol.to_ne_bytes());
+            assert_eq!(nm.tol.grad().to_ne_bytes(), tol.to_ne_bytes());
         }
 
         // incorrect parameters
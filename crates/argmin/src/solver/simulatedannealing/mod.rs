@@ -19,7 +19,7 @@
 //! DOI: 10.1126/science.220.4598.671
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, TerminationReason,
+    ArgminFloat, CostFunction, DerivativeFreeState, Error, Problem, Solver, TerminationReason,
     TerminationStatus, KV,
 };
 use rand::prelude::*;
@@ -131,6 +131,13 @@ pub enum SATempFunc<F> {
 /// modified. Please see the Simulated Annealing example for one approach to do so for floating
 /// point parameters.
 ///
+/// With the `serde1` feature, checkpoints contain the RNG used for accepting solutions as well
+/// as the current temperature and the counters of the cooling and reannealing schedules,
+/// therefore a resumed run continues exactly as an uninterrupted one. Random numbers drawn in
+/// [`Anneal::anneal`] are not covered, since the problem is not part of checkpoints; for fully
+/// reproducible runs, they need to be derived deterministically (for instance from a seed and
+/// the parameter vector).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`].
@@ -251,6 +258,31 @@ where
         }
     }
 
+    /// Reseeds the random number generator, which makes runs reproducible.
+    ///
+    /// Only available for RNGs which implement `rand::SeedableRng`, such as the default
+    /// `Xoshiro256PlusPlus`. Note that the random numbers used for annealing the parameter vector
+    /// are drawn by the problem (see [`Anneal`]) and need to be seeded there.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::SimulatedAnnealing;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let sa = SimulatedAnnealing::new(100.0f64)?.with_seed(1729);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self
+    where
+        R: SeedableRng,
+    {
+        self.rng = R::seed_from_u64(seed);
+        self
+    }
+
     /// Set temperature function
     ///
     /// The temperature function defines how the temperature is decreased over the course of the
@@ -435,21 +467,25 @@ where
     }
 }
 
-impl<O, P, F, R> Solver<O, IterState<P, (), (), (), (), F>> for SimulatedAnnealing<F, R>
+impl<O, P, F, R> Solver<O, DerivativeFreeState<P, F>> for SimulatedAnnealing<F, R>
 where
     O: CostFunction<Param = P, Output = F> + Anneal<Param = P, Output = P, Float = F>,
     P: Clone,
     F: ArgminFloat,
-    R: Rng,
+    R: Rng + SeedableRng,
 {
     fn name(&self) -> &str {
         "Simulated Annealing"
     }
+
+    fn reseed(&mut self, seed: u64) {
+        self.rng = R::seed_from_u64(seed);
+    }
     fn init(
         &mut self,
         problem: &mut Problem<O>,
-        mut state: IterState<P, (), (), (), (), F>,
-    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        mut state: DerivativeFreeState<P, F>,
+    ) -> Result<(DerivativeFreeState<P, F>, Option<KV>), Error> {
         let param = state.take_param().ok_or_else(argmin_error_closure!(
             NotInitialized,
             concat!(
@@ -482,8 +518,8 @@ where
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
-        mut state: IterState<P, (), (), (), (), F>,
-    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        mut state: DerivativeFreeState<P, F>,
+    ) -> Result<(DerivativeFreeState<P, F>, Option<KV>), Error> {
         // Careful: The order in here is *very* important, even if it may not seem so. Everything
         // is linked to the iteration number, and getting things mixed up may lead to unexpected
         // behavior.
@@ -554,7 +590,7 @@ where
         ))
     }
 
-    fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+    fn terminate(&mut self, _state: &DerivativeFreeState<P, F>) -> TerminationStatus {
         if self.stall_iter_accepted > self.stall_iter_accepted_limit {
             return TerminationStatus::Terminated(TerminationReason::SolverExit(
                 "AcceptedStallIterExceeded".to_string(),
@@ -624,6 +660,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_with_seed() {
+        let mut sa1 = SimulatedAnnealing::new(100.0f64).unwrap().with_seed(1729);
+        let mut sa2 = SimulatedAnnealing::new(100.0f64).unwrap().with_seed(1729);
+        let draws1: Vec<f64> = (0..5).map(|_| sa1.rng.gen()).collect();
+        let draws2: Vec<f64> = (0..5).map(|_| sa2.rng.gen()).collect();
+        assert_eq!(
+            draws1.iter().map(|x| x.to_ne_bytes()).collect::<Vec<_>>(),
+            draws2.iter().map(|x| x.to_ne_bytes()).collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn test_new_with_rng() {
         #[derive(Eq, PartialEq, Debug)]
@@ -844,7 +892,7 @@ mod tests {
             .with_reannealing_best(reanneal_best);
 
         // Forgot to initialize the parameter vector
-        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        let state: DerivativeFreeState<Vec<f64>, f64> = DerivativeFreeState::new();
         let problem = TestProblem::new();
         let res = sa.init(&mut Problem::new(problem), state);
         assert_error!(
@@ -857,7 +905,8 @@ mod tests {
         );
 
         // All good.
-        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new().param(param.clone());
+        let state: DerivativeFreeState<Vec<f64>, f64> =
+            DerivativeFreeState::new().param(param.clone());
         let problem = TestProblem::new();
         let (mut state_out, kv) = sa.init(&mut Problem::new(problem), state).unwrap();
 
@@ -880,4 +929,77 @@ mod tests {
 
         assert_eq!(state_out.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes())
     }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_resume_from_checkpoint() {
+        use crate::core::test_utils::MemoryCheckpoint;
+        use crate::core::{CostFunction, Executor, TerminationReason, TerminationStatus};
+
+        /// Deterministic annealing, such that the trajectory only depends on the solver's RNG
+        struct Problem {}
+
+        impl CostFunction for Problem {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        impl Anneal for Problem {
+            type Param = Vec<f64>;
+            type Output = Vec<f64>;
+            type Float = f64;
+
+            fn anneal(&self, p: &Vec<f64>, temp: f64) -> Result<Vec<f64>, Error> {
+                Ok(p.iter()
+                    .map(|x| x + 0.1 * temp.sqrt() * (12.9898 * x).sin())
+                    .collect())
+            }
+        }
+
+        let sa = || {
+            SimulatedAnnealing::new(10.0)
+                .unwrap()
+                .with_seed(42)
+                .with_reannealing_fixed(7)
+        };
+        let run = |checkpoint: Option<MemoryCheckpoint>, stop_at: u64| {
+            let executor = Executor::new(Problem {}, sa())
+                .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(30))
+                .add_termination_criterion(move |state: &DerivativeFreeState<Vec<f64>, f64>| {
+                    if state.get_iter() >= stop_at {
+                        TerminationStatus::Terminated(TerminationReason::Interrupt)
+                    } else {
+                        TerminationStatus::NotTerminated
+                    }
+                });
+            match checkpoint {
+                Some(checkpoint) => executor.checkpointing(checkpoint),
+                None => executor,
+            }
+            .run()
+            .unwrap()
+        };
+
+        let uninterrupted = run(None, u64::MAX);
+
+        let checkpoint = MemoryCheckpoint::default();
+        // Interrupted after 12 iterations and resumed from the checkpoint
+        run(Some(checkpoint.clone()), 12);
+        let resumed = run(Some(checkpoint), u64::MAX);
+
+        assert_eq!(resumed.state.get_iter(), 30);
+        assert_eq!(
+            resumed.state.get_best_cost().to_ne_bytes(),
+            uninterrupted.state.get_best_cost().to_ne_bytes()
+        );
+        assert_eq!(resumed.state.get_param(), uninterrupted.state.get_param());
+        assert_eq!(
+            resumed.solver.cur_temp.to_ne_bytes(),
+            uninterrupted.solver.cur_temp.to_ne_bytes()
+        );
+    }
 }
@@ -6,11 +6,11 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, Error, Gradient, Hessian, IterState, Problem, Solver, State, TerminationReason,
-    TerminationStatus, TrustRegionRadius, KV,
+    ArgminError, ArgminFloat, Error, Gradient, Hessian, IterState, Problem, Solver, State,
+    TerminationReason, TerminationStatus, TrustRegionRadius, KV,
 };
 use argmin_math::{
-    ArgminAdd, ArgminDot, ArgminInv, ArgminL2Norm, ArgminMul, ArgminSub, ArgminWeightedDot,
+    ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminSolve, ArgminSub, ArgminWeightedDot,
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -62,7 +62,7 @@ where
         + ArgminDot<P, F>
         + ArgminAdd<P, P>
         + ArgminSub<P, P>,
-    H: ArgminInv<H> + ArgminDot<P, P>,
+    H: ArgminSolve<P, P> + ArgminDot<P, P>,
     F: ArgminFloat,
 {
     fn name(&self) -> &str {
@@ -95,13 +95,26 @@ where
         let pstar;
 
         // pb = -H^-1g
-        let pb = (h.inv()?).dot(&g).mul(&float!(-1.0));
+        let pb = h
+            .solve(&g)
+            .map_err(|e| ArgminError::SingularMatrix {
+                text: format!("`Dogleg`: Hessian could not be solved: {e}"),
+            })?
+            .mul(&float!(-1.0));
 
         if pb.l2_norm() <= self.radius {
             pstar = pb;
         } else {
             // pu = - (g^Tg)/(g^THg) * g
             let pu = g.mul(&(-g.dot(&g) / g.weighted_dot(&h, &g)));
+            let pu_norm = pu.l2_norm();
+
+            // If the Cauchy point lies outside of the trust region, the dogleg path leaves the
+            // trust region along its first segment.
+            if pu_norm >= self.radius {
+                let pstar = pu.mul(&(self.radius / pu_norm));
+                return Ok((state.param(pstar).gradient(g).hessian(h), None));
+            }
 
             let k = pb.sub(&pu); // p^b - p^u
             let c = pu.dot(&k); // p^u^T * (p^b - p^u)
@@ -0,0 +1,870 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Problem, Solver, State, TerminationReason, TerminationStatus,
+    TrustRegionRadius, KV,
+};
+use argmin_math::{
+    ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminRandom, ArgminSub, ArgminZeroLike,
+};
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of iterations of the implicit QL method per eigenvalue
+const MAX_QL_ITERS: usize = 60;
+
+/// Maximum number of iterations for the multiplier of the tridiagonal subproblem
+const MAX_MULTIPLIER_ITERS: usize = 200;
+
+/// # Generalized Lanczos trust region (GLTR) method
+///
+/// Solves the trust region subproblem
+///
+/// `min_p g^T p + 1/2 p^T H p` subject to `||p|| <= radius`
+///
+/// nearly exactly using only products of the Hessian `H` with vectors (Gould et al., 1999).
+///
+/// The Lanczos process builds an orthonormal basis `Q_k` of the Krylov subspace spanned by `g`,
+/// `H g`, ..., `H^{k-1} g` together with the tridiagonal matrix `T_k = Q_k^T H Q_k`. In each
+/// iteration, the subproblem restricted to this subspace, which only involves `T_k`, is solved
+/// exactly via the eigendecomposition of `T_k` and the secular equation for the multiplier
+/// `lambda >= 0` of the trust region constraint. The algorithm stops once
+/// `||(H + lambda I) p + g||` is below `epsilon * (||g|| + lambda * radius)`.
+///
+/// As long as no negative curvature is encountered and the solution lies within the trust region,
+/// the iterates coincide with those of the [`Steihaug`](`crate::solver::trustregion::Steihaug`)
+/// method. Unlike Steihaug's method, GLTR continues to minimize the model on the boundary of the
+/// trust region once it is reached or negative curvature is detected. Furthermore, it handles the
+/// hard case, in which `g` is orthogonal to the eigenvectors of the smallest eigenvalue of an
+/// indefinite `H`, including `g = 0` (for instance at saddle points): if the Krylov subspace
+/// becomes invariant under `H`, the Lanczos process is restarted once with a (deterministically
+/// seeded) random vector orthogonal to the current subspace, which exposes the remaining
+/// curvature of `H`.
+///
+/// All Lanczos vectors are stored and reorthogonalized against, hence memory and computational
+/// costs grow with the number of iterations, which can be limited via
+/// [`with_max_iters`](`GLTR::with_max_iters`).
+///
+/// ## Reference
+///
+/// Nicholas I. M. Gould, Stefano Lucidi, Massimo Roma and Philippe L. Toint (1999). Solving the
+/// trust-region subproblem using the Lanczos method. SIAM Journal on Optimization 9(2), 504–525.
+///
+/// Jorge J. Moré and Danny C. Sorensen (1983). Computing a trust region step. SIAM Journal on
+/// Scientific and Statistical Computing 4(3), 553–572.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GLTR<P, F> {
+    /// Radius
+    radius: F,
+    /// Relative tolerance
+    epsilon: F,
+    /// max iters
+    max_iters: u64,
+    /// Norm of the gradient
+    g_norm: F,
+    /// Lanczos vectors
+    q: Vec<P>,
+    /// Diagonal of the tridiagonal matrix
+    alpha: Vec<F>,
+    /// Off-diagonal of the tridiagonal matrix (zero where the Lanczos process was restarted)
+    beta: Vec<F>,
+    /// Whether the Lanczos process was restarted
+    restarted: bool,
+}
+
+impl<P, F> GLTR<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`GLTR`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::GLTR;
+    /// let gltr: GLTR<Vec<f64>, f64> = GLTR::new();
+    /// ```
+    pub fn new() -> Self {
+        GLTR {
+            radius: F::nan(),
+            epsilon: F::epsilon().sqrt(),
+            max_iters: u64::MAX,
+            g_norm: F::nan(),
+            q: vec![],
+            alpha: vec![],
+            beta: vec![],
+            restarted: false,
+        }
+    }
+
+    /// Set epsilon
+    ///
+    /// The algorithm stops when `||(H + lambda I) p + g|| <= epsilon * (||g|| + lambda * radius)`.
+    ///
+    /// Must be larger than 0 and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::GLTR;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gltr: GLTR<Vec<f64>, f64> = GLTR::new().with_epsilon(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_epsilon(mut self, epsilon: F) -> Result<Self, Error> {
+        if epsilon <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GLTR`: epsilon must be > 0.0."
+            ));
+        }
+        self.epsilon = epsilon;
+        Ok(self)
+    }
+
+    /// Set maximum number of iterations
+    ///
+    /// The algorithm stops after `iter` iterations.
+    ///
+    /// Defaults to `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::GLTR;
+    /// let gltr: GLTR<Vec<f64>, f64> = GLTR::new().with_max_iters(100);
+    /// ```
+    #[must_use]
+    pub fn with_max_iters(mut self, iters: u64) -> Self {
+        self.max_iters = iters;
+        self
+    }
+}
+
+impl<P, F> Default for GLTR<P, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        GLTR::new()
+    }
+}
+
+impl<P, F> GLTR<P, F>
+where
+    P: Clone
+        + ArgminMul<F, P>
+        + ArgminSub<P, P>
+        + ArgminAdd<F, P>
+        + ArgminDot<P, F>
+        + ArgminL2Norm<F>
+        + ArgminZeroLike
+        + ArgminRandom,
+    F: ArgminFloat,
+{
+    /// Orthogonalizes `v` against all Lanczos vectors (twice, for numerical stability)
+    fn orthogonalize(&self, mut v: P) -> P {
+        for _ in 0..2 {
+            for qi in self.q.iter() {
+                let c: F = qi.dot(&v);
+                v = v.sub(&qi.mul(&c));
+            }
+        }
+        v
+    }
+
+    /// Normalized random vector orthogonal to all Lanczos vectors, or `None` if they already span
+    /// the whole space
+    fn restart_vector(&self, template: &P) -> Option<P> {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(self.q.len() as u64);
+        let zero = template.zero_like();
+        let v = P::rand_from_range(&zero.add(&float!(-1.0)), &zero.add(&float!(1.0)), &mut rng);
+        let v_norm = v.l2_norm();
+        if v_norm <= float!(0.0) {
+            return None;
+        }
+        let v = self.orthogonalize(v.mul(&(float!(1.0) / v_norm)));
+        let v_norm = v.l2_norm();
+        if v_norm <= F::epsilon().sqrt() {
+            return None;
+        }
+        Some(v.mul(&(float!(1.0) / v_norm)))
+    }
+}
+
+impl<P, O, F, H> Solver<O, IterState<P, P, (), H, (), F>> for GLTR<P, F>
+where
+    P: Clone
+        + ArgminMul<F, P>
+        + ArgminSub<P, P>
+        + ArgminAdd<P, P>
+        + ArgminAdd<F, P>
+        + ArgminDot<P, F>
+        + ArgminL2Norm<F>
+        + ArgminZeroLike
+        + ArgminRandom,
+    H: ArgminDot<P, P>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "GLTR"
+    }
+
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, P, (), H, (), F>,
+    ) -> Result<(IterState<P, P, (), H, (), F>, Option<KV>), Error> {
+        let grad = state.get_gradient().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`GLTR` requires an initial gradient. ",
+                "Please provide an initial gradient via `Executor`s `configure` method."
+            )
+        ))?;
+
+        if state.get_hessian().is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                concat!(
+                    "`GLTR` requires an initial Hessian. ",
+                    "Please provide an initial Hessian via `Executor`s `configure` method."
+                )
+            ));
+        }
+
+        self.q.clear();
+        self.alpha.clear();
+        self.beta.clear();
+        self.restarted = false;
+        self.g_norm = grad.l2_norm();
+
+        let q0 = if self.g_norm > float!(0.0) {
+            grad.mul(&(float!(1.0) / self.g_norm))
+        } else {
+            // Without a gradient, the Krylov subspace is started from a random vector.
+            self.restarted = true;
+            self.restart_vector(grad).ok_or_else(argmin_error_closure!(
+                InvalidParameter,
+                "`GLTR`: parameter vector must not be empty."
+            ))?
+        };
+        let p = grad.zero_like();
+        self.q.push(q0);
+
+        Ok((state.param(p), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        _problem: &mut Problem<O>,
+        mut state: IterState<P, P, (), H, (), F>,
+    ) -> Result<(IterState<P, P, (), H, (), F>, Option<KV>), Error> {
+        let grad = state.take_gradient().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`GLTR`: Gradient in state not set."
+        ))?;
+
+        let h = state.take_hessian().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`GLTR`: Hessian in state not set."
+        ))?;
+
+        // Lanczos step
+        let k = self.alpha.len();
+        let qk = &self.q[k];
+        let hq = h.dot(qk);
+        let alpha: F = qk.dot(&hq);
+        let mut w = hq.sub(&qk.mul(&alpha));
+        if k > 0 {
+            w = w.sub(&self.q[k - 1].mul(&self.beta[k - 1]));
+        }
+        let w = self.orthogonalize(w);
+        let beta_next = w.l2_norm();
+        self.alpha.push(alpha);
+
+        // Subproblem restricted to the Krylov subspace
+        let (coeffs, lambda) =
+            tridiagonal_subproblem(&self.alpha, &self.beta, self.g_norm, self.radius);
+        let p = self
+            .q
+            .iter()
+            .zip(coeffs.iter())
+            .fold(grad.zero_like(), |acc, (qi, ci)| acc.add(&qi.mul(ci)));
+        let model = self.g_norm * coeffs[0]
+            + float!(0.5)
+                * (0..=k).fold(float!(0.0), |acc, i| {
+                    let mut th = self.alpha[i] * coeffs[i];
+                    if i > 0 {
+                        th = th + self.beta[i - 1] * coeffs[i - 1];
+                    }
+                    if i < k {
+                        th = th + self.beta[i] * coeffs[i + 1];
+                    }
+                    acc + coeffs[i] * th
+                });
+
+        // ||(H + lambda I) p + g||
+        let residual = beta_next * coeffs[k].abs();
+        let t_norm = self
+            .alpha
+            .iter()
+            .chain(self.beta.iter())
+            .chain(std::iter::once(&beta_next))
+            .fold(float!(0.0), |acc: F, x| acc.max(x.abs()));
+        let kv = kv!("lambda" => lambda; "krylov_dim" => self.alpha.len() as u64;);
+
+        if beta_next <= float!(100.0) * F::epsilon() * t_norm {
+            // The Krylov subspace is invariant under `H`; restart once to detect the hard case.
+            let restart = if self.restarted {
+                None
+            } else {
+                self.restart_vector(&grad)
+            };
+            let state = state.param(p).cost(model).gradient(grad).hessian(h);
+            return Ok(match restart {
+                Some(v) => {
+                    self.restarted = true;
+                    self.q.push(v);
+                    self.beta.push(float!(0.0));
+                    (state, Some(kv))
+                }
+                None => (
+                    state.terminate_with(TerminationReason::SolverConverged),
+                    Some(kv),
+                ),
+            });
+        }
+
+        // For a vanishing gradient, the residual is zero for any iterate; negative curvature can
+        // only be detected by running the Lanczos process until the subspace is invariant.
+        if self.g_norm > float!(0.0)
+            && residual <= self.epsilon * (self.g_norm + lambda * self.radius)
+        {
+            return Ok((
+                state
+                    .param(p)
+                    .cost(model)
+                    .terminate_with(TerminationReason::SolverConverged),
+                Some(kv),
+            ));
+        }
+
+        self.q.push(w.mul(&(float!(1.0) / beta_next)));
+        self.beta.push(beta_next);
+
+        Ok((
+            state.param(p).cost(model).gradient(grad).hessian(h),
+            Some(kv),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, P, (), H, (), F>) -> TerminationStatus {
+        if state.get_iter() >= self.max_iters {
+            return TerminationStatus::Terminated(TerminationReason::MaxItersReached);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+impl<P, F: ArgminFloat> TrustRegionRadius<F> for GLTR<P, F> {
+    /// Set current radius.
+    ///
+    /// Needed by [`TrustRegion`](`crate::solver::trustregion::TrustRegion`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::solver::trustregion::{GLTR, TrustRegionRadius};
+    /// let mut gltr: GLTR<Vec<f64>, f64> = GLTR::new();
+    /// gltr.set_radius(0.8);
+    /// ```
+    fn set_radius(&mut self, radius: F) {
+        self.radius = radius;
+    }
+}
+
+/// Eigenvalues and eigenvectors (columns of the returned matrix) of the symmetric tridiagonal
+/// matrix with diagonal `diag` and off-diagonal `off` via the implicit QL method
+fn tridiagonal_eigen<F: ArgminFloat>(diag: &[F], off: &[F]) -> (Vec<F>, Vec<Vec<F>>) {
+    let n = diag.len();
+    let mut d = diag.to_vec();
+    let mut e: Vec<F> = off.iter().cloned().chain([float!(0.0)]).take(n).collect();
+    let mut z: Vec<Vec<F>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if i == j { float!(1.0) } else { float!(0.0) })
+                .collect()
+        })
+        .collect();
+    for l in 0..n {
+        for _ in 0..MAX_QL_ITERS {
+            // Find a negligible off-diagonal element to split the matrix
+            let mut m = l;
+            while m + 1 < n {
+                if e[m].abs() <= F::epsilon() * (d[m].abs() + d[m + 1].abs()) {
+                    break;
+                }
+                m += 1;
+            }
+            if m == l {
+                break;
+            }
+            let mut g = (d[l + 1] - d[l]) / (float!(2.0) * e[l]);
+            let mut r = g.hypot(float!(1.0));
+            g = d[m] - d[l] + e[l] / (g + r.copysign(g));
+            let (mut s, mut c, mut p) = (float!(1.0), float!(1.0), float!(0.0));
+            let mut underflow = false;
+            for i in (l..m).rev() {
+                let f = s * e[i];
+                let b = c * e[i];
+                r = f.hypot(g);
+                e[i + 1] = r;
+                if r == float!(0.0) {
+                    d[i + 1] = d[i + 1] - p;
+                    e[m] = float!(0.0);
+                    underflow = true;
+                    break;
+                }
+                s = f / r;
+                c = g / r;
+                g = d[i + 1] - p;
+                r = (d[i] - g) * s + float!(2.0) * c * b;
+                p = s * r;
+                d[i + 1] = g + p;
+                g = c * r - b;
+                for row in z.iter_mut() {
+                    let f = row[i + 1];
+                    row[i + 1] = s * row[i] + c * f;
+                    row[i] = c * row[i] - s * f;
+                }
+            }
+            if underflow {
+                continue;
+            }
+            d[l] = d[l] - p;
+            e[l] = g;
+            e[m] = float!(0.0);
+        }
+    }
+    (d, z)
+}
+
+/// Solves `min_h g_norm * h_0 + 1/2 h^T T h` subject to `||h|| <= radius` for the symmetric
+/// tridiagonal matrix `T` with diagonal `diag` and off-diagonal `off`.
+///
+/// Returns the solution `h` and the multiplier `lambda` of the constraint.
+fn tridiagonal_subproblem<F: ArgminFloat>(
+    diag: &[F],
+    off: &[F],
+    g_norm: F,
+    radius: F,
+) -> (Vec<F>, F) {
+    let n = diag.len();
+    let (eig, z) = tridiagonal_eigen(diag, off);
+    // Right hand side in the eigenbasis
+    let c: Vec<F> = (0..n).map(|i| g_norm * z[0][i]).collect();
+    let (i_min, lambda_min) =
+        eig.iter()
+            .cloned()
+            .enumerate()
+            .fold(
+                (0, F::infinity()),
+                |acc, (i, x)| if x < acc.1 { (i, x) } else { acc },
+            );
+
+    // Coefficients of the solution in the eigenbasis for a given multiplier, skipping
+    // components without contribution
+    let coeffs = |lambda: F, skip: &dyn Fn(usize) -> bool| -> Vec<F> {
+        (0..n)
+            .map(|i| {
+                if c[i] == float!(0.0) || skip(i) {
+                    float!(0.0)
+                } else {
+                    -c[i] / (eig[i] + lambda)
+                }
+            })
+            .collect()
+    };
+    let norm = |y: &[F]| {
+        y.iter()
+            .fold(float!(0.0), |acc, yi| acc + yi.powi(2))
+            .sqrt()
+    };
+    let to_tridiagonal_basis = |y: &[F]| -> Vec<F> {
+        (0..n)
+            .map(|j| (0..n).fold(float!(0.0), |acc, i| acc + z[j][i] * y[i]))
+            .collect()
+    };
+
+    // Interior solution
+    if lambda_min >= float!(0.0) {
+        let y = coeffs(float!(0.0), &|_| false);
+        if norm(&y) <= radius {
+            return (to_tridiagonal_basis(&y), float!(0.0));
+        }
+    }
+
+    let lambda_low = float!(0.0).max(-lambda_min);
+
+    // Hard case: the right hand side has (numerically) no component along the eigenvectors of the
+    // smallest eigenvalue and the remaining components do not reach the boundary.
+    if lambda_min <= float!(0.0) {
+        let eig_scale = eig.iter().fold(float!(0.0), |acc: F, x| acc.max(x.abs()));
+        let degenerate = |i: usize| eig[i] - lambda_min <= F::epsilon().sqrt() * eig_scale;
+        let c_min = (0..n)
+            .filter(|&i| degenerate(i))
+            .fold(float!(0.0), |acc, i| acc + c[i].powi(2))
+            .sqrt();
+        if c_min <= F::epsilon().sqrt() * g_norm {
+            let mut y = coeffs(lambda_low, &degenerate);
+            let y_norm = norm(&y);
+            if y_norm <= radius {
+                y[i_min] = (radius.powi(2) - y_norm.powi(2)).sqrt();
+                return (to_tridiagonal_basis(&y), lambda_low);
+            }
+        }
+    }
+
+    // Safeguarded Newton iteration on `1/||h(lambda)|| - 1/radius`
+    let mut lo = lambda_low;
+    let mut hi = lambda_low + g_norm / radius;
+    let mut lambda = hi;
+    for _ in 0..MAX_MULTIPLIER_ITERS {
+        let y = coeffs(lambda, &|_| false);
+        let y_norm = norm(&y);
+        if (y_norm - radius).abs() <= float!(10.0) * F::epsilon() * radius {
+            break;
+        }
+        if y_norm > radius {
+            lo = lambda;
+        } else {
+            hi = lambda;
+        }
+        let dy = (0..n).fold(float!(0.0), |acc, i| {
+            if y[i] == float!(0.0) {
+                acc
+            } else {
+                acc + y[i].powi(2) / (eig[i] + lambda)
+            }
+        });
+        let phi = float!(1.0) / y_norm - float!(1.0) / radius;
+        let dphi = dy / y_norm.powi(3);
+        let mut next = lambda - phi / dphi;
+        if !(next > lo && next < hi) {
+            next = float!(0.5) * (lo + hi);
+        }
+        if hi - lo <= F::epsilon() * hi {
+            break;
+        }
+        lambda = next;
+    }
+    (to_tridiagonal_basis(&coeffs(lambda, &|_| false)), lambda)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_utils::TestProblem;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::trustregion::Steihaug;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(gltr, GLTR<Vec<f64>, f64>);
+
+    /// Runs `solver` on the subproblem with gradient `g` and Hessian `h`
+    fn solve<S>(solver: S, g: Vec<f64>, h: Vec<Vec<f64>>) -> (Vec<f64>, f64)
+    where
+        S: Solver<TestProblem, IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64>>,
+    {
+        let n = g.len();
+        let res = Executor::new(TestProblem::new(), solver)
+            .configure(|state| {
+                state
+                    .param(vec![0.0; n])
+                    .gradient(g.clone())
+                    .hessian(h.clone())
+            })
+            .run()
+            .unwrap();
+        let p = res.state.get_param().unwrap().clone();
+        let hp: Vec<f64> = h
+            .iter()
+            .map(|row| row.iter().zip(p.iter()).map(|(a, b)| a * b).sum())
+            .collect();
+        let model = g
+            .iter()
+            .zip(p.iter())
+            .zip(hp.iter())
+            .map(|((gi, pi), hpi)| gi * pi + 0.5 * pi * hpi)
+            .sum();
+        (p, model)
+    }
+
+    fn diag(d: &[f64]) -> Vec<Vec<f64>> {
+        (0..d.len())
+            .map(|i| {
+                (0..d.len())
+                    .map(|j| if i == j { d[i] } else { 0.0 })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Multiplier of the diagonal subproblem with solution on the boundary via bisection
+    fn boundary_lambda(d: &[f64], g: &[f64], radius: f64) -> f64 {
+        let norm = |lambda: f64| {
+            d.iter()
+                .zip(g.iter())
+                .map(|(di, gi)| (gi / (di + lambda)).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        };
+        let mut lo = d.iter().cloned().fold(0.0f64, |acc, di| acc.max(-di));
+        let mut hi = lo + 1e3;
+        for _ in 0..200 {
+            let mid = 0.5 * (lo + hi);
+            if norm(mid) > radius {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        0.5 * (lo + hi)
+    }
+
+    #[test]
+    fn test_new() {
+        let gltr: GLTR<Vec<f64>, f64> = GLTR::new();
+
+        let GLTR {
+            radius,
+            epsilon,
+            max_iters,
+            g_norm,
+            q,
+            alpha,
+            beta,
+            restarted,
+        } = gltr;
+
+        assert_eq!(radius.to_ne_bytes(), f64::NAN.to_ne_bytes());
+        assert_eq!(epsilon.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(max_iters, u64::MAX);
+        assert_eq!(g_norm.to_ne_bytes(), f64::NAN.to_ne_bytes());
+        assert!(q.is_empty());
+        assert!(alpha.is_empty());
+        assert!(beta.is_empty());
+        assert!(!restarted);
+    }
+
+    #[test]
+    fn test_with_epsilon() {
+        for tolerance in [f64::EPSILON, 1e-10, 1e-6, 1.0] {
+            let gltr: GLTR<Vec<f64>, f64> = GLTR::new().with_epsilon(tolerance).unwrap();
+            assert_eq!(gltr.epsilon.to_ne_bytes(), tolerance.to_ne_bytes());
+        }
+
+        for tolerance in [-f64::EPSILON, 0.0, -1.0] {
+            let res: Result<GLTR<Vec<f64>, f64>, _> = GLTR::new().with_epsilon(tolerance);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`GLTR`: epsilon must be > 0.0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_iters() {
+        for iters in [1, 2, 3, 5, 8, 13] {
+            let gltr: GLTR<Vec<f64>, f64> = GLTR::new().with_max_iters(iters);
+            assert_eq!(gltr.max_iters, iters);
+        }
+    }
+
+    #[test]
+    fn test_init() {
+        let mut gltr: GLTR<Vec<f64>, f64> = GLTR::new();
+        gltr.set_radius(1.0);
+
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> = IterState::new();
+        let res = gltr.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`GLTR` requires an initial gradient. Please ",
+                "provide an initial gradient via `Executor`s `configure` method.\""
+            )
+        );
+
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> =
+            IterState::new().gradient(vec![3.0, 4.0]);
+        let res = gltr.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`GLTR` requires an initial Hessian. Please ",
+                "provide an initial Hessian via `Executor`s `configure` method.\""
+            )
+        );
+
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> = IterState::new()
+            .gradient(vec![3.0, 4.0])
+            .hessian(diag(&[1.0, 2.0]));
+        let (mut state, kv) = gltr
+            .init(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+        assert!(kv.is_none());
+        assert_eq!(state.take_param().unwrap(), vec![0.0, 0.0]);
+        assert_eq!(gltr.g_norm.to_ne_bytes(), 5.0f64.to_ne_bytes());
+        assert_relative_eq!(gltr.q[0][0], 0.6, epsilon = f64::EPSILON);
+        assert_relative_eq!(gltr.q[0][1], 0.8, epsilon = f64::EPSILON);
+        assert!(!gltr.restarted);
+    }
+
+    #[test]
+    fn test_tridiagonal_eigen() {
+        let (eig, z) = tridiagonal_eigen(&[2.0, 2.0, 2.0], &[-1.0, -1.0]);
+        let mut sorted = eig.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let sqrt2 = 2.0f64.sqrt();
+        assert_relative_eq!(sorted[0], 2.0 - sqrt2, epsilon = 1e-12);
+        assert_relative_eq!(sorted[1], 2.0, epsilon = 1e-12);
+        assert_relative_eq!(sorted[2], 2.0 + sqrt2, epsilon = 1e-12);
+        let t = [[2.0, -1.0, 0.0], [-1.0, 2.0, -1.0], [0.0, -1.0, 2.0]];
+        for (k, lk) in eig.iter().enumerate() {
+            for i in 0..3 {
+                let tz: f64 = (0..3).map(|j| t[i][j] * z[j][k]).sum();
+                assert_relative_eq!(tz, lk * z[i][k], epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interior() {
+        let mut gltr: GLTR<Vec<f64>, f64> = GLTR::new();
+        gltr.set_radius(10.0);
+        let h = vec![
+            vec![4.0, 1.0, 0.0],
+            vec![1.0, 3.0, 1.0],
+            vec![0.0, 1.0, 2.0],
+        ];
+        let (p, _) = solve(gltr, vec![1.0, -2.0, 0.5], h.clone());
+        // H p = -g
+        for (row, gi) in h.iter().zip([1.0, -2.0, 0.5]) {
+            let hp: f64 = row.iter().zip(p.iter()).map(|(a, b)| a * b).sum();
+            assert_relative_eq!(hp, -gi, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_boundary() {
+        // Positive definite and indefinite Hessians with solutions on the boundary
+        for d in [[1.0, 2.0, 4.0], [-1.0, 2.0, 3.0]] {
+            let g = [1.0, 1.0, 1.0];
+            let radius = 0.5;
+            let mut gltr: GLTR<Vec<f64>, f64> = GLTR::new();
+            gltr.set_radius(radius);
+            let (p, _) = solve(gltr, g.to_vec(), diag(&d));
+            let lambda = boundary_lambda(&d, &g, radius);
+            for i in 0..3 {
+                assert_relative_eq!(p[i], -g[i] / (d[i] + lambda), epsilon = 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hard_case() {
+        // The gradient is orthogonal to the eigenvector of the negative eigenvalue
+        let d = [-2.0, 1.0, 3.0];
+        let g = vec![0.0, 1.0, 1.0];
+        let radius = 2.0;
+        let mut gltr: GLTR<Vec<f64>, f64> = GLTR::new();
+        gltr.set_radius(radius);
+        let (p, model) = solve(gltr, g.clone(), diag(&d));
+
+        // lambda = 2 and the step is completed along the first coordinate axis
+        let tau = (radius.powi(2) - 1.0 / 9.0 - 1.0 / 25.0).sqrt();
+        assert_relative_eq!(p[0].abs(), tau, epsilon = 1e-8);
+        assert_relative_eq!(p[1], -1.0 / 3.0, epsilon = 1e-8);
+        assert_relative_eq!(p[2], -1.0 / 5.0, epsilon = 1e-8);
+
+        // Steihaug's method stays within the Krylov subspace spanned by the gradient
+        let mut sh: Steihaug<Vec<f64>, f64> = Steihaug::new();
+        sh.set_radius(radius);
+        let (p_sh, model_sh) = solve(sh, g, diag(&d));
+        assert_relative_eq!(p_sh[0], 0.0, epsilon = 1e-12);
+        assert!(model < model_sh - 1.0);
+    }
+
+    #[test]
+    fn test_zero_gradient() {
+        // Saddle point: the step follows the direction of negative curvature
+        let mut gltr: GLTR<Vec<f64>, f64> = GLTR::new();
+        gltr.set_radius(1.0);
+        let (p, model) = solve(gltr, vec![0.0, 0.0], diag(&[1.0, -1.0]));
+        assert_relative_eq!(p[0], 0.0, epsilon = 1e-8);
+        assert_relative_eq!(p[1].abs(), 1.0, epsilon = 1e-8);
+        assert_relative_eq!(model, -0.5, epsilon = 1e-8);
+
+        // Minimum: no step
+        let mut gltr: GLTR<Vec<f64>, f64> = GLTR::new();
+        gltr.set_radius(1.0);
+        let (p, _) = solve(gltr, vec![0.0, 0.0], diag(&[1.0, 2.0]));
+        assert_eq!(p, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_trust_region() {
+        use crate::core::{CostFunction, Gradient, Hessian};
+        use crate::solver::trustregion::TrustRegion;
+
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(argmin_testfunctions::rosenbrock_derivative(p))
+            }
+        }
+
+        impl Hessian for Rosenbrock {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(argmin_testfunctions::rosenbrock_hessian(p))
+            }
+        }
+
+        let res = Executor::new(Rosenbrock {}, TrustRegion::new(GLTR::new()))
+            .configure(|state| state.param(vec![-1.2, 1.0, -1.2, 1.0]).max_iters(200))
+            .run()
+            .unwrap();
+        assert!(res.state.get_best_cost() < 1e-10);
+    }
+}
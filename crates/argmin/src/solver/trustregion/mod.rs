@@ -22,6 +22,8 @@
 mod cauchypoint;
 /// Dogleg method
 mod dogleg;
+/// GLTR method
+mod gltr;
 /// Steihaug method
 mod steihaug;
 /// Trust region solver
@@ -29,9 +31,12 @@ mod trustregion_method;
 
 pub use self::cauchypoint::*;
 pub use self::dogleg::*;
+pub use self::gltr::*;
 pub use self::steihaug::*;
 pub use self::trustregion_method::*;
 
+use argmin_math::{ArgminDiv, ArgminLInfNorm, ArgminMul};
+
 /// An interface methods which calculate approximate steps for trust region methods must implement.
 ///
 /// # Example
@@ -85,11 +90,45 @@ pub fn reduction_ratio<F: crate::core::ArgminFloat>(fxk: F, fxkpk: F, mk0: F, mk
     (fxk - fxkpk) / (mk0 - mkpk)
 }
 
+/// Shortens `step` such that no component exceeds the per-component `move_limits`.
+///
+/// The step is scaled instead of cutting off single components, which keeps its direction and
+/// therefore the decrease of the model.
+pub(crate) fn limit_step<P, F>(step: P, move_limits: Option<&P>) -> P
+where
+    P: ArgminDiv<P, P> + ArgminLInfNorm<F> + ArgminMul<F, P>,
+    F: crate::core::ArgminFloat,
+{
+    match move_limits {
+        Some(move_limits) => {
+            let ratio = step.div(move_limits).linf_norm();
+            if ratio > float!(1.0) {
+                step.mul(&(float!(1.0) / ratio))
+            } else {
+                step
+            }
+        }
+        None => step,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use approx::assert_relative_eq;
 
+    #[test]
+    fn test_limit_step() {
+        let step = vec![0.5f64, -2.0, 3.0];
+        // The second component is the limiting one
+        let limited = limit_step::<_, f64>(step.clone(), Some(&vec![1.0, 1.0, 2.0]));
+        assert_eq!(limited, vec![0.25, -1.0, 1.5]);
+        // Within the limits
+        let limited = limit_step::<_, f64>(step.clone(), Some(&vec![1.0, 2.0, 3.0]));
+        assert_eq!(limited, step);
+        assert_eq!(limit_step::<_, f64>(step.clone(), None), step);
+    }
+
     #[test]
     fn test_reduction_ration() {
         let fxk = 10.0f64;
@@ -61,7 +61,7 @@ where
     pub fn new() -> Self {
         Steihaug {
             radius: F::nan(),
-            epsilon: float!(10e-10),
+            epsilon: float!(10e-10).max(F::epsilon()),
             p: None,
             r: None,
             rtr: F::nan(),
@@ -75,7 +75,7 @@ where
     ///
     /// The algorithm stops when the residual is smaller than `epsilon`.
     ///
-    /// Must be larger than 0 and defaults to 10^-10.
+    /// Must be larger than 0 and defaults to `10e-10` (or `EPSILON` if it is larger, as for `f32`).
     ///
     /// # Example
     ///
@@ -336,6 +336,13 @@ mod tests {
 
     test_trait_impl!(steihaug, Steihaug<TestProblem, f64>);
 
+    #[test]
+    fn test_new_f32() {
+        let sh: Steihaug<Vec<f32>, f32> = Steihaug::new();
+        // The default epsilon is below the precision of f32
+        assert_eq!(sh.epsilon.to_ne_bytes(), f32::EPSILON.to_ne_bytes());
+    }
+
     #[test]
     fn test_new() {
         let sh: Steihaug<Vec<f64>, f64> = Steihaug::new();
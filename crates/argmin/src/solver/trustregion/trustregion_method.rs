@@ -7,10 +7,12 @@
 
 use crate::core::{
     ArgminFloat, CostFunction, Error, Executor, Gradient, Hessian, IterState, OptimizationResult,
-    Problem, Solver, TerminationStatus, TrustRegionRadius, KV,
+    Problem, Solver, State, TerminationStatus, TrustRegionRadius, KV,
+};
+use crate::solver::trustregion::{limit_step, reduction_ratio};
+use argmin_math::{
+    ArgminAdd, ArgminDiv, ArgminDot, ArgminL2Norm, ArgminLInfNorm, ArgminMul, ArgminWeightedDot,
 };
-use crate::solver::trustregion::reduction_ratio;
-use argmin_math::{ArgminAdd, ArgminDot, ArgminL2Norm, ArgminWeightedDot};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -26,12 +28,20 @@ use serde::{Deserialize, Serialize};
 /// * [Cauchy point](`crate::solver::trustregion::CauchyPoint`)
 /// * [Dogleg method](`crate::solver::trustregion::Dogleg`)
 /// * [Steihaug method](`crate::solver::trustregion::Steihaug`)
+/// * [GLTR method](`crate::solver::trustregion::GLTR`)
 ///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`], [`Gradient`] and
 /// [`Hessian`].
 ///
+/// The subproblem solvers only require products of the Hessian with vectors;
+/// [`Dogleg`](`crate::solver::trustregion::Dogleg`) additionally solves `H p = g` via
+/// `ArgminSolve`. This allows for sparse Hessians (for instance `CsMat` of the `sprs` backend of
+/// `argmin-math`, which is solved via a sparse `LDL^T` decomposition), which is essential for
+/// large structured problems. The sparsity pattern of the Hessian can be declared via
+/// [`Hessian::hessian_sparsity`].
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
@@ -161,7 +171,15 @@ where
     O: CostFunction<Param = P, Output = F>
         + Gradient<Param = P, Gradient = G>
         + Hessian<Param = P, Hessian = H>,
-    P: Clone + ArgminL2Norm<F> + ArgminDot<P, F> + ArgminDot<G, F> + ArgminAdd<P, P>,
+    P: Clone
+        + ArgminL2Norm<F>
+        + ArgminDot<P, F>
+        + ArgminDot<G, F>
+        + ArgminAdd<P, P>
+        + ArgminMul<F, P>
+        + ArgminDiv<P, P>
+        + ArgminLInfNorm<F>
+        + 'static,
     G: Clone,
     H: Clone + ArgminDot<P, P>,
     R: Clone + TrustRegionRadius<F> + Solver<O, IterState<P, G, (), H, (), F>>,
@@ -232,6 +250,10 @@ where
             "`TrustRegion`: Hessian in state not set."
         ))?;
 
+        // The radius must not exceed the maximum step length
+        if let Some(max_step_length) = problem.max_step_length(&param) {
+            self.radius = self.radius.min(max_step_length);
+        }
         self.subproblem.set_radius(self.radius);
 
         let OptimizationResult {
@@ -248,7 +270,8 @@ where
             .ctrlc(false)
             .run()?;
 
-        let pk = sub_state.take_param().unwrap();
+        // No component of the step may exceed its move limit
+        let pk = limit_step(sub_state.take_param().unwrap(), problem.move_limits::<P>());
 
         // Consume intermediate problem again. This takes care of the function evaluation counts.
         problem.consume_problem(sub_problem);
@@ -297,6 +320,38 @@ where
     fn terminate(&mut self, _state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
         TerminationStatus::NotTerminated
     }
+
+    /// Shrinks the radius to a fourth and restores the cost function value of `state`.
+    fn reject_step(
+        &mut self,
+        state: IterState<P, G, (), H, (), F>,
+    ) -> Result<IterState<P, G, (), H, (), F>, Error> {
+        self.radius = float!(0.25) * self.radius;
+        self.fxk = state.get_cost();
+        self.mk0 = self.fxk;
+        Ok(state)
+    }
+
+    /// Takes over the radius of `previous` (limited by the maximum radius) and the parameter
+    /// vector of `previous_state`. The Hessian is recomputed.
+    fn warm_start(
+        &mut self,
+        previous: Self,
+        mut previous_state: IterState<P, G, (), H, (), F>,
+        state: IterState<P, G, (), H, (), F>,
+    ) -> Result<IterState<P, G, (), H, (), F>, Error> {
+        self.radius = previous.radius.min(self.max_radius);
+        previous_state.hessian = None;
+        Ok(state.warm_start(previous_state))
+    }
+
+    /// Exposes the current `radius` and the maximum radius `max_radius`.
+    fn internals(&self) -> KV {
+        kv!(
+            "radius" => self.radius;
+            "max_radius" => self.max_radius;
+        )
+    }
 }
 
 #[cfg(test)]
@@ -429,4 +484,288 @@ mod tests {
         assert_eq!(fxk.to_ne_bytes(), 1.0f64.sqrt().to_ne_bytes());
         assert_eq!(mk0.to_ne_bytes(), 1.0f64.to_ne_bytes());
     }
+
+    #[test]
+    fn test_reject_step() {
+        let cp: CauchyPoint<f64> = CauchyPoint::new();
+        let mut tr: TrustRegion<_, f64> = TrustRegion::new(cp).with_radius(2.0).unwrap();
+
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> =
+            IterState::new().param(vec![1.0, 2.0]).cost(3.0);
+        let state =
+            <TrustRegion<_, f64> as Solver<TestProblem, _>>::reject_step(&mut tr, state).unwrap();
+
+        assert_eq!(state.get_param(), Some(&vec![1.0, 2.0]));
+        assert_eq!(tr.radius.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(tr.fxk.to_ne_bytes(), 3.0f64.to_ne_bytes());
+        assert_eq!(tr.mk0.to_ne_bytes(), 3.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_warm_start() {
+        let mut previous: TrustRegion<_, f64> = TrustRegion::new(CauchyPoint::new());
+        previous.radius = 150.0;
+        let mut tr: TrustRegion<_, f64> = TrustRegion::new(CauchyPoint::new());
+
+        let previous_state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> =
+            IterState::new()
+                .param(vec![1.0, 2.0])
+                .gradient(vec![1.0, 2.0])
+                .hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+                .cost(3.0);
+        let state = <TrustRegion<_, f64> as Solver<TestProblem, _>>::warm_start(
+            &mut tr,
+            previous,
+            previous_state,
+            IterState::new(),
+        )
+        .unwrap();
+
+        // Limited by the maximum radius
+        assert_eq!(tr.radius.to_ne_bytes(), 100.0f64.to_ne_bytes());
+        assert_eq!(state.get_param(), Some(&vec![1.0, 2.0]));
+        assert!(state.get_gradient().is_none());
+        assert!(state.get_hessian().is_none());
+        assert!(state.get_cost().is_infinite());
+    }
+
+    #[test]
+    fn test_max_step_length() {
+        use crate::core::{CostFunction, Executor, Gradient, Hessian, MaxStepLength};
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(argmin_testfunctions::rosenbrock_derivative(p))
+            }
+        }
+
+        impl Hessian for Rosenbrock {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(argmin_testfunctions::rosenbrock_hessian(p))
+            }
+        }
+
+        let longest_step = Rc::new(Cell::new(0.0f64));
+        let longest = Rc::clone(&longest_step);
+        let mut prev_param = Some(vec![-1.2, 1.0]);
+        let res = Executor::new(
+            Rosenbrock {},
+            TrustRegion::new(Steihaug::new()).with_radius(10.0).unwrap(),
+        )
+        .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(500))
+        .max_step_length(MaxStepLength::new().with_absolute(0.05).unwrap())
+        .add_iteration_hook(
+            move |state: &mut IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64>,
+                  _problem: &mut Problem<Rosenbrock>| {
+                let param = state.get_param().unwrap().clone();
+                if let Some(prev_param) = prev_param.replace(param.clone()) {
+                    let step = param
+                        .iter()
+                        .zip(prev_param)
+                        .map(|(a, b)| (a - b).powi(2))
+                        .sum::<f64>()
+                        .sqrt();
+                    longest.set(longest.get().max(step));
+                }
+                Ok(TerminationStatus::NotTerminated)
+            },
+        )
+        .run()
+        .unwrap();
+
+        assert!(longest_step.get() > 0.0);
+        assert!(longest_step.get() <= 0.05 + 1e-12);
+        assert!(res.state.get_best_cost() < 1e-10);
+    }
+
+    #[test]
+    fn test_move_limits() {
+        use crate::core::{CostFunction, Executor, Gradient, Hessian};
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(argmin_testfunctions::rosenbrock_derivative(p))
+            }
+        }
+
+        impl Hessian for Rosenbrock {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(argmin_testfunctions::rosenbrock_hessian(p))
+            }
+        }
+
+        // Largest change of each component in a single iteration
+        let largest_changes = Rc::new(RefCell::new(vec![0.0f64; 2]));
+        let changes = Rc::clone(&largest_changes);
+        let mut prev_param = vec![-1.2, 1.0];
+        let res = Executor::new(
+            Rosenbrock {},
+            TrustRegion::new(Steihaug::new()).with_radius(10.0).unwrap(),
+        )
+        .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(500))
+        .move_limits(vec![0.02, 0.1])
+        .add_iteration_hook(
+            move |state: &mut IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64>,
+                  _problem: &mut Problem<Rosenbrock>| {
+                let param = state.get_param().unwrap().clone();
+                for (change, (a, b)) in changes
+                    .borrow_mut()
+                    .iter_mut()
+                    .zip(param.iter().zip(prev_param.iter()))
+                {
+                    *change = change.max((a - b).abs());
+                }
+                prev_param = param;
+                Ok(TerminationStatus::NotTerminated)
+            },
+        )
+        .run()
+        .unwrap();
+
+        let largest_changes = largest_changes.borrow();
+        assert!(largest_changes[0] > 0.0);
+        assert!(largest_changes[0] <= 0.02 + 1e-12);
+        assert!(largest_changes[1] <= 0.1 + 1e-12);
+        assert!(res.state.get_best_cost() < 1e-10);
+    }
+
+    #[test]
+    fn test_internals() {
+        let tr: TrustRegion<_, f64> = TrustRegion::new(CauchyPoint::new())
+            .with_radius(0.5)
+            .unwrap();
+        let kv = <TrustRegion<_, f64> as Solver<
+            TestProblem,
+            IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64>,
+        >>::internals(&tr);
+        assert_eq!(kv.get("radius").unwrap().get_float(), Some(0.5));
+        assert_eq!(kv.get("max_radius").unwrap().get_float(), Some(100.0));
+    }
+
+    #[cfg(feature = "_ndarrayl")]
+    #[test]
+    fn test_sparse_hessian() {
+        use crate::core::{CostFunction, Executor, Gradient, Hessian};
+        use crate::solver::trustregion::Dogleg;
+        use argmin_math::ArgminL2Norm;
+        use ndarray::Array1;
+        use sprs::{CsMat, TriMat};
+
+        // f(x) = 1/2 x^T A x - x^T b + 1/4 sum_i x_i^4 with tridiagonal A
+        struct Problem {
+            a: CsMat<f64>,
+            b: Array1<f64>,
+        }
+
+        impl CostFunction for Problem {
+            type Param = Array1<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                let ap = &self.a * p;
+                Ok((p * &(0.5 * &ap - &self.b + 0.25 * p.mapv(|x| x.powi(3)))).sum())
+            }
+        }
+
+        impl Gradient for Problem {
+            type Param = Array1<f64>;
+            type Gradient = Array1<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(&self.a * p - &self.b + p.mapv(|x| x.powi(3)))
+            }
+        }
+
+        impl Hessian for Problem {
+            type Param = Array1<f64>;
+            type Hessian = CsMat<f64>;
+
+            fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+                let diag = CsMat::new_csc(
+                    (p.len(), p.len()),
+                    (0..=p.len()).collect(),
+                    (0..p.len()).collect(),
+                    p.mapv(|x| 3.0 * x.powi(2)).to_vec(),
+                );
+                Ok(&self.a + &diag)
+            }
+        }
+
+        let n = 50;
+        let mut a = TriMat::new((n, n));
+        for i in 0..n {
+            a.add_triplet(i, i, 4.0);
+            if i > 0 {
+                a.add_triplet(i, i - 1, -1.0);
+                a.add_triplet(i - 1, i, -1.0);
+            }
+        }
+        let a: CsMat<f64> = a.to_csc();
+        let b = Array1::from_shape_fn(n, |i| (i % 5) as f64);
+        let problem = || Problem {
+            a: a.clone(),
+            b: b.clone(),
+        };
+
+        let check = |param: &Array1<f64>| {
+            let grad: f64 = problem().gradient(param).unwrap().l2_norm();
+            assert!(grad < 1e-6, "gradient norm {grad}");
+        };
+
+        let res = Executor::new(problem(), TrustRegion::new(Dogleg::new()))
+            .configure(|config| config.param(Array1::zeros(n)).max_iters(50))
+            .run()
+            .unwrap();
+        check(res.state.get_best_param().unwrap());
+
+        let res = Executor::new(problem(), TrustRegion::new(Steihaug::new()))
+            .configure(|config| config.param(Array1::zeros(n)).max_iters(50))
+            .run()
+            .unwrap();
+        check(res.state.get_best_param().unwrap());
+
+        let res = Executor::new(problem(), TrustRegion::new(CauchyPoint::new()))
+            .configure(|config| config.param(Array1::zeros(n)).max_iters(1000))
+            .run()
+            .unwrap();
+        check(res.state.get_best_param().unwrap());
+    }
 }
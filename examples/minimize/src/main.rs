@@ -0,0 +1,47 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::Error,
+    minimize::{minimize, Algorithm, Objective, Options},
+};
+use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+
+fn run() -> Result<(), Error> {
+    // Bounds are required by particle swarm optimization and respected by all algorithms
+    let bounds = (vec![-5.0, -5.0], vec![5.0, 5.0]);
+
+    for algorithm in Algorithm::ALL {
+        let objective = Objective::new(|x: &[f64]| rosenbrock(x))
+            .with_gradient(|x: &[f64]| rosenbrock_derivative(x));
+        let options = Options {
+            max_iters: 2000,
+            tol_grad: Some(1e-10),
+            bounds: Some(bounds.clone()),
+            seed: Some(42),
+            ..Options::new(algorithm)
+        };
+        let res = minimize(objective, &[-1.2, 1.0], &options)?;
+        println!(
+            "{:<20} param: {:?}, cost: {:.3e}, iters: {}, cost evals: {}, gradient evals: {}",
+            algorithm.name(),
+            res.param,
+            res.cost,
+            res.iters,
+            res.cost_count,
+            res.gradient_count,
+        );
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
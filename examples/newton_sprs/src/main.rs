@@ -0,0 +1,90 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor, Gradient, Hessian},
+    solver::newton::Newton,
+};
+use argmin_observer_slog::SlogLogger;
+use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+use ndarray::Array1;
+use sprs::{CsMat, TriMat};
+
+/// Multidimensional Rosenbrock function, whose Hessian is tridiagonal
+struct Rosenbrock {}
+
+impl CostFunction for Rosenbrock {
+    type Param = Array1<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rosenbrock(p.as_slice().unwrap()))
+    }
+}
+
+impl Gradient for Rosenbrock {
+    type Param = Array1<f64>;
+    type Gradient = Array1<f64>;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(Array1::from(rosenbrock_derivative(p.as_slice().unwrap())))
+    }
+}
+
+impl Hessian for Rosenbrock {
+    type Param = Array1<f64>;
+    type Hessian = CsMat<f64>;
+
+    fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+        // Only the 3n - 2 nonzero entries are computed and stored
+        let n = p.len();
+        let mut h = TriMat::with_capacity((n, n), 3 * n - 2);
+        for i in 0..n {
+            let mut diag = 0.0;
+            if i + 1 < n {
+                diag += 2.0 + 1200.0 * p[i].powi(2) - 400.0 * p[i + 1];
+                let off_diag = -400.0 * p[i];
+                h.add_triplet(i, i + 1, off_diag);
+                h.add_triplet(i + 1, i, off_diag);
+            }
+            if i > 0 {
+                diag += 200.0;
+            }
+            h.add_triplet(i, i, diag);
+        }
+        Ok(h.to_csc())
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define cost function
+    let cost = Rosenbrock {};
+
+    // Define initial parameter vector
+    let init_param: Array1<f64> = Array1::from_elem(10_000, 1.2);
+
+    // Set up solver. The linear system with the sparse Hessian is solved via a sparse LDL^T
+    // decomposition in each iteration.
+    let solver: Newton<f64> = Newton::new();
+
+    // Run solver
+    let res = Executor::new(cost, solver)
+        .configure(|state| state.param(init_param).max_iters(10))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
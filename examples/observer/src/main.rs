@@ -6,6 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use argmin::core::observers::{Observe, ObserverMode};
+use argmin::core::time::Duration;
 use argmin::core::{ArgminFloat, CostFunction, Error, Executor, PopulationState, State, KV};
 use argmin::solver::particleswarm::{Particle, ParticleSwarm};
 use argmin_testfunctions::himmelblau;
@@ -35,7 +36,7 @@ pub struct Visualizer3d {
     /// Optional visualized surface of cost function
     surface: Option<Surface>,
     /// Optional delay between iterations
-    delay: Option<instant::Duration>,
+    delay: Option<Duration>,
 }
 
 impl Visualizer3d {
@@ -56,7 +57,7 @@ impl Visualizer3d {
 
     /// Set delay
     #[must_use]
-    pub fn delay(mut self, duration: instant::Duration) -> Self {
+    pub fn delay(mut self, duration: Duration) -> Self {
         self.delay = Some(duration);
         self
     }
@@ -229,7 +230,7 @@ fn run() -> Result<(), Error> {
     let cost_function = Himmelblau {};
 
     let visualizer = Visualizer3d::new()
-        .delay(std::time::Duration::from_secs(1))
+        .delay(Duration::from_secs(1))
         .surface(Surface::new(Himmelblau {}, (-4.0, -4.0, 4.0, 4.0), 0.1));
 
     {
@@ -12,7 +12,7 @@ use argmin::core::{CostFunction, Error, Executor, Gradient};
 use argmin::solver::gradientdescent::SteepestDescent;
 use argmin::solver::linesearch::condition::{ArmijoCondition, LineSearchCondition};
 use argmin::solver::linesearch::BacktrackingLineSearch;
-use argmin_math::ArgminScaledAdd;
+use argmin_math::ArgminScaledAddAssign;
 use argmin_observer_slog::SlogLogger;
 
 use serde::{Deserialize, Serialize};
@@ -51,11 +51,9 @@ impl Gradient for ClosestPointOnCircle {
     }
 }
 
-impl ArgminScaledAdd<f64, f64, CirclePoint> for CirclePoint {
-    fn scaled_add(&self, alpha: &f64, delta: &f64) -> Self {
-        CirclePoint {
-            angle: self.angle + alpha * delta,
-        }
+impl ArgminScaledAddAssign<f64, f64> for CirclePoint {
+    fn scaled_add_assign(&mut self, alpha: &f64, delta: &f64) {
+        self.angle += alpha * delta;
     }
 }
 
@@ -5,8 +5,8 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-// This example shows how to add a timeout to an optimization run. The optimization will be
-// terminated once the 3 seconds timeout is reached.
+// This example shows how to add a time limit to an optimization run. The optimization will be
+// terminated once the time limit of 3 seconds is reached.
 
 use argmin::{
     core::{observers::ObserverMode, CostFunction, Error, Executor},
@@ -75,10 +75,10 @@ fn run() -> Result<(), Error> {
         .add_observer(SlogLogger::term(), ObserverMode::Always)
         /////////////////////////////////////////////////////////////////////////////////////////
         //                                                                                     //
-        //             Add a timeout of 3 seconds                                              //
+        //             Add a time limit of 3 seconds                                           //
         //                                                                                     //
         /////////////////////////////////////////////////////////////////////////////////////////
-        .timeout(std::time::Duration::from_secs(3))
+        .max_time(std::time::Duration::from_secs(3))
         .run()?;
 
     // Print result
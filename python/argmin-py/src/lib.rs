@@ -0,0 +1,398 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Python bindings for a selection of the solvers of argmin.
+//!
+//! Parameter vectors are exchanged with Python as one-dimensional `numpy` arrays of `float64`.
+//! Internally, the solvers operate on `ndarray` arrays, therefore all linear algebra of the
+//! solvers happens in Rust and only the cost function, gradient and observers are evaluated in
+//! Python. If no gradient is provided, it is approximated via central finite differences.
+//!
+//! Exceptions raised in Python callbacks abort the optimization and are re-raised to the caller.
+
+use argmin::core::observers::{Observe, ObserverMode};
+use argmin::core::{
+    ArgminError, CostFunction, Error, Executor, Gradient, IterState, KvValue, PopulationState,
+    Solver, State, KV,
+};
+use argmin::solver::gradientdescent::SteepestDescent;
+use argmin::solver::linesearch::MoreThuenteLineSearch;
+use argmin::solver::neldermead::NelderMead;
+use argmin::solver::particleswarm::{Particle, ParticleSwarm};
+use argmin::solver::quasinewton::{BFGS, LBFGS};
+use finitediff::ndarr::central_diff_ndarray;
+use ndarray::{Array1, Array2};
+use numpy::{PyArray1, PyReadonlyArray1, ToPyArray};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+
+type Param = Array1<f64>;
+
+/// Converts a one-dimensional `numpy` array or any sequence of floats into a parameter vector.
+fn extract_param(obj: &Bound<'_, PyAny>) -> PyResult<Param> {
+    if let Ok(array) = obj.extract::<PyReadonlyArray1<f64>>() {
+        Ok(array.as_array().to_owned())
+    } else {
+        Ok(Array1::from(obj.extract::<Vec<f64>>()?))
+    }
+}
+
+/// Converts an error returned by argmin into a Python exception.
+///
+/// Exceptions raised in Python callbacks are passed through unchanged.
+fn to_py_err(err: Error) -> PyErr {
+    match err.downcast::<PyErr>() {
+        Ok(err) => err,
+        Err(err) => match err.downcast_ref::<ArgminError>() {
+            Some(ArgminError::InvalidParameter { .. }) => PyValueError::new_err(err.to_string()),
+            _ => PyRuntimeError::new_err(err.to_string()),
+        },
+    }
+}
+
+/// Optimization problem defined by Python callables
+struct PyProblem {
+    cost: PyObject,
+    gradient: Option<PyObject>,
+}
+
+impl CostFunction for PyProblem {
+    type Param = Param;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Python::with_gil(|py| {
+            let cost = self.cost.call1(py, (param.to_pyarray_bound(py),))?;
+            Ok(cost.extract(py)?)
+        })
+    }
+}
+
+impl Gradient for PyProblem {
+    type Param = Param;
+    type Gradient = Param;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let Some(gradient) = self.gradient.as_ref() else {
+            return central_diff_ndarray(param, &|p| self.cost(p));
+        };
+        let gradient = Python::with_gil(|py| {
+            let gradient = gradient.call1(py, (param.to_pyarray_bound(py),))?;
+            extract_param(gradient.bind(py))
+        })?;
+        if gradient.len() != param.len() {
+            return Err(PyValueError::new_err(format!(
+                "gradient has {} elements, expected {}",
+                gradient.len(),
+                param.len()
+            ))
+            .into());
+        }
+        Ok(gradient)
+    }
+}
+
+/// Gives access to the parameter vectors of the states of the exposed solvers.
+trait PyState: State<Float = f64> {
+    fn param_array(&self) -> Option<&Param>;
+
+    fn best_param_array(&self) -> Option<&Param>;
+}
+
+impl<G, J, H, R> PyState for IterState<Param, G, J, H, R, f64>
+where
+    Self: State<Param = Param, Float = f64>,
+{
+    fn param_array(&self) -> Option<&Param> {
+        self.get_param()
+    }
+
+    fn best_param_array(&self) -> Option<&Param> {
+        self.get_best_param()
+    }
+}
+
+impl PyState for PopulationState<Particle<Param, f64>, f64> {
+    fn param_array(&self) -> Option<&Param> {
+        self.get_param().map(|particle| &particle.position)
+    }
+
+    fn best_param_array(&self) -> Option<&Param> {
+        self.get_best_param().map(|particle| &particle.position)
+    }
+}
+
+/// Observer which calls a Python callable with a `dict` describing the current iteration.
+struct PyObserver {
+    callback: PyObject,
+}
+
+impl<I: PyState> Observe<I> for PyObserver {
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        Python::with_gil(|py| {
+            let iteration = PyDict::new_bound(py);
+            for (key, value) in kv.kv.iter() {
+                match value {
+                    KvValue::Float(v) => iteration.set_item(key, v)?,
+                    KvValue::Int(v) => iteration.set_item(key, v)?,
+                    KvValue::Uint(v) => iteration.set_item(key, v)?,
+                    KvValue::Bool(v) => iteration.set_item(key, v)?,
+                    KvValue::Str(v) => iteration.set_item(key, v)?,
+                }
+            }
+            iteration.set_item("iter", state.get_iter())?;
+            iteration.set_item("cost", state.get_cost())?;
+            iteration.set_item("best_cost", state.get_best_cost())?;
+            if let Some(param) = state.param_array() {
+                iteration.set_item("param", param.to_pyarray_bound(py))?;
+            }
+            if let Some(best_param) = state.best_param_array() {
+                iteration.set_item("best_param", best_param.to_pyarray_bound(py))?;
+            }
+            self.callback.call1(py, (iteration,))?;
+            Ok(())
+        })
+    }
+}
+
+/// Result of an optimization run
+#[pyclass(name = "OptimizationResult", get_all, frozen)]
+struct PyOptimizationResult {
+    /// Best parameter vector found
+    best_param: Option<Py<PyArray1<f64>>>,
+    /// Cost function value of the best parameter vector
+    best_cost: f64,
+    /// Number of iterations
+    iterations: u64,
+    /// Reason for termination
+    termination_status: String,
+    /// Number of evaluations of the cost function, the gradient, ...
+    function_evaluations: HashMap<String, u64>,
+    /// Duration of the run in seconds
+    time: Option<f64>,
+}
+
+#[pymethods]
+impl PyOptimizationResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "OptimizationResult(best_cost={}, iterations={}, termination_status='{}')",
+            self.best_cost, self.iterations, self.termination_status
+        )
+    }
+}
+
+/// Runs the executor and converts the final state into a result for Python.
+fn run<S, I>(
+    py: Python<'_>,
+    mut executor: Executor<PyProblem, S, I>,
+    observer: Option<PyObject>,
+    observer_every: u64,
+) -> PyResult<PyOptimizationResult>
+where
+    S: Solver<PyProblem, I>,
+    I: PyState + 'static,
+{
+    if let Some(callback) = observer {
+        if observer_every == 0 {
+            return Err(PyValueError::new_err(
+                "`observer_every` must be larger than 0",
+            ));
+        }
+        executor =
+            executor.add_observer(PyObserver { callback }, ObserverMode::Every(observer_every));
+    }
+    let result = executor.run().map_err(to_py_err)?;
+    let state = result.state();
+    Ok(PyOptimizationResult {
+        best_param: state
+            .best_param_array()
+            .map(|param| param.to_pyarray_bound(py).unbind()),
+        best_cost: state.get_best_cost(),
+        iterations: state.get_iter(),
+        termination_status: state.get_termination_status().to_string(),
+        function_evaluations: result
+            .func_counts()
+            .iter()
+            .map(|(k, &v)| (k.to_string(), v))
+            .collect(),
+        time: state.get_time().map(|time| time.as_secs_f64()),
+    })
+}
+
+/// Minimizes `cost` via steepest descent with a More-Thuente line search.
+///
+/// If `gradient` is not given, it is approximated via finite differences.
+#[pyfunction]
+#[pyo3(signature = (cost, x0, gradient = None, max_iters = 100, observer = None, observer_every = 1))]
+fn steepest_descent(
+    py: Python<'_>,
+    cost: PyObject,
+    x0: &Bound<'_, PyAny>,
+    gradient: Option<PyObject>,
+    max_iters: u64,
+    observer: Option<PyObject>,
+    observer_every: u64,
+) -> PyResult<PyOptimizationResult> {
+    let x0 = extract_param(x0)?;
+    let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+    let executor = Executor::new(PyProblem { cost, gradient }, solver).configure(
+        |state: IterState<Param, Param, (), (), (), f64>| state.param(x0).max_iters(max_iters),
+    );
+    run(py, executor, observer, observer_every)
+}
+
+/// Minimizes `cost` via L-BFGS with a More-Thuente line search and `m` stored updates.
+///
+/// If `gradient` is not given, it is approximated via finite differences.
+#[pyfunction]
+#[pyo3(signature = (cost, x0, gradient = None, m = 7, tol_grad = None, tol_cost = None, max_iters = 100, observer = None, observer_every = 1))]
+#[allow(clippy::too_many_arguments)]
+fn lbfgs(
+    py: Python<'_>,
+    cost: PyObject,
+    x0: &Bound<'_, PyAny>,
+    gradient: Option<PyObject>,
+    m: usize,
+    tol_grad: Option<f64>,
+    tol_cost: Option<f64>,
+    max_iters: u64,
+    observer: Option<PyObject>,
+    observer_every: u64,
+) -> PyResult<PyOptimizationResult> {
+    let x0 = extract_param(x0)?;
+    let mut solver = LBFGS::new(MoreThuenteLineSearch::new(), m);
+    if let Some(tol_grad) = tol_grad {
+        solver = solver.with_tolerance_grad(tol_grad).map_err(to_py_err)?;
+    }
+    if let Some(tol_cost) = tol_cost {
+        solver = solver.with_tolerance_cost(tol_cost).map_err(to_py_err)?;
+    }
+    let executor = Executor::new(PyProblem { cost, gradient }, solver).configure(
+        |state: IterState<Param, Param, (), (), (), f64>| state.param(x0).max_iters(max_iters),
+    );
+    run(py, executor, observer, observer_every)
+}
+
+/// Minimizes `cost` via BFGS with a More-Thuente line search.
+///
+/// The inverse Hessian is initialized with the identity matrix. If `gradient` is not given, it is
+/// approximated via finite differences.
+#[pyfunction]
+#[pyo3(signature = (cost, x0, gradient = None, tol_grad = None, tol_cost = None, max_iters = 100, observer = None, observer_every = 1))]
+#[allow(clippy::too_many_arguments)]
+fn bfgs(
+    py: Python<'_>,
+    cost: PyObject,
+    x0: &Bound<'_, PyAny>,
+    gradient: Option<PyObject>,
+    tol_grad: Option<f64>,
+    tol_cost: Option<f64>,
+    max_iters: u64,
+    observer: Option<PyObject>,
+    observer_every: u64,
+) -> PyResult<PyOptimizationResult> {
+    let x0 = extract_param(x0)?;
+    let inv_hessian = Array2::eye(x0.len());
+    let mut solver = BFGS::new(MoreThuenteLineSearch::new());
+    if let Some(tol_grad) = tol_grad {
+        solver = solver.with_tolerance_grad(tol_grad).map_err(to_py_err)?;
+    }
+    if let Some(tol_cost) = tol_cost {
+        solver = solver.with_tolerance_cost(tol_cost).map_err(to_py_err)?;
+    }
+    let executor = Executor::new(PyProblem { cost, gradient }, solver).configure(
+        |state: IterState<Param, Param, (), Array2<f64>, (), f64>| {
+            state
+                .param(x0)
+                .inv_hessian(inv_hessian)
+                .max_iters(max_iters)
+        },
+    );
+    run(py, executor, observer, observer_every)
+}
+
+/// Minimizes `cost` via the Nelder-Mead method starting from the given `simplex`.
+///
+/// The simplex is a sequence of `n + 1` parameter vectors, where `n` is the number of parameters.
+#[pyfunction]
+#[pyo3(signature = (cost, simplex, sd_tolerance = None, max_iters = 100, observer = None, observer_every = 1))]
+fn nelder_mead(
+    py: Python<'_>,
+    cost: PyObject,
+    simplex: Vec<Bound<'_, PyAny>>,
+    sd_tolerance: Option<f64>,
+    max_iters: u64,
+    observer: Option<PyObject>,
+    observer_every: u64,
+) -> PyResult<PyOptimizationResult> {
+    let simplex = simplex
+        .iter()
+        .map(extract_param)
+        .collect::<PyResult<Vec<_>>>()?;
+    let mut solver = NelderMead::new(simplex);
+    if let Some(sd_tolerance) = sd_tolerance {
+        solver = solver.with_sd_tolerance(sd_tolerance).map_err(to_py_err)?;
+    }
+    let problem = PyProblem {
+        cost,
+        gradient: None,
+    };
+    let executor = Executor::new(problem, solver)
+        .configure(|state: IterState<Param, (), (), (), (), f64>| state.max_iters(max_iters));
+    run(py, executor, observer, observer_every)
+}
+
+/// Minimizes `cost` via Particle Swarm Optimization within the bounds `lower` and `upper`.
+///
+/// With `seed`, the random number generator is seeded, which makes the run reproducible.
+#[pyfunction]
+#[pyo3(signature = (cost, lower, upper, num_particles = 40, max_iters = 100, seed = None, observer = None, observer_every = 1))]
+#[allow(clippy::too_many_arguments)]
+fn particle_swarm(
+    py: Python<'_>,
+    cost: PyObject,
+    lower: &Bound<'_, PyAny>,
+    upper: &Bound<'_, PyAny>,
+    num_particles: usize,
+    max_iters: u64,
+    seed: Option<u64>,
+    observer: Option<PyObject>,
+    observer_every: u64,
+) -> PyResult<PyOptimizationResult> {
+    let bounds = (extract_param(lower)?, extract_param(upper)?);
+    if bounds.0.len() != bounds.1.len() {
+        return Err(PyValueError::new_err(
+            "`lower` and `upper` must have the same number of elements",
+        ));
+    }
+    let problem = PyProblem {
+        cost,
+        gradient: None,
+    };
+    let solver = ParticleSwarm::new(bounds, num_particles);
+    let mut executor = Executor::new(problem, solver)
+        .configure(|state: PopulationState<Particle<Param, f64>, f64>| state.max_iters(max_iters));
+    if let Some(seed) = seed {
+        executor = executor.deterministic(seed);
+    }
+    run(py, executor, observer, observer_every)
+}
+
+/// Python bindings for argmin
+#[pymodule]
+fn argmin_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyOptimizationResult>()?;
+    m.add_function(wrap_pyfunction!(steepest_descent, m)?)?;
+    m.add_function(wrap_pyfunction!(lbfgs, m)?)?;
+    m.add_function(wrap_pyfunction!(bfgs, m)?)?;
+    m.add_function(wrap_pyfunction!(nelder_mead, m)?)?;
+    m.add_function(wrap_pyfunction!(particle_swarm, m)?)?;
+    Ok(())
+}